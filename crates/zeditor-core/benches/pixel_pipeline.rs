@@ -0,0 +1,111 @@
+//! Throughput of the per-frame pixel work at 1080p, SIMD kernels against the
+//! scalar reference. Run with `cargo bench -p zeditor-core --bench pixel_pipeline`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use zeditor_core::pipeline::{FrameBuffer, alpha_composite_rgba, blit_clip_to_canvas};
+use zeditor_core::pixel_ops::{self, scalar};
+use zeditor_core::scaler::{Placement, ScaleQuality};
+
+const WIDTH: u32 = 1920;
+const HEIGHT: u32 = 1080;
+const ITERATIONS: u32 = 50;
+
+/// Deterministic frame with a mix of opaque, transparent and partial alpha.
+fn test_frame(width: u32, height: u32, seed: u32) -> FrameBuffer {
+    let mut state = seed;
+    let mut data: Vec<u8> = (0..width as usize * height as usize * 4)
+        .map(|_| {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (state >> 24) as u8
+        })
+        .collect();
+    for (i, pixel) in data.chunks_exact_mut(4).enumerate() {
+        // Mostly opaque with a transparent band, like a scaled overlay clip
+        pixel[3] = match (i / 64) % 8 {
+            0..=5 => 255,
+            6 => 0,
+            _ => pixel[3],
+        };
+    }
+    FrameBuffer::from_rgba_vec(width, height, data)
+}
+
+fn bench(name: &str, mut f: impl FnMut()) {
+    f(); // warm up caches and the rayon pool
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let per_frame = start.elapsed() / ITERATIONS;
+    let fps = 1.0 / per_frame.as_secs_f64();
+    println!(
+        "{name:<32} {:>9.3} ms/frame {fps:>9.1} fps",
+        per_frame.as_secs_f64() * 1000.0
+    );
+}
+
+fn main() {
+    let src = test_frame(WIDTH, HEIGHT, 1);
+    let dst = test_frame(WIDTH, HEIGHT, 2);
+    let row_bytes = WIDTH as usize * 4;
+
+    let mut frame = src.data.clone();
+    bench("grayscale (scalar)", || {
+        frame
+            .chunks_exact_mut(row_bytes)
+            .for_each(scalar::grayscale_row);
+        black_box(&frame);
+    });
+    bench("grayscale (simd)", || {
+        frame
+            .chunks_exact_mut(row_bytes)
+            .for_each(pixel_ops::grayscale_row);
+        black_box(&frame);
+    });
+
+    bench("brightness (scalar)", || {
+        frame
+            .chunks_exact_mut(row_bytes)
+            .for_each(|row| scalar::brightness_row(row, 12));
+        black_box(&frame);
+    });
+    bench("brightness (simd)", || {
+        frame
+            .chunks_exact_mut(row_bytes)
+            .for_each(|row| pixel_ops::brightness_row(row, 12));
+        black_box(&frame);
+    });
+
+    let mut out = dst.data.clone();
+    bench("composite (scalar)", || {
+        out.copy_from_slice(&dst.data);
+        scalar::composite_row(&src.data, &mut out);
+        black_box(&out);
+    });
+    bench("composite (simd)", || {
+        out.copy_from_slice(&dst.data);
+        pixel_ops::composite_row(&src.data, &mut out);
+        black_box(&out);
+    });
+
+    // Full-frame entry points as used by the renderer, rayon included
+    let mut canvas = dst.clone();
+    bench("alpha_composite_rgba", || {
+        canvas.data.copy_from_slice(&dst.data);
+        alpha_composite_rgba(&src, &mut canvas);
+        black_box(&canvas);
+    });
+    let clip = test_frame(1280, 720, 3);
+    let placement = Placement::fit(clip.width, clip.height, WIDTH, HEIGHT);
+    for quality in [
+        ScaleQuality::Nearest,
+        ScaleQuality::Bilinear,
+        ScaleQuality::Lanczos,
+    ] {
+        bench(&format!("blit 720p->1080p ({quality:?})"), || {
+            black_box(blit_clip_to_canvas(clip.clone(), WIDTH, HEIGHT, placement, quality));
+        });
+    }
+}
@@ -0,0 +1,231 @@
+use serde::{Deserialize, Serialize};
+
+/// How a clip's first two audio channels feed its stereo output. Meant for
+/// camera files that record the mic on one channel and silence or scratch
+/// audio on the other.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ChannelMap {
+    /// Channels pass through unchanged.
+    #[default]
+    Stereo,
+    /// The left channel is played on both sides.
+    LeftOnly,
+    /// The right channel is played on both sides.
+    RightOnly,
+    /// Left and right are averaged and played on both sides.
+    MixToMono,
+    /// Left and right trade places.
+    Swap,
+}
+
+impl ChannelMap {
+    pub const ALL: [ChannelMap; 5] = [
+        Self::Stereo,
+        Self::LeftOnly,
+        Self::RightOnly,
+        Self::MixToMono,
+        Self::Swap,
+    ];
+
+    /// Short label for the inspector buttons.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Stereo => "Stereo",
+            Self::LeftOnly => "Left",
+            Self::RightOnly => "Right",
+            Self::MixToMono => "Mono",
+            Self::Swap => "Swap",
+        }
+    }
+
+    /// Remap interleaved `samples` with `channels` channels per frame in
+    /// place. Only the first two channels of each frame are touched; mono
+    /// audio is left alone.
+    pub fn apply(self, samples: &mut [f32], channels: usize) {
+        if self == Self::Stereo || channels < 2 {
+            return;
+        }
+        for frame in samples.chunks_exact_mut(channels) {
+            let (left, right) = (frame[0], frame[1]);
+            let (new_left, new_right) = match self {
+                Self::Stereo => (left, right),
+                Self::LeftOnly => (left, left),
+                Self::RightOnly => (right, right),
+                Self::MixToMono => {
+                    let mono = (left + right) * 0.5;
+                    (mono, mono)
+                }
+                Self::Swap => (right, left),
+            };
+            frame[0] = new_left;
+            frame[1] = new_right;
+        }
+    }
+}
+
+/// Quietest envelope gain; points at or below it mute the audio.
+pub const MIN_GAIN_DB: f32 = -60.0;
+/// Loudest envelope gain.
+pub const MAX_GAIN_DB: f32 = 12.0;
+
+/// A point on a clip's volume envelope.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GainPoint {
+    /// Source time in seconds, so points stay with the audio when the clip
+    /// is trimmed or moved.
+    pub time: f64,
+    pub gain_db: f32,
+}
+
+/// Volume automation of a clip: gain in dB interpolated linearly between
+/// points, held flat before the first and after the last. No points means
+/// unity gain.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VolumeEnvelope {
+    /// Points ordered by time, at most one per time.
+    pub points: Vec<GainPoint>,
+}
+
+impl VolumeEnvelope {
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Gain in dB at source time `time`.
+    pub fn gain_db_at(&self, time: f64) -> f32 {
+        let index = self.points.partition_point(|p| p.time <= time);
+        match (index.checked_sub(1).map(|i| self.points[i]), self.points.get(index)) {
+            (None, None) => 0.0,
+            (Some(before), None) => before.gain_db,
+            (None, Some(after)) => after.gain_db,
+            (Some(before), Some(after)) => {
+                let t = ((time - before.time) / (after.time - before.time)) as f32;
+                before.gain_db + (after.gain_db - before.gain_db) * t
+            }
+        }
+    }
+
+    /// Linear gain factor at source time `time`.
+    pub fn gain_at(&self, time: f64) -> f32 {
+        db_to_gain(self.gain_db_at(time))
+    }
+
+    /// Add a point, replacing any point at the same time. The gain is
+    /// clamped to [`MIN_GAIN_DB`, `MAX_GAIN_DB`]. Returns the point's index.
+    pub fn add_point(&mut self, time: f64, gain_db: f32) -> usize {
+        let point = GainPoint {
+            time,
+            gain_db: gain_db.clamp(MIN_GAIN_DB, MAX_GAIN_DB),
+        };
+        let index = self.points.partition_point(|p| p.time < time);
+        if self.points.get(index).is_some_and(|p| p.time == time) {
+            self.points[index] = point;
+        } else {
+            self.points.insert(index, point);
+        }
+        index
+    }
+
+    /// Move point `index` to a new time and gain. Returns its new index, or
+    /// `None` if there is no such point.
+    pub fn move_point(&mut self, index: usize, time: f64, gain_db: f32) -> Option<usize> {
+        self.remove_point(index)?;
+        Some(self.add_point(time, gain_db))
+    }
+
+    pub fn remove_point(&mut self, index: usize) -> Option<GainPoint> {
+        (index < self.points.len()).then(|| self.points.remove(index))
+    }
+
+    /// Raise or lower the part of the line through source time `time` by
+    /// `delta_db`, as when dragging it: the two points around `time`, or the
+    /// first or last point when `time` is on a flat end. An empty envelope
+    /// gets a single point at `time`, changing the whole clip's gain.
+    pub fn shift_at(&mut self, time: f64, delta_db: f32) {
+        if self.points.is_empty() {
+            self.add_point(time, delta_db);
+            return;
+        }
+        let index = self.points.partition_point(|p| p.time <= time);
+        let range = match index {
+            0 => 0..1,
+            i if i == self.points.len() => i - 1..i,
+            i => i - 1..i + 1,
+        };
+        for point in &mut self.points[range] {
+            point.gain_db = (point.gain_db + delta_db).clamp(MIN_GAIN_DB, MAX_GAIN_DB);
+        }
+    }
+
+    /// Scale interleaved `samples` (`channels` per frame at `sample_rate`)
+    /// whose first frame plays at source time `start_time`, one gain per
+    /// frame.
+    pub fn apply(&self, samples: &mut [f32], channels: usize, sample_rate: u32, start_time: f64) {
+        if self.points.is_empty() || channels == 0 || sample_rate == 0 {
+            return;
+        }
+        for (i, frame) in samples.chunks_exact_mut(channels).enumerate() {
+            let gain = self.gain_at(start_time + i as f64 / sample_rate as f64);
+            for sample in frame {
+                *sample *= gain;
+            }
+        }
+    }
+}
+
+/// Linear factor for a gain in dB; [`MIN_GAIN_DB`] and below are silent.
+pub fn db_to_gain(gain_db: f32) -> f32 {
+    if gain_db <= MIN_GAIN_DB {
+        0.0
+    } else {
+        10f32.powf(gain_db / 20.0)
+    }
+}
+
+/// Peak level below which audio counts as silent (about -50 dBFS).
+pub const SILENCE_LEVEL: f32 = 0.003;
+/// Shortest quiet stretch taken for silence rather than a dip in a sound.
+pub const MIN_SILENCE_SECS: f64 = 0.02;
+
+/// Source time near `target_secs` where cutting mono `samples` (at
+/// `sample_rate`, the first at `start_secs`) won't click: the nearest
+/// point of silence if the samples hold any, otherwise the nearest zero
+/// crossing, otherwise `target_secs` itself.
+pub fn quiet_cut_point(samples: &[f32], sample_rate: u32, start_secs: f64, target_secs: f64) -> f64 {
+    if samples.is_empty() || sample_rate == 0 {
+        return target_secs;
+    }
+    let rate = sample_rate as f64;
+    let target = ((target_secs - start_secs) * rate).round().clamp(0.0, (samples.len() - 1) as f64) as usize;
+    let min_run = ((MIN_SILENCE_SECS * rate).ceil() as usize).max(1);
+
+    let mut nearest_silence: Option<usize> = None;
+    let mut run_start = None;
+    for i in 0..=samples.len() {
+        let quiet = samples.get(i).is_some_and(|s| s.abs() < SILENCE_LEVEL);
+        match (quiet, run_start) {
+            (true, None) => run_start = Some(i),
+            (false, Some(start)) => {
+                if i - start >= min_run {
+                    let candidate = target.clamp(start, i - 1);
+                    if nearest_silence.is_none_or(|best| candidate.abs_diff(target) < best.abs_diff(target)) {
+                        nearest_silence = Some(candidate);
+                    }
+                }
+                run_start = None;
+            }
+            _ => {}
+        }
+    }
+
+    // A crossing between samples i - 1 and i is cut at i
+    let index = nearest_silence.or_else(|| {
+        (1..samples.len())
+            .filter(|&i| (samples[i - 1] < 0.0) != (samples[i] < 0.0))
+            .min_by_key(|&i| i.abs_diff(target))
+    });
+    match index {
+        Some(index) => start_secs + index as f64 / rate,
+        None => target_secs,
+    }
+}
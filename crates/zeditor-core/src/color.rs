@@ -0,0 +1,282 @@
+//! Colour management. Assets are tagged with the transfer function and
+//! primaries their pixels are encoded in, and are converted into the working
+//! space (Rec.709 SDR) before effects run, so grading and export see the same
+//! values. A display transform (a `.cube` LUT) may then be applied to preview
+//! frames only; exports never go through it.
+
+use std::path::Path;
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{CoreError, Result};
+
+/// Luminance of HDR reference (diffuse) white, per ITU-R BT.2408.
+const HDR_REFERENCE_WHITE_NITS: f32 = 203.0;
+/// Exponent of the SDR display curve (BT.1886).
+const SDR_GAMMA: f32 = 2.4;
+/// HLG signal level of reference white.
+const HLG_REFERENCE_WHITE: f32 = 0.75;
+/// HLG system gamma for a 1000-nit display (BT.2100 OOTF).
+const HLG_SYSTEM_GAMMA: f32 = 1.2;
+/// HDR levels above this (relative to reference white) are rolled off
+/// smoothly towards 1.0 instead of clipping.
+const TONE_MAP_KNEE: f32 = 0.8;
+/// Largest `.cube` grid accepted.
+const MAX_LUT_SIZE: usize = 256;
+
+/// Tone curve an asset's pixel values are encoded with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TransferFunction {
+    /// Rec.709, sRGB and other SDR gamma curves, which display as-is.
+    #[default]
+    Sdr,
+    Linear,
+    /// SMPTE ST 2084 (HDR10).
+    Pq,
+    /// ARIB STD-B67 hybrid log-gamma.
+    Hlg,
+}
+
+/// RGB primaries (gamut) of an asset.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ColorPrimaries {
+    #[default]
+    Bt709,
+    Bt2020,
+    /// DCI-P3 with a D65 white point.
+    DisplayP3,
+}
+
+/// Colour encoding of an asset, as probed from its stream metadata.
+/// Untagged media is assumed to be Rec.709 SDR.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ColorSpace {
+    pub transfer: TransferFunction,
+    pub primaries: ColorPrimaries,
+}
+
+impl ColorSpace {
+    /// Rec.709 SDR: the space effects work in and exports are encoded as.
+    pub const WORKING: ColorSpace = ColorSpace {
+        transfer: TransferFunction::Sdr,
+        primaries: ColorPrimaries::Bt709,
+    };
+
+    pub fn is_working(self) -> bool {
+        self == Self::WORKING
+    }
+
+    pub fn is_hdr(self) -> bool {
+        matches!(self.transfer, TransferFunction::Pq | TransferFunction::Hlg)
+    }
+}
+
+/// Convert 8-bit RGBA pixels encoded in `space` to the working space, in
+/// place. HDR is tone mapped so reference white lands on SDR white; wide
+/// gamut colours outside Rec.709 are clipped. Alpha is left alone.
+pub fn to_working_space(rgba: &mut [u8], space: ColorSpace) {
+    if space.is_working() {
+        return;
+    }
+    let to_linear: [f32; 256] = std::array::from_fn(|v| decode(space.transfer, v as f32 / 255.0));
+    let matrix = to_bt709_matrix(space.primaries);
+    let hdr = space.is_hdr();
+    rgba.par_chunks_exact_mut(4).for_each(|pixel| {
+        let linear = [
+            to_linear[pixel[0] as usize],
+            to_linear[pixel[1] as usize],
+            to_linear[pixel[2] as usize],
+        ];
+        for (channel, row) in matrix.iter().enumerate() {
+            let mut value = row[0] * linear[0] + row[1] * linear[1] + row[2] * linear[2];
+            if hdr {
+                value = tone_map(value);
+            }
+            pixel[channel] = (value.clamp(0.0, 1.0).powf(1.0 / SDR_GAMMA) * 255.0 + 0.5) as u8;
+        }
+    });
+}
+
+/// Display light of a normalized code value, relative to SDR (or HDR
+/// reference) white.
+fn decode(transfer: TransferFunction, value: f32) -> f32 {
+    match transfer {
+        TransferFunction::Sdr => value.powf(SDR_GAMMA),
+        TransferFunction::Linear => value,
+        TransferFunction::Pq => pq_to_nits(value) / HDR_REFERENCE_WHITE_NITS,
+        TransferFunction::Hlg => {
+            // Per-channel approximation of the OOTF, which strictly applies
+            // the system gamma to luminance
+            (hlg_to_scene(value) / hlg_to_scene(HLG_REFERENCE_WHITE)).powf(HLG_SYSTEM_GAMMA)
+        }
+    }
+}
+
+/// ST 2084 EOTF: absolute luminance in nits.
+fn pq_to_nits(value: f32) -> f32 {
+    const M1: f32 = 2610.0 / 16384.0;
+    const M2: f32 = 2523.0 / 4096.0 * 128.0;
+    const C1: f32 = 3424.0 / 4096.0;
+    const C2: f32 = 2413.0 / 4096.0 * 32.0;
+    const C3: f32 = 2392.0 / 4096.0 * 32.0;
+    let p = value.powf(1.0 / M2);
+    10000.0 * ((p - C1).max(0.0) / (C2 - C3 * p)).powf(1.0 / M1)
+}
+
+/// Inverse HLG OETF: normalized scene light.
+fn hlg_to_scene(value: f32) -> f32 {
+    const A: f32 = 0.178_832_77;
+    const B: f32 = 0.284_668_92;
+    const C: f32 = 0.559_910_7;
+    if value <= 0.5 {
+        value * value / 3.0
+    } else {
+        (((value - C) / A).exp() + B) / 12.0
+    }
+}
+
+/// Identity below the knee, then an exponential shoulder that approaches 1.0
+/// with a continuous slope.
+fn tone_map(value: f32) -> f32 {
+    if value <= TONE_MAP_KNEE {
+        return value;
+    }
+    let headroom = 1.0 - TONE_MAP_KNEE;
+    TONE_MAP_KNEE + headroom * (1.0 - (-(value - TONE_MAP_KNEE) / headroom).exp())
+}
+
+/// Linear-light RGB conversion from `primaries` to Rec.709 (both D65).
+fn to_bt709_matrix(primaries: ColorPrimaries) -> [[f32; 3]; 3] {
+    match primaries {
+        ColorPrimaries::Bt709 => [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        ColorPrimaries::Bt2020 => [
+            [1.6605, -0.5876, -0.0728],
+            [-0.1246, 1.1329, -0.0083],
+            [-0.0182, -0.1006, 1.1187],
+        ],
+        ColorPrimaries::DisplayP3 => [
+            [1.2249, -0.2247, 0.0],
+            [-0.0420, 1.0419, 0.0],
+            [-0.0197, -0.0786, 1.0979],
+        ],
+    }
+}
+
+/// A 3D colour lookup table read from an Adobe/Resolve `.cube` file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CubeLut {
+    size: usize,
+    domain_min: [f32; 3],
+    domain_max: [f32; 3],
+    /// `size³` output colours, red varying fastest.
+    table: Vec<[f32; 3]>,
+}
+
+impl CubeLut {
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Parse `.cube` text. Only 3D tables are supported.
+    pub fn parse(text: &str) -> Result<Self> {
+        let invalid = CoreError::InvalidLut;
+        let floats = |rest: &str| -> Option<[f32; 3]> {
+            let values: Vec<f32> = rest
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<std::result::Result<_, _>>()
+                .ok()?;
+            values.try_into().ok()
+        };
+        let mut size = None;
+        let mut domain_min = [0.0; 3];
+        let mut domain_max = [1.0; 3];
+        let mut table = Vec::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with("TITLE") {
+                continue;
+            }
+            let (keyword, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            match keyword {
+                "LUT_3D_SIZE" => {
+                    let n = rest
+                        .trim()
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|n| (2..=MAX_LUT_SIZE).contains(n))
+                        .ok_or_else(|| invalid(format!("bad LUT_3D_SIZE '{}'", rest.trim())))?;
+                    size = Some(n);
+                }
+                "LUT_1D_SIZE" => return Err(invalid("1D LUTs are not supported".into())),
+                "DOMAIN_MIN" => {
+                    domain_min = floats(rest).ok_or_else(|| invalid(format!("bad DOMAIN_MIN on line {}", number + 1)))?;
+                }
+                "DOMAIN_MAX" => {
+                    domain_max = floats(rest).ok_or_else(|| invalid(format!("bad DOMAIN_MAX on line {}", number + 1)))?;
+                }
+                _ => {
+                    let rgb = floats(line).ok_or_else(|| invalid(format!("unexpected line {}: '{line}'", number + 1)))?;
+                    table.push(rgb);
+                }
+            }
+        }
+        let size = size.ok_or_else(|| invalid("missing LUT_3D_SIZE".into()))?;
+        if table.len() != size * size * size {
+            return Err(invalid(format!("expected {} entries, found {}", size * size * size, table.len())));
+        }
+        if (0..3).any(|c| domain_max[c] <= domain_min[c]) {
+            return Err(invalid("DOMAIN_MAX must exceed DOMAIN_MIN".into()));
+        }
+        Ok(Self {
+            size,
+            domain_min,
+            domain_max,
+            table,
+        })
+    }
+
+    /// Grid points per axis.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Map 8-bit RGBA pixels through the table in place, interpolating
+    /// trilinearly between grid points. Alpha is left alone.
+    pub fn apply(&self, rgba: &mut [u8]) {
+        rgba.par_chunks_exact_mut(4).for_each(|pixel| {
+            let out = self.lookup([pixel[0], pixel[1], pixel[2]].map(|v| v as f32 / 255.0));
+            for (channel, value) in out.into_iter().enumerate() {
+                pixel[channel] = (value.clamp(0.0, 1.0) * 255.0 + 0.5) as u8;
+            }
+        });
+    }
+
+    /// Output colour for a normalized input colour.
+    pub fn lookup(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let max_index = (self.size - 1) as f32;
+        // Grid coordinates, and the lower corner and weight on each axis
+        let coords: [(usize, f32); 3] = std::array::from_fn(|c| {
+            let t = (rgb[c] - self.domain_min[c]) / (self.domain_max[c] - self.domain_min[c]);
+            let x = t.clamp(0.0, 1.0) * max_index;
+            let lower = (x.floor() as usize).min(self.size - 2);
+            (lower, x - lower as f32)
+        });
+        let entry = |r: usize, g: usize, b: usize| self.table[r + self.size * (g + self.size * b)];
+        let [(r, fr), (g, fg), (b, fb)] = coords;
+        let mut out = [0.0; 3];
+        for (dr, wr) in [(0, 1.0 - fr), (1, fr)] {
+            for (dg, wg) in [(0, 1.0 - fg), (1, fg)] {
+                for (db, wb) in [(0, 1.0 - fb), (1, fb)] {
+                    let weight = wr * wg * wb;
+                    let value = entry(r + dr, g + dg, b + db);
+                    for (out, value) in out.iter_mut().zip(value) {
+                        *out += weight * value;
+                    }
+                }
+            }
+        }
+        out
+    }
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 
 use crate::error::{CoreError, Result};
+use crate::journal::{CommandJournal, JournalEntry};
+use crate::media::{MediaAsset, SourceLibrary};
 use crate::timeline::Timeline;
 
 /// A command that can be applied to and reverted from a timeline.
@@ -12,6 +14,61 @@ pub enum Command {
         before: Timeline,
         after: Timeline,
     },
+    /// Like `Snapshot`, plus the one asset an import or removal added to or
+    /// removed from the source library. Only that asset is touched on undo
+    /// and redo, so other library changes made since are kept.
+    Library {
+        description: String,
+        before: Timeline,
+        after: Timeline,
+        change: Box<LibraryChange>,
+    },
+}
+
+/// The asset a library command added or removed and its place in the
+/// library. The asset is refreshed from the library whenever undo or redo
+/// takes it out, so edits made to it in between survive.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LibraryChange {
+    Added { index: usize, asset: MediaAsset },
+    Removed { index: usize, asset: MediaAsset },
+}
+
+impl LibraryChange {
+    pub(crate) fn apply(&mut self, library: &mut SourceLibrary) {
+        match self {
+            LibraryChange::Added { index, asset } => put_asset(library, *index, asset),
+            LibraryChange::Removed { index, asset } => take_asset(library, index, asset),
+        }
+    }
+
+    fn revert(&mut self, library: &mut SourceLibrary) {
+        match self {
+            LibraryChange::Added { index, asset } => take_asset(library, index, asset),
+            LibraryChange::Removed { index, asset } => put_asset(library, *index, asset),
+        }
+    }
+
+    /// The change that undoes this one.
+    fn inverse(&self) -> LibraryChange {
+        match self {
+            LibraryChange::Added { index, asset } => LibraryChange::Removed { index: *index, asset: asset.clone() },
+            LibraryChange::Removed { index, asset } => LibraryChange::Added { index: *index, asset: asset.clone() },
+        }
+    }
+}
+
+fn take_asset(library: &mut SourceLibrary, index: &mut usize, asset: &mut MediaAsset) {
+    if let Some(position) = library.position(asset.id) {
+        *index = position;
+        *asset = library.remove(asset.id).expect("asset was just found");
+    }
+}
+
+fn put_asset(library: &mut SourceLibrary, index: usize, asset: &MediaAsset) {
+    if library.get(asset.id).is_none() {
+        library.insert(index, asset.clone());
+    }
 }
 
 impl Command {
@@ -30,7 +87,7 @@ impl Command {
 
     pub fn description(&self) -> &str {
         match self {
-            Command::Snapshot { description, .. } => description,
+            Command::Snapshot { description, .. } | Command::Library { description, .. } => description,
         }
     }
 }
@@ -40,6 +97,10 @@ impl Command {
 pub struct CommandHistory {
     undo_stack: Vec<Command>,
     redo_stack: Vec<Command>,
+    /// Optional crash-recovery journal; every resulting timeline state (and
+    /// library change) is appended to it.
+    #[serde(skip)]
+    journal: Option<CommandJournal>,
 }
 
 impl CommandHistory {
@@ -47,6 +108,34 @@ impl CommandHistory {
         Self {
             undo_stack: Vec::new(),
             redo_stack: Vec::new(),
+            journal: None,
+        }
+    }
+
+    /// Attach (or detach with `None`) a journal that records every executed,
+    /// undone and redone command.
+    pub fn set_journal(&mut self, journal: Option<CommandJournal>) {
+        self.journal = journal;
+    }
+
+    /// Detach and return the current journal, if any.
+    pub fn take_journal(&mut self) -> Option<CommandJournal> {
+        self.journal.take()
+    }
+
+    pub fn journal(&self) -> Option<&CommandJournal> {
+        self.journal.as_ref()
+    }
+
+    /// Journal writes are best-effort: a failing disk must never fail the edit itself.
+    fn write_journal(&self, description: &str, timeline: &Timeline, library: Option<LibraryChange>) {
+        if let Some(journal) = &self.journal {
+            let entry = JournalEntry {
+                description: description.to_string(),
+                timeline: timeline.clone(),
+                library,
+            };
+            journal.append_entry(&entry).ok();
         }
     }
 
@@ -62,29 +151,95 @@ impl CommandHistory {
         let cmd = Command::snapshot(description, &before, timeline);
         self.undo_stack.push(cmd);
         self.redo_stack.clear();
+        self.write_journal(description, timeline, None);
+        Ok(result)
+    }
+
+    /// Like `execute`, for operations that add or remove a source library
+    /// asset too. The closure reports which one as a `LibraryChange`; undo
+    /// and redo restore the timeline and take out or put back just that
+    /// asset (see `undo_with_library`).
+    pub fn execute_with_library<F, T>(
+        &mut self,
+        timeline: &mut Timeline,
+        library: &mut SourceLibrary,
+        description: &str,
+        f: F,
+    ) -> Result<T>
+    where
+        F: FnOnce(&mut Timeline, &mut SourceLibrary) -> Result<(T, LibraryChange)>,
+    {
+        let before = timeline.clone();
+        let (result, change) = f(timeline, library)?;
+        self.write_journal(description, timeline, Some(change.clone()));
+        self.undo_stack.push(Command::Library {
+            description: description.to_string(),
+            before,
+            after: timeline.clone(),
+            change: Box::new(change),
+        });
+        self.redo_stack.clear();
         Ok(result)
     }
 
     /// Undo the last command, restoring the timeline to its previous state.
+    /// A library command's library state is left alone; use
+    /// `undo_with_library` when there is one.
     pub fn undo(&mut self, timeline: &mut Timeline) -> Result<()> {
-        let cmd = self.undo_stack.pop().ok_or(CoreError::NothingToUndo)?;
-        match &cmd {
+        self.step_back(timeline, None)
+    }
+
+    /// Undo the last command, restoring the timeline and source library.
+    pub fn undo_with_library(&mut self, timeline: &mut Timeline, library: &mut SourceLibrary) -> Result<()> {
+        self.step_back(timeline, Some(library))
+    }
+
+    /// Redo the last undone command.
+    pub fn redo(&mut self, timeline: &mut Timeline) -> Result<()> {
+        self.step_forward(timeline, None)
+    }
+
+    /// Redo the last undone command, on the timeline and source library.
+    pub fn redo_with_library(&mut self, timeline: &mut Timeline, library: &mut SourceLibrary) -> Result<()> {
+        self.step_forward(timeline, Some(library))
+    }
+
+    fn step_back(&mut self, timeline: &mut Timeline, library: Option<&mut SourceLibrary>) -> Result<()> {
+        let mut cmd = self.undo_stack.pop().ok_or(CoreError::NothingToUndo)?;
+        let mut library_change = None;
+        match &mut cmd {
             Command::Snapshot { before, .. } => {
                 *timeline = before.clone();
             }
+            Command::Library { before, change, .. } => {
+                *timeline = before.clone();
+                if let Some(library) = library {
+                    change.revert(library);
+                    library_change = Some(change.inverse());
+                }
+            }
         }
+        self.write_journal(&format!("Undo {}", cmd.description()), timeline, library_change);
         self.redo_stack.push(cmd);
         Ok(())
     }
 
-    /// Redo the last undone command.
-    pub fn redo(&mut self, timeline: &mut Timeline) -> Result<()> {
-        let cmd = self.redo_stack.pop().ok_or(CoreError::NothingToRedo)?;
-        match &cmd {
+    fn step_forward(&mut self, timeline: &mut Timeline, library: Option<&mut SourceLibrary>) -> Result<()> {
+        let mut cmd = self.redo_stack.pop().ok_or(CoreError::NothingToRedo)?;
+        let mut library_change = None;
+        match &mut cmd {
             Command::Snapshot { after, .. } => {
                 *timeline = after.clone();
             }
+            Command::Library { after, change, .. } => {
+                *timeline = after.clone();
+                if let Some(library) = library {
+                    change.apply(library);
+                    library_change = Some(change.as_ref().clone());
+                }
+            }
         }
+        self.write_journal(&format!("Redo {}", cmd.description()), timeline, library_change);
         self.undo_stack.push(cmd);
         Ok(())
     }
@@ -0,0 +1,68 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::media::SourceLibrary;
+use crate::timeline::Timeline;
+
+/// `M:SS`, or `H:MM:SS` from an hour on: the chapter timestamp format video
+/// sites pick up from descriptions. Fractions of a second are dropped.
+pub fn format_timecode(time: Duration) -> String {
+    let secs = time.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+/// Plain-text summary of the timeline for a video description or edit log:
+/// a chapter list from the markers, then every clip with its source range.
+/// Chapters start at 0:00 (a "Start" chapter is added if no marker is
+/// there), and linked clips are listed once.
+pub fn timeline_description(timeline: &Timeline, library: &SourceLibrary) -> String {
+    let mut out = String::new();
+
+    if !timeline.markers.is_empty() {
+        out.push_str("Chapters\n");
+        if timeline.markers[0].position.as_duration() >= Duration::from_secs(1) {
+            out.push_str("0:00 Start\n");
+        }
+        for marker in &timeline.markers {
+            let _ = writeln!(out, "{} {}", format_timecode(marker.position.as_duration()), marker.name);
+        }
+    }
+
+    let mut clips: Vec<_> = timeline
+        .tracks
+        .iter()
+        .enumerate()
+        .flat_map(|(index, track)| track.clips.iter().map(move |clip| (index, track, clip)))
+        .collect();
+    clips.sort_by_key(|(index, _, clip)| (clip.timeline_range.start, *index));
+    let mut seen_links = HashSet::new();
+    clips.retain(|(_, _, clip)| clip.link_id.is_none_or(|id| seen_links.insert(id)));
+    if clips.is_empty() {
+        return out;
+    }
+
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out.push_str("Clips\n");
+    for (_, track, clip) in clips {
+        let name = library.get(clip.asset_id).map_or("(missing media)", |a| a.name.as_str());
+        let _ = writeln!(
+            out,
+            "{}-{}  {}  {} ({}-{})",
+            format_timecode(clip.timeline_range.start.as_duration()),
+            format_timecode(clip.timeline_range.end.as_duration()),
+            track.name,
+            name,
+            format_timecode(clip.source_range.start.as_duration()),
+            format_timecode(clip.source_range.end.as_duration()),
+        );
+    }
+    out
+}
@@ -8,6 +8,8 @@ pub enum EffectType {
     Grayscale,
     Brightness,
     Opacity,
+    Denoise,
+    Sharpen,
 }
 
 impl EffectType {
@@ -18,9 +20,29 @@ impl EffectType {
             Self::Grayscale => "Grayscale",
             Self::Brightness => "Brightness",
             Self::Opacity => "Opacity",
+            Self::Denoise => "Denoise",
+            Self::Sharpen => "Sharpen",
         }
     }
 
+    /// Browser category this effect is listed under.
+    pub fn category(&self) -> EffectCategory {
+        match self {
+            Self::Transform => EffectCategory::Transform,
+            Self::Grayscale | Self::Brightness | Self::Opacity => EffectCategory::Color,
+            Self::Denoise | Self::Sharpen => EffectCategory::Blur,
+        }
+    }
+
+    /// Case-insensitive match of `query` against the effect or category name.
+    /// An empty query matches everything.
+    pub fn matches_search(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        query.is_empty()
+            || self.display_name().to_lowercase().contains(&query)
+            || self.category().display_name().to_lowercase().contains(&query)
+    }
+
     /// Parameter definitions for this effect type.
     pub fn parameter_definitions(&self) -> Vec<ParameterDefinition> {
         match self {
@@ -63,6 +85,46 @@ impl EffectType {
                     max: 1.0,
                 },
             }],
+            Self::Denoise => vec![
+                ParameterDefinition {
+                    name: "strength".to_string(),
+                    label: "Strength".to_string(),
+                    param_type: ParameterType::Float {
+                        default: 0.5,
+                        min: 0.0,
+                        max: 1.0,
+                    },
+                },
+                ParameterDefinition {
+                    name: "radius".to_string(),
+                    label: "Radius".to_string(),
+                    param_type: ParameterType::Float {
+                        default: 2.0,
+                        min: 1.0,
+                        max: 4.0,
+                    },
+                },
+            ],
+            Self::Sharpen => vec![
+                ParameterDefinition {
+                    name: "amount".to_string(),
+                    label: "Amount".to_string(),
+                    param_type: ParameterType::Float {
+                        default: 0.5,
+                        min: 0.0,
+                        max: 3.0,
+                    },
+                },
+                ParameterDefinition {
+                    name: "radius".to_string(),
+                    label: "Radius".to_string(),
+                    param_type: ParameterType::Float {
+                        default: 1.0,
+                        min: 1.0,
+                        max: 5.0,
+                    },
+                },
+            ],
         }
     }
 
@@ -73,6 +135,41 @@ impl EffectType {
             EffectType::Grayscale,
             EffectType::Brightness,
             EffectType::Opacity,
+            EffectType::Denoise,
+            EffectType::Sharpen,
+        ]
+    }
+}
+
+/// Grouping used by the effects browser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EffectCategory {
+    Color,
+    Blur,
+    Transform,
+    Audio,
+    Transitions,
+}
+
+impl EffectCategory {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Color => "Color",
+            Self::Blur => "Blur",
+            Self::Transform => "Transform",
+            Self::Audio => "Audio",
+            Self::Transitions => "Transitions",
+        }
+    }
+
+    /// All categories, in browser display order.
+    pub fn all() -> [EffectCategory; 5] {
+        [
+            Self::Color,
+            Self::Blur,
+            Self::Transform,
+            Self::Audio,
+            Self::Transitions,
         ]
     }
 }
@@ -199,11 +296,13 @@ mod tests {
     #[test]
     fn test_effect_type_all_builtin() {
         let all = EffectType::all_builtin();
-        assert_eq!(all.len(), 4);
+        assert_eq!(all.len(), 6);
         assert!(all.contains(&EffectType::Transform));
         assert!(all.contains(&EffectType::Grayscale));
         assert!(all.contains(&EffectType::Brightness));
         assert!(all.contains(&EffectType::Opacity));
+        assert!(all.contains(&EffectType::Denoise));
+        assert!(all.contains(&EffectType::Sharpen));
     }
 
     #[test]
@@ -214,6 +313,24 @@ mod tests {
         assert_eq!(EffectType::Opacity.display_name(), "Opacity");
     }
 
+    #[test]
+    fn test_effect_categories() {
+        assert_eq!(EffectType::Transform.category(), EffectCategory::Transform);
+        assert_eq!(EffectType::Grayscale.category(), EffectCategory::Color);
+        assert_eq!(EffectType::Sharpen.category(), EffectCategory::Blur);
+        for effect_type in EffectType::all_builtin() {
+            assert!(EffectCategory::all().contains(&effect_type.category()));
+        }
+    }
+
+    #[test]
+    fn test_effect_matches_search() {
+        assert!(EffectType::Grayscale.matches_search(""));
+        assert!(EffectType::Grayscale.matches_search("GRAY"));
+        assert!(EffectType::Brightness.matches_search(" color "));
+        assert!(!EffectType::Transform.matches_search("blur"));
+    }
+
     #[test]
     fn test_grayscale_no_params() {
         let effect = EffectInstance::new(EffectType::Grayscale);
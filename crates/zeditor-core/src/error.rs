@@ -14,9 +14,24 @@ pub enum CoreError {
     #[error("media asset not found: {0}")]
     AssetNotFound(Uuid),
 
+    #[error("timeline snapshot not found: {0}")]
+    SnapshotNotFound(Uuid),
+
+    #[error("render range not found: {0}")]
+    RenderRangeNotFound(Uuid),
+
+    #[error("sub-clip not found: {0}")]
+    SubClipNotFound(Uuid),
+
+    #[error("marker not found: {0}")]
+    MarkerNotFound(Uuid),
+
     #[error("cut position {position:?} is outside clip bounds")]
     CutOutsideClip { position: TimelinePosition },
 
+    #[error("marker position {position:?} is outside the clip")]
+    MarkerOutsideClip { position: TimelinePosition },
+
     #[error("clip overlap detected at timeline position {position:?}")]
     ClipOverlap { position: TimelinePosition },
 
@@ -38,6 +53,36 @@ pub enum CoreError {
     #[error("cannot insert: track {0} is not a {1:?} track")]
     InvalidTrackInsertion(usize, crate::timeline::TrackType),
 
+    #[error("track {0} is locked")]
+    TrackLocked(usize),
+
+    #[error("cannot remove the only {0:?} track")]
+    LastTrackOfType(crate::timeline::TrackType),
+
+    #[error("a group needs at least two clips")]
+    GroupTooSmall,
+
+    #[error("clip {0} would be synced entirely before the timeline start")]
+    SyncBeforeTimelineStart(Uuid),
+
+    #[error("clip {0} is linked or grouped with other clips; only a standalone clip can be synced")]
+    SyncClipNotStandalone(Uuid),
+
+    #[error("clip {0} shares a track with the clip it would be synced to")]
+    SyncSharesTrack(Uuid),
+
+    #[error("invalid clip speed {0}: must be a positive number")]
+    InvalidSpeed(f64),
+
+    #[error("clip {0} has no stereo audio to split")]
+    NoStereoAudio(Uuid),
+
+    #[error("replacement media is incompatible: {0}")]
+    IncompatibleMedia(String),
+
+    #[error("invalid LUT file: {0}")]
+    InvalidLut(String),
+
     #[error("nothing to undo")]
     NothingToUndo,
 
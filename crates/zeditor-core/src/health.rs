@@ -0,0 +1,98 @@
+//! Timeline health checks, run before export to catch edits that render
+//! wrong: black gaps between clips, clips overlapping by a sliver, clips
+//! reading past the end of their media, and clips whose media is gone.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::media::{MediaKind, SourceLibrary};
+use crate::timeline::{Timeline, TimelinePosition};
+
+/// Differences below this are float noise from position arithmetic, not edits.
+const TOLERANCE: Duration = Duration::from_micros(1);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HealthIssueKind {
+    /// Empty space between two clips on a track, starting at the issue position.
+    Gap { duration: Duration },
+    /// The clip starts before the previous clip on its track (`other_clip_id`) ends.
+    Overlap { duration: Duration, other_clip_id: Uuid },
+    /// The clip's source range ends `overrun` past the end of its asset.
+    SourceOutOfRange { overrun: Duration },
+    /// The clip's asset is not in the source library.
+    MissingAsset { asset_id: Uuid },
+    /// The clip's asset file no longer exists on disk.
+    MissingFile { path: PathBuf },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthIssue {
+    pub track_index: usize,
+    /// The clip the issue belongs to; `None` for gaps.
+    pub clip_id: Option<Uuid>,
+    /// Where on the timeline to jump to for this issue.
+    pub position: TimelinePosition,
+    pub kind: HealthIssueKind,
+}
+
+/// Every issue on the timeline, ordered by position and then track.
+pub fn inspect_timeline(timeline: &Timeline, library: &SourceLibrary) -> Vec<HealthIssue> {
+    let mut issues = Vec::new();
+
+    for (track_index, track) in timeline.tracks.iter().enumerate() {
+        let mut clips: Vec<_> = track.clips.iter().collect();
+        clips.sort_by_key(|c| c.timeline_range.start.as_duration());
+
+        for pair in clips.windows(2) {
+            let (prev, next) = (pair[0], pair[1]);
+            let prev_end = prev.timeline_range.end.as_duration();
+            let next_start = next.timeline_range.start.as_duration();
+            if next_start > prev_end + TOLERANCE {
+                issues.push(HealthIssue {
+                    track_index,
+                    clip_id: None,
+                    position: prev.timeline_range.end,
+                    kind: HealthIssueKind::Gap { duration: next_start - prev_end },
+                });
+            } else if prev_end > next_start + TOLERANCE {
+                issues.push(HealthIssue {
+                    track_index,
+                    clip_id: Some(next.id),
+                    position: next.timeline_range.start,
+                    kind: HealthIssueKind::Overlap {
+                        duration: prev_end - next_start,
+                        other_clip_id: prev.id,
+                    },
+                });
+            }
+        }
+
+        for clip in &clips {
+            let issue = |kind| HealthIssue {
+                track_index,
+                clip_id: Some(clip.id),
+                position: clip.timeline_range.start,
+                kind,
+            };
+            let Some(asset) = library.get(clip.asset_id) else {
+                issues.push(issue(HealthIssueKind::MissingAsset { asset_id: clip.asset_id }));
+                continue;
+            };
+            if !asset.path.exists() {
+                issues.push(issue(HealthIssueKind::MissingFile { path: asset.path.clone() }));
+            }
+            // Stills have no length to run past
+            let source_end = clip.source_range.end.as_duration();
+            if asset.kind() != MediaKind::Image && source_end > asset.duration + TOLERANCE {
+                issues.push(issue(HealthIssueKind::SourceOutOfRange {
+                    overrun: source_end - asset.duration,
+                }));
+            }
+        }
+    }
+
+    issues.sort_by_key(|issue| (issue.position.as_duration(), issue.track_index));
+    issues
+}
@@ -0,0 +1,186 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::{CommandHistory, LibraryChange};
+use crate::error::Result;
+use crate::media::SourceLibrary;
+use crate::timeline::Timeline;
+
+/// A single journaled operation: its description and the timeline state it produced.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub description: String,
+    pub timeline: Timeline,
+    /// The asset an import or removal added or removed, if it changed the
+    /// source library.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub library: Option<LibraryChange>,
+}
+
+/// Append-only journal of executed timeline commands, stored next to the project file.
+///
+/// Every entry is one JSON line holding the timeline state after the operation,
+/// plus the asset an import or removal added or removed.
+/// The journal is cleared whenever the project is saved, so the saved file plus
+/// the journal always reconstructs the latest in-memory state after a crash.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandJournal {
+    path: PathBuf,
+}
+
+impl CommandJournal {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Journal for a project file: `<project path>.journal`.
+    pub fn for_project(project_path: &Path) -> Self {
+        let mut name = project_path.as_os_str().to_owned();
+        name.push(".journal");
+        Self::new(name)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append a timeline state as a JSON line.
+    pub fn append(&self, description: &str, timeline: &Timeline) -> Result<()> {
+        self.append_entry(&JournalEntry {
+            description: description.to_string(),
+            timeline: timeline.clone(),
+            library: None,
+        })
+    }
+
+    /// Append one entry as a JSON line.
+    pub fn append_entry(&self, entry: &JournalEntry) -> Result<()> {
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line.as_bytes())?;
+        file.flush()?;
+        Ok(())
+    }
+
+    /// Read all complete entries. A missing journal yields no entries.
+    /// Reading stops at the first unparseable line, which is what a crash
+    /// in the middle of a write leaves behind.
+    pub fn read_entries(&self) -> Result<Vec<JournalEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let contents = fs::read_to_string(&self.path)?;
+        let mut entries = Vec::new();
+        for line in contents.lines().filter(|l| !l.trim().is_empty()) {
+            match serde_json::from_str::<JournalEntry>(line) {
+                Ok(entry) => entries.push(entry),
+                Err(_) => break,
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Replace the journal with just `entries`, dropping anything after them
+    /// (such as the half-written line a crash leaves) so later appends
+    /// stay readable. Written through a `.tmp` file and a rename.
+    pub fn rewrite(&self, entries: &[JournalEntry]) -> Result<()> {
+        if entries.is_empty() {
+            return self.clear();
+        }
+        let mut contents = String::new();
+        for entry in entries {
+            contents.push_str(&serde_json::to_string(entry)?);
+            contents.push('\n');
+        }
+        let mut tmp = self.path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        fs::write(&tmp, contents)?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+
+    /// Remove the journal file. A missing file is not an error.
+    pub fn clear(&self) -> Result<()> {
+        match fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Replay journal entries onto a timeline and source library, recording each
+/// as an undoable step. Any journal attached to `history` is detached during
+/// replay so the entries aren't written a second time. Returns the number of
+/// entries applied.
+pub fn replay(
+    history: &mut CommandHistory,
+    timeline: &mut Timeline,
+    library: &mut SourceLibrary,
+    entries: Vec<JournalEntry>,
+) -> Result<usize> {
+    let journal = history.take_journal();
+    let count = entries.len();
+    let mut result = Ok(count);
+    for entry in entries {
+        let applied = match entry.library {
+            Some(mut change) => history.execute_with_library(timeline, library, &entry.description, |tl, library| {
+                *tl = entry.timeline;
+                change.apply(library);
+                Ok(((), change))
+            }),
+            None => history.execute(timeline, &entry.description, |tl| {
+                *tl = entry.timeline;
+                Ok(())
+            }),
+        };
+        if let Err(e) = applied {
+            result = Err(e);
+            break;
+        }
+    }
+    history.set_journal(journal);
+    result
+}
+
+/// Entries that bring a project saved with `saved_timeline` and
+/// `saved_library` up to `timeline` and `library`, for edits made while the
+/// file was being written: one per asset removed or added since, or a lone
+/// timeline entry if only the timeline changed. Empty if nothing did.
+pub fn catch_up(
+    description: &str,
+    saved_timeline: &Timeline,
+    saved_library: &SourceLibrary,
+    timeline: &Timeline,
+    library: &SourceLibrary,
+) -> Vec<JournalEntry> {
+    let removed = saved_library
+        .assets()
+        .iter()
+        .enumerate()
+        .filter(|(_, a)| library.get(a.id).is_none())
+        .map(|(index, a)| LibraryChange::Removed { index, asset: a.clone() });
+    let added = library
+        .assets()
+        .iter()
+        .enumerate()
+        .filter(|(_, a)| saved_library.get(a.id).is_none())
+        .map(|(index, a)| LibraryChange::Added { index, asset: a.clone() });
+    let entry = |library: Option<LibraryChange>| JournalEntry {
+        description: description.to_string(),
+        timeline: timeline.clone(),
+        library,
+    };
+    let mut entries: Vec<JournalEntry> = removed.chain(added).map(|change| entry(Some(change))).collect();
+    if entries.is_empty() && timeline != saved_timeline {
+        entries.push(entry(None));
+    }
+    entries
+}
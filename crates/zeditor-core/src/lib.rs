@@ -1,7 +1,21 @@
+pub mod audio;
+pub mod color;
 pub mod commands;
+pub mod description;
 pub mod effects;
 pub mod error;
+pub mod health;
+pub mod journal;
 pub mod media;
 pub mod pipeline;
+pub mod pixel_ops;
 pub mod project;
+pub mod reframe;
+pub mod render_ranges;
+pub mod scaler;
+pub mod snapshots;
+pub mod stats;
+pub mod timecode;
 pub mod timeline;
+pub mod transcript;
+pub mod versions;
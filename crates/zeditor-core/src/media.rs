@@ -1,10 +1,62 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::color::ColorSpace;
 use crate::error::{CoreError, Result};
+use crate::timeline::{TimeRange, TimelinePosition};
+use crate::transcript::TranscriptWord;
+
+/// File extensions imported as still images.
+pub const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "bmp", "gif", "tif", "tiff", "webp"];
+/// File extensions of video containers offered for import.
+pub const VIDEO_EXTENSIONS: &[&str] = &["mp4", "mov", "avi", "mkv", "webm", "m4v", "mxf"];
+/// File extensions of audio files offered for import.
+pub const AUDIO_EXTENSIONS: &[&str] = &["wav", "mp3", "aac", "m4a", "flac", "ogg", "opus"];
+
+/// Whether `path` has the extension of a video, audio or image file.
+pub fn is_supported_media(path: &Path) -> bool {
+    let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+        return false;
+    };
+    let ext = ext.to_ascii_lowercase();
+    [VIDEO_EXTENSIONS, AUDIO_EXTENSIONS, IMAGE_EXTENSIONS]
+        .iter()
+        .any(|list| list.contains(&ext.as_str()))
+}
+
+/// Every supported media file under `dir`, recursively, in path order.
+/// Hidden files and folders (starting with '.') are skipped.
+pub fn scan_media_folder(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut found = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let path = entry.path();
+            if entry.file_type()?.is_dir() {
+                pending.push(path);
+            } else if is_supported_media(&path) {
+                found.push(path);
+            }
+        }
+    }
+    found.sort();
+    Ok(found)
+}
+
+/// Broad category of a media asset, derived from its probed properties.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MediaKind {
+    Video,
+    Audio,
+    Image,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct MediaAsset {
@@ -19,6 +71,46 @@ pub struct MediaAsset {
     /// Rotation metadata in degrees (0, 90, 180, 270). Defaults to 0.
     #[serde(default)]
     pub rotation: u32,
+    /// Codec name of the primary stream (video, or audio for audio-only files).
+    /// Empty when unknown, e.g. for assets imported before this was probed.
+    #[serde(default)]
+    pub codec: String,
+    /// Source times where a new shot starts (after the first), found by shot
+    /// detection on import. Empty for single-shot or unanalysed media.
+    #[serde(default)]
+    pub shot_starts: Vec<Duration>,
+    /// Words spoken in the asset's audio, in source time. Filled in by
+    /// background transcription; empty until then.
+    #[serde(default)]
+    pub transcript: Vec<TranscriptWord>,
+    /// Transfer function and primaries of the video, from its stream tags.
+    #[serde(default)]
+    pub color: ColorSpace,
+    /// Library bin the asset is grouped into, e.g. the folder it was
+    /// imported from. `None` for loose assets.
+    #[serde(default)]
+    pub bin: Option<String>,
+    /// Named in/out ranges logged from the asset, in the order they were added.
+    #[serde(default)]
+    pub subclips: Vec<SubClip>,
+    /// Hash of the file's contents, used to spot the same file imported
+    /// again from another path. Only taken once another asset has the same
+    /// `file_size`, so `None` for most assets.
+    #[serde(default)]
+    pub content_hash: Option<u64>,
+    /// Size of the file in bytes when it was imported. `None` if the asset
+    /// predates it.
+    #[serde(default)]
+    pub file_size: Option<u64>,
+}
+
+/// A named source range of an asset. Shown as its own library card and
+/// placed on the timeline already trimmed to `range`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SubClip {
+    pub id: Uuid,
+    pub name: String,
+    pub range: TimeRange,
 }
 
 impl MediaAsset {
@@ -41,9 +133,62 @@ impl MediaAsset {
             fps,
             has_audio,
             rotation: 0,
+            codec: String::new(),
+            shot_starts: Vec::new(),
+            transcript: Vec::new(),
+            color: ColorSpace::default(),
+            bin: None,
+            subclips: Vec::new(),
+            content_hash: None,
+            file_size: None,
         }
     }
 
+    /// Log `range` as a sub-clip named `name`, returning its id. The range
+    /// must end within the asset.
+    pub fn add_subclip(&mut self, name: impl Into<String>, range: TimeRange) -> Result<Uuid> {
+        let end = TimelinePosition::from_secs_f64(self.duration.as_secs_f64());
+        if range.end > end {
+            return Err(CoreError::InvalidTimeRange { start: range.start, end: range.end });
+        }
+        let subclip = SubClip { id: Uuid::new_v4(), name: name.into(), range };
+        let id = subclip.id;
+        self.subclips.push(subclip);
+        Ok(id)
+    }
+
+    pub fn subclip(&self, id: Uuid) -> Option<&SubClip> {
+        self.subclips.iter().find(|s| s.id == id)
+    }
+
+    pub fn rename_subclip(&mut self, id: Uuid, name: impl Into<String>) -> Result<()> {
+        let subclip = self
+            .subclips
+            .iter_mut()
+            .find(|s| s.id == id)
+            .ok_or(CoreError::SubClipNotFound(id))?;
+        subclip.name = name.into();
+        Ok(())
+    }
+
+    pub fn remove_subclip(&mut self, id: Uuid) -> Result<SubClip> {
+        let idx = self
+            .subclips
+            .iter()
+            .position(|s| s.id == id)
+            .ok_or(CoreError::SubClipNotFound(id))?;
+        Ok(self.subclips.remove(idx))
+    }
+
+    /// Source ranges `(start, end)` of the detected shots, covering the whole
+    /// asset. A single range when no cuts were found.
+    pub fn shots(&self) -> Vec<(Duration, Duration)> {
+        let mut bounds = vec![Duration::ZERO];
+        bounds.extend(self.shot_starts.iter().copied().filter(|s| !s.is_zero() && *s < self.duration));
+        bounds.push(self.duration);
+        bounds.windows(2).map(|w| (w[0], w[1])).collect()
+    }
+
     /// Width after applying rotation (swaps for 90/270).
     pub fn display_width(&self) -> u32 {
         if self.rotation == 90 || self.rotation == 270 {
@@ -61,6 +206,57 @@ impl MediaAsset {
             self.height
         }
     }
+
+    /// Audio-only files have no picture; stills are recognised by extension or
+    /// by having a picture but no duration.
+    pub fn kind(&self) -> MediaKind {
+        if self.width == 0 || self.height == 0 {
+            return MediaKind::Audio;
+        }
+        let is_image_ext = self
+            .path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| IMAGE_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()));
+        if is_image_ext || self.duration.is_zero() {
+            MediaKind::Image
+        } else {
+            MediaKind::Video
+        }
+    }
+}
+
+/// An asset whose path starts with the prefix of a bulk remap
+/// ([`SourceLibrary::preview_path_remap`]).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathRemap {
+    pub asset_id: Uuid,
+    pub old_path: PathBuf,
+    pub new_path: PathBuf,
+    /// Whether a file exists at `new_path`. Only these are applied.
+    pub resolves: bool,
+}
+
+/// `path` with its leading `from` replaced by `to`, or `None` if `path`
+/// doesn't start with `from` on a component boundary. Matching is textual and
+/// accepts either separator, so `D:\footage` works in a project saved on
+/// Windows and opened elsewhere; an empty `from` matches nothing.
+pub fn remap_path_prefix(path: &Path, from: &Path, to: &Path) -> Option<PathBuf> {
+    let from = from.to_string_lossy();
+    let from = from.trim_end_matches(['/', '\\']);
+    if from.is_empty() {
+        return None;
+    }
+    let path = path.to_string_lossy();
+    let rest = path.strip_prefix(from)?;
+    if !rest.is_empty() && !rest.starts_with(['/', '\\']) {
+        return None;
+    }
+    let mut remapped = to.to_path_buf();
+    for part in rest.split(['/', '\\']).filter(|part| !part.is_empty()) {
+        remapped.push(part);
+    }
+    Some(remapped)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
@@ -77,12 +273,18 @@ impl SourceLibrary {
         self.assets.push(asset);
     }
 
+    /// Put `asset` at `index`, or at the end when the library is shorter.
+    pub fn insert(&mut self, index: usize, asset: MediaAsset) {
+        self.assets.insert(index.min(self.assets.len()), asset);
+    }
+
+    /// Where the asset sits in the library's order.
+    pub fn position(&self, id: Uuid) -> Option<usize> {
+        self.assets.iter().position(|a| a.id == id)
+    }
+
     pub fn remove(&mut self, id: Uuid) -> Result<MediaAsset> {
-        let idx = self
-            .assets
-            .iter()
-            .position(|a| a.id == id)
-            .ok_or(CoreError::AssetNotFound(id))?;
+        let idx = self.position(id).ok_or(CoreError::AssetNotFound(id))?;
         Ok(self.assets.remove(idx))
     }
 
@@ -90,10 +292,42 @@ impl SourceLibrary {
         self.assets.iter().find(|a| a.id == id)
     }
 
+    pub fn get_mut(&mut self, id: Uuid) -> Option<&mut MediaAsset> {
+        self.assets.iter_mut().find(|a| a.id == id)
+    }
+
     pub fn assets(&self) -> &[MediaAsset] {
         &self.assets
     }
 
+    /// An asset already imported from `path`, or with the same contents.
+    pub fn find_duplicate(&self, path: &Path, content_hash: Option<u64>) -> Option<&MediaAsset> {
+        self.assets
+            .iter()
+            .find(|a| a.path == path || (content_hash.is_some() && a.content_hash == content_hash))
+    }
+
+    /// Assets whose file was `size` bytes on import: the only ones a new
+    /// file of that size can have the same contents as.
+    pub fn with_file_size(&self, size: u64) -> impl Iterator<Item = &MediaAsset> {
+        self.assets.iter().filter(move |a| a.file_size == Some(size))
+    }
+
+    /// A sub-clip by id, with the asset it belongs to.
+    pub fn find_subclip(&self, id: Uuid) -> Option<(&MediaAsset, &SubClip)> {
+        self.assets
+            .iter()
+            .find_map(|a| a.subclip(id).map(|s| (a, s)))
+    }
+
+    /// Names of the bins assets are grouped into, sorted and without repeats.
+    pub fn bins(&self) -> Vec<&str> {
+        let mut bins: Vec<&str> = self.assets.iter().filter_map(|a| a.bin.as_deref()).collect();
+        bins.sort_unstable();
+        bins.dedup();
+        bins
+    }
+
     pub fn len(&self) -> usize {
         self.assets.len()
     }
@@ -101,4 +335,38 @@ impl SourceLibrary {
     pub fn is_empty(&self) -> bool {
         self.assets.is_empty()
     }
+
+    /// Every asset whose path would change by replacing the `from` prefix
+    /// with `to`, in library order, and whether its new path exists.
+    pub fn preview_path_remap(&self, from: &Path, to: &Path) -> Vec<PathRemap> {
+        self.assets
+            .iter()
+            .filter_map(|asset| {
+                let new_path = remap_path_prefix(&asset.path, from, to)?;
+                Some(PathRemap {
+                    asset_id: asset.id,
+                    old_path: asset.path.clone(),
+                    resolves: new_path.exists(),
+                    new_path,
+                })
+            })
+            .collect()
+    }
+
+    /// Replace the `from` prefix with `to` in the path of every asset whose
+    /// new path exists; assets that wouldn't resolve keep their path. Returns
+    /// the remaps applied.
+    pub fn remap_paths(&mut self, from: &Path, to: &Path) -> Vec<PathRemap> {
+        let applied: Vec<PathRemap> = self
+            .preview_path_remap(from, to)
+            .into_iter()
+            .filter(|remap| remap.resolves)
+            .collect();
+        for remap in &applied {
+            if let Some(asset) = self.get_mut(remap.asset_id) {
+                asset.path = remap.new_path.clone();
+            }
+        }
+        applied
+    }
 }
@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use rayon::prelude::*;
 
 use crate::effects::{EffectInstance, EffectType, ParameterValue};
+use crate::pixel_ops;
+use crate::scaler::{self, Placement, ScaleQuality};
 
 // =============================================================================
 // FrameBuffer
@@ -71,6 +73,8 @@ pub struct EffectContext {
     pub time_secs: f64,
     pub frame_number: u64,
     pub fps: f64,
+    /// Filter used when the clip is fitted onto the canvas.
+    pub scale_quality: ScaleQuality,
 }
 
 /// Trait for pixel-processing effects. All effects — including Transform —
@@ -189,18 +193,7 @@ impl PixelEffect for GrayscaleEffect {
         input
             .data
             .par_chunks_exact_mut(row_bytes)
-            .for_each(|row| {
-                for pixel in row.chunks_exact_mut(4) {
-                    let r = pixel[0] as f32;
-                    let g = pixel[1] as f32;
-                    let b = pixel[2] as f32;
-                    let l = (0.299 * r + 0.587 * g + 0.114 * b).round() as u8;
-                    pixel[0] = l;
-                    pixel[1] = l;
-                    pixel[2] = l;
-                    // alpha unchanged
-                }
-            });
+            .for_each(pixel_ops::grayscale_row);
         input
     }
 }
@@ -223,14 +216,7 @@ impl PixelEffect for BrightnessEffect {
         input
             .data
             .par_chunks_exact_mut(row_bytes)
-            .for_each(|row| {
-                for pixel in row.chunks_exact_mut(4) {
-                    pixel[0] = (pixel[0] as i16 + shift).clamp(0, 255) as u8;
-                    pixel[1] = (pixel[1] as i16 + shift).clamp(0, 255) as u8;
-                    pixel[2] = (pixel[2] as i16 + shift).clamp(0, 255) as u8;
-                    // alpha unchanged
-                }
-            });
+            .for_each(|row| pixel_ops::brightness_row(row, shift));
         input
     }
 
@@ -273,6 +259,147 @@ impl PixelEffect for OpacityEffect {
     }
 }
 
+/// Largest per-channel difference a neighbour may have from the pixel being
+/// denoised, at full strength, and still be averaged in.
+const MAX_DENOISE_THRESHOLD: f64 = 48.0;
+
+/// Filter radius for `params`, dropped to 1 for draft previews (anything but
+/// Lanczos scaling, i.e. during playback) so neighbourhood effects stay
+/// interactive. Renders always use the full radius.
+fn effect_radius(params: &[(String, ParameterValue)], ctx: &EffectContext, default: f64) -> usize {
+    let radius = get_float_param(params, "radius").unwrap_or(default).round().max(1.0) as usize;
+    if ctx.scale_quality == ScaleQuality::Lanczos { radius } else { 1 }
+}
+
+/// Edge-preserving spatial denoise (a sigma filter): each pixel becomes the
+/// mean of the neighbours within `radius` whose channels all lie within a
+/// strength-scaled threshold of it, so grain flattens while edges, which
+/// differ by more, are left sharp. Preserves alpha.
+pub struct DenoiseEffect;
+
+impl PixelEffect for DenoiseEffect {
+    fn process(
+        &self,
+        input: FrameBuffer,
+        params: &[(String, ParameterValue)],
+        ctx: &EffectContext,
+    ) -> FrameBuffer {
+        let strength = get_float_param(params, "strength").unwrap_or(0.5).clamp(0.0, 1.0);
+        let threshold = (strength * MAX_DENOISE_THRESHOLD).round() as u8;
+        let radius = effect_radius(params, ctx, 2.0);
+        let (width, height) = (input.width as usize, input.height as usize);
+        let row_bytes = width * 4;
+
+        let mut output = input.data.clone();
+        output
+            .par_chunks_exact_mut(row_bytes)
+            .enumerate()
+            .for_each(|(y, out_row)| {
+                let rows = y.saturating_sub(radius)..=(y + radius).min(height - 1);
+                for x in 0..width {
+                    let center = &input.data[y * row_bytes + x * 4..][..3];
+                    let mut sum = [0u32; 3];
+                    let mut count = 0u32;
+                    for ny in rows.clone() {
+                        for nx in x.saturating_sub(radius)..=(x + radius).min(width - 1) {
+                            let neighbour = &input.data[ny * row_bytes + nx * 4..][..3];
+                            if neighbour.iter().zip(center).all(|(a, b)| a.abs_diff(*b) <= threshold) {
+                                for c in 0..3 {
+                                    sum[c] += neighbour[c] as u32;
+                                }
+                                count += 1;
+                            }
+                        }
+                    }
+                    // The center always matches itself, so count >= 1
+                    for c in 0..3 {
+                        out_row[x * 4 + c] = ((sum[c] + count / 2) / count) as u8;
+                    }
+                }
+            });
+        FrameBuffer::from_rgba_vec(input.width, input.height, output)
+    }
+
+    fn is_identity(&self, params: &[(String, ParameterValue)]) -> bool {
+        get_float_param(params, "strength").unwrap_or(0.5) == 0.0
+    }
+}
+
+/// Unsharp mask: adds `amount` times the difference between each pixel and a
+/// box blur of `radius` around it, clamped. Preserves alpha.
+pub struct SharpenEffect;
+
+impl PixelEffect for SharpenEffect {
+    fn process(
+        &self,
+        mut input: FrameBuffer,
+        params: &[(String, ParameterValue)],
+        ctx: &EffectContext,
+    ) -> FrameBuffer {
+        let amount = get_float_param(params, "amount").unwrap_or(0.5) as f32;
+        let blurred = box_blur_rgb(&input, effect_radius(params, ctx, 1.0));
+
+        let row_bytes = input.width as usize * 4;
+        input
+            .data
+            .par_chunks_exact_mut(row_bytes)
+            .zip(blurred.par_chunks_exact(row_bytes))
+            .for_each(|(row, blurred_row)| {
+                for (pixel, blurred) in row.chunks_exact_mut(4).zip(blurred_row.chunks_exact(4)) {
+                    for c in 0..3 {
+                        let detail = pixel[c] as f32 - blurred[c] as f32;
+                        pixel[c] = (pixel[c] as f32 + amount * detail).round().clamp(0.0, 255.0) as u8;
+                    }
+                }
+            });
+        input
+    }
+
+    fn is_identity(&self, params: &[(String, ParameterValue)]) -> bool {
+        get_float_param(params, "amount").unwrap_or(0.5) == 0.0
+    }
+}
+
+/// RGB box blur of `radius` as separable horizontal and vertical passes.
+/// Windows are cut short at the frame edges. Alpha is copied through.
+fn box_blur_rgb(input: &FrameBuffer, radius: usize) -> Vec<u8> {
+    let (width, height) = (input.width as usize, input.height as usize);
+    let row_bytes = width * 4;
+    let average = |sum: u32, count: usize| ((sum + count as u32 / 2) / count as u32) as u8;
+
+    let mut horizontal = input.data.clone();
+    horizontal
+        .par_chunks_exact_mut(row_bytes)
+        .enumerate()
+        .for_each(|(y, out_row)| {
+            let src = &input.data[y * row_bytes..(y + 1) * row_bytes];
+            for x in 0..width {
+                let columns = x.saturating_sub(radius)..=(x + radius).min(width - 1);
+                let count = columns.clone().count();
+                for c in 0..3 {
+                    let sum: u32 = columns.clone().map(|nx| src[nx * 4 + c] as u32).sum();
+                    out_row[x * 4 + c] = average(sum, count);
+                }
+            }
+        });
+
+    let mut output = horizontal.clone();
+    output
+        .par_chunks_exact_mut(row_bytes)
+        .enumerate()
+        .for_each(|(y, out_row)| {
+            let rows = y.saturating_sub(radius)..=(y + radius).min(height - 1);
+            let count = rows.clone().count();
+            for x in 0..width {
+                for c in 0..3 {
+                    let sum: u32 = rows.clone().map(|ny| horizontal[ny * row_bytes + x * 4 + c] as u32).sum();
+                    out_row[x * 4 + c] = average(sum, count);
+                }
+            }
+        });
+    output
+}
+
 // =============================================================================
 // Effect Registry
 // =============================================================================
@@ -293,6 +420,8 @@ impl EffectRegistry {
         registry.register(EffectType::Grayscale, Box::new(GrayscaleEffect));
         registry.register(EffectType::Brightness, Box::new(BrightnessEffect));
         registry.register(EffectType::Opacity, Box::new(OpacityEffect));
+        registry.register(EffectType::Denoise, Box::new(DenoiseEffect));
+        registry.register(EffectType::Sharpen, Box::new(SharpenEffect));
         registry
     }
 
@@ -311,13 +440,16 @@ impl EffectRegistry {
 // Pipeline functions
 // =============================================================================
 
-/// Blit a source RGBA frame onto a canvas-sized buffer, centered and
-/// aspect-ratio preserved (letterboxed). Nearest-neighbor scaling.
-/// Takes ownership of clip to avoid cloning when dimensions already match.
+/// Blit a source RGBA frame onto a canvas-sized buffer at `placement`
+/// (usually from [`crate::scaler::CanvasFit::placement`]), resampled with
+/// `quality`. Takes ownership of clip to avoid cloning when it already covers
+/// the canvas exactly.
 pub fn blit_clip_to_canvas(
     clip: FrameBuffer,
     canvas_w: u32,
     canvas_h: u32,
+    placement: Placement,
+    quality: ScaleQuality,
 ) -> FrameBuffer {
     if clip.width == 0 || clip.height == 0 || canvas_w == 0 || canvas_h == 0 {
         return FrameBuffer::new(canvas_w, canvas_h);
@@ -325,56 +457,22 @@ pub fn blit_clip_to_canvas(
 
     // Fast path: dimensions match exactly — no scaling or centering needed.
     // This is the common case when preview resolution matches decoded frame size.
-    if clip.width == canvas_w && clip.height == canvas_h {
+    if covers_exactly(&clip, placement, canvas_w, canvas_h) {
         return clip;
     }
 
     let mut canvas = FrameBuffer::new(canvas_w, canvas_h);
-
-    // Compute fit: scale clip to fit canvas, preserving aspect ratio
-    let scale_x = canvas_w as f64 / clip.width as f64;
-    let scale_y = canvas_h as f64 / clip.height as f64;
-    let scale = scale_x.min(scale_y);
-
-    let dst_w = (clip.width as f64 * scale).round() as u32;
-    let dst_h = (clip.height as f64 * scale).round() as u32;
-
-    if dst_w == 0 || dst_h == 0 {
-        return canvas;
-    }
-
-    // Center on canvas
-    let offset_x = (canvas_w.saturating_sub(dst_w)) / 2;
-    let offset_y = (canvas_h.saturating_sub(dst_h)) / 2;
-
-    let src_stride = clip.width as usize * 4;
-    let dst_stride = canvas_w as usize * 4;
-
-    // Nearest-neighbor blit using integer math to avoid f64 division per pixel
-    for dy in 0..dst_h {
-        let cy = offset_y + dy;
-        if cy >= canvas_h {
-            break;
-        }
-        let sy = ((dy as u64 * clip.height as u64) / dst_h as u64).min(clip.height as u64 - 1) as u32;
-        let dst_row_offset = cy as usize * dst_stride;
-        let src_row_offset = sy as usize * src_stride;
-
-        for dx in 0..dst_w {
-            let cx = offset_x + dx;
-            if cx >= canvas_w {
-                break;
-            }
-            let sx = ((dx as u64 * clip.width as u64) / dst_w as u64).min(clip.width as u64 - 1) as u32;
-            let si = src_row_offset + sx as usize * 4;
-            let di = dst_row_offset + cx as usize * 4;
-            canvas.data[di..di + 4].copy_from_slice(&clip.data[si..si + 4]);
-        }
-    }
-
+    scaler::blit_scaled(&clip, &mut canvas, placement, quality);
     canvas
 }
 
+/// Whether `clip` at `placement` is the canvas pixel for pixel.
+fn covers_exactly(clip: &FrameBuffer, placement: Placement, canvas_w: u32, canvas_h: u32) -> bool {
+    clip.width == canvas_w
+        && clip.height == canvas_h
+        && placement == Placement { x: 0, y: 0, width: canvas_w, height: canvas_h }
+}
+
 /// Result of running the effect pipeline, including metadata for the compositor.
 pub struct PipelineResult {
     pub frame: FrameBuffer,
@@ -389,7 +487,8 @@ pub struct PipelineResult {
 
 /// Run the full effect pipeline on a decoded clip frame.
 ///
-/// 1. Blits the clip onto a canvas-sized buffer (centered, aspect-fit)
+/// 1. Blits the clip onto a canvas-sized buffer at `placement` (scaled with
+///    `ctx.scale_quality`)
 /// 2. Runs each effect in order (skipping identity effects)
 /// 3. Returns the processed canvas-sized FrameBuffer plus transparency metadata
 ///
@@ -398,6 +497,7 @@ pub fn run_effect_pipeline(
     clip_frame: FrameBuffer,
     canvas_width: u32,
     canvas_height: u32,
+    placement: Placement,
     effects: &[EffectInstance],
     registry: &EffectRegistry,
     ctx: &EffectContext,
@@ -405,18 +505,19 @@ pub fn run_effect_pipeline(
     // Track whether clip fills the canvas (dimensions match exactly).
     // blit_clip_to_canvas returns the clip unchanged when dimensions match,
     // so we check before calling it.
-    let fills_canvas = clip_frame.width == canvas_width && clip_frame.height == canvas_height;
-    let mut canvas = blit_clip_to_canvas(clip_frame, canvas_width, canvas_height);
+    let fills_canvas = covers_exactly(&clip_frame, placement, canvas_width, canvas_height);
+    let mut canvas =
+        blit_clip_to_canvas(clip_frame, canvas_width, canvas_height, placement, ctx.scale_quality);
     let mut may_have_transparency = false;
 
     for effect in effects {
-        if let Some(pixel_effect) = registry.get(&effect.effect_type) {
-            if !pixel_effect.is_identity(&effect.parameters) {
-                if pixel_effect.may_produce_transparency() {
-                    may_have_transparency = true;
-                }
-                canvas = pixel_effect.process(canvas, &effect.parameters, ctx);
+        if let Some(pixel_effect) = registry.get(&effect.effect_type)
+            && !pixel_effect.is_identity(&effect.parameters)
+        {
+            if pixel_effect.may_produce_transparency() {
+                may_have_transparency = true;
             }
+            canvas = pixel_effect.process(canvas, &effect.parameters, ctx);
         }
     }
 
@@ -427,9 +528,53 @@ pub fn run_effect_pipeline(
     }
 }
 
+/// Placement adjustments of a clip whose effects only move and fade it. A
+/// compositor that positions layers itself (the GPU preview path) can apply
+/// these directly instead of running the pixel pipeline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayerGeometry {
+    /// Whole-pixel offset on the canvas, as `TransformEffect` applies it.
+    pub x_offset: i64,
+    pub y_offset: i64,
+    pub opacity: f64,
+}
+
+/// The `LayerGeometry` equivalent to `effects`, or `None` if the chain has an
+/// active effect other than Transform and Opacity (or more than one active
+/// Transform, since each one crops what it shifts off the canvas).
+pub fn layer_geometry(effects: &[EffectInstance], registry: &EffectRegistry) -> Option<LayerGeometry> {
+    let mut geometry = LayerGeometry {
+        x_offset: 0,
+        y_offset: 0,
+        opacity: 1.0,
+    };
+    let mut transformed = false;
+    for effect in effects {
+        let Some(pixel_effect) = registry.get(&effect.effect_type) else {
+            continue;
+        };
+        if pixel_effect.is_identity(&effect.parameters) {
+            continue;
+        }
+        match effect.effect_type {
+            EffectType::Transform if !transformed => {
+                transformed = true;
+                geometry.x_offset = get_float_param(&effect.parameters, "x_offset").unwrap_or(0.0).round() as i64;
+                geometry.y_offset = get_float_param(&effect.parameters, "y_offset").unwrap_or(0.0).round() as i64;
+            }
+            EffectType::Opacity => {
+                geometry.opacity *= get_float_param(&effect.parameters, "opacity").unwrap_or(1.0);
+            }
+            _ => return None,
+        }
+    }
+    Some(geometry)
+}
+
 /// Alpha-over composite: blend src onto dst. Both must have the same dimensions.
 /// Standard Porter-Duff "over" operation.
-/// Uses row-based parallelism to avoid rayon per-pixel scheduling overhead.
+/// Uses row-based parallelism to avoid rayon per-pixel scheduling overhead, with
+/// SIMD fast paths for runs of opaque or transparent source pixels.
 pub fn alpha_composite_rgba(src: &FrameBuffer, dst: &mut FrameBuffer) {
     assert_eq!(src.width, dst.width);
     assert_eq!(src.height, dst.height);
@@ -444,92 +589,27 @@ pub fn alpha_composite_rgba(src: &FrameBuffer, dst: &mut FrameBuffer) {
         .enumerate()
         .for_each(|(row, dst_row)| {
             let src_start = row * row_bytes;
-            let src_row = &src_data[src_start..src_start + row_bytes];
-
-            for i in (0..row_bytes).step_by(4) {
-                let sa = src_row[i + 3] as u32;
-                if sa == 255 {
-                    // Fully opaque source — just copy
-                    dst_row[i..i + 4].copy_from_slice(&src_row[i..i + 4]);
-                } else if sa > 0 {
-                    let da = dst_row[i + 3] as u32;
-                    let inv_sa = 255 - sa;
-                    // out_a = sa + da * (1 - sa/255), scaled to 0..255
-                    let out_a = sa + ((da * inv_sa + 127) / 255);
-                    if out_a > 0 {
-                        for c in 0..3 {
-                            let sc = src_row[i + c] as u32;
-                            let dc = dst_row[i + c] as u32;
-                            // Porter-Duff: (sc * sa + dc * da * inv_sa / 255) / out_a
-                            let num = sc * sa + ((dc * da * inv_sa + 127) / 255);
-                            dst_row[i + c] = ((num + out_a / 2) / out_a).min(255) as u8;
-                        }
-                        dst_row[i + 3] = out_a.min(255) as u8;
-                    }
-                }
-                // sa == 0: fully transparent source, dst unchanged
-            }
+            pixel_ops::composite_row(&src_data[src_start..src_start + row_bytes], dst_row);
         });
 }
 
-/// Blit a source clip directly onto an existing canvas, centered and aspect-ratio
-/// preserved (letterboxed). Only writes to the clip's actual pixel area — letterbox
-/// regions of the canvas are untouched. This avoids creating an intermediate
-/// canvas-sized buffer and prevents transparent letterbox pixels from erasing
-/// background content.
-pub fn blit_onto_canvas(clip: &FrameBuffer, canvas: &mut FrameBuffer) {
+/// Blit a source clip directly onto an existing canvas at `placement`,
+/// resampled with `quality`. Only writes to the clip's actual pixel area —
+/// letterbox regions of the canvas are untouched. This avoids creating an
+/// intermediate canvas-sized buffer and prevents transparent letterbox pixels
+/// from erasing background content.
+pub fn blit_onto_canvas(clip: &FrameBuffer, canvas: &mut FrameBuffer, placement: Placement, quality: ScaleQuality) {
     if clip.width == 0 || clip.height == 0 || canvas.width == 0 || canvas.height == 0 {
         return;
     }
 
     // Fast path: dimensions match exactly — copy all data
-    if clip.width == canvas.width && clip.height == canvas.height {
+    if covers_exactly(clip, placement, canvas.width, canvas.height) {
         canvas.data.copy_from_slice(&clip.data);
         return;
     }
 
-    // Compute fit: scale clip to fit canvas, preserving aspect ratio
-    let scale_x = canvas.width as f64 / clip.width as f64;
-    let scale_y = canvas.height as f64 / clip.height as f64;
-    let scale = scale_x.min(scale_y);
-
-    let dst_w = (clip.width as f64 * scale).round() as u32;
-    let dst_h = (clip.height as f64 * scale).round() as u32;
-
-    if dst_w == 0 || dst_h == 0 {
-        return;
-    }
-
-    // Center on canvas
-    let offset_x = (canvas.width.saturating_sub(dst_w)) / 2;
-    let offset_y = (canvas.height.saturating_sub(dst_h)) / 2;
-
-    let src_stride = clip.width as usize * 4;
-    let dst_stride = canvas.width as usize * 4;
-
-    // Nearest-neighbor blit using integer math
-    for dy in 0..dst_h {
-        let cy = offset_y + dy;
-        if cy >= canvas.height {
-            break;
-        }
-        let sy =
-            ((dy as u64 * clip.height as u64) / dst_h as u64).min(clip.height as u64 - 1) as u32;
-        let dst_row_offset = cy as usize * dst_stride;
-        let src_row_offset = sy as usize * src_stride;
-
-        for dx in 0..dst_w {
-            let cx = offset_x + dx;
-            if cx >= canvas.width {
-                break;
-            }
-            let sx = ((dx as u64 * clip.width as u64) / dst_w as u64)
-                .min(clip.width as u64 - 1) as u32;
-            let si = src_row_offset + sx as usize * 4;
-            let di = dst_row_offset + cx as usize * 4;
-            canvas.data[di..di + 4].copy_from_slice(&clip.data[si..si + 4]);
-        }
-    }
+    scaler::blit_scaled(clip, canvas, placement, quality);
 }
 
 /// Direct opaque overwrite: copy src onto dst. Both must have the same dimensions.
@@ -548,11 +628,16 @@ pub fn composite_opaque(src: &FrameBuffer, dst: &mut FrameBuffer) {
 mod tests {
     use super::*;
 
+    fn fit(clip: &FrameBuffer, canvas_w: u32, canvas_h: u32) -> Placement {
+        Placement::fit(clip.width, clip.height, canvas_w, canvas_h)
+    }
+
     fn dummy_ctx() -> EffectContext {
         EffectContext {
             time_secs: 0.0,
             frame_number: 0,
             fps: 30.0,
+            scale_quality: ScaleQuality::Nearest,
         }
     }
 
@@ -796,6 +881,74 @@ mod tests {
         assert!(effect.may_produce_transparency());
     }
 
+    // --- Denoise / Sharpen effect tests ---
+
+    fn render_ctx() -> EffectContext {
+        EffectContext {
+            scale_quality: ScaleQuality::Lanczos,
+            ..dummy_ctx()
+        }
+    }
+
+    /// 4x1 grey strip: mild grain on the left pair, a hard edge to white.
+    fn grainy_edge() -> FrameBuffer {
+        FrameBuffer::from_rgba_vec(4, 1, vec![
+            100, 100, 100, 255,   110, 110, 110, 255,   250, 250, 250, 255,   250, 250, 250, 200,
+        ])
+    }
+
+    #[test]
+    fn test_denoise_smooths_grain_but_keeps_edges() {
+        let effect = DenoiseEffect;
+        let params = vec![
+            ("strength".to_string(), ParameterValue::Float(0.5)),
+            ("radius".to_string(), ParameterValue::Float(1.0)),
+        ];
+        let result = effect.process(grainy_edge(), &params, &render_ctx());
+        assert_eq!(result.pixel(0, 0), &[105, 105, 105, 255]);
+        assert_eq!(result.pixel(1, 0), &[105, 105, 105, 255]);
+        assert_eq!(result.pixel(2, 0), &[250, 250, 250, 255]);
+        assert_eq!(result.pixel(3, 0)[3], 200);
+    }
+
+    #[test]
+    fn test_denoise_zero_strength_is_identity() {
+        let params = vec![("strength".to_string(), ParameterValue::Float(0.0))];
+        assert!(DenoiseEffect.is_identity(&params));
+        assert!(!DenoiseEffect.is_identity(&[]));
+    }
+
+    #[test]
+    fn test_sharpen_increases_edge_contrast() {
+        let effect = SharpenEffect;
+        let params = vec![
+            ("amount".to_string(), ParameterValue::Float(1.0)),
+            ("radius".to_string(), ParameterValue::Float(1.0)),
+        ];
+        let input = grainy_edge();
+        let result = effect.process(input.clone(), &params, &render_ctx());
+        assert!(result.pixel(1, 0)[0] < input.pixel(1, 0)[0]);
+        assert!(result.pixel(2, 0)[0] >= input.pixel(2, 0)[0]);
+        assert_eq!(result.pixel(3, 0)[3], 200);
+    }
+
+    #[test]
+    fn test_sharpen_zero_amount_is_identity() {
+        let params = vec![("amount".to_string(), ParameterValue::Float(0.0))];
+        assert!(SharpenEffect.is_identity(&params));
+    }
+
+    #[test]
+    fn test_draft_preview_uses_smallest_radius() {
+        let params = vec![("radius".to_string(), ParameterValue::Float(4.0))];
+        assert_eq!(effect_radius(&params, &render_ctx(), 1.0), 4);
+        let playback = EffectContext {
+            scale_quality: ScaleQuality::Bilinear,
+            ..dummy_ctx()
+        };
+        assert_eq!(effect_radius(&params, &playback, 1.0), 1);
+    }
+
     // --- EffectRegistry tests ---
 
     #[test]
@@ -805,6 +958,8 @@ mod tests {
         assert!(registry.get(&EffectType::Grayscale).is_some());
         assert!(registry.get(&EffectType::Brightness).is_some());
         assert!(registry.get(&EffectType::Opacity).is_some());
+        assert!(registry.get(&EffectType::Denoise).is_some());
+        assert!(registry.get(&EffectType::Sharpen).is_some());
     }
 
     // --- Pipeline tests ---
@@ -816,7 +971,8 @@ mod tests {
             255, 0, 0, 255,   0, 255, 0, 255,
             0, 0, 255, 255,   255, 255, 0, 255,
         ]);
-        let result = run_effect_pipeline(clip, 4, 4, &[], &registry, &dummy_ctx());
+        let placement = fit(&clip, 4, 4);
+        let result = run_effect_pipeline(clip, 4, 4, placement, &[], &registry, &dummy_ctx());
         assert_eq!(result.frame.width, 4);
         assert_eq!(result.frame.height, 4);
         assert!(!result.may_have_transparency);
@@ -835,11 +991,12 @@ mod tests {
         brightness.set_float("brightness", 0.5);
         let grayscale = EffectInstance::new(EffectType::Grayscale);
         let effects_1 = vec![brightness.clone(), grayscale.clone()];
-        let result_1 = run_effect_pipeline(clip.clone(), 1, 1, &effects_1, &registry, &dummy_ctx());
+        let placement = fit(&clip, 1, 1);
+        let result_1 = run_effect_pipeline(clip.clone(), 1, 1, placement, &effects_1, &registry, &dummy_ctx());
 
         // Order 2: Grayscale then Brightness
         let effects_2 = vec![grayscale, brightness];
-        let result_2 = run_effect_pipeline(clip, 1, 1, &effects_2, &registry, &dummy_ctx());
+        let result_2 = run_effect_pipeline(clip, 1, 1, placement, &effects_2, &registry, &dummy_ctx());
 
         // Results should differ (brightness applied to color vs gray gives different grayscale)
         assert_ne!(result_1.frame.data, result_2.frame.data);
@@ -855,12 +1012,46 @@ mod tests {
 
         // Transform at (0,0) should be skipped as identity
         let transform = EffectInstance::new(EffectType::Transform);
-        let result_with = run_effect_pipeline(clip.clone(), 2, 2, &[transform], &registry, &dummy_ctx());
+        let placement = fit(&clip, 2, 2);
+        let result_with = run_effect_pipeline(clip.clone(), 2, 2, placement, &[transform], &registry, &dummy_ctx());
 
-        let result_without = run_effect_pipeline(clip, 2, 2, &[], &registry, &dummy_ctx());
+        let result_without = run_effect_pipeline(clip, 2, 2, placement, &[], &registry, &dummy_ctx());
         assert_eq!(result_with.frame.data, result_without.frame.data);
     }
 
+    #[test]
+    fn test_layer_geometry_folds_transform_and_opacity() {
+        let registry = EffectRegistry::with_builtins();
+        let mut transform = EffectInstance::new(EffectType::Transform);
+        transform.set_float("x_offset", 10.4);
+        transform.set_float("y_offset", -3.0);
+        let mut opacity = EffectInstance::new(EffectType::Opacity);
+        opacity.set_float("opacity", 0.5);
+        let effects = vec![opacity.clone(), transform, opacity];
+
+        assert_eq!(
+            layer_geometry(&effects, &registry),
+            Some(LayerGeometry { x_offset: 10, y_offset: -3, opacity: 0.25 })
+        );
+        assert_eq!(
+            layer_geometry(&[], &registry),
+            Some(LayerGeometry { x_offset: 0, y_offset: 0, opacity: 1.0 })
+        );
+    }
+
+    #[test]
+    fn test_layer_geometry_rejects_pixel_effects() {
+        let registry = EffectRegistry::with_builtins();
+        let mut transform = EffectInstance::new(EffectType::Transform);
+        transform.set_float("x_offset", 5.0);
+
+        assert_eq!(layer_geometry(&[EffectInstance::new(EffectType::Grayscale)], &registry), None);
+        assert_eq!(layer_geometry(&[transform.clone(), transform.clone()], &registry), None);
+        // An identity Brightness is skipped like the pipeline skips it.
+        let effects = vec![EffectInstance::new(EffectType::Brightness), transform];
+        assert!(layer_geometry(&effects, &registry).is_some());
+    }
+
     #[test]
     fn test_pipeline_transparency_tracking() {
         let registry = EffectRegistry::with_builtins();
@@ -868,20 +1059,21 @@ mod tests {
 
         // Grayscale alone should not report transparency
         let effects = vec![EffectInstance::new(EffectType::Grayscale)];
-        let result = run_effect_pipeline(clip.clone(), 1, 1, &effects, &registry, &dummy_ctx());
+        let placement = fit(&clip, 1, 1);
+        let result = run_effect_pipeline(clip.clone(), 1, 1, placement, &effects, &registry, &dummy_ctx());
         assert!(!result.may_have_transparency);
 
         // Opacity (non-identity) should report transparency
         let mut opacity = EffectInstance::new(EffectType::Opacity);
         opacity.set_float("opacity", 0.5);
         let effects = vec![opacity];
-        let result = run_effect_pipeline(clip.clone(), 1, 1, &effects, &registry, &dummy_ctx());
+        let result = run_effect_pipeline(clip.clone(), 1, 1, placement, &effects, &registry, &dummy_ctx());
         assert!(result.may_have_transparency);
 
         // Opacity at identity (1.0) should NOT report transparency
         let opacity_id = EffectInstance::new(EffectType::Opacity);
         let effects = vec![opacity_id];
-        let result = run_effect_pipeline(clip, 1, 1, &effects, &registry, &dummy_ctx());
+        let result = run_effect_pipeline(clip, 1, 1, placement, &effects, &registry, &dummy_ctx());
         assert!(!result.may_have_transparency);
     }
 
@@ -893,7 +1085,8 @@ mod tests {
             10, 20, 30, 255,   40, 50, 60, 255,   70, 80, 90, 255,   100, 110, 120, 255,
         ]);
         let data_copy = clip.data.clone();
-        let canvas = blit_clip_to_canvas(clip, 4, 2);
+        let placement = fit(&clip, 4, 2);
+        let canvas = blit_clip_to_canvas(clip, 4, 2, placement, ScaleQuality::Nearest);
         assert_eq!(canvas.width, 4);
         assert_eq!(canvas.height, 2);
         assert_eq!(canvas.data, data_copy);
@@ -905,7 +1098,8 @@ mod tests {
         let clip = FrameBuffer::from_rgba_vec(2, 1, vec![
             255, 0, 0, 255,   0, 255, 0, 255,
         ]);
-        let canvas = blit_clip_to_canvas(clip, 4, 2);
+        let placement = fit(&clip, 4, 2);
+        let canvas = blit_clip_to_canvas(clip, 4, 2, placement, ScaleQuality::Nearest);
         assert_eq!(canvas.width, 4);
         assert_eq!(canvas.height, 2);
         // Clip should be scaled to fit (2x1 → 4x2 preserving aspect: 4x2)
@@ -922,7 +1116,8 @@ mod tests {
                 clip.pixel_mut(x, y).copy_from_slice(&[255, 0, 0, 255]);
             }
         }
-        let canvas = blit_clip_to_canvas(clip, 4, 4);
+        let placement = fit(&clip, 4, 4);
+        let canvas = blit_clip_to_canvas(clip, 4, 4, placement, ScaleQuality::Nearest);
         // Top and bottom rows should be transparent (letterboxed)
         assert_eq!(canvas.pixel(0, 0)[3], 0);
         assert_eq!(canvas.pixel(0, 3)[3], 0);
@@ -986,7 +1181,8 @@ mod tests {
             10, 20, 30, 255,   40, 50, 60, 255,   70, 80, 90, 255,   100, 110, 120, 255,
         ]);
         let mut canvas = FrameBuffer::new(4, 2);
-        blit_onto_canvas(&clip, &mut canvas);
+        let placement = fit(&clip, canvas.width, canvas.height);
+        blit_onto_canvas(&clip, &mut canvas, placement, ScaleQuality::Nearest);
         assert_eq!(canvas.data, clip.data);
     }
 
@@ -1004,7 +1200,8 @@ mod tests {
         for pixel in canvas.data.chunks_exact_mut(4) {
             pixel.copy_from_slice(&[0, 255, 0, 255]);
         }
-        blit_onto_canvas(&clip, &mut canvas);
+        let placement = fit(&clip, canvas.width, canvas.height);
+        blit_onto_canvas(&clip, &mut canvas, placement, ScaleQuality::Nearest);
         // Letterbox rows (top/bottom) should remain green (untouched)
         assert_eq!(canvas.pixel(0, 0), &[0, 255, 0, 255]);
         assert_eq!(canvas.pixel(0, 3), &[0, 255, 0, 255]);
@@ -1017,7 +1214,8 @@ mod tests {
     fn test_blit_onto_canvas_zero_dimensions() {
         let clip = FrameBuffer::new(0, 0);
         let mut canvas = FrameBuffer::new(4, 4);
-        blit_onto_canvas(&clip, &mut canvas);
+        let placement = fit(&clip, canvas.width, canvas.height);
+        blit_onto_canvas(&clip, &mut canvas, placement, ScaleQuality::Nearest);
         // Canvas unchanged
         assert!(canvas.data.iter().all(|&b| b == 0));
     }
@@ -1028,15 +1226,17 @@ mod tests {
     fn test_pipeline_fills_canvas_true_when_matching() {
         let registry = EffectRegistry::with_builtins();
         let clip = FrameBuffer::from_rgba_vec(4, 4, vec![255; 4 * 4 * 4]);
-        let result = run_effect_pipeline(clip, 4, 4, &[], &registry, &dummy_ctx());
+        let placement = fit(&clip, 4, 4);
+        let result = run_effect_pipeline(clip, 4, 4, placement, &[], &registry, &dummy_ctx());
         assert!(result.fills_canvas);
     }
 
     #[test]
     fn test_pipeline_fills_canvas_false_when_different() {
         let registry = EffectRegistry::with_builtins();
-        let clip = FrameBuffer::from_rgba_vec(2, 1, vec![255; 2 * 1 * 4]);
-        let result = run_effect_pipeline(clip, 4, 4, &[], &registry, &dummy_ctx());
+        let clip = FrameBuffer::from_rgba_vec(2, 1, vec![255; 2 * 4]);
+        let placement = fit(&clip, 4, 4);
+        let result = run_effect_pipeline(clip, 4, 4, placement, &[], &registry, &dummy_ctx());
         assert!(!result.fills_canvas);
     }
 
@@ -1050,7 +1250,8 @@ mod tests {
             100, 150, 200, 255,   100, 150, 200, 255,
         ]);
         let effects = vec![EffectInstance::new(EffectType::Grayscale)];
-        let result = run_effect_pipeline(clip, 2, 2, &effects, &registry, &dummy_ctx());
+        let placement = fit(&clip, 2, 2);
+        let result = run_effect_pipeline(clip, 2, 2, placement, &effects, &registry, &dummy_ctx());
         assert!(result.fills_canvas);
         assert!(!result.may_have_transparency);
     }
@@ -1063,7 +1264,8 @@ mod tests {
         let clip = FrameBuffer::from_rgba_vec(2, 2, vec![255; 2 * 2 * 4]);
         let mut opacity = EffectInstance::new(EffectType::Opacity);
         opacity.set_float("opacity", 0.5);
-        let result = run_effect_pipeline(clip, 2, 2, &[opacity], &registry, &dummy_ctx());
+        let placement = fit(&clip, 2, 2);
+        let result = run_effect_pipeline(clip, 2, 2, placement, &[opacity], &registry, &dummy_ctx());
         assert!(result.fills_canvas);
         assert!(result.may_have_transparency);
     }
@@ -0,0 +1,369 @@
+//! Per-row RGBA kernels behind the built-in effects and the compositor.
+//!
+//! Each kernel has a portable scalar version in [`scalar`] and an SSE2
+//! (x86_64) or NEON (aarch64) fast path. Both ISAs are part of their target's
+//! baseline, so the choice is made at compile time with no runtime detection.
+//! The SIMD paths produce exactly the same bytes as the scalar ones.
+
+/// Luma weights shared by every grayscale implementation (Rec. 601).
+const LUMA_R: f32 = 0.299;
+const LUMA_G: f32 = 0.587;
+const LUMA_B: f32 = 0.114;
+
+/// Replace RGB with luminance, leaving alpha untouched.
+pub fn grayscale_row(row: &mut [u8]) {
+    #[cfg(target_arch = "x86_64")]
+    {
+        // SAFETY: SSE2 is part of the x86_64 baseline.
+        let done = unsafe { sse2::grayscale(row) };
+        scalar::grayscale_row(&mut row[done..]);
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    scalar::grayscale_row(row);
+}
+
+/// Add `shift` to RGB with saturation, leaving alpha untouched.
+pub fn brightness_row(row: &mut [u8], shift: i16) {
+    let shift = shift.clamp(-255, 255);
+    #[cfg(target_arch = "x86_64")]
+    {
+        // SAFETY: SSE2 is part of the x86_64 baseline.
+        let done = unsafe { sse2::brightness(row, shift) };
+        scalar::brightness_row(&mut row[done..], shift);
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        // SAFETY: NEON is part of the aarch64 baseline.
+        let done = unsafe { neon::brightness(row, shift) };
+        scalar::brightness_row(&mut row[done..], shift);
+    }
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    scalar::brightness_row(row, shift);
+}
+
+/// Porter-Duff "over" of `src` onto `dst` (same length). Runs of fully opaque
+/// or fully transparent source pixels, the common case, are handled 4 at a time.
+pub fn composite_row(src: &[u8], dst: &mut [u8]) {
+    debug_assert_eq!(src.len(), dst.len());
+    let mut i = 0;
+    while i + 16 <= src.len() {
+        let block = &src[i..i + 16];
+        match block_alpha(block) {
+            BlockAlpha::Opaque => dst[i..i + 16].copy_from_slice(block),
+            BlockAlpha::Transparent => {}
+            BlockAlpha::Mixed => scalar::composite_row(block, &mut dst[i..i + 16]),
+        }
+        i += 16;
+    }
+    scalar::composite_row(&src[i..], &mut dst[i..]);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockAlpha {
+    Opaque,
+    Transparent,
+    Mixed,
+}
+
+/// Classify the alpha of 4 RGBA pixels.
+fn block_alpha(block: &[u8]) -> BlockAlpha {
+    #[cfg(target_arch = "x86_64")]
+    {
+        // SAFETY: SSE2 is part of the x86_64 baseline.
+        unsafe { sse2::block_alpha(block) }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        // SAFETY: NEON is part of the aarch64 baseline.
+        unsafe { neon::block_alpha(block) }
+    }
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    {
+        let alphas = [block[3], block[7], block[11], block[15]];
+        if alphas.iter().all(|&a| a == 255) {
+            BlockAlpha::Opaque
+        } else if alphas.iter().all(|&a| a == 0) {
+            BlockAlpha::Transparent
+        } else {
+            BlockAlpha::Mixed
+        }
+    }
+}
+
+/// Reference implementations, also used for the tails the SIMD paths leave over.
+pub mod scalar {
+    use super::{LUMA_B, LUMA_G, LUMA_R};
+
+    pub fn grayscale_row(row: &mut [u8]) {
+        for pixel in row.chunks_exact_mut(4) {
+            let r = pixel[0] as f32;
+            let g = pixel[1] as f32;
+            let b = pixel[2] as f32;
+            // Luma is never negative, so adding 0.5 and truncating rounds to nearest
+            let l = (LUMA_R * r + LUMA_G * g + LUMA_B * b + 0.5) as u8;
+            pixel[0] = l;
+            pixel[1] = l;
+            pixel[2] = l;
+        }
+    }
+
+    pub fn brightness_row(row: &mut [u8], shift: i16) {
+        for pixel in row.chunks_exact_mut(4) {
+            pixel[0] = (pixel[0] as i16 + shift).clamp(0, 255) as u8;
+            pixel[1] = (pixel[1] as i16 + shift).clamp(0, 255) as u8;
+            pixel[2] = (pixel[2] as i16 + shift).clamp(0, 255) as u8;
+        }
+    }
+
+    pub fn composite_row(src: &[u8], dst: &mut [u8]) {
+        for (s, d) in src.chunks_exact(4).zip(dst.chunks_exact_mut(4)) {
+            let sa = s[3] as u32;
+            if sa == 255 {
+                // Fully opaque source — just copy
+                d.copy_from_slice(s);
+            } else if sa > 0 {
+                let da = d[3] as u32;
+                let inv_sa = 255 - sa;
+                // out_a = sa + da * (1 - sa/255), scaled to 0..255
+                // Never zero here since sa > 0
+                let out_a = sa + ((da * inv_sa + 127) / 255);
+                for c in 0..3 {
+                    let sc = s[c] as u32;
+                    let dc = d[c] as u32;
+                    // Porter-Duff: (sc * sa + dc * da * inv_sa / 255) / out_a
+                    let num = sc * sa + ((dc * da * inv_sa + 127) / 255);
+                    d[c] = ((num + out_a / 2) / out_a).min(255) as u8;
+                }
+                d[3] = out_a.min(255) as u8;
+            }
+            // sa == 0: fully transparent source, dst unchanged
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod sse2 {
+    use std::arch::x86_64::*;
+
+    use super::{BlockAlpha, LUMA_B, LUMA_G, LUMA_R};
+
+    /// Bytes 3, 7, 11 and 15 of a `_mm_movemask_epi8` result: the alpha lanes.
+    const ALPHA_BITS: i32 = 0x8888;
+
+    #[target_feature(enable = "sse2")]
+    fn load(bytes: &[u8]) -> __m128i {
+        assert!(bytes.len() >= 16);
+        // SAFETY: at least 16 readable bytes; loadu has no alignment requirement.
+        unsafe { _mm_loadu_si128(bytes.as_ptr().cast()) }
+    }
+
+    #[target_feature(enable = "sse2")]
+    fn store(bytes: &mut [u8], value: __m128i) {
+        assert!(bytes.len() >= 16);
+        // SAFETY: at least 16 writable bytes; storeu has no alignment requirement.
+        unsafe { _mm_storeu_si128(bytes.as_mut_ptr().cast(), value) }
+    }
+
+    /// Processes whole 16-byte blocks; returns how many bytes were done.
+    #[target_feature(enable = "sse2")]
+    pub(super) fn grayscale(row: &mut [u8]) -> usize {
+        let byte_mask = _mm_set1_epi32(0xFF);
+        let alpha_mask = _mm_set1_epi32(0xFF00_0000_u32 as i32);
+        let (wr, wg, wb) = (
+            _mm_set1_ps(LUMA_R),
+            _mm_set1_ps(LUMA_G),
+            _mm_set1_ps(LUMA_B),
+        );
+        let half = _mm_set1_ps(0.5);
+        let blocks = row.len() / 16;
+        for chunk in row.chunks_exact_mut(16) {
+            let px = load(chunk);
+            let r = _mm_cvtepi32_ps(_mm_and_si128(px, byte_mask));
+            let g = _mm_cvtepi32_ps(_mm_and_si128(_mm_srli_epi32::<8>(px), byte_mask));
+            let b = _mm_cvtepi32_ps(_mm_and_si128(_mm_srli_epi32::<16>(px), byte_mask));
+            // Same operation order as the scalar path, so results match exactly
+            let l = _mm_add_ps(
+                _mm_add_ps(
+                    _mm_add_ps(_mm_mul_ps(wr, r), _mm_mul_ps(wg, g)),
+                    _mm_mul_ps(wb, b),
+                ),
+                half,
+            );
+            let l = _mm_cvttps_epi32(l);
+            let gray = _mm_or_si128(
+                _mm_or_si128(l, _mm_slli_epi32::<8>(l)),
+                _mm_slli_epi32::<16>(l),
+            );
+            store(chunk, _mm_or_si128(gray, _mm_and_si128(px, alpha_mask)));
+        }
+        blocks * 16
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) fn brightness(row: &mut [u8], shift: i16) -> usize {
+        let amount = shift.unsigned_abs() as u8;
+        // Zero in the alpha lanes leaves alpha untouched by the saturating ops
+        let delta = _mm_set1_epi32(i32::from_le_bytes([amount, amount, amount, 0]));
+        let blocks = row.len() / 16;
+        for chunk in row.chunks_exact_mut(16) {
+            let px = load(chunk);
+            let out = if shift >= 0 {
+                _mm_adds_epu8(px, delta)
+            } else {
+                _mm_subs_epu8(px, delta)
+            };
+            store(chunk, out);
+        }
+        blocks * 16
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) fn block_alpha(block: &[u8]) -> BlockAlpha {
+        let px = load(block);
+        let opaque = _mm_movemask_epi8(_mm_cmpeq_epi8(px, _mm_set1_epi8(-1))) & ALPHA_BITS;
+        if opaque == ALPHA_BITS {
+            return BlockAlpha::Opaque;
+        }
+        let clear = _mm_movemask_epi8(_mm_cmpeq_epi8(px, _mm_setzero_si128())) & ALPHA_BITS;
+        if clear == ALPHA_BITS {
+            BlockAlpha::Transparent
+        } else {
+            BlockAlpha::Mixed
+        }
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod neon {
+    use std::arch::aarch64::*;
+
+    use super::BlockAlpha;
+
+    const ALPHA_LANES: [u8; 16] = [0, 0, 0, 255, 0, 0, 0, 255, 0, 0, 0, 255, 0, 0, 0, 255];
+
+    #[target_feature(enable = "neon")]
+    fn load(bytes: &[u8]) -> uint8x16_t {
+        assert!(bytes.len() >= 16);
+        // SAFETY: at least 16 readable bytes; vld1q_u8 has no alignment requirement.
+        unsafe { vld1q_u8(bytes.as_ptr()) }
+    }
+
+    #[target_feature(enable = "neon")]
+    fn store(bytes: &mut [u8], value: uint8x16_t) {
+        assert!(bytes.len() >= 16);
+        // SAFETY: at least 16 writable bytes; vst1q_u8 has no alignment requirement.
+        unsafe { vst1q_u8(bytes.as_mut_ptr(), value) }
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) fn brightness(row: &mut [u8], shift: i16) -> usize {
+        let amount = shift.unsigned_abs() as u8;
+        let rgb = [amount, amount, amount, 0];
+        let lanes: [u8; 16] = std::array::from_fn(|i| rgb[i % 4]);
+        let delta = load(&lanes);
+        let blocks = row.len() / 16;
+        for chunk in row.chunks_exact_mut(16) {
+            let px = load(chunk);
+            let out = if shift >= 0 {
+                vqaddq_u8(px, delta)
+            } else {
+                vqsubq_u8(px, delta)
+            };
+            store(chunk, out);
+        }
+        blocks * 16
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) fn block_alpha(block: &[u8]) -> BlockAlpha {
+        let px = load(block);
+        let alpha_mask = load(&ALPHA_LANES);
+        let alpha = vandq_u8(px, alpha_mask);
+        if vmaxvq_u8(alpha) == 0 {
+            return BlockAlpha::Transparent;
+        }
+        // Force the color lanes to 255 so only alpha can pull the minimum down
+        if vminvq_u8(vorrq_u8(px, vmvnq_u8(alpha_mask))) == 255 {
+            BlockAlpha::Opaque
+        } else {
+            BlockAlpha::Mixed
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random bytes, with some fully opaque and fully
+    /// transparent pixels mixed in to exercise the composite fast paths.
+    fn test_pixels(len: usize, seed: u32) -> Vec<u8> {
+        let mut state = seed;
+        let mut data: Vec<u8> = (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (state >> 24) as u8
+            })
+            .collect();
+        for (i, pixel) in data.chunks_exact_mut(4).enumerate() {
+            match i % 7 {
+                0..=2 => pixel[3] = 255,
+                3 => pixel[3] = 0,
+                _ => {}
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn test_grayscale_matches_scalar() {
+        // 37 pixels: several SIMD blocks plus a scalar tail
+        let mut fast = test_pixels(37 * 4, 1);
+        let mut reference = fast.clone();
+        grayscale_row(&mut fast);
+        scalar::grayscale_row(&mut reference);
+        assert_eq!(fast, reference);
+    }
+
+    #[test]
+    fn test_grayscale_known_values() {
+        let mut row = vec![255, 0, 0, 200, 255, 255, 255, 255];
+        grayscale_row(&mut row);
+        assert_eq!(row, vec![76, 76, 76, 200, 255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn test_brightness_matches_scalar() {
+        for shift in [-255, -64, -1, 1, 64, 255] {
+            let mut fast = test_pixels(37 * 4, 2);
+            let mut reference = fast.clone();
+            brightness_row(&mut fast, shift);
+            scalar::brightness_row(&mut reference, shift);
+            assert_eq!(fast, reference, "shift {shift}");
+        }
+    }
+
+    #[test]
+    fn test_composite_matches_scalar() {
+        let src = test_pixels(61 * 4, 3);
+        let mut fast = test_pixels(61 * 4, 4);
+        let mut reference = fast.clone();
+        composite_row(&src, &mut fast);
+        scalar::composite_row(&src, &mut reference);
+        assert_eq!(fast, reference);
+    }
+
+    #[test]
+    fn test_block_alpha_classification() {
+        let opaque = [10, 20, 30, 255].repeat(4);
+        assert_eq!(block_alpha(&opaque), BlockAlpha::Opaque);
+        let clear = [10, 20, 30, 0].repeat(4);
+        assert_eq!(block_alpha(&clear), BlockAlpha::Transparent);
+        let mut mixed = opaque.clone();
+        mixed[7] = 128;
+        assert_eq!(block_alpha(&mixed), BlockAlpha::Mixed);
+        // Color bytes of 255 must not count as opaque alpha
+        let white_clear = [255, 255, 255, 0].repeat(4);
+        assert_eq!(block_alpha(&white_clear), BlockAlpha::Transparent);
+    }
+}
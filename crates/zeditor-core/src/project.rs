@@ -3,11 +3,14 @@ use std::path::Path;
 
 use semver::Version;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
-use crate::commands::CommandHistory;
+use crate::commands::{CommandHistory, LibraryChange};
 use crate::error::{CoreError, Result};
-use crate::media::SourceLibrary;
-use crate::timeline::{Timeline, TrackType};
+use crate::media::{MediaAsset, MediaKind, SourceLibrary};
+use crate::render_ranges::RenderRange;
+use crate::snapshots::{self, ClipChange, TimelineSnapshot};
+use crate::timeline::{TimeRange, Timeline, TrackType};
 
 /// Project-level settings defining the editing canvas and default framerate.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -27,6 +30,87 @@ impl Default for ProjectSettings {
     }
 }
 
+impl ProjectSettings {
+    /// These settings with the canvas reshaped to `preset`, keeping the
+    /// current short side (so a 4K project stays 4K).
+    pub fn with_canvas_preset(&self, preset: CanvasPreset) -> Self {
+        let (canvas_width, canvas_height) = preset.canvas_size(self.canvas_width.min(self.canvas_height));
+        Self {
+            canvas_width,
+            canvas_height,
+            ..self.clone()
+        }
+    }
+}
+
+/// Common delivery shapes: widescreen, and the vertical, square and
+/// portrait frames social platforms expect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CanvasPreset {
+    Widescreen,
+    Vertical,
+    Square,
+    Portrait,
+}
+
+impl CanvasPreset {
+    pub const ALL: [CanvasPreset; 4] = [
+        CanvasPreset::Widescreen,
+        CanvasPreset::Vertical,
+        CanvasPreset::Square,
+        CanvasPreset::Portrait,
+    ];
+
+    /// Width and height of the aspect ratio, e.g. (9, 16).
+    pub fn ratio(self) -> (u32, u32) {
+        match self {
+            CanvasPreset::Widescreen => (16, 9),
+            CanvasPreset::Vertical => (9, 16),
+            CanvasPreset::Square => (1, 1),
+            CanvasPreset::Portrait => (4, 5),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CanvasPreset::Widescreen => "16:9",
+            CanvasPreset::Vertical => "9:16",
+            CanvasPreset::Square => "1:1",
+            CanvasPreset::Portrait => "4:5",
+        }
+    }
+
+    /// Canvas size with `short_side` pixels on the shorter edge. Both edges
+    /// are rounded to even numbers, which 4:2:0 encoding needs.
+    pub fn canvas_size(self, short_side: u32) -> (u32, u32) {
+        let (w, h) = self.ratio();
+        let short = (short_side / 2 * 2).max(2);
+        let long = |long_ratio: u32, short_ratio: u32| {
+            let exact = short as f64 * long_ratio as f64 / short_ratio as f64;
+            (exact / 2.0).round() as u32 * 2
+        };
+        if w >= h {
+            (long(w, h), short)
+        } else {
+            (short, long(h, w))
+        }
+    }
+
+    /// The preset whose aspect ratio the given canvas has, if any.
+    pub fn matching(width: u32, height: u32) -> Option<CanvasPreset> {
+        Self::ALL.into_iter().find(|preset| {
+            let (w, h) = preset.ratio();
+            width as u64 * h as u64 == height as u64 * w as u64
+        })
+    }
+
+    /// The preset after this one, for cycling through them.
+    pub fn next(self) -> CanvasPreset {
+        let index = Self::ALL.iter().position(|p| *p == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
 /// Current version written into new save files.
 pub const CURRENT_PROJECT_VERSION: &str = "1.0.0";
 
@@ -41,6 +125,38 @@ pub struct ProjectFile {
     pub project: Project,
 }
 
+impl ProjectFile {
+    /// Serialize and write atomically: the JSON goes to a temporary file next
+    /// to `path`, which is then renamed over it, so a crash mid-save never
+    /// leaves a truncated project.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+        tmp_name.push(".tmp");
+        let tmp = path.with_file_name(tmp_name);
+        fs::write(&tmp, json)?;
+        if let Err(e) = fs::rename(&tmp, path) {
+            fs::remove_file(&tmp).ok();
+            return Err(e.into());
+        }
+        Ok(())
+    }
+}
+
+/// Frame rates closer than this count as the same when replacing media or
+/// conforming clips.
+const FPS_TOLERANCE: f64 = 0.01;
+
+/// How swapping an asset's media for another file affects the timeline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaReplacement {
+    /// The new file's frame rate differs from the old one's, so frame-based
+    /// edit points may land between frames.
+    pub fps_changed: bool,
+    /// Clips, as `(track index, clip id)`, reading past the end of the new file.
+    pub clips_past_end: Vec<(usize, Uuid)>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Project {
     pub name: String,
@@ -48,6 +164,12 @@ pub struct Project {
     pub source_library: SourceLibrary,
     #[serde(default)]
     pub settings: ProjectSettings,
+    /// Named timeline snapshots, oldest first.
+    #[serde(default)]
+    pub snapshots: Vec<TimelineSnapshot>,
+    /// Timeline ranges flagged for batch export, in the order added.
+    #[serde(default)]
+    pub render_ranges: Vec<RenderRange>,
     #[serde(skip)]
     pub command_history: CommandHistory,
 }
@@ -63,18 +185,211 @@ impl Project {
             timeline,
             source_library: SourceLibrary::new(),
             settings: ProjectSettings::default(),
+            snapshots: Vec::new(),
+            render_ranges: Vec::new(),
             command_history: CommandHistory::new(),
         }
     }
 
+    /// Add `asset` to the source library as an undoable command.
+    pub fn import_asset(&mut self, asset: MediaAsset) {
+        let _ = self.command_history.execute_with_library(
+            &mut self.timeline,
+            &mut self.source_library,
+            "Import media",
+            |_, library| {
+                let index = library.len();
+                library.import(asset.clone());
+                Ok(((), LibraryChange::Added { index, asset }))
+            },
+        );
+    }
+
+    /// Remove an asset and every clip using it as one undoable command.
+    pub fn remove_asset(&mut self, asset_id: Uuid) -> Result<MediaAsset> {
+        self.command_history.execute_with_library(
+            &mut self.timeline,
+            &mut self.source_library,
+            "Remove media",
+            |timeline, library| {
+                let index = library.position(asset_id).ok_or(CoreError::AssetNotFound(asset_id))?;
+                let asset = library.remove(asset_id)?;
+                timeline.remove_clips_by_asset(asset_id);
+                Ok((asset.clone(), LibraryChange::Removed { index, asset }))
+            },
+        )
+    }
+
+    /// Undo the last command, restoring the source library too if it
+    /// changed it.
+    pub fn undo(&mut self) -> Result<()> {
+        self.command_history.undo_with_library(&mut self.timeline, &mut self.source_library)
+    }
+
+    /// Redo the last undone command, library changes included.
+    pub fn redo(&mut self) -> Result<()> {
+        self.command_history.redo_with_library(&mut self.timeline, &mut self.source_library)
+    }
+
     pub fn save(&self, path: &Path) -> Result<()> {
-        let file = ProjectFile {
+        self.save_snapshot().write(path)
+    }
+
+    /// Everything that gets saved, without the undo history, so it can be
+    /// written from a background thread while editing continues.
+    pub fn save_snapshot(&self) -> ProjectFile {
+        ProjectFile {
             version: CURRENT_PROJECT_VERSION.to_string(),
-            project: self.clone(),
+            project: Project {
+                name: self.name.clone(),
+                timeline: self.timeline.clone(),
+                source_library: self.source_library.clone(),
+                settings: self.settings.clone(),
+                snapshots: self.snapshots.clone(),
+                render_ranges: self.render_ranges.clone(),
+                command_history: CommandHistory::new(),
+            },
+        }
+    }
+
+    /// Check `replacement` (a freshly probed file) as new media for asset
+    /// `asset_id`, without changing anything. Fails if the file can't stand
+    /// in for the asset: a different kind of media, or no audio when the
+    /// timeline uses the asset's audio.
+    pub fn check_media_replacement(&self, asset_id: Uuid, replacement: &MediaAsset) -> Result<MediaReplacement> {
+        let asset = self
+            .source_library
+            .get(asset_id)
+            .ok_or(CoreError::AssetNotFound(asset_id))?;
+        if asset.kind() != replacement.kind() {
+            return Err(CoreError::IncompatibleMedia(format!(
+                "expected {:?} media, got {:?}",
+                asset.kind(),
+                replacement.kind()
+            )));
+        }
+        let clips = self.timeline.clips_using_asset(asset_id);
+        let uses_audio = clips.iter().any(|(track, _)| {
+            self.timeline
+                .tracks
+                .get(*track)
+                .is_some_and(|t| t.track_type == TrackType::Audio)
+        });
+        if uses_audio && !replacement.has_audio {
+            return Err(CoreError::IncompatibleMedia(
+                "the timeline uses its audio but the new file has none".into(),
+            ));
+        }
+
+        let fps_changed = asset.fps > 0.0
+            && replacement.fps > 0.0
+            && (asset.fps - replacement.fps).abs() > FPS_TOLERANCE;
+        // Stills have no duration and can be held for any length
+        let clips_past_end = if replacement.kind() == MediaKind::Image {
+            Vec::new()
+        } else {
+            clips
+                .into_iter()
+                .filter(|(track, clip_id)| {
+                    self.timeline
+                        .tracks
+                        .get(*track)
+                        .and_then(|t| t.clips.iter().find(|c| c.id == *clip_id))
+                        .is_some_and(|c| c.source_range.end.as_duration() > replacement.duration)
+                })
+                .collect()
         };
-        let json = serde_json::to_string_pretty(&file)?;
-        fs::write(path, json)?;
-        Ok(())
+        Ok(MediaReplacement {
+            fps_changed,
+            clips_past_end,
+        })
+    }
+
+    /// Whether footage of asset `asset_id` runs at a different frame rate than
+    /// the project, so its clips' rate conform makes a difference. Stills and
+    /// assets with an unknown rate never do.
+    pub fn has_mismatched_fps(&self, asset_id: Uuid) -> bool {
+        self.source_library.get(asset_id).is_some_and(|asset| {
+            asset.kind() == MediaKind::Video
+                && asset.fps > 0.0
+                && (asset.fps - self.settings.fps).abs() > FPS_TOLERANCE
+        })
+    }
+
+    /// Repoint asset `asset_id`, and with it every clip using it, at the
+    /// probed `replacement` file. The asset keeps its id, name, bin and
+    /// sub-clips; everything derived from the old file (shots, transcript) is
+    /// taken from `replacement`. Clips keep their source ranges even past the new end, so
+    /// the returned [`MediaReplacement`] should be shown to the user.
+    pub fn replace_media(&mut self, asset_id: Uuid, replacement: MediaAsset) -> Result<MediaReplacement> {
+        let report = self.check_media_replacement(asset_id, &replacement)?;
+        let asset = self
+            .source_library
+            .get_mut(asset_id)
+            .ok_or(CoreError::AssetNotFound(asset_id))?;
+        *asset = MediaAsset {
+            id: asset.id,
+            name: std::mem::take(&mut asset.name),
+            bin: asset.bin.take(),
+            subclips: std::mem::take(&mut asset.subclips),
+            ..replacement
+        };
+        Ok(report)
+    }
+
+    /// Save a copy of the current timeline under `name`, returning its id.
+    pub fn take_snapshot(&mut self, name: impl Into<String>) -> Uuid {
+        let snapshot = TimelineSnapshot::new(name, &self.timeline);
+        let id = snapshot.id;
+        self.snapshots.push(snapshot);
+        id
+    }
+
+    pub fn snapshot(&self, id: Uuid) -> Option<&TimelineSnapshot> {
+        self.snapshots.iter().find(|s| s.id == id)
+    }
+
+    pub fn remove_snapshot(&mut self, id: Uuid) -> Result<TimelineSnapshot> {
+        let index = self
+            .snapshots
+            .iter()
+            .position(|s| s.id == id)
+            .ok_or(CoreError::SnapshotNotFound(id))?;
+        Ok(self.snapshots.remove(index))
+    }
+
+    /// What changed in the timeline since snapshot `id` was taken.
+    pub fn diff_snapshot(&self, id: Uuid) -> Result<Vec<ClipChange>> {
+        let snapshot = self.snapshot(id).ok_or(CoreError::SnapshotNotFound(id))?;
+        Ok(snapshots::diff_timelines(&snapshot.timeline, &self.timeline))
+    }
+
+    /// Replace the timeline with snapshot `id`, as one undoable command. The
+    /// snapshot itself is kept.
+    pub fn restore_snapshot(&mut self, id: Uuid) -> Result<()> {
+        let snapshot = self.snapshot(id).ok_or(CoreError::SnapshotNotFound(id))?;
+        let (description, timeline) = (format!("Restore snapshot {}", snapshot.name), snapshot.timeline.clone());
+        self.command_history.execute(&mut self.timeline, &description, |current| {
+            *current = timeline;
+            Ok(())
+        })
+    }
+
+    /// Flag `range` of the timeline for export under `name`, returning its id.
+    pub fn add_render_range(&mut self, name: impl Into<String>, range: TimeRange) -> Uuid {
+        let render_range = RenderRange::new(name, range);
+        let id = render_range.id;
+        self.render_ranges.push(render_range);
+        id
+    }
+
+    pub fn remove_render_range(&mut self, id: Uuid) -> Result<RenderRange> {
+        let index = self
+            .render_ranges
+            .iter()
+            .position(|r| r.id == id)
+            .ok_or(CoreError::RenderRangeNotFound(id))?;
+        Ok(self.render_ranges.remove(index))
     }
 
     pub fn load(path: &Path) -> Result<Self> {
@@ -123,6 +438,8 @@ impl PartialEq for Project {
             && self.timeline == other.timeline
             && self.source_library == other.source_library
             && self.settings == other.settings
+            && self.snapshots == other.snapshots
+            && self.render_ranges == other.render_ranges
     }
 }
 
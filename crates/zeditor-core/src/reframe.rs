@@ -0,0 +1,93 @@
+//! Reframing: where a clip filled onto a narrower canvas (a 16:9 shot in a
+//! 9:16 project) is cropped, as a path of horizontal pan keys over source
+//! time. Keys live in source time so trims, cuts and moves keep them lined up
+//! with the picture.
+
+use serde::{Deserialize, Serialize};
+
+use crate::scaler::Placement;
+
+/// The horizontal point of the frame, as a fraction of its width, kept at
+/// the canvas center `source_secs` into the source.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PanKey {
+    pub source_secs: f64,
+    pub center_x: f64,
+}
+
+/// Pan keys of a clip, ordered by source time. Empty for a centered crop.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PanPath {
+    pub keys: Vec<PanKey>,
+}
+
+impl PanPath {
+    /// Path through `samples` of `(source_secs, center_x)`, dropping samples
+    /// a straight line between their neighbours already passes within
+    /// `tolerance` of.
+    pub fn simplified(samples: &[(f64, f64)], tolerance: f64) -> Self {
+        let mut keys: Vec<PanKey> = Vec::new();
+        for (i, &(source_secs, center_x)) in samples.iter().enumerate() {
+            let redundant = match (keys.last(), samples.get(i + 1)) {
+                (Some(prev), Some(&(next_secs, next_x))) if next_secs > prev.source_secs => {
+                    let t = (source_secs - prev.source_secs) / (next_secs - prev.source_secs);
+                    let on_line = prev.center_x + (next_x - prev.center_x) * t;
+                    (on_line - center_x).abs() <= tolerance
+                }
+                _ => false,
+            };
+            if !redundant {
+                keys.push(PanKey { source_secs, center_x });
+            }
+        }
+        Self { keys }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Interpolated center at `source_secs`, held before the first key and
+    /// after the last; `None` without keys.
+    pub fn center_at(&self, source_secs: f64) -> Option<f64> {
+        let first = self.keys.first()?;
+        let next = self.keys.partition_point(|k| k.source_secs <= source_secs);
+        if next == 0 {
+            return Some(first.center_x);
+        }
+        let prev = self.keys[next - 1];
+        let Some(next) = self.keys.get(next) else {
+            return Some(prev.center_x);
+        };
+        let t = (source_secs - prev.source_secs) / (next.source_secs - prev.source_secs);
+        Some(prev.center_x + (next.center_x - prev.center_x) * t)
+    }
+
+    /// `placement` on a `dst_w` wide canvas slid sideways so the frame's
+    /// center at `source_secs` sits mid-canvas, without uncovering the
+    /// canvas edges. Placements no wider than the canvas are left alone.
+    pub fn apply(&self, placement: Placement, dst_w: u32, source_secs: f64) -> Placement {
+        let Some(center_x) = self.center_at(source_secs) else {
+            return placement;
+        };
+        if placement.width <= dst_w {
+            return placement;
+        }
+        let x = dst_w as f64 / 2.0 - center_x * placement.width as f64;
+        Placement {
+            x: x.round().clamp((dst_w as i64 - placement.width as i64) as f64, 0.0) as i32,
+            ..placement
+        }
+    }
+}
+
+/// Share of a frame's width left visible when it fills a canvas of the
+/// given size (1.0 when the canvas is at least as wide in proportion).
+pub fn visible_width_fraction(frame_w: u32, frame_h: u32, canvas_w: u32, canvas_h: u32) -> f64 {
+    if frame_w == 0 || frame_h == 0 || canvas_h == 0 {
+        return 1.0;
+    }
+    let canvas_aspect = canvas_w as f64 / canvas_h as f64;
+    let frame_aspect = frame_w as f64 / frame_h as f64;
+    (canvas_aspect / frame_aspect).min(1.0)
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::timeline::TimeRange;
+
+/// File name pattern used when none is set: `{project}`, `{name}` and
+/// `{index}` are replaced per range.
+pub const DEFAULT_FILE_PATTERN: &str = "{project}-{name}";
+
+/// A flagged sub-range of the timeline to export on its own (e.g. one of
+/// several social clips cut from the same edit).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RenderRange {
+    pub id: Uuid,
+    pub name: String,
+    pub range: TimeRange,
+}
+
+impl RenderRange {
+    pub fn new(name: impl Into<String>, range: TimeRange) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: name.into(),
+            range,
+        }
+    }
+}
+
+/// Output file name (without extension) for the `index`th (0-based) range
+/// of a batch export. `{index}` is 1-based and zero-padded to two digits.
+/// Characters that are not allowed in file names become `_`, and an empty
+/// result falls back to the index.
+pub fn file_name(pattern: &str, project_name: &str, range_name: &str, index: usize) -> String {
+    let name = pattern
+        .replace("{project}", project_name)
+        .replace("{name}", range_name)
+        .replace("{index}", &format!("{:02}", index + 1));
+    let name: String = name
+        .trim()
+        .chars()
+        .map(|c| if matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') || c.is_control() { '_' } else { c })
+        .collect();
+    if name.is_empty() {
+        format!("{:02}", index + 1)
+    } else {
+        name
+    }
+}
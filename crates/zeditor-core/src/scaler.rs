@@ -0,0 +1,446 @@
+//! RGBA resampling shared by the preview compositor, the renderer and thumbnails.
+//!
+//! Bilinear and Lanczos are separable filters applied in premultiplied alpha, so
+//! transparent pixels don't bleed dark fringes into their neighbours. When
+//! downscaling, the kernels are stretched over the covered source area, which
+//! makes both of them area-averaging instead of aliasing.
+
+use std::f64::consts::PI;
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::pipeline::FrameBuffer;
+
+/// Resampling filter used whenever a frame is drawn at a size other than its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScaleQuality {
+    /// Cheapest; aliases and shimmers on any non-integer ratio.
+    Nearest,
+    /// Smooth at a small cost; used during playback.
+    #[default]
+    Bilinear,
+    /// Lanczos-3, the sharpest; used for paused preview, thumbnails and render.
+    Lanczos,
+}
+
+/// Where a scaled image lands on the destination, in destination pixels.
+/// May hang off any edge; only the overlapping part is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Placement {
+    /// The largest rectangle with the source's aspect ratio that fits in
+    /// `dst_w`×`dst_h`, centered (letterboxed or pillarboxed).
+    pub fn fit(src_w: u32, src_h: u32, dst_w: u32, dst_h: u32) -> Self {
+        if src_w == 0 || src_h == 0 {
+            return Self {
+                x: 0,
+                y: 0,
+                width: 0,
+                height: 0,
+            };
+        }
+        let scale = (dst_w as f64 / src_w as f64).min(dst_h as f64 / src_h as f64);
+        let width = (src_w as f64 * scale).round() as u32;
+        let height = (src_h as f64 * scale).round() as u32;
+        Self {
+            x: (dst_w.saturating_sub(width) / 2) as i32,
+            y: (dst_h.saturating_sub(height) / 2) as i32,
+            width,
+            height,
+        }
+    }
+}
+
+/// How a clip's frame is sized onto the canvas.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CanvasFit {
+    /// The largest size that shows the whole frame, letterboxed or pillarboxed.
+    #[default]
+    Fit,
+    /// The smallest size that covers the canvas, cropping the overflow.
+    Fill,
+    /// Exactly the canvas, ignoring the frame's aspect ratio.
+    Stretch,
+    /// One source pixel per canvas pixel, centered.
+    Original,
+}
+
+impl CanvasFit {
+    pub const ALL: [CanvasFit; 4] = [Self::Fit, Self::Fill, Self::Stretch, Self::Original];
+
+    /// Short label for the inspector buttons.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Fit => "Fit",
+            Self::Fill => "Fill",
+            Self::Stretch => "Stretch",
+            Self::Original => "1:1",
+        }
+    }
+
+    /// Where a decoded `frame_w`×`frame_h` frame lands on a `dst_w`×`dst_h`
+    /// destination. `original` is the source's full frame size in destination
+    /// pixels, used by [`CanvasFit::Original`] since decoders may hand over a
+    /// shrunk frame; `(0, 0)` falls back to the frame's own size.
+    pub fn placement(self, frame_w: u32, frame_h: u32, original: (u32, u32), dst_w: u32, dst_h: u32) -> Placement {
+        let centered = |width: u32, height: u32| Placement {
+            x: ((dst_w as i64 - width as i64) / 2) as i32,
+            y: ((dst_h as i64 - height as i64) / 2) as i32,
+            width,
+            height,
+        };
+        match self {
+            Self::Fit => Placement::fit(frame_w, frame_h, dst_w, dst_h),
+            Self::Fill if frame_w == 0 || frame_h == 0 => centered(0, 0),
+            Self::Fill => {
+                let scale = (dst_w as f64 / frame_w as f64).max(dst_h as f64 / frame_h as f64);
+                centered((frame_w as f64 * scale).round() as u32, (frame_h as f64 * scale).round() as u32)
+            }
+            Self::Stretch => Placement {
+                x: 0,
+                y: 0,
+                width: dst_w,
+                height: dst_h,
+            },
+            Self::Original if original.0 == 0 || original.1 == 0 => centered(frame_w, frame_h),
+            Self::Original => centered(original.0, original.1),
+        }
+    }
+}
+
+/// Resample `src` to `width`×`height`.
+pub fn scale_rgba(
+    src: &FrameBuffer,
+    width: u32,
+    height: u32,
+    quality: ScaleQuality,
+) -> FrameBuffer {
+    if width == 0 || height == 0 || src.width == 0 || src.height == 0 {
+        return FrameBuffer::new(width, height);
+    }
+    if src.width == width && src.height == height {
+        return src.clone();
+    }
+    match quality {
+        ScaleQuality::Nearest => {
+            let mut out = FrameBuffer::new(width, height);
+            let placement = Placement {
+                x: 0,
+                y: 0,
+                width,
+                height,
+            };
+            nearest_blit(src, &mut out, placement);
+            out
+        }
+        ScaleQuality::Bilinear => resample(src, width, height, Filter::Triangle),
+        ScaleQuality::Lanczos => resample(src, width, height, Filter::Lanczos3),
+    }
+}
+
+/// Scale `src` into `placement` on `dst`, overwriting the covered pixels.
+/// Pixels of `dst` outside the placement are left untouched.
+pub fn blit_scaled(
+    src: &FrameBuffer,
+    dst: &mut FrameBuffer,
+    placement: Placement,
+    quality: ScaleQuality,
+) {
+    if src.width == 0 || src.height == 0 || placement.width == 0 || placement.height == 0 {
+        return;
+    }
+    let same_size = src.width == placement.width && src.height == placement.height;
+    if quality == ScaleQuality::Nearest || same_size {
+        // Nearest at 1:1 is an exact copy, so no intermediate buffer is needed
+        nearest_blit(src, dst, placement);
+        return;
+    }
+    let scaled = scale_rgba(src, placement.width, placement.height, quality);
+    nearest_blit(&scaled, dst, placement);
+}
+
+/// Nearest-neighbor scale of `src` into `placement` on `dst`. Source columns
+/// are mapped once up front and rows run in parallel, so the inner loop is just
+/// a gather of 4-byte pixels.
+fn nearest_blit(src: &FrameBuffer, dst: &mut FrameBuffer, placement: Placement) {
+    // Visible region in dst coords
+    let x0 = placement.x.max(0) as i64;
+    let y0 = placement.y.max(0) as i64;
+    let x1 = (placement.x as i64 + placement.width as i64).min(dst.width as i64);
+    let y1 = (placement.y as i64 + placement.height as i64).min(dst.height as i64);
+    if x0 >= x1 || y0 >= y1 {
+        return;
+    }
+
+    let src_stride = src.width as usize * 4;
+    let dst_stride = dst.width as usize * 4;
+
+    // Integer math to avoid f64 division per pixel
+    let src_cols: Vec<usize> = (x0..x1)
+        .map(|dx| {
+            let local_x = (dx - placement.x as i64) as u64;
+            ((local_x * src.width as u64) / placement.width as u64).min(src.width as u64 - 1)
+                as usize
+                * 4
+        })
+        .collect();
+    let x_start = x0 as usize * 4;
+    let x_end = x1 as usize * 4;
+
+    dst.data[y0 as usize * dst_stride..y1 as usize * dst_stride]
+        .par_chunks_exact_mut(dst_stride)
+        .enumerate()
+        .for_each(|(row, dst_row)| {
+            let local_y = (y0 + row as i64 - placement.y as i64) as u64;
+            let sy = ((local_y * src.height as u64) / placement.height as u64)
+                .min(src.height as u64 - 1) as usize;
+            let src_row = &src.data[sy * src_stride..(sy + 1) * src_stride];
+            for (dst_px, &sx) in dst_row[x_start..x_end].chunks_exact_mut(4).zip(&src_cols) {
+                dst_px.copy_from_slice(&src_row[sx..sx + 4]);
+            }
+        });
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Filter {
+    Triangle,
+    Lanczos3,
+}
+
+impl Filter {
+    /// Kernel radius in source pixels at 1:1.
+    fn support(self) -> f64 {
+        match self {
+            Self::Triangle => 1.0,
+            Self::Lanczos3 => 3.0,
+        }
+    }
+
+    fn weight(self, x: f64) -> f64 {
+        let x = x.abs();
+        match self {
+            Self::Triangle => (1.0 - x).max(0.0),
+            Self::Lanczos3 if x < 3.0 => sinc(x) * sinc(x / 3.0),
+            Self::Lanczos3 => 0.0,
+        }
+    }
+}
+
+fn sinc(x: f64) -> f64 {
+    if x == 0.0 {
+        1.0
+    } else {
+        let px = PI * x;
+        px.sin() / px
+    }
+}
+
+/// Source pixels contributing to one output pixel along an axis.
+struct Taps {
+    start: usize,
+    /// Normalized to sum to 1.
+    weights: Vec<f32>,
+}
+
+fn taps(src_len: u32, dst_len: u32, filter: Filter) -> Vec<Taps> {
+    let ratio = src_len as f64 / dst_len as f64;
+    // Stretch the kernel when downscaling so every covered source pixel counts
+    let stretch = ratio.max(1.0);
+    let support = filter.support() * stretch;
+    (0..dst_len)
+        .map(|i| {
+            let center = (i as f64 + 0.5) * ratio;
+            let start = ((center - support).floor().max(0.0) as usize).min(src_len as usize - 1);
+            let end = ((center + support).ceil() as usize).clamp(start + 1, src_len as usize);
+            let raw: Vec<f64> = (start..end)
+                .map(|j| filter.weight((j as f64 + 0.5 - center) / stretch))
+                .collect();
+            let sum: f64 = raw.iter().sum();
+            let weights = if sum.abs() > f64::EPSILON {
+                raw.iter().map(|w| (w / sum) as f32).collect()
+            } else {
+                // Degenerate kernel: fall back to the nearest source pixel
+                let mut weights = vec![0.0; raw.len()];
+                let nearest = (center as usize).clamp(start, end - 1) - start;
+                weights[nearest] = 1.0;
+                weights
+            };
+            Taps { start, weights }
+        })
+        .collect()
+}
+
+/// Separable resample: horizontal pass into a premultiplied f32 buffer,
+/// then a vertical pass back to straight-alpha bytes.
+fn resample(src: &FrameBuffer, width: u32, height: u32, filter: Filter) -> FrameBuffer {
+    let h_taps = taps(src.width, width, filter);
+    let v_taps = taps(src.height, height, filter);
+    let src_stride = src.width as usize * 4;
+    let row_len = width as usize * 4;
+
+    let mut horizontal = vec![0.0_f32; src.height as usize * row_len];
+    horizontal
+        .par_chunks_exact_mut(row_len)
+        .zip(src.data.par_chunks_exact(src_stride))
+        .for_each(|(out_row, src_row)| {
+            for (out_px, taps) in out_row.chunks_exact_mut(4).zip(&h_taps) {
+                let mut acc = [0.0_f32; 4];
+                for (k, &w) in taps.weights.iter().enumerate() {
+                    let px = &src_row[(taps.start + k) * 4..][..4];
+                    let a = px[3] as f32;
+                    let wa = w * a / 255.0;
+                    acc[0] += wa * px[0] as f32;
+                    acc[1] += wa * px[1] as f32;
+                    acc[2] += wa * px[2] as f32;
+                    acc[3] += w * a;
+                }
+                out_px.copy_from_slice(&acc);
+            }
+        });
+
+    let mut out = FrameBuffer::new(width, height);
+    out.data
+        .par_chunks_exact_mut(row_len)
+        .zip(v_taps.par_iter())
+        .for_each(|(out_row, taps)| {
+            let mut acc = vec![0.0_f32; row_len];
+            for (k, &w) in taps.weights.iter().enumerate() {
+                let row = &horizontal[(taps.start + k) * row_len..][..row_len];
+                for (a, &v) in acc.iter_mut().zip(row) {
+                    *a += w * v;
+                }
+            }
+            for (out_px, px) in out_row.chunks_exact_mut(4).zip(acc.chunks_exact(4)) {
+                // Lanczos lobes can overshoot, so clamp before un-premultiplying
+                let a = px[3].clamp(0.0, 255.0);
+                if a > 0.0 {
+                    let unpremultiply = 255.0 / a;
+                    for c in 0..3 {
+                        out_px[c] = (px[c] * unpremultiply).round().clamp(0.0, 255.0) as u8;
+                    }
+                }
+                out_px[3] = a.round() as u8;
+            }
+        });
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(width: u32, height: u32, rgba: [u8; 4]) -> FrameBuffer {
+        FrameBuffer::from_rgba_vec(width, height, rgba.repeat((width * height) as usize))
+    }
+
+    #[test]
+    fn test_fit_letterboxes_and_centers() {
+        assert_eq!(
+            Placement::fit(1920, 1080, 960, 720),
+            Placement {
+                x: 0,
+                y: 90,
+                width: 960,
+                height: 540
+            }
+        );
+        assert_eq!(Placement::fit(0, 10, 100, 100).width, 0);
+    }
+
+    #[test]
+    fn test_canvas_fit_placements() {
+        // 4:3 frame on a 16:9 canvas
+        assert_eq!(CanvasFit::Fit.placement(640, 480, (0, 0), 1920, 1080), Placement::fit(640, 480, 1920, 1080));
+        assert_eq!(
+            CanvasFit::Fill.placement(640, 480, (0, 0), 1920, 1080),
+            Placement { x: 0, y: -180, width: 1920, height: 1440 }
+        );
+        assert_eq!(
+            CanvasFit::Stretch.placement(640, 480, (0, 0), 1920, 1080),
+            Placement { x: 0, y: 0, width: 1920, height: 1080 }
+        );
+        // A shrunk decode still lands at the source's own size
+        assert_eq!(
+            CanvasFit::Original.placement(320, 240, (640, 480), 1920, 1080),
+            Placement { x: 640, y: 300, width: 640, height: 480 }
+        );
+        assert_eq!(
+            CanvasFit::Original.placement(3840, 2160, (0, 0), 1920, 1080),
+            Placement { x: -960, y: -540, width: 3840, height: 2160 }
+        );
+    }
+
+    #[test]
+    fn test_solid_color_survives_every_filter() {
+        let src = solid(7, 5, [200, 100, 50, 255]);
+        for quality in [
+            ScaleQuality::Nearest,
+            ScaleQuality::Bilinear,
+            ScaleQuality::Lanczos,
+        ] {
+            for (w, h) in [(3, 2), (16, 11)] {
+                let out = scale_rgba(&src, w, h, quality);
+                assert_eq!((out.width, out.height), (w, h));
+                assert!(
+                    out.data.chunks_exact(4).all(|px| px == [200, 100, 50, 255]),
+                    "{quality:?} {w}x{h}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_downscale_averages_instead_of_aliasing() {
+        // 1px black/white stripes: nearest picks one phase, area filters average to gray
+        let mut src = FrameBuffer::new(32, 1);
+        for (x, px) in src.data.chunks_exact_mut(4).enumerate() {
+            let v = if x % 2 == 0 { 0 } else { 255 };
+            px.copy_from_slice(&[v, v, v, 255]);
+        }
+        let nearest = scale_rgba(&src, 4, 1, ScaleQuality::Nearest);
+        assert!(nearest.data[4] == 0 || nearest.data[4] == 255);
+        for quality in [ScaleQuality::Bilinear, ScaleQuality::Lanczos] {
+            let out = scale_rgba(&src, 4, 1, quality);
+            // Interior pixels, away from where the kernel is cut off at the edges
+            for x in 1..3 {
+                let v = out.pixel(x, 0)[0];
+                assert!((v as i32 - 128).abs() <= 2, "{quality:?} x={x}: {v}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_transparent_neighbours_do_not_darken_edges() {
+        // Red next to fully transparent black: color must stay red where visible
+        let src = FrameBuffer::from_rgba_vec(2, 1, vec![255, 0, 0, 255, 0, 0, 0, 0]);
+        let out = scale_rgba(&src, 4, 1, ScaleQuality::Bilinear);
+        for px in out.data.chunks_exact(4).filter(|px| px[3] > 0) {
+            assert_eq!(px[0], 255);
+        }
+    }
+
+    #[test]
+    fn test_blit_clips_to_destination() {
+        let src = solid(2, 2, [0, 255, 0, 255]);
+        let mut dst = solid(4, 4, [0, 0, 0, 255]);
+        let placement = Placement {
+            x: -1,
+            y: 3,
+            width: 4,
+            height: 4,
+        };
+        blit_scaled(&src, &mut dst, placement, ScaleQuality::Bilinear);
+        // Only the bottom row is covered; x = -1..3 clips to 0..3
+        assert_eq!(dst.pixel(0, 3), &[0, 255, 0, 255]);
+        assert_eq!(dst.pixel(2, 3), &[0, 255, 0, 255]);
+        assert_eq!(dst.pixel(3, 3), &[0, 0, 0, 255]);
+        assert_eq!(dst.pixel(0, 2), &[0, 0, 0, 255]);
+    }
+}
@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::timeline::{Clip, Timeline, TimelinePosition};
+
+/// A named copy of the timeline saved inside the project (e.g. "v1 cut"), to
+/// compare the current edit against or go back to. Unlike project versions,
+/// snapshots are taken deliberately and live in the project file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TimelineSnapshot {
+    pub id: Uuid,
+    pub name: String,
+    pub timeline: Timeline,
+}
+
+impl TimelineSnapshot {
+    pub fn new(name: impl Into<String>, timeline: &Timeline) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: name.into(),
+            timeline: timeline.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipChangeKind {
+    /// In the current timeline but not the snapshot.
+    Added,
+    /// In the snapshot but no longer in the current timeline.
+    Removed,
+    /// In both, with at least one of these differing.
+    Changed {
+        /// Different start or track.
+        moved: bool,
+        /// Different source range or duration.
+        trimmed: bool,
+        effects: bool,
+    },
+}
+
+/// One clip that differs between a snapshot and the current timeline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClipChange {
+    pub clip_id: Uuid,
+    pub asset_id: Uuid,
+    /// Track and start in the current timeline, or in the snapshot for
+    /// removed clips.
+    pub track_index: usize,
+    pub position: TimelinePosition,
+    pub kind: ClipChangeKind,
+}
+
+/// Clip-level differences from `snapshot` to `current`, ordered by position
+/// and then track. Clips are matched by id, so a cut shows up as the
+/// original clip removed and its two pieces added.
+pub fn diff_timelines(snapshot: &Timeline, current: &Timeline) -> Vec<ClipChange> {
+    let (before, after) = (clips_with_tracks(snapshot), clips_with_tracks(current));
+    let change = |track_index: usize, clip: &Clip, kind: ClipChangeKind| ClipChange {
+        clip_id: clip.id,
+        asset_id: clip.asset_id,
+        track_index,
+        position: clip.timeline_range.start,
+        kind,
+    };
+
+    let mut changes = Vec::new();
+    for &(track_index, clip) in &after {
+        let Some(&(old_track, old)) = before.iter().find(|(_, old)| old.id == clip.id) else {
+            changes.push(change(track_index, clip, ClipChangeKind::Added));
+            continue;
+        };
+        let moved = old_track != track_index || old.timeline_range.start != clip.timeline_range.start;
+        let trimmed = old.source_range != clip.source_range
+            || old.timeline_range.duration() != clip.timeline_range.duration();
+        let effects = old.effects != clip.effects;
+        if moved || trimmed || effects {
+            changes.push(change(track_index, clip, ClipChangeKind::Changed { moved, trimmed, effects }));
+        }
+    }
+    for &(track_index, clip) in &before {
+        if !after.iter().any(|(_, new)| new.id == clip.id) {
+            changes.push(change(track_index, clip, ClipChangeKind::Removed));
+        }
+    }
+    changes.sort_by_key(|c| (c.position.as_duration(), c.track_index));
+    changes
+}
+
+/// Every clip of `timeline` with its track index.
+fn clips_with_tracks(timeline: &Timeline) -> Vec<(usize, &Clip)> {
+    timeline
+        .tracks
+        .iter()
+        .enumerate()
+        .flat_map(|(track_index, track)| track.clips.iter().map(move |clip| (track_index, clip)))
+        .collect()
+}
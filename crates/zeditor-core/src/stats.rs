@@ -0,0 +1,110 @@
+//! Project statistics: how long the cut is, what it is made of, and roughly
+//! how large an export of it will be.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::effects::EffectType;
+use crate::media::{MediaKind, SourceLibrary};
+use crate::timeline::Timeline;
+
+/// Video bits per pixel x264 spends at CRF 23; every 6 CRF steps halve or
+/// double it. Real footage varies a lot, so estimates are only a guide.
+const BITS_PER_PIXEL_AT_CRF_23: f64 = 0.1;
+/// Bitrate of the AAC audio in renders (ffmpeg's default for stereo).
+const AUDIO_BITS_PER_SEC: f64 = 128_000.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackStats {
+    pub name: String,
+    pub clip_count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectStats {
+    pub duration: Duration,
+    pub tracks: Vec<TrackStats>,
+    /// Length of source media the timeline reads, each stretch of an asset
+    /// counted once however many clips use it. Stills are left out.
+    pub source_used: Duration,
+    /// Total length of the imported audio and video assets.
+    pub source_imported: Duration,
+    /// Clips carrying each effect type, most used first.
+    pub effect_counts: Vec<(EffectType, usize)>,
+}
+
+impl ProjectStats {
+    pub fn clip_count(&self) -> usize {
+        self.tracks.iter().map(|t| t.clip_count).sum()
+    }
+}
+
+/// Summarize `timeline` and the library it draws from.
+pub fn project_stats(timeline: &Timeline, library: &SourceLibrary) -> ProjectStats {
+    let tracks = timeline
+        .tracks
+        .iter()
+        .map(|track| TrackStats {
+            name: track.name.clone(),
+            clip_count: track.clips.len(),
+        })
+        .collect();
+
+    let is_timed = |asset_id: Uuid| library.get(asset_id).is_some_and(|a| a.kind() != MediaKind::Image);
+    let mut used_ranges: HashMap<Uuid, Vec<(Duration, Duration)>> = HashMap::new();
+    let mut effect_counts: HashMap<EffectType, usize> = HashMap::new();
+    for clip in timeline.tracks.iter().flat_map(|t| &t.clips) {
+        if is_timed(clip.asset_id) {
+            used_ranges
+                .entry(clip.asset_id)
+                .or_default()
+                .push((clip.source_range.start.as_duration(), clip.source_range.end.as_duration()));
+        }
+        for effect in &clip.effects {
+            *effect_counts.entry(effect.effect_type).or_default() += 1;
+        }
+    }
+    let source_used = used_ranges.into_values().map(union_length).sum();
+    let source_imported = library
+        .assets()
+        .iter()
+        .filter(|a| a.kind() != MediaKind::Image)
+        .map(|a| a.duration)
+        .sum();
+
+    let mut effect_counts: Vec<_> = effect_counts.into_iter().collect();
+    effect_counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.display_name().cmp(b.0.display_name())));
+
+    ProjectStats {
+        duration: timeline.duration(),
+        tracks,
+        source_used,
+        source_imported,
+        effect_counts,
+    }
+}
+
+/// Total length covered by `ranges`, overlaps counted once.
+fn union_length(mut ranges: Vec<(Duration, Duration)>) -> Duration {
+    ranges.sort();
+    let mut total = Duration::ZERO;
+    let mut covered_to = Duration::ZERO;
+    for (start, end) in ranges {
+        let start = start.max(covered_to);
+        if end > start {
+            total += end - start;
+            covered_to = end;
+        }
+    }
+    total
+}
+
+/// Rough size in bytes of an H.264/AAC export of `duration` at the given
+/// frame size, rate and CRF.
+pub fn estimate_export_bytes(duration: Duration, width: u32, height: u32, fps: f64, crf: u32) -> u64 {
+    let bits_per_pixel = BITS_PER_PIXEL_AT_CRF_23 * 2f64.powf((23.0 - crf as f64) / 6.0);
+    let video_bits_per_sec = width as f64 * height as f64 * fps * bits_per_pixel;
+    ((video_bits_per_sec + AUDIO_BITS_PER_SEC) * duration.as_secs_f64() / 8.0) as u64
+}
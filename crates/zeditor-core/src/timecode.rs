@@ -0,0 +1,67 @@
+use crate::timeline::TimelinePosition;
+
+/// Frames per timecode second: the frame rate rounded, so 29.97 counts
+/// 30 frames a second (non-drop-frame).
+fn nominal_fps(fps: f64) -> u64 {
+    (fps.round() as u64).max(1)
+}
+
+/// `HH:MM:SS:FF` of `position` at `fps`, on the nearest frame.
+pub fn to_timecode(position: TimelinePosition, fps: f64) -> String {
+    let nominal = nominal_fps(fps);
+    let total = (position.as_secs_f64() * fps).round() as u64;
+    let (secs, frames) = (total / nominal, total % nominal);
+    format!("{:02}:{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60, frames)
+}
+
+/// Parse a typed timecode into a timeline position at `fps`.
+///
+/// Fields are `HH:MM:SS:FF`, filled from the right, so `1:10` is one second
+/// and ten frames. Digits without separators are read in pairs from the
+/// right the same way (`200` is two seconds). A leading `+` or `-` moves
+/// relative to `current`, stopping at zero. `None` if the input isn't a
+/// timecode.
+pub fn parse_timecode(input: &str, current: TimelinePosition, fps: f64) -> Option<TimelinePosition> {
+    let input = input.trim();
+    let (sign, body) = match input.as_bytes().first()? {
+        b'+' => (1, &input[1..]),
+        b'-' => (-1, &input[1..]),
+        _ => (0, input),
+    };
+    let body = body.trim();
+
+    let fields: Vec<&str> = if body.contains([':', ';', '.']) {
+        body.split([':', ';', '.']).collect()
+    } else {
+        if body.len() > 8 || !body.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        // Pairs from the right: "12310" -> "1", "23", "10"
+        let split = body.len() % 2;
+        let mut pairs: Vec<&str> = (split..body.len()).step_by(2).map(|i| &body[i..i + 2]).collect();
+        if split == 1 {
+            pairs.insert(0, &body[..1]);
+        }
+        pairs
+    };
+    if fields.is_empty() || fields.len() > 4 {
+        return None;
+    }
+    let mut values = [0u64; 4];
+    let offset = 4 - fields.len();
+    for (i, field) in fields.iter().enumerate() {
+        if field.is_empty() || !field.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        values[offset + i] = field.parse().ok()?;
+    }
+
+    let [hours, minutes, seconds, frames] = values;
+    let total_frames = ((hours * 60 + minutes) * 60 + seconds) * nominal_fps(fps) + frames;
+    let secs = total_frames as f64 / fps;
+    let secs = match sign {
+        0 => secs,
+        sign => (current.as_secs_f64() + sign as f64 * secs).max(0.0),
+    };
+    Some(TimelinePosition::from_secs_f64(secs))
+}
@@ -3,7 +3,10 @@ use std::time::Duration;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::audio::{ChannelMap, VolumeEnvelope};
 use crate::effects::EffectInstance;
+use crate::reframe::PanPath;
+use crate::scaler::CanvasFit;
 use crate::error::{CoreError, Result};
 
 /// A position on the timeline, represented as a duration from the start.
@@ -75,7 +78,7 @@ impl TimeRange {
 }
 
 /// A clip placed on a track, referencing a portion of a media asset.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Clip {
     pub id: Uuid,
     /// The media asset this clip references.
@@ -88,9 +91,83 @@ pub struct Clip {
     /// Clips with the same link_id move/resize/cut together.
     #[serde(default)]
     pub link_id: Option<Uuid>,
+    /// User-made group (Ctrl+G) spanning any clips on any tracks. Members
+    /// move, resize and delete together, along with their linked partners.
+    #[serde(default)]
+    pub group_id: Option<Uuid>,
     /// Effects applied to this clip.
     #[serde(default)]
     pub effects: Vec<EffectInstance>,
+    /// Routing of the clip's audio channels in preview and render.
+    #[serde(default)]
+    pub channel_map: ChannelMap,
+    /// Volume automation of the clip's audio, in source time.
+    #[serde(default)]
+    pub volume_envelope: VolumeEnvelope,
+    /// Notes on moments of the clip, ordered by source time.
+    #[serde(default)]
+    pub markers: Vec<ClipMarker>,
+    /// How source frames map to project frames when the frame rates differ.
+    #[serde(default)]
+    pub rate_conform: RateConform,
+    /// How the clip's frame is sized to the canvas, before its effects.
+    #[serde(default)]
+    pub canvas_fit: CanvasFit,
+    /// Time-stretch the audio of a retimed clip so it keeps its pitch,
+    /// instead of speeding it up or slowing it down like tape.
+    #[serde(default)]
+    pub preserve_pitch: bool,
+    /// Pan keys keeping the subject in frame when a Fill clip is cropped
+    /// sideways (see `reframe`).
+    #[serde(default)]
+    pub pan: PanPath,
+}
+
+/// How a clip whose source frame rate differs from the project's is played.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RateConform {
+    /// Show the source frame current at each project frame's timestamp,
+    /// repeating or dropping frames. Duration and audio pitch are kept.
+    #[default]
+    Resample,
+    /// Play every source frame for exactly one project frame, speeding the
+    /// clip (and its audio) up or down by the ratio of the rates.
+    Reinterpret,
+}
+
+impl RateConform {
+    pub const ALL: [RateConform; 2] = [Self::Resample, Self::Reinterpret];
+
+    /// Short label for the inspector buttons.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Resample => "Resample",
+            Self::Reinterpret => "Reinterpret",
+        }
+    }
+}
+
+/// How a clip's speed reads on the timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetimeKind {
+    Normal,
+    Slow,
+    Fast,
+    /// So little source spread over the clip that it shows one frame.
+    Hold,
+}
+
+/// A slowed clip whose source is no longer than this holds a single frame
+/// (one frame at 24 fps, with some slack).
+const FRAME_HOLD_MAX_SOURCE_SECS: f64 = 0.05;
+
+/// A named moment of a clip (e.g. "best take"), in source time so it stays
+/// on the same frame when the clip is moved or trimmed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ClipMarker {
+    pub id: Uuid,
+    pub name: String,
+    pub source_time: TimelinePosition,
 }
 
 impl Clip {
@@ -111,15 +188,101 @@ impl Clip {
             timeline_range,
             source_range,
             link_id: None,
+            group_id: None,
             effects: Vec::new(),
+            channel_map: ChannelMap::Stereo,
+            volume_envelope: VolumeEnvelope::default(),
+            markers: Vec::new(),
+            rate_conform: RateConform::Resample,
+            canvas_fit: CanvasFit::Fit,
+            preserve_pitch: false,
+            pan: PanPath::default(),
         }
     }
 
     pub fn duration(&self) -> Duration {
         self.timeline_range.duration()
     }
+
+    /// Source seconds played per timeline second: 1.0 unless the clip was
+    /// retimed (e.g. by fit-to-fill).
+    pub fn speed(&self) -> f64 {
+        let timeline_secs = self.timeline_range.duration().as_secs_f64();
+        if timeline_secs <= 0.0 {
+            return 1.0;
+        }
+        self.source_range.duration().as_secs_f64() / timeline_secs
+    }
+
+    pub fn is_retimed(&self) -> bool {
+        (self.speed() - 1.0).abs() > RETIME_TOLERANCE
+    }
+
+    /// Whether the clip plays slowed down, sped up or as a frame hold.
+    pub fn retime_kind(&self) -> RetimeKind {
+        if !self.is_retimed() {
+            RetimeKind::Normal
+        } else if self.speed() > 1.0 {
+            RetimeKind::Fast
+        } else if self.source_range.duration().as_secs_f64() <= FRAME_HOLD_MAX_SOURCE_SECS {
+            RetimeKind::Hold
+        } else {
+            RetimeKind::Slow
+        }
+    }
+
+    /// Source time covered by `offset` of the clip's timeline time.
+    pub fn source_offset(&self, offset: Duration) -> Duration {
+        if self.is_retimed() {
+            offset.mul_f64(self.speed())
+        } else {
+            offset
+        }
+    }
+
+    /// Source time (seconds) shown at timeline position `pos`.
+    pub fn source_secs_at(&self, pos: TimelinePosition) -> f64 {
+        let offset = pos.as_secs_f64() - self.timeline_range.start.as_secs_f64();
+        self.source_range.start.as_secs_f64() + offset * self.speed()
+    }
+
+    /// Source time (seconds) of the frame to show at timeline position `pos`,
+    /// for a source shot at `source_fps` in a `project_fps` project.
+    /// Reinterpreted clips pick the frame by index, counting one source frame
+    /// per project frame, so rounding never repeats or skips a frame.
+    pub fn source_frame_secs_at(&self, pos: TimelinePosition, source_fps: f64, project_fps: f64) -> f64 {
+        if self.rate_conform == RateConform::Resample || source_fps <= 0.0 || project_fps <= 0.0 {
+            return self.source_secs_at(pos);
+        }
+        let offset = pos.as_secs_f64() - self.timeline_range.start.as_secs_f64();
+        let project_frame = (offset * project_fps + FRAME_EPSILON).floor();
+        let source_frame = (project_frame / project_fps * self.speed() * source_fps).round();
+        self.source_range.start.as_secs_f64() + source_frame / source_fps
+    }
+
+    /// Timeline time (seconds) at which source time `source_secs` plays.
+    pub fn timeline_secs_at(&self, source_secs: f64) -> f64 {
+        let offset = source_secs - self.source_range.start.as_secs_f64();
+        self.timeline_range.start.as_secs_f64() + offset / self.speed()
+    }
+
+    /// Markers within the used part of the source, with their timeline
+    /// times in seconds. Trimmed-off markers are kept but not shown.
+    pub fn visible_markers(&self) -> impl Iterator<Item = (&ClipMarker, f64)> {
+        self.markers
+            .iter()
+            .filter(|m| m.source_time >= self.source_range.start && m.source_time <= self.source_range.end)
+            .map(|m| (m, self.timeline_secs_at(m.source_time.as_secs_f64())))
+    }
 }
 
+/// Speed differences below this are rounding in the range arithmetic.
+const RETIME_TOLERANCE: f64 = 1e-6;
+
+/// Slack for positions that land a hair before a frame boundary after
+/// `Duration` rounding.
+const FRAME_EPSILON: f64 = 1e-6;
+
 /// Preview of what trimming would happen to a clip during a drag operation.
 #[derive(Debug, Clone)]
 pub struct TrimPreview {
@@ -140,6 +303,45 @@ pub enum TrackType {
     Audio,
 }
 
+/// How tall a track's lane is drawn, as a multiple of the normal height.
+/// Taller lanes show waveforms and envelopes in more detail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum TrackHeight {
+    #[default]
+    Normal,
+    Double,
+    Triple,
+}
+
+impl TrackHeight {
+    pub const ALL: [TrackHeight; 3] = [Self::Normal, Self::Double, Self::Triple];
+
+    pub fn multiplier(self) -> f32 {
+        match self {
+            Self::Normal => 1.0,
+            Self::Double => 2.0,
+            Self::Triple => 3.0,
+        }
+    }
+
+    /// The next taller height, wrapping back to normal.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Normal => Self::Double,
+            Self::Double => Self::Triple,
+            Self::Triple => Self::Normal,
+        }
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Normal => "1x",
+            Self::Double => "2x",
+            Self::Triple => "3x",
+        }
+    }
+}
+
 /// A track containing an ordered sequence of non-overlapping clips.
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct Track {
@@ -150,6 +352,20 @@ pub struct Track {
     /// Tracks sharing a group_id are grouped (e.g. Video 1 + Audio 1).
     #[serde(default)]
     pub group_id: Option<Uuid>,
+    /// Audio tracks only: silenced in playback and render.
+    #[serde(default)]
+    pub muted: bool,
+    /// Audio tracks only: while any audio track is soloed, only soloed tracks are heard.
+    #[serde(default)]
+    pub solo: bool,
+    /// Video tracks only: excluded from the preview composite and render.
+    #[serde(default)]
+    pub hidden: bool,
+    /// Locked tracks reject clip edits.
+    #[serde(default)]
+    pub locked: bool,
+    #[serde(default)]
+    pub height: TrackHeight,
 }
 
 impl Track {
@@ -159,6 +375,11 @@ impl Track {
             clips: Vec::new(),
             track_type,
             group_id: None,
+            muted: false,
+            solo: false,
+            hidden: false,
+            locked: false,
+            height: TrackHeight::Normal,
         }
     }
 
@@ -210,6 +431,28 @@ impl Track {
             .find(|c| c.timeline_range.contains(pos))
     }
 
+    /// The empty stretch containing `pos` between two clips (or between the
+    /// timeline start and the first clip). `None` on a clip or past the last.
+    pub fn gap_at(&self, pos: TimelinePosition) -> Option<TimeRange> {
+        if self.clip_at(pos).is_some() {
+            return None;
+        }
+        let start = self
+            .clips
+            .iter()
+            .map(|c| c.timeline_range.end)
+            .filter(|end| *end <= pos)
+            .max()
+            .unwrap_or(TimelinePosition::zero());
+        let end = self
+            .clips
+            .iter()
+            .map(|c| c.timeline_range.start)
+            .filter(|clip_start| *clip_start > pos)
+            .min()?;
+        TimeRange::new(start, end).ok()
+    }
+
     /// Add a clip, trimming any existing overlapping clips to make room.
     ///
     /// - If an existing clip starts before the new clip → trim its end to the new clip's start
@@ -237,7 +480,7 @@ impl Track {
                 // mirroring the split on linked tracks and reassigning link_ids.
                 let right_source_start = TimelinePosition(
                     existing.source_range.start.as_duration()
-                        + (new_end.as_duration() - ex_start.as_duration()),
+                        + existing.source_offset(new_end.as_duration() - ex_start.as_duration()),
                 );
                 let right_piece = Clip {
                     id: Uuid::new_v4(),
@@ -251,13 +494,21 @@ impl Track {
                         end: existing.source_range.end,
                     },
                     link_id: existing.link_id,
+                    group_id: existing.group_id,
                     effects: existing.effects.clone(),
+                    channel_map: existing.channel_map,
+                    volume_envelope: existing.volume_envelope.clone(),
+                    markers: existing.markers.clone(),
+                    rate_conform: existing.rate_conform,
+                    canvas_fit: existing.canvas_fit,
+                    preserve_pitch: existing.preserve_pitch,
+                    pan: existing.pan.clone(),
                 };
                 to_add.push(right_piece);
 
                 // Trim existing in-place to be the left piece
                 let left_duration =
-                    new_start.as_duration() - ex_start.as_duration();
+                    existing.source_offset(new_start.as_duration() - ex_start.as_duration());
                 existing.timeline_range.end = new_start;
                 existing.source_range.end = TimelinePosition(
                     existing.source_range.start.as_duration() + left_duration,
@@ -268,7 +519,7 @@ impl Track {
             } else if ex_start < new_start {
                 // Existing starts before new clip, ends inside → trim end
                 let trimmed_duration =
-                    new_start.as_duration() - ex_start.as_duration();
+                    existing.source_offset(new_start.as_duration() - ex_start.as_duration());
                 existing.timeline_range.end = new_start;
                 existing.source_range.end = TimelinePosition(
                     existing.source_range.start.as_duration() + trimmed_duration,
@@ -276,7 +527,7 @@ impl Track {
             } else {
                 // Existing starts inside new clip, extends past → trim start
                 let cut_amount =
-                    new_end.as_duration() - ex_start.as_duration();
+                    existing.source_offset(new_end.as_duration() - ex_start.as_duration());
                 existing.timeline_range.start = new_end;
                 existing.source_range.start = TimelinePosition(
                     existing.source_range.start.as_duration() + cut_amount,
@@ -380,9 +631,24 @@ impl Track {
         trim_previews: &[TrimPreview],
         snap_threshold_secs: f64,
     ) -> Option<f64> {
+        self.preview_snap_edge(clip_start, clip_end, exclude_id, trim_previews, snap_threshold_secs)
+            .map(|(start, _)| start)
+    }
+
+    /// Like [`Self::preview_snap_position`], also returning the neighbour
+    /// edge snapped to (for drawing a snap guide).
+    pub fn preview_snap_edge(
+        &self,
+        clip_start: f64,
+        clip_end: f64,
+        exclude_id: Option<Uuid>,
+        trim_previews: &[TrimPreview],
+        snap_threshold_secs: f64,
+    ) -> Option<(f64, f64)> {
         let clip_duration = clip_end - clip_start;
         let mut best_gap = f64::MAX;
         let mut best_start = clip_start;
+        let mut best_edge = clip_start;
         let mut found = false;
 
         let affected: std::collections::HashSet<Uuid> =
@@ -416,6 +682,7 @@ impl Track {
             if gap <= snap_threshold_secs && gap < best_gap {
                 best_gap = gap;
                 best_start = other_end;
+                best_edge = other_end;
                 found = true;
             }
 
@@ -424,12 +691,13 @@ impl Track {
             if gap <= snap_threshold_secs && gap < best_gap {
                 best_gap = gap;
                 best_start = other_start - clip_duration;
+                best_edge = other_start;
                 found = true;
             }
         }
 
         if found {
-            Some(best_start)
+            Some((best_start, best_edge))
         } else {
             None
         }
@@ -444,15 +712,107 @@ impl Track {
     }
 }
 
+/// Snap a clip spanning [clip_start, clip_end) so that its start or end
+/// lands on the nearest of `points` (e.g. markers or the playhead) within
+/// `threshold_secs`. Returns the snapped start and the point snapped to.
+pub fn snap_to_points(clip_start: f64, clip_end: f64, points: &[f64], threshold_secs: f64) -> Option<(f64, f64)> {
+    let mut best: Option<(f64, f64, f64)> = None;
+    for &point in points {
+        for (gap, start) in [
+            ((clip_start - point).abs(), point),
+            ((clip_end - point).abs(), point - (clip_end - clip_start)),
+        ] {
+            if gap <= threshold_secs && best.is_none_or(|(best_gap, _, _)| gap < best_gap) {
+                best = Some((gap, start, point));
+            }
+        }
+    }
+    best.map(|(_, start, point)| (start, point))
+}
+
+/// A named point on the timeline (e.g. a chapter start).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Marker {
+    pub id: Uuid,
+    pub name: String,
+    pub position: TimelinePosition,
+}
+
 /// The timeline containing all tracks.
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct Timeline {
     pub tracks: Vec<Track>,
+    /// Timeline markers, ordered by position.
+    #[serde(default)]
+    pub markers: Vec<Marker>,
+    /// Guide lines clips snap to (e.g. a beat grid), ordered. Unlike markers
+    /// they have no name and are not exported.
+    #[serde(default)]
+    pub guides: Vec<TimelinePosition>,
 }
 
 impl Timeline {
     pub fn new() -> Self {
-        Self { tracks: Vec::new() }
+        Self {
+            tracks: Vec::new(),
+            markers: Vec::new(),
+            guides: Vec::new(),
+        }
+    }
+
+    /// Add a marker at `position`, keeping markers ordered. Returns its id.
+    pub fn add_marker(&mut self, position: TimelinePosition, name: impl Into<String>) -> Uuid {
+        let marker = Marker {
+            id: Uuid::new_v4(),
+            name: name.into(),
+            position,
+        };
+        let id = marker.id;
+        let index = self.markers.partition_point(|m| m.position <= position);
+        self.markers.insert(index, marker);
+        id
+    }
+
+    pub fn remove_marker(&mut self, id: Uuid) -> Result<Marker> {
+        let index = self
+            .markers
+            .iter()
+            .position(|m| m.id == id)
+            .ok_or(CoreError::MarkerNotFound(id))?;
+        Ok(self.markers.remove(index))
+    }
+
+    /// Add a guide at `position`, keeping guides ordered. Returns false if
+    /// there already is one there.
+    pub fn add_guide(&mut self, position: TimelinePosition) -> bool {
+        match self.guides.binary_search(&position) {
+            Ok(_) => false,
+            Err(index) => {
+                self.guides.insert(index, position);
+                true
+            }
+        }
+    }
+
+    /// Add a guide every `interval` from `start` up to and including `end`
+    /// (a zero interval adds none). Returns how many new guides were added.
+    pub fn generate_guides(&mut self, start: TimelinePosition, end: TimelinePosition, interval: Duration) -> usize {
+        if interval.is_zero() {
+            return 0;
+        }
+        let mut added = 0;
+        let mut step = 0u32;
+        loop {
+            let position = TimelinePosition(start.as_duration() + interval * step);
+            if position > end {
+                break;
+            }
+            if self.add_guide(position) {
+                added += 1;
+            }
+            step += 1;
+        }
+        added
     }
 
     pub fn add_track(&mut self, name: impl Into<String>, track_type: TrackType) -> usize {
@@ -590,6 +950,31 @@ impl Timeline {
         Ok(insert_idx)
     }
 
+    /// Remove a track and its clips, renumbering the rest. Clips elsewhere
+    /// left without a linked partner are unlinked. The last video or audio
+    /// track can't be removed.
+    pub fn remove_track(&mut self, track_index: usize) -> Result<Track> {
+        let track = self.track(track_index)?;
+        if track.locked {
+            return Err(CoreError::TrackLocked(track_index));
+        }
+        let track_type = track.track_type;
+        if self.tracks.iter().filter(|t| t.track_type == track_type).count() == 1 {
+            return Err(CoreError::LastTrackOfType(track_type));
+        }
+        let removed = self.tracks.remove(track_index);
+        for link_id in removed.clips.iter().filter_map(|c| c.link_id) {
+            let partners = self.find_linked_clips(link_id);
+            if let [(partner_track, partner_id)] = partners[..]
+                && let Some(clip) = self.tracks[partner_track].get_clip_mut(partner_id)
+            {
+                clip.link_id = None;
+            }
+        }
+        self.renumber_tracks();
+        Ok(removed)
+    }
+
     /// Rename all tracks using KDEnlive-style naming:
     /// Video tracks top-to-bottom: VN, V(N-1), ..., V2, V1
     /// Audio tracks top-to-bottom: A1, A2, ..., AN
@@ -644,6 +1029,160 @@ impl Timeline {
         result
     }
 
+    /// All clips that edit together with the given clip: its linked partners
+    /// and group members, followed transitively (a group member's linked
+    /// partner comes along too). Excludes the clip itself.
+    pub fn clip_companions(&self, track_index: usize, clip_id: Uuid) -> Vec<(usize, Uuid)> {
+        let Some(clip) = self.tracks.get(track_index).and_then(|t| t.get_clip(clip_id)) else {
+            return Vec::new();
+        };
+        let mut link_ids: Vec<Uuid> = clip.link_id.into_iter().collect();
+        let mut group_ids: Vec<Uuid> = clip.group_id.into_iter().collect();
+        let mut found = vec![(track_index, clip_id)];
+        loop {
+            let before = found.len();
+            for (i, track) in self.tracks.iter().enumerate() {
+                for clip in &track.clips {
+                    let connected = clip.link_id.is_some_and(|id| link_ids.contains(&id))
+                        || clip.group_id.is_some_and(|id| group_ids.contains(&id));
+                    if !connected || found.contains(&(i, clip.id)) {
+                        continue;
+                    }
+                    found.push((i, clip.id));
+                    link_ids.extend(clip.link_id.filter(|id| !link_ids.contains(id)));
+                    group_ids.extend(clip.group_id.filter(|id| !group_ids.contains(id)));
+                }
+            }
+            if found.len() == before {
+                break;
+            }
+        }
+        found.remove(0);
+        found
+    }
+
+    /// Group the given clips, merging any groups they already belong to.
+    /// Returns the new group id. Needs at least two clips.
+    pub fn group_clips(&mut self, clips: &[(usize, Uuid)]) -> Result<Uuid> {
+        let mut old_groups = Vec::new();
+        for &(track_index, clip_id) in clips {
+            let clip = self.track(track_index)?.get_clip(clip_id).ok_or(CoreError::ClipNotFound(clip_id))?;
+            old_groups.extend(clip.group_id);
+        }
+        let mut members: Vec<(usize, Uuid)> = Vec::new();
+        for (i, track) in self.tracks.iter().enumerate() {
+            for clip in &track.clips {
+                let selected = clips.contains(&(i, clip.id));
+                let in_old_group = clip.group_id.is_some_and(|id| old_groups.contains(&id));
+                if selected || in_old_group {
+                    members.push((i, clip.id));
+                }
+            }
+        }
+        if members.len() < 2 {
+            return Err(CoreError::GroupTooSmall);
+        }
+        for &(track_index, _) in &members {
+            self.ensure_unlocked(track_index)?;
+        }
+
+        let group_id = Uuid::new_v4();
+        for (track_index, clip_id) in members {
+            if let Some(clip) = self.tracks[track_index].get_clip_mut(clip_id) {
+                clip.group_id = Some(group_id);
+            }
+        }
+        Ok(group_id)
+    }
+
+    /// Align `other` with `reference` after an audio sync: `other` is placed
+    /// `offset_secs` after the reference clip's start (measured from both
+    /// clips' source in-points) and linked to it, joining the reference's
+    /// link if it has one. When that would start before zero, `other`'s head
+    /// is trimmed instead. `other` must be a standalone clip on a track none
+    /// of the reference's clips use, so nothing left behind falls out of sync
+    /// and placing it can't trim the reference. Returns the link id.
+    pub fn sync_clip(
+        &mut self,
+        reference: (usize, Uuid),
+        other: (usize, Uuid),
+        offset_secs: f64,
+    ) -> Result<Uuid> {
+        self.ensure_group_unlocked(reference.0, reference.1)?;
+        self.ensure_unlocked(other.0)?;
+        let reference_clip = self
+            .track(reference.0)?
+            .get_clip(reference.1)
+            .ok_or(CoreError::ClipNotFound(reference.1))?;
+        let reference_start = reference_clip.timeline_range.start.as_secs_f64();
+        let link_id = reference_clip.link_id.unwrap_or_else(Uuid::new_v4);
+        let mut clip = self
+            .track(other.0)?
+            .get_clip(other.1)
+            .ok_or(CoreError::ClipNotFound(other.1))?
+            .clone();
+        if !self.clip_companions(other.0, other.1).is_empty() {
+            return Err(CoreError::SyncClipNotStandalone(other.1));
+        }
+        let shares_track = other.0 == reference.0
+            || self.clip_companions(reference.0, reference.1).iter().any(|&(i, _)| i == other.0);
+        if shares_track {
+            return Err(CoreError::SyncSharesTrack(other.1));
+        }
+
+        let start = reference_start + offset_secs;
+        let trim = (-start).max(0.0);
+        let duration = clip.duration().as_secs_f64();
+        if trim >= duration {
+            return Err(CoreError::SyncBeforeTimelineStart(other.1));
+        }
+        let source_start = clip.source_range.start.as_secs_f64() + trim * clip.speed();
+        clip.source_range.start = TimelinePosition::from_secs_f64(source_start);
+        clip.timeline_range = TimeRange {
+            start: TimelinePosition::from_secs_f64(start.max(0.0)),
+            end: TimelinePosition::from_secs_f64(start.max(0.0) + duration - trim),
+        };
+        clip.link_id = Some(link_id);
+
+        self.track_mut(other.0)?.remove_clip(other.1)?;
+        self.add_clip_trimming_overlaps(other.0, clip)?;
+        if let Some(reference_clip) = self.track_mut(reference.0)?.get_clip_mut(reference.1) {
+            reference_clip.link_id = Some(link_id);
+        }
+        Ok(link_id)
+    }
+
+    /// Dissolve every group any of the given clips belongs to. Returns the
+    /// number of clips that left a group.
+    pub fn ungroup_clips(&mut self, clips: &[(usize, Uuid)]) -> Result<usize> {
+        let mut groups = Vec::new();
+        for &(track_index, clip_id) in clips {
+            let clip = self.track(track_index)?.get_clip(clip_id).ok_or(CoreError::ClipNotFound(clip_id))?;
+            groups.extend(clip.group_id);
+        }
+        let members: Vec<usize> = self
+            .tracks
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.clips.iter().any(|c| c.group_id.is_some_and(|id| groups.contains(&id))))
+            .map(|(i, _)| i)
+            .collect();
+        for &track_index in &members {
+            self.ensure_unlocked(track_index)?;
+        }
+
+        let mut count = 0;
+        for track_index in members {
+            for clip in &mut self.tracks[track_index].clips {
+                if clip.group_id.is_some_and(|id| groups.contains(&id)) {
+                    clip.group_id = None;
+                    count += 1;
+                }
+            }
+        }
+        Ok(count)
+    }
+
     /// Find the audio track paired with a video track via mirror computation.
     pub fn find_paired_audio_track(&self, video_track_index: usize) -> Option<usize> {
         self.mirror_audio_track_for_video(video_track_index)
@@ -659,8 +1198,51 @@ impl Timeline {
             .ok_or(CoreError::TrackNotFound(index))
     }
 
+    /// Whether the track is heard: an unmuted audio track that is soloed,
+    /// or any unmuted audio track when nothing is soloed.
+    pub fn is_track_audible(&self, index: usize) -> bool {
+        let Some(track) = self.tracks.get(index) else {
+            return false;
+        };
+        if track.track_type != TrackType::Audio || track.muted {
+            return false;
+        }
+        let any_solo = self
+            .tracks
+            .iter()
+            .any(|t| t.track_type == TrackType::Audio && t.solo);
+        !any_solo || track.solo
+    }
+
+    /// Whether the track contributes to the video composite.
+    pub fn is_track_visible(&self, index: usize) -> bool {
+        self.tracks
+            .get(index)
+            .is_some_and(|t| t.track_type == TrackType::Video && !t.hidden)
+    }
+
+    /// Error if the track is locked (or does not exist).
+    pub fn ensure_unlocked(&self, track_index: usize) -> Result<()> {
+        if self.track(track_index)?.locked {
+            return Err(CoreError::TrackLocked(track_index));
+        }
+        Ok(())
+    }
+
+    /// Error if the clip's track or the track of any of its companions (linked
+    /// partners and group members) is locked. Checked up front by grouped
+    /// edits so they fail before changing anything.
+    fn ensure_group_unlocked(&self, track_index: usize, clip_id: Uuid) -> Result<()> {
+        self.ensure_unlocked(track_index)?;
+        for (companion_track, _) in self.clip_companions(track_index, clip_id) {
+            self.ensure_unlocked(companion_track)?;
+        }
+        Ok(())
+    }
+
     /// Add a clip to the specified track.
     pub fn add_clip(&mut self, track_index: usize, clip: Clip) -> Result<()> {
+        self.ensure_unlocked(track_index)?;
         self.track_mut(track_index)?.add_clip(clip)
     }
 
@@ -669,6 +1251,7 @@ impl Timeline {
     /// track is also split at the same boundaries, and new link_ids are assigned
     /// so left pieces stay in one group and right pieces form a new group.
     pub fn add_clip_trimming_overlaps(&mut self, track_index: usize, clip: Clip) -> Result<()> {
+        self.ensure_unlocked(track_index)?;
         let new_start = clip.timeline_range.start;
         let new_end = clip.timeline_range.end;
 
@@ -680,10 +1263,9 @@ impl Timeline {
             for existing in &track.clips {
                 if existing.timeline_range.start < new_start
                     && existing.timeline_range.end > new_end
+                    && let Some(link_id) = existing.link_id
                 {
-                    if let Some(link_id) = existing.link_id {
-                        split_link_ids.push(link_id);
-                    }
+                    split_link_ids.push(link_id);
                 }
             }
         }
@@ -769,6 +1351,7 @@ impl Timeline {
         track_index: usize,
         position: TimelinePosition,
     ) -> Result<(Uuid, Uuid)> {
+        self.ensure_unlocked(track_index)?;
         let track = self.track_mut(track_index)?;
 
         let clip_idx = track
@@ -787,9 +1370,10 @@ impl Timeline {
         let offset_in_clip = position.as_duration() - clip.timeline_range.start.as_duration();
 
         let source_split =
-            TimelinePosition(clip.source_range.start.as_duration() + offset_in_clip);
+            TimelinePosition(clip.source_range.start.as_duration() + clip.source_offset(offset_in_clip));
 
         let clip_link_id = clip.link_id;
+        let clip_group_id = clip.group_id;
         let clip_effects = clip.effects.clone();
 
         // Left clip: original start to cut position.
@@ -805,7 +1389,15 @@ impl Timeline {
                 end: source_split,
             },
             link_id: clip_link_id,
+            group_id: clip_group_id,
             effects: clip_effects.clone(),
+            channel_map: clip.channel_map,
+            volume_envelope: clip.volume_envelope.clone(),
+            markers: clip.markers.clone(),
+            rate_conform: clip.rate_conform,
+            canvas_fit: clip.canvas_fit,
+            preserve_pitch: clip.preserve_pitch,
+            pan: clip.pan.clone(),
         };
 
         // Right clip: cut position to original end.
@@ -821,7 +1413,15 @@ impl Timeline {
                 end: clip.source_range.end,
             },
             link_id: clip_link_id,
+            group_id: clip_group_id,
             effects: clip_effects,
+            channel_map: clip.channel_map,
+            volume_envelope: clip.volume_envelope.clone(),
+            markers: clip.markers.clone(),
+            rate_conform: clip.rate_conform,
+            canvas_fit: clip.canvas_fit,
+            preserve_pitch: clip.preserve_pitch,
+            pan: clip.pan.clone(),
         };
 
         let left_id = left.id;
@@ -843,6 +1443,8 @@ impl Timeline {
         dest_track: usize,
         new_position: TimelinePosition,
     ) -> Result<()> {
+        self.ensure_unlocked(source_track)?;
+        self.ensure_unlocked(dest_track)?;
         let mut clip = self.track_mut(source_track)?.remove_clip(clip_id)?;
 
         let duration_pos = TimelinePosition(clip.timeline_range.duration());
@@ -862,6 +1464,7 @@ impl Timeline {
         clip_id: Uuid,
         new_end: TimelinePosition,
     ) -> Result<()> {
+        self.ensure_unlocked(track_index)?;
         let track = self.track_mut(track_index)?;
         let clip = track
             .get_clip_mut(clip_id)
@@ -875,7 +1478,8 @@ impl Timeline {
         }
 
         let new_duration = new_end.as_duration() - clip.timeline_range.start.as_duration();
-        let new_source_end = TimelinePosition(clip.source_range.start.as_duration() + new_duration);
+        let new_source_end =
+            TimelinePosition(clip.source_range.start.as_duration() + clip.source_offset(new_duration));
 
         clip.timeline_range.end = new_end;
         clip.source_range.end = new_source_end;
@@ -922,10 +1526,8 @@ impl Timeline {
             } else {
                 other.timeline_range.end.as_duration() - clip_start.as_duration()
             };
-            if gap <= snap_threshold {
-                if best_snap.is_none() || gap < best_snap.unwrap().0 {
-                    best_snap = Some((gap, other.timeline_range.end));
-                }
+            if gap <= snap_threshold && (best_snap.is_none() || gap < best_snap.unwrap().0) {
+                best_snap = Some((gap, other.timeline_range.end));
             }
 
             // Check snapping our end to other's start.
@@ -968,6 +1570,8 @@ impl Timeline {
         position: TimelinePosition,
         source_range: TimeRange,
     ) -> Result<(Uuid, Uuid)> {
+        self.ensure_unlocked(video_track)?;
+        self.ensure_unlocked(audio_track)?;
         let link_id = Uuid::new_v4();
 
         let mut video_clip = Clip::new(asset_id, position, source_range);
@@ -984,9 +1588,42 @@ impl Timeline {
         Ok((video_clip_id, audio_clip_id))
     }
 
-    /// Move a clip and all its linked clips by the same delta.
-    /// When dest_track differs from source_track, computes the mirror track
-    /// for linked clips. Returns `NoMirrorTrack` if no mirror exists.
+    /// Fill `gap` exactly with `source_range` of an asset, retiming the clip
+    /// to whatever speed that takes. With `audio_track`, a linked audio clip
+    /// is placed there too. Fails without changes if the gap isn't empty on
+    /// every target track. Returns the (video or only) clip's id.
+    pub fn fit_to_fill(
+        &mut self,
+        track_index: usize,
+        audio_track: Option<usize>,
+        asset_id: Uuid,
+        source_range: TimeRange,
+        gap: TimeRange,
+    ) -> Result<Uuid> {
+        let targets: Vec<usize> = std::iter::once(track_index).chain(audio_track).collect();
+        for &index in &targets {
+            self.ensure_unlocked(index)?;
+            if self.track(index)?.clips.iter().any(|c| c.timeline_range.overlaps(&gap)) {
+                return Err(CoreError::ClipOverlap { position: gap.start });
+            }
+        }
+
+        let link_id = audio_track.map(|_| Uuid::new_v4());
+        let mut ids = Vec::new();
+        for index in targets {
+            let mut clip = Clip::new(asset_id, gap.start, source_range);
+            clip.timeline_range = gap;
+            clip.link_id = link_id;
+            ids.push(clip.id);
+            self.track_mut(index)?.add_clip(clip)?;
+        }
+        Ok(ids[0])
+    }
+
+    /// Move a clip and its companions (linked partners and group members) by
+    /// the same delta. When dest_track differs from source_track, the clip's
+    /// linked partners go to the mirror track and other companions stay on
+    /// their own tracks. Returns `NoMirrorTrack` if no mirror exists.
     pub fn move_clip_grouped(
         &mut self,
         source_track: usize,
@@ -994,6 +1631,8 @@ impl Timeline {
         dest_track: usize,
         new_position: TimelinePosition,
     ) -> Result<()> {
+        self.ensure_group_unlocked(source_track, clip_id)?;
+        self.ensure_unlocked(dest_track)?;
         // Get the original clip's position and link_id
         let (old_position, link_id) = {
             let track = self.track(source_track)?;
@@ -1002,100 +1641,88 @@ impl Timeline {
         };
 
         // If cross-track move with linked clips, verify mirror track exists
-        if source_track != dest_track {
-            if let Some(_link_id) = link_id {
-                let source_type = self.track(source_track)?.track_type;
-                if source_type == TrackType::Video {
-                    if self.mirror_audio_track_for_video(dest_track).is_none() {
-                        return Err(CoreError::NoMirrorTrack(dest_track));
-                    }
-                } else if self.mirror_video_track_for_audio(dest_track).is_none() {
-                    return Err(CoreError::NoMirrorTrack(dest_track));
-                }
-            }
-        }
+        let mirror = if source_track != dest_track && link_id.is_some() {
+            let source_type = self.track(source_track)?.track_type;
+            let mirror = if source_type == TrackType::Video {
+                self.mirror_audio_track_for_video(dest_track)
+            } else {
+                self.mirror_video_track_for_audio(dest_track)
+            };
+            let mirror = mirror.ok_or(CoreError::NoMirrorTrack(dest_track))?;
+            self.ensure_unlocked(mirror)?;
+            Some(mirror)
+        } else {
+            None
+        };
 
-        // Move the primary clip
-        self.move_clip(source_track, clip_id, dest_track, new_position)?;
+        let delta_secs = new_position.as_secs_f64() - old_position.as_secs_f64();
+        let mut moves = vec![(source_track, clip_id, dest_track, new_position)];
+        for (track_idx, companion_id) in self.clip_companions(source_track, clip_id) {
+            let companion = self
+                .track(track_idx)?
+                .get_clip(companion_id)
+                .ok_or(CoreError::ClipNotFound(companion_id))?;
+            let start = TimelinePosition::from_secs_f64(
+                (companion.timeline_range.start.as_secs_f64() + delta_secs).max(0.0),
+            );
+            let dest = match mirror {
+                Some(mirror) if companion.link_id == link_id => mirror,
+                _ => track_idx,
+            };
+            moves.push((track_idx, companion_id, dest, start));
+        }
 
-        // If linked, move linked clips by same delta
-        if let Some(link_id) = link_id {
-            let delta_secs = new_position.as_secs_f64() - old_position.as_secs_f64();
-            let linked = self.find_linked_clips(link_id);
-            for (track_idx, linked_clip_id) in linked {
-                if linked_clip_id == clip_id {
-                    continue;
-                }
-                let linked_pos = {
-                    let track = self.track(track_idx)?;
-                    match track.get_clip(linked_clip_id) {
-                        Some(clip) => clip.timeline_range.start,
-                        None => continue,
-                    }
-                };
-                let new_linked_pos = TimelinePosition::from_secs_f64(
-                    (linked_pos.as_secs_f64() + delta_secs).max(0.0)
-                );
-                // Compute destination for linked clip
-                let linked_dest = if source_track != dest_track {
-                    // Move linked clip to the mirror of dest_track
-                    let dest_type = self.track(dest_track)?.track_type;
-                    if dest_type == TrackType::Video {
-                        // Primary moved to a video track, linked is audio → mirror
-                        self.mirror_audio_track_for_video(dest_track)
-                            .ok_or(CoreError::NoMirrorTrack(dest_track))?
-                    } else {
-                        // Primary moved to an audio track, linked is video → mirror
-                        self.mirror_video_track_for_audio(dest_track)
-                            .ok_or(CoreError::NoMirrorTrack(dest_track))?
-                    }
-                } else {
-                    track_idx
-                };
-                self.move_clip(track_idx, linked_clip_id, linked_dest, new_linked_pos)?;
-            }
+        // Lift every clip before placing any, so clips moving together never
+        // trim each other.
+        let mut lifted = Vec::with_capacity(moves.len());
+        for (track_idx, id, dest, start) in moves {
+            let mut clip = self.track_mut(track_idx)?.remove_clip(id)?;
+            let duration_pos = TimelinePosition(clip.timeline_range.duration());
+            clip.timeline_range = TimeRange {
+                start,
+                end: start + duration_pos,
+            };
+            lifted.push((dest, clip));
+        }
+        for (dest, clip) in lifted {
+            self.add_clip_trimming_overlaps(dest, clip)?;
         }
 
         Ok(())
     }
 
-    /// Resize a clip and all its linked clips by the same delta.
+    /// Resize a clip and its companions (linked partners and group members)
+    /// by the same delta.
     pub fn resize_clip_grouped(
         &mut self,
         track_index: usize,
         clip_id: Uuid,
         new_end: TimelinePosition,
     ) -> Result<()> {
-        // Get old end and link_id
-        let (old_end, link_id) = {
+        self.ensure_group_unlocked(track_index, clip_id)?;
+        let old_end = {
             let track = self.track(track_index)?;
             let clip = track.get_clip(clip_id).ok_or(CoreError::ClipNotFound(clip_id))?;
-            (clip.timeline_range.end, clip.link_id)
+            clip.timeline_range.end
         };
+        let companions = self.clip_companions(track_index, clip_id);
 
         // Resize primary clip
         self.resize_clip(track_index, clip_id, new_end)?;
 
-        // If linked, resize linked clips by same delta
-        if let Some(link_id) = link_id {
-            let delta_secs = new_end.as_secs_f64() - old_end.as_secs_f64();
-            let linked = self.find_linked_clips(link_id);
-            for (track_idx, linked_clip_id) in linked {
-                if linked_clip_id == clip_id {
-                    continue;
+        let delta_secs = new_end.as_secs_f64() - old_end.as_secs_f64();
+        for (track_idx, companion_id) in companions {
+            let companion_end = {
+                let track = self.track(track_idx)?;
+                match track.get_clip(companion_id) {
+                    Some(clip) => clip.timeline_range.end,
+                    None => continue,
                 }
-                let linked_end = {
-                    let track = self.track(track_idx)?;
-                    match track.get_clip(linked_clip_id) {
-                        Some(clip) => clip.timeline_range.end,
-                        None => continue, // linked clip was deleted independently
-                    }
-                };
-                let new_linked_end = TimelinePosition::from_secs_f64(
-                    linked_end.as_secs_f64() + delta_secs
-                );
-                self.resize_clip(track_idx, linked_clip_id, new_linked_end)?;
-            }
+            };
+            let new_companion_end = TimelinePosition::from_secs_f64(
+                companion_end.as_secs_f64() + delta_secs
+            );
+            self.resize_clip(track_idx, companion_id, new_companion_end)?;
         }
 
         Ok(())
@@ -1109,13 +1736,14 @@ impl Timeline {
         position: TimelinePosition,
     ) -> Result<Vec<(Uuid, Uuid)>> {
         // Find the clip at position and get its link_id
-        let link_id = {
+        let (clip_id, link_id) = {
             let track = self.track(track_index)?;
             let clip = track
                 .clip_at(position)
                 .ok_or(CoreError::CutOutsideClip { position })?;
-            clip.link_id
+            (clip.id, clip.link_id)
         };
+        self.ensure_group_unlocked(track_index, clip_id)?;
 
         // Cut the primary clip
         let (left_id, right_id) = self.cut_at(track_index, position)?;
@@ -1190,25 +1818,560 @@ impl Timeline {
         count
     }
 
-    /// Remove a clip and any linked partner clips.
+    /// Remove a clip and its companions (linked partners and group members).
     pub fn remove_clip_grouped(&mut self, track_index: usize, clip_id: Uuid) -> Result<()> {
-        let link_id = self
+        self.ensure_group_unlocked(track_index, clip_id)?;
+        let companions = self.clip_companions(track_index, clip_id);
+
+        // Remove the primary clip
+        self.track_mut(track_index)?.remove_clip(clip_id)?;
+
+        for (companion_track, companion_id) in companions {
+            let _ = self.track_mut(companion_track).ok().map(|t| t.remove_clip(companion_id));
+        }
+
+        Ok(())
+    }
+
+    /// Resize a clip and its companions like [`Self::resize_clip_grouped`],
+    /// shifting every later clip on their tracks by the change in length so
+    /// nothing downstream loses its place relative to the clip's end.
+    pub fn resize_clip_ripple(
+        &mut self,
+        track_index: usize,
+        clip_id: Uuid,
+        new_end: TimelinePosition,
+    ) -> Result<()> {
+        let old_end = self
             .track(track_index)?
             .get_clip(clip_id)
             .ok_or(CoreError::ClipNotFound(clip_id))?
-            .link_id;
+            .timeline_range
+            .end;
+        let tracks = self.ripple_tracks(track_index, clip_id);
+        self.all_or_nothing(|tl| {
+            // Make room before growing, close the gap after shrinking
+            if new_end > old_end {
+                tl.ripple_shift(&tracks, old_end, new_end)?;
+                tl.resize_clip_grouped(track_index, clip_id, new_end)
+            } else {
+                tl.resize_clip_grouped(track_index, clip_id, new_end)?;
+                tl.ripple_shift(&tracks, old_end, new_end)
+            }
+        })
+    }
 
-        // Remove the primary clip
-        self.track_mut(track_index)?.remove_clip(clip_id)?;
+    /// Remove a clip and its companions like [`Self::remove_clip_grouped`],
+    /// pulling every later clip on their tracks left to close the gap.
+    pub fn remove_clip_ripple(&mut self, track_index: usize, clip_id: Uuid) -> Result<()> {
+        let range = self
+            .track(track_index)?
+            .get_clip(clip_id)
+            .ok_or(CoreError::ClipNotFound(clip_id))?
+            .timeline_range;
+        let tracks = self.ripple_tracks(track_index, clip_id);
+        self.all_or_nothing(|tl| {
+            tl.remove_clip_grouped(track_index, clip_id)?;
+            tl.ripple_shift(&tracks, range.end, range.start)
+        })
+    }
 
-        // Remove linked clips if any
-        if let Some(link_id) = link_id {
-            let linked = self.find_linked_clips(link_id);
-            for (linked_track, linked_clip_id) in linked {
-                let _ = self.track_mut(linked_track).ok().map(|t| t.remove_clip(linked_clip_id));
+    /// Set the channel routing of a clip and its linked partners, so a
+    /// video clip's audio follows when the map is set from the video side.
+    pub fn set_channel_map(&mut self, track_index: usize, clip_id: Uuid, map: ChannelMap) -> Result<()> {
+        let clip = self.track(track_index)?.get_clip(clip_id).ok_or(CoreError::ClipNotFound(clip_id))?;
+        let clips = match clip.link_id {
+            Some(link_id) => self.find_linked_clips(link_id),
+            None => vec![(track_index, clip_id)],
+        };
+        for &(index, _) in &clips {
+            self.ensure_unlocked(index)?;
+        }
+        for (index, id) in clips {
+            if let Some(clip) = self.track_mut(index)?.get_clip_mut(id) {
+                clip.channel_map = map;
+            }
+        }
+        Ok(())
+    }
+
+    /// Turn pitch preservation on or off for a clip and its linked clips.
+    pub fn set_preserve_pitch(&mut self, track_index: usize, clip_id: Uuid, preserve: bool) -> Result<()> {
+        let clip = self.track(track_index)?.get_clip(clip_id).ok_or(CoreError::ClipNotFound(clip_id))?;
+        let clips = match clip.link_id {
+            Some(link_id) => self.find_linked_clips(link_id),
+            None => vec![(track_index, clip_id)],
+        };
+        for &(index, _) in &clips {
+            self.ensure_unlocked(index)?;
+        }
+        for (index, id) in clips {
+            if let Some(clip) = self.track_mut(index)?.get_clip_mut(id) {
+                clip.preserve_pitch = preserve;
+            }
+        }
+        Ok(())
+    }
+
+    /// Set how a clip's frame is sized to the canvas. Linked audio has no
+    /// picture, so only this clip changes.
+    pub fn set_canvas_fit(&mut self, track_index: usize, clip_id: Uuid, fit: CanvasFit) -> Result<()> {
+        self.ensure_unlocked(track_index)?;
+        let clip = self.track_mut(track_index)?.get_clip_mut(clip_id).ok_or(CoreError::ClipNotFound(clip_id))?;
+        clip.canvas_fit = fit;
+        Ok(())
+    }
+
+    /// Replace a clip's pan keys; an empty path crops it centered again.
+    pub fn set_pan(&mut self, track_index: usize, clip_id: Uuid, pan: PanPath) -> Result<()> {
+        self.ensure_unlocked(track_index)?;
+        let clip = self.track_mut(track_index)?.get_clip_mut(clip_id).ok_or(CoreError::ClipNotFound(clip_id))?;
+        clip.pan = pan;
+        Ok(())
+    }
+
+    /// Set the volume envelope of a clip and its linked partners. Points are
+    /// in source time, which linked clips share.
+    pub fn set_volume_envelope(&mut self, track_index: usize, clip_id: Uuid, envelope: VolumeEnvelope) -> Result<()> {
+        let clip = self.track(track_index)?.get_clip(clip_id).ok_or(CoreError::ClipNotFound(clip_id))?;
+        let clips = match clip.link_id {
+            Some(link_id) => self.find_linked_clips(link_id),
+            None => vec![(track_index, clip_id)],
+        };
+        for &(index, _) in &clips {
+            self.ensure_unlocked(index)?;
+        }
+        for (index, id) in clips {
+            if let Some(clip) = self.track_mut(index)?.get_clip_mut(id) {
+                clip.volume_envelope = envelope.clone();
+            }
+        }
+        Ok(())
+    }
+
+    /// Replace the markers of a clip and its linked partners, which share
+    /// source time.
+    pub fn set_clip_markers(&mut self, track_index: usize, clip_id: Uuid, markers: Vec<ClipMarker>) -> Result<()> {
+        let clip = self.track(track_index)?.get_clip(clip_id).ok_or(CoreError::ClipNotFound(clip_id))?;
+        let clips = match clip.link_id {
+            Some(link_id) => self.find_linked_clips(link_id),
+            None => vec![(track_index, clip_id)],
+        };
+        for &(index, _) in &clips {
+            self.ensure_unlocked(index)?;
+        }
+        for (index, id) in clips {
+            if let Some(clip) = self.track_mut(index)?.get_clip_mut(id) {
+                clip.markers = markers.clone();
+            }
+        }
+        Ok(())
+    }
+
+    /// Add a marker to a clip (and its linked partners) at timeline
+    /// `position`, which must be within the clip. Returns its id.
+    pub fn add_clip_marker(
+        &mut self,
+        track_index: usize,
+        clip_id: Uuid,
+        position: TimelinePosition,
+        name: impl Into<String>,
+    ) -> Result<Uuid> {
+        let clip = self.track(track_index)?.get_clip(clip_id).ok_or(CoreError::ClipNotFound(clip_id))?;
+        if position < clip.timeline_range.start || position > clip.timeline_range.end {
+            return Err(CoreError::MarkerOutsideClip { position });
+        }
+        let source_time = TimelinePosition::from_secs_f64(clip.source_secs_at(position));
+        let marker = ClipMarker { id: Uuid::new_v4(), name: name.into(), source_time };
+        let id = marker.id;
+        let mut markers = clip.markers.clone();
+        let index = markers.partition_point(|m| m.source_time <= source_time);
+        markers.insert(index, marker);
+        self.set_clip_markers(track_index, clip_id, markers)?;
+        Ok(id)
+    }
+
+    pub fn remove_clip_marker(&mut self, track_index: usize, clip_id: Uuid, marker_id: Uuid) -> Result<ClipMarker> {
+        let clip = self.track(track_index)?.get_clip(clip_id).ok_or(CoreError::ClipNotFound(clip_id))?;
+        let mut markers = clip.markers.clone();
+        let index = markers
+            .iter()
+            .position(|m| m.id == marker_id)
+            .ok_or(CoreError::MarkerNotFound(marker_id))?;
+        let removed = markers.remove(index);
+        self.set_clip_markers(track_index, clip_id, markers)?;
+        Ok(removed)
+    }
+
+    pub fn rename_clip_marker(
+        &mut self,
+        track_index: usize,
+        clip_id: Uuid,
+        marker_id: Uuid,
+        name: impl Into<String>,
+    ) -> Result<()> {
+        let clip = self.track(track_index)?.get_clip(clip_id).ok_or(CoreError::ClipNotFound(clip_id))?;
+        let mut markers = clip.markers.clone();
+        markers
+            .iter_mut()
+            .find(|m| m.id == marker_id)
+            .ok_or(CoreError::MarkerNotFound(marker_id))?
+            .name = name.into();
+        self.set_clip_markers(track_index, clip_id, markers)
+    }
+
+    /// Give a clip new source in/out points, moving its linked partners' in
+    /// and out by the same amounts. Each clip keeps its timeline start and
+    /// speed, so its end moves with the new source length. Fails with
+    /// `ClipOverlap` if a clip would run into the next one on its track.
+    pub fn set_source_range(&mut self, track_index: usize, clip_id: Uuid, source: TimeRange) -> Result<()> {
+        self.ensure_group_unlocked(track_index, clip_id)?;
+        let clip = self.track(track_index)?.get_clip(clip_id).ok_or(CoreError::ClipNotFound(clip_id))?;
+        let start_delta = source.start.as_secs_f64() - clip.source_range.start.as_secs_f64();
+        let end_delta = source.end.as_secs_f64() - clip.source_range.end.as_secs_f64();
+        let clips = match clip.link_id {
+            Some(link_id) => self.find_linked_clips(link_id),
+            None => vec![(track_index, clip_id)],
+        };
+
+        let mut updates = Vec::with_capacity(clips.len());
+        for &(index, id) in &clips {
+            let track = self.track(index)?;
+            let clip = track.get_clip(id).ok_or(CoreError::ClipNotFound(id))?;
+            let start = clip.source_range.start.as_secs_f64() + start_delta;
+            let end = clip.source_range.end.as_secs_f64() + end_delta;
+            if start < 0.0 || end <= start {
+                return Err(CoreError::InvalidTimeRange {
+                    start: TimelinePosition::from_secs_f64(start.max(0.0)),
+                    end: TimelinePosition::from_secs_f64(end.max(0.0)),
+                });
+            }
+            let source_range = TimeRange::new(TimelinePosition::from_secs_f64(start), TimelinePosition::from_secs_f64(end))?;
+            let timeline_range = TimeRange {
+                start: clip.timeline_range.start,
+                end: clip.timeline_range.start + TimelinePosition(source_range.duration().div_f64(clip.speed())),
+            };
+            let collides = track
+                .clips
+                .iter()
+                .any(|other| !clips.contains(&(index, other.id)) && other.timeline_range.overlaps(&timeline_range));
+            if collides {
+                return Err(CoreError::ClipOverlap { position: timeline_range.start });
+            }
+            updates.push((index, id, source_range, timeline_range));
+        }
+        for (index, id, source_range, timeline_range) in updates {
+            if let Some(clip) = self.track_mut(index)?.get_clip_mut(id) {
+                clip.source_range = source_range;
+                clip.timeline_range = timeline_range;
             }
         }
+        Ok(())
+    }
 
+    /// Split a clip's stereo audio into two mono clips: the audio clip keeps
+    /// its track and plays the left channel, and a copy playing the right
+    /// channel goes on the first audio track below it that is free over the
+    /// clip's range (a new track is added at the bottom if none is). Both stay
+    /// linked to the video. `clip_id` may be the audio clip or its linked
+    /// video clip. Returns where the right-channel clip was placed.
+    pub fn split_dual_mono(&mut self, track_index: usize, clip_id: Uuid) -> Result<(usize, Uuid)> {
+        let clip = self.track(track_index)?.get_clip(clip_id).ok_or(CoreError::ClipNotFound(clip_id))?;
+        let (audio_index, audio_id) = if self.tracks[track_index].track_type == TrackType::Audio {
+            (track_index, clip_id)
+        } else {
+            clip.link_id
+                .and_then(|link_id| {
+                    self.find_linked_clips(link_id)
+                        .into_iter()
+                        .find(|&(index, _)| self.tracks[index].track_type == TrackType::Audio)
+                })
+                .ok_or(CoreError::NoStereoAudio(clip_id))?
+        };
+        self.ensure_unlocked(audio_index)?;
+        let audio = self.tracks[audio_index].get_clip(audio_id).ok_or(CoreError::ClipNotFound(audio_id))?;
+        if audio.channel_map != ChannelMap::Stereo {
+            return Err(CoreError::NoStereoAudio(audio_id));
+        }
+        let range = audio.timeline_range;
+        let mut right = audio.clone();
+        right.id = Uuid::new_v4();
+        right.channel_map = ChannelMap::RightOnly;
+        let link_id = *right.link_id.get_or_insert_with(Uuid::new_v4);
+
+        let free = self.audio_track_indices().into_iter().find(|&index| {
+            let track = &self.tracks[index];
+            index > audio_index && !track.locked && !track.clips.iter().any(|c| c.timeline_range.overlaps(&range))
+        });
+        let target = match free {
+            Some(index) => index,
+            None => {
+                let index = self.add_track("", TrackType::Audio);
+                self.renumber_tracks();
+                index
+            }
+        };
+        if let Some(left) = self.tracks[audio_index].get_clip_mut(audio_id) {
+            left.channel_map = ChannelMap::LeftOnly;
+            left.link_id = Some(link_id);
+        }
+        let right_id = right.id;
+        self.tracks[target].add_clip(right)?;
+        Ok((target, right_id))
+    }
+
+    /// Retime a clip and its companions so `speed` source seconds play per
+    /// timeline second. Source ranges are kept and each clip's end moves;
+    /// fails with `ClipOverlap` if a slowed clip would run into the next one.
+    pub fn set_clip_speed_grouped(&mut self, track_index: usize, clip_id: Uuid, speed: f64) -> Result<()> {
+        if !speed.is_finite() || speed <= 0.0 {
+            return Err(CoreError::InvalidSpeed(speed));
+        }
+        self.ensure_group_unlocked(track_index, clip_id)?;
+        let clips: Vec<(usize, Uuid)> = std::iter::once((track_index, clip_id))
+            .chain(self.clip_companions(track_index, clip_id))
+            .collect();
+
+        let mut ends = Vec::with_capacity(clips.len());
+        for &(index, id) in &clips {
+            let track = self.track(index)?;
+            let clip = track.get_clip(id).ok_or(CoreError::ClipNotFound(id))?;
+            let range = TimeRange {
+                start: clip.timeline_range.start,
+                end: clip.timeline_range.start + TimelinePosition(clip.source_range.duration().div_f64(speed)),
+            };
+            let collides = track
+                .clips
+                .iter()
+                .any(|other| !clips.contains(&(index, other.id)) && other.timeline_range.overlaps(&range));
+            if collides {
+                return Err(CoreError::ClipOverlap { position: range.start });
+            }
+            ends.push(range.end);
+        }
+        for ((index, id), end) in clips.into_iter().zip(ends) {
+            if let Some(clip) = self.track_mut(index)?.get_clip_mut(id) {
+                clip.timeline_range.end = end;
+            }
+        }
+        Ok(())
+    }
+
+    /// Set how a clip and its companions play a source shot at `source_fps`
+    /// in a `project_fps` project. Switching to reinterpret speeds them up by
+    /// `project_fps / source_fps` (and back when switching to resample), on
+    /// top of any retime already applied.
+    pub fn set_rate_conform(
+        &mut self,
+        track_index: usize,
+        clip_id: Uuid,
+        conform: RateConform,
+        source_fps: f64,
+        project_fps: f64,
+    ) -> Result<()> {
+        let clip = self.track(track_index)?.get_clip(clip_id).ok_or(CoreError::ClipNotFound(clip_id))?;
+        let ratio = project_fps / source_fps;
+        let speed = match (clip.rate_conform, conform) {
+            (current, wanted) if current == wanted => return Ok(()),
+            (_, RateConform::Reinterpret) => clip.speed() * ratio,
+            (_, RateConform::Resample) => clip.speed() / ratio,
+        };
+        self.set_clip_speed_grouped(track_index, clip_id, speed)?;
+        let clips: Vec<(usize, Uuid)> = std::iter::once((track_index, clip_id))
+            .chain(self.clip_companions(track_index, clip_id))
+            .collect();
+        for (index, id) in clips {
+            if let Some(clip) = self.track_mut(index)?.get_clip_mut(id) {
+                clip.rate_conform = conform;
+            }
+        }
+        Ok(())
+    }
+
+    /// Retime a clip and its companions like [`Self::set_clip_speed_grouped`],
+    /// shifting every later clip on their tracks by the change in length.
+    pub fn set_clip_speed_ripple(&mut self, track_index: usize, clip_id: Uuid, speed: f64) -> Result<()> {
+        if !speed.is_finite() || speed <= 0.0 {
+            return Err(CoreError::InvalidSpeed(speed));
+        }
+        let clip = self.track(track_index)?.get_clip(clip_id).ok_or(CoreError::ClipNotFound(clip_id))?;
+        let old_end = clip.timeline_range.end;
+        let new_end = clip.timeline_range.start + TimelinePosition(clip.source_range.duration().div_f64(speed));
+        let tracks = self.ripple_tracks(track_index, clip_id);
+        self.all_or_nothing(|tl| {
+            if new_end > old_end {
+                tl.ripple_shift(&tracks, old_end, new_end)?;
+                tl.set_clip_speed_grouped(track_index, clip_id, speed)
+            } else {
+                tl.set_clip_speed_grouped(track_index, clip_id, speed)?;
+                tl.ripple_shift(&tracks, old_end, new_end)
+            }
+        })
+    }
+
+    /// Move every clip on `tracks` that starts at or after `from` by
+    /// `to - from`, so a clip starting at `from` ends up starting at `to`.
+    /// Fails without changes if a track with clips to move is locked, or if
+    /// pulling clips left would overlap an earlier clip.
+    pub fn ripple_shift(&mut self, tracks: &[usize], from: TimelinePosition, to: TimelinePosition) -> Result<()> {
+        let shifted = |start: TimelinePosition| {
+            if to >= from {
+                TimelinePosition(start.0 + (to.0 - from.0))
+            } else {
+                TimelinePosition(start.0 - (from.0 - to.0))
+            }
+        };
+        for &index in tracks {
+            let track = self.track(index)?;
+            let Some(first) = track.clips.iter().map(|c| c.timeline_range.start).filter(|&s| s >= from).min() else {
+                continue;
+            };
+            if track.locked {
+                return Err(CoreError::TrackLocked(index));
+            }
+            let earlier_end = track
+                .clips
+                .iter()
+                .filter(|c| c.timeline_range.start < from)
+                .map(|c| c.timeline_range.end)
+                .max();
+            if earlier_end.is_some_and(|end| end > shifted(first)) {
+                return Err(CoreError::ClipOverlap { position: shifted(first) });
+            }
+        }
+        for &index in tracks {
+            for clip in &mut self.track_mut(index)?.clips {
+                if clip.timeline_range.start >= from {
+                    clip.timeline_range.start = shifted(clip.timeline_range.start);
+                    clip.timeline_range.end = shifted(clip.timeline_range.end);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Tracks a ripple edit of the given clip shifts: its own and its companions'.
+    fn ripple_tracks(&self, track_index: usize, clip_id: Uuid) -> Vec<usize> {
+        let mut tracks = vec![track_index];
+        for (index, _) in self.clip_companions(track_index, clip_id) {
+            if !tracks.contains(&index) {
+                tracks.push(index);
+            }
+        }
+        tracks
+    }
+
+    /// Run `edit`, putting every track back as it was if it fails partway.
+    fn all_or_nothing<T>(&mut self, edit: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        let before = self.tracks.clone();
+        let result = edit(self);
+        if result.is_err() {
+            self.tracks = before;
+        }
+        result
+    }
+
+    /// Remove `range` from every track and close the gap, pulling everything
+    /// after it left, markers and guides included; those inside the range
+    /// go with it. Clips spanning the range edges are cut; linked pieces
+    /// either side stay linked. Fails, changing nothing, if a locked track
+    /// has clips past the range start, since rippling the others would
+    /// knock it out of sync.
+    pub fn ripple_delete_range(&mut self, range: TimeRange) -> Result<()> {
+        for (index, track) in self.tracks.iter().enumerate() {
+            if track.locked && track.end_position() > range.start {
+                return Err(CoreError::TrackLocked(index));
+            }
+        }
+        self.all_or_nothing(|tl| {
+            tl.cut_all_tracks_at(range.start)?;
+            tl.cut_all_tracks_at(range.end)?;
+
+            let shift = range.duration();
+            for track in &mut tl.tracks {
+                track
+                    .clips
+                    .retain(|c| c.timeline_range.start < range.start || c.timeline_range.end > range.end);
+                for clip in &mut track.clips {
+                    if clip.timeline_range.start >= range.end {
+                        clip.timeline_range.start = TimelinePosition(clip.timeline_range.start.0 - shift);
+                        clip.timeline_range.end = TimelinePosition(clip.timeline_range.end.0 - shift);
+                    }
+                }
+            }
+            tl.markers.retain(|m| !range.contains(m.position));
+            for marker in tl.markers.iter_mut().filter(|m| m.position >= range.end) {
+                marker.position = TimelinePosition(marker.position.0 - shift);
+            }
+            tl.guides.retain(|g| !range.contains(*g));
+            for guide in tl.guides.iter_mut().filter(|g| **g >= range.end) {
+                *guide = TimelinePosition(guide.0 - shift);
+            }
+            Ok(())
+        })
+    }
+
+    /// Open `duration` of empty time at `position` on every unlocked track:
+    /// clips spanning it are cut there and everything from it on moves
+    /// right, along with the markers and guides from it on. Locked tracks
+    /// are left as they are.
+    pub fn insert_time(&mut self, position: TimelinePosition, duration: Duration) -> Result<()> {
+        self.all_or_nothing(|tl| {
+            tl.cut_all_tracks_at(position)?;
+            for track in tl.tracks.iter_mut().filter(|t| !t.locked) {
+                for clip in &mut track.clips {
+                    if clip.timeline_range.start >= position {
+                        clip.timeline_range.start = TimelinePosition(clip.timeline_range.start.0 + duration);
+                        clip.timeline_range.end = TimelinePosition(clip.timeline_range.end.0 + duration);
+                    }
+                }
+            }
+            for marker in tl.markers.iter_mut().filter(|m| m.position >= position) {
+                marker.position = TimelinePosition(marker.position.0 + duration);
+            }
+            for guide in tl.guides.iter_mut().filter(|g| **g >= position) {
+                *guide = TimelinePosition(guide.0 + duration);
+            }
+            Ok(())
+        })
+    }
+
+    /// Cut every clip on an unlocked track that spans `position`. Linked
+    /// pieces are relinked per side, as in [`Self::cut_at_grouped`].
+    fn cut_all_tracks_at(&mut self, position: TimelinePosition) -> Result<()> {
+        let mut cuts: Vec<(Option<Uuid>, Uuid, Uuid)> = Vec::new();
+        for track_index in 0..self.tracks.len() {
+            if self.tracks[track_index].locked {
+                continue;
+            }
+            let link_id = match self.tracks[track_index].clip_at(position) {
+                Some(clip) if clip.timeline_range.start != position => clip.link_id,
+                _ => continue,
+            };
+            let (left, right) = self.cut_at(track_index, position)?;
+            cuts.push((link_id, left, right));
+        }
+
+        let mut relinked: Vec<(Uuid, Uuid, Uuid)> = Vec::new();
+        for (link_id, left, right) in cuts {
+            let Some(link_id) = link_id else { continue };
+            let (left_link, right_link) = match relinked.iter().find(|(old, _, _)| *old == link_id) {
+                Some(&(_, l, r)) => (l, r),
+                None => {
+                    let links = (Uuid::new_v4(), Uuid::new_v4());
+                    relinked.push((link_id, links.0, links.1));
+                    links
+                }
+            };
+            for track in &mut self.tracks {
+                if let Some(clip) = track.get_clip_mut(left) {
+                    clip.link_id = Some(left_link);
+                }
+                if let Some(clip) = track.get_clip_mut(right) {
+                    clip.link_id = Some(right_link);
+                }
+            }
+        }
         Ok(())
     }
 
@@ -1220,4 +2383,52 @@ impl Timeline {
             .max()
             .unwrap_or(Duration::ZERO)
     }
+
+    /// Copy of the part of the timeline inside `range`, moved to start at
+    /// zero: clips outside it are dropped and clips crossing its edges are
+    /// trimmed to it. Tracks and their flags are kept, so the copy renders
+    /// exactly what plays over `range`; markers inside it move along.
+    pub fn extract_range(&self, range: TimeRange) -> Timeline {
+        let mut extracted = self.clone();
+        for (track, source) in extracted.tracks.iter_mut().zip(&self.tracks) {
+            track.clips = source
+                .clips
+                .iter()
+                .filter(|clip| clip.timeline_range.overlaps(&range))
+                .map(|clip| {
+                    let start = clip.timeline_range.start.max(range.start);
+                    let end = clip.timeline_range.end.min(range.end);
+                    let source_start = clip.source_range.start.as_duration()
+                        + clip.source_offset(start.as_duration() - clip.timeline_range.start.as_duration());
+                    let source_end = source_start + clip.source_offset(end.as_duration() - start.as_duration());
+                    let mut piece = clip.clone();
+                    piece.timeline_range = TimeRange {
+                        start: start - range.start,
+                        end: end - range.start,
+                    };
+                    piece.source_range = TimeRange {
+                        start: TimelinePosition(source_start),
+                        end: TimelinePosition(source_end),
+                    };
+                    piece
+                })
+                .collect();
+        }
+        extracted.markers = self
+            .markers
+            .iter()
+            .filter(|m| range.contains(m.position))
+            .map(|m| Marker {
+                position: m.position - range.start,
+                ..m.clone()
+            })
+            .collect();
+        extracted.guides = self
+            .guides
+            .iter()
+            .filter(|&&guide| range.contains(guide))
+            .map(|&guide| guide - range.start)
+            .collect();
+        extracted
+    }
 }
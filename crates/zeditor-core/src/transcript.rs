@@ -0,0 +1,131 @@
+//! Transcripts of spoken audio, laid out on the timeline for text-based
+//! editing and exported as SRT subtitles.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::media::SourceLibrary;
+use crate::timeline::{TimeRange, Timeline, TimelinePosition, TrackType};
+
+/// Longest subtitle cue written by [`to_srt`].
+const MAX_CUE_WORDS: usize = 7;
+const MAX_CUE_DURATION: Duration = Duration::from_millis(3500);
+/// A pause at least this long between words starts a new cue.
+const CUE_BREAK_PAUSE: Duration = Duration::from_millis(750);
+
+/// A recognised word, timed in its asset's source time.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TranscriptWord {
+    pub text: String,
+    pub start: Duration,
+    pub end: Duration,
+}
+
+/// A transcript word as it plays on the timeline, through a particular clip.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineWord {
+    pub track_index: usize,
+    pub clip_id: Uuid,
+    pub text: String,
+    pub range: TimeRange,
+}
+
+/// Words of every transcribed clip on the audio tracks, in timeline order.
+/// Words cut by a clip's in or out point are clamped to the clip; words
+/// entirely outside it are left out.
+pub fn timeline_words(timeline: &Timeline, library: &SourceLibrary) -> Vec<TimelineWord> {
+    let mut words = Vec::new();
+    for (track_index, track) in timeline.tracks.iter().enumerate() {
+        if track.track_type != TrackType::Audio {
+            continue;
+        }
+        for clip in &track.clips {
+            let Some(asset) = library.get(clip.asset_id) else {
+                continue;
+            };
+            let source_start = clip.source_range.start.as_duration();
+            let source_end = clip.source_range.end.as_duration();
+            let to_timeline = |source: Duration| {
+                let offset = (source - source_start).as_secs_f64() / clip.speed();
+                TimelinePosition::from_secs_f64(clip.timeline_range.start.as_secs_f64() + offset)
+            };
+            for word in &asset.transcript {
+                let start = word.start.max(source_start);
+                let end = word.end.min(source_end);
+                if end <= start {
+                    continue;
+                }
+                let range = TimeRange::new(to_timeline(start), to_timeline(end));
+                if let Ok(range) = range {
+                    words.push(TimelineWord {
+                        track_index,
+                        clip_id: clip.id,
+                        text: word.text.clone(),
+                        range,
+                    });
+                }
+            }
+        }
+    }
+    words.sort_by_key(|w| (w.range.start, w.track_index));
+    words
+}
+
+/// Timeline range spanning the words from index `first` to `last`
+/// (inclusive, either order). `None` if an index is out of bounds.
+pub fn words_span(words: &[TimelineWord], first: usize, last: usize) -> Option<TimeRange> {
+    let (first, last) = (first.min(last), first.max(last));
+    let selected = words.get(first..=last)?;
+    let end = selected.iter().map(|w| w.range.end).max()?;
+    TimeRange::new(selected[0].range.start, end).ok()
+}
+
+/// SubRip subtitles for `words` (in timeline order). Cues hold up to
+/// [`MAX_CUE_WORDS`] words, and break at sentence ends and pauses.
+pub fn to_srt(words: &[TimelineWord]) -> String {
+    let mut cues: Vec<&[TimelineWord]> = Vec::new();
+    let mut cue_start = 0;
+    for i in 0..words.len() {
+        let next = words.get(i + 1);
+        let sentence_end = words[i].text.trim_end().ends_with(['.', '?', '!']);
+        let pause = next.is_some_and(|n| {
+            n.range.start.as_duration() >= words[i].range.end.as_duration() + CUE_BREAK_PAUSE
+        });
+        let too_long = next.is_some_and(|n| {
+            n.range.end.as_duration() - words[cue_start].range.start.as_duration() > MAX_CUE_DURATION
+        });
+        if next.is_none() || sentence_end || pause || too_long || i + 1 - cue_start >= MAX_CUE_WORDS {
+            cues.push(&words[cue_start..=i]);
+            cue_start = i + 1;
+        }
+    }
+
+    let mut srt = String::new();
+    for (n, cue) in cues.iter().enumerate() {
+        let text = cue.iter().map(|w| w.text.trim()).collect::<Vec<_>>().join(" ");
+        let _ = writeln!(
+            srt,
+            "{}\n{} --> {}\n{}\n",
+            n + 1,
+            srt_timestamp(cue[0].range.start.as_duration()),
+            srt_timestamp(cue[cue.len() - 1].range.end.as_duration()),
+            text
+        );
+    }
+    srt
+}
+
+/// `HH:MM:SS,mmm`, rounded to the nearest millisecond.
+fn srt_timestamp(time: Duration) -> String {
+    let millis = (time.as_nanos() + 500_000) / 1_000_000;
+    format!(
+        "{:02}:{:02}:{:02},{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
@@ -0,0 +1,116 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::Result;
+
+/// Number of saved versions kept next to a project before the oldest are pruned.
+pub const DEFAULT_KEPT_VERSIONS: usize = 10;
+
+/// One restorable copy of a project file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectVersion {
+    pub path: PathBuf,
+    /// UTC save time as written in the file name, e.g. `2024-06-01T12-00-00`.
+    pub label: String,
+}
+
+/// Rolling timestamped copies of a project file, kept in `<project path>.versions/`.
+///
+/// Every save records a copy named after its UTC save time; only the newest
+/// `keep` copies are retained. Unlike the journal these are deliberate restore
+/// points, so they survive later saves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectVersions {
+    dir: PathBuf,
+}
+
+impl ProjectVersions {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Versions of a project file: `<project path>.versions/`.
+    pub fn for_project(project_path: &Path) -> Self {
+        let mut name = project_path.as_os_str().to_owned();
+        name.push(".versions");
+        Self::new(name)
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Copy the project file at `project_path` in as the version saved at
+    /// `saved_at`, then prune to the newest `keep` versions. Saving twice in
+    /// the same second replaces that second's version.
+    pub fn record(&self, project_path: &Path, saved_at: SystemTime, keep: usize) -> Result<PathBuf> {
+        fs::create_dir_all(&self.dir)?;
+        let extension = project_path
+            .extension()
+            .map(|e| e.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "zpf".to_string());
+        let path = self.dir.join(format!("{}.{extension}", timestamp_label(saved_at)));
+        fs::copy(project_path, &path)?;
+        self.prune(keep)?;
+        Ok(path)
+    }
+
+    /// Saved versions, newest first. A missing directory yields no versions.
+    pub fn list(&self) -> Result<Vec<ProjectVersion>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut versions = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if !path.is_file() {
+                continue;
+            }
+            let Some(label) = path.file_stem().map(|s| s.to_string_lossy().into_owned()) else {
+                continue;
+            };
+            versions.push(ProjectVersion { path, label });
+        }
+        // Labels are zero-padded timestamps, so they sort chronologically
+        versions.sort_by(|a, b| b.label.cmp(&a.label));
+        Ok(versions)
+    }
+
+    /// Delete all but the newest `keep` versions.
+    pub fn prune(&self, keep: usize) -> Result<()> {
+        for version in self.list()?.into_iter().skip(keep) {
+            fs::remove_file(&version.path)?;
+        }
+        Ok(())
+    }
+}
+
+/// `YYYY-MM-DDTHH-MM-SS` in UTC; dashes instead of colons keep it a valid
+/// file name everywhere.
+pub fn timestamp_label(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    let of_day = secs % 86_400;
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}-{:02}-{:02}",
+        of_day / 3600,
+        of_day / 60 % 60,
+        of_day % 60
+    )
+}
+
+/// Proleptic Gregorian date of a day count since 1970-01-01
+/// (Howard Hinnant's `civil_from_days`).
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
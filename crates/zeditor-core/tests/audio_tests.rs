@@ -0,0 +1,114 @@
+use zeditor_core::audio::{db_to_gain, quiet_cut_point, ChannelMap, VolumeEnvelope, MAX_GAIN_DB, MIN_GAIN_DB};
+
+/// Two stereo frames: mic on the left, scratch audio on the right.
+fn camera_samples() -> Vec<f32> {
+    vec![0.8, 0.2, -0.4, 0.0]
+}
+
+fn mapped(map: ChannelMap) -> Vec<f32> {
+    let mut samples = camera_samples();
+    map.apply(&mut samples, 2);
+    samples
+}
+
+#[test]
+fn test_channel_map_routes_stereo_frames() {
+    assert_eq!(mapped(ChannelMap::Stereo), camera_samples());
+    assert_eq!(mapped(ChannelMap::LeftOnly), vec![0.8, 0.8, -0.4, -0.4]);
+    assert_eq!(mapped(ChannelMap::RightOnly), vec![0.2, 0.2, 0.0, 0.0]);
+    assert_eq!(mapped(ChannelMap::MixToMono), vec![0.5, 0.5, -0.2, -0.2]);
+    assert_eq!(mapped(ChannelMap::Swap), vec![0.2, 0.8, 0.0, -0.4]);
+}
+
+#[test]
+fn test_channel_map_leaves_mono_and_extra_channels_alone() {
+    let mut mono = vec![0.1, 0.2, 0.3];
+    ChannelMap::LeftOnly.apply(&mut mono, 1);
+    assert_eq!(mono, vec![0.1, 0.2, 0.3]);
+
+    // 3-channel frames: only the first two are remapped
+    let mut surround = vec![0.1, 0.2, 0.9, 0.3, 0.4, 0.9];
+    ChannelMap::Swap.apply(&mut surround, 3);
+    assert_eq!(surround, vec![0.2, 0.1, 0.9, 0.4, 0.3, 0.9]);
+}
+
+#[test]
+fn test_volume_envelope_interpolates_in_db() {
+    let mut envelope = VolumeEnvelope::default();
+    assert_eq!(envelope.gain_at(3.0), 1.0);
+
+    envelope.add_point(2.0, -20.0);
+    envelope.add_point(1.0, 0.0);
+    assert_eq!(envelope.points.iter().map(|p| p.time).collect::<Vec<_>>(), vec![1.0, 2.0]);
+    // Held flat outside the points, linear in dB between them
+    assert_eq!(envelope.gain_db_at(0.0), 0.0);
+    assert_eq!(envelope.gain_db_at(1.5), -10.0);
+    assert_eq!(envelope.gain_db_at(5.0), -20.0);
+    assert!((envelope.gain_at(5.0) - 0.1).abs() < 1e-6);
+
+    // Same time replaces; gains are clamped
+    envelope.add_point(2.0, 100.0);
+    assert_eq!(envelope.points.len(), 2);
+    assert_eq!(envelope.points[1].gain_db, MAX_GAIN_DB);
+    assert_eq!(db_to_gain(MIN_GAIN_DB), 0.0);
+}
+
+#[test]
+fn test_volume_envelope_edits() {
+    let mut envelope = VolumeEnvelope::default();
+    envelope.add_point(1.0, -6.0);
+    envelope.add_point(2.0, -6.0);
+    envelope.add_point(3.0, 0.0);
+
+    // Between points 1 and 2, then on the flat end after the last point
+    envelope.shift_at(1.5, -6.0);
+    envelope.shift_at(9.0, -1.0);
+    let gains: Vec<f32> = envelope.points.iter().map(|p| p.gain_db).collect();
+    assert_eq!(gains, vec![-12.0, -12.0, -1.0]);
+
+    // Moving a point past its neighbour reorders it
+    assert_eq!(envelope.move_point(0, 2.5, -3.0), Some(1));
+    assert_eq!(envelope.points[1].time, 2.5);
+    assert!(envelope.move_point(7, 0.0, 0.0).is_none());
+    assert_eq!(envelope.remove_point(0).map(|p| p.time), Some(2.0));
+    assert!(envelope.remove_point(5).is_none());
+
+    // Dragging a line with no points sets a whole-clip gain
+    let mut flat = VolumeEnvelope::default();
+    flat.shift_at(4.0, -3.0);
+    assert_eq!(flat.gain_db_at(0.0), -3.0);
+    assert_eq!(flat.gain_db_at(100.0), -3.0);
+}
+
+#[test]
+fn test_volume_envelope_applies_per_frame() {
+    let mut envelope = VolumeEnvelope::default();
+    envelope.add_point(10.0, 0.0);
+    envelope.add_point(10.0 + 2.0 / 4.0, MIN_GAIN_DB);
+    // 4 Hz stereo starting at 10s: frames at 10.0, 10.25, 10.5, 10.75
+    let mut samples = vec![1.0; 8];
+    envelope.apply(&mut samples, 2, 4, 10.0);
+    assert_eq!(samples[0..2], [1.0, 1.0]);
+    let half = db_to_gain(MIN_GAIN_DB / 2.0);
+    assert!((samples[2] - half).abs() < 1e-6 && (samples[3] - half).abs() < 1e-6);
+    assert_eq!(samples[4..], [0.0; 4]);
+}
+
+#[test]
+fn test_quiet_cut_point_prefers_silence_then_zero_crossings() {
+    // 1 kHz sampling: a 0.1s tone at 50 Hz, then 0.05s of silence, starting 2s into the source
+    let tone = (0..100).map(|i| (i as f32 * std::f32::consts::TAU / 20.0).sin() * 0.5);
+    let samples: Vec<f32> = tone.chain(std::iter::repeat_n(0.0, 50)).collect();
+
+    // Silence is in reach, so a cut in the tone moves to where it ends
+    let cut = quiet_cut_point(&samples, 1000, 2.0, 2.06);
+    assert!((cut - 2.1).abs() < 1e-9, "{cut}");
+    // Already silent: left where it is
+    assert!((quiet_cut_point(&samples, 1000, 2.0, 2.12) - 2.12).abs() < 1e-9);
+
+    // Tone only: the nearest zero crossing (every 10 samples)
+    let cut = quiet_cut_point(&samples[..100], 1000, 2.0, 2.053);
+    assert!((cut - 2.05).abs() < 1e-9, "{cut}");
+
+    assert_eq!(quiet_cut_point(&[], 1000, 2.0, 2.5), 2.5);
+}
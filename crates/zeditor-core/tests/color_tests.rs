@@ -0,0 +1,127 @@
+use zeditor_core::color::{to_working_space, ColorPrimaries, ColorSpace, CubeLut, TransferFunction};
+use zeditor_core::error::CoreError;
+
+const PQ_BT2020: ColorSpace = ColorSpace {
+    transfer: TransferFunction::Pq,
+    primaries: ColorPrimaries::Bt2020,
+};
+
+/// Identity `.cube` of the given size, red varying fastest.
+fn identity_cube(size: usize) -> String {
+    let max = (size - 1) as f32;
+    let mut text = format!("TITLE \"identity\"\n# comment\nLUT_3D_SIZE {size}\n");
+    for b in 0..size {
+        for g in 0..size {
+            for r in 0..size {
+                text.push_str(&format!("{} {} {}\n", r as f32 / max, g as f32 / max, b as f32 / max));
+            }
+        }
+    }
+    text
+}
+
+#[test]
+fn test_working_space_is_untouched() {
+    let mut pixels = vec![12, 130, 250, 255, 0, 0, 0, 128];
+    let original = pixels.clone();
+    to_working_space(&mut pixels, ColorSpace::default());
+    assert!(ColorSpace::default().is_working());
+    assert_eq!(pixels, original);
+}
+
+#[test]
+fn test_pq_reference_white_maps_near_sdr_white() {
+    // PQ code value for 203 nits is ~0.58
+    let code = (0.58 * 255.0_f32).round() as u8;
+    let mut pixels = vec![code, code, code, 255];
+    to_working_space(&mut pixels, PQ_BT2020);
+    assert!(pixels[0] >= 235, "reference white too dark: {pixels:?}");
+    assert_eq!(pixels[0], pixels[1]);
+    assert_eq!(pixels[1], pixels[2]);
+    assert_eq!(pixels[3], 255);
+
+    // Highlights roll off rather than clip, and stay ordered
+    let mut highlights = vec![170, 170, 170, 255, 220, 220, 220, 255, 0, 0, 0, 255];
+    to_working_space(&mut highlights, PQ_BT2020);
+    assert!(highlights[0] <= highlights[4]);
+    assert_eq!(highlights[8], 0);
+}
+
+#[test]
+fn test_hlg_reference_white_maps_near_sdr_white() {
+    let hlg = ColorSpace {
+        transfer: TransferFunction::Hlg,
+        primaries: ColorPrimaries::Bt2020,
+    };
+    let mut pixels: Vec<u8> = (0..=255u8).flat_map(|v| [v, v, v, 255]).collect();
+    to_working_space(&mut pixels, hlg);
+    // Reference white (75% signal) lands near SDR white
+    let white = pixels[191 * 4];
+    assert!(white >= 230, "HLG reference white too dark: {white}");
+    // Monotonic in the input
+    for pair in pixels.chunks_exact(4).collect::<Vec<_>>().windows(2) {
+        assert!(pair[0][0] <= pair[1][0]);
+    }
+}
+
+#[test]
+fn test_wide_gamut_primaries_desaturate_into_bt709() {
+    let p3 = ColorSpace {
+        transfer: TransferFunction::Sdr,
+        primaries: ColorPrimaries::DisplayP3,
+    };
+    // Grey is the same in every gamut sharing the D65 white point
+    let mut grey = vec![128, 128, 128, 255];
+    to_working_space(&mut grey, p3);
+    assert!(grey[..3].iter().all(|&v| (127..=129).contains(&v)), "{grey:?}");
+
+    // Pure P3 green is outside BT.709: red clips to 0, green to full
+    let mut green = vec![0, 255, 0, 255];
+    to_working_space(&mut green, p3);
+    assert_eq!(green[0], 0);
+    assert_eq!(green[1], 255);
+}
+
+#[test]
+fn test_identity_cube_lut_leaves_pixels_alone() {
+    let lut = CubeLut::parse(&identity_cube(17)).unwrap();
+    assert_eq!(lut.size(), 17);
+    let mut pixels: Vec<u8> = vec![0, 0, 0, 255, 255, 255, 255, 0, 10, 128, 240, 77];
+    let original = pixels.clone();
+    lut.apply(&mut pixels);
+    assert_eq!(pixels, original);
+}
+
+#[test]
+fn test_cube_lut_interpolates_between_grid_points() {
+    // 2-point inverting LUT
+    let text = "LUT_3D_SIZE 2\n\
+        1 1 1\n0 1 1\n1 0 1\n0 0 1\n\
+        1 1 0\n0 1 0\n1 0 0\n0 0 0\n";
+    let lut = CubeLut::parse(text).unwrap();
+    let out = lut.lookup([0.25, 0.5, 1.0]);
+    assert!((out[0] - 0.75).abs() < 1e-5);
+    assert!((out[1] - 0.5).abs() < 1e-5);
+    assert!(out[2].abs() < 1e-5);
+}
+
+#[test]
+fn test_cube_lut_honours_domain() {
+    let text = "LUT_3D_SIZE 2\nDOMAIN_MIN 0 0 0\nDOMAIN_MAX 2 2 2\n\
+        0 0 0\n1 0 0\n0 1 0\n1 1 0\n0 0 1\n1 0 1\n0 1 1\n1 1 1\n";
+    let lut = CubeLut::parse(text).unwrap();
+    let out = lut.lookup([1.0, 1.0, 1.0]);
+    assert!(out.iter().all(|v| (v - 0.5).abs() < 1e-5), "{out:?}");
+}
+
+#[test]
+fn test_cube_lut_rejects_malformed_files() {
+    let invalid = |text: &str| matches!(CubeLut::parse(text), Err(CoreError::InvalidLut(_)));
+    assert!(invalid(""));
+    assert!(invalid("LUT_1D_SIZE 4\n0 0 0\n"));
+    assert!(invalid("LUT_3D_SIZE 2\n0 0 0\n"));
+    assert!(invalid("LUT_3D_SIZE 1\n0 0 0\n"));
+    assert!(invalid("LUT_3D_SIZE 2\n0 0\n"));
+    let truncated = identity_cube(3).replace("1 1 1\n", "");
+    assert!(invalid(&truncated));
+}
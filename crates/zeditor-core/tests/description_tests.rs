@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use zeditor_core::description::{format_timecode, timeline_description};
+use zeditor_core::media::{MediaAsset, SourceLibrary};
+use zeditor_core::timeline::{Clip, TimeRange, Timeline, TimelinePosition, TrackType};
+
+fn asset(name: &str) -> MediaAsset {
+    MediaAsset::new(
+        name.into(),
+        format!("/media/{name}").into(),
+        Duration::from_secs(600),
+        1920,
+        1080,
+        30.0,
+        true,
+    )
+}
+
+fn secs(secs: f64) -> TimelinePosition {
+    TimelinePosition::from_secs_f64(secs)
+}
+
+#[test]
+fn test_format_timecode() {
+    assert_eq!(format_timecode(Duration::from_secs_f64(5.9)), "0:05");
+    assert_eq!(format_timecode(Duration::from_secs(83)), "1:23");
+    assert_eq!(format_timecode(Duration::from_secs(3725)), "1:02:05");
+}
+
+#[test]
+fn test_description_lists_chapters_and_clips() {
+    let mut library = SourceLibrary::new();
+    let (interview, broll) = (asset("interview.mp4"), asset("broll.mov"));
+    let (interview_id, broll_id) = (interview.id, broll.id);
+    library.import(interview);
+    library.import(broll);
+
+    let mut timeline = Timeline::new();
+    timeline.add_track("V1", TrackType::Video);
+    timeline.add_track("A1", TrackType::Audio);
+    timeline
+        .add_clip_with_audio(0, 1, interview_id, secs(0.0), TimeRange::new(secs(10.0), secs(70.0)).unwrap())
+        .unwrap();
+    timeline
+        .add_clip(0, Clip::new(broll_id, secs(70.0), TimeRange::new(secs(0.0), secs(15.0)).unwrap()))
+        .unwrap();
+    timeline.add_marker(secs(70.0), "B-roll");
+    timeline.add_marker(secs(30.0), "Interview");
+
+    assert_eq!(
+        timeline_description(&timeline, &library),
+        "Chapters\n\
+         0:00 Start\n\
+         0:30 Interview\n\
+         1:10 B-roll\n\
+         \n\
+         Clips\n\
+         0:00-1:00  V1  interview.mp4 (0:10-1:10)\n\
+         1:10-1:25  V1  broll.mov (0:00-0:15)\n"
+    );
+}
+
+#[test]
+fn test_description_without_markers_or_clips() {
+    let library = SourceLibrary::new();
+    let mut timeline = Timeline::new();
+    assert_eq!(timeline_description(&timeline, &library), "");
+    timeline.add_marker(secs(0.0), "Intro");
+    assert_eq!(timeline_description(&timeline, &library), "Chapters\n0:00 Intro\n");
+}
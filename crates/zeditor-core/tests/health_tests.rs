@@ -0,0 +1,134 @@
+use std::path::Path;
+use std::time::Duration;
+
+use uuid::Uuid;
+use zeditor_core::health::{inspect_timeline, HealthIssueKind};
+use zeditor_core::media::{MediaAsset, SourceLibrary};
+use zeditor_core::timeline::*;
+
+fn make_asset(path: &Path, duration_secs: f64) -> MediaAsset {
+    MediaAsset::new(
+        "clip.mp4".into(),
+        path.to_path_buf(),
+        Duration::from_secs_f64(duration_secs),
+        1920,
+        1080,
+        30.0,
+        false,
+    )
+}
+
+fn make_clip(asset_id: Uuid, start_secs: f64, source_start: f64, source_end: f64) -> Clip {
+    let source_range = TimeRange::new(
+        TimelinePosition::from_secs_f64(source_start),
+        TimelinePosition::from_secs_f64(source_end),
+    )
+    .unwrap();
+    Clip::new(asset_id, TimelinePosition::from_secs_f64(start_secs), source_range)
+}
+
+/// A library holding one 10s asset backed by a real file.
+fn library_with_asset(dir: &Path) -> (SourceLibrary, Uuid) {
+    let path = dir.join("clip.mp4");
+    std::fs::write(&path, b"").unwrap();
+    let asset = make_asset(&path, 10.0);
+    let id = asset.id;
+    let mut library = SourceLibrary::new();
+    library.import(asset);
+    (library, id)
+}
+
+#[test]
+fn test_healthy_timeline_has_no_issues() {
+    let dir = tempfile::tempdir().unwrap();
+    let (library, asset_id) = library_with_asset(dir.path());
+    let mut timeline = Timeline::new();
+    timeline.add_track("V1", TrackType::Video);
+    timeline.add_clip(0, make_clip(asset_id, 0.0, 0.0, 4.0)).unwrap();
+    timeline.add_clip(0, make_clip(asset_id, 4.0, 4.0, 10.0)).unwrap();
+
+    assert!(inspect_timeline(&timeline, &library).is_empty());
+}
+
+#[test]
+fn test_reports_gap_between_clips_but_not_before_first() {
+    let dir = tempfile::tempdir().unwrap();
+    let (library, asset_id) = library_with_asset(dir.path());
+    let mut timeline = Timeline::new();
+    timeline.add_track("V1", TrackType::Video);
+    timeline.add_clip(0, make_clip(asset_id, 1.0, 0.0, 2.0)).unwrap();
+    timeline.add_clip(0, make_clip(asset_id, 3.5, 0.0, 2.0)).unwrap();
+
+    let issues = inspect_timeline(&timeline, &library);
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert_eq!(issues[0].track_index, 0);
+    assert_eq!(issues[0].clip_id, None);
+    assert_eq!(issues[0].position, TimelinePosition::from_secs_f64(3.0));
+    assert_eq!(issues[0].kind, HealthIssueKind::Gap { duration: Duration::from_millis(500) });
+}
+
+#[test]
+fn test_reports_sliver_overlap() {
+    let dir = tempfile::tempdir().unwrap();
+    let (library, asset_id) = library_with_asset(dir.path());
+    let mut timeline = Timeline::new();
+    timeline.add_track("V1", TrackType::Video);
+    let first = make_clip(asset_id, 0.0, 0.0, 2.0);
+    let second = make_clip(asset_id, 2.0 - 1.0 / 30.0, 0.0, 2.0);
+    let (first_id, second_id) = (first.id, second.id);
+    // Pushed directly: the editing API trims overlaps away, older or
+    // hand-edited projects may not have.
+    timeline.tracks[0].clips.extend([first, second]);
+
+    let issues = inspect_timeline(&timeline, &library);
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert_eq!(issues[0].clip_id, Some(second_id));
+    match &issues[0].kind {
+        HealthIssueKind::Overlap { duration, other_clip_id } => {
+            assert_eq!(*other_clip_id, first_id);
+            assert!((duration.as_secs_f64() - 1.0 / 30.0).abs() < 1e-6);
+        }
+        other => panic!("expected overlap, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_reports_source_range_past_asset_end() {
+    let dir = tempfile::tempdir().unwrap();
+    let (library, asset_id) = library_with_asset(dir.path());
+    let mut timeline = Timeline::new();
+    timeline.add_track("V1", TrackType::Video);
+    let clip = make_clip(asset_id, 0.0, 8.0, 12.0);
+    let clip_id = clip.id;
+    timeline.add_clip(0, clip).unwrap();
+
+    let issues = inspect_timeline(&timeline, &library);
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert_eq!(issues[0].clip_id, Some(clip_id));
+    assert_eq!(issues[0].kind, HealthIssueKind::SourceOutOfRange { overrun: Duration::from_secs(2) });
+}
+
+#[test]
+fn test_reports_missing_assets_and_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut library = SourceLibrary::new();
+    let gone = make_asset(&dir.path().join("deleted.mp4"), 10.0);
+    let gone_id = gone.id;
+    library.import(gone);
+    let unknown_id = Uuid::new_v4();
+
+    let mut timeline = Timeline::new();
+    timeline.add_track("V1", TrackType::Video);
+    timeline.add_track("V2", TrackType::Video);
+    timeline.add_clip(0, make_clip(gone_id, 0.0, 0.0, 2.0)).unwrap();
+    timeline.add_clip(1, make_clip(unknown_id, 1.0, 0.0, 2.0)).unwrap();
+
+    let issues = inspect_timeline(&timeline, &library);
+    assert_eq!(issues.len(), 2, "{issues:?}");
+    assert_eq!(
+        issues[0].kind,
+        HealthIssueKind::MissingFile { path: dir.path().join("deleted.mp4") }
+    );
+    assert_eq!(issues[1].track_index, 1);
+    assert_eq!(issues[1].kind, HealthIssueKind::MissingAsset { asset_id: unknown_id });
+}
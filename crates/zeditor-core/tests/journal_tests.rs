@@ -0,0 +1,238 @@
+use std::io::Write;
+use std::path::Path;
+
+use uuid::Uuid;
+use zeditor_core::commands::CommandHistory;
+use zeditor_core::journal::{self, CommandJournal};
+use zeditor_core::media::{MediaAsset, SourceLibrary};
+use zeditor_core::project::Project;
+use zeditor_core::timeline::*;
+
+fn make_clip(asset_id: Uuid, start_secs: f64, duration_secs: f64) -> Clip {
+    let source_range = TimeRange::new(
+        TimelinePosition::zero(),
+        TimelinePosition::from_secs_f64(duration_secs),
+    )
+    .unwrap();
+    Clip::new(asset_id, TimelinePosition::from_secs_f64(start_secs), source_range)
+}
+
+fn make_timeline() -> Timeline {
+    let mut timeline = Timeline::new();
+    timeline.add_track("V1", TrackType::Video);
+    timeline
+}
+
+#[test]
+fn test_journal_path_for_project() {
+    let journal = CommandJournal::for_project(Path::new("/tmp/my.zpf"));
+    assert_eq!(journal.path(), Path::new("/tmp/my.zpf.journal"));
+}
+
+#[test]
+fn test_missing_journal_reads_empty() {
+    let dir = tempfile::tempdir().unwrap();
+    let journal = CommandJournal::new(dir.path().join("none.journal"));
+    assert!(journal.read_entries().unwrap().is_empty());
+    assert!(journal.clear().is_ok());
+}
+
+#[test]
+fn test_history_appends_execute_undo_redo() {
+    let dir = tempfile::tempdir().unwrap();
+    let journal = CommandJournal::new(dir.path().join("p.journal"));
+    let mut timeline = make_timeline();
+    let mut history = CommandHistory::new();
+    history.set_journal(Some(journal.clone()));
+
+    let asset_id = Uuid::new_v4();
+    history
+        .execute(&mut timeline, "Add clip", |tl| {
+            tl.add_clip(0, make_clip(asset_id, 0.0, 5.0))
+        })
+        .unwrap();
+    history.undo(&mut timeline).unwrap();
+    history.redo(&mut timeline).unwrap();
+
+    let entries = journal.read_entries().unwrap();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0].description, "Add clip");
+    assert_eq!(entries[1].description, "Undo Add clip");
+    assert!(entries[1].timeline.tracks[0].clips.is_empty());
+    assert_eq!(entries[2].description, "Redo Add clip");
+    assert_eq!(entries[2].timeline, timeline);
+}
+
+#[test]
+fn test_failed_command_is_not_journaled() {
+    let dir = tempfile::tempdir().unwrap();
+    let journal = CommandJournal::new(dir.path().join("p.journal"));
+    let mut timeline = make_timeline();
+    let mut history = CommandHistory::new();
+    history.set_journal(Some(journal.clone()));
+
+    let result = history.execute(&mut timeline, "Bad", |tl| {
+        tl.remove_clip_grouped(0, Uuid::new_v4())
+    });
+    assert!(result.is_err());
+    assert!(journal.read_entries().unwrap().is_empty());
+}
+
+#[test]
+fn test_replay_reconstructs_timeline() {
+    let dir = tempfile::tempdir().unwrap();
+    let journal = CommandJournal::new(dir.path().join("p.journal"));
+    let base = make_timeline();
+
+    let mut timeline = base.clone();
+    let mut history = CommandHistory::new();
+    history.set_journal(Some(journal.clone()));
+    let asset_id = Uuid::new_v4();
+    history
+        .execute(&mut timeline, "Add clip", |tl| {
+            tl.add_clip(0, make_clip(asset_id, 0.0, 5.0))
+        })
+        .unwrap();
+    history
+        .execute(&mut timeline, "Add clip", |tl| {
+            tl.add_clip(0, make_clip(asset_id, 6.0, 2.0))
+        })
+        .unwrap();
+
+    // Simulate a crash: start again from the saved base state.
+    let mut recovered = base.clone();
+    let mut recovered_history = CommandHistory::new();
+    recovered_history.set_journal(Some(journal.clone()));
+    let entries = journal.read_entries().unwrap();
+    let mut library = SourceLibrary::new();
+    let applied = journal::replay(&mut recovered_history, &mut recovered, &mut library, entries).unwrap();
+
+    assert_eq!(applied, 2);
+    assert_eq!(recovered, timeline);
+    assert!(recovered_history.can_undo());
+    // Replay must not duplicate entries in the journal.
+    assert_eq!(journal.read_entries().unwrap().len(), 2);
+    assert!(recovered_history.journal().is_some());
+}
+
+#[test]
+fn test_truncated_trailing_line_is_ignored() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("p.journal");
+    let journal = CommandJournal::new(&path);
+    let timeline = make_timeline();
+    journal.append("Add track", &timeline).unwrap();
+
+    let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+    file.write_all(b"{\"description\":\"Half wri").unwrap();
+
+    let entries = journal.read_entries().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].description, "Add track");
+}
+
+#[test]
+fn test_rewrite_drops_half_written_line() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("p.journal");
+    let journal = CommandJournal::new(&path);
+    journal.append("Add track", &make_timeline()).unwrap();
+    let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+    file.write_all(b"{\"description\":\"Half wri").unwrap();
+
+    // Recovery keeps journaling onto the same file
+    journal.rewrite(&journal.read_entries().unwrap()).unwrap();
+    journal.append("Add clip", &make_timeline()).unwrap();
+
+    let entries = journal.read_entries().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[1].description, "Add clip");
+}
+
+#[test]
+fn test_replay_restores_imported_assets() {
+    let dir = tempfile::tempdir().unwrap();
+    let journal = CommandJournal::new(dir.path().join("p.journal"));
+    let mut project = Project::new("Journal");
+    let saved = project.clone();
+    project.command_history.set_journal(Some(journal.clone()));
+
+    let asset = MediaAsset::new(
+        "a.mp4".into(),
+        "/tmp/a.mp4".into(),
+        std::time::Duration::from_secs(10),
+        1920,
+        1080,
+        30.0,
+        true,
+    );
+    let asset_id = asset.id;
+    project.import_asset(asset);
+    project
+        .command_history
+        .execute(&mut project.timeline, "Add clip", |tl| {
+            tl.add_clip(0, make_clip(asset_id, 0.0, 5.0))
+        })
+        .unwrap();
+
+    let mut recovered = saved;
+    let entries = journal.read_entries().unwrap();
+    journal::replay(
+        &mut recovered.command_history,
+        &mut recovered.timeline,
+        &mut recovered.source_library,
+        entries,
+    )
+    .unwrap();
+
+    assert!(recovered.source_library.get(asset_id).is_some());
+    assert_eq!(recovered.timeline, project.timeline);
+    // Undoing past the replayed import takes the asset out again
+    recovered.undo().unwrap();
+    recovered.undo().unwrap();
+    assert!(recovered.source_library.is_empty());
+}
+
+#[test]
+fn test_catch_up_replays_library_and_timeline_changes() {
+    let asset = |name: &str| {
+        MediaAsset::new(name.into(), format!("/tmp/{name}").into(), std::time::Duration::from_secs(10), 1920, 1080, 30.0, true)
+    };
+    let mut saved = Project::new("Journal");
+    saved.source_library.import(asset("a.mp4"));
+    saved.source_library.import(asset("b.mp4"));
+    assert!(journal::catch_up("Edit", &saved.timeline, &saved.source_library, &saved.timeline, &saved.source_library).is_empty());
+
+    // Edited while the save was written: one asset removed, one imported
+    let mut project = saved.clone();
+    let removed = project.source_library.assets()[0].id;
+    project.remove_asset(removed).unwrap();
+    let added = asset("c.mp4");
+    let added_id = added.id;
+    project.import_asset(added);
+    project.timeline.add_clip(0, make_clip(added_id, 0.0, 5.0)).unwrap();
+
+    let entries = journal::catch_up("Edit", &saved.timeline, &saved.source_library, &project.timeline, &project.source_library);
+    assert_eq!(entries.len(), 2);
+    let mut recovered = saved.clone();
+    journal::replay(&mut recovered.command_history, &mut recovered.timeline, &mut recovered.source_library, entries).unwrap();
+    assert_eq!(recovered.source_library, project.source_library);
+    assert_eq!(recovered.timeline, project.timeline);
+
+    // A timeline-only edit is one entry without a library change
+    let mut timeline = saved.timeline.clone();
+    timeline.add_track("V2", TrackType::Video);
+    let entries = journal::catch_up("Add track", &saved.timeline, &saved.source_library, &timeline, &saved.source_library);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].library, None);
+}
+
+#[test]
+fn test_clear_removes_journal() {
+    let dir = tempfile::tempdir().unwrap();
+    let journal = CommandJournal::new(dir.path().join("p.journal"));
+    journal.append("Add track", &make_timeline()).unwrap();
+    assert!(journal.path().exists());
+    journal.clear().unwrap();
+    assert!(!journal.path().exists());
+}
@@ -1,8 +1,10 @@
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
+use uuid::Uuid;
 use zeditor_core::error::CoreError;
-use zeditor_core::media::MediaAsset;
-use zeditor_core::project::{Project, ProjectSettings, CURRENT_PROJECT_VERSION};
+use zeditor_core::media::{is_supported_media, remap_path_prefix, scan_media_folder, MediaAsset, MediaKind, SourceLibrary};
+use zeditor_core::project::{CanvasPreset, MediaReplacement, Project, ProjectSettings, CURRENT_PROJECT_VERSION};
 use zeditor_core::timeline::*;
 
 #[test]
@@ -54,6 +56,185 @@ fn test_project_save_load() {
     assert_eq!(loaded_asset.width, 1920);
 }
 
+#[test]
+fn test_canvas_presets_keep_short_side() {
+    let uhd = ProjectSettings {
+        canvas_width: 3840,
+        canvas_height: 2160,
+        fps: 25.0,
+    };
+    let vertical = uhd.with_canvas_preset(CanvasPreset::Vertical);
+    assert_eq!((vertical.canvas_width, vertical.canvas_height, vertical.fps), (2160, 3840, 25.0));
+
+    let hd = ProjectSettings::default();
+    let size = |preset| {
+        let s = hd.with_canvas_preset(preset);
+        (s.canvas_width, s.canvas_height)
+    };
+    assert_eq!(size(CanvasPreset::Square), (1080, 1080));
+    assert_eq!(size(CanvasPreset::Portrait), (1080, 1350));
+    assert_eq!(size(CanvasPreset::Widescreen), (1920, 1080));
+
+    // Odd results round to even sizes
+    assert_eq!(CanvasPreset::Widescreen.canvas_size(721), (1280, 720));
+    assert_eq!(CanvasPreset::matching(1080, 1350), Some(CanvasPreset::Portrait));
+    assert_eq!(CanvasPreset::matching(1000, 1001), None);
+    assert_eq!(CanvasPreset::Portrait.next(), CanvasPreset::Widescreen);
+}
+
+fn project_with_clip(clip_secs: (f64, f64)) -> (Project, Uuid, Uuid) {
+    let mut project = Project::new("Replace");
+    let asset = MediaAsset::new(
+        "draft_vo.mp4".into(),
+        "/tmp/draft_vo.mp4".into(),
+        Duration::from_secs(20),
+        1920,
+        1080,
+        30.0,
+        true,
+    );
+    let asset_id = asset.id;
+    project.source_library.import(asset);
+    let source_range = TimeRange::new(
+        TimelinePosition::from_secs_f64(clip_secs.0),
+        TimelinePosition::from_secs_f64(clip_secs.1),
+    )
+    .unwrap();
+    let clip = Clip::new(asset_id, TimelinePosition::zero(), source_range);
+    let clip_id = clip.id;
+    project.timeline.add_clip(0, clip).unwrap();
+    (project, asset_id, clip_id)
+}
+
+#[test]
+fn test_asset_import_and_removal_are_undoable() {
+    let (mut project, asset_id, clip_id) = project_with_clip((0.0, 5.0));
+
+    let removed = project.remove_asset(asset_id).unwrap();
+    assert!(project.source_library.get(asset_id).is_none());
+    assert!(project.timeline.tracks[0].clips.is_empty());
+
+    // Undo brings back the asset and the clip that used it, in one step
+    project.undo().unwrap();
+    assert!(project.source_library.get(asset_id).is_some());
+    assert!(project.timeline.tracks[0].get_clip(clip_id).is_some());
+    project.redo().unwrap();
+    assert!(project.source_library.is_empty());
+    assert!(project.timeline.tracks[0].clips.is_empty());
+
+    project.undo().unwrap();
+    project.import_asset(MediaAsset { id: Uuid::new_v4(), ..removed });
+    assert_eq!(project.source_library.len(), 2);
+    project.undo().unwrap();
+    assert_eq!(project.source_library.len(), 1);
+    project.redo().unwrap();
+    assert_eq!(project.source_library.len(), 2);
+    assert!(matches!(project.remove_asset(Uuid::new_v4()), Err(CoreError::AssetNotFound(_))));
+}
+
+#[test]
+fn test_undoing_an_import_keeps_later_library_changes() {
+    let (mut project, asset_id, _) = project_with_clip((0.0, 5.0));
+    let second = MediaAsset::new("b.mp4".into(), "/tmp/b.mp4".into(), Duration::from_secs(8), 1920, 1080, 30.0, true);
+    let second_id = second.id;
+    project.import_asset(second);
+
+    // Library edits outside the undo history, like a saved sub-clip
+    let range = TimeRange::new(TimelinePosition::zero(), TimelinePosition::from_secs_f64(2.0)).unwrap();
+    project.source_library.get_mut(asset_id).unwrap().add_subclip("Intro", range).unwrap();
+    project.source_library.get_mut(second_id).unwrap().add_subclip("Wide", range).unwrap();
+
+    project.undo().unwrap();
+    assert!(project.source_library.get(second_id).is_none());
+    assert_eq!(project.source_library.get(asset_id).unwrap().subclips.len(), 1);
+
+    // Redo brings the imported asset back as it was last seen
+    project.redo().unwrap();
+    assert_eq!(project.source_library.get(second_id).unwrap().subclips.len(), 1);
+    assert_eq!(project.source_library.get(asset_id).unwrap().subclips.len(), 1);
+}
+
+#[test]
+fn test_undoing_a_removal_restores_the_asset_in_place() {
+    let (mut project, first_id, _) = project_with_clip((0.0, 5.0));
+    let second = MediaAsset::new("b.mp4".into(), "/tmp/b.mp4".into(), Duration::from_secs(8), 1920, 1080, 30.0, true);
+    let second_id = second.id;
+    project.source_library.import(second);
+
+    project.remove_asset(first_id).unwrap();
+    project.undo().unwrap();
+    let order: Vec<Uuid> = project.source_library.assets().iter().map(|a| a.id).collect();
+    assert_eq!(order, vec![first_id, second_id]);
+}
+
+#[test]
+fn test_replace_media_repoints_asset() {
+    let (mut project, asset_id, clip_id) = project_with_clip((12.0, 18.0));
+    let mut final_vo = MediaAsset::new(
+        "final_vo.mp4".into(),
+        "/tmp/final_vo.mp4".into(),
+        Duration::from_secs(15),
+        1280,
+        720,
+        25.0,
+        true,
+    );
+    final_vo.codec = "h264".into();
+
+    let report = project.replace_media(asset_id, final_vo).unwrap();
+    assert_eq!(
+        report,
+        MediaReplacement {
+            fps_changed: true,
+            clips_past_end: vec![(0, clip_id)],
+        }
+    );
+
+    // Same asset (so clips follow it) and name, new file
+    let asset = project.source_library.get(asset_id).unwrap();
+    assert_eq!(asset.name, "draft_vo.mp4");
+    assert_eq!(asset.path, std::path::Path::new("/tmp/final_vo.mp4"));
+    assert_eq!(asset.duration, Duration::from_secs(15));
+    assert_eq!(asset.width, 1280);
+    assert_eq!(asset.codec, "h264");
+    assert_eq!(project.timeline.clips_using_asset(asset_id), vec![(0, clip_id)]);
+}
+
+#[test]
+fn test_replace_media_rejects_incompatible_file() {
+    let (mut project, asset_id, _) = project_with_clip((0.0, 5.0));
+    let audio_only = MediaAsset::new(
+        "final_vo.wav".into(),
+        "/tmp/final_vo.wav".into(),
+        Duration::from_secs(30),
+        0,
+        0,
+        0.0,
+        true,
+    );
+    assert_eq!(audio_only.kind(), MediaKind::Audio);
+    let result = project.replace_media(asset_id, audio_only);
+    assert!(matches!(result, Err(CoreError::IncompatibleMedia(_))));
+    assert_eq!(
+        project.source_library.get(asset_id).unwrap().path,
+        std::path::Path::new("/tmp/draft_vo.mp4")
+    );
+
+    // A longer file at the same rate fits every clip
+    let longer = MediaAsset::new(
+        "v2.mp4".into(),
+        "/tmp/v2.mp4".into(),
+        Duration::from_secs(30),
+        1920,
+        1080,
+        30.0,
+        true,
+    );
+    let report = project.check_media_replacement(asset_id, &longer).unwrap();
+    assert!(!report.fps_changed);
+    assert!(report.clips_past_end.is_empty());
+}
+
 #[test]
 fn test_source_library_operations() {
     let mut project = Project::new("Test");
@@ -81,6 +262,106 @@ fn test_source_library_operations() {
     assert!(project.source_library.is_empty());
 }
 
+#[test]
+fn test_remap_path_prefix() {
+    let remap = |path: &str, from: &str, to: &str| remap_path_prefix(Path::new(path), Path::new(from), Path::new(to));
+    assert_eq!(
+        remap("/media/old-drive/a/clip.mp4", "/media/old-drive", "/mnt/new"),
+        Some(PathBuf::from("/mnt/new/a/clip.mp4"))
+    );
+    // A trailing separator on the prefix doesn't matter
+    assert_eq!(
+        remap("/media/old-drive/clip.mp4", "/media/old-drive/", "/mnt/new"),
+        Some(PathBuf::from("/mnt/new/clip.mp4"))
+    );
+    // Only whole components match
+    assert_eq!(remap("/media/old-drive2/clip.mp4", "/media/old-drive", "/mnt/new"), None);
+    assert_eq!(remap("/other/clip.mp4", "/media/old-drive", "/mnt/new"), None);
+    assert_eq!(remap("/media/clip.mp4", "", "/mnt/new"), None);
+    // Windows paths from another machine
+    assert_eq!(
+        remap("D:\\footage\\day1\\clip.mp4", "D:", "/mnt/footage-drive"),
+        Some(PathBuf::from("/mnt/footage-drive/footage/day1/clip.mp4"))
+    );
+}
+
+#[test]
+fn test_source_library_remaps_only_resolving_paths() {
+    let dir = tempfile::tempdir().unwrap();
+    let new_drive = dir.path().join("new-drive");
+    std::fs::create_dir_all(new_drive.join("shoot")).unwrap();
+    std::fs::write(new_drive.join("shoot/a.mp4"), b"").unwrap();
+
+    let mut project = Project::new("Test");
+    let asset = |path: &str| {
+        MediaAsset::new("clip".into(), path.into(), Duration::from_secs(5), 1920, 1080, 30.0, true)
+    };
+    let found = asset("/media/old-drive/shoot/a.mp4");
+    let still_missing = asset("/media/old-drive/shoot/b.mp4");
+    let elsewhere = asset("/home/me/c.mp4");
+    let (found_id, missing_id) = (found.id, still_missing.id);
+    project.source_library.import(found);
+    project.source_library.import(still_missing);
+    project.source_library.import(elsewhere);
+
+    let from = Path::new("/media/old-drive");
+    let preview = project.source_library.preview_path_remap(from, &new_drive);
+    assert_eq!(preview.len(), 2);
+    assert_eq!(preview[0].asset_id, found_id);
+    assert_eq!(preview[0].new_path, new_drive.join("shoot/a.mp4"));
+    assert!(preview[0].resolves);
+    assert_eq!(preview[1].asset_id, missing_id);
+    assert!(!preview[1].resolves);
+
+    let applied = project.source_library.remap_paths(from, &new_drive);
+    assert_eq!(applied.len(), 1);
+    assert_eq!(project.source_library.get(found_id).unwrap().path, new_drive.join("shoot/a.mp4"));
+    assert_eq!(
+        project.source_library.get(missing_id).unwrap().path,
+        PathBuf::from("/media/old-drive/shoot/b.mp4")
+    );
+}
+
+#[test]
+fn test_media_asset_kind() {
+    let video = MediaAsset::new(
+        "clip.mp4".into(), "/tmp/clip.mp4".into(), Duration::from_secs(5), 1920, 1080, 30.0, true,
+    );
+    let audio = MediaAsset::new(
+        "song.wav".into(), "/tmp/song.wav".into(), Duration::from_secs(60), 0, 0, 0.0, true,
+    );
+    let image = MediaAsset::new(
+        "logo.PNG".into(), "/tmp/logo.PNG".into(), Duration::from_secs(5), 512, 512, 0.0, false,
+    );
+    assert_eq!(video.kind(), MediaKind::Video);
+    assert_eq!(audio.kind(), MediaKind::Audio);
+    assert_eq!(image.kind(), MediaKind::Image);
+}
+
+#[test]
+fn test_media_asset_shots() {
+    let mut asset = MediaAsset::new(
+        "clip.mp4".into(), "/tmp/clip.mp4".into(), Duration::from_secs(10), 1920, 1080, 30.0, true,
+    );
+    assert_eq!(asset.shots(), vec![(Duration::ZERO, Duration::from_secs(10))]);
+
+    asset.shot_starts = vec![Duration::from_secs(3), Duration::from_millis(7500)];
+    assert_eq!(
+        asset.shots(),
+        vec![
+            (Duration::ZERO, Duration::from_secs(3)),
+            (Duration::from_secs(3), Duration::from_millis(7500)),
+            (Duration::from_millis(7500), Duration::from_secs(10)),
+        ]
+    );
+
+    // Projects saved before shot detection load without shots.
+    let mut value = serde_json::to_value(&asset).unwrap();
+    value.as_object_mut().unwrap().remove("shot_starts");
+    let old: MediaAsset = serde_json::from_value(value).unwrap();
+    assert!(old.shot_starts.is_empty());
+}
+
 #[test]
 fn test_project_file_contains_version() {
     let project = Project::new("Versioned");
@@ -273,3 +554,130 @@ fn test_project_load_without_settings_field() {
     let loaded = Project::load(&path).unwrap();
     assert_eq!(loaded.settings, ProjectSettings::default());
 }
+
+#[test]
+fn test_project_save_replaces_file_atomically() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("atomic.zpf");
+    Project::new("First").save(&path).unwrap();
+
+    let mut project = Project::new("Second");
+    project.command_history.execute(&mut project.timeline, "noop", |_| Ok(())).unwrap();
+    let snapshot = project.save_snapshot();
+    assert!(!snapshot.project.command_history.can_undo(), "snapshots leave the undo history behind");
+    snapshot.write(&path).unwrap();
+
+    assert_eq!(Project::load(&path).unwrap().name, "Second");
+    let leftovers: Vec<_> = std::fs::read_dir(dir.path()).unwrap().map(|e| e.unwrap().file_name()).collect();
+    assert_eq!(leftovers, vec![std::ffi::OsString::from("atomic.zpf")]);
+}
+
+#[test]
+fn test_scan_media_folder_finds_supported_files_recursively() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::create_dir_all(root.join("day2/audio")).unwrap();
+    std::fs::create_dir_all(root.join(".cache")).unwrap();
+    for file in ["b.MOV", "a.mp4", "notes.txt", "day2/c.mkv", "day2/audio/d.wav", "day2/still.png", ".hidden.mp4", ".cache/e.mp4"] {
+        std::fs::write(root.join(file), b"").unwrap();
+    }
+
+    let found: Vec<PathBuf> = scan_media_folder(root)
+        .unwrap()
+        .into_iter()
+        .map(|p| p.strip_prefix(root).unwrap().to_path_buf())
+        .collect();
+    let expected: Vec<PathBuf> = ["a.mp4", "b.MOV", "day2/audio/d.wav", "day2/c.mkv", "day2/still.png"]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+    assert_eq!(found, expected);
+    assert!(!is_supported_media(Path::new("clip")));
+    assert!(scan_media_folder(&root.join("missing")).is_err());
+}
+
+#[test]
+fn test_source_library_bins() {
+    let mut library = SourceLibrary::new();
+    for (name, bin) in [("a", Some("Shoot")), ("b", None), ("c", Some("Music")), ("d", Some("Shoot"))] {
+        let mut asset = MediaAsset::new(name.into(), PathBuf::from(name), Duration::from_secs(1), 320, 240, 30.0, false);
+        asset.bin = bin.map(String::from);
+        library.import(asset);
+    }
+    assert_eq!(library.bins(), vec!["Music", "Shoot"]);
+}
+
+#[test]
+fn test_subclips_logged_on_asset() {
+    let mut library = SourceLibrary::new();
+    let mut asset = MediaAsset::new("interview".into(), PathBuf::from("i.mp4"), Duration::from_secs(60), 320, 240, 30.0, true);
+    let secs = TimelinePosition::from_secs_f64;
+    let id = asset.add_subclip("Answer 1", TimeRange::new(secs(5.0), secs(12.0)).unwrap()).unwrap();
+    // Ranges past the end of the asset are rejected
+    assert!(asset.add_subclip("Too long", TimeRange::new(secs(50.0), secs(70.0)).unwrap()).is_err());
+    asset.rename_subclip(id, "Intro").unwrap();
+    let asset_id = asset.id;
+    library.import(asset);
+
+    let (parent, subclip) = library.find_subclip(id).unwrap();
+    assert_eq!(parent.id, asset_id);
+    assert_eq!(subclip.name, "Intro");
+    assert_eq!(subclip.range.duration(), Duration::from_secs(7));
+
+    // Sub-clips survive a save round trip
+    let mut project = Project::new("Subclips");
+    project.source_library = library;
+    let json = serde_json::to_string(&project).unwrap();
+    let loaded: Project = serde_json::from_str(&json).unwrap();
+    assert!(loaded.source_library.find_subclip(id).is_some());
+
+    let asset = project.source_library.get_mut(asset_id).unwrap();
+    asset.remove_subclip(id).unwrap();
+    assert!(matches!(asset.remove_subclip(id), Err(CoreError::SubClipNotFound(_))));
+}
+
+#[test]
+fn test_find_duplicate_by_path_or_contents() {
+    let mut library = SourceLibrary::new();
+    let mut asset = MediaAsset::new("a".into(), PathBuf::from("/media/a.mp4"), Duration::from_secs(1), 320, 240, 30.0, false);
+    asset.content_hash = Some(42);
+    let id = asset.id;
+    library.import(asset);
+    library.import(MediaAsset::new("b".into(), PathBuf::from("/media/b.mp4"), Duration::from_secs(1), 320, 240, 30.0, false));
+
+    assert_eq!(library.find_duplicate(Path::new("/media/a.mp4"), None).map(|a| a.id), Some(id));
+    assert_eq!(library.find_duplicate(Path::new("/copies/a.mp4"), Some(42)).map(|a| a.id), Some(id));
+    assert!(library.find_duplicate(Path::new("/copies/c.mp4"), Some(7)).is_none());
+    // Assets without a hash only match by path
+    assert!(library.find_duplicate(Path::new("/copies/c.mp4"), None).is_none());
+}
+
+#[test]
+fn test_with_file_size() {
+    let mut library = SourceLibrary::new();
+    let mut sized = MediaAsset::new("a".into(), PathBuf::from("/media/a.mp4"), Duration::from_secs(1), 320, 240, 30.0, false);
+    sized.file_size = Some(1024);
+    let id = sized.id;
+    library.import(sized);
+    // Assets from before sizes were recorded never match
+    library.import(MediaAsset::new("b".into(), PathBuf::from("/media/b.mp4"), Duration::from_secs(1), 320, 240, 30.0, false));
+
+    assert_eq!(library.with_file_size(1024).map(|a| a.id).collect::<Vec<_>>(), vec![id]);
+    assert_eq!(library.with_file_size(2048).count(), 0);
+}
+
+#[test]
+fn test_has_mismatched_fps() {
+    let mut project = Project::new("conform");
+    let film = MediaAsset::new("film".into(), PathBuf::from("/media/film.mp4"), Duration::from_secs(1), 320, 240, 23.976, false);
+    let native = MediaAsset::new("native".into(), PathBuf::from("/media/native.mp4"), Duration::from_secs(1), 320, 240, 30.0, false);
+    let still = MediaAsset::new("still".into(), PathBuf::from("/media/still.png"), Duration::ZERO, 320, 240, 25.0, false);
+    let ids = [film.id, native.id, still.id];
+    for asset in [film, native, still] {
+        project.source_library.import(asset);
+    }
+    assert!(project.has_mismatched_fps(ids[0]));
+    assert!(!project.has_mismatched_fps(ids[1]));
+    assert!(!project.has_mismatched_fps(ids[2]));
+    assert!(!project.has_mismatched_fps(Uuid::new_v4()));
+}
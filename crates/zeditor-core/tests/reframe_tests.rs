@@ -0,0 +1,46 @@
+use zeditor_core::reframe::{visible_width_fraction, PanKey, PanPath};
+use zeditor_core::scaler::Placement;
+
+fn key(source_secs: f64, center_x: f64) -> PanKey {
+    PanKey { source_secs, center_x }
+}
+
+#[test]
+fn test_simplified_drops_keys_on_a_straight_line() {
+    let samples = [(0.0, 0.5), (1.0, 0.6), (2.0, 0.7), (3.0, 0.7), (4.0, 0.3)];
+    let path = PanPath::simplified(&samples, 0.001);
+    assert_eq!(path.keys, vec![key(0.0, 0.5), key(2.0, 0.7), key(3.0, 0.7), key(4.0, 0.3)]);
+}
+
+#[test]
+fn test_center_interpolates_and_holds_at_ends() {
+    let path = PanPath { keys: vec![key(1.0, 0.2), key(3.0, 0.6)] };
+    assert_eq!(path.center_at(0.0), Some(0.2));
+    assert!((path.center_at(2.0).unwrap() - 0.4).abs() < 1e-9);
+    assert_eq!(path.center_at(5.0), Some(0.6));
+    assert_eq!(PanPath::default().center_at(1.0), None);
+}
+
+#[test]
+fn test_apply_slides_fill_placement_within_canvas() {
+    // 16:9 frame filled onto a 1080x1920 canvas: 3413 wide, centered
+    let fill = Placement { x: -1167, y: 0, width: 3413, height: 1920 };
+    let path = |center_x| PanPath { keys: vec![key(0.0, center_x)] };
+
+    assert_eq!(path(0.5).apply(fill, 1080, 0.0).x, -1167);
+    assert_eq!(path(0.25).apply(fill, 1080, 0.0).x, -313);
+    // Pans to the very edge stop where the frame still covers the canvas
+    assert_eq!(path(0.0).apply(fill, 1080, 0.0).x, 0);
+    assert_eq!(path(1.0).apply(fill, 1080, 0.0).x, 1080 - 3413);
+    // No keys, or a frame that isn't wider than the canvas: unchanged
+    assert_eq!(PanPath::default().apply(fill, 1080, 0.0), fill);
+    let fit = Placement { x: 0, y: 656, width: 1080, height: 608 };
+    assert_eq!(path(0.0).apply(fit, 1080, 0.0), fit);
+}
+
+#[test]
+fn test_visible_width_fraction() {
+    assert!((visible_width_fraction(1920, 1080, 1080, 1920) - 0.31640625).abs() < 1e-9);
+    assert_eq!(visible_width_fraction(1920, 1080, 1920, 1080), 1.0);
+    assert_eq!(visible_width_fraction(1080, 1920, 1920, 1080), 1.0);
+}
@@ -0,0 +1,37 @@
+use zeditor_core::project::Project;
+use zeditor_core::render_ranges::{self, DEFAULT_FILE_PATTERN};
+use zeditor_core::timeline::{TimeRange, TimelinePosition};
+
+fn range(start: f64, end: f64) -> TimeRange {
+    TimeRange::new(TimelinePosition::from_secs_f64(start), TimelinePosition::from_secs_f64(end)).unwrap()
+}
+
+#[test]
+fn test_file_name_fills_pattern() {
+    assert_eq!(render_ranges::file_name(DEFAULT_FILE_PATTERN, "Trip", "Teaser", 0), "Trip-Teaser");
+    assert_eq!(render_ranges::file_name("{index}_{name}", "Trip", "Reel", 2), "03_Reel");
+    // Path separators and reserved characters can't end up in the name
+    assert_eq!(render_ranges::file_name("{project}/{name}", "A:B", "why?", 0), "A_B_why_");
+    assert_eq!(render_ranges::file_name("  ", "Trip", "Reel", 4), "05");
+}
+
+#[test]
+fn test_render_ranges_add_remove_and_save() {
+    let mut project = Project::new("Trip");
+    let first = project.add_render_range("Teaser", range(0.0, 15.0));
+    let second = project.add_render_range("Reel", range(30.0, 90.0));
+    assert_eq!(project.render_ranges.len(), 2);
+
+    let loaded: Project = serde_json::from_str(&serde_json::to_string(&project).unwrap()).unwrap();
+    assert_eq!(loaded.render_ranges, project.render_ranges);
+
+    assert_eq!(project.remove_render_range(first).unwrap().name, "Teaser");
+    assert_eq!(project.render_ranges[0].id, second);
+    assert!(project.remove_render_range(first).is_err());
+
+    // Projects saved before render ranges existed load with none.
+    let mut value = serde_json::to_value(&project).unwrap();
+    value.as_object_mut().unwrap().remove("render_ranges");
+    let old: Project = serde_json::from_value(value).unwrap();
+    assert!(old.render_ranges.is_empty());
+}
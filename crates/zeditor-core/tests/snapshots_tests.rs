@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use uuid::Uuid;
+use zeditor_core::error::CoreError;
+use zeditor_core::project::Project;
+use zeditor_core::snapshots::ClipChangeKind;
+use zeditor_core::timeline::{Clip, TimeRange, TimelinePosition};
+
+fn secs(s: f64) -> TimelinePosition {
+    TimelinePosition::from_secs_f64(s)
+}
+
+/// Project with three 2s clips back to back on V1.
+fn project_with_clips() -> (Project, Vec<Uuid>) {
+    let mut project = Project::new("Snapshots");
+    let asset_id = Uuid::new_v4();
+    let ids = (0..3)
+        .map(|i| {
+            let clip = Clip::new(asset_id, secs(i as f64 * 2.0), TimeRange::new(secs(0.0), secs(2.0)).unwrap());
+            let id = clip.id;
+            project.timeline.add_clip(0, clip).unwrap();
+            id
+        })
+        .collect();
+    (project, ids)
+}
+
+#[test]
+fn test_snapshot_of_unchanged_timeline_has_no_diff() {
+    let (mut project, _) = project_with_clips();
+    let id = project.take_snapshot("v1 cut");
+    assert_eq!(project.snapshot(id).unwrap().name, "v1 cut");
+    assert!(project.diff_snapshot(id).unwrap().is_empty());
+}
+
+#[test]
+fn test_snapshot_diff_reports_moves_trims_adds_and_removals() {
+    let (mut project, ids) = project_with_clips();
+    let id = project.take_snapshot("v1 cut");
+
+    project.timeline.remove_clip_grouped(0, ids[0]).unwrap();
+    project.timeline.move_clip(0, ids[2], 0, secs(10.0)).unwrap();
+    project.timeline.resize_clip(0, ids[1], secs(3.0)).unwrap();
+    let added = Clip::new(Uuid::new_v4(), secs(6.0), TimeRange::new(secs(0.0), secs(1.0)).unwrap());
+    let added_id = added.id;
+    project.timeline.add_clip(0, added).unwrap();
+
+    let changes = project.diff_snapshot(id).unwrap();
+    let kinds: Vec<(Uuid, ClipChangeKind)> = changes.iter().map(|c| (c.clip_id, c.kind)).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (ids[0], ClipChangeKind::Removed),
+            (ids[1], ClipChangeKind::Changed { moved: false, trimmed: true, effects: false }),
+            (added_id, ClipChangeKind::Added),
+            (ids[2], ClipChangeKind::Changed { moved: true, trimmed: false, effects: false }),
+        ]
+    );
+    // Removed clips are located where they were in the snapshot
+    assert_eq!(changes[0].position, secs(0.0));
+    assert_eq!(changes[3].position.as_duration(), Duration::from_secs(10));
+}
+
+#[test]
+fn test_restore_snapshot_is_undoable_and_keeps_snapshot() {
+    let (mut project, ids) = project_with_clips();
+    let id = project.take_snapshot("v1 cut");
+    let original = project.timeline.clone();
+    project.timeline.remove_clip_grouped(0, ids[1]).unwrap();
+    let edited = project.timeline.clone();
+
+    project.restore_snapshot(id).unwrap();
+    assert_eq!(project.timeline, original);
+    assert_eq!(project.snapshots.len(), 1);
+
+    project.command_history.undo(&mut project.timeline).unwrap();
+    assert_eq!(project.timeline, edited);
+}
+
+#[test]
+fn test_snapshots_are_saved_with_project_and_removable() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("project.zpf");
+    let (mut project, _) = project_with_clips();
+    let id = project.take_snapshot("v1 cut");
+    project.save(&path).unwrap();
+
+    let mut loaded = Project::load(&path).unwrap();
+    assert_eq!(loaded.snapshots, project.snapshots);
+
+    assert_eq!(loaded.remove_snapshot(id).unwrap().name, "v1 cut");
+    assert!(loaded.snapshots.is_empty());
+    assert!(matches!(loaded.remove_snapshot(id), Err(CoreError::SnapshotNotFound(_))));
+    assert!(matches!(loaded.restore_snapshot(id), Err(CoreError::SnapshotNotFound(_))));
+}
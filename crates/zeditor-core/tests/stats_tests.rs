@@ -0,0 +1,59 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use zeditor_core::effects::{EffectInstance, EffectType};
+use zeditor_core::media::{MediaAsset, SourceLibrary};
+use zeditor_core::stats::{estimate_export_bytes, project_stats};
+use zeditor_core::timeline::*;
+
+fn make_clip(asset_id: uuid::Uuid, start_secs: f64, source_start: f64, source_end: f64) -> Clip {
+    let source_range = TimeRange::new(
+        TimelinePosition::from_secs_f64(source_start),
+        TimelinePosition::from_secs_f64(source_end),
+    )
+    .unwrap();
+    Clip::new(asset_id, TimelinePosition::from_secs_f64(start_secs), source_range)
+}
+
+#[test]
+fn test_project_stats_counts_clips_source_and_effects() {
+    let video = MediaAsset::new("a.mp4".into(), PathBuf::from("/a.mp4"), Duration::from_secs(10), 1920, 1080, 30.0, true);
+    let unused = MediaAsset::new("b.mp4".into(), PathBuf::from("/b.mp4"), Duration::from_secs(20), 1920, 1080, 30.0, true);
+    let still = MediaAsset::new("c.png".into(), PathBuf::from("/c.png"), Duration::ZERO, 1920, 1080, 0.0, false);
+    let (video_id, still_id) = (video.id, still.id);
+    let mut library = SourceLibrary::new();
+    library.import(video);
+    library.import(unused);
+    library.import(still);
+
+    let mut timeline = Timeline::new();
+    timeline.add_track("V1", TrackType::Video);
+    timeline.add_track("A1", TrackType::Audio);
+    let mut first = make_clip(video_id, 0.0, 0.0, 4.0);
+    first.effects.push(EffectInstance::new(EffectType::Grayscale));
+    first.effects.push(EffectInstance::new(EffectType::Brightness));
+    let mut second = make_clip(video_id, 4.0, 2.0, 6.0);
+    second.effects.push(EffectInstance::new(EffectType::Brightness));
+    timeline.add_clip(0, first).unwrap();
+    timeline.add_clip(0, second).unwrap();
+    timeline.add_clip(0, make_clip(still_id, 8.0, 0.0, 5.0)).unwrap();
+    timeline.add_clip(1, make_clip(video_id, 0.0, 0.0, 4.0)).unwrap();
+
+    let stats = project_stats(&timeline, &library);
+    assert_eq!(stats.duration, Duration::from_secs(13));
+    assert_eq!(stats.tracks.iter().map(|t| t.clip_count).collect::<Vec<_>>(), vec![3, 1]);
+    assert_eq!(stats.clip_count(), 4);
+    // 0-4s and 2-6s of the video overlap, and the still has no length of its own
+    assert_eq!(stats.source_used, Duration::from_secs(6));
+    assert_eq!(stats.source_imported, Duration::from_secs(30));
+    assert_eq!(stats.effect_counts, vec![(EffectType::Brightness, 2), (EffectType::Grayscale, 1)]);
+}
+
+#[test]
+fn test_export_size_estimate_scales_with_duration_and_crf() {
+    let minute = estimate_export_bytes(Duration::from_secs(60), 1920, 1080, 30.0, 23);
+    assert!((30_000_000..60_000_000).contains(&minute), "{minute}");
+    assert_eq!(estimate_export_bytes(Duration::from_secs(120), 1920, 1080, 30.0, 23), minute * 2);
+    let smaller = estimate_export_bytes(Duration::from_secs(60), 1920, 1080, 30.0, 29);
+    assert!(smaller < minute * 6 / 10);
+}
@@ -0,0 +1,42 @@
+use zeditor_core::timecode::{parse_timecode, to_timecode};
+use zeditor_core::timeline::TimelinePosition;
+
+fn secs(s: f64) -> TimelinePosition {
+    TimelinePosition::from_secs_f64(s)
+}
+
+#[test]
+fn test_to_timecode() {
+    assert_eq!(to_timecode(secs(83.4), 25.0), "00:01:23:10");
+    assert_eq!(to_timecode(secs(3725.0), 30.0), "01:02:05:00");
+    assert_eq!(to_timecode(TimelinePosition::zero(), 29.97), "00:00:00:00");
+}
+
+#[test]
+fn test_parse_full_and_partial_timecodes() {
+    let here = secs(10.0);
+    assert_eq!(parse_timecode("00:01:23:10", here, 25.0), Some(secs(83.4)));
+    // Fields fill from the right
+    assert_eq!(parse_timecode("1:10", here, 25.0), Some(secs(1.4)));
+    // Bare digits are read in pairs from the right
+    assert_eq!(parse_timecode("12310", here, 25.0), Some(secs(83.4)));
+    assert_eq!(parse_timecode("200", here, 25.0), Some(secs(2.0)));
+    // Round trip
+    assert_eq!(parse_timecode(&to_timecode(secs(83.4), 25.0), here, 25.0), Some(secs(83.4)));
+}
+
+#[test]
+fn test_parse_relative_offsets() {
+    let here = secs(10.0);
+    assert_eq!(parse_timecode("+200", here, 25.0), Some(secs(12.0)));
+    assert_eq!(parse_timecode("-1:00", here, 25.0), Some(secs(9.0)));
+    assert_eq!(parse_timecode("-20:00", here, 25.0), Some(TimelinePosition::zero()));
+}
+
+#[test]
+fn test_parse_rejects_garbage() {
+    let here = secs(10.0);
+    for input in ["", "+", "abc", "1:2:3:4:5", "1::2", "123456789", "1.5s"] {
+        assert_eq!(parse_timecode(input, here, 25.0), None, "{input:?}");
+    }
+}
@@ -1,7 +1,9 @@
 use std::time::Duration;
 
 use uuid::Uuid;
+use zeditor_core::audio::ChannelMap;
 use zeditor_core::effects::{EffectInstance, EffectType};
+use zeditor_core::scaler::CanvasFit;
 use zeditor_core::timeline::*;
 
 fn make_clip(asset_id: Uuid, start_secs: f64, duration_secs: f64) -> Clip {
@@ -688,6 +690,30 @@ fn test_preview_snap_uses_trimmed_edges() {
     assert!((snap.unwrap() - 3.0).abs() < 0.001);
 }
 
+#[test]
+fn test_preview_snap_edge_reports_edge() {
+    let mut timeline = Timeline::new();
+    timeline.add_track("Video 1", TrackType::Video);
+    timeline.add_clip(0, make_clip(Uuid::new_v4(), 5.0, 5.0)).unwrap();
+
+    // End snaps onto A's start at 5.0
+    let previews = timeline.tracks[0].preview_trim_overlaps(1.85, 4.85, None);
+    let (start, edge) = timeline.tracks[0].preview_snap_edge(1.85, 4.85, None, &previews, 0.2).unwrap();
+    assert!((start - 2.0).abs() < 0.001);
+    assert_eq!(edge, 5.0);
+}
+
+#[test]
+fn test_snap_to_points_picks_nearest_edge() {
+    let points = [4.0, 10.0];
+    // Start 3.9 is 0.1 from 4.0; end 9.95 is 0.05 from 10.0, which wins
+    let (start, point) = snap_to_points(3.9, 9.95, &points, 0.2).unwrap();
+    assert!((start - 3.95).abs() < 1e-9);
+    assert_eq!(point, 10.0);
+    assert_eq!(snap_to_points(3.7, 4.7, &points, 0.2), None);
+    assert_eq!(snap_to_points(3.9, 4.9, &[], 0.2), None);
+}
+
 // ===== Grouped operations tests =====
 
 #[test]
@@ -1082,7 +1108,6 @@ fn test_cut_preserves_effects() {
     assert_eq!(right.effects[0].get_float("x_offset"), Some(42.0));
 }
 
-#[test]
 // ===== Multiple tracks tests =====
 
 #[test]
@@ -1189,6 +1214,33 @@ fn test_insert_audio_track_above() {
     assert_eq!(timeline.tracks[2].name, "A2");
 }
 
+#[test]
+fn test_remove_track_unlinks_partners_and_renumbers() {
+    use zeditor_core::error::CoreError;
+
+    let mut timeline = Timeline::new();
+    timeline.add_track("V2", TrackType::Video);
+    timeline.add_track("V1", TrackType::Video);
+    timeline.add_track("A1", TrackType::Audio);
+    let link_id = Uuid::new_v4();
+    let mut video = make_clip(Uuid::new_v4(), 0.0, 5.0);
+    video.link_id = Some(link_id);
+    let mut audio = make_clip(video.asset_id, 0.0, 5.0);
+    audio.link_id = Some(link_id);
+    timeline.add_clip(1, video).unwrap();
+    timeline.add_clip(2, audio).unwrap();
+
+    let removed = timeline.remove_track(1).unwrap();
+    assert_eq!(removed.clips.len(), 1);
+    assert_eq!(timeline.tracks.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(), vec!["V1", "A1"]);
+    assert_eq!(timeline.tracks[1].clips[0].link_id, None);
+
+    assert!(matches!(timeline.remove_track(0), Err(CoreError::LastTrackOfType(TrackType::Video))));
+    timeline.insert_video_track_above(0).unwrap();
+    timeline.tracks[0].locked = true;
+    assert!(matches!(timeline.remove_track(0), Err(CoreError::TrackLocked(0))));
+}
+
 #[test]
 fn test_renumber_tracks() {
     let mut timeline = Timeline::new();
@@ -1425,3 +1477,916 @@ fn test_video_clips_at_position_partial_overlap() {
     assert!(timeline.tracks[v1_idx].clip_at(pos).is_none());
     assert!(timeline.tracks[v2_idx].clip_at(pos).is_some());
 }
+
+#[test]
+fn test_locked_track_rejects_edits() {
+    use zeditor_core::error::CoreError;
+
+    let mut timeline = Timeline::new();
+    timeline.add_track("Video 1", TrackType::Video);
+    let asset_id = Uuid::new_v4();
+    timeline.add_clip(0, make_clip(asset_id, 0.0, 5.0)).unwrap();
+    let clip_id = timeline.tracks[0].clips[0].id;
+    timeline.tracks[0].locked = true;
+
+    assert!(matches!(
+        timeline.add_clip(0, make_clip(asset_id, 6.0, 1.0)),
+        Err(CoreError::TrackLocked(0))
+    ));
+    assert!(timeline.cut_at(0, TimelinePosition::from_secs_f64(2.0)).is_err());
+    assert!(timeline.resize_clip(0, clip_id, TimelinePosition::from_secs_f64(3.0)).is_err());
+    assert!(timeline.remove_clip_grouped(0, clip_id).is_err());
+    assert_eq!(timeline.tracks[0].clips.len(), 1);
+
+    timeline.tracks[0].locked = false;
+    assert!(timeline.cut_at(0, TimelinePosition::from_secs_f64(2.0)).is_ok());
+}
+
+#[test]
+fn test_grouped_edit_blocked_by_locked_linked_track() {
+    let mut timeline = Timeline::new();
+    timeline.add_track("Video 1", TrackType::Video);
+    timeline.add_track("Audio 1", TrackType::Audio);
+    let source_range = TimeRange::new(TimelinePosition::zero(), TimelinePosition::from_secs_f64(5.0)).unwrap();
+    let (vid, _) = timeline
+        .add_clip_with_audio(0, 1, Uuid::new_v4(), TimelinePosition::zero(), source_range)
+        .unwrap();
+    timeline.tracks[1].locked = true;
+    let before = timeline.clone();
+
+    // Neither the video clip nor its locked audio partner moves.
+    assert!(timeline.move_clip_grouped(0, vid, 0, TimelinePosition::from_secs_f64(2.0)).is_err());
+    assert!(timeline.cut_at_grouped(0, TimelinePosition::from_secs_f64(1.0)).is_err());
+    assert_eq!(timeline, before);
+}
+
+#[test]
+fn test_mute_and_solo_audibility() {
+    let mut timeline = Timeline::new();
+    timeline.add_track("Video 1", TrackType::Video);
+    timeline.add_track("Audio 1", TrackType::Audio);
+    timeline.add_track("Audio 2", TrackType::Audio);
+
+    assert!(!timeline.is_track_audible(0));
+    assert!(timeline.is_track_audible(1));
+    assert!(timeline.is_track_audible(2));
+
+    timeline.tracks[1].muted = true;
+    assert!(!timeline.is_track_audible(1));
+
+    timeline.tracks[1].muted = false;
+    timeline.tracks[2].solo = true;
+    assert!(!timeline.is_track_audible(1));
+    assert!(timeline.is_track_audible(2));
+
+    // A muted solo track stays silent.
+    timeline.tracks[2].muted = true;
+    assert!(!timeline.is_track_audible(2));
+}
+
+#[test]
+fn test_hidden_video_track_not_visible() {
+    let mut timeline = Timeline::new();
+    timeline.add_track("Video 1", TrackType::Video);
+    timeline.add_track("Audio 1", TrackType::Audio);
+    assert!(timeline.is_track_visible(0));
+    assert!(!timeline.is_track_visible(1));
+    timeline.tracks[0].hidden = true;
+    assert!(!timeline.is_track_visible(0));
+}
+
+/// Video 1 + Audio 1 with a linked pair at 0s, and Video 2 with a lone clip at 6s.
+fn grouping_timeline() -> (Timeline, Uuid, Uuid, Uuid) {
+    let mut timeline = Timeline::new();
+    timeline.add_track("Video 2", TrackType::Video);
+    timeline.add_track("Video 1", TrackType::Video);
+    timeline.add_track("Audio 1", TrackType::Audio);
+    let source_range = TimeRange::new(TimelinePosition::zero(), TimelinePosition::from_secs_f64(5.0)).unwrap();
+    let (vid, aud) = timeline
+        .add_clip_with_audio(1, 2, Uuid::new_v4(), TimelinePosition::zero(), source_range)
+        .unwrap();
+    let title = make_clip(Uuid::new_v4(), 6.0, 2.0);
+    let title_id = title.id;
+    timeline.add_clip(0, title).unwrap();
+    (timeline, vid, aud, title_id)
+}
+
+#[test]
+fn test_group_clips_moves_members_and_linked_partners() {
+    let (mut timeline, vid, aud, title) = grouping_timeline();
+    let group_id = timeline.group_clips(&[(1, vid), (0, title)]).unwrap();
+    assert_eq!(timeline.tracks[0].get_clip(title).unwrap().group_id, Some(group_id));
+    assert_eq!(timeline.tracks[2].get_clip(aud).unwrap().group_id, None, "partners join via their link");
+
+    let mut companions = timeline.clip_companions(0, title);
+    companions.sort();
+    assert_eq!(companions, vec![(1, vid), (2, aud)]);
+
+    timeline.move_clip_grouped(0, title, 0, TimelinePosition::from_secs_f64(7.0)).unwrap();
+    assert_eq!(timeline.tracks[0].get_clip(title).unwrap().timeline_range.start, TimelinePosition::from_secs_f64(7.0));
+    assert_eq!(timeline.tracks[1].get_clip(vid).unwrap().timeline_range.start, TimelinePosition::from_secs_f64(1.0));
+    assert_eq!(timeline.tracks[2].get_clip(aud).unwrap().timeline_range.start, TimelinePosition::from_secs_f64(1.0));
+}
+
+#[test]
+fn test_grouped_members_on_one_track_move_without_trimming_each_other() {
+    let mut timeline = Timeline::new();
+    timeline.add_track("Video 1", TrackType::Video);
+    let a = make_clip(Uuid::new_v4(), 0.0, 2.0);
+    let b = make_clip(Uuid::new_v4(), 2.0, 2.0);
+    let (a_id, b_id) = (a.id, b.id);
+    timeline.add_clip(0, a).unwrap();
+    timeline.add_clip(0, b).unwrap();
+    timeline.group_clips(&[(0, a_id), (0, b_id)]).unwrap();
+
+    // Moving A right by 1s would overlap B's old spot if moved one at a time.
+    timeline.move_clip_grouped(0, a_id, 0, TimelinePosition::from_secs_f64(1.0)).unwrap();
+    let track = &timeline.tracks[0];
+    assert_eq!(track.clips.len(), 2);
+    assert_eq!(track.get_clip(a_id).unwrap().duration(), Duration::from_secs(2));
+    assert_eq!(track.get_clip(b_id).unwrap().timeline_range.start, TimelinePosition::from_secs_f64(3.0));
+}
+
+#[test]
+fn test_group_resize_and_remove_apply_to_members() {
+    let (mut timeline, vid, aud, title) = grouping_timeline();
+    timeline.group_clips(&[(1, vid), (0, title)]).unwrap();
+
+    timeline.resize_clip_grouped(0, title, TimelinePosition::from_secs_f64(7.0)).unwrap();
+    assert_eq!(timeline.tracks[1].get_clip(vid).unwrap().timeline_range.end, TimelinePosition::from_secs_f64(4.0));
+    assert_eq!(timeline.tracks[2].get_clip(aud).unwrap().timeline_range.end, TimelinePosition::from_secs_f64(4.0));
+
+    timeline.remove_clip_grouped(2, aud).unwrap();
+    assert!(timeline.tracks.iter().all(|t| t.clips.is_empty()));
+}
+
+#[test]
+fn test_group_clips_merges_existing_groups_and_ungroup_dissolves() {
+    let mut timeline = Timeline::new();
+    timeline.add_track("Video 1", TrackType::Video);
+    let clips: Vec<Clip> = (0..4).map(|i| make_clip(Uuid::new_v4(), i as f64 * 2.0, 1.0)).collect();
+    let ids: Vec<Uuid> = clips.iter().map(|c| c.id).collect();
+    for clip in clips {
+        timeline.add_clip(0, clip).unwrap();
+    }
+
+    assert!(matches!(
+        timeline.group_clips(&[(0, ids[0])]),
+        Err(zeditor_core::error::CoreError::GroupTooSmall)
+    ));
+    timeline.group_clips(&[(0, ids[0]), (0, ids[1])]).unwrap();
+    let merged = timeline.group_clips(&[(0, ids[1]), (0, ids[2])]).unwrap();
+    let grouped: Vec<Uuid> = timeline.tracks[0]
+        .clips
+        .iter()
+        .filter(|c| c.group_id == Some(merged))
+        .map(|c| c.id)
+        .collect();
+    assert_eq!(grouped, ids[..3].to_vec());
+
+    assert_eq!(timeline.ungroup_clips(&[(0, ids[2])]).unwrap(), 3);
+    assert!(timeline.tracks[0].clips.iter().all(|c| c.group_id.is_none()));
+    assert!(timeline.clip_companions(0, ids[0]).is_empty());
+}
+
+#[test]
+fn test_group_edit_blocked_by_locked_member_track() {
+    let (mut timeline, vid, _, title) = grouping_timeline();
+    timeline.group_clips(&[(1, vid), (0, title)]).unwrap();
+    timeline.tracks[0].locked = true;
+    let before = timeline.clone();
+
+    assert!(timeline.move_clip_grouped(1, vid, 1, TimelinePosition::from_secs_f64(2.0)).is_err());
+    assert!(timeline.remove_clip_grouped(1, vid).is_err());
+    assert_eq!(timeline, before);
+}
+
+#[test]
+fn test_cut_keeps_both_pieces_in_group() {
+    let (mut timeline, vid, _, title) = grouping_timeline();
+    let group_id = timeline.group_clips(&[(1, vid), (0, title)]).unwrap();
+    let pieces = timeline.cut_at_grouped(1, TimelinePosition::from_secs_f64(2.0)).unwrap();
+    let (left, right) = pieces[0];
+    for id in [left, right] {
+        assert_eq!(timeline.tracks[1].get_clip(id).unwrap().group_id, Some(group_id));
+    }
+    // The audio partner's pieces still come along through their link.
+    assert!(timeline.clip_companions(1, right).contains(&(2, pieces[1].1)));
+}
+
+#[test]
+fn test_clip_group_id_serializes_and_defaults() {
+    let (mut timeline, vid, _, title) = grouping_timeline();
+    let group_id = timeline.group_clips(&[(1, vid), (0, title)]).unwrap();
+    let json = serde_json::to_string(&timeline).unwrap();
+    let loaded: Timeline = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.tracks[0].get_clip(title).unwrap().group_id, Some(group_id));
+
+    // Clips saved before groups existed load ungrouped.
+    let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    value["tracks"][0]["clips"][0].as_object_mut().unwrap().remove("group_id");
+    let old: Timeline = serde_json::from_value(value).unwrap();
+    assert_eq!(old.tracks[0].get_clip(title).unwrap().group_id, None);
+}
+
+#[test]
+fn test_track_height_cycles_and_defaults() {
+    let mut height = TrackHeight::default();
+    for expected in [TrackHeight::Double, TrackHeight::Triple, TrackHeight::Normal] {
+        height = height.next();
+        assert_eq!(height, expected);
+    }
+
+    let mut timeline = Timeline::new();
+    timeline.add_track("V1", TrackType::Video);
+    timeline.tracks[0].height = TrackHeight::Triple;
+    let json = serde_json::to_string(&timeline).unwrap();
+    let loaded: Timeline = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.tracks[0].height, TrackHeight::Triple);
+
+    // Tracks saved before heights existed load at normal height.
+    let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    value["tracks"][0].as_object_mut().unwrap().remove("height");
+    let old: Timeline = serde_json::from_value(value).unwrap();
+    assert_eq!(old.tracks[0].height, TrackHeight::Normal);
+}
+
+#[test]
+fn test_extract_range_trims_and_shifts_clips() {
+    let mut timeline = Timeline::new();
+    timeline.add_track("V1", TrackType::Video);
+    timeline.add_track("A1", TrackType::Audio);
+    let asset_id = Uuid::new_v4();
+    let a = make_clip(asset_id, 0.0, 4.0);
+    let b = make_clip(asset_id, 5.0, 4.0);
+    let c = make_clip(asset_id, 0.0, 4.0);
+    let (a_id, b_id, c_id) = (a.id, b.id, c.id);
+    timeline.add_clip(0, a).unwrap();
+    timeline.add_clip(0, b).unwrap();
+    timeline.add_clip(1, c).unwrap();
+    timeline.add_clip(1, make_clip(asset_id, 7.0, 1.0)).unwrap();
+    // Double speed: 4s of source over 0..2
+    timeline.set_clip_speed_grouped(1, c_id, 2.0).unwrap();
+    timeline.tracks[1].muted = true;
+
+    let range = TimeRange::new(TimelinePosition::from_secs_f64(1.0), TimelinePosition::from_secs_f64(6.0)).unwrap();
+    let extracted = timeline.extract_range(range);
+    let span = |clip: &Clip| {
+        (
+            clip.timeline_range.start.as_secs_f64(),
+            clip.timeline_range.end.as_secs_f64(),
+            clip.source_range.start.as_secs_f64(),
+            clip.source_range.end.as_secs_f64(),
+        )
+    };
+    assert_eq!(span(extracted.tracks[0].get_clip(a_id).unwrap()), (0.0, 3.0, 1.0, 4.0));
+    assert_eq!(span(extracted.tracks[0].get_clip(b_id).unwrap()), (4.0, 5.0, 0.0, 1.0));
+    assert_eq!(span(extracted.tracks[1].get_clip(c_id).unwrap()), (0.0, 1.0, 2.0, 4.0));
+    // The clip at 7s is outside the range
+    assert_eq!(extracted.tracks[1].clips.len(), 1);
+    assert!(extracted.tracks[1].muted);
+    assert_eq!(extracted.duration(), Duration::from_secs(5));
+}
+
+#[test]
+fn test_markers_stay_ordered_and_load_from_old_files() {
+    let mut timeline = Timeline::new();
+    let late = timeline.add_marker(TimelinePosition::from_secs_f64(9.0), "Late");
+    timeline.add_marker(TimelinePosition::from_secs_f64(2.0), "Early");
+    let names: Vec<&str> = timeline.markers.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(names, vec!["Early", "Late"]);
+
+    let range = TimeRange::new(TimelinePosition::from_secs_f64(5.0), TimelinePosition::from_secs_f64(10.0)).unwrap();
+    let extracted = timeline.extract_range(range);
+    assert_eq!(extracted.markers.len(), 1);
+    assert_eq!(extracted.markers[0].position, TimelinePosition::from_secs_f64(4.0));
+
+    assert_eq!(timeline.remove_marker(late).unwrap().name, "Late");
+    assert!(timeline.remove_marker(late).is_err());
+
+    let mut value = serde_json::to_value(&timeline).unwrap();
+    value.as_object_mut().unwrap().remove("markers");
+    let old: Timeline = serde_json::from_value(value).unwrap();
+    assert!(old.markers.is_empty());
+}
+
+#[test]
+fn test_generate_guides_on_interval() {
+    let mut timeline = Timeline::new();
+    assert!(timeline.add_guide(TimelinePosition::from_secs_f64(1.0)));
+    assert!(!timeline.add_guide(TimelinePosition::from_secs_f64(1.0)));
+
+    // Every 0.5s over [0, 2]: 0, 0.5, 1 (already there), 1.5, 2
+    let added = timeline.generate_guides(
+        TimelinePosition::zero(),
+        TimelinePosition::from_secs_f64(2.0),
+        Duration::from_millis(500),
+    );
+    assert_eq!(added, 4);
+    let secs: Vec<f64> = timeline.guides.iter().map(|g| g.as_secs_f64()).collect();
+    assert_eq!(secs, vec![0.0, 0.5, 1.0, 1.5, 2.0]);
+    assert_eq!(timeline.generate_guides(TimelinePosition::zero(), TimelinePosition::from_secs_f64(2.0), Duration::ZERO), 0);
+
+    let range = TimeRange::new(TimelinePosition::from_secs_f64(1.0), TimelinePosition::from_secs_f64(2.0)).unwrap();
+    let extracted: Vec<f64> = timeline.extract_range(range).guides.iter().map(|g| g.as_secs_f64()).collect();
+    assert_eq!(extracted, vec![0.0, 0.5]);
+
+    let mut value = serde_json::to_value(&timeline).unwrap();
+    value.as_object_mut().unwrap().remove("guides");
+    let old: Timeline = serde_json::from_value(value).unwrap();
+    assert!(old.guides.is_empty());
+}
+
+/// Camera clip (V1 + A1, linked) at 2s and a recorder clip on A2 at 10s.
+fn sync_timeline() -> (Timeline, Uuid, Uuid, Uuid) {
+    let mut timeline = Timeline::new();
+    timeline.add_track("Video 1", TrackType::Video);
+    timeline.add_track("Audio 1", TrackType::Audio);
+    timeline.add_track("Audio 2", TrackType::Audio);
+    let source_range = TimeRange::new(TimelinePosition::zero(), TimelinePosition::from_secs_f64(5.0)).unwrap();
+    let (vid, aud) = timeline
+        .add_clip_with_audio(0, 1, Uuid::new_v4(), TimelinePosition::from_secs_f64(2.0), source_range)
+        .unwrap();
+    let recorder = make_clip(Uuid::new_v4(), 10.0, 8.0);
+    let recorder_id = recorder.id;
+    timeline.add_clip(2, recorder).unwrap();
+    (timeline, vid, aud, recorder_id)
+}
+
+#[test]
+fn test_set_channel_map_follows_linked_clips() {
+    let (mut timeline, vid, aud, recorder) = sync_timeline();
+    timeline.set_channel_map(0, vid, ChannelMap::LeftOnly).unwrap();
+    assert_eq!(timeline.tracks[1].get_clip(aud).unwrap().channel_map, ChannelMap::LeftOnly);
+    assert_eq!(timeline.tracks[2].get_clip(recorder).unwrap().channel_map, ChannelMap::Stereo);
+
+    timeline.tracks[1].locked = true;
+    let before = timeline.clone();
+    assert!(timeline.set_channel_map(0, vid, ChannelMap::Swap).is_err());
+    assert_eq!(timeline, before);
+}
+
+#[test]
+fn test_set_preserve_pitch_follows_linked_clips() {
+    let (mut timeline, vid, aud, recorder) = sync_timeline();
+    assert!(!timeline.tracks[1].get_clip(aud).unwrap().preserve_pitch);
+    timeline.set_preserve_pitch(0, vid, true).unwrap();
+    assert!(timeline.tracks[1].get_clip(aud).unwrap().preserve_pitch);
+    assert!(!timeline.tracks[2].get_clip(recorder).unwrap().preserve_pitch);
+
+    // Cut halves keep the setting
+    timeline.cut_at(1, TimelinePosition::from_secs_f64(3.0)).unwrap();
+    assert!(timeline.tracks[1].clips.iter().all(|c| c.preserve_pitch));
+
+    timeline.tracks[1].locked = true;
+    let before = timeline.clone();
+    assert!(timeline.set_preserve_pitch(0, vid, false).is_err());
+    assert_eq!(timeline, before);
+}
+
+#[test]
+fn test_set_source_range_trims_linked_clips() {
+    let (mut timeline, vid, aud, _) = sync_timeline();
+    let source = TimeRange::new(TimelinePosition::from_secs_f64(1.0), TimelinePosition::from_secs_f64(4.0)).unwrap();
+    timeline.set_source_range(0, vid, source).unwrap();
+    for (track, id) in [(0, vid), (1, aud)] {
+        let clip = timeline.tracks[track].get_clip(id).unwrap();
+        assert_eq!(clip.source_range, source);
+        assert_eq!(clip.timeline_range.start, TimelinePosition::from_secs_f64(2.0));
+        assert_eq!(clip.timeline_range.end, TimelinePosition::from_secs_f64(5.0));
+    }
+
+    // Lengthening the source into the next clip on the track is refused.
+    timeline.add_clip(0, make_clip(Uuid::new_v4(), 8.0, 1.0)).unwrap();
+    let before = timeline.clone();
+    let longer = TimeRange::new(TimelinePosition::zero(), TimelinePosition::from_secs_f64(7.0)).unwrap();
+    let err = timeline.set_source_range(1, aud, longer).unwrap_err();
+    assert!(matches!(err, zeditor_core::error::CoreError::ClipOverlap { .. }), "{err:?}");
+    assert_eq!(timeline, before);
+
+    timeline.tracks[1].locked = true;
+    let before = timeline.clone();
+    assert!(timeline.set_source_range(0, vid, longer).is_err());
+    assert_eq!(timeline, before);
+}
+
+#[test]
+fn test_split_dual_mono_puts_right_channel_on_free_track_below() {
+    let (mut timeline, vid, aud, _) = sync_timeline();
+    let (track, right) = timeline.split_dual_mono(0, vid).unwrap();
+    // A2's recorder clip starts after the camera clip ends, so it has room
+    assert_eq!(track, 2);
+    let left = timeline.tracks[1].get_clip(aud).unwrap().clone();
+    let right = timeline.tracks[2].get_clip(right).unwrap().clone();
+    assert_eq!(left.channel_map, ChannelMap::LeftOnly);
+    assert_eq!(right.channel_map, ChannelMap::RightOnly);
+    assert_eq!(right.timeline_range, left.timeline_range);
+    assert_eq!(right.source_range, left.source_range);
+    let mut linked = timeline.find_linked_clips(left.link_id.unwrap());
+    linked.sort();
+    let mut expected = vec![(0, vid), (1, aud), (2, right.id)];
+    expected.sort();
+    assert_eq!(linked, expected);
+
+    // Already split
+    let err = timeline.split_dual_mono(1, aud).unwrap_err();
+    assert!(matches!(err, zeditor_core::error::CoreError::NoStereoAudio(_)), "{err:?}");
+}
+
+#[test]
+fn test_split_dual_mono_adds_track_and_links_unlinked_audio() {
+    let (mut timeline, _, _, recorder) = sync_timeline();
+    let (track, right) = timeline.split_dual_mono(2, recorder).unwrap();
+    assert_eq!(track, 3);
+    assert_eq!(timeline.tracks[3].name, "A3");
+    assert_eq!(timeline.tracks[3].track_type, TrackType::Audio);
+    let link_id = timeline.tracks[2].get_clip(recorder).unwrap().link_id;
+    assert!(link_id.is_some());
+    assert_eq!(timeline.tracks[3].get_clip(right).unwrap().link_id, link_id);
+}
+
+#[test]
+fn test_clip_channel_map_serializes_and_defaults() {
+    let (mut timeline, _, aud, _) = sync_timeline();
+    timeline.set_channel_map(1, aud, ChannelMap::MixToMono).unwrap();
+    let json = serde_json::to_string(&timeline).unwrap();
+    let loaded: Timeline = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.tracks[1].get_clip(aud).unwrap().channel_map, ChannelMap::MixToMono);
+
+    // Clips saved before channel routing existed play as stereo.
+    let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    value["tracks"][1]["clips"][0].as_object_mut().unwrap().remove("channel_map");
+    let old: Timeline = serde_json::from_value(value).unwrap();
+    assert_eq!(old.tracks[1].get_clip(aud).unwrap().channel_map, ChannelMap::Stereo);
+}
+
+#[test]
+fn test_clip_markers_travel_with_clip() {
+    let (mut timeline, vid, aud, _) = sync_timeline();
+    let secs = TimelinePosition::from_secs_f64;
+    // 3.5s into the timeline is 1.5s into the source
+    let id = timeline.add_clip_marker(0, vid, secs(3.5), "Best take").unwrap();
+    let err = timeline.add_clip_marker(0, vid, secs(9.0), "Outside").unwrap_err();
+    assert!(matches!(err, zeditor_core::error::CoreError::MarkerOutsideClip { .. }), "{err:?}");
+    let audio = timeline.tracks[1].get_clip(aud).unwrap();
+    assert_eq!(audio.markers[0].source_time, secs(1.5));
+
+    timeline.move_clip(0, vid, 0, secs(4.0)).unwrap();
+    let clip = timeline.tracks[0].get_clip(vid).unwrap();
+    let visible: Vec<f64> = clip.visible_markers().map(|(_, t)| t).collect();
+    assert_eq!(visible, vec![5.5]);
+
+    // Trimmed off the start: kept but hidden
+    let source = TimeRange::new(secs(2.0), secs(5.0)).unwrap();
+    timeline.set_source_range(0, vid, source).unwrap();
+    assert_eq!(timeline.tracks[0].get_clip(vid).unwrap().visible_markers().count(), 0);
+    assert_eq!(timeline.tracks[0].get_clip(vid).unwrap().markers.len(), 1);
+
+    timeline.rename_clip_marker(0, vid, id, "Keeper").unwrap();
+    assert_eq!(timeline.tracks[1].get_clip(aud).unwrap().markers[0].name, "Keeper");
+    timeline.remove_clip_marker(0, vid, id).unwrap();
+    assert!(timeline.tracks[1].get_clip(aud).unwrap().markers.is_empty());
+    assert!(timeline.remove_clip_marker(0, vid, id).is_err());
+}
+
+#[test]
+fn test_sync_clip_places_and_links_recording() {
+    let (mut timeline, vid, aud, recorder) = sync_timeline();
+    let link_id = timeline.sync_clip((0, vid), (2, recorder), 0.5).unwrap();
+
+    let clip = timeline.tracks[2].get_clip(recorder).unwrap();
+    assert_eq!(clip.timeline_range.start, TimelinePosition::from_secs_f64(2.5));
+    assert_eq!(clip.duration(), Duration::from_secs(8));
+    assert_eq!(clip.link_id, Some(link_id));
+    assert_eq!(clip.group_id, None);
+    // Joins the camera clip's existing link with its scratch audio
+    assert_eq!(timeline.tracks[0].get_clip(vid).unwrap().link_id, Some(link_id));
+    assert_eq!(timeline.tracks[1].get_clip(aud).unwrap().link_id, Some(link_id));
+
+    // The recording now follows the camera clip and its scratch audio.
+    timeline.move_clip_grouped(0, vid, 0, TimelinePosition::from_secs_f64(4.0)).unwrap();
+    assert_eq!(timeline.tracks[1].get_clip(aud).unwrap().timeline_range.start, TimelinePosition::from_secs_f64(4.0));
+    assert_eq!(timeline.tracks[2].get_clip(recorder).unwrap().timeline_range.start, TimelinePosition::from_secs_f64(4.5));
+}
+
+#[test]
+fn test_sync_clip_refuses_a_recording_with_linked_partners() {
+    let (mut timeline, vid, _, _) = sync_timeline();
+    // A second camera clip with its own audio, linked across V1 and A2
+    let source_range = TimeRange::new(TimelinePosition::zero(), TimelinePosition::from_secs_f64(3.0)).unwrap();
+    let (other_vid, other_aud) = timeline
+        .add_clip_with_audio(0, 2, Uuid::new_v4(), TimelinePosition::from_secs_f64(20.0), source_range)
+        .unwrap();
+    let before = timeline.clone();
+
+    let err = timeline.sync_clip((0, vid), (2, other_aud), 0.5).unwrap_err();
+    assert!(matches!(err, zeditor_core::error::CoreError::SyncClipNotStandalone(id) if id == other_aud), "{err:?}");
+    assert_eq!(timeline, before);
+    assert_eq!(timeline.clip_companions(0, other_vid), vec![(2, other_aud)]);
+}
+
+#[test]
+fn test_sync_clip_refuses_a_clip_on_the_reference_track() {
+    let (mut timeline, vid, aud, _) = sync_timeline();
+    // A loose clip on the scratch audio's track would trim it when placed
+    let loose = make_clip(Uuid::new_v4(), 12.0, 4.0);
+    let loose_id = loose.id;
+    timeline.add_clip(1, loose).unwrap();
+    let before = timeline.clone();
+
+    let err = timeline.sync_clip((0, vid), (1, loose_id), 0.0).unwrap_err();
+    assert!(matches!(err, zeditor_core::error::CoreError::SyncSharesTrack(id) if id == loose_id), "{err:?}");
+    let err = timeline.sync_clip((1, aud), (1, loose_id), 0.0).unwrap_err();
+    assert!(matches!(err, zeditor_core::error::CoreError::SyncSharesTrack(_)), "{err:?}");
+    assert_eq!(timeline, before);
+}
+
+#[test]
+fn test_sync_clip_trims_head_that_would_start_before_zero() {
+    let (mut timeline, vid, _, recorder) = sync_timeline();
+    // Recorder rolled 3s before the camera, which sits at 2s.
+    timeline.sync_clip((0, vid), (2, recorder), -3.0).unwrap();
+
+    let clip = timeline.tracks[2].get_clip(recorder).unwrap();
+    assert_eq!(clip.timeline_range.start, TimelinePosition::zero());
+    assert_eq!(clip.source_range.start, TimelinePosition::from_secs_f64(1.0));
+    assert_eq!(clip.duration(), Duration::from_secs(7));
+
+    // Nothing left after the trim: refused without changing anything.
+    let (mut timeline, vid, _, recorder) = sync_timeline();
+    let before = timeline.clone();
+    let err = timeline.sync_clip((0, vid), (2, recorder), -20.0).unwrap_err();
+    assert!(matches!(err, zeditor_core::error::CoreError::SyncBeforeTimelineStart(id) if id == recorder));
+    assert_eq!(timeline, before);
+}
+
+#[test]
+fn test_ripple_delete_range_cuts_and_closes_gap_on_all_tracks() {
+    let mut timeline = Timeline::new();
+    timeline.add_track("V1", TrackType::Video);
+    timeline.add_track("A1", TrackType::Audio);
+    let asset_id = Uuid::new_v4();
+    let source = TimeRange::new(TimelinePosition::zero(), TimelinePosition::from_secs_f64(10.0)).unwrap();
+    timeline
+        .add_clip_with_audio(0, 1, asset_id, TimelinePosition::zero(), source)
+        .unwrap();
+    timeline.add_clip(0, make_clip(asset_id, 12.0, 2.0)).unwrap();
+
+    let range = TimeRange::new(TimelinePosition::from_secs_f64(3.0), TimelinePosition::from_secs_f64(4.5)).unwrap();
+    timeline.ripple_delete_range(range).unwrap();
+
+    for track in &timeline.tracks {
+        let mut clips: Vec<_> = track.clips.iter().collect();
+        clips.sort_by_key(|c| c.timeline_range.start);
+        assert_eq!(clips[0].timeline_range.end, TimelinePosition::from_secs_f64(3.0));
+        assert_eq!(clips[1].timeline_range.start, TimelinePosition::from_secs_f64(3.0));
+        assert_eq!(clips[1].source_range.start, TimelinePosition::from_secs_f64(4.5));
+    }
+    assert_eq!(timeline.tracks[0].clips.len(), 3);
+    assert!(timeline.tracks[0]
+        .clips
+        .iter()
+        .any(|c| c.timeline_range.start == TimelinePosition::from_secs_f64(10.5)));
+
+    // Each side of the cut is still linked to its partner, and only to it.
+    let video_right = timeline.tracks[0].clip_at(TimelinePosition::from_secs_f64(4.0)).unwrap();
+    let audio_right = timeline.tracks[1].clip_at(TimelinePosition::from_secs_f64(4.0)).unwrap();
+    let audio_left = timeline.tracks[1].clip_at(TimelinePosition::from_secs_f64(1.0)).unwrap();
+    assert_eq!(video_right.link_id, audio_right.link_id);
+    assert_ne!(audio_left.link_id, audio_right.link_id);
+}
+
+#[test]
+fn test_ripple_delete_range_refuses_locked_track() {
+    let mut timeline = Timeline::new();
+    timeline.add_track("V1", TrackType::Video);
+    timeline.add_track("A1", TrackType::Audio);
+    let asset_id = Uuid::new_v4();
+    timeline.add_clip(0, make_clip(asset_id, 0.0, 5.0)).unwrap();
+    timeline.add_clip(1, make_clip(asset_id, 0.0, 5.0)).unwrap();
+    timeline.tracks[1].locked = true;
+
+    timeline.add_marker(TimelinePosition::from_secs_f64(3.0), "After");
+    timeline.add_guide(TimelinePosition::from_secs_f64(1.5));
+    let before = timeline.clone();
+
+    let range = TimeRange::new(TimelinePosition::from_secs_f64(1.0), TimelinePosition::from_secs_f64(2.0)).unwrap();
+    let err = timeline.ripple_delete_range(range).unwrap_err();
+    assert!(matches!(err, zeditor_core::error::CoreError::TrackLocked(1)));
+    // Nothing is cut, moved or dropped on any track, marker or guide
+    assert_eq!(timeline, before);
+}
+
+#[test]
+fn test_ripple_delete_range_moves_markers_and_guides_and_drops_those_inside() {
+    let mut timeline = Timeline::new();
+    timeline.add_track("V1", TrackType::Video);
+    timeline.add_clip(0, make_clip(Uuid::new_v4(), 0.0, 10.0)).unwrap();
+    let secs = TimelinePosition::from_secs_f64;
+    timeline.add_marker(secs(1.0), "Before");
+    timeline.add_marker(secs(3.5), "Inside");
+    timeline.add_marker(secs(6.0), "After");
+    timeline.add_guide(secs(3.0));
+    timeline.add_guide(secs(5.0));
+    timeline.add_guide(secs(8.0));
+
+    let range = TimeRange::new(secs(3.0), secs(5.0)).unwrap();
+    timeline.ripple_delete_range(range).unwrap();
+
+    let markers: Vec<_> = timeline.markers.iter().map(|m| (m.name.as_str(), m.position)).collect();
+    assert_eq!(markers, vec![("Before", secs(1.0)), ("After", secs(4.0))]);
+    // The guide at the range start goes with it; the one at its end moves to its start
+    assert_eq!(timeline.guides, vec![secs(3.0), secs(6.0)]);
+}
+
+#[test]
+fn test_insert_time_splits_and_shifts_unlocked_tracks() {
+    let mut timeline = Timeline::new();
+    timeline.add_track("V1", TrackType::Video);
+    timeline.add_track("A1", TrackType::Audio);
+    timeline.add_track("V2", TrackType::Video);
+    let asset_id = Uuid::new_v4();
+    let source = TimeRange::new(TimelinePosition::zero(), TimelinePosition::from_secs_f64(10.0)).unwrap();
+    timeline
+        .add_clip_with_audio(0, 1, asset_id, TimelinePosition::zero(), source)
+        .unwrap();
+    timeline.add_clip(2, make_clip(asset_id, 2.0, 6.0)).unwrap();
+    timeline.tracks[2].locked = true;
+
+    let secs = TimelinePosition::from_secs_f64;
+    timeline.insert_time(secs(4.0), Duration::from_secs(2)).unwrap();
+
+    for track in &timeline.tracks[..2] {
+        assert_eq!(track.clips.len(), 2);
+        assert_eq!(track.clip_at(secs(1.0)).unwrap().timeline_range.end, secs(4.0));
+        assert!(track.clip_at(secs(5.0)).is_none());
+        let right = track.clip_at(secs(6.0)).unwrap();
+        assert_eq!(right.timeline_range.end, secs(12.0));
+        assert_eq!(right.source_range.start, secs(4.0));
+    }
+    let video_right = timeline.tracks[0].clip_at(secs(6.0)).unwrap();
+    let audio_right = timeline.tracks[1].clip_at(secs(6.0)).unwrap();
+    assert_eq!(video_right.link_id, audio_right.link_id);
+
+    // The locked track is neither cut nor moved
+    assert_eq!(timeline.tracks[2].clips.len(), 1);
+    assert_eq!(timeline.tracks[2].clips[0].timeline_range.start, secs(2.0));
+}
+
+#[test]
+fn test_insert_time_moves_markers_and_guides_after_it() {
+    let mut timeline = Timeline::new();
+    timeline.add_track("V1", TrackType::Video);
+    timeline.add_clip(0, make_clip(Uuid::new_v4(), 5.0, 2.0)).unwrap();
+    let secs = TimelinePosition::from_secs_f64;
+    timeline.add_marker(secs(1.0), "Before");
+    timeline.add_marker(secs(5.0), "Clip start");
+    timeline.add_guide(secs(3.0));
+    timeline.add_guide(secs(5.0));
+
+    timeline.insert_time(secs(4.0), Duration::from_secs(2)).unwrap();
+
+    let markers: Vec<_> = timeline.markers.iter().map(|m| m.position).collect();
+    assert_eq!(markers, vec![secs(1.0), secs(7.0)]);
+    assert_eq!(timeline.guides, vec![secs(3.0), secs(7.0)]);
+    assert_eq!(timeline.tracks[0].clips[0].timeline_range.start, secs(7.0));
+}
+
+#[test]
+fn test_track_gap_at() {
+    let mut track = Track::video("V1");
+    let asset_id = Uuid::new_v4();
+    track.add_clip(make_clip(asset_id, 2.0, 3.0)).unwrap();
+    track.add_clip(make_clip(asset_id, 8.0, 2.0)).unwrap();
+
+    let secs = TimelinePosition::from_secs_f64;
+    assert_eq!(track.gap_at(secs(6.0)), Some(TimeRange::new(secs(5.0), secs(8.0)).unwrap()));
+    assert_eq!(track.gap_at(secs(5.0)), Some(TimeRange::new(secs(5.0), secs(8.0)).unwrap()));
+    assert_eq!(track.gap_at(secs(1.0)), Some(TimeRange::new(secs(0.0), secs(2.0)).unwrap()));
+    assert_eq!(track.gap_at(secs(3.0)), None);
+    assert_eq!(track.gap_at(secs(11.0)), None);
+}
+
+#[test]
+fn test_retime_kind_classifies_speed_and_frame_holds() {
+    let secs = TimelinePosition::from_secs_f64;
+    let asset_id = Uuid::new_v4();
+    let clip = |source_secs: f64, timeline_secs: f64| {
+        let mut clip = make_clip(asset_id, 0.0, source_secs);
+        clip.timeline_range = TimeRange::new(secs(0.0), secs(timeline_secs)).unwrap();
+        clip
+    };
+    assert_eq!(clip(2.0, 2.0).retime_kind(), RetimeKind::Normal);
+    assert_eq!(clip(2.0, 4.0).retime_kind(), RetimeKind::Slow);
+    assert_eq!(clip(4.0, 2.0).retime_kind(), RetimeKind::Fast);
+    assert_eq!(clip(1.0 / 30.0, 3.0).retime_kind(), RetimeKind::Hold);
+}
+
+#[test]
+fn test_fit_to_fill_retimes_source_into_gap() {
+    let mut timeline = Timeline::new();
+    timeline.add_track("V1", TrackType::Video);
+    timeline.add_track("A1", TrackType::Audio);
+    let asset_id = Uuid::new_v4();
+    let secs = TimelinePosition::from_secs_f64;
+    let gap = TimeRange::new(secs(2.0), secs(6.0)).unwrap();
+    let source = TimeRange::new(secs(10.0), secs(12.0)).unwrap();
+
+    let clip_id = timeline.fit_to_fill(0, Some(1), asset_id, source, gap).unwrap();
+    let clip = timeline.tracks[0].get_clip(clip_id).unwrap();
+    assert_eq!(clip.timeline_range, gap);
+    assert!((clip.speed() - 0.5).abs() < 1e-9);
+    assert!(clip.is_retimed());
+    assert!((clip.source_secs_at(secs(4.0)) - 11.0).abs() < 1e-9);
+    let audio = &timeline.tracks[1].clips[0];
+    assert_eq!(audio.timeline_range, gap);
+    assert_eq!(audio.link_id, clip.link_id);
+
+    // Cutting a retimed clip splits the source at the same speed
+    let (left, right) = timeline.cut_at(0, secs(3.0)).unwrap();
+    let left = timeline.tracks[0].get_clip(left).unwrap();
+    let right = timeline.tracks[0].get_clip(right).unwrap();
+    assert!((left.source_range.end.as_secs_f64() - 10.5).abs() < 1e-9);
+    assert!((right.source_range.start.as_secs_f64() - 10.5).abs() < 1e-9);
+    assert!((right.speed() - 0.5).abs() < 1e-6);
+
+    // An occupied gap is refused
+    let err = timeline.fit_to_fill(0, None, asset_id, source, gap).unwrap_err();
+    assert!(matches!(err, zeditor_core::error::CoreError::ClipOverlap { .. }));
+}
+
+/// Linked 4s V1/A1 pair at 0s followed by a linked 2s pair at 4s and an
+/// unlinked 2s clip on V2 at 6s. Returns (timeline, first video clip, next video clip).
+fn ripple_timeline() -> (Timeline, Uuid, Uuid) {
+    let mut timeline = Timeline::new();
+    timeline.add_track("V1", TrackType::Video);
+    timeline.add_track("A1", TrackType::Audio);
+    timeline.add_track("V2", TrackType::Video);
+    let asset_id = Uuid::new_v4();
+    let secs = TimelinePosition::from_secs_f64;
+    let (first, _) = timeline
+        .add_clip_with_audio(0, 1, asset_id, secs(0.0), TimeRange::new(secs(0.0), secs(4.0)).unwrap())
+        .unwrap();
+    let (next, _) = timeline
+        .add_clip_with_audio(0, 1, asset_id, secs(4.0), TimeRange::new(secs(0.0), secs(2.0)).unwrap())
+        .unwrap();
+    timeline.add_clip(2, make_clip(asset_id, 6.0, 2.0)).unwrap();
+    (timeline, first, next)
+}
+
+fn starts(timeline: &Timeline, track: usize) -> Vec<f64> {
+    timeline.tracks[track].clips.iter().map(|c| c.timeline_range.start.as_secs_f64()).collect()
+}
+
+#[test]
+fn test_resize_clip_ripple_shifts_downstream_on_linked_tracks() {
+    let (mut timeline, first, next) = ripple_timeline();
+
+    timeline.resize_clip_ripple(0, first, TimelinePosition::from_secs_f64(3.0)).unwrap();
+    assert_eq!(starts(&timeline, 0), vec![0.0, 3.0]);
+    assert_eq!(starts(&timeline, 1), vec![0.0, 3.0]);
+    assert_eq!(starts(&timeline, 2), vec![6.0], "unrelated tracks stay put");
+    assert_eq!(timeline.tracks[0].get_clip(next).unwrap().duration(), Duration::from_secs(2));
+
+    // Growing pushes the next clip instead of failing on the overlap.
+    timeline.resize_clip_ripple(0, first, TimelinePosition::from_secs_f64(5.0)).unwrap();
+    assert_eq!(starts(&timeline, 0), vec![0.0, 5.0]);
+    assert_eq!(starts(&timeline, 1), vec![0.0, 5.0]);
+}
+
+#[test]
+fn test_remove_clip_ripple_closes_gap() {
+    let (mut timeline, first, _) = ripple_timeline();
+    timeline.remove_clip_ripple(0, first).unwrap();
+    assert_eq!(starts(&timeline, 0), vec![0.0]);
+    assert_eq!(starts(&timeline, 1), vec![0.0]);
+    assert_eq!(starts(&timeline, 2), vec![6.0]);
+}
+
+#[test]
+fn test_ripple_edit_fails_without_changes_on_overlap() {
+    let mut timeline = Timeline::new();
+    timeline.add_track("V1", TrackType::Video);
+    timeline.add_track("A1", TrackType::Audio);
+    let asset_id = Uuid::new_v4();
+    let link_id = Uuid::new_v4();
+    let mut video = make_clip(asset_id, 0.0, 4.0);
+    let mut audio = make_clip(asset_id, 0.0, 2.0);
+    video.link_id = Some(link_id);
+    audio.link_id = Some(link_id);
+    let video_id = video.id;
+    timeline.add_clip(0, video).unwrap();
+    timeline.add_clip(1, audio).unwrap();
+    timeline.add_clip(0, make_clip(asset_id, 4.0, 2.0)).unwrap();
+    // Unrelated audio in the first clip's range, plus some after it
+    timeline.add_clip(1, make_clip(asset_id, 2.0, 1.5)).unwrap();
+    timeline.add_clip(1, make_clip(asset_id, 5.0, 1.0)).unwrap();
+    let before = timeline.clone();
+
+    let err = timeline.remove_clip_ripple(0, video_id).unwrap_err();
+    assert!(matches!(err, zeditor_core::error::CoreError::ClipOverlap { .. }), "{err:?}");
+    assert_eq!(timeline, before);
+}
+
+#[test]
+fn test_set_clip_speed_retimes_linked_clips() {
+    let (mut timeline, first, _) = ripple_timeline();
+    assert!(timeline.set_clip_speed_grouped(0, first, 0.0).is_err());
+    assert!(matches!(
+        timeline.set_clip_speed_grouped(0, first, f64::NAN),
+        Err(zeditor_core::error::CoreError::InvalidSpeed(_))
+    ));
+
+    timeline.set_clip_speed_grouped(0, first, 2.0).unwrap();
+    for track in 0..2 {
+        let clip = &timeline.tracks[track].clips[0];
+        assert_eq!(clip.duration(), Duration::from_secs(2));
+        assert_eq!(clip.source_range.duration(), Duration::from_secs(4));
+        assert!((clip.speed() - 2.0).abs() < 1e-9);
+    }
+    assert_eq!(starts(&timeline, 0), vec![0.0, 4.0], "without ripple a gap opens");
+
+    // Slowing down past the next clip needs ripple.
+    let err = timeline.set_clip_speed_grouped(0, first, 0.5).unwrap_err();
+    assert!(matches!(err, zeditor_core::error::CoreError::ClipOverlap { .. }));
+    timeline.set_clip_speed_ripple(0, first, 0.5).unwrap();
+    assert_eq!(timeline.tracks[0].clips[0].duration(), Duration::from_secs(8));
+    // Downstream clips move by the change in length, keeping the earlier gap.
+    assert_eq!(starts(&timeline, 0), vec![0.0, 10.0]);
+    assert_eq!(starts(&timeline, 1), vec![0.0, 10.0]);
+    assert_eq!(starts(&timeline, 2), vec![6.0]);
+}
+
+#[test]
+fn test_rate_conform_reinterpret_retimes_linked_clips() {
+    let (mut timeline, vid, aud, _) = sync_timeline();
+    // 5s of 24fps source reinterpreted at 30fps plays in 4s
+    timeline.set_rate_conform(0, vid, RateConform::Reinterpret, 24.0, 30.0).unwrap();
+    for (track, id) in [(0, vid), (1, aud)] {
+        let clip = timeline.tracks[track].get_clip(id).unwrap();
+        assert_eq!(clip.rate_conform, RateConform::Reinterpret);
+        assert!((clip.duration().as_secs_f64() - 4.0).abs() < 1e-6);
+    }
+
+    // One source frame per project frame, picked by index
+    let clip = timeline.tracks[0].get_clip(vid).unwrap().clone();
+    for frame in 0..60 {
+        let pos = TimelinePosition::from_secs_f64(2.0 + frame as f64 / 30.0);
+        let source = clip.source_frame_secs_at(pos, 24.0, 30.0);
+        assert!((source * 24.0 - frame as f64).abs() < 1e-6, "frame {frame}: {source}");
+    }
+
+    timeline.set_rate_conform(1, aud, RateConform::Resample, 24.0, 30.0).unwrap();
+    let clip = timeline.tracks[0].get_clip(vid).unwrap();
+    assert_eq!(clip.rate_conform, RateConform::Resample);
+    assert!(!clip.is_retimed());
+    // Resampled clips sample by timestamp
+    let pos = TimelinePosition::from_secs_f64(3.5);
+    assert_eq!(clip.source_frame_secs_at(pos, 24.0, 30.0), clip.source_secs_at(pos));
+}
+
+#[test]
+fn test_rate_conform_refuses_to_overlap_next_clip() {
+    let (mut timeline, vid, _, _) = sync_timeline();
+    timeline.add_clip(0, make_clip(Uuid::new_v4(), 8.0, 1.0)).unwrap();
+    let before = timeline.clone();
+    // 5s of 30fps source reinterpreted at 24fps would run to 8.25s
+    let err = timeline.set_rate_conform(0, vid, RateConform::Reinterpret, 30.0, 24.0).unwrap_err();
+    assert!(matches!(err, zeditor_core::error::CoreError::ClipOverlap { .. }), "{err:?}");
+    assert_eq!(timeline, before);
+}
+
+#[test]
+fn test_set_canvas_fit_changes_only_the_clip() {
+    let (mut timeline, vid, aud, _) = sync_timeline();
+    timeline.set_canvas_fit(0, vid, CanvasFit::Fill).unwrap();
+    assert_eq!(timeline.tracks[0].get_clip(vid).unwrap().canvas_fit, CanvasFit::Fill);
+    assert_eq!(timeline.tracks[1].get_clip(aud).unwrap().canvas_fit, CanvasFit::Fit);
+
+    // Cuts keep the fit on both pieces
+    let (left, right) = timeline.cut_at(0, TimelinePosition::from_secs_f64(3.0)).unwrap();
+    assert_eq!(timeline.tracks[0].get_clip(left).unwrap().canvas_fit, CanvasFit::Fill);
+    assert_eq!(timeline.tracks[0].get_clip(right).unwrap().canvas_fit, CanvasFit::Fill);
+
+    timeline.tracks[0].locked = true;
+    assert!(timeline.set_canvas_fit(0, left, CanvasFit::Stretch).is_err());
+}
+
+#[test]
+fn test_set_pan_survives_cuts() {
+    use zeditor_core::reframe::{PanKey, PanPath};
+
+    let (mut timeline, vid, _, _) = sync_timeline();
+    let pan = PanPath { keys: vec![PanKey { source_secs: 0.0, center_x: 0.3 }, PanKey { source_secs: 4.0, center_x: 0.7 }] };
+    timeline.set_pan(0, vid, pan.clone()).unwrap();
+
+    // Keys are in source time, so both pieces keep the whole path
+    let (left, right) = timeline.cut_at(0, TimelinePosition::from_secs_f64(3.0)).unwrap();
+    assert_eq!(timeline.tracks[0].get_clip(left).unwrap().pan, pan);
+    assert_eq!(timeline.tracks[0].get_clip(right).unwrap().pan, pan);
+
+    timeline.set_pan(0, right, PanPath::default()).unwrap();
+    assert!(timeline.tracks[0].get_clip(right).unwrap().pan.is_empty());
+    timeline.tracks[0].locked = true;
+    assert!(timeline.set_pan(0, left, PanPath::default()).is_err());
+}
@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use uuid::Uuid;
+use zeditor_core::media::{MediaAsset, SourceLibrary};
+use zeditor_core::timeline::*;
+use zeditor_core::transcript::{timeline_words, to_srt, words_span, TranscriptWord};
+
+fn word(text: &str, start_ms: u64, end_ms: u64) -> TranscriptWord {
+    TranscriptWord {
+        text: text.into(),
+        start: Duration::from_millis(start_ms),
+        end: Duration::from_millis(end_ms),
+    }
+}
+
+/// A 10s asset saying "Hello there. General Kenobi" and a library holding it.
+fn transcribed_library() -> (SourceLibrary, Uuid) {
+    let mut asset = MediaAsset::new(
+        "talk.wav".into(),
+        "/tmp/talk.wav".into(),
+        Duration::from_secs(10),
+        0,
+        0,
+        0.0,
+        true,
+    );
+    asset.transcript = vec![
+        word("Hello", 1000, 1400),
+        word("there.", 1500, 2000),
+        word("General", 4000, 4500),
+        word("Kenobi", 4600, 5200),
+    ];
+    let id = asset.id;
+    let mut library = SourceLibrary::new();
+    library.import(asset);
+    (library, id)
+}
+
+fn secs(s: f64) -> TimelinePosition {
+    TimelinePosition::from_secs_f64(s)
+}
+
+fn assert_range(range: TimeRange, start: f64, end: f64) {
+    assert!((range.start.as_secs_f64() - start).abs() < 1e-6, "{range:?}");
+    assert!((range.end.as_secs_f64() - end).abs() < 1e-6, "{range:?}");
+}
+
+#[test]
+fn test_timeline_words_follow_clip_placement_and_trim() {
+    let (library, asset_id) = transcribed_library();
+    let mut timeline = Timeline::new();
+    timeline.add_track("V1", TrackType::Video);
+    timeline.add_track("A1", TrackType::Audio);
+    // Source 1.8s..4.8s placed at 10s: "there." is cut, "Kenobi" is cut
+    let source = TimeRange::new(secs(1.8), secs(4.8)).unwrap();
+    timeline
+        .add_clip_with_audio(0, 1, asset_id, secs(10.0), source)
+        .unwrap();
+
+    let words = timeline_words(&timeline, &library);
+    let texts: Vec<_> = words.iter().map(|w| w.text.as_str()).collect();
+    assert_eq!(texts, ["there.", "General", "Kenobi"]);
+    assert!(words.iter().all(|w| w.track_index == 1));
+    assert_range(words[0].range, 10.0, 10.2);
+    assert_range(words[1].range, 12.2, 12.7);
+    assert_range(words[2].range, 12.8, 13.0);
+
+    assert_range(words_span(&words, 2, 1).unwrap(), 12.2, 13.0);
+    assert_eq!(words_span(&words, 1, 3), None);
+}
+
+#[test]
+fn test_srt_breaks_cues_at_sentences_and_pauses() {
+    let (library, asset_id) = transcribed_library();
+    let mut timeline = Timeline::new();
+    timeline.add_track("A1", TrackType::Audio);
+    let source = TimeRange::new(TimelinePosition::zero(), secs(10.0)).unwrap();
+    timeline.add_clip(0, Clip::new(asset_id, secs(3600.0), source)).unwrap();
+
+    let srt = to_srt(&timeline_words(&timeline, &library));
+    assert_eq!(
+        srt,
+        "1\n01:00:01,000 --> 01:00:02,000\nHello there.\n\n\
+         2\n01:00:04,000 --> 01:00:05,200\nGeneral Kenobi\n\n"
+    );
+    assert_eq!(to_srt(&[]), "");
+}
@@ -0,0 +1,58 @@
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, UNIX_EPOCH};
+
+use zeditor_core::versions::{timestamp_label, ProjectVersions};
+
+#[test]
+fn test_versions_dir_for_project() {
+    let versions = ProjectVersions::for_project(Path::new("/tmp/MyProject.zpf"));
+    assert_eq!(versions.dir(), Path::new("/tmp/MyProject.zpf.versions"));
+}
+
+#[test]
+fn test_timestamp_label_is_utc_and_file_safe() {
+    assert_eq!(timestamp_label(UNIX_EPOCH), "1970-01-01T00-00-00");
+    // 2024-06-01 12:00:00 UTC
+    assert_eq!(
+        timestamp_label(UNIX_EPOCH + Duration::from_secs(1_717_243_200)),
+        "2024-06-01T12-00-00"
+    );
+    // Last second of a leap day
+    assert_eq!(
+        timestamp_label(UNIX_EPOCH + Duration::from_secs(951_868_799)),
+        "2000-02-29T23-59-59"
+    );
+}
+
+#[test]
+fn test_missing_versions_dir_lists_empty() {
+    let dir = tempfile::tempdir().unwrap();
+    let versions = ProjectVersions::for_project(&dir.path().join("none.zpf"));
+    assert!(versions.list().unwrap().is_empty());
+}
+
+#[test]
+fn test_record_keeps_newest_versions() {
+    let dir = tempfile::tempdir().unwrap();
+    let project = dir.path().join("MyProject.zpf");
+    let versions = ProjectVersions::for_project(&project);
+    let start = UNIX_EPOCH + Duration::from_secs(1_717_243_200);
+
+    for minute in 0..5u64 {
+        fs::write(&project, format!("save {minute}")).unwrap();
+        versions
+            .record(&project, start + Duration::from_secs(minute * 60), 3)
+            .unwrap();
+    }
+
+    let listed = versions.list().unwrap();
+    let labels: Vec<_> = listed.iter().map(|v| v.label.as_str()).collect();
+    assert_eq!(
+        labels,
+        ["2024-06-01T12-04-00", "2024-06-01T12-03-00", "2024-06-01T12-02-00"]
+    );
+    assert_eq!(listed[0].path, versions.dir().join("2024-06-01T12-04-00.zpf"));
+    assert_eq!(fs::read_to_string(&listed[0].path).unwrap(), "save 4");
+    assert_eq!(fs::read_to_string(&listed[2].path).unwrap(), "save 2");
+}
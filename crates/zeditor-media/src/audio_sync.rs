@@ -0,0 +1,184 @@
+//! Waveform-based sync of separately recorded audio (dual-system sound).
+//!
+//! Both files are reduced to an onset curve (rises in loudness, sampled at
+//! [`ENVELOPE_RATE`]), which matches well between a camera's scratch mic and a
+//! dedicated recorder even though their levels and tone differ. The curves
+//! are cross-correlated and the best lag becomes the offset between them.
+
+use std::path::Path;
+
+use crate::audio_decoder::FfmpegAudioDecoder;
+use crate::error::{MediaError, Result};
+
+/// Onset curve samples per second (10 ms resolution, well under a frame).
+pub const ENVELOPE_RATE: f64 = 100.0;
+
+/// Longest stretch of each file analysed, which bounds the correlation cost.
+pub const MAX_ANALYSIS_SECS: f64 = 120.0;
+
+/// Normalised correlation below this is treated as "no match".
+pub const MIN_CONFIDENCE: f32 = 0.3;
+
+/// Result of [`find_offset`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SyncMatch {
+    /// Seconds to add to the reference's start so the other recording lines
+    /// up: a sound at `t` into the reference is at `t - offset_secs` into the
+    /// other.
+    pub offset_secs: f64,
+    /// Normalised correlation at the match, 0..=1.
+    pub confidence: f32,
+}
+
+/// Onset curve of `duration_secs` of audio starting `start_secs` into `path`
+/// (channels mixed down). Stops at [`MAX_ANALYSIS_SECS`] or the end of file.
+pub fn onset_envelope(path: &Path, start_secs: f64, duration_secs: f64) -> Result<Vec<f32>> {
+    let mut decoder = FfmpegAudioDecoder::open(path)?;
+    if start_secs > 0.0 {
+        decoder.seek_to(start_secs)?;
+    }
+    let duration_secs = duration_secs.min(MAX_ANALYSIS_SECS);
+    let end_secs = start_secs + duration_secs;
+    let bucket_count = (duration_secs * ENVELOPE_RATE).ceil() as usize;
+
+    // Sum of squares and sample count per bucket
+    let mut energy = vec![0.0f64; bucket_count];
+    let mut counts = vec![0u32; bucket_count];
+    while let Some(frame) = decoder.decode_next_audio_frame()? {
+        if frame.pts_secs >= end_secs {
+            break;
+        }
+        let channels = frame.channels.max(1) as usize;
+        let rate = frame.sample_rate.max(1) as f64;
+        for (i, chunk) in frame.samples.chunks_exact(channels).enumerate() {
+            let t = frame.pts_secs + i as f64 / rate - start_secs;
+            if t < 0.0 {
+                continue;
+            }
+            let bucket = (t * ENVELOPE_RATE) as usize;
+            if bucket >= bucket_count {
+                break;
+            }
+            let mono = chunk.iter().sum::<f32>() / channels as f32;
+            energy[bucket] += (mono * mono) as f64;
+            counts[bucket] += 1;
+        }
+    }
+
+    let rms: Vec<f32> = energy
+        .iter()
+        .zip(&counts)
+        .map(|(&e, &n)| if n == 0 { 0.0 } else { (e / n as f64).sqrt() as f32 })
+        .collect();
+    Ok(onsets(&rms))
+}
+
+/// Positive steps of a loudness curve: peaks where sounds start, flat
+/// through sustained tones and silence.
+pub fn onsets(rms: &[f32]) -> Vec<f32> {
+    let mut prev = rms.first().copied().unwrap_or(0.0);
+    rms.iter()
+        .map(|&level| {
+            let rise = (level - prev).max(0.0);
+            prev = level;
+            rise
+        })
+        .collect()
+}
+
+/// Best alignment of two onset curves sampled at [`ENVELOPE_RATE`]. Lags
+/// where the curves overlap by at least half the shorter one are tried;
+/// `None` when either curve is flat or the best match is below
+/// [`MIN_CONFIDENCE`].
+pub fn find_offset(reference: &[f32], other: &[f32]) -> Option<SyncMatch> {
+    let reference = centered(reference)?;
+    let other = centered(other)?;
+    let min_overlap = (reference.len().min(other.len()) / 2).max(1) as isize;
+    // Prefix sums of squares give each lag's overlap energy in O(1)
+    let ref_energy = prefix_energy(&reference);
+    let other_energy = prefix_energy(&other);
+
+    // Lag L pairs other[j] with reference[j + L]
+    let overlap = |lag: isize| {
+        let j_start = (-lag).max(0) as usize;
+        let j_end = (other.len() as isize).min(reference.len() as isize - lag).max(0) as usize;
+        (j_start, j_end.max(j_start))
+    };
+    let score = |lag: isize| -> f64 {
+        let (j_start, j_end) = overlap(lag);
+        (j_start..j_end)
+            .map(|j| other[j] as f64 * reference[(j as isize + lag) as usize] as f64)
+            .sum()
+    };
+    let min_lag = min_overlap - other.len() as isize;
+    let max_lag = reference.len() as isize - min_overlap;
+    if min_lag > max_lag {
+        return None;
+    }
+    let scores: Vec<f64> = (min_lag..=max_lag).map(score).collect();
+    let (best, &peak) = scores
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))?;
+    let best_lag = best as isize + min_lag;
+
+    let (j_start, j_end) = overlap(best_lag);
+    let (r_start, r_end) = ((j_start as isize + best_lag) as usize, (j_end as isize + best_lag) as usize);
+    let norm = ((other_energy[j_end] - other_energy[j_start]) * (ref_energy[r_end] - ref_energy[r_start])).sqrt();
+    let confidence = (peak / norm) as f32;
+    if !confidence.is_finite() || confidence < MIN_CONFIDENCE {
+        return None;
+    }
+
+    // Parabolic interpolation between neighbouring lags for sub-bucket precision
+    let refine = match (best.checked_sub(1).map(|i| scores[i]), scores.get(best + 1)) {
+        (Some(left), Some(&right)) => {
+            let curvature = left - 2.0 * peak + right;
+            if curvature < 0.0 { 0.5 * (left - right) / curvature } else { 0.0 }
+        }
+        _ => 0.0,
+    };
+    Some(SyncMatch {
+        offset_secs: (best_lag as f64 + refine) / ENVELOPE_RATE,
+        confidence: confidence.min(1.0),
+    })
+}
+
+/// Offset between the audio of two files, each analysed from its source
+/// range (`(start_secs, duration_secs)`). See [`SyncMatch::offset_secs`];
+/// offsets are relative to the range starts.
+pub fn sync_offset(
+    reference: &Path,
+    reference_range: (f64, f64),
+    other: &Path,
+    other_range: (f64, f64),
+) -> Result<SyncMatch> {
+    let reference = onset_envelope(reference, reference_range.0, reference_range.1)?;
+    let other = onset_envelope(other, other_range.0, other_range.1)?;
+    find_offset(&reference, &other).ok_or(MediaError::NoSyncMatch)
+}
+
+/// Zero-mean copy, or `None` for a flat (silent or constant) curve.
+fn centered(curve: &[f32]) -> Option<Vec<f32>> {
+    if curve.is_empty() {
+        return None;
+    }
+    let mean = curve.iter().sum::<f32>() / curve.len() as f32;
+    let centered: Vec<f32> = curve.iter().map(|v| v - mean).collect();
+    (energy(&centered) > 1e-12).then_some(centered)
+}
+
+fn energy(curve: &[f32]) -> f64 {
+    curve.iter().map(|&v| v as f64 * v as f64).sum()
+}
+
+fn prefix_energy(curve: &[f32]) -> Vec<f64> {
+    let mut sums = Vec::with_capacity(curve.len() + 1);
+    let mut total = 0.0;
+    sums.push(total);
+    for &v in curve {
+        total += v as f64 * v as f64;
+        sums.push(total);
+    }
+    sums
+}
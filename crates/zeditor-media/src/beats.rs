@@ -0,0 +1,114 @@
+//! Beat and tempo detection for music clips.
+
+use std::path::Path;
+
+use crate::audio_decoder::FfmpegAudioDecoder;
+use crate::error::Result;
+
+/// Slowest and fastest tempo considered, in beats per minute.
+pub const MIN_BPM: f64 = 60.0;
+pub const MAX_BPM: f64 = 200.0;
+
+/// Samples per analysis frame (about 11ms at 48kHz).
+const HOP: usize = 512;
+
+/// Tempo and beat times found in a stretch of audio.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BeatAnalysis {
+    pub bpm: f64,
+    /// Beat times in seconds, in order.
+    pub beats: Vec<f64>,
+}
+
+/// Detect a steady beat in mono `samples`. The onset envelope (rise in
+/// energy per frame) is autocorrelated to find the tempo, then the beat grid
+/// is placed at the phase where onsets line up best. Returns `None` for
+/// silence or audio too short to hold two beats at the slowest tempo.
+pub fn detect_beats(samples: &[f32], sample_rate: u32) -> Option<BeatAnalysis> {
+    if sample_rate == 0 {
+        return None;
+    }
+    let frames_per_sec = sample_rate as f64 / HOP as f64;
+    let energies: Vec<f64> = samples
+        .chunks(HOP)
+        .map(|chunk| chunk.iter().map(|&s| (s as f64) * (s as f64)).sum::<f64>() / chunk.len() as f64)
+        .collect();
+    let onsets: Vec<f64> = energies
+        .iter()
+        .enumerate()
+        .map(|(i, &e)| if i == 0 { e } else { (e - energies[i - 1]).max(0.0) })
+        .collect();
+
+    let min_lag = ((frames_per_sec * 60.0 / MAX_BPM).floor() as usize).max(1);
+    let max_lag = ((frames_per_sec * 60.0 / MIN_BPM).ceil() as usize).min(onsets.len() / 2);
+    if max_lag <= min_lag {
+        return None;
+    }
+    let autocorrelation = |lag: usize| -> f64 { onsets.iter().zip(&onsets[lag..]).map(|(a, b)| a * b).sum() };
+    let scores: Vec<f64> = (min_lag - 1..=max_lag + 1).map(autocorrelation).collect();
+    let (best, &best_score) = scores[1..scores.len() - 1]
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))?;
+    if best_score <= 0.0 {
+        return None;
+    }
+    // Parabolic interpolation around the peak for a fractional period
+    let (before, after) = (scores[best], scores[best + 2]);
+    let curvature = before - 2.0 * best_score + after;
+    let offset = if curvature < 0.0 { 0.5 * (before - after) / curvature } else { 0.0 };
+    let period = (min_lag + best) as f64 + offset.clamp(-0.5, 0.5);
+
+    let grid = |phase: f64| {
+        (0..)
+            .map(move |k| phase + k as f64 * period)
+            .take_while(|&frame| (frame.round() as usize) < onsets.len())
+    };
+    let (phase, _) = (0..period.ceil() as usize)
+        .map(|phase| {
+            let phase = phase as f64;
+            (phase, grid(phase).map(|frame| onsets[frame.round() as usize]).sum::<f64>())
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))?;
+
+    Some(BeatAnalysis {
+        bpm: 60.0 * frames_per_sec / period,
+        beats: grid(phase).map(|frame| frame / frames_per_sec).collect(),
+    })
+}
+
+/// Detect beats in `duration_secs` of `path` starting `start_secs` in. Beat
+/// times are in seconds from the start of the file.
+pub fn detect_beats_in_file(path: &Path, start_secs: f64, duration_secs: f64) -> Result<Option<BeatAnalysis>> {
+    let mut decoder = FfmpegAudioDecoder::open(path)?;
+    if start_secs > 0.0 {
+        decoder.seek_to(start_secs)?;
+    }
+    let end_secs = start_secs + duration_secs;
+    let mut mono = Vec::new();
+    let mut sample_rate = 0;
+    while let Some(frame) = decoder.decode_next_audio_frame()? {
+        if frame.pts_secs >= end_secs {
+            break;
+        }
+        let channels = frame.channels.max(1) as usize;
+        sample_rate = frame.sample_rate;
+        let rate = frame.sample_rate.max(1) as f64;
+        for (i, chunk) in frame.samples.chunks_exact(channels).enumerate() {
+            let t = frame.pts_secs + i as f64 / rate;
+            if t < start_secs {
+                continue;
+            }
+            if t >= end_secs {
+                break;
+            }
+            mono.push(chunk.iter().sum::<f32>() / channels as f32);
+        }
+    }
+    Ok(detect_beats(&mono, sample_rate).map(|mut analysis| {
+        for beat in &mut analysis.beats {
+            *beat += start_secs;
+        }
+        analysis
+    }))
+}
@@ -1,6 +1,10 @@
 use std::path::Path;
+use std::sync::Arc;
+
+use zeditor_core::scaler::ScaleQuality;
 
 use crate::error::Result;
+use crate::seek_index::SeekIndex;
 
 /// Decoded video frame with raw pixel data.
 pub struct VideoFrame {
@@ -40,9 +44,15 @@ pub struct FfmpegDecoder {
     decode_ctx: rsmpeg::avcodec::AVCodecContext,
     sws_ctx: Option<rsmpeg::swscale::SwsContext>,
     sws_dst_dims: (i32, i32),
+    /// Filter used by the SWS context when frames are scaled for display.
+    scale_quality: ScaleQuality,
     video_stream_index: usize,
     stream_info: StreamInfo,
     rotation: u32,
+    /// Keyframe index of the video stream, when it could be built.
+    seek_index: Option<Arc<SeekIndex>>,
+    /// PTS (stream time base) of the last frame returned, cleared by seeks.
+    last_pts: Option<i64>,
 }
 
 impl VideoDecoder for FfmpegDecoder {
@@ -124,14 +134,20 @@ impl VideoDecoder for FfmpegDecoder {
             }, rotation)
         };
 
+        // Built in the background at import; until then seeks go through the container
+        let seek_index = SeekIndex::loaded(path).filter(|index| index.stream_index == video_stream_index);
+
         Ok(Self {
             input_ctx,
             decode_ctx,
             sws_ctx: None,
             sws_dst_dims: (0, 0),
+            scale_quality: ScaleQuality::default(),
             video_stream_index,
             stream_info,
             rotation,
+            seek_index,
+            last_pts: None,
         })
     }
 
@@ -177,11 +193,29 @@ impl VideoDecoder for FfmpegDecoder {
         let ts = (timestamp_secs * tb.den as f64 / tb.num as f64) as i64;
         let _ = streams;
 
+        // With an index, seek to the exact keyframe that starts the target's
+        // GOP, or skip the seek entirely when the decoder is already inside
+        // that GOP and short of the target.
+        let keyframe = self.seek_index.as_ref().and_then(|index| index.keyframe_at_or_before(ts));
+        if let (Some(keyframe), Some(index)) = (keyframe, &self.seek_index) {
+            let in_current_gop = self
+                .last_pts
+                .is_some_and(|last| last < ts && index.keyframe_at_or_before(last) == Some(keyframe));
+            if in_current_gop {
+                return Ok(());
+            }
+        }
+
         self.input_ctx
-            .seek(self.video_stream_index as i32, ts, rsmpeg::ffi::AVSEEK_FLAG_BACKWARD as i32)
+            .seek(
+                self.video_stream_index as i32,
+                keyframe.unwrap_or(ts),
+                rsmpeg::ffi::AVSEEK_FLAG_BACKWARD as i32,
+            )
             .map_err(|e| crate::error::MediaError::SeekError(format!("{e}")))?;
 
         self.decode_ctx.flush_buffers();
+        self.last_pts = None;
 
         Ok(())
     }
@@ -240,7 +274,26 @@ pub fn rotate_rgba_270(data: &[u8], w: u32, h: u32) -> (Vec<u8>, u32, u32) {
     (out, new_w, new_h)
 }
 
+/// SWS flags for a scale quality. Bilinear keeps the fast approximation since
+/// it's used for every frame during playback.
+fn sws_flags(quality: ScaleQuality) -> rsmpeg::ffi::SwsFlags {
+    match quality {
+        ScaleQuality::Nearest => rsmpeg::ffi::SWS_POINT,
+        ScaleQuality::Bilinear => rsmpeg::ffi::SWS_FAST_BILINEAR,
+        ScaleQuality::Lanczos => rsmpeg::ffi::SWS_LANCZOS,
+    }
+}
+
 impl FfmpegDecoder {
+    /// Filter for the `*_scaled` decode methods. Changing it rebuilds the
+    /// SWS context on the next frame.
+    pub fn set_scale_quality(&mut self, quality: ScaleQuality) {
+        if self.scale_quality != quality {
+            self.scale_quality = quality;
+            self.sws_ctx = None;
+        }
+    }
+
     /// Decode the next raw video frame without pixel format conversion.
     /// Returns the raw AVFrame in the decoder's native pixel format along with PTS in seconds.
     /// Used by the renderer to avoid unnecessary RGB round-trips that lose data due to stride.
@@ -260,6 +313,7 @@ impl FfmpegDecoder {
                     match self.decode_ctx.receive_frame() {
                         Ok(frame) => {
                             let pts_secs = self.frame_pts_secs(&frame);
+                            self.note_frame_pts(&frame);
                             return Ok(Some((frame, pts_secs)));
                         }
                         Err(_) => continue,
@@ -270,6 +324,7 @@ impl FfmpegDecoder {
                     match self.decode_ctx.receive_frame() {
                         Ok(frame) => {
                             let pts_secs = self.frame_pts_secs(&frame);
+                            self.note_frame_pts(&frame);
                             return Ok(Some((frame, pts_secs)));
                         }
                         Err(_) => return Ok(None),
@@ -284,6 +339,17 @@ impl FfmpegDecoder {
         }
     }
 
+    fn note_frame_pts(&mut self, frame: &rsmpeg::avutil::AVFrame) {
+        if frame.pts != rsmpeg::ffi::AV_NOPTS_VALUE {
+            self.last_pts = Some(frame.pts);
+        }
+    }
+
+    /// The keyframe index used by `seek_to`, if one could be built.
+    pub fn seek_index(&self) -> Option<&SeekIndex> {
+        self.seek_index.as_deref()
+    }
+
     fn frame_pts_secs(&self, frame: &rsmpeg::avutil::AVFrame) -> f64 {
         let streams = self.input_ctx.streams();
         let tb = streams[self.video_stream_index].time_base;
@@ -415,7 +481,7 @@ impl FfmpegDecoder {
                     dst_w,
                     dst_h,
                     dst_fmt,
-                    rsmpeg::ffi::SWS_FAST_BILINEAR,
+                    sws_flags(self.scale_quality),
                     None,
                     None,
                     None,
@@ -461,6 +527,7 @@ impl FfmpegDecoder {
             }
         };
 
+        self.note_frame_pts(frame);
         let pts_secs = {
             let streams = self.input_ctx.streams();
             let tb = streams[self.video_stream_index].time_base;
@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use crate::error::{MediaError, Result};
@@ -62,3 +62,60 @@ impl FfmpegExporter {
         Ok(())
     }
 }
+
+/// Join rendered parts end to end into `output` without re-encoding, via
+/// the ffmpeg CLI's concat demuxer. The parts must share codecs and
+/// settings, as segments of one render do.
+pub fn concat_lossless(parts: &[PathBuf], output: &Path) -> Result<()> {
+    let list_path = output.with_extension("concat.txt");
+    // The concat list quotes paths in single quotes; a quote inside is written as '\''
+    let list: String = parts
+        .iter()
+        .map(|part| format!("file '{}'\n", part.to_string_lossy().replace('\'', "'\\''")))
+        .collect();
+    std::fs::write(&list_path, list)?;
+
+    let result = Command::new("ffmpeg")
+        .args(["-y", "-f", "concat", "-safe", "0", "-i"])
+        .arg(&list_path)
+        .args(["-c", "copy"])
+        .arg(output)
+        .output();
+    let _ = std::fs::remove_file(&list_path);
+    let finished = result.map_err(|e| MediaError::EncoderError(format!("failed to spawn ffmpeg: {e}")))?;
+
+    if !finished.status.success() {
+        let stderr = String::from_utf8_lossy(&finished.stderr);
+        return Err(MediaError::EncoderError(format!(
+            "ffmpeg exited with {}: {}",
+            finished.status, stderr
+        )));
+    }
+
+    Ok(())
+}
+
+/// Write `output` with the video stream of `video` copied as is and the
+/// sound of `audio` encoded to AAC beside it, via the ffmpeg CLI.
+pub fn mux_audio(video: &Path, audio: &Path, output: &Path) -> Result<()> {
+    let finished = Command::new("ffmpeg")
+        .arg("-y")
+        .arg("-i")
+        .arg(video)
+        .arg("-i")
+        .arg(audio)
+        .args(["-map", "0:v", "-map", "1:a", "-c:v", "copy", "-c:a", "aac"])
+        .arg(output)
+        .output()
+        .map_err(|e| MediaError::EncoderError(format!("failed to spawn ffmpeg: {e}")))?;
+
+    if !finished.status.success() {
+        let stderr = String::from_utf8_lossy(&finished.stderr);
+        return Err(MediaError::EncoderError(format!(
+            "ffmpeg exited with {}: {}",
+            finished.status, stderr
+        )));
+    }
+
+    Ok(())
+}
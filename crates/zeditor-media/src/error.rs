@@ -20,6 +20,24 @@ pub enum MediaError {
     #[error("encoder error: {0}")]
     EncoderError(String),
 
+    #[error("no confident audio match between the clips")]
+    NoSyncMatch,
+
+    #[error("built without speech-to-text support (enable the `whisper` feature)")]
+    TranscriptionUnavailable,
+
+    #[error("transcription failed: {0}")]
+    TranscriptionFailed(String),
+
+    #[error("a {0:.2}s loop crossfade needs a range at least twice as long")]
+    LoopTooShort(f64),
+
+    #[error("cannot write the render output: {0}")]
+    OutputNotWritable(String),
+
+    #[error("not enough disk space: the render needs about {} MB, {} MB are free", .needed / 1_000_000, .available / 1_000_000)]
+    NotEnoughSpace { needed: u64, available: u64 },
+
     #[error("probe error: {0}")]
     ProbeError(String),
 
@@ -1,8 +1,20 @@
 pub mod audio_decoder;
+pub mod audio_sync;
+pub mod beats;
 pub mod decoder;
 pub mod encoder;
 pub mod error;
+pub mod output_check;
 pub mod probe;
+pub mod reframe;
 pub mod render_profile;
+#[cfg(feature = "test-helpers")]
+pub mod render_harness;
 pub mod renderer;
+pub mod seek_index;
+pub mod segmented;
+pub mod shot_detect;
+pub mod stems;
 pub mod thumbnail;
+pub mod transcribe;
+pub mod waveform;
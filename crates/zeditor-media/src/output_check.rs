@@ -0,0 +1,84 @@
+//! Checks on a render's output path before a long render starts: whether a
+//! file is already there, whether its folder can be written, and whether
+//! the disk has room for the estimated size.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{MediaError, Result};
+
+/// `path` if nothing is there yet, otherwise the first free
+/// "name (2).ext", "name (3).ext", ... next to it.
+pub fn next_free_path(path: &Path) -> Result<PathBuf> {
+    if !path.exists() {
+        return Ok(path.to_path_buf());
+    }
+    let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    let extension = path.extension().map(|e| e.to_string_lossy().into_owned());
+    (2..=u32::MAX)
+        .map(|n| {
+            let name = match &extension {
+                Some(extension) => format!("{stem} ({n}).{extension}"),
+                None => format!("{stem} ({n})"),
+            };
+            path.with_file_name(name)
+        })
+        .find(|candidate| !candidate.exists())
+        .ok_or_else(|| MediaError::OutputNotWritable(format!("no free numbered name next to {}", path.display())))
+}
+
+/// Fail when `path` can't be written (missing or read-only folder, or a
+/// read-only file in the way) or its disk has less than `needed_bytes` free.
+/// Disks whose free space can't be read pass the space check.
+pub fn check_output(path: &Path, needed_bytes: u64) -> Result<()> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    if !dir.is_dir() {
+        return Err(MediaError::OutputNotWritable(format!("folder {} does not exist", dir.display())));
+    }
+    if fs::metadata(path).is_ok_and(|m| m.permissions().readonly()) {
+        return Err(MediaError::OutputNotWritable(format!("{} is read-only", path.display())));
+    }
+    // Permission bits don't tell the whole story (ACLs, read-only mounts), so
+    // try it with a fresh file that is deleted again when dropped
+    tempfile::Builder::new()
+        .prefix(".zeditor-write-check-")
+        .tempfile_in(dir)
+        .map_err(|e| MediaError::OutputNotWritable(format!("{}: {e}", dir.display())))?;
+
+    match available_space(dir) {
+        Some(available) if available < needed_bytes => Err(MediaError::NotEnoughSpace {
+            needed: needed_bytes,
+            available,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Bytes an unprivileged user can still write on the disk holding `dir`,
+/// or `None` when that can't be found out.
+#[cfg(unix)]
+#[allow(clippy::useless_conversion)] // the statvfs field types differ between platforms
+pub fn available_space(dir: &Path) -> Option<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(dir.as_os_str().as_bytes()).ok()?;
+    // SAFETY: `c_path` is NUL-terminated and `stats` is a plain C struct
+    // that statvfs fills in; it's only read after a successful call.
+    let stats = unsafe {
+        let mut stats: libc::statvfs = std::mem::zeroed();
+        if libc::statvfs(c_path.as_ptr(), &mut stats) != 0 {
+            return None;
+        }
+        stats
+    };
+    Some(u64::from(stats.f_bavail).saturating_mul(u64::from(stats.f_frsize)))
+}
+
+#[cfg(not(unix))]
+pub fn available_space(_dir: &Path) -> Option<u64> {
+    None
+}
@@ -2,6 +2,7 @@ use std::ffi::CString;
 use std::path::Path;
 use std::time::Duration;
 
+use zeditor_core::color::{ColorPrimaries, ColorSpace, TransferFunction};
 use zeditor_core::media::MediaAsset;
 
 use crate::error::{MediaError, Result};
@@ -21,6 +22,9 @@ pub fn probe(path: &Path) -> Result<MediaAsset> {
     let mut fps = 30.0f64;
     let mut has_audio = false;
     let mut rotation = 0u32;
+    let mut video_codec = None;
+    let mut audio_codec = None;
+    let mut color = ColorSpace::default();
 
     for stream in streams.iter() {
         let codecpar = stream.codecpar();
@@ -33,8 +37,11 @@ pub fn probe(path: &Path) -> Result<MediaAsset> {
             }
             // Extract rotation from stream side data
             rotation = extract_rotation_from_side_data(&stream);
+            video_codec = Some(codec_name(codecpar.codec_id));
+            color = color_space(codecpar.color_trc, codecpar.color_primaries);
         } else if codecpar.codec_type == rsmpeg::ffi::AVMEDIA_TYPE_AUDIO {
             has_audio = true;
+            audio_codec.get_or_insert_with(|| codec_name(codecpar.codec_id));
         }
     }
 
@@ -56,9 +63,146 @@ pub fn probe(path: &Path) -> Result<MediaAsset> {
         has_audio,
     );
     asset.rotation = rotation;
+    asset.codec = video_codec.or(audio_codec).unwrap_or_default();
+    asset.color = color;
     Ok(asset)
 }
 
+/// Technical details of a media file for the asset info panel, beyond what
+/// `probe` keeps on the asset.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MediaDetails {
+    /// Container format, e.g. "QuickTime / MOV".
+    pub container: String,
+    pub video_codec: Option<String>,
+    pub width: u32,
+    pub height: u32,
+    pub fps: Option<f64>,
+    /// Pixel format name (e.g. "yuv420p") and bits per component.
+    pub pixel_format: Option<String>,
+    pub bit_depth: Option<u32>,
+    pub audio_codec: Option<String>,
+    pub audio_channels: Option<u32>,
+    pub sample_rate: Option<u32>,
+    /// Overall bit rate in bits per second.
+    pub bit_rate: Option<u64>,
+    pub file_size: u64,
+    /// The container's `creation_time` tag, else the file's creation (or
+    /// modification) time, as `YYYY-MM-DD HH:MM:SS` UTC.
+    pub created: Option<String>,
+}
+
+/// Probe a media file for the asset info panel.
+pub fn probe_details(path: &Path) -> Result<MediaDetails> {
+    let path_str = path.to_string_lossy().to_string();
+    let c_path =
+        CString::new(path_str.clone()).map_err(|_| MediaError::ProbeError(path_str.clone()))?;
+    let file = std::fs::metadata(path).map_err(|e| MediaError::ProbeError(format!("{path_str}: {e}")))?;
+
+    let input_ctx = rsmpeg::avformat::AVFormatContextInput::open(&c_path)
+        .map_err(|e| MediaError::ProbeError(format!("{path_str}: {e}")))?;
+
+    let mut details = MediaDetails {
+        file_size: file.len(),
+        bit_rate: (input_ctx.bit_rate > 0).then_some(input_ctx.bit_rate as u64),
+        ..MediaDetails::default()
+    };
+    // The input format and its names are static strings owned by FFmpeg
+    unsafe {
+        if let Some(format) = input_ctx.iformat.as_ref() {
+            let name = if format.long_name.is_null() { format.name } else { format.long_name };
+            if !name.is_null() {
+                details.container = std::ffi::CStr::from_ptr(name).to_string_lossy().into_owned();
+            }
+        }
+    }
+
+    for stream in input_ctx.streams().iter() {
+        let codecpar = stream.codecpar();
+        if codecpar.codec_type == rsmpeg::ffi::AVMEDIA_TYPE_VIDEO && details.video_codec.is_none() {
+            details.video_codec = Some(codec_name(codecpar.codec_id));
+            details.width = codecpar.width as u32;
+            details.height = codecpar.height as u32;
+            let r = stream.r_frame_rate;
+            details.fps = (r.den > 0 && r.num > 0).then(|| r.num as f64 / r.den as f64);
+            // Descriptors and names are static tables; null for unknown formats
+            unsafe {
+                let format = codecpar.format;
+                let name = rsmpeg::ffi::av_get_pix_fmt_name(format);
+                if !name.is_null() {
+                    details.pixel_format = Some(std::ffi::CStr::from_ptr(name).to_string_lossy().into_owned());
+                }
+                if let Some(desc) = rsmpeg::ffi::av_pix_fmt_desc_get(format).as_ref() {
+                    details.bit_depth = Some(desc.comp[0].depth as u32);
+                }
+            }
+        } else if codecpar.codec_type == rsmpeg::ffi::AVMEDIA_TYPE_AUDIO && details.audio_codec.is_none() {
+            details.audio_codec = Some(codec_name(codecpar.codec_id));
+            details.audio_channels = Some(codecpar.ch_layout.nb_channels as u32);
+            details.sample_rate = Some(codecpar.sample_rate as u32);
+        }
+    }
+
+    // Tag values are owned by the format context, copied out before it drops
+    let creation_tag = unsafe {
+        let entry = rsmpeg::ffi::av_dict_get(input_ctx.metadata, c"creation_time".as_ptr(), std::ptr::null(), 0);
+        entry
+            .as_ref()
+            .filter(|e| !e.value.is_null())
+            .map(|e| std::ffi::CStr::from_ptr(e.value).to_string_lossy().into_owned())
+    };
+    details.created = creation_tag
+        .and_then(|tag| creation_time_label(&tag))
+        .or_else(|| {
+            let time = file.created().or_else(|_| file.modified()).ok()?;
+            let label = zeditor_core::versions::timestamp_label(time);
+            let (date, time) = label.split_once('T')?;
+            Some(format!("{date} {}", time.replace('-', ":")))
+        });
+    Ok(details)
+}
+
+/// `YYYY-MM-DD HH:MM:SS` from an ISO 8601 `creation_time` tag such as
+/// "2024-05-01T12:30:00.000000Z". `None` if it doesn't look like one.
+pub fn creation_time_label(tag: &str) -> Option<String> {
+    let (date, time) = tag.trim().split_once(['T', ' '])?;
+    let time = time.get(..8)?;
+    let valid = date.len() == 10
+        && date.chars().enumerate().all(|(i, c)| if i == 4 || i == 7 { c == '-' } else { c.is_ascii_digit() })
+        && time.chars().enumerate().all(|(i, c)| if i == 2 || i == 5 { c == ':' } else { c.is_ascii_digit() });
+    valid.then(|| format!("{date} {time}"))
+}
+
+/// Short FFmpeg name for a codec id (e.g. "h264", "aac").
+fn codec_name(codec_id: rsmpeg::ffi::AVCodecID) -> String {
+    // avcodec_get_name always returns a valid static string ("unknown_codec" for unknown ids)
+    unsafe {
+        std::ffi::CStr::from_ptr(rsmpeg::ffi::avcodec_get_name(codec_id))
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+/// Colour space from a video stream's tags. Untagged or unrecognised values
+/// fall back to Rec.709 SDR, which is what players assume too.
+fn color_space(
+    trc: rsmpeg::ffi::AVColorTransferCharacteristic,
+    primaries: rsmpeg::ffi::AVColorPrimaries,
+) -> ColorSpace {
+    let transfer = match trc {
+        rsmpeg::ffi::AVCOL_TRC_SMPTE2084 => TransferFunction::Pq,
+        rsmpeg::ffi::AVCOL_TRC_ARIB_STD_B67 => TransferFunction::Hlg,
+        rsmpeg::ffi::AVCOL_TRC_LINEAR => TransferFunction::Linear,
+        _ => TransferFunction::Sdr,
+    };
+    let primaries = match primaries {
+        rsmpeg::ffi::AVCOL_PRI_BT2020 => ColorPrimaries::Bt2020,
+        rsmpeg::ffi::AVCOL_PRI_SMPTE432 => ColorPrimaries::DisplayP3,
+        _ => ColorPrimaries::Bt709,
+    };
+    ColorSpace { transfer, primaries }
+}
+
 /// Extract rotation from stream display matrix side data.
 pub fn extract_rotation_from_side_data(stream: &rsmpeg::avformat::AVStreamRef<'_>) -> u32 {
     // Try to get the display matrix from stream side data
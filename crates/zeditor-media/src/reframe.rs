@@ -0,0 +1,132 @@
+//! Auto-reframe analysis: follows where the action is across a clip so a
+//! wide shot can be cropped to a narrower canvas with its subject in frame.
+//!
+//! Frames are sampled at a small size and each column is weighted by motion
+//! (difference from the previous sample) and skin-toned pixels, a cheap
+//! stand-in for faces. The crop window covering the most weight gives the
+//! center for that sample; the centers are then smoothed and speed-limited
+//! so the crop glides instead of jittering.
+
+use std::path::Path;
+
+use zeditor_core::reframe::PanPath;
+
+use crate::decoder::{FfmpegDecoder, VideoDecoder};
+use crate::error::Result;
+
+/// Size frames are decoded at for analysis.
+const ANALYSIS_WIDTH: u32 = 96;
+const ANALYSIS_HEIGHT: u32 = 54;
+
+/// Source seconds between analysed frames.
+const SAMPLE_INTERVAL_SECS: f64 = 0.2;
+
+const MOTION_WEIGHT: f64 = 1.0;
+const SKIN_WEIGHT: f64 = 0.5;
+
+/// Samples averaged (centered) when smoothing the path.
+const SMOOTHING_SAMPLES: usize = 5;
+
+/// Fastest pan, in frame widths per second.
+pub const MAX_PAN_SPEED: f64 = 0.25;
+
+/// How far a dropped key may sit from the simplified path, in frame widths.
+const KEY_TOLERANCE: f64 = 0.01;
+
+/// Pan keys for `source_start..source_end` of the video at `path`, cropped
+/// to `visible_fraction` of the frame width.
+pub fn analyze_pan(path: &Path, source_start: f64, source_end: f64, visible_fraction: f64) -> Result<PanPath> {
+    let mut decoder = FfmpegDecoder::open(path)?;
+    decoder.seek_to(source_start)?;
+
+    let mut samples = Vec::new();
+    let mut previous: Option<Vec<u8>> = None;
+    let mut next_sample = source_start;
+    while let Some(frame) = decoder.decode_next_frame_scaled(ANALYSIS_WIDTH, ANALYSIS_HEIGHT)? {
+        if frame.pts_secs > source_end {
+            break;
+        }
+        if frame.pts_secs < next_sample {
+            continue;
+        }
+        next_sample = frame.pts_secs + SAMPLE_INTERVAL_SECS;
+        let weights = column_weights(previous.as_deref(), &frame.data, ANALYSIS_WIDTH as usize);
+        samples.push((frame.pts_secs, focus_center(&weights, visible_fraction)));
+        previous = Some(frame.data);
+    }
+
+    Ok(PanPath::simplified(&smooth_path(&samples, visible_fraction), KEY_TOLERANCE))
+}
+
+/// Interest of each column of an RGB24 frame `width` pixels wide: motion
+/// against `previous` (same size) plus skin-toned pixels.
+pub fn column_weights(previous: Option<&[u8]>, rgb: &[u8], width: usize) -> Vec<f64> {
+    let mut weights = vec![0.0; width];
+    let previous = previous.filter(|p| p.len() == rgb.len());
+    for (i, pixel) in rgb.chunks_exact(3).enumerate() {
+        let column = i % width;
+        if let Some(previous) = previous {
+            let before = &previous[i * 3..i * 3 + 3];
+            let motion = pixel.iter().zip(before).map(|(a, b)| a.abs_diff(*b) as f64).sum::<f64>() / (3.0 * 255.0);
+            weights[column] += MOTION_WEIGHT * motion;
+        }
+        if is_skin(pixel[0], pixel[1], pixel[2]) {
+            weights[column] += SKIN_WEIGHT;
+        }
+    }
+    weights
+}
+
+/// The common RGB skin-tone rule (Kovac et al.), for daylight footage.
+fn is_skin(r: u8, g: u8, b: u8) -> bool {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    r > 95 && g > 40 && b > 20 && max - min > 15 && r.abs_diff(g) > 15 && r > g && r > b
+}
+
+/// Center (fraction of the width) of the `visible_fraction` wide window of
+/// columns holding the most weight, or `None` when nothing stands out.
+pub fn focus_center(weights: &[f64], visible_fraction: f64) -> Option<f64> {
+    let n = weights.len();
+    if n == 0 || weights.iter().sum::<f64>() <= f64::EPSILON {
+        return None;
+    }
+    let window = ((visible_fraction * n as f64).round() as usize).clamp(1, n);
+    let mut sum: f64 = weights[..window].iter().sum();
+    let (mut best, mut best_start) = (sum, 0);
+    for start in 1..=n - window {
+        sum += weights[start + window - 1] - weights[start - 1];
+        if sum > best + f64::EPSILON {
+            (best, best_start) = (sum, start);
+        }
+    }
+    Some((best_start as f64 + window as f64 / 2.0) / n as f64)
+}
+
+/// Smoothed, speed-limited centers for `(source_secs, center)` samples.
+/// Samples without a center hold the previous one (the middle at first),
+/// and centers stay where the crop window fits inside the frame.
+pub fn smooth_path(samples: &[(f64, Option<f64>)], visible_fraction: f64) -> Vec<(f64, f64)> {
+    let mut held = 0.5;
+    let raw: Vec<f64> = samples
+        .iter()
+        .map(|&(_, center)| {
+            held = center.unwrap_or(held);
+            held
+        })
+        .collect();
+
+    let half = SMOOTHING_SAMPLES / 2;
+    let margin = (visible_fraction / 2.0).min(0.5);
+    let mut path: Vec<(f64, f64)> = Vec::with_capacity(samples.len());
+    for (i, &(source_secs, _)) in samples.iter().enumerate() {
+        let window = &raw[i.saturating_sub(half)..(i + half + 1).min(raw.len())];
+        let mut center = (window.iter().sum::<f64>() / window.len() as f64).clamp(margin, 1.0 - margin);
+        if let Some(&(prev_secs, prev_center)) = path.last() {
+            let max_step = MAX_PAN_SPEED * (source_secs - prev_secs);
+            center = center.clamp(prev_center - max_step, prev_center + max_step);
+        }
+        path.push((source_secs, center));
+    }
+    path
+}
@@ -0,0 +1,284 @@
+//! Deterministic render regression harness.
+//!
+//! Renders a small timeline of synthetic sources (solid colors, gradients, a
+//! moving bar) through the same clip lookup, effect pipeline and compositing
+//! as the effects path of `render_timeline`, but to raw RGBA frames instead of
+//! an encoded file. Frames are hashed and compared against golden hash files,
+//! so refactors of the pipeline can be checked without depending on decoder or
+//! encoder output.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use uuid::Uuid;
+use zeditor_core::color;
+use zeditor_core::effects::EffectInstance;
+use zeditor_core::media::{MediaAsset, SourceLibrary};
+use zeditor_core::pipeline::{EffectContext, EffectRegistry, FrameBuffer};
+use zeditor_core::timeline::{Clip, TimeRange, Timeline, TimelinePosition, TrackType};
+
+use crate::renderer::{self, ScalingAlgorithm};
+
+/// Set to `1` or `true` to rewrite golden files with the current output.
+pub const UPDATE_GOLDEN_ENV: &str = "ZEDITOR_UPDATE_GOLDEN";
+
+/// A generated video source. Pixels depend only on the frame size and the
+/// source time, so every render of the same timeline is bit-identical.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyntheticSource {
+    Solid([u8; 4]),
+    /// Left-to-right blend from `from` to `to`.
+    HorizontalGradient { from: [u8; 4], to: [u8; 4] },
+    /// Top-to-bottom blend from `from` to `to`.
+    VerticalGradient { from: [u8; 4], to: [u8; 4] },
+    /// A vertical bar moving right at `speed` pixels per second (wrapping),
+    /// so a frame shows which source time was rendered.
+    MovingBar {
+        color: [u8; 4],
+        background: [u8; 4],
+        bar_width: u32,
+        speed: f64,
+    },
+}
+
+impl SyntheticSource {
+    /// The source frame at `time_secs`, generated at `width`x`height`.
+    pub fn frame(&self, width: u32, height: u32, time_secs: f64) -> FrameBuffer {
+        let mut frame = FrameBuffer::new(width, height);
+        let bar_left = match self {
+            Self::MovingBar { speed, .. } => (time_secs * speed).round() as i64,
+            _ => 0,
+        };
+        for y in 0..height {
+            for x in 0..width {
+                let rgba = match *self {
+                    Self::Solid(color) => color,
+                    Self::HorizontalGradient { from, to } => lerp_rgba(from, to, x, width),
+                    Self::VerticalGradient { from, to } => lerp_rgba(from, to, y, height),
+                    Self::MovingBar {
+                        color,
+                        background,
+                        bar_width,
+                        ..
+                    } => {
+                        let offset = (x as i64 - bar_left).rem_euclid(width as i64);
+                        if offset < bar_width as i64 { color } else { background }
+                    }
+                };
+                frame.pixel_mut(x, y).copy_from_slice(&rgba);
+            }
+        }
+        frame
+    }
+}
+
+/// Integer blend so results don't depend on float rounding.
+fn lerp_rgba(from: [u8; 4], to: [u8; 4], step: u32, steps: u32) -> [u8; 4] {
+    let den = steps.saturating_sub(1).max(1);
+    let num = step.min(den);
+    std::array::from_fn(|i| {
+        ((from[i] as u32 * (den - num) + to[i] as u32 * num + den / 2) / den) as u8
+    })
+}
+
+/// A timeline whose media are synthetic sources, plus the canvas it renders to.
+///
+/// Sources are registered as media assets with placeholder paths, so clips
+/// are placed with the regular `Timeline` API (or `add_clip`).
+pub struct SyntheticTimeline {
+    pub timeline: Timeline,
+    pub canvas_width: u32,
+    pub canvas_height: u32,
+    pub fps: f64,
+    source_library: SourceLibrary,
+    sources: HashMap<PathBuf, (SyntheticSource, u32, u32)>,
+}
+
+impl SyntheticTimeline {
+    pub fn new(canvas_width: u32, canvas_height: u32, fps: f64) -> Self {
+        Self {
+            timeline: Timeline::new(),
+            canvas_width,
+            canvas_height,
+            fps,
+            source_library: SourceLibrary::new(),
+            sources: HashMap::new(),
+        }
+    }
+
+    /// Register a source with the given native size and duration; returns
+    /// its asset id.
+    pub fn add_source(
+        &mut self,
+        name: &str,
+        source: SyntheticSource,
+        width: u32,
+        height: u32,
+        duration_secs: f64,
+    ) -> Uuid {
+        let path = PathBuf::from(format!("synthetic/{}/{name}", self.sources.len()));
+        let asset = MediaAsset::new(
+            name.to_string(),
+            path.clone(),
+            Duration::from_secs_f64(duration_secs),
+            width,
+            height,
+            self.fps,
+            false,
+        );
+        let id = asset.id;
+        self.source_library.import(asset);
+        self.sources.insert(path, (source, width, height));
+        id
+    }
+
+    pub fn add_video_track(&mut self, name: &str) -> usize {
+        self.timeline.add_track(name, TrackType::Video)
+    }
+
+    /// Place `duration_secs` of a source (from its start) on a track.
+    pub fn add_clip(
+        &mut self,
+        track_index: usize,
+        asset_id: Uuid,
+        start_secs: f64,
+        duration_secs: f64,
+        effects: Vec<EffectInstance>,
+    ) -> zeditor_core::error::Result<()> {
+        let source_range = TimeRange::new(
+            TimelinePosition::zero(),
+            TimelinePosition::from_secs_f64(duration_secs),
+        )?;
+        let mut clip = Clip::new(asset_id, TimelinePosition::from_secs_f64(start_secs), source_range);
+        clip.effects = effects;
+        self.timeline.add_clip_trimming_overlaps(track_index, clip)
+    }
+
+    /// Number of frames the export would encode.
+    pub fn frame_count(&self) -> u64 {
+        (self.timeline.duration().as_secs_f64() * self.fps).ceil() as u64
+    }
+
+    /// Render every frame of the timeline to RGBA. Layers go through the
+    /// export's effects path even when they have no effects, since the
+    /// YUV fast path needs decoded ffmpeg frames.
+    pub fn render_frames(&self, scaling: ScalingAlgorithm) -> Vec<FrameBuffer> {
+        (0..self.frame_count())
+            .map(|frame_idx| self.render_frame(frame_idx, scaling))
+            .collect()
+    }
+
+    /// Render one frame, as `render_timeline` would before color conversion.
+    pub fn render_frame(&self, frame_idx: u64, scaling: ScalingAlgorithm) -> FrameBuffer {
+        // Same even dimensions the export uses.
+        let width = (self.canvas_width & !1).max(2);
+        let height = (self.canvas_height & !1).max(2);
+        let registry = EffectRegistry::with_builtins();
+        let timeline_time = frame_idx as f64 / self.fps;
+        let ctx = EffectContext {
+            time_secs: timeline_time,
+            frame_number: frame_idx,
+            fps: self.fps,
+            scale_quality: scaling.scale_quality(),
+        };
+
+        let mut canvas = FrameBuffer::new(width, height);
+        for pixel in canvas.data.chunks_exact_mut(4) {
+            pixel[3] = 255;
+        }
+        let pos = TimelinePosition::from_secs_f64(timeline_time);
+        for layer in renderer::find_all_video_clips_at(&self.timeline, &self.source_library, pos, self.fps) {
+            let Some((source, src_w, src_h)) = self.sources.get(&layer.path) else {
+                continue;
+            };
+            let (w, h) = decoded_size(*src_w, *src_h, width, height);
+            let mut clip_frame = source.frame(w, h, layer.source_time);
+            color::to_working_space(&mut clip_frame.data, layer.color);
+            let placement = layer.placement(&clip_frame, width, height);
+            renderer::composite_rgba_layer(clip_frame, &layer.effects, placement, &mut canvas, &registry, &ctx, false);
+        }
+        canvas
+    }
+
+    /// Hash of every rendered frame, for comparing against a golden file.
+    pub fn render_hashes(&self, scaling: ScalingAlgorithm) -> Vec<u64> {
+        self.render_frames(scaling).iter().map(frame_hash).collect()
+    }
+}
+
+/// Size the decoder delivers a source at: shrunk (never enlarged) to fit the
+/// canvas, with even dimensions.
+fn decoded_size(src_w: u32, src_h: u32, max_w: u32, max_h: u32) -> (u32, u32) {
+    if src_w <= max_w && src_h <= max_h {
+        return (src_w, src_h);
+    }
+    let scale = (max_w as f64 / src_w as f64).min(max_h as f64 / src_h as f64);
+    let w = ((src_w as f64 * scale) as u32).max(2) & !1;
+    let h = ((src_h as f64 * scale) as u32).max(2) & !1;
+    (w, h)
+}
+
+/// FNV-1a hash of a frame's dimensions and pixels; stable across builds and
+/// platforms, unlike `DefaultHasher`.
+pub fn frame_hash(frame: &FrameBuffer) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let header = [frame.width.to_le_bytes(), frame.height.to_le_bytes()].concat();
+    for &byte in header.iter().chain(&frame.data) {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+fn update_golden_requested() -> bool {
+    std::env::var(UPDATE_GOLDEN_ENV)
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+/// Compare frame hashes against the golden file at `path` (one hex hash per
+/// line; `#` starts a comment). A missing golden file is recorded from
+/// `hashes`, as is any file when `ZEDITOR_UPDATE_GOLDEN` is set. The error
+/// names the first frame that differs.
+pub fn check_golden(path: &Path, hashes: &[u64]) -> Result<(), String> {
+    if update_golden_requested() || !path.exists() {
+        return write_golden(path, hashes).map_err(|e| format!("{}: {e}", path.display()));
+    }
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let golden = text
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| u64::from_str_radix(line, 16).map_err(|e| format!("{}: bad hash {line:?}: {e}", path.display())))
+        .collect::<Result<Vec<u64>, String>>()?;
+
+    if let Some(frame) = golden.iter().zip(hashes).position(|(want, got)| want != got) {
+        return Err(format!(
+            "{}: frame {frame} hash {:016x} != golden {:016x} (set {UPDATE_GOLDEN_ENV}=1 to accept)",
+            path.display(),
+            hashes[frame],
+            golden[frame]
+        ));
+    }
+    if golden.len() != hashes.len() {
+        return Err(format!(
+            "{}: rendered {} frames, golden has {}",
+            path.display(),
+            hashes.len(),
+            golden.len()
+        ));
+    }
+    Ok(())
+}
+
+fn write_golden(path: &Path, hashes: &[u64]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut text = format!("# Frame hashes; regenerate with {UPDATE_GOLDEN_ENV}=1\n");
+    for hash in hashes {
+        text.push_str(&format!("{hash:016x}\n"));
+    }
+    std::fs::write(path, text)
+}
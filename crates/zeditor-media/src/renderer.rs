@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::ffi::CString;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use rsmpeg::avcodec::{AVCodec, AVCodecContext};
 use rsmpeg::avformat::{AVFormatContextInput, AVFormatContextOutput};
@@ -9,11 +10,14 @@ use rsmpeg::ffi;
 use rsmpeg::swresample::SwrContext;
 use rsmpeg::swscale::SwsContext;
 
+use zeditor_core::color::{self, ColorSpace};
 use zeditor_core::effects::EffectInstance;
 use zeditor_core::media::SourceLibrary;
 use zeditor_core::pipeline::{self, EffectContext, EffectRegistry, FrameBuffer};
 use zeditor_core::project::ProjectSettings;
-use zeditor_core::timeline::{Timeline, TimelinePosition, TrackType};
+use zeditor_core::reframe::PanPath;
+use zeditor_core::scaler::{CanvasFit, Placement, ScaleQuality};
+use zeditor_core::timeline::{Clip, Timeline, TimelinePosition, TrackType};
 
 use crate::decoder::{FfmpegDecoder, VideoDecoder};
 use crate::error::{MediaError, Result};
@@ -39,12 +43,21 @@ impl ScalingAlgorithm {
             Self::Lanczos => ffi::SWS_LANCZOS,
         }
     }
+
+    /// The shared scaler's closest filter, for the RGBA (effects) path.
+    pub fn scale_quality(self) -> ScaleQuality {
+        match self {
+            Self::FastBilinear | Self::Bilinear => ScaleQuality::Bilinear,
+            Self::Bicubic | Self::Lanczos => ScaleQuality::Lanczos,
+        }
+    }
 }
 
 /// Configuration for timeline rendering.
 ///
 /// Future fields: video_codec, audio_codec, container_format,
 /// audio_sample_rate, audio_channels, pixel_format.
+#[derive(Clone)]
 pub struct RenderConfig {
     pub output_path: PathBuf,
     pub width: u32,
@@ -108,8 +121,8 @@ struct CachedVideoDecoder {
     last_rgba_frame: Option<(FrameBuffer, f64)>,
 }
 
-const OUTPUT_SAMPLE_RATE: i32 = 48000;
-const OUTPUT_CHANNELS: i32 = 2;
+pub(crate) const OUTPUT_SAMPLE_RATE: i32 = 48000;
+pub(crate) const OUTPUT_CHANNELS: i32 = 2;
 
 /// Render the timeline to an output video file.
 ///
@@ -123,6 +136,35 @@ pub fn render_timeline(
     source_library: &SourceLibrary,
     config: &RenderConfig,
     progress_tx: Option<std::sync::mpsc::Sender<RenderProgress>>,
+) -> Result<()> {
+    render(timeline, source_library, config, timeline.duration(), None, true, progress_tx)
+}
+
+/// Render the timeline as a seamless loop: its last `crossfade_secs` are
+/// dissolved into its start (picture and sound), so the output is that much
+/// shorter and its last frame runs straight into its first. Fails with
+/// `LoopTooShort` unless the timeline is at least twice the crossfade.
+pub fn render_loop(
+    timeline: &Timeline,
+    source_library: &SourceLibrary,
+    config: &RenderConfig,
+    crossfade_secs: f64,
+    progress_tx: Option<std::sync::mpsc::Sender<RenderProgress>>,
+) -> Result<()> {
+    render(timeline, source_library, config, timeline.duration(), Some(crossfade_secs), true, progress_tx)
+}
+
+/// Render the first `total_duration` of the timeline, black and silent past
+/// its last clip, optionally as a loop (see `render_loop`). Without
+/// `with_audio` the output has a video stream only.
+pub(crate) fn render(
+    timeline: &Timeline,
+    source_library: &SourceLibrary,
+    config: &RenderConfig,
+    total_duration: Duration,
+    loop_crossfade_secs: Option<f64>,
+    with_audio: bool,
+    progress_tx: Option<std::sync::mpsc::Sender<RenderProgress>>,
 ) -> Result<()> {
     let render_start = std::time::Instant::now();
     let profiling_enabled = render_profile::is_profiling_enabled();
@@ -138,13 +180,23 @@ pub fn render_timeline(
         preset: config.preset.clone(),
     });
 
-    let total_duration = timeline.duration();
     if total_duration.as_secs_f64() <= 0.0 {
         return Err(MediaError::EncoderError("Timeline is empty".into()));
     }
 
     let total_frames =
         (total_duration.as_secs_f64() * config.fps).ceil() as u64;
+    let loop_fade = match loop_crossfade_secs {
+        Some(secs) => {
+            let fade_frames = (secs * config.fps).round() as u64;
+            if fade_frames == 0 || fade_frames * 2 > total_frames {
+                return Err(MediaError::LoopTooShort(secs));
+            }
+            Some(LoopFade::new(total_frames - fade_frames, fade_frames))
+        }
+        None => None,
+    };
+    let audio_fade_secs = loop_fade.as_ref().map(|fade| fade.fade_frames as f64 / config.fps);
 
     // --- Setup stage ---
     let setup_start = std::time::Instant::now();
@@ -198,34 +250,11 @@ pub fn render_timeline(
         .map_err(|e| MediaError::EncoderError(format!("Failed to open video encoder: {e}")))?;
 
     // --- Audio encoder setup ---
-    let audio_codec = AVCodec::find_encoder_by_name(c"aac")
-        .ok_or_else(|| MediaError::EncoderError("AAC encoder not found".into()))?;
-
-    let mut audio_enc_ctx = AVCodecContext::new(&audio_codec);
-    audio_enc_ctx.set_sample_rate(OUTPUT_SAMPLE_RATE);
-    audio_enc_ctx.set_sample_fmt(ffi::AV_SAMPLE_FMT_FLTP);
-    audio_enc_ctx.set_time_base(ffi::AVRational { num: 1, den: OUTPUT_SAMPLE_RATE });
-
-    let stereo_layout = AVChannelLayout::from_nb_channels(OUTPUT_CHANNELS);
-    unsafe {
-        ffi::av_channel_layout_copy(
-            &mut (*audio_enc_ctx.as_mut_ptr()).ch_layout,
-            stereo_layout.as_ptr(),
-        );
-    }
-
-    if needs_global_header {
-        unsafe {
-            use rsmpeg::UnsafeDerefMut;
-            audio_enc_ctx.deref_mut().flags |= ffi::AV_CODEC_FLAG_GLOBAL_HEADER as i32;
-        }
-    }
-
-    audio_enc_ctx
-        .open(None)
-        .map_err(|e| MediaError::EncoderError(format!("Failed to open audio encoder: {e}")))?;
-
-    let audio_frame_size = unsafe { (*audio_enc_ctx.as_ptr()).frame_size };
+    let mut audio_enc_ctx = if with_audio {
+        Some(open_audio_encoder(needs_global_header)?)
+    } else {
+        None
+    };
 
     // --- Create streams (each scoped to release borrow before next) ---
     let video_stream_index = 0i32;
@@ -235,7 +264,7 @@ pub fn render_timeline(
         let mut stream = output_ctx.new_stream();
         stream.set_codecpar(codecpar);
     }
-    {
+    if let Some(audio_enc_ctx) = &audio_enc_ctx {
         let codecpar = audio_enc_ctx.extract_codecpar();
         let mut stream = output_ctx.new_stream();
         stream.set_codecpar(codecpar);
@@ -248,7 +277,9 @@ pub fn render_timeline(
 
     // Get stream time bases after write_header (they may have been adjusted)
     let video_stream_tb = output_ctx.streams()[video_stream_index as usize].time_base;
-    let audio_stream_tb = output_ctx.streams()[audio_stream_index as usize].time_base;
+    let audio_stream_tb = audio_enc_ctx
+        .as_ref()
+        .map(|_| output_ctx.streams()[audio_stream_index as usize].time_base);
 
     // --- Decoder cache for video ---
     let mut video_decoders: HashMap<PathBuf, CachedVideoDecoder> = HashMap::new();
@@ -272,6 +303,7 @@ pub fn render_timeline(
         video_stream_index,
         video_stream_tb,
         &mut video_decoders,
+        loop_fade,
         &mut profiler,
         &progress_tx,
         render_start,
@@ -279,19 +311,23 @@ pub fn render_timeline(
     profiler.stages.video_encode_ms = video_start.elapsed().as_secs_f64() * 1000.0;
 
     // --- Audio encoding: pre-render all clips into buffer, then encode ---
-    send_progress(&progress_tx, total_frames, total_frames, render_start, RenderStage::AudioEncoding);
-    let audio_start = std::time::Instant::now();
-    encode_audio_offline(
-        timeline,
-        source_library,
-        total_duration.as_secs_f64(),
-        audio_frame_size,
-        &mut audio_enc_ctx,
-        &mut output_ctx,
-        audio_stream_index,
-        audio_stream_tb,
-    )?;
-    profiler.stages.audio_encode_ms = audio_start.elapsed().as_secs_f64() * 1000.0;
+    if let (Some(audio_enc_ctx), Some(audio_stream_tb)) = (&mut audio_enc_ctx, audio_stream_tb) {
+        send_progress(&progress_tx, total_frames, total_frames, render_start, RenderStage::AudioEncoding);
+        let audio_start = std::time::Instant::now();
+        let audio_frame_size = unsafe { (*audio_enc_ctx.as_ptr()).frame_size };
+        encode_audio_offline(
+            timeline,
+            source_library,
+            total_duration.as_secs_f64(),
+            audio_fade_secs,
+            audio_frame_size,
+            audio_enc_ctx,
+            &mut output_ctx,
+            audio_stream_index,
+            audio_stream_tb,
+        )?;
+        profiler.stages.audio_encode_ms = audio_start.elapsed().as_secs_f64() * 1000.0;
+    }
 
     // --- Flush encoders ---
     send_progress(&progress_tx, total_frames, total_frames, render_start, RenderStage::Flushing);
@@ -302,12 +338,14 @@ pub fn render_timeline(
         video_stream_index,
         video_stream_tb,
     )?;
-    flush_encoder(
-        &mut audio_enc_ctx,
-        &mut output_ctx,
-        audio_stream_index,
-        audio_stream_tb,
-    )?;
+    if let (Some(audio_enc_ctx), Some(audio_stream_tb)) = (&mut audio_enc_ctx, audio_stream_tb) {
+        flush_encoder(
+            audio_enc_ctx,
+            &mut output_ctx,
+            audio_stream_index,
+            audio_stream_tb,
+        )?;
+    }
     profiler.stages.flush_ms = flush_start.elapsed().as_secs_f64() * 1000.0;
 
     // --- Write trailer ---
@@ -329,6 +367,38 @@ pub fn render_timeline(
     Ok(())
 }
 
+/// Open the AAC encoder every render writes its sound with: 48kHz stereo
+/// planar float.
+fn open_audio_encoder(needs_global_header: bool) -> Result<AVCodecContext> {
+    let audio_codec = AVCodec::find_encoder_by_name(c"aac")
+        .ok_or_else(|| MediaError::EncoderError("AAC encoder not found".into()))?;
+
+    let mut audio_enc_ctx = AVCodecContext::new(&audio_codec);
+    audio_enc_ctx.set_sample_rate(OUTPUT_SAMPLE_RATE);
+    audio_enc_ctx.set_sample_fmt(ffi::AV_SAMPLE_FMT_FLTP);
+    audio_enc_ctx.set_time_base(ffi::AVRational { num: 1, den: OUTPUT_SAMPLE_RATE });
+
+    let stereo_layout = AVChannelLayout::from_nb_channels(OUTPUT_CHANNELS);
+    unsafe {
+        ffi::av_channel_layout_copy(
+            &mut (*audio_enc_ctx.as_mut_ptr()).ch_layout,
+            stereo_layout.as_ptr(),
+        );
+    }
+
+    if needs_global_header {
+        unsafe {
+            use rsmpeg::UnsafeDerefMut;
+            audio_enc_ctx.deref_mut().flags |= ffi::AV_CODEC_FLAG_GLOBAL_HEADER as i32;
+        }
+    }
+
+    audio_enc_ctx
+        .open(None)
+        .map_err(|e| MediaError::EncoderError(format!("Failed to open audio encoder: {e}")))?;
+    Ok(audio_enc_ctx)
+}
+
 /// Send a progress update if a channel is available. Silently ignores send errors.
 fn send_progress(
     tx: &Option<std::sync::mpsc::Sender<RenderProgress>>,
@@ -451,6 +521,7 @@ fn encode_video_frames(
     stream_index: i32,
     stream_tb: ffi::AVRational,
     video_decoders: &mut HashMap<PathBuf, CachedVideoDecoder>,
+    mut loop_fade: Option<LoopFade>,
     profiler: &mut ProfileCollector,
     progress_tx: &Option<std::sync::mpsc::Sender<RenderProgress>>,
     render_start: std::time::Instant,
@@ -475,7 +546,12 @@ fn encode_video_frames(
     // Cached RGBA→YUV converter — avoids creating SWS context + AVFrames per frame
     let mut rgba_to_yuv = CachedRgbaToYuvConverter::new(width, height)?;
 
-    for frame_idx in 0..total_frames {
+    let order: Vec<u64> = match &loop_fade {
+        Some(fade) => fade.render_order().collect(),
+        None => (0..total_frames).collect(),
+    };
+    for (step, frame_idx) in order.into_iter().enumerate() {
+        let step = step as u64;
         let frame_start = if profiling {
             Some(std::time::Instant::now())
         } else {
@@ -486,20 +562,13 @@ fn encode_video_frames(
         let pos = TimelinePosition::from_secs_f64(timeline_time);
 
         let t0 = if profiling { Some(std::time::Instant::now()) } else { None };
-        let all_clips = find_all_video_clips_at(timeline, source_library, pos);
+        let all_clips = find_all_video_clips_at(timeline, source_library, pos, config.fps);
         let find_clips_ms = t0.map(|t| t.elapsed().as_secs_f64() * 1000.0).unwrap_or(0.0);
 
         if all_clips.is_empty() {
             let mut frame = create_black_yuv_frame(width, height)?;
-            frame.set_pts(frame_idx as i64);
             let t_enc = if profiling { Some(std::time::Instant::now()) } else { None };
-            encode_frame(
-                video_enc_ctx,
-                output_ctx,
-                Some(&frame),
-                stream_index,
-                stream_tb,
-            )?;
+            emit_video_frame(&mut frame, frame_idx, &mut loop_fade, video_enc_ctx, output_ctx, stream_index, stream_tb)?;
             let encode_ms = t_enc.map(|t| t.elapsed().as_secs_f64() * 1000.0).unwrap_or(0.0);
 
             if profiling {
@@ -519,21 +588,26 @@ fn encode_video_frames(
             }
 
             // Send progress every 10 frames
-            if frame_idx % 10 == 0 || frame_idx == total_frames - 1 {
-                send_progress(progress_tx, frame_idx + 1, total_frames, render_start, RenderStage::VideoEncoding);
+            if step % 10 == 0 || step == total_frames - 1 {
+                send_progress(progress_tx, step + 1, total_frames, render_start, RenderStage::VideoEncoding);
             }
             continue;
         }
 
-        let any_has_effects = all_clips.iter().any(|(_, _, effects)| !effects.is_empty());
+        // Clips that aren't in the working colour space need converting in
+        // RGBA before compositing, just like clips with effects
+        let needs_rgba = all_clips
+            .iter()
+            .any(|layer| !layer.effects.is_empty() || !layer.color.is_working() || layer.fit != CanvasFit::Fit);
         let clip_count = all_clips.len();
 
-        if any_has_effects {
+        if needs_rgba {
             // Effect path: work in RGBA, run pipeline, convert to YUV at end
             let ctx = EffectContext {
                 time_secs: timeline_time,
                 frame_number: frame_idx,
                 fps: config.fps,
+                scale_quality: config.scaling.scale_quality(),
             };
 
             // Reset canvas to opaque black (fast memcpy instead of per-pixel init)
@@ -543,42 +617,32 @@ fn encode_video_frames(
             let mut effects_ms = 0.0;
             let mut composite_ms = 0.0;
 
-            for (source_path, source_time, clip_effects) in &all_clips {
+            for layer in &all_clips {
                 let t_dec = if profiling { Some(std::time::Instant::now()) } else { None };
                 let rgba_frame = decode_clip_to_rgba(
-                    source_path,
-                    *source_time,
+                    &layer.path,
+                    layer.source_time,
                     width as u32,
                     height as u32,
+                    config.scaling.scale_quality(),
                     video_decoders,
                 )?;
                 decode_ms += t_dec.map(|t| t.elapsed().as_secs_f64() * 1000.0).unwrap_or(0.0);
 
-                if let Some(clip_frame) = rgba_frame {
-                    if clip_effects.is_empty() {
-                        let t_comp = if profiling { Some(std::time::Instant::now()) } else { None };
-                        pipeline::blit_onto_canvas(&clip_frame, &mut rgba_canvas);
-                        composite_ms += t_comp.map(|t| t.elapsed().as_secs_f64() * 1000.0).unwrap_or(0.0);
-                    } else {
-                        let t_fx = if profiling { Some(std::time::Instant::now()) } else { None };
-                        let result = pipeline::run_effect_pipeline(
-                            clip_frame,
-                            width as u32,
-                            height as u32,
-                            clip_effects,
-                            &registry,
-                            &ctx,
-                        );
-                        effects_ms += t_fx.map(|t| t.elapsed().as_secs_f64() * 1000.0).unwrap_or(0.0);
-
-                        let t_comp = if profiling { Some(std::time::Instant::now()) } else { None };
-                        if !result.may_have_transparency && result.fills_canvas {
-                            pipeline::composite_opaque(&result.frame, &mut rgba_canvas);
-                        } else {
-                            pipeline::alpha_composite_rgba(&result.frame, &mut rgba_canvas);
-                        }
-                        composite_ms += t_comp.map(|t| t.elapsed().as_secs_f64() * 1000.0).unwrap_or(0.0);
-                    }
+                if let Some(mut clip_frame) = rgba_frame {
+                    color::to_working_space(&mut clip_frame.data, layer.color);
+                    let placement = layer.placement(&clip_frame, width as u32, height as u32);
+                    let (fx_ms, comp_ms) = composite_rgba_layer(
+                        clip_frame,
+                        &layer.effects,
+                        placement,
+                        &mut rgba_canvas,
+                        &registry,
+                        &ctx,
+                        profiling,
+                    );
+                    effects_ms += fx_ms;
+                    composite_ms += comp_ms;
                 }
             }
 
@@ -587,15 +651,8 @@ fn encode_video_frames(
             let yuv_frame = rgba_to_yuv.convert(&rgba_canvas)?;
             let color_convert_ms = t_cc.map(|t| t.elapsed().as_secs_f64() * 1000.0).unwrap_or(0.0);
 
-            yuv_frame.set_pts(frame_idx as i64);
             let t_enc = if profiling { Some(std::time::Instant::now()) } else { None };
-            encode_frame(
-                video_enc_ctx,
-                output_ctx,
-                Some(yuv_frame),
-                stream_index,
-                stream_tb,
-            )?;
+            emit_video_frame(yuv_frame, frame_idx, &mut loop_fade, video_enc_ctx, output_ctx, stream_index, stream_tb)?;
             let encode_ms = t_enc.map(|t| t.elapsed().as_secs_f64() * 1000.0).unwrap_or(0.0);
 
             if profiling {
@@ -619,11 +676,11 @@ fn encode_video_frames(
             let mut decode_ms = 0.0;
             let mut composite_ms = 0.0;
 
-            for (source_path, source_time, _clip_effects) in &all_clips {
+            for layer in &all_clips {
                 let t_dec = if profiling { Some(std::time::Instant::now()) } else { None };
                 let clip_frame = decode_and_scale_clip(
-                    source_path,
-                    *source_time,
+                    &layer.path,
+                    layer.source_time,
                     width,
                     height,
                     canvas_w,
@@ -639,15 +696,8 @@ fn encode_video_frames(
                     composite_ms += t_comp.map(|t| t.elapsed().as_secs_f64() * 1000.0).unwrap_or(0.0);
                 }
             }
-            canvas.set_pts(frame_idx as i64);
             let t_enc = if profiling { Some(std::time::Instant::now()) } else { None };
-            encode_frame(
-                video_enc_ctx,
-                output_ctx,
-                Some(&canvas),
-                stream_index,
-                stream_tb,
-            )?;
+            emit_video_frame(&mut canvas, frame_idx, &mut loop_fade, video_enc_ctx, output_ctx, stream_index, stream_tb)?;
             let encode_ms = t_enc.map(|t| t.elapsed().as_secs_f64() * 1000.0).unwrap_or(0.0);
 
             if profiling {
@@ -668,13 +718,158 @@ fn encode_video_frames(
         }
 
         // Send progress every 10 frames and on final frame
-        if frame_idx % 10 == 0 || frame_idx == total_frames - 1 {
-            send_progress(progress_tx, frame_idx + 1, total_frames, render_start, RenderStage::VideoEncoding);
+        if step % 10 == 0 || step == total_frames - 1 {
+            send_progress(progress_tx, step + 1, total_frames, render_start, RenderStage::VideoEncoding);
         }
     }
     Ok(())
 }
 
+/// Frame bookkeeping for a loop render. The `fade_frames` after the first
+/// `body_frames` are rendered first and kept; the body follows, and its
+/// first `fade_frames` get them dissolved in, fading from all tail to none.
+pub struct LoopFade {
+    pub body_frames: u64,
+    pub fade_frames: u64,
+    tail: Vec<YuvPlanes>,
+}
+
+impl LoopFade {
+    pub fn new(body_frames: u64, fade_frames: u64) -> Self {
+        Self {
+            body_frames,
+            fade_frames,
+            tail: Vec::with_capacity(fade_frames as usize),
+        }
+    }
+
+    /// Timeline frame indices in the order they must be rendered.
+    pub fn render_order(&self) -> impl Iterator<Item = u64> + use<> {
+        let end = self.body_frames + self.fade_frames;
+        (self.body_frames..end).chain(0..self.body_frames)
+    }
+
+    /// How much of the kept tail frame shows in output frame `index`.
+    pub fn tail_weight(&self, index: u64) -> f32 {
+        if index >= self.fade_frames {
+            0.0
+        } else {
+            1.0 - index as f32 / self.fade_frames as f32
+        }
+    }
+}
+
+/// Copy of a YUV420P frame's planes, without line padding.
+struct YuvPlanes {
+    planes: [Vec<u8>; 3],
+    widths: [usize; 3],
+}
+
+impl YuvPlanes {
+    fn copy_from(frame: &AVFrame) -> Self {
+        let (w, h) = (frame.width as usize, frame.height as usize);
+        let widths = [w, w / 2, w / 2];
+        let heights = [h, h / 2, h / 2];
+        let planes = std::array::from_fn(|plane| {
+            let mut data = Vec::with_capacity(widths[plane] * heights[plane]);
+            unsafe {
+                let ptr = (*frame.as_ptr()).data[plane] as *const u8;
+                let linesize = (*frame.as_ptr()).linesize[plane] as usize;
+                for row in 0..heights[plane] {
+                    data.extend_from_slice(std::slice::from_raw_parts(ptr.add(row * linesize), widths[plane]));
+                }
+            }
+            data
+        });
+        Self { planes, widths }
+    }
+
+    /// Mix these planes into `frame` at `weight` (0 leaves it, 1 replaces it).
+    fn blend_into(&self, frame: &mut AVFrame, weight: f32) {
+        unsafe {
+            let data = (*frame.as_mut_ptr()).data;
+            let linesize = (*frame.as_mut_ptr()).linesize;
+            for plane in 0..3 {
+                let width = self.widths[plane];
+                for (row, tail) in self.planes[plane].chunks_exact(width.max(1)).enumerate() {
+                    let dst = std::slice::from_raw_parts_mut(data[plane].add(row * linesize[plane] as usize), width);
+                    for (d, &t) in dst.iter_mut().zip(tail) {
+                        *d = (*d as f32 + (t as f32 - *d as f32) * weight).round() as u8;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Encode a composited frame as output frame `frame_idx`. For loop renders,
+/// tail frames are kept instead, and the first frames get them blended in.
+fn emit_video_frame(
+    frame: &mut AVFrame,
+    frame_idx: u64,
+    loop_fade: &mut Option<LoopFade>,
+    video_enc_ctx: &mut AVCodecContext,
+    output_ctx: &mut AVFormatContextOutput,
+    stream_index: i32,
+    stream_tb: ffi::AVRational,
+) -> Result<()> {
+    if let Some(fade) = loop_fade {
+        if frame_idx >= fade.body_frames {
+            fade.tail.push(YuvPlanes::copy_from(frame));
+            return Ok(());
+        }
+        if let Some(tail) = fade.tail.get(frame_idx as usize) {
+            unsafe {
+                ffi::av_frame_make_writable(frame.as_mut_ptr());
+            }
+            tail.blend_into(frame, fade.tail_weight(frame_idx));
+        }
+    }
+    frame.set_pts(frame_idx as i64);
+    encode_frame(video_enc_ctx, output_ctx, Some(frame), stream_index, stream_tb)
+}
+
+/// Run a decoded clip frame's effects and composite it onto the RGBA canvas
+/// at `placement`, as the effects path of the export does for every layer.
+/// Returns the time spent on effects and on compositing, in milliseconds,
+/// when `profiling`.
+pub(crate) fn composite_rgba_layer(
+    clip_frame: FrameBuffer,
+    clip_effects: &[EffectInstance],
+    placement: Placement,
+    canvas: &mut FrameBuffer,
+    registry: &EffectRegistry,
+    ctx: &EffectContext,
+    profiling: bool,
+) -> (f64, f64) {
+    let elapsed_ms = |t: Option<std::time::Instant>| t.map(|t| t.elapsed().as_secs_f64() * 1000.0).unwrap_or(0.0);
+    if clip_effects.is_empty() {
+        let t_comp = if profiling { Some(std::time::Instant::now()) } else { None };
+        pipeline::blit_onto_canvas(&clip_frame, canvas, placement, ctx.scale_quality);
+        return (0.0, elapsed_ms(t_comp));
+    }
+
+    let t_fx = if profiling { Some(std::time::Instant::now()) } else { None };
+    let result = pipeline::run_effect_pipeline(
+        clip_frame,
+        canvas.width,
+        canvas.height,
+        placement,
+        clip_effects,
+        registry,
+        ctx,
+    );
+    let effects_ms = elapsed_ms(t_fx);
+
+    let t_comp = if profiling { Some(std::time::Instant::now()) } else { None };
+    if !result.may_have_transparency && result.fills_canvas {
+        pipeline::composite_opaque(&result.frame, canvas);
+    } else {
+        pipeline::alpha_composite_rgba(&result.frame, canvas);
+    }
+    (effects_ms, elapsed_ms(t_comp))
+}
+
 /// Decode and scale a video frame from source, returning the scaled/rotated frame
 /// and its canvas layout. Returns None if no frame is available (EOF).
 fn decode_and_scale_clip(
@@ -809,6 +1004,7 @@ fn decode_clip_to_rgba(
     source_time: f64,
     max_w: u32,
     max_h: u32,
+    quality: ScaleQuality,
     decoders: &mut HashMap<PathBuf, CachedVideoDecoder>,
 ) -> Result<Option<FrameBuffer>> {
     let path_key = source_path.to_path_buf();
@@ -831,6 +1027,7 @@ fn decode_clip_to_rgba(
     }
 
     let cached = decoders.get_mut(&path_key).unwrap();
+    cached.decoder.set_scale_quality(quality);
 
     // Check frame cache: if source_time maps to the same source frame, reuse it
     if let Some((ref cached_frame, cached_pts)) = cached.last_rgba_frame {
@@ -885,6 +1082,7 @@ fn encode_audio_offline(
     timeline: &Timeline,
     source_library: &SourceLibrary,
     total_duration_secs: f64,
+    loop_fade_secs: Option<f64>,
     frame_size: i32,
     audio_enc_ctx: &mut AVCodecContext,
     output_ctx: &mut AVFormatContextOutput,
@@ -892,28 +1090,10 @@ fn encode_audio_offline(
     stream_tb: ffi::AVRational,
 ) -> Result<()> {
     let samples_per_frame = if frame_size > 0 { frame_size } else { 1024 };
-    let total_samples =
-        (total_duration_secs * OUTPUT_SAMPLE_RATE as f64).ceil() as usize;
-
-    // Pre-allocate output buffer: interleaved f32 at 48kHz stereo, initialized to silence
-    let mut output_buffer = vec![0.0f32; total_samples * OUTPUT_CHANNELS as usize];
+    let mut output_buffer = mix_audio(timeline, source_library, total_duration_secs)?;
 
-    // Process each audio clip: decode sequentially and write into the buffer
-    for track in &timeline.tracks {
-        if track.track_type != TrackType::Audio {
-            continue;
-        }
-        for clip in &track.clips {
-            if let Some(asset) = source_library.get(clip.asset_id) {
-                decode_audio_clip_into_buffer(
-                    &asset.path,
-                    clip.source_range.start.as_secs_f64(),
-                    clip.timeline_range.start.as_secs_f64(),
-                    clip.timeline_range.end.as_secs_f64(),
-                    &mut output_buffer,
-                )?;
-            }
-        }
+    if let Some(secs) = loop_fade_secs {
+        loop_crossfade_audio(&mut output_buffer, (secs * OUTPUT_SAMPLE_RATE as f64).round() as usize);
     }
 
     // Encode the buffer into AAC frames
@@ -951,15 +1131,60 @@ fn encode_audio_offline(
     Ok(())
 }
 
+/// Mix every audible audio clip of the first `total_duration_secs` of the
+/// timeline into one interleaved f32 buffer at 48kHz stereo, silent where
+/// nothing plays.
+pub(crate) fn mix_audio(timeline: &Timeline, source_library: &SourceLibrary, total_duration_secs: f64) -> Result<Vec<f32>> {
+    let total_samples =
+        (total_duration_secs * OUTPUT_SAMPLE_RATE as f64).ceil() as usize;
+
+    // Pre-allocate output buffer: interleaved f32 at 48kHz stereo, initialized to silence
+    let mut output_buffer = vec![0.0f32; total_samples * OUTPUT_CHANNELS as usize];
+
+    // Process each audio clip: decode sequentially and write into the buffer
+    for (track_index, track) in timeline.tracks.iter().enumerate() {
+        // Skips video tracks as well as muted (or un-soloed) audio tracks.
+        if !timeline.is_track_audible(track_index) {
+            continue;
+        }
+        for clip in &track.clips {
+            if let Some(asset) = source_library.get(clip.asset_id) {
+                decode_audio_clip_into_buffer(&asset.path, clip, &mut output_buffer)?;
+            }
+        }
+    }
+    Ok(output_buffer)
+}
+
+/// Dissolve the last `fade_samples` (per channel) of an interleaved stereo
+/// buffer into its start and drop them, so the buffer loops seamlessly.
+pub fn loop_crossfade_audio(buffer: &mut Vec<f32>, fade_samples: usize) {
+    let channels = OUTPUT_CHANNELS as usize;
+    let total = buffer.len() / channels;
+    let fade = fade_samples.min(total / 2);
+    if fade == 0 {
+        return;
+    }
+    let body = total - fade;
+    for i in 0..fade {
+        let head_weight = i as f32 / fade as f32;
+        for c in 0..channels {
+            let tail = buffer[(body + i) * channels + c];
+            let head = &mut buffer[i * channels + c];
+            *head = *head * head_weight + tail * (1.0 - head_weight);
+        }
+    }
+    buffer.truncate(body * channels);
+}
+
 /// Decode audio from a single clip and write resampled samples into the output buffer.
-/// Uses a SwrContext to convert from source format to 48kHz stereo interleaved f32.
-fn decode_audio_clip_into_buffer(
-    source_path: &Path,
-    source_start_secs: f64,
-    timeline_start_secs: f64,
-    timeline_end_secs: f64,
-    output_buffer: &mut [f32],
-) -> Result<()> {
+/// Uses a SwrContext to convert from source format to 48kHz stereo interleaved f32,
+/// then applies the clip's channel map, volume envelope and speed (see `Retime`).
+pub(crate) fn decode_audio_clip_into_buffer(source_path: &Path, clip: &Clip, output_buffer: &mut [f32]) -> Result<()> {
+    let source_start_secs = clip.source_range.start.as_secs_f64();
+    let timeline_start_secs = clip.timeline_range.start.as_secs_f64();
+    let timeline_end_secs = clip.timeline_range.end.as_secs_f64();
+    let speed = clip.is_retimed().then(|| clip.speed());
     let path_str = source_path.to_string_lossy().to_string();
     let c_path = CString::new(path_str.clone())
         .map_err(|_| MediaError::OpenFailed(path_str.clone()))?;
@@ -1055,6 +1280,8 @@ fn decode_audio_clip_into_buffer(
 
     let mut samples_written = 0usize;
     let mut past_source_start = false;
+    // Retimed clips play at the new speed, pitch and all unless told to keep it
+    let mut retime = speed.map(|speed| Retime::new(speed, clip.preserve_pitch));
 
     // Decode sequentially
     loop {
@@ -1076,10 +1303,16 @@ fn decode_audio_clip_into_buffer(
                                 &input_ctx,
                                 audio_stream_index,
                             )?;
-                            if let Some((samples, pts_secs)) = converted {
+                            if let Some((mut samples, pts_secs)) = converted {
                                 if pts_secs < source_start_secs - 0.05 {
                                     continue;
                                 }
+                                clip.channel_map.apply(&mut samples, OUTPUT_CHANNELS as usize);
+                                clip.volume_envelope.apply(&mut samples, OUTPUT_CHANNELS as usize, OUTPUT_SAMPLE_RATE as u32, pts_secs);
+                                let samples = match retime.as_mut() {
+                                    Some(v) => v.process(&samples),
+                                    None => samples,
+                                };
                                 let written = write_samples_to_buffer(
                                     &samples,
                                     output_buffer,
@@ -1115,7 +1348,7 @@ fn decode_audio_clip_into_buffer(
                         &input_ctx,
                         audio_stream_index,
                     )?;
-                    if let Some((samples, pts_secs)) = converted {
+                    if let Some((mut samples, pts_secs)) = converted {
                         // Skip frames before source start
                         if !past_source_start {
                             if pts_secs < source_start_secs - 0.05 {
@@ -1123,6 +1356,12 @@ fn decode_audio_clip_into_buffer(
                             }
                             past_source_start = true;
                         }
+                        clip.channel_map.apply(&mut samples, OUTPUT_CHANNELS as usize);
+                        clip.volume_envelope.apply(&mut samples, OUTPUT_CHANNELS as usize, OUTPUT_SAMPLE_RATE as u32, pts_secs);
+                        let samples = match retime.as_mut() {
+                            Some(v) => v.process(&samples),
+                            None => samples,
+                        };
 
                         let written = write_samples_to_buffer(
                             &samples,
@@ -1203,6 +1442,191 @@ fn convert_audio_frame(
     Ok(Some((samples, pts_secs)))
 }
 
+/// Streaming linear-interpolation resampler for interleaved stereo chunks,
+/// playing them back `speed` times faster (pitch shifts with it).
+pub struct Varispeed {
+    speed: f64,
+    /// Read position in frames, relative to the start of the next chunk;
+    /// -1 is `previous`, the last frame of the chunk before.
+    position: f64,
+    previous: Option<[f32; 2]>,
+}
+
+impl Varispeed {
+    pub fn new(speed: f64) -> Self {
+        Self {
+            speed,
+            position: 0.0,
+            previous: None,
+        }
+    }
+
+    pub fn process(&mut self, samples: &[f32]) -> Vec<f32> {
+        let frames = (samples.len() / 2) as isize;
+        if frames == 0 {
+            return Vec::new();
+        }
+        let previous = self.previous.unwrap_or([samples[0], samples[1]]);
+        let frame = |i: isize| -> [f32; 2] {
+            if i < 0 {
+                previous
+            } else {
+                [samples[2 * i as usize], samples[2 * i as usize + 1]]
+            }
+        };
+        let mut out = Vec::with_capacity((frames as f64 / self.speed) as usize * 2 + 2);
+        while (self.position.floor() as isize) < frames - 1 {
+            let i = self.position.floor() as isize;
+            let t = (self.position - i as f64) as f32;
+            let (a, b) = (frame(i), frame(i + 1));
+            out.push(a[0] + (b[0] - a[0]) * t);
+            out.push(a[1] + (b[1] - a[1]) * t);
+            self.position += self.speed;
+        }
+        self.position -= frames as f64;
+        self.previous = Some(frame(frames - 1));
+        out
+    }
+}
+
+/// Frames per WSOLA window (about 21 ms at 48 kHz).
+const STRETCH_WINDOW: usize = 1024;
+/// Output hop: windows overlap by half.
+const STRETCH_HOP: usize = STRETCH_WINDOW / 2;
+/// How far (in frames) a window may move from its nominal input position
+/// to line up with the previous one.
+const STRETCH_TOLERANCE: usize = 256;
+
+/// Streaming WSOLA time-stretcher for interleaved stereo chunks, playing
+/// them back `speed` times faster at the original pitch. Output lags the
+/// input by one window and fades in over the first hop.
+pub struct TimeStretch {
+    speed: f64,
+    /// Input not yet consumed; frame 0 is absolute input frame `input_start`.
+    input: Vec<f32>,
+    input_start: usize,
+    /// Absolute input frame where the next window would start unshifted.
+    nominal: f64,
+    /// Absolute input frame of the last window placed.
+    previous: Option<usize>,
+    /// Overlap-add accumulator, one window long.
+    overlap: Vec<f32>,
+    window: Vec<f32>,
+}
+
+impl TimeStretch {
+    pub fn new(speed: f64) -> Self {
+        // Periodic Hann: overlapping by half sums to exactly one
+        let window = (0..STRETCH_WINDOW)
+            .map(|n| (0.5 - 0.5 * (2.0 * std::f64::consts::PI * n as f64 / STRETCH_WINDOW as f64).cos()) as f32)
+            .collect();
+        Self {
+            speed,
+            input: Vec::new(),
+            input_start: 0,
+            nominal: 0.0,
+            previous: None,
+            overlap: vec![0.0; STRETCH_WINDOW * 2],
+            window,
+        }
+    }
+
+    pub fn process(&mut self, samples: &[f32]) -> Vec<f32> {
+        self.input.extend_from_slice(samples);
+        let mut out = Vec::new();
+        loop {
+            let input_end = self.input_start + self.input.len() / 2;
+            let nominal = self.nominal.round() as usize;
+            let (lo, hi) = match self.previous {
+                None => (nominal, nominal),
+                Some(_) => (
+                    nominal.saturating_sub(STRETCH_TOLERANCE).max(self.input_start),
+                    nominal + STRETCH_TOLERANCE,
+                ),
+            };
+            let needed = match self.previous {
+                Some(previous) => (hi + STRETCH_WINDOW).max(previous + STRETCH_HOP + STRETCH_HOP),
+                None => hi + STRETCH_WINDOW,
+            };
+            if input_end < needed {
+                break;
+            }
+            let start = match self.previous {
+                Some(previous) => self.best_match(previous + STRETCH_HOP, lo, hi),
+                None => nominal,
+            };
+
+            // Overlap-add the window, then hand out the finished first hop
+            let offset = (start - self.input_start) * 2;
+            for (n, w) in self.window.iter().enumerate() {
+                self.overlap[2 * n] += self.input[offset + 2 * n] * w;
+                self.overlap[2 * n + 1] += self.input[offset + 2 * n + 1] * w;
+            }
+            out.extend(self.overlap.drain(..STRETCH_HOP * 2));
+            self.overlap.resize(STRETCH_WINDOW * 2, 0.0);
+
+            self.previous = Some(start);
+            self.nominal += STRETCH_HOP as f64 * self.speed;
+            // Drop input no later window can reach
+            let keep_from = (self.nominal.round() as usize)
+                .saturating_sub(STRETCH_TOLERANCE)
+                .min(start + STRETCH_HOP)
+                .max(self.input_start);
+            self.input.drain(..(keep_from - self.input_start) * 2);
+            self.input_start = keep_from;
+        }
+        out
+    }
+
+    /// Start in `lo..=hi` whose first hop best matches (normalized cross
+    /// correlation, on the mid channel) the hop starting at `template`.
+    fn best_match(&self, template: usize, lo: usize, hi: usize) -> usize {
+        let mid = |frame: usize| {
+            let i = (frame - self.input_start) * 2;
+            self.input[i] + self.input[i + 1]
+        };
+        let mut best = (f32::NEG_INFINITY, lo);
+        for start in lo..=hi {
+            let (mut dot, mut energy) = (0.0f32, 0.0f32);
+            for n in 0..STRETCH_HOP {
+                let x = mid(start + n);
+                dot += x * mid(template + n);
+                energy += x * x;
+            }
+            let score = dot / energy.sqrt().max(1e-6);
+            if score > best.0 {
+                best = (score, start);
+            }
+        }
+        best.1
+    }
+}
+
+/// How a retimed clip's audio is brought to its new speed.
+pub enum Retime {
+    /// Resampled like tape: pitch follows speed.
+    Varispeed(Varispeed),
+    /// Time-stretched at the original pitch.
+    TimeStretch(TimeStretch),
+}
+
+impl Retime {
+    pub fn new(speed: f64, preserve_pitch: bool) -> Self {
+        if preserve_pitch {
+            Self::TimeStretch(TimeStretch::new(speed))
+        } else {
+            Self::Varispeed(Varispeed::new(speed))
+        }
+    }
+
+    pub fn process(&mut self, samples: &[f32]) -> Vec<f32> {
+        match self {
+            Self::Varispeed(varispeed) => varispeed.process(samples),
+            Self::TimeStretch(stretch) => stretch.process(samples),
+        }
+    }
+}
+
 /// Write decoded samples into the output buffer at the correct position.
 /// Returns the number of f32 values written.
 pub fn write_samples_to_buffer(
@@ -1548,8 +1972,7 @@ fn find_video_clip_at(
         if track.track_type == TrackType::Video {
             if let Some(clip) = track.clip_at(pos) {
                 if let Some(asset) = source_library.get(clip.asset_id) {
-                    let source_time = clip.source_range.start.as_secs_f64()
-                        + (pos.as_secs_f64() - clip.timeline_range.start.as_secs_f64());
+                    let source_time = clip.source_secs_at(pos);
                     return Some((asset.path.clone(), source_time, clip.effects.clone()));
                 }
             }
@@ -1558,28 +1981,57 @@ fn find_video_clip_at(
     None
 }
 
-/// Find ALL video clips at a timeline position, ordered bottom-to-top (V1 first, VN last).
+/// A video clip under a timeline position, as the export composites it.
+pub(crate) struct VideoLayer {
+    pub path: PathBuf,
+    pub source_time: f64,
+    pub effects: Vec<EffectInstance>,
+    pub color: ColorSpace,
+    pub fit: CanvasFit,
+    /// Displayed frame size of the source, which 1:1 placement keeps.
+    pub source_size: (u32, u32),
+    pub pan: PanPath,
+}
+
+impl VideoLayer {
+    /// Where a decoded frame of this layer lands on a full-size canvas.
+    pub fn placement(&self, frame: &FrameBuffer, canvas_w: u32, canvas_h: u32) -> Placement {
+        let placement = self.fit.placement(frame.width, frame.height, self.source_size, canvas_w, canvas_h);
+        self.pan.apply(placement, canvas_w, self.source_time)
+    }
+}
+
+/// Find ALL video clips at a timeline position, ordered bottom-to-top (V1 first, VN last),
+/// with each clip's source time, effects, source colour space and canvas fit.
+/// Source times follow each clip's rate conform against the project's `fps`.
 ///
 /// Video tracks are stored top-to-bottom in the vec (VN...V1), so we collect
 /// in reverse order to get V1 (bottom) first and VN (top) last.
-fn find_all_video_clips_at(
+pub(crate) fn find_all_video_clips_at(
     timeline: &Timeline,
     source_library: &SourceLibrary,
     pos: TimelinePosition,
-) -> Vec<(PathBuf, f64, Vec<EffectInstance>)> {
-    let mut clips: Vec<(PathBuf, f64, Vec<EffectInstance>)> = Vec::new();
+    fps: f64,
+) -> Vec<VideoLayer> {
+    let mut clips: Vec<VideoLayer> = Vec::new();
     // Iterate video tracks: in the vec, index 0 is the topmost video track
     // We want bottom-to-top ordering, so collect in reverse
     let video_tracks: Vec<_> = timeline.tracks.iter()
-        .filter(|t| t.track_type == TrackType::Video)
+        .filter(|t| t.track_type == TrackType::Video && !t.hidden)
         .collect();
 
     for track in video_tracks.iter().rev() {
         if let Some(clip) = track.clip_at(pos) {
             if let Some(asset) = source_library.get(clip.asset_id) {
-                let source_time = clip.source_range.start.as_secs_f64()
-                    + (pos.as_secs_f64() - clip.timeline_range.start.as_secs_f64());
-                clips.push((asset.path.clone(), source_time, clip.effects.clone()));
+                clips.push(VideoLayer {
+                    path: asset.path.clone(),
+                    source_time: clip.source_frame_secs_at(pos, asset.fps, fps),
+                    effects: clip.effects.clone(),
+                    color: asset.color,
+                    fit: clip.canvas_fit,
+                    source_size: (asset.display_width(), asset.display_height()),
+                    pan: clip.pan.clone(),
+                });
             }
         }
     }
@@ -1597,8 +2049,7 @@ fn find_audio_clip_at(
         if track.track_type == TrackType::Audio {
             if let Some(clip) = track.clip_at(pos) {
                 if let Some(asset) = source_library.get(clip.asset_id) {
-                    let source_time = clip.source_range.start.as_secs_f64()
-                        + (pos.as_secs_f64() - clip.timeline_range.start.as_secs_f64());
+                    let source_time = clip.source_secs_at(pos);
                     return Some((asset.path.clone(), source_time));
                 }
             }
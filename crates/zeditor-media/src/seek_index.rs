@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError};
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MediaError, Result};
+
+/// Bumped whenever the on-disk format changes, so old entries are rebuilt.
+const SEEK_INDEX_VERSION: u32 = 1;
+
+/// Keyframe timestamps of an asset's video stream, gathered by demuxing the
+/// file once (no decoding). Lets the decoder seek straight to the keyframe
+/// that starts the target's GOP instead of trusting the container's own
+/// seeking, which is slow and can land a GOP early (or late) in long-GOP
+/// footage.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeekIndex {
+    version: u32,
+    /// Size and mtime of the file the index was built from.
+    source_len: u64,
+    source_mtime_nanos: u64,
+    pub stream_index: usize,
+    /// Stream time base as (num, den).
+    pub time_base: (i32, i32),
+    /// Keyframe PTS in stream time base, ascending.
+    pub keyframes: Vec<i64>,
+}
+
+impl SeekIndex {
+    /// Demux every packet of the first decodable video stream and record the
+    /// keyframes.
+    pub fn build(path: &Path) -> Result<Self> {
+        use std::ffi::CString;
+
+        let (source_len, source_mtime_nanos) = source_stamp(path)?;
+        let path_str = path.to_string_lossy().to_string();
+        let c_path = CString::new(path_str.clone()).map_err(|_| MediaError::OpenFailed(path_str.clone()))?;
+        let mut input_ctx = rsmpeg::avformat::AVFormatContextInput::open(&c_path)
+            .map_err(|e| MediaError::OpenFailed(format!("{path_str}: {e}")))?;
+
+        let (stream_index, time_base) = input_ctx
+            .streams()
+            .iter()
+            .enumerate()
+            .find(|(_, stream)| {
+                let codecpar = stream.codecpar();
+                codecpar.codec_type == rsmpeg::ffi::AVMEDIA_TYPE_VIDEO
+                    && rsmpeg::avcodec::AVCodec::find_decoder(codecpar.codec_id).is_some()
+            })
+            .map(|(i, stream)| (i, (stream.time_base.num, stream.time_base.den)))
+            .ok_or(MediaError::NoVideoStream)?;
+
+        let mut keyframes = Vec::new();
+        while let Some(packet) = input_ctx
+            .read_packet()
+            .map_err(|e| MediaError::DecoderError(format!("read_packet: {e}")))?
+        {
+            if packet.stream_index as usize != stream_index
+                || packet.flags & rsmpeg::ffi::AV_PKT_FLAG_KEY as i32 == 0
+            {
+                continue;
+            }
+            let pts = if packet.pts != rsmpeg::ffi::AV_NOPTS_VALUE { packet.pts } else { packet.dts };
+            if pts != rsmpeg::ffi::AV_NOPTS_VALUE {
+                keyframes.push(pts);
+            }
+        }
+        keyframes.sort_unstable();
+        keyframes.dedup();
+
+        Ok(Self {
+            version: SEEK_INDEX_VERSION,
+            source_len,
+            source_mtime_nanos,
+            stream_index,
+            time_base,
+            keyframes,
+        })
+    }
+
+    /// The index for `path`: from memory or `cache_dir` when it still matches
+    /// the file, otherwise built and stored in both. Building demuxes the
+    /// whole file, so this belongs on a background thread. A failed cache
+    /// write only costs a rebuild next session.
+    pub fn load_or_build(path: &Path, cache_dir: Option<&Path>) -> Result<Arc<Self>> {
+        let stamp = source_stamp(path)?;
+        if let Some(index) = Self::loaded(path) {
+            return Ok(index);
+        }
+
+        let cache_path = cache_dir.map(|dir| dir.join(cache_file_name(path)));
+        let cached = cache_path
+            .as_deref()
+            .and_then(|p| std::fs::read(p).ok())
+            .and_then(|bytes| serde_json::from_slice::<Self>(&bytes).ok())
+            .filter(|index| index.matches(stamp));
+        let index = match cached {
+            Some(index) => index,
+            None => {
+                let index = Self::build(path)?;
+                if let Some(cache_path) = &cache_path {
+                    let _ = index.write(cache_path);
+                }
+                index
+            }
+        };
+
+        let index = Arc::new(index);
+        loaded().insert(path.to_path_buf(), Arc::clone(&index));
+        Ok(index)
+    }
+
+    /// The index `load_or_build` has already made ready for `path`, if it
+    /// still matches the file. Never touches the disk cache or demuxes, so
+    /// decoders can ask on open and fall back to container seeking.
+    pub fn loaded(path: &Path) -> Option<Arc<Self>> {
+        let stamp = source_stamp(path).ok()?;
+        loaded().get(path).filter(|index| index.matches(stamp)).cloned()
+    }
+
+    /// The last keyframe at or before `pts` (stream time base), i.e. the one
+    /// a decode of `pts` has to start from.
+    pub fn keyframe_at_or_before(&self, pts: i64) -> Option<i64> {
+        let after = self.keyframes.partition_point(|&k| k <= pts);
+        after.checked_sub(1).map(|i| self.keyframes[i])
+    }
+
+    pub fn secs_to_pts(&self, secs: f64) -> i64 {
+        let (num, den) = self.time_base;
+        (secs * den as f64 / num.max(1) as f64).round() as i64
+    }
+
+    pub fn pts_to_secs(&self, pts: i64) -> f64 {
+        let (num, den) = self.time_base;
+        pts as f64 * num as f64 / den.max(1) as f64
+    }
+
+    fn matches(&self, (len, mtime_nanos): (u64, u64)) -> bool {
+        self.version == SEEK_INDEX_VERSION && self.source_len == len && self.source_mtime_nanos == mtime_nanos
+    }
+
+    /// Written to a temporary file and renamed so readers never see a
+    /// partial entry.
+    fn write(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_vec(self).map_err(std::io::Error::other)?;
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(tmp, path)
+    }
+}
+
+/// Indexes ready this session, by path. A thread that panicked while holding
+/// the lock can't have left a half-inserted entry, so poisoning is ignored.
+fn loaded() -> MutexGuard<'static, HashMap<PathBuf, Arc<SeekIndex>>> {
+    static LOADED: OnceLock<Mutex<HashMap<PathBuf, Arc<SeekIndex>>>> = OnceLock::new();
+    LOADED.get_or_init(Default::default).lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn source_stamp(path: &Path) -> Result<(u64, u64)> {
+    let metadata = std::fs::metadata(path)?;
+    let mtime_nanos = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_nanos() as u64);
+    Ok((metadata.len(), mtime_nanos))
+}
+
+/// FNV-1a of the path, which stays stable across builds unlike `DefaultHasher`.
+fn cache_file_name(path: &Path) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in path.to_string_lossy().bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("{hash:016x}.json")
+}
@@ -0,0 +1,167 @@
+//! Segmented export: the timeline is cut into frame-aligned spans whose
+//! pictures are rendered concurrently, each on its own thread with its own
+//! decoders and encoder, then joined without re-encoding. The sound is mixed
+//! once over the whole timeline and muxed onto the joined picture, so the
+//! joins can't be heard.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use zeditor_core::media::SourceLibrary;
+use zeditor_core::timeline::{TimeRange, Timeline, TimelinePosition};
+
+use crate::encoder;
+use crate::error::{MediaError, Result};
+use crate::render_profile::{RenderProgress, RenderStage};
+use crate::renderer::{self, RenderConfig, OUTPUT_CHANNELS, OUTPUT_SAMPLE_RATE};
+use crate::stems;
+
+/// How often combined progress is reported while segments render.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Split `duration` at `fps` into at most `segments` spans of whole frames,
+/// in order. Every span but the last has the same number of frames.
+pub fn segment_ranges(duration: Duration, fps: f64, segments: usize) -> Vec<TimeRange> {
+    let total_frames = (duration.as_secs_f64() * fps).ceil() as u64;
+    if total_frames == 0 {
+        return Vec::new();
+    }
+    let per_segment = total_frames.div_ceil(segments.max(1) as u64);
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    while start < total_frames {
+        let end = (start + per_segment).min(total_frames);
+        let end_secs = if end == total_frames {
+            duration.as_secs_f64()
+        } else {
+            end as f64 / fps
+        };
+        ranges.push(TimeRange {
+            start: TimelinePosition::from_secs_f64(start as f64 / fps),
+            end: TimelinePosition::from_secs_f64(end_secs),
+        });
+        start = end;
+    }
+    ranges
+}
+
+/// Render the timeline in up to `segments` parallel parts and join them
+/// into `config.output_path`. One segment renders like `render_timeline`.
+/// The parts, the joined picture and the mixed sound go in a temporary
+/// folder beside the output, removed however the render ends.
+pub fn render_segmented(
+    timeline: &Timeline,
+    source_library: &SourceLibrary,
+    config: &RenderConfig,
+    segments: usize,
+    progress_tx: Option<mpsc::Sender<RenderProgress>>,
+) -> Result<()> {
+    let duration = timeline.duration();
+    let ranges = segment_ranges(duration, config.fps, segments);
+    if ranges.len() <= 1 {
+        return renderer::render_timeline(timeline, source_library, config, progress_tx);
+    }
+
+    let render_start = Instant::now();
+    let extension = config
+        .output_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("mp4");
+    let dir = match config.output_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let work_dir = tempfile::Builder::new().prefix(".zeditor-segments-").tempdir_in(dir)?;
+    let parts: Vec<PathBuf> = (0..ranges.len())
+        .map(|index| work_dir.path().join(format!("part{:02}.{extension}", index + 1)))
+        .collect();
+
+    let results: Vec<Result<()>> = std::thread::scope(|scope| {
+        let mut receivers = Vec::new();
+        let handles: Vec<_> = ranges
+            .iter()
+            .zip(&parts)
+            .map(|(range, part)| {
+                let (tx, rx) = mpsc::channel();
+                receivers.push(rx);
+                let segment_timeline = timeline.extract_range(*range);
+                let segment_config = RenderConfig {
+                    output_path: part.clone(),
+                    ..config.clone()
+                };
+                let span = range.duration();
+                scope.spawn(move || {
+                    renderer::render(&segment_timeline, source_library, &segment_config, span, None, false, Some(tx))
+                })
+            })
+            .collect();
+
+        // Sum the segments' frame counts into one progress report
+        let mut latest: Vec<Option<RenderProgress>> = vec![None; receivers.len()];
+        loop {
+            let finished = handles.iter().all(|handle| handle.is_finished());
+            for (slot, rx) in latest.iter_mut().zip(&receivers) {
+                while let Ok(progress) = rx.try_recv() {
+                    *slot = Some(progress);
+                }
+            }
+            if let Some(tx) = &progress_tx {
+                let current: u64 = latest.iter().flatten().map(|p| p.current_frame).sum();
+                let total: u64 = latest.iter().flatten().map(|p| p.total_frames).sum();
+                let _ = tx.send(RenderProgress {
+                    current_frame: current,
+                    total_frames: total,
+                    elapsed: render_start.elapsed(),
+                    stage: RenderStage::VideoEncoding,
+                });
+            }
+            if finished {
+                break;
+            }
+            std::thread::sleep(PROGRESS_INTERVAL);
+        }
+
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|_| Err(MediaError::EncoderError("segment render panicked".into())))
+            })
+            .collect()
+    });
+
+    results.into_iter().collect::<Result<Vec<()>>>()?;
+    let video = work_dir.path().join(format!("video.{extension}"));
+    encoder::concat_lossless(&parts, &video)?;
+    // The joined picture holds everything the parts did
+    for part in &parts {
+        let _ = std::fs::remove_file(part);
+    }
+
+    let total_frames = (duration.as_secs_f64() * config.fps).ceil() as u64;
+    if let Some(tx) = &progress_tx {
+        let _ = tx.send(RenderProgress {
+            current_frame: total_frames,
+            total_frames,
+            elapsed: render_start.elapsed(),
+            stage: RenderStage::AudioEncoding,
+        });
+    }
+    let samples = renderer::mix_audio(timeline, source_library, duration.as_secs_f64())?;
+    let audio = work_dir.path().join("audio.wav");
+    stems::write_wav(&audio, &samples, OUTPUT_SAMPLE_RATE as u32, OUTPUT_CHANNELS as u16)?;
+    encoder::mux_audio(&video, &audio, &config.output_path)?;
+
+    if let Some(tx) = &progress_tx {
+        let _ = tx.send(RenderProgress {
+            current_frame: total_frames,
+            total_frames,
+            elapsed: render_start.elapsed(),
+            stage: RenderStage::Complete,
+        });
+    }
+    Ok(())
+}
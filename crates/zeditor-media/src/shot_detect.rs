@@ -0,0 +1,81 @@
+//! Shot (scene cut) detection by frame differencing.
+//!
+//! Frames are decoded at thumbnail size and compared with the previous one
+//! by per-channel colour histograms and mean pixel difference. A cut is a
+//! frame whose score jumps past a threshold, at least [`MIN_SHOT_SECS`] after
+//! the previous cut so flashes and fast pans don't split a shot into slivers.
+
+use std::path::Path;
+
+use crate::decoder::{FfmpegDecoder, VideoDecoder};
+use crate::error::Result;
+
+/// Score (0..=1) a frame change must exceed to count as a cut.
+pub const DEFAULT_CUT_THRESHOLD: f64 = 0.35;
+
+/// Shortest shot reported; closer cuts are merged into the earlier one.
+pub const MIN_SHOT_SECS: f64 = 0.5;
+
+/// Size frames are decoded at for analysis.
+const ANALYSIS_WIDTH: u32 = 64;
+const ANALYSIS_HEIGHT: u32 = 36;
+
+const HISTOGRAM_BINS: usize = 16;
+
+/// Source times (seconds) where a new shot starts, excluding the start of
+/// the file.
+pub fn detect_shots(path: &Path, threshold: f64) -> Result<Vec<f64>> {
+    let mut decoder = FfmpegDecoder::open(path)?;
+    let mut scores = Vec::new();
+    let mut previous: Option<Vec<u8>> = None;
+    while let Some(frame) = decoder.decode_next_frame_scaled(ANALYSIS_WIDTH, ANALYSIS_HEIGHT)? {
+        if let Some(previous) = &previous {
+            scores.push((frame.pts_secs, frame_difference(previous, &frame.data)));
+        }
+        previous = Some(frame.data);
+    }
+    Ok(pick_cuts(&scores, threshold, MIN_SHOT_SECS))
+}
+
+/// Change between two equally sized RGB24 frames, 0 (identical) to 1: the
+/// average of the colour histogram distance, which ignores motion, and the
+/// mean per-channel difference, which catches cuts between shots with
+/// similar colours.
+pub fn frame_difference(a: &[u8], b: &[u8]) -> f64 {
+    if a.is_empty() || a.len() != b.len() {
+        return 0.0;
+    }
+    let histogram = |rgb: &[u8]| {
+        let mut bins = [[0usize; HISTOGRAM_BINS]; 3];
+        for pixel in rgb.chunks_exact(3) {
+            for (channel, &value) in pixel.iter().enumerate() {
+                bins[channel][value as usize * HISTOGRAM_BINS / 256] += 1;
+            }
+        }
+        bins
+    };
+    let (ha, hb) = (histogram(a), histogram(b));
+    let histogram_distance = ha
+        .iter()
+        .flatten()
+        .zip(hb.iter().flatten())
+        .map(|(x, y)| x.abs_diff(*y))
+        .sum::<usize>() as f64
+        / (2 * a.len()) as f64;
+    let mean_difference = a.iter().zip(b).map(|(x, y)| x.abs_diff(*y) as u64).sum::<u64>() as f64
+        / (255 * a.len()) as f64;
+    (histogram_distance + mean_difference) / 2.0
+}
+
+/// Cut times from `(pts_secs, score)` pairs: scores above `threshold`, no
+/// closer than `min_shot_secs` to the previous cut or the start.
+pub fn pick_cuts(scores: &[(f64, f64)], threshold: f64, min_shot_secs: f64) -> Vec<f64> {
+    let mut cuts: Vec<f64> = Vec::new();
+    for &(pts, score) in scores {
+        let last = cuts.last().copied().unwrap_or(0.0);
+        if score > threshold && pts - last >= min_shot_secs {
+            cuts.push(pts);
+        }
+    }
+    cuts
+}
@@ -0,0 +1,74 @@
+//! Audio stems: each audible audio track mixed on its own and written as a
+//! 24-bit WAV next to the main render, for handing the mix to an audio
+//! engineer.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use zeditor_core::media::SourceLibrary;
+use zeditor_core::render_ranges;
+use zeditor_core::timeline::Timeline;
+
+use crate::error::Result;
+use crate::renderer::{decode_audio_clip_into_buffer, OUTPUT_CHANNELS, OUTPUT_SAMPLE_RATE};
+
+/// Stem file for the track named `track_name` (the `index`th track) of a
+/// render to `output_path`: `<output name> - <track name>.wav` beside it.
+pub fn stem_path(output_path: &Path, track_name: &str, index: usize) -> PathBuf {
+    let output_name = output_path.file_stem().and_then(|s| s.to_str()).unwrap_or("render");
+    let name = render_ranges::file_name("{project} - {name}", output_name, track_name, index);
+    output_path.with_file_name(format!("{name}.wav"))
+}
+
+/// Write a stem per audible audio track of `timeline` beside `output_path`,
+/// each the length of the timeline, and return their paths in track order.
+/// Tracks are mixed exactly as in the main render, just not summed.
+pub fn render_stems(timeline: &Timeline, source_library: &SourceLibrary, output_path: &Path) -> Result<Vec<PathBuf>> {
+    let channels = OUTPUT_CHANNELS as usize;
+    let total_samples = (timeline.duration().as_secs_f64() * OUTPUT_SAMPLE_RATE as f64).ceil() as usize;
+    let mut paths = Vec::new();
+    for (track_index, track) in timeline.tracks.iter().enumerate() {
+        // Skips video tracks as well as muted (or un-soloed) audio tracks
+        if !timeline.is_track_audible(track_index) {
+            continue;
+        }
+        let mut buffer = vec![0.0f32; total_samples * channels];
+        for clip in &track.clips {
+            if let Some(asset) = source_library.get(clip.asset_id) {
+                decode_audio_clip_into_buffer(&asset.path, clip, &mut buffer)?;
+            }
+        }
+        let path = stem_path(output_path, &track.name, track_index);
+        write_wav(&path, &buffer, OUTPUT_SAMPLE_RATE as u32, OUTPUT_CHANNELS as u16)?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+/// Write interleaved `samples` as a 24-bit PCM WAV file, clipping to ±1.
+pub fn write_wav(path: &Path, samples: &[f32], sample_rate: u32, channels: u16) -> Result<()> {
+    const BYTES_PER_SAMPLE: u16 = 3;
+    let data_len = (samples.len() * BYTES_PER_SAMPLE as usize) as u32;
+    let block_align = channels * BYTES_PER_SAMPLE;
+
+    let mut bytes = Vec::with_capacity(44 + data_len as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    bytes.extend_from_slice(&channels.to_le_bytes());
+    bytes.extend_from_slice(&sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&(sample_rate * block_align as u32).to_le_bytes());
+    bytes.extend_from_slice(&block_align.to_le_bytes());
+    bytes.extend_from_slice(&(BYTES_PER_SAMPLE * 8).to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        let value = (sample.clamp(-1.0, 1.0) * 8_388_607.0).round() as i32;
+        bytes.extend_from_slice(&value.to_le_bytes()[..3]);
+    }
+
+    std::fs::File::create(path)?.write_all(&bytes)?;
+    Ok(())
+}
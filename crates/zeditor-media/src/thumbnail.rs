@@ -1,5 +1,7 @@
 use std::path::Path;
 
+use zeditor_core::scaler::ScaleQuality;
+
 use crate::decoder::{FfmpegDecoder, VideoDecoder, VideoFrame};
 use crate::error::Result;
 
@@ -28,7 +30,36 @@ pub fn generate_thumbnail_rgba_scaled(
     max_height: u32,
 ) -> Result<VideoFrame> {
     let mut decoder = FfmpegDecoder::open(path)?;
+    // Thumbnails are decoded once and shown for a long time, so use the best filter
+    decoder.set_scale_quality(ScaleQuality::Lanczos);
     decoder
         .decode_next_frame_rgba_scaled(max_width, max_height)?
         .ok_or_else(|| crate::error::MediaError::DecoderError("no frames in video".into()))
 }
+
+/// Decode the frame on screen at `timestamp_secs` (the last one starting at
+/// or before it) scaled to fit within max_width x max_height, in RGBA. Frames
+/// are decoded forward from the keyframe before it, as when scrubbing, so
+/// the result is exact rather than the nearest keyframe.
+pub fn frame_rgba_scaled_at(
+    path: &Path,
+    timestamp_secs: f64,
+    max_width: u32,
+    max_height: u32,
+) -> Result<VideoFrame> {
+    // Allow for pts rounding in the stream's time base
+    const PTS_EPSILON: f64 = 1e-3;
+
+    let mut decoder = FfmpegDecoder::open(path)?;
+    decoder.seek_to(timestamp_secs.max(0.0))?;
+    let mut shown = None;
+    while let Some(frame) = decoder.decode_next_frame_rgba_scaled(max_width, max_height)? {
+        if frame.pts_secs > timestamp_secs + PTS_EPSILON {
+            // Past the target: keep the previous frame, unless the target
+            // lies before the first frame
+            return Ok(shown.unwrap_or(frame));
+        }
+        shown = Some(frame);
+    }
+    shown.ok_or_else(|| crate::error::MediaError::DecoderError("no frame at timestamp".into()))
+}
@@ -0,0 +1,153 @@
+//! Offline speech-to-text with whisper.cpp.
+//!
+//! Audio is mixed down and resampled to the 16 kHz mono whisper expects, and
+//! its sub-word tokens are joined back into timed words. The engine is behind
+//! the `whisper` feature because whisper.cpp is a C++ build and needs a
+//! downloaded `ggml-*.bin` model; without it [`transcribe`] fails with
+//! [`MediaError::TranscriptionUnavailable`].
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use zeditor_core::transcript::TranscriptWord;
+
+use crate::audio_decoder::FfmpegAudioDecoder;
+use crate::error::{MediaError, Result};
+
+/// Sample rate whisper models are trained on.
+pub const WHISPER_SAMPLE_RATE: u32 = 16_000;
+
+/// Overrides the model file used for transcription.
+pub const MODEL_ENV_VAR: &str = "ZEDITOR_WHISPER_MODEL";
+
+/// A whisper token with its time in seconds from the start of the audio.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechToken {
+    pub text: String,
+    pub start_secs: f64,
+    pub end_secs: f64,
+}
+
+/// Model file to transcribe with: `$ZEDITOR_WHISPER_MODEL`, else
+/// `~/.local/share/zeditor/models/ggml-base.bin`.
+pub fn model_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(MODEL_ENV_VAR) {
+        return Some(PathBuf::from(path));
+    }
+    let home = std::env::var_os("HOME")?;
+    Some(PathBuf::from(home).join(".local/share/zeditor/models/ggml-base.bin"))
+}
+
+/// The whole audio of `path`, mixed to mono at [`WHISPER_SAMPLE_RATE`].
+pub fn decode_speech_audio(path: &Path) -> Result<Vec<f32>> {
+    let mut decoder = FfmpegAudioDecoder::open(path)?;
+    let rate = decoder.sample_rate().max(1);
+    let mut mono = Vec::new();
+    while let Some(frame) = decoder.decode_next_audio_frame()? {
+        let channels = frame.channels.max(1) as usize;
+        mono.extend(
+            frame
+                .samples
+                .chunks_exact(channels)
+                .map(|chunk| chunk.iter().sum::<f32>() / channels as f32),
+        );
+    }
+    Ok(resample(&mono, rate, WHISPER_SAMPLE_RATE))
+}
+
+/// Linear-interpolation resample of mono samples.
+pub fn resample(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
+    if from_rate == to_rate || samples.is_empty() {
+        return samples.to_vec();
+    }
+    let len = (samples.len() as u64 * to_rate as u64 / from_rate as u64) as usize;
+    let step = from_rate as f64 / to_rate as f64;
+    (0..len)
+        .map(|i| {
+            let pos = i as f64 * step;
+            let index = pos as usize;
+            let a = samples[index];
+            let b = samples.get(index + 1).copied().unwrap_or(a);
+            a + (b - a) * (pos - index as f64) as f32
+        })
+        .collect()
+}
+
+/// Join whisper's sub-word tokens into words: a token starting with a space
+/// starts a new word, others (word pieces, punctuation) extend the current
+/// one. Special tokens (`[_BEG_]`, `<|endoftext|>`, ...) are dropped.
+pub fn words_from_tokens(tokens: &[SpeechToken]) -> Vec<TranscriptWord> {
+    let mut words: Vec<TranscriptWord> = Vec::new();
+    for token in tokens {
+        if token.text.starts_with("[_") || token.text.starts_with("<|") || token.text.trim().is_empty() {
+            continue;
+        }
+        let start = Duration::from_secs_f64(token.start_secs.max(0.0));
+        let end = Duration::from_secs_f64(token.end_secs.max(token.start_secs).max(0.0));
+        match words.last_mut() {
+            Some(word) if !token.text.starts_with(char::is_whitespace) => {
+                word.text.push_str(&token.text);
+                word.end = word.end.max(end);
+            }
+            _ => words.push(TranscriptWord {
+                text: token.text.trim_start().to_string(),
+                start,
+                end,
+            }),
+        }
+    }
+    words
+}
+
+/// Transcribe the speech in `path` with the whisper model at `model`.
+/// Words are timed in source time.
+pub fn transcribe(path: &Path, model: &Path) -> Result<Vec<TranscriptWord>> {
+    let audio = decode_speech_audio(path)?;
+    if audio.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(words_from_tokens(&run_whisper(&audio, model)?))
+}
+
+#[cfg(feature = "whisper")]
+fn run_whisper(audio: &[f32], model: &Path) -> Result<Vec<SpeechToken>> {
+    use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+    let failed = |e: whisper_rs::WhisperError| MediaError::TranscriptionFailed(e.to_string());
+    if !model.exists() {
+        return Err(MediaError::TranscriptionFailed(format!(
+            "whisper model not found at {} (set {MODEL_ENV_VAR})",
+            model.display()
+        )));
+    }
+    let context = WhisperContext::new_with_params(&model.to_string_lossy(), WhisperContextParameters::default())
+        .map_err(failed)?;
+    let mut state = context.create_state().map_err(failed)?;
+    let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+    params.set_language(Some("auto"));
+    params.set_token_timestamps(true);
+    params.set_print_progress(false);
+    params.set_print_realtime(false);
+    params.set_print_special(false);
+    state.full(params, audio).map_err(failed)?;
+
+    // Token times are in centiseconds
+    let mut tokens = Vec::new();
+    for segment in 0..state.full_n_segments().map_err(failed)? {
+        for token in 0..state.full_n_tokens(segment).map_err(failed)? {
+            let text = state.full_get_token_text(segment, token).map_err(failed)?;
+            let data = state.full_get_token_data(segment, token).map_err(failed)?;
+            tokens.push(SpeechToken {
+                text,
+                start_secs: data.t0 as f64 / 100.0,
+                end_secs: data.t1 as f64 / 100.0,
+            });
+        }
+    }
+    Ok(tokens)
+}
+
+#[cfg(not(feature = "whisper"))]
+fn run_whisper(_audio: &[f32], _model: &Path) -> Result<Vec<SpeechToken>> {
+    Err(MediaError::TranscriptionUnavailable)
+}
@@ -0,0 +1,87 @@
+//! Peak waveforms of a stretch of an audio file, for drawing, and quiet
+//! points to cut it at.
+
+use std::path::Path;
+
+use zeditor_core::audio;
+
+use crate::audio_decoder::FfmpegAudioDecoder;
+use crate::error::Result;
+
+/// How far a trimmed audio edge may move to reach a quiet cut point.
+pub const TRIM_SNAP_WINDOW_SECS: f64 = 0.1;
+
+/// Peak absolute sample (all channels) in each of `buckets` equal slices of
+/// `duration_secs` of audio starting `start_secs` into `path`. Slices past
+/// the end of the file stay 0.0.
+pub fn waveform_peaks(path: &Path, start_secs: f64, duration_secs: f64, buckets: usize) -> Result<Vec<f32>> {
+    let mut peaks = vec![0.0f32; buckets];
+    if buckets == 0 || duration_secs <= 0.0 {
+        return Ok(peaks);
+    }
+    let mut decoder = FfmpegAudioDecoder::open(path)?;
+    if start_secs > 0.0 {
+        decoder.seek_to(start_secs)?;
+    }
+    let end_secs = start_secs + duration_secs;
+    let buckets_per_sec = buckets as f64 / duration_secs;
+    while let Some(frame) = decoder.decode_next_audio_frame()? {
+        if frame.pts_secs >= end_secs {
+            break;
+        }
+        let channels = frame.channels.max(1) as usize;
+        let rate = frame.sample_rate.max(1) as f64;
+        for (i, chunk) in frame.samples.chunks_exact(channels).enumerate() {
+            let t = frame.pts_secs + i as f64 / rate - start_secs;
+            if t < 0.0 {
+                continue;
+            }
+            let bucket = (t * buckets_per_sec) as usize;
+            if bucket >= buckets {
+                break;
+            }
+            let peak = chunk.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+            peaks[bucket] = peaks[bucket].max(peak);
+        }
+    }
+    Ok(peaks)
+}
+
+/// Source time within [`TRIM_SNAP_WINDOW_SECS`] of `source_secs` where
+/// cutting the audio at `path` won't click (see
+/// [`audio::quiet_cut_point`]), never before 0 or after `max_secs`.
+pub fn quiet_cut_near(path: &Path, source_secs: f64, max_secs: f64) -> Result<f64> {
+    let start_secs = (source_secs - TRIM_SNAP_WINDOW_SECS).max(0.0);
+    let end_secs = (source_secs + TRIM_SNAP_WINDOW_SECS).min(max_secs);
+    let mut decoder = FfmpegAudioDecoder::open(path)?;
+    if start_secs > 0.0 {
+        decoder.seek_to(start_secs)?;
+    }
+
+    // Mono mix, from the first sample at or after start_secs
+    let mut samples = Vec::new();
+    let mut first_secs = None;
+    let mut sample_rate = 0;
+    'frames: while let Some(frame) = decoder.decode_next_audio_frame()? {
+        let channels = frame.channels.max(1) as usize;
+        let rate = frame.sample_rate.max(1) as f64;
+        sample_rate = frame.sample_rate;
+        for (i, chunk) in frame.samples.chunks_exact(channels).enumerate() {
+            let t = frame.pts_secs + i as f64 / rate;
+            if t < start_secs {
+                continue;
+            }
+            if t > end_secs {
+                break 'frames;
+            }
+            first_secs.get_or_insert(t);
+            samples.push(chunk.iter().sum::<f32>() / channels as f32);
+        }
+    }
+    let Some(first_secs) = first_secs else {
+        return Ok(source_secs);
+    };
+    let cut = audio::quiet_cut_point(&samples, sample_rate, first_secs, source_secs);
+    Ok(cut.clamp(0.0, max_secs))
+}
+
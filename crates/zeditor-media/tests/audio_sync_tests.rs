@@ -0,0 +1,76 @@
+use zeditor_media::audio_sync::{find_offset, onset_envelope, onsets, sync_offset, ENVELOPE_RATE};
+use zeditor_test_harness::fixtures;
+
+/// Irregularly spaced so only one alignment lines them all up.
+const BEEPS: [f64; 6] = [0.5, 1.3, 1.7, 3.0, 4.2, 4.55];
+
+/// Onset curve of a loudness curve that sits at a floor and jumps to `level`
+/// for 100 ms at each bucket in `at`.
+fn beep_curve(len: usize, at: &[usize], level: f32) -> Vec<f32> {
+    let mut rms = vec![0.01; len];
+    for &start in at {
+        for bucket in start..(start + 10).min(len) {
+            rms[bucket] = level;
+        }
+    }
+    onsets(&rms)
+}
+
+#[test]
+fn test_find_offset_of_later_starting_recording() {
+    let beeps = [50, 130, 170, 300, 420, 455, 700];
+    let reference = beep_curve(1000, &beeps, 0.8);
+    // Started recording 1.2s after the reference, and quieter
+    let shifted: Vec<usize> = beeps.iter().filter(|&&b| b >= 120).map(|b| b - 120).collect();
+    let other = beep_curve(600, &shifted, 0.2);
+
+    let found = find_offset(&reference, &other).unwrap();
+    assert!((found.offset_secs - 1.2).abs() < 1.0 / ENVELOPE_RATE, "{found:?}");
+    assert!(found.confidence > 0.9, "{found:?}");
+}
+
+#[test]
+fn test_find_offset_of_earlier_starting_recording() {
+    let beeps = [50, 130, 170, 300, 420, 455];
+    let other_beeps: Vec<usize> = beeps.iter().map(|b| b + 40).collect();
+    let found = find_offset(&beep_curve(500, &beeps, 1.0), &beep_curve(800, &other_beeps, 0.5)).unwrap();
+    assert!((found.offset_secs + 0.4).abs() < 1.0 / ENVELOPE_RATE, "{found:?}");
+}
+
+#[test]
+fn test_find_offset_rejects_silence() {
+    let silent = beep_curve(500, &[], 1.0);
+    let beeps = beep_curve(500, &[50, 130, 300], 1.0);
+    assert_eq!(find_offset(&silent, &beeps), None);
+    assert_eq!(find_offset(&beeps, &[]), None);
+}
+
+#[test]
+fn test_onset_envelope_peaks_at_beeps() {
+    let dir = fixtures::fixture_dir();
+    let path = fixtures::generate_test_audio_with_beeps(dir.path(), "sync_env", 3.0, &[0.5, 2.0]);
+
+    let envelope = onset_envelope(&path, 0.0, 3.0).unwrap();
+    assert_eq!(envelope.len(), 300);
+    let peak = envelope.iter().cloned().fold(0.0f32, f32::max);
+    let peaks: Vec<usize> = (0..envelope.len()).filter(|&i| envelope[i] > peak * 0.5).collect();
+    assert!(peaks.iter().any(|&i| (49..=51).contains(&i)), "peaks: {peaks:?}");
+    assert!(peaks.iter().any(|&i| (199..=201).contains(&i)), "peaks: {peaks:?}");
+    assert!(peaks.iter().all(|&i| (49..=51).contains(&i) || (199..=201).contains(&i)), "peaks: {peaks:?}");
+}
+
+#[test]
+fn test_sync_offset_between_camera_and_recorder() {
+    let dir = fixtures::fixture_dir();
+    let camera = fixtures::generate_test_video_with_beeps(dir.path(), "sync_camera", 6.0, &BEEPS);
+    // The recorder was started 0.8s before the camera
+    let recorder_beeps: Vec<f64> = BEEPS.iter().map(|b| b + 0.8).collect();
+    let recorder = fixtures::generate_test_audio_with_beeps(dir.path(), "sync_recorder", 7.0, &recorder_beeps);
+
+    let found = sync_offset(&camera, (0.0, 6.0), &recorder, (0.0, 7.0)).unwrap();
+    assert!((found.offset_secs + 0.8).abs() < 0.02, "{found:?}");
+
+    // Analysing from a trimmed in-point shifts the offset by the trim
+    let found = sync_offset(&camera, (1.0, 5.0), &recorder, (0.0, 7.0)).unwrap();
+    assert!((found.offset_secs + 1.8).abs() < 0.02, "{found:?}");
+}
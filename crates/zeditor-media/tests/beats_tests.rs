@@ -0,0 +1,28 @@
+use zeditor_media::beats::{detect_beats, detect_beats_in_file};
+use zeditor_test_harness::fixtures;
+
+#[test]
+fn test_detect_beats_silence_has_no_beat() {
+    assert!(detect_beats(&vec![0.0; 48000 * 4], 48000).is_none());
+    assert!(detect_beats(&[0.5; 100], 48000).is_none());
+}
+
+#[test]
+fn test_detect_beats_in_file_finds_tempo_and_grid() {
+    let dir = fixtures::fixture_dir();
+    // A click every 0.5s (120 BPM) starting at 0.25s
+    let beeps: Vec<f64> = (0..10).map(|i| 0.25 + i as f64 * 0.5).collect();
+    let path = fixtures::generate_test_audio_with_beeps(dir.path(), "beats", 5.0, &beeps);
+
+    let analysis = detect_beats_in_file(&path, 0.0, 5.0).unwrap().unwrap();
+    assert!((analysis.bpm - 120.0).abs() < 2.0, "bpm {}", analysis.bpm);
+    assert!(analysis.beats.len() >= 9, "beats {:?}", analysis.beats);
+    for beat in &analysis.beats {
+        let off_grid = (beat - 0.25).rem_euclid(0.5);
+        assert!(off_grid < 0.03 || off_grid > 0.47, "beat {beat} off the click grid");
+    }
+
+    // Times stay in file seconds when analysing from mid-file
+    let later = detect_beats_in_file(&path, 2.0, 3.0).unwrap().unwrap();
+    assert!(later.beats[0] >= 2.0, "beats {:?}", later.beats);
+}
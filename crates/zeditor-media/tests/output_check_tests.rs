@@ -0,0 +1,33 @@
+use zeditor_media::error::MediaError;
+use zeditor_media::output_check::{check_output, next_free_path};
+
+#[test]
+fn test_next_free_path_numbers_past_existing_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cut.mkv");
+    assert_eq!(next_free_path(&path).unwrap(), path);
+
+    std::fs::write(&path, b"").unwrap();
+    std::fs::write(dir.path().join("cut (2).mkv"), b"").unwrap();
+    assert_eq!(next_free_path(&path).unwrap(), dir.path().join("cut (3).mkv"));
+}
+
+#[test]
+fn test_check_output_rejects_missing_folders_and_full_disks() {
+    let dir = tempfile::tempdir().unwrap();
+    // The write probe leaves the user's own files alone and cleans up after itself
+    std::fs::write(dir.path().join(".zeditor-write-check"), b"mine").unwrap();
+    check_output(&dir.path().join("cut.mkv"), 1).unwrap();
+    assert_eq!(std::fs::read(dir.path().join(".zeditor-write-check")).unwrap(), b"mine");
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+    let missing = dir.path().join("nope").join("cut.mkv");
+    assert!(matches!(check_output(&missing, 1), Err(MediaError::OutputNotWritable(_))));
+
+    if cfg!(unix) {
+        assert!(matches!(
+            check_output(&dir.path().join("cut.mkv"), u64::MAX),
+            Err(MediaError::NotEnoughSpace { .. })
+        ));
+    }
+}
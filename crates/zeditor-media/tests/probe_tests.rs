@@ -56,3 +56,43 @@ fn test_probe_non_rotated_video() {
     assert_eq!(asset.display_width(), 320);
     assert_eq!(asset.display_height(), 240);
 }
+
+#[test]
+fn test_probe_reports_video_codec() {
+    let dir = fixtures::fixture_dir();
+    let path = fixtures::generate_test_video(dir.path(), "probe_codec", 1.0);
+
+    let asset = probe::probe(&path).unwrap();
+    assert!(!asset.codec.is_empty());
+    assert_ne!(asset.codec, "unknown_codec");
+}
+
+#[test]
+fn test_probe_details_video_with_audio() {
+    let dir = fixtures::fixture_dir();
+    let path = fixtures::generate_test_video_with_audio(dir.path(), "probe_details", 1.0);
+
+    let details = probe::probe_details(&path).unwrap();
+    assert!(details.container.contains("MOV") || details.container.contains("mp4"), "container: {}", details.container);
+    assert_eq!(details.video_codec.as_deref(), Some("h264"));
+    assert_eq!((details.width, details.height), (320, 240));
+    assert!(details.fps.is_some_and(|fps| (fps - 30.0).abs() < 0.01));
+    assert_eq!(details.pixel_format.as_deref(), Some("yuv420p"));
+    assert_eq!(details.bit_depth, Some(8));
+    assert_eq!(details.audio_codec.as_deref(), Some("aac"));
+    assert_eq!(details.audio_channels, Some(1));
+    assert_eq!(details.sample_rate, Some(44100));
+    assert_eq!(details.file_size, std::fs::metadata(&path).unwrap().len());
+    assert!(details.created.is_some());
+}
+
+#[test]
+fn test_creation_time_label() {
+    assert_eq!(
+        probe::creation_time_label("2024-05-01T12:30:07.000000Z").as_deref(),
+        Some("2024-05-01 12:30:07")
+    );
+    assert_eq!(probe::creation_time_label("2024-05-01 12:30:07").as_deref(), Some("2024-05-01 12:30:07"));
+    assert!(probe::creation_time_label("yesterday").is_none());
+    assert!(probe::creation_time_label("2024-05-01T12").is_none());
+}
@@ -0,0 +1,63 @@
+use zeditor_media::reframe::{analyze_pan, column_weights, focus_center, smooth_path, MAX_PAN_SPEED};
+use zeditor_test_harness::fixtures;
+
+#[test]
+fn test_column_weights_count_motion_and_skin() {
+    // Two columns, two rows: a skin-toned pixel on the left, a pixel on the
+    // right that turned from white to black
+    let previous = [200, 120, 90, 255, 255, 255, 0, 0, 0, 0, 0, 0];
+    let current = [200, 120, 90, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    assert_eq!(column_weights(Some(&previous), &current, 2), vec![0.5, 1.0]);
+    assert_eq!(column_weights(None, &current, 2), vec![0.5, 0.0]);
+}
+
+#[test]
+fn test_focus_center_follows_the_heaviest_window() {
+    let mut weights = vec![0.0; 10];
+    weights[8] = 1.0;
+    weights[9] = 1.0;
+    assert_eq!(focus_center(&weights, 0.3), Some(0.85));
+    weights[8] = 0.0;
+    weights[9] = 0.0;
+    weights[1] = 2.0;
+    assert_eq!(focus_center(&weights, 0.3), Some(0.15));
+    assert_eq!(focus_center(&[0.0; 10], 0.3), None);
+}
+
+#[test]
+fn test_smooth_path_holds_clamps_and_limits_speed() {
+    // Subject far right: the center stops where the crop window still fits
+    let samples: Vec<_> = (0..5).map(|i| (i as f64 * 0.2, Some(0.95))).collect();
+    assert!(smooth_path(&samples, 0.4).iter().all(|&(_, c)| (c - 0.8).abs() < 1e-9));
+
+    // A jump from left to right is eased in at no more than the max speed,
+    // and samples with no focus hold the last center
+    let samples: Vec<_> = (0..20)
+        .map(|i| {
+            let center = match i {
+                0..5 => Some(0.3),
+                5..8 => None,
+                _ => Some(0.7),
+            };
+            (i as f64 * 0.2, center)
+        })
+        .collect();
+    let path = smooth_path(&samples, 0.3);
+    for pair in path.windows(2) {
+        let step = (pair[1].1 - pair[0].1).abs();
+        assert!(step <= MAX_PAN_SPEED * 0.2 + 1e-9, "{step}");
+    }
+    assert!((path[0].1 - 0.3).abs() < 1e-9);
+    assert!((path[19].1 - 0.7).abs() < 1e-9);
+}
+
+#[test]
+fn test_analyze_pan_keeps_crop_inside_frame() {
+    let dir = fixtures::fixture_dir();
+    let path = fixtures::generate_test_video(dir.path(), "reframe", 2.0);
+
+    let pan = analyze_pan(&path, 0.5, 1.5, 0.3).unwrap();
+    assert!(!pan.is_empty());
+    assert!(pan.keys.iter().all(|k| (0.5..=1.5).contains(&k.source_secs)));
+    assert!(pan.keys.iter().all(|k| (0.15..=0.85).contains(&k.center_x)));
+}
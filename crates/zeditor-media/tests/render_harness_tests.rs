@@ -0,0 +1,210 @@
+use std::path::PathBuf;
+
+use zeditor_core::effects::{EffectInstance, EffectType};
+use zeditor_core::scaler::CanvasFit;
+use zeditor_media::render_harness::{SyntheticSource, SyntheticTimeline, check_golden, frame_hash};
+use zeditor_media::renderer::ScalingAlgorithm;
+
+const RED: [u8; 4] = [255, 0, 0, 255];
+const BLUE: [u8; 4] = [0, 0, 255, 255];
+const WHITE: [u8; 4] = [255, 255, 255, 255];
+const BLACK: [u8; 4] = [0, 0, 0, 255];
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join(format!("{name}.hashes"))
+}
+
+fn effect(effect_type: EffectType, params: &[(&str, f64)]) -> EffectInstance {
+    let mut instance = EffectInstance::new(effect_type);
+    for (name, value) in params {
+        assert!(instance.set_float(name, *value), "unknown parameter {name}");
+    }
+    instance
+}
+
+/// Three tracks of synthetic sources exercising letterboxing, every built-in
+/// effect and alpha compositing, with cuts between layers mid-render.
+fn effects_stack_timeline() -> SyntheticTimeline {
+    let mut synthetic = SyntheticTimeline::new(64, 36, 30.0);
+    let top = synthetic.add_video_track("V3");
+    let middle = synthetic.add_video_track("V2");
+    let bottom = synthetic.add_video_track("V1");
+
+    let gradient = synthetic.add_source(
+        "gradient",
+        SyntheticSource::HorizontalGradient { from: RED, to: BLUE },
+        128,
+        72,
+        1.0,
+    );
+    let bar = synthetic.add_source(
+        "bar",
+        SyntheticSource::MovingBar { color: WHITE, background: BLACK, bar_width: 6, speed: 40.0 },
+        48,
+        36,
+        1.0,
+    );
+    let overlay = synthetic.add_source(
+        "overlay",
+        SyntheticSource::VerticalGradient { from: [0, 255, 0, 255], to: [255, 255, 0, 255] },
+        64,
+        36,
+        0.5,
+    );
+
+    synthetic.add_clip(bottom, gradient, 0.0, 1.0, vec![]).unwrap();
+    synthetic
+        .add_clip(
+            middle,
+            bar,
+            0.2,
+            0.6,
+            vec![
+                effect(EffectType::Brightness, &[("brightness", -0.25)]),
+                effect(EffectType::Transform, &[("x_offset", 5.0), ("y_offset", -3.0)]),
+            ],
+        )
+        .unwrap();
+    synthetic
+        .add_clip(
+            top,
+            overlay,
+            0.4,
+            0.5,
+            vec![
+                effect(EffectType::Grayscale, &[]),
+                effect(EffectType::Opacity, &[("opacity", 0.5)]),
+            ],
+        )
+        .unwrap();
+    synthetic
+}
+
+#[test]
+fn test_solid_source_fills_canvas() {
+    let mut synthetic = SyntheticTimeline::new(32, 18, 30.0);
+    let track = synthetic.add_video_track("V1");
+    let red = synthetic.add_source("red", SyntheticSource::Solid(RED), 32, 18, 0.5);
+    synthetic.add_clip(track, red, 0.0, 0.5, vec![]).unwrap();
+
+    let frames = synthetic.render_frames(ScalingAlgorithm::Bilinear);
+    assert_eq!(frames.len(), 15);
+    for frame in &frames {
+        assert!(frame.data.chunks_exact(4).all(|p| p == RED));
+    }
+}
+
+#[test]
+fn test_narrow_source_is_letterboxed_on_black() {
+    let mut synthetic = SyntheticTimeline::new(64, 36, 30.0);
+    let track = synthetic.add_video_track("V1");
+    let blue = synthetic.add_source("blue", SyntheticSource::Solid(BLUE), 36, 36, 0.1);
+    synthetic.add_clip(track, blue, 0.0, 0.1, vec![]).unwrap();
+
+    let frame = synthetic.render_frame(0, ScalingAlgorithm::Bilinear);
+    assert_eq!(frame.pixel(0, 18), BLACK);
+    assert_eq!(frame.pixel(32, 18), BLUE);
+    assert_eq!(frame.pixel(63, 18), BLACK);
+}
+
+#[test]
+fn test_canvas_fit_fill_stretch_and_original() {
+    let render = |fit: CanvasFit| {
+        let mut synthetic = SyntheticTimeline::new(64, 36, 30.0);
+        let track = synthetic.add_video_track("V1");
+        let blue = synthetic.add_source("blue", SyntheticSource::Solid(BLUE), 18, 18, 0.1);
+        synthetic.add_clip(track, blue, 0.0, 0.1, vec![]).unwrap();
+        let clip_id = synthetic.timeline.tracks[track].clips[0].id;
+        synthetic.timeline.set_canvas_fit(track, clip_id, fit).unwrap();
+        synthetic.render_frame(0, ScalingAlgorithm::Bilinear)
+    };
+
+    // Fill and Stretch cover the canvas; Fill crops the square top and bottom
+    for fit in [CanvasFit::Fill, CanvasFit::Stretch] {
+        assert!(render(fit).data.chunks_exact(4).all(|p| p == BLUE), "{fit:?}");
+    }
+
+    // 1:1 keeps the 18px source at 18px, centered
+    let frame = render(CanvasFit::Original);
+    assert_eq!(frame.pixel(32, 18), BLUE);
+    assert_eq!(frame.pixel(32, 5), BLACK);
+    assert_eq!(frame.pixel(20, 18), BLACK);
+}
+
+#[test]
+fn test_clip_samples_source_time_relative_to_its_start() {
+    let mut synthetic = SyntheticTimeline::new(64, 36, 30.0);
+    let track = synthetic.add_video_track("V1");
+    let bar = synthetic.add_source(
+        "bar",
+        SyntheticSource::MovingBar { color: WHITE, background: BLACK, bar_width: 1, speed: 30.0 },
+        64,
+        36,
+        2.0,
+    );
+    synthetic.add_clip(track, bar, 1.0, 1.0, vec![]).unwrap();
+
+    // Frame 35 is 5 frames into the clip, so the bar has moved 5 pixels.
+    let frame = synthetic.render_frame(35, ScalingAlgorithm::Bilinear);
+    assert_eq!(frame.pixel(5, 0), WHITE);
+    assert_eq!(frame.pixel(4, 0), BLACK);
+    assert_eq!(frame.pixel(6, 0), BLACK);
+
+    // Before the clip starts the canvas is black.
+    let frame = synthetic.render_frame(10, ScalingAlgorithm::Bilinear);
+    assert!(frame.data.chunks_exact(4).all(|p| p == BLACK));
+}
+
+#[test]
+fn test_render_is_deterministic() {
+    let synthetic = effects_stack_timeline();
+    let first = synthetic.render_hashes(ScalingAlgorithm::Lanczos);
+    let second = synthetic.render_hashes(ScalingAlgorithm::Lanczos);
+    assert_eq!(first, second);
+    assert_eq!(first.len() as u64, synthetic.frame_count());
+}
+
+#[test]
+fn test_effects_stack_matches_golden() {
+    let synthetic = effects_stack_timeline();
+    for (scaling, name) in [
+        (ScalingAlgorithm::Bilinear, "effects_stack_bilinear"),
+        (ScalingAlgorithm::Lanczos, "effects_stack_lanczos"),
+    ] {
+        let hashes = synthetic.render_hashes(scaling);
+        check_golden(&golden_path(name), &hashes).unwrap();
+    }
+}
+
+#[test]
+fn test_check_golden_reports_first_mismatch() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("golden").join("sample.hashes");
+
+    // A missing golden file is recorded.
+    check_golden(&path, &[1, 2, 3]).unwrap();
+    assert!(path.exists());
+    check_golden(&path, &[1, 2, 3]).unwrap();
+
+    let err = check_golden(&path, &[1, 9, 3]).unwrap_err();
+    assert!(err.contains("frame 1"), "{err}");
+    let err = check_golden(&path, &[1, 2]).unwrap_err();
+    assert!(err.contains("2 frames"), "{err}");
+}
+
+#[test]
+fn test_frame_hash_covers_dimensions() {
+    let mut synthetic = SyntheticTimeline::new(4, 2, 30.0);
+    let track = synthetic.add_video_track("V1");
+    let red = synthetic.add_source("red", SyntheticSource::Solid(RED), 4, 2, 0.1);
+    synthetic.add_clip(track, red, 0.0, 0.1, vec![]).unwrap();
+    let wide = synthetic.render_frame(0, ScalingAlgorithm::Bilinear);
+
+    let mut tall = wide.clone();
+    tall.width = 2;
+    tall.height = 4;
+    assert_ne!(frame_hash(&wide), frame_hash(&tall));
+}
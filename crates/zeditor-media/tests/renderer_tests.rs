@@ -5,7 +5,7 @@ use zeditor_core::media::{MediaAsset, SourceLibrary};
 use zeditor_core::project::ProjectSettings;
 use zeditor_core::timeline::{Clip, TimeRange, Timeline, TimelinePosition};
 use zeditor_media::renderer::{
-    compute_canvas_layout, derive_render_config, render_timeline, RenderConfig, ScalingAlgorithm,
+    compute_canvas_layout, derive_render_config, render_loop, render_timeline, RenderConfig, ScalingAlgorithm,
 };
 use zeditor_test_harness::fixtures;
 
@@ -92,6 +92,100 @@ fn test_render_single_clip() {
     );
 }
 
+#[test]
+fn test_render_loop_drops_crossfade_length() {
+    let dir = fixtures::fixture_dir();
+    let video_path = fixtures::generate_test_video(dir.path(), "render_loop", 3.0);
+    let output_path = dir.path().join("output_loop.mkv");
+
+    let asset = zeditor_media::probe::probe(&video_path).unwrap();
+    let (timeline, source_library) = single_clip_timeline(&asset, false);
+    let mut config = RenderConfig::default_with_path(output_path.clone());
+    (config.width, config.height, config.canvas_width, config.canvas_height) = (320, 240, 320, 240);
+
+    let err = render_loop(&timeline, &source_library, &config, 2.0, None).unwrap_err();
+    assert!(matches!(err, zeditor_media::error::MediaError::LoopTooShort(_)), "{err:?}");
+
+    render_loop(&timeline, &source_library, &config, 1.0, None).unwrap();
+    let dur = zeditor_media::probe::probe(&output_path).unwrap().duration.as_secs_f64();
+    assert!((dur - 2.0).abs() < 0.2, "Expected ~2s loop, got {dur}s");
+}
+
+/// Loop renders keep the tail first, then fade it out over the head.
+#[test]
+fn test_loop_fade_order_and_audio_crossfade() {
+    use zeditor_media::renderer::{loop_crossfade_audio, LoopFade};
+
+    let fade = LoopFade::new(6, 2);
+    assert_eq!(fade.render_order().collect::<Vec<_>>(), vec![6, 7, 0, 1, 2, 3, 4, 5]);
+    assert_eq!(fade.tail_weight(0), 1.0);
+    assert_eq!(fade.tail_weight(1), 0.5);
+    assert_eq!(fade.tail_weight(2), 0.0);
+
+    // Head of ones, tail of zeros: the loop starts on the tail and ramps up
+    let mut buffer: Vec<f32> = (0..8).flat_map(|i| if i < 6 { [1.0, 1.0] } else { [0.0, 0.0] }).collect();
+    loop_crossfade_audio(&mut buffer, 2);
+    assert_eq!(buffer, vec![0.0, 0.0, 0.5, 0.5, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0]);
+}
+
+#[test]
+fn test_render_segmented_matches_duration() {
+    use zeditor_media::segmented::{render_segmented, segment_ranges};
+
+    let ranges = segment_ranges(std::time::Duration::from_secs(1), 30.0, 4);
+    assert_eq!(ranges.len(), 4);
+    assert_eq!(ranges[0].end, TimelinePosition::from_secs_f64(8.0 / 30.0));
+    assert_eq!(ranges[3].end, TimelinePosition::from_secs_f64(1.0));
+
+    let dir = fixtures::fixture_dir();
+    let video_path = fixtures::generate_test_video_with_audio(dir.path(), "render_segmented", 3.0);
+    let output_path = dir.path().join("output_segmented.mkv");
+    // A user's file with an old part name is none of the render's business
+    let bystander = output_path.with_extension("part01.mkv");
+    std::fs::write(&bystander, b"keep me").unwrap();
+
+    let asset = zeditor_media::probe::probe(&video_path).unwrap();
+    let (timeline, source_library) = single_clip_timeline(&asset, true);
+    let mut config = RenderConfig::default_with_path(output_path.clone());
+    (config.width, config.height, config.canvas_width, config.canvas_height) = (320, 240, 320, 240);
+
+    render_segmented(&timeline, &source_library, &config, 3, None).unwrap();
+    let rendered = zeditor_media::probe::probe(&output_path).unwrap();
+    let dur = rendered.duration.as_secs_f64();
+    assert!((dur - 3.0).abs() < 0.2, "Expected ~3s render, got {dur}s");
+    assert!(rendered.has_audio, "the mixed sound should be muxed in");
+    assert_eq!(std::fs::read(&bystander).unwrap(), b"keep me");
+    let leftovers: Vec<_> = std::fs::read_dir(dir.path())
+        .unwrap()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(".zeditor-segments-"))
+        .collect();
+    assert!(leftovers.is_empty(), "the work folder should be removed");
+}
+
+#[test]
+fn test_render_stems_writes_a_wav_per_audio_track() {
+    use zeditor_media::stems::{render_stems, stem_path};
+
+    let dir = fixtures::fixture_dir();
+    let video_path = fixtures::generate_test_video_with_audio(dir.path(), "render_stems", 1.0);
+    let output_path = dir.path().join("Final: cut.mkv");
+    assert_eq!(stem_path(&output_path, "Audio 1", 1), dir.path().join("Final_ cut - Audio 1.wav"));
+
+    let asset = zeditor_media::probe::probe(&video_path).unwrap();
+    let (timeline, source_library) = single_clip_timeline(&asset, true);
+    let stems = render_stems(&timeline, &source_library, &output_path).unwrap();
+    assert_eq!(stems, vec![stem_path(&output_path, "Audio 1", 1)]);
+
+    let wav = std::fs::read(&stems[0]).unwrap();
+    assert_eq!(&wav[..4], b"RIFF");
+    assert_eq!(&wav[8..16], b"WAVEfmt ");
+    // The timeline's length of 48kHz stereo at 3 bytes per sample, after the 44-byte header
+    let samples = (timeline.duration().as_secs_f64() * 48_000.0).ceil() as usize;
+    assert_eq!(wav.len(), 44 + samples * 2 * 3);
+    assert!(wav[44..].iter().any(|&b| b != 0), "stem should not be silent");
+}
+
 #[test]
 fn test_render_with_gap() {
     let dir = fixtures::fixture_dir();
@@ -719,6 +813,56 @@ fn test_audio_mixing_additive() {
     assert_eq!(buffer[4], 0.0);
 }
 
+/// Retimed clips are resampled continuously across decoded chunks.
+#[test]
+fn test_varispeed_resamples_across_chunks() {
+    use zeditor_media::renderer::Varispeed;
+
+    let ramp: Vec<f32> = (0..20).flat_map(|i| [i as f32, -(i as f32)]).collect();
+    let run = |speed: f64| -> Vec<f32> {
+        let mut varispeed = Varispeed::new(speed);
+        let out: Vec<f32> = ramp.chunks(6).flat_map(|chunk| varispeed.process(chunk)).collect();
+        out.iter().step_by(2).copied().collect()
+    };
+
+    let double = run(2.0);
+    assert_eq!(double, (0..10).map(|i| (2 * i) as f32).collect::<Vec<_>>());
+    let half = run(0.5);
+    assert_eq!(half.len(), 38);
+    assert!(half.iter().enumerate().all(|(i, &v)| (v - i as f32 * 0.5).abs() < 1e-6));
+}
+
+/// Pitch-preserving retimes change the length but not the frequency.
+#[test]
+fn test_time_stretch_keeps_pitch() {
+    use zeditor_media::renderer::Retime;
+
+    let sine: Vec<f32> = (0..48_000)
+        .flat_map(|i| {
+            let v = (2.0 * std::f64::consts::PI * 440.0 * i as f64 / 48_000.0).sin() as f32 * 0.5;
+            [v, v]
+        })
+        .collect();
+    // (output frames, rising zero crossings per second) over the steady middle half
+    let run = |speed: f64, preserve_pitch: bool| -> (usize, f64) {
+        let mut retime = Retime::new(speed, preserve_pitch);
+        let out: Vec<f32> = sine.chunks(1000).flat_map(|chunk| retime.process(chunk)).collect();
+        let left: Vec<f32> = out.iter().step_by(2).copied().collect();
+        let middle = &left[left.len() / 4..3 * left.len() / 4];
+        let crossings = middle.windows(2).filter(|w| w[0] < 0.0 && w[1] >= 0.0).count();
+        (left.len(), crossings as f64 * 48_000.0 / middle.len() as f64)
+    };
+
+    for speed in [0.5, 2.0] {
+        let (frames, freq) = run(speed, true);
+        let expected = 48_000.0 / speed;
+        assert!((frames as f64 - expected).abs() < 2048.0, "speed {speed}: {frames} frames");
+        assert!((freq - 440.0).abs() < 5.0, "speed {speed}: {freq} Hz");
+    }
+    let (_, freq) = run(2.0, false);
+    assert!((freq - 880.0).abs() < 10.0, "varispeed: {freq} Hz");
+}
+
 // =============================================================================
 // Brief 16: Pixel effect pipeline integration tests
 // =============================================================================
@@ -0,0 +1,110 @@
+use zeditor_media::decoder::{FfmpegDecoder, VideoDecoder};
+use zeditor_media::seek_index::SeekIndex;
+use zeditor_test_harness::fixtures;
+
+fn keyframe_secs(index: &SeekIndex) -> Vec<f64> {
+    index.keyframes.iter().map(|&pts| index.pts_to_secs(pts)).collect()
+}
+
+#[test]
+fn test_build_finds_every_keyframe() {
+    let dir = fixtures::fixture_dir();
+    let path = fixtures::generate_test_video_with_gop(dir.path(), "gop_index", 3.0, 30);
+
+    let index = SeekIndex::build(&path).unwrap();
+    let secs = keyframe_secs(&index);
+    assert_eq!(secs.len(), 3, "keyframes: {secs:?}");
+    for (found, expected) in secs.iter().zip([0.0, 1.0, 2.0]) {
+        assert!((found - expected).abs() < 0.02, "keyframes: {secs:?}");
+    }
+}
+
+#[test]
+fn test_keyframe_at_or_before() {
+    let dir = fixtures::fixture_dir();
+    let path = fixtures::generate_test_video_with_gop(dir.path(), "gop_lookup", 3.0, 30);
+    let index = SeekIndex::build(&path).unwrap();
+
+    let lookup = |secs: f64| index.keyframe_at_or_before(index.secs_to_pts(secs)).map(|pts| index.pts_to_secs(pts));
+    assert!(lookup(1.5).is_some_and(|k| (k - 1.0).abs() < 0.02));
+    assert!(lookup(2.9).is_some_and(|k| (k - 2.0).abs() < 0.02));
+    let first = index.keyframes[0];
+    assert_eq!(index.keyframe_at_or_before(first), Some(first));
+    assert_eq!(index.keyframe_at_or_before(first - 1), None);
+}
+
+#[test]
+fn test_load_or_build_caches_on_disk_and_rebuilds_when_file_changes() {
+    let dir = fixtures::fixture_dir();
+    let cache = tempfile::tempdir().unwrap();
+    let path = fixtures::generate_test_video_with_gop(dir.path(), "gop_cached", 2.0, 30);
+
+    let index = SeekIndex::load_or_build(&path, Some(cache.path())).unwrap();
+    assert_eq!(index.keyframes.len(), 2);
+    assert_eq!(std::fs::read_dir(cache.path()).unwrap().count(), 1, "index written to the cache");
+    let again = SeekIndex::load_or_build(&path, Some(cache.path())).unwrap();
+    assert_eq!(again, index);
+
+    // Replacing the file invalidates both the in-memory and on-disk entries.
+    fixtures::generate_test_video_with_gop(dir.path(), "gop_cached", 4.0, 30);
+    let rebuilt = SeekIndex::load_or_build(&path, Some(cache.path())).unwrap();
+    assert_eq!(rebuilt.keyframes.len(), 4);
+}
+
+#[test]
+fn test_seek_lands_on_keyframe_of_target_gop() {
+    let dir = fixtures::fixture_dir();
+    let path = fixtures::generate_test_video_with_gop(dir.path(), "gop_seek", 4.0, 60);
+
+    SeekIndex::load_or_build(&path, None).unwrap();
+    let mut decoder = FfmpegDecoder::open(&path).unwrap();
+    assert!(decoder.seek_index().is_some());
+    decoder.seek_to(3.5).unwrap();
+
+    let first = decoder.decode_next_frame().unwrap().unwrap();
+    assert!((first.pts_secs - 2.0).abs() < 0.02, "first frame after seek: {}", first.pts_secs);
+
+    let mut pts = first.pts_secs;
+    while pts < 3.5 - 0.001 {
+        pts = decoder.decode_next_frame().unwrap().unwrap().pts_secs;
+    }
+    assert!((pts - 3.5).abs() < 1.0 / 30.0 + 0.001, "landed at {pts}");
+}
+
+#[test]
+fn test_seek_ahead_within_current_gop_keeps_decoding_forward() {
+    let dir = fixtures::fixture_dir();
+    let path = fixtures::generate_test_video_with_gop(dir.path(), "gop_forward", 3.0, 90);
+
+    SeekIndex::load_or_build(&path, None).unwrap();
+    let mut decoder = FfmpegDecoder::open(&path).unwrap();
+    let mut pts = 0.0;
+    while pts < 1.0 {
+        pts = decoder.decode_next_frame().unwrap().unwrap().pts_secs;
+    }
+
+    // Same GOP, ahead of the last frame: no rewind to the keyframe at 0.
+    decoder.seek_to(2.0).unwrap();
+    let next = decoder.decode_next_frame().unwrap().unwrap();
+    assert!(next.pts_secs > pts, "expected to continue after {pts}, got {}", next.pts_secs);
+
+    // Backwards within the GOP must seek back to its keyframe.
+    decoder.seek_to(0.5).unwrap();
+    let rewound = decoder.decode_next_frame().unwrap().unwrap();
+    assert!(rewound.pts_secs < 0.02, "expected the keyframe at 0, got {}", rewound.pts_secs);
+}
+
+#[test]
+fn test_open_without_a_built_index_seeks_through_the_container() {
+    let dir = fixtures::fixture_dir();
+    let path = fixtures::generate_test_video_with_gop(dir.path(), "gop_unindexed", 3.0, 30);
+
+    // Nothing has built an index for this file, and opening must not either
+    let mut decoder = FfmpegDecoder::open(&path).unwrap();
+    assert!(decoder.seek_index().is_none());
+    assert!(SeekIndex::loaded(&path).is_none());
+
+    decoder.seek_to(2.5).unwrap();
+    let frame = decoder.decode_next_frame().unwrap().unwrap();
+    assert!(frame.pts_secs <= 2.5 + 0.001, "seek overshot to {}", frame.pts_secs);
+}
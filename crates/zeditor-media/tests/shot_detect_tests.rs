@@ -0,0 +1,53 @@
+use zeditor_media::shot_detect::{detect_shots, frame_difference, pick_cuts, DEFAULT_CUT_THRESHOLD};
+use zeditor_test_harness::fixtures;
+
+fn solid(rgb: [u8; 3], pixels: usize) -> Vec<u8> {
+    rgb.repeat(pixels)
+}
+
+#[test]
+fn test_frame_difference_range() {
+    let red = solid([255, 0, 0], 100);
+    let blue = solid([0, 0, 255], 100);
+    assert_eq!(frame_difference(&red, &red), 0.0);
+    assert!(frame_difference(&red, &blue) > DEFAULT_CUT_THRESHOLD);
+    assert!(frame_difference(&red, &blue) <= 1.0);
+
+    // A small brightness change (lighting, noise) stays well below a cut
+    let dimmer = solid([245, 0, 0], 100);
+    assert!(frame_difference(&red, &dimmer) < 0.1);
+
+    // Mismatched sizes can't be compared
+    assert_eq!(frame_difference(&red, &blue[..30]), 0.0);
+}
+
+#[test]
+fn test_pick_cuts_applies_threshold_and_min_shot_length() {
+    let scores = [
+        (0.2, 0.9),  // too close to the start
+        (1.0, 0.8),  // cut
+        (1.2, 0.9),  // too close to the previous cut
+        (2.0, 0.1),  // below threshold
+        (3.0, 0.5),  // cut
+    ];
+    assert_eq!(pick_cuts(&scores, 0.35, 0.5), vec![1.0, 3.0]);
+    assert!(pick_cuts(&scores, 0.95, 0.5).is_empty());
+}
+
+#[test]
+fn test_detect_shots_finds_hard_cuts() {
+    let dir = fixtures::fixture_dir();
+    let path = fixtures::generate_test_video_with_shots(dir.path(), "shots", &[1.0, 1.5, 1.0]);
+
+    let cuts = detect_shots(&path, DEFAULT_CUT_THRESHOLD).unwrap();
+    assert_eq!(cuts.len(), 2, "cuts: {cuts:?}");
+    assert!((cuts[0] - 1.0).abs() < 0.05, "cuts: {cuts:?}");
+    assert!((cuts[1] - 2.5).abs() < 0.05, "cuts: {cuts:?}");
+}
+
+#[test]
+fn test_detect_shots_on_single_shot() {
+    let dir = fixtures::fixture_dir();
+    let path = fixtures::generate_test_video(dir.path(), "one_shot", 2.0);
+    assert!(detect_shots(&path, DEFAULT_CUT_THRESHOLD).unwrap().is_empty());
+}
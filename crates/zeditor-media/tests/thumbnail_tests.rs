@@ -29,3 +29,24 @@ fn test_generate_thumbnail_at_timestamp() {
     // Verify we got a valid frame (the exact PTS depends on keyframe placement).
     assert!(frame.data.len() > 0, "frame should have pixel data");
 }
+
+#[test]
+fn test_frame_rgba_scaled_at_lands_on_exact_frame() {
+    let dir = fixtures::fixture_dir();
+    // One keyframe per second, so 1.5s is mid-GOP
+    let path = fixtures::generate_test_video_with_gop(dir.path(), "trim_frame_test", 3.0, 30);
+
+    let frame = thumbnail::frame_rgba_scaled_at(&path, 1.5, 160, 160).unwrap();
+    assert_eq!(frame.width, 160);
+    assert_eq!(frame.height, 120);
+    assert_eq!(frame.data.len(), (160 * 120 * 4) as usize);
+    assert!(
+        frame.pts_secs <= 1.5 + 1e-3 && frame.pts_secs > 1.5 - 1.0 / 30.0,
+        "frame pts: {}",
+        frame.pts_secs
+    );
+
+    // Between frames, the earlier one is on screen
+    let frame = thumbnail::frame_rgba_scaled_at(&path, 0.05, 160, 160).unwrap();
+    assert!((frame.pts_secs - 1.0 / 30.0).abs() < 1e-3, "frame pts: {}", frame.pts_secs);
+}
@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+use zeditor_media::transcribe::{decode_speech_audio, resample, words_from_tokens, SpeechToken, WHISPER_SAMPLE_RATE};
+use zeditor_test_harness::fixtures;
+
+fn token(text: &str, start_secs: f64, end_secs: f64) -> SpeechToken {
+    SpeechToken { text: text.into(), start_secs, end_secs }
+}
+
+#[test]
+fn test_words_from_tokens_joins_pieces_and_drops_specials() {
+    let tokens = [
+        token("[_BEG_]", 0.0, 0.0),
+        token(" Hel", 0.5, 0.7),
+        token("lo", 0.7, 0.9),
+        token(",", 0.9, 0.9),
+        token(" world", 1.0, 1.4),
+        token(".", 1.4, 1.5),
+        token("<|endoftext|>", 1.5, 1.5),
+    ];
+    let words = words_from_tokens(&tokens);
+    assert_eq!(words.len(), 2, "{words:?}");
+    assert_eq!(words[0].text, "Hello,");
+    assert_eq!(words[0].start, Duration::from_millis(500));
+    assert_eq!(words[0].end, Duration::from_millis(900));
+    assert_eq!(words[1].text, "world.");
+    assert_eq!(words[1].end, Duration::from_millis(1500));
+}
+
+#[test]
+fn test_resample_to_whisper_rate() {
+    assert_eq!(resample(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0], 48_000, 16_000), [0.0, 3.0]);
+    assert_eq!(resample(&[0.0, 1.0], 8_000, 16_000), [0.0, 0.5, 1.0, 1.0]);
+    assert_eq!(resample(&[0.25], 16_000, 16_000), [0.25]);
+}
+
+#[test]
+fn test_decode_speech_audio_is_16k_mono() {
+    let dir = fixtures::fixture_dir();
+    let path = fixtures::generate_test_audio_with_beeps(dir.path(), "speech", 2.0, &[0.5]);
+
+    let audio = decode_speech_audio(&path).unwrap();
+    let expected = 2 * WHISPER_SAMPLE_RATE as usize;
+    assert!(audio.len().abs_diff(expected) < 200, "{} samples", audio.len());
+    // The beep at 0.5s survives the resample
+    let beep = &audio[WHISPER_SAMPLE_RATE as usize / 2 + 100..WHISPER_SAMPLE_RATE as usize / 2 + 400];
+    assert!(beep.iter().any(|s| s.abs() > 0.5));
+    assert!(audio[..1000].iter().all(|s| s.abs() < 0.01));
+}
+
+#[cfg(not(feature = "whisper"))]
+#[test]
+fn test_transcribe_without_engine_reports_unavailable() {
+    let dir = fixtures::fixture_dir();
+    let path = fixtures::generate_test_audio_with_beeps(dir.path(), "speech", 1.0, &[0.5]);
+    let err = zeditor_media::transcribe::transcribe(&path, std::path::Path::new("/nonexistent.bin")).unwrap_err();
+    assert!(matches!(err, zeditor_media::error::MediaError::TranscriptionUnavailable));
+}
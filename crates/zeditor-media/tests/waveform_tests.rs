@@ -0,0 +1,39 @@
+use zeditor_media::waveform::{quiet_cut_near, waveform_peaks};
+use zeditor_test_harness::fixtures;
+
+#[test]
+fn test_waveform_peaks_follow_beeps() {
+    let dir = fixtures::fixture_dir();
+    let path = fixtures::generate_test_audio_with_beeps(dir.path(), "waveform", 3.0, &[0.5, 2.0]);
+
+    // 10 buckets of 0.3s each
+    let peaks = waveform_peaks(&path, 0.0, 3.0, 10).unwrap();
+    assert_eq!(peaks.len(), 10);
+    let loud: Vec<usize> = (0..peaks.len()).filter(|&i| peaks[i] > 0.1).collect();
+    assert!(loud.contains(&1) && loud.contains(&6), "peaks: {peaks:?}");
+    assert!(peaks[0] < 0.1 && peaks[9] < 0.1, "peaks: {peaks:?}");
+
+    // A window starting mid-file only sees the second beep
+    let later = waveform_peaks(&path, 1.5, 1.5, 5).unwrap();
+    assert!(later[1] > 0.1 || later[2] > 0.1, "peaks: {later:?}");
+    assert!(later[4] < 0.1, "peaks: {later:?}");
+
+    // Past the end of the file stays silent
+    let past_end = waveform_peaks(&path, 2.5, 2.0, 4).unwrap();
+    assert_eq!(past_end[3], 0.0);
+}
+
+#[test]
+fn test_quiet_cut_near_moves_to_the_nearest_silence() {
+    let dir = fixtures::fixture_dir();
+    // The first beep sounds from 0.5s to 0.6s
+    let path = fixtures::generate_test_audio_with_beeps(dir.path(), "cut_point", 3.0, &[0.5, 2.0]);
+
+    let cut = quiet_cut_near(&path, 0.57, 3.0).unwrap();
+    assert!((cut - 0.6).abs() < 0.002, "{cut}");
+    let cut = quiet_cut_near(&path, 0.53, 3.0).unwrap();
+    assert!((cut - 0.5).abs() < 0.002, "{cut}");
+
+    // Silence is left alone
+    assert!((quiet_cut_near(&path, 1.25, 3.0).unwrap() - 1.25).abs() < 1e-3);
+}
@@ -152,6 +152,8 @@ impl ProjectBuilder {
         if !self.tracks.is_empty() {
             project.timeline = Timeline {
                 tracks: self.tracks,
+                markers: Vec::new(),
+                guides: Vec::new(),
             };
         }
         project
@@ -72,6 +72,51 @@ pub fn generate_test_video_with_size(
     output_path
 }
 
+/// Generate a test video with a keyframe exactly every `gop` frames, for
+/// testing seeks within long GOPs.
+pub fn generate_test_video_with_gop(
+    output_dir: &Path,
+    name: &str,
+    duration_secs: f64,
+    gop: u32,
+) -> PathBuf {
+    let output_path = output_dir.join(format!("{name}.mp4"));
+    let gop = gop.to_string();
+
+    let status = Command::new("ffmpeg")
+        .args([
+            "-y",
+            "-f",
+            "lavfi",
+            "-i",
+            &format!("testsrc=duration={duration_secs}:size=320x240:rate=30"),
+            "-c:v",
+            "libx264",
+            "-pix_fmt",
+            "yuv420p",
+            "-preset",
+            "ultrafast",
+            "-g",
+            &gop,
+            "-keyint_min",
+            &gop,
+            "-sc_threshold",
+            "0",
+        ])
+        .arg(&output_path)
+        .stderr(std::process::Stdio::null())
+        .status()
+        .expect("ffmpeg must be installed to generate test fixtures");
+
+    assert!(
+        status.success(),
+        "ffmpeg failed to generate test video {name}"
+    );
+    assert!(output_path.exists(), "test video was not created: {name}");
+
+    output_path
+}
+
 /// Generate a test video with audio.
 pub fn generate_test_video_with_audio(
     output_dir: &Path,
@@ -114,6 +159,129 @@ pub fn generate_test_video_with_audio(
     output_path
 }
 
+/// `aevalsrc` source of 100 ms 1 kHz beeps starting at `beeps` (seconds),
+/// silent in between.
+fn beeps_source(duration_secs: f64, beeps: &[f64]) -> String {
+    let gates: Vec<String> = beeps
+        .iter()
+        .map(|b| format!("between(t,{b},{})", b + 0.1))
+        .collect();
+    let gate = if gates.is_empty() { "0".to_string() } else { gates.join("+") };
+    format!("aevalsrc='if({gate},0.8*sin(2*PI*1000*t),0)':s=48000:d={duration_secs}")
+}
+
+/// Generate a WAV file of beeps at the given times, standing in for a
+/// separately recorded audio track.
+pub fn generate_test_audio_with_beeps(
+    output_dir: &Path,
+    name: &str,
+    duration_secs: f64,
+    beeps: &[f64],
+) -> PathBuf {
+    let output_path = output_dir.join(format!("{name}.wav"));
+
+    let status = Command::new("ffmpeg")
+        .args(["-y", "-f", "lavfi", "-i", &beeps_source(duration_secs, beeps)])
+        .arg(&output_path)
+        .stderr(std::process::Stdio::null())
+        .status()
+        .expect("ffmpeg must be installed to generate test fixtures");
+
+    assert!(
+        status.success(),
+        "ffmpeg failed to generate test audio {name}"
+    );
+
+    output_path
+}
+
+/// Generate a test video whose audio is beeps at the given times, standing
+/// in for camera footage with a scratch track.
+pub fn generate_test_video_with_beeps(
+    output_dir: &Path,
+    name: &str,
+    duration_secs: f64,
+    beeps: &[f64],
+) -> PathBuf {
+    let output_path = output_dir.join(format!("{name}.mp4"));
+
+    let status = Command::new("ffmpeg")
+        .args([
+            "-y",
+            "-f",
+            "lavfi",
+            "-i",
+            &format!("testsrc=duration={duration_secs}:size=320x240:rate=30"),
+            "-f",
+            "lavfi",
+            "-i",
+            &beeps_source(duration_secs, beeps),
+            "-c:v",
+            "libx264",
+            "-pix_fmt",
+            "yuv420p",
+            "-preset",
+            "ultrafast",
+            "-c:a",
+            "aac",
+            "-shortest",
+        ])
+        .arg(&output_path)
+        .stderr(std::process::Stdio::null())
+        .status()
+        .expect("ffmpeg must be installed to generate test fixtures");
+
+    assert!(
+        status.success(),
+        "ffmpeg failed to generate test video with beeps {name}"
+    );
+
+    output_path
+}
+
+/// Generate a test video made of solid-colour shots of the given lengths,
+/// with a hard cut between each (colours cycle red, white, blue, black).
+pub fn generate_test_video_with_shots(output_dir: &Path, name: &str, shot_secs: &[f64]) -> PathBuf {
+    const COLORS: [&str; 4] = ["red", "white", "blue", "black"];
+    let output_path = output_dir.join(format!("{name}.mp4"));
+
+    let mut graph = String::new();
+    for (i, secs) in shot_secs.iter().enumerate() {
+        let color = COLORS[i % COLORS.len()];
+        graph.push_str(&format!("color=c={color}:s=320x240:r=30:d={secs}[s{i}];"));
+    }
+    for i in 0..shot_secs.len() {
+        graph.push_str(&format!("[s{i}]"));
+    }
+    graph.push_str(&format!("concat=n={}:v=1:a=0", shot_secs.len()));
+
+    let status = Command::new("ffmpeg")
+        .args([
+            "-y",
+            "-f",
+            "lavfi",
+            "-i",
+            &graph,
+            "-c:v",
+            "libx264",
+            "-pix_fmt",
+            "yuv420p",
+            "-preset",
+            "ultrafast",
+        ])
+        .arg(&output_path)
+        .stderr(std::process::Stdio::null())
+        .status()
+        .expect("ffmpeg must be installed to generate test fixtures");
+
+    assert!(
+        status.success(),
+        "ffmpeg failed to generate test video with shots {name}"
+    );
+
+    output_path
+}
+
 /// Generate a test video with rotation metadata (display matrix).
 /// Creates a base MP4 then remuxes with `-display_rotation` to produce a MOV
 /// that has the rotation set in the stream side data, similar to phone-recorded videos.
@@ -204,6 +372,24 @@ mod tests {
         assert!(metadata.len() > 0, "generated video should not be empty");
     }
 
+    #[test]
+    fn test_generate_test_audio_with_beeps() {
+        let dir = fixture_dir();
+        let path = generate_test_audio_with_beeps(dir.path(), "test_beeps", 1.0, &[0.2, 0.5]);
+        assert!(path.exists());
+        let metadata = std::fs::metadata(&path).unwrap();
+        assert!(metadata.len() > 0, "generated audio should not be empty");
+    }
+
+    #[test]
+    fn test_generate_test_video_with_gop() {
+        let dir = fixture_dir();
+        let path = generate_test_video_with_gop(dir.path(), "test_gop", 1.0, 10);
+        assert!(path.exists());
+        let metadata = std::fs::metadata(&path).unwrap();
+        assert!(metadata.len() > 0, "generated video should not be empty");
+    }
+
     #[test]
     fn test_generate_test_video_rotated() {
         let dir = fixture_dir();
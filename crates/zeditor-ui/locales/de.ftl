@@ -0,0 +1,450 @@
+# German strings. Keys missing here fall back to locales/en.ftl.
+
+## Menus
+menu-file = Datei
+menu-edit = Bearbeiten
+menu-view = Ansicht
+menu-new-project = Neues Projekt
+menu-load-project = Projekt laden
+menu-save = Speichern
+menu-save-as = Speichern unter...
+menu-restore-version = Version wiederherstellen...
+menu-import-folder = Ordner importieren...
+menu-remap-paths = Medienpfade umleiten...
+menu-render = Rendern
+menu-export-stems = Audio-Stems exportieren
+menu-export-description = Beschreibung exportieren...
+menu-exit = Beenden
+menu-undo = Rückgängig
+menu-redo = Wiederholen
+menu-group-clips = Clips gruppieren
+menu-ungroup-clips = Gruppierung aufheben
+menu-sync-clips = Clips synchronisieren
+menu-split-dual-mono = In Dual-Mono aufteilen
+menu-fit-to-fill = In Lücke einpassen
+menu-create-slideshow = Diashow erstellen...
+menu-transcribe-clip = Audio transkribieren
+menu-detect-beats = Beats erkennen
+menu-auto-reframe = Automatisch neu framen
+menu-replace-media = Medium ersetzen...
+menu-relink-media = Medien neu verknüpfen...
+menu-reveal-in-timeline = In Zeitleiste anzeigen
+menu-ripple-edits = Ripple-Bearbeitung
+menu-snap-to-markers = An Markern einrasten
+menu-snap-to-playhead = Am Abspielkopf einrasten
+menu-snap-quiet-audio = Audio-Schnitte an leisen Stellen einrasten
+menu-preferences = Einstellungen...
+menu-ui-scale = UI-Skalierung { $percent } %
+menu-add-video-track-above = Videospur darüber hinzufügen
+menu-add-video-track-below = Videospur darunter hinzufügen
+menu-add-audio-track-above = Audiospur darüber hinzufügen
+menu-add-audio-track-below = Audiospur darunter hinzufügen
+menu-delete-track = Spur löschen
+
+## Dialogs
+dialog-cancel = Abbrechen
+dialog-apply = Übernehmen
+dialog-delete = Löschen
+dialog-replay-journal = Journal wiederherstellen
+dialog-relink = Verknüpfen
+dialog-save = Speichern
+dialog-discard = Verwerfen
+dialog-overwrite = Überschreiben
+dialog-keep-both = Beide behalten
+dialog-asset-in-use = Dieses Medium wird von { $count } Clip(s) in der Zeitleiste verwendet. Medium und alle zugehörigen Clips löschen?
+dialog-recovery-journal = Ein Wiederherstellungsjournal mit { $count } ungespeicherten Aktion(en) aus einer früheren Sitzung wurde gefunden. Wiederherstellen?
+dialog-delete-track = Spur { $name } enthält { $count } Clip(s). Spur und Clips löschen?
+dialog-relink-media = Neuer Pfad für { $name }:
+dialog-unsaved-changes = Dieses Projekt hat ungespeicherte Änderungen. Zuerst speichern?
+dialog-render-output-exists = { $name } existiert bereits. Überschreiben oder in eine neue nummerierte Datei daneben rendern?
+dialog-render-outputs-exist = { $count } dieser Ausgabedateien existieren bereits. Überschreiben oder in neue nummerierte Dateien daneben rendern?
+dialog-restore-version = Gespeicherte Version dieses Projekts wiederherstellen:
+dialog-remap-paths = Pfadpräfix in allen Medienpfaden ersetzen:
+dialog-remap-from = Ersetzen
+dialog-remap-to = Durch
+dialog-remap-summary = { $found } von { $count } passenden Datei(en) unter dem neuen Pfad gefunden
+dialog-create-slideshow = Diashow aus den ausgewählten Medien erstellen:
+dialog-slideshow-summary = { $count } Medium/Medien, angehängt an die erste Videospur
+dialog-slideshow-clip-secs = Sekunden pro Clip
+dialog-slideshow-shuffle = Zufällige Reihenfolge
+dialog-create = Erstellen
+
+## Preferences dialog
+prefs-title = Einstellungen
+prefs-canvas-width = Breite neuer Projekte (px)
+prefs-canvas-height = Höhe neuer Projekte (px)
+prefs-fps = Bildrate neuer Projekte
+prefs-autosave-minutes = Automatisch speichern alle (Minuten, 0 = aus)
+prefs-snap-threshold = Einrastabstand (ms)
+prefs-still-duration = Dauer von Standbildern (s)
+prefs-render-segments = Parallele Exportsegmente
+prefs-cache-dir = Cache-Verzeichnis (leer = Standard)
+prefs-display-lut = Anzeige-LUT für die Vorschau (.cube, leer = keine)
+prefs-preview-quality = Vorschauqualität
+prefs-canvas-preset = Bildformat
+prefs-preview-draft = Entwurf
+prefs-preview-full = Voll
+
+## Timeline toolbar
+timeline-insert-time = Sekunden einfügen
+timeline-fit = Einpassen
+timeline-find = In Timeline suchen...
+timeline-match-position = { $current } von { $count }
+timeline-matches-found = { $count } gefunden
+timeline-guides-every = Hilfslinien alle
+timeline-guide-frames = Frames
+timeline-guide-seconds = s
+timeline-add-guides = Hinzufügen
+timeline-clear-guides = Entfernen
+
+## Left panel
+tab-library = Projektbibliothek
+tab-effects = Effekte
+tab-health = Zustand
+tab-transcript = Transkript
+tab-snapshots = Schnappschüsse
+tab-exports = Exporte
+tab-stats = Statistik
+
+## Project library
+library-import = Importieren
+library-search = Medien suchen...
+library-filter-video = Video
+library-filter-audio = Audio
+library-filter-image = Bild
+library-filter-used = Verwendet
+library-filter-unused = Unbenutzt
+library-clear-filters = Zurücksetzen
+library-view-list = Liste
+library-view-grid = Raster
+library-sort-date = Datum
+library-sort-name = Name
+library-sort-length = Länge
+library-sort-resolution = Aufl.
+library-no-matches = Keine passenden Medien
+library-column-name = Name
+library-column-duration = Dauer
+library-column-size = Größe
+library-column-codec = Codec
+library-column-uses = Verw.
+library-info = Info { $arrow }
+library-loading = Wird geladen...
+library-shots = Szenen ({ $count })
+library-shot = Szene { $number }  { $start } - { $end }
+library-importing-folder = „{ $name }“ wird importiert
+library-import-progress = { $done } / { $total } Dateien
+info-container = Container
+info-video = Video
+info-resolution = Auflösung
+info-frame-rate = Bildrate
+info-bit-depth = Bittiefe
+info-pixel-format = Pixelformat
+info-audio = Audio
+info-channels = Kanäle
+info-bit-rate = Bitrate
+info-file-size = Dateigröße
+info-created = Erstellt
+
+## Effects browser
+effects-search = Effekte suchen...
+effects-add-to-clip = Zum Clip hinzufügen
+effects-none-available = Keine Effekte verfügbar
+effects-no-matches = Keine passenden Effekte
+
+## Timeline health
+health-no-issues = Keine Probleme gefunden
+health-gap = Lücke von { $duration }
+health-overlap = Überlappung von { $duration }
+health-source-past-end = Quelle { $duration } über das Medienende hinaus
+health-missing-asset = Medium fehlt in der Bibliothek
+health-missing-file = Datei fehlt: { $name }
+
+## Statistics
+stats-timeline-duration = Timeline-Dauer
+stats-source-used = Verwendetes Quellmaterial
+stats-source-imported = Importiertes Quellmaterial
+stats-export-size = Geschätzte Exportgröße
+stats-clips = Clips
+stats-total = Gesamt
+stats-effects = Effekte
+stats-no-effects = Keine Effekte angewendet
+
+## Snapshots
+snapshots-name = Name des Schnappschusses
+snapshots-take = Schnappschuss erstellen
+snapshots-empty = Keine Schnappschüsse. Einen erstellen, um diesen Schnitt beim Experimentieren zu behalten.
+snapshots-clip-count = { $count } Clips
+snapshots-compare = Vergleichen
+snapshots-hide = Ausblenden
+snapshots-restore = Wiederherstellen
+snapshots-delete = Löschen
+snapshots-no-changes = Keine Änderungen seit diesem Schnappschuss
+snapshots-added = Hinzugefügt
+snapshots-removed = Entfernt
+snapshots-moved = verschoben
+snapshots-trimmed = getrimmt
+snapshots-effects-changed = Effekte geändert
+
+## Render ranges
+ranges-flag-in = In markieren
+ranges-flag-out = Out markieren
+ranges-name = Bereichsname
+ranges-add = Bereich hinzufügen
+ranges-export-all = Alle exportieren
+ranges-pattern-help = Dateinamen: {project}, {name} und {index} werden je Bereich ersetzt
+ranges-empty = Keine Renderbereiche. Einen In- und Out-Punkt am Abspielkopf markieren, um einen hinzuzufügen.
+ranges-loop = Schleife
+ranges-delete = Löschen
+
+## Transcript
+transcript-empty = Keine transkribierten Clips. Einen Clip auswählen und Bearbeiten > Audio transkribieren verwenden.
+transcript-cut = Ausgewählte Wörter schneiden
+transcript-export-srt = SRT exportieren...
+
+## Clip inspector
+inspector-no-clip = Kein Clip ausgewählt
+inspector-clip-not-found = Clip nicht gefunden
+inspector-title = Clip-Effekte
+inspector-speed = Geschwindigkeit: { $percent }%
+inspector-keep-pitch = Tonhöhe halten
+inspector-channels = Kanäle: { $map }
+inspector-fit = Einpassen: { $fit }
+inspector-reframed = Neu gerahmt: { $count } Schwenkpunkte
+inspector-clear-reframe = Entfernen
+inspector-trim-source = Quelle: { $start }s - { $end }s (zum Trimmen die Griffe ziehen)
+inspector-show-waveform = Wellenform anzeigen
+inspector-markers = Marker
+inspector-add-marker = Am Abspielkopf hinzufügen
+inspector-marker-name = Markername
+inspector-no-effects = Keine Effekte
+inspector-effects-bypassed = Effekte umgangen
+inspector-effects-on = Effekte aktiv
+inspector-remove-effect = Entfernen
+inspector-reset-parameter = Zurücksetzen
+inspector-parameter-mixed = { $label }: gemischt
+
+## Source monitor
+source-title = Quelle: { $name }
+source-close = Schließen
+source-loading = Wird geladen...
+source-mark-in = In setzen
+source-mark-out = Out setzen
+source-clear-marks = Löschen
+source-marks = In { $in } | Out { $out }
+source-subclip-name = Name des Subclips
+source-save-subclip = Subclip speichern
+
+## Preview
+playback-play = Wiedergabe
+playback-pause = Pause
+preview-no-video = Kein Video
+preview-outgoing = Ausgehend
+preview-incoming = Eingehend
+preview-loop-on = Schleife: An
+preview-loop-off = Schleife: Aus
+preview-safe-areas = Sicher
+preview-thirds-grid = Raster
+preview-center-cross = Mitte
+preview-canvas = Leinwand: { $canvas }
+preview-background = Hgr.: { $background }
+preview-background-checker = Schachbrett
+preview-background-black = Schwarz
+preview-background-gray = Grau
+preview-background-white = Weiß
+preview-stats = Statistik
+preview-memory = Speicher
+preview-play-around = Umgebung abspielen
+preview-preroll = Vor { $secs }s
+preview-postroll = Nach { $secs }s
+preview-prerender = Vorrendern
+preview-background-render = Hintergrund-Rendern
+
+## Notifications panel
+notifications-clear = Leeren
+notifications-close = Schließen
+notifications-empty = Keine Benachrichtigungen
+notifications-info = Info
+notifications-success = Fertig
+notifications-warning = Warnung
+notifications-error = Fehler
+
+## Status bar
+status-none = Keine Systemmeldungen
+status-notifications = Benachrichtigungen
+status-notifications-count = Benachrichtigungen ({ $count })
+status-rendering-progress = Rendern: { $current }/{ $total } Frames ({ $percent } %) | Vergangen: { $elapsed }
+status-playing = Wiedergabe
+status-stopped = Gestoppt
+
+## Status messages
+status-opening-file-dialog = Dateidialog wird geöffnet...
+status-import-cancelled = Import abgebrochen
+status-importing = Importiere...
+status-imported = Importiert: { $name }
+status-import-failed = Import fehlgeschlagen: { $error }
+status-folder-empty = Keine unterstützten Medien in { $name }
+status-folder-scan-failed = Ordner konnte nicht gelesen werden: { $error }
+status-folder-imported = { $count } Dateien in { $name } importiert ({ $skipped } bereits vorhanden, { $failed } fehlgeschlagen)
+status-already-imported = Bereits importiert: { $name }
+status-replace-needs-asset = Ein Medium in der Bibliothek zum Ersetzen auswählen
+status-replace-cancelled = Ersetzen abgebrochen
+status-replace-failed = Ersetzen fehlgeschlagen: { $error }
+status-media-replaced = Medium von { $name } ersetzt
+status-reveal-needs-asset = Ein Medium in der Bibliothek zum Anzeigen auswählen
+status-asset-unused = Das Medium wird in der Zeitleiste nicht verwendet
+status-asset-usage = Verwendung { $index } von { $count }
+status-shot-detection-failed = Szenenerkennung fehlgeschlagen: { $error }
+status-removed = Entfernt: { $name }
+status-remove-failed = Entfernen fehlgeschlagen: { $error }
+status-journal-recovered = { $count } Aktion(en) aus dem Journal wiederhergestellt
+status-journal-replay-failed = Wiederherstellung aus dem Journal fehlgeschlagen: { $error }
+status-journal-discarded = Wiederherstellungsjournal verworfen
+status-clip-removed = Clip entfernt
+status-remove-clip-failed = Clip entfernen fehlgeschlagen: { $error }
+status-drop-effect-on-clip = Effekte auf einen Clip ziehen
+status-asset-not-found = Medium nicht gefunden
+status-clips-added = { $count } Clips hinzugefügt
+status-slideshow-created = Diashow mit { $count } Clips erstellt
+status-canvas-changed = Leinwand auf { $width }×{ $height } gesetzt
+status-slideshow-no-assets = Wähle Medien in der Bibliothek aus, um eine Diashow zu erstellen
+status-slideshow-invalid-duration = Gib eine Cliplänge zwischen 0 und 3600 Sekunden ein
+status-no-video-track = Die Zeitleiste hat keine Videospur
+status-add-clips-failed = Clips hinzufügen fehlgeschlagen: { $error }
+status-clip-added = Clip hinzugefügt
+status-add-clip-failed = Clip hinzufügen fehlgeschlagen: { $error }
+status-move-failed = Verschieben fehlgeschlagen: { $error }
+status-clip-cut = Clip geschnitten
+status-clips-grouped = { $count } Clips gruppiert
+status-clips-ungrouped = Gruppierung von { $count } Clips aufgehoben
+status-group-failed = Gruppieren fehlgeschlagen: { $error }
+status-sync-needs-two-clips = Zwei Clips zum Synchronisieren auswählen
+status-sync-started = Audio wird analysiert, um Clips zu synchronisieren...
+status-clips-synced = Clips synchronisiert (Versatz { $offset } s)
+status-sync-failed = Synchronisieren fehlgeschlagen: { $error }
+status-dual-mono-needs-clip = Einen Clip auswählen, um seinen Ton aufzuteilen
+status-dual-mono-done = Ton aufgeteilt: linker Kanal bleibt, rechter Kanal auf { $track }
+status-dual-mono-failed = Aufteilen in Dual-Mono nicht möglich: { $error }
+status-transcribe-needs-clip = Einen Clip mit Audio zum Transkribieren auswählen
+status-transcription-started = { $name } wird transkribiert...
+status-transcription-done = Transkription abgeschlossen ({ $count } Wörter)
+status-transcription-failed = Transkription fehlgeschlagen: { $error }
+status-beats-need-clip = Clip mit Audio auswählen, um Beats zu erkennen
+status-beats-started = Beats werden erkannt...
+status-beats-none = Kein gleichmäßiger Beat gefunden
+status-beats-done = { $count } Beat-Hilfslinien hinzugefügt ({ $bpm } BPM)
+status-beats-failed = Beat-Erkennung fehlgeschlagen: { $error }
+status-reframe-need-clip = Videoclip auswählen, um ihn neu zu framen
+status-reframe-fits = Der Clip passt bereits in die Leinwandbreite
+status-reframe-started = Clip wird für das Neu-Framen analysiert...
+status-reframe-done = Clip mit { $count } Schwenk-Keys neu gerahmt
+status-reframe-failed = Automatisches Neu-Framen fehlgeschlagen: { $error }
+status-words-cut = Ausgewählte Wörter geschnitten
+status-cut-words-failed = Wörter können nicht geschnitten werden: { $error }
+status-srt-exported = Untertitel nach { $path } exportiert
+status-srt-export-failed = Untertitel-Export fehlgeschlagen: { $error }
+status-invalid-timecode = Kein gültiger Timecode: { $input }
+status-marker-added = { $name } hinzugefügt
+status-clip-marker-needs-clip = Zum Markieren einen Clip auswählen
+status-clip-marker-failed = Clip-Marker fehlgeschlagen: { $error }
+status-guide-added = Hilfslinie hinzugefügt
+status-guide-interval-invalid = Hilfslinien-Abstand muss eine positive Zahl sein
+status-guides-need-clips = Erst Clips zur Timeline hinzufügen, dann Hilfslinien erzeugen
+status-guides-generated = { $count } Hilfslinien hinzugefügt
+status-insert-time-invalid = Einzufügende Zeit muss eine positive Sekundenzahl sein
+status-time-inserted = { $secs } s am Abspielkopf eingefügt
+status-insert-time-failed = Zeit konnte nicht eingefügt werden: { $error }
+status-description-exported = Beschreibung nach { $path } exportiert
+status-description-export-failed = Beschreibung-Export fehlgeschlagen: { $error }
+status-timeline-search-no-match = Keine passenden Clips gefunden
+status-fit-to-fill-needs-source = Einen Clip im Quellmonitor laden, um ihn einzupassen
+status-fit-to-fill-no-gap = Keine Lücke unter dem Abspielkopf
+status-fit-to-fill-done = Lücke mit { $speed } % Geschwindigkeit gefüllt
+status-fit-to-fill-failed = Einpassen nicht möglich: { $error }
+status-cut-failed = Schneiden fehlgeschlagen: { $error }
+status-resize-failed = Größe ändern fehlgeschlagen: { $error }
+status-ripple-edits-on = Ripple-Bearbeitung an
+status-ripple-edits-off = Ripple-Bearbeitung aus
+status-export-stems-on = Renders schreiben zusätzlich eine WAV pro Audiospur
+status-export-stems-off = Renders schreiben nur die Hauptmischung
+status-snap-target-on = { $target }: an
+status-snap-target-off = { $target }: aus
+status-speed-failed = Geschwindigkeit ändern fehlgeschlagen: { $error }
+status-channel-map-failed = Kanalzuordnung ändern fehlgeschlagen: { $error }
+status-preserve-pitch-failed = Tonhöhenerhalt ändern fehlgeschlagen: { $error }
+status-rate-conform-failed = Bildratenanpassung ändern fehlgeschlagen: { $error }
+status-canvas-fit-failed = Bildeinpassung ändern fehlgeschlagen: { $error }
+status-volume-envelope-failed = Lautstärkekurve ändern fehlgeschlagen: { $error }
+status-waveform-needs-audio = Clip mit Audio auswählen, um die Wellenform anzuzeigen
+status-waveform-failed = Wellenform laden fehlgeschlagen: { $error }
+status-source-trim-failed = Trimmen fehlgeschlagen: { $error }
+status-nothing-to-fit = Nichts einzupassen: die Zeitleiste ist leer
+status-ui-scale = UI-Skalierung { $percent } %
+status-language = Sprache: { $language }
+status-loop-on = Schleifenwiedergabe an
+status-loop-off = Schleifenwiedergabe aus
+status-source-monitor = Quellmonitor: { $name }
+status-subclip-saved = Teilclip gespeichert: { $name }
+status-subclip-failed = Teilclip konnte nicht gespeichert werden: { $error }
+status-undone = Rückgängig gemacht
+status-snapshot-taken = Schnappschuss gespeichert: { $name }
+status-snapshot-restored = Schnappschuss wiederhergestellt: { $name }
+status-snapshot-restore-failed = Schnappschuss wiederherstellen fehlgeschlagen: { $error }
+status-snapshot-deleted = Schnappschuss gelöscht: { $name }
+status-undo-failed = Rückgängig fehlgeschlagen: { $error }
+status-redone = Wiederholt
+status-redo-failed = Wiederholen fehlgeschlagen: { $error }
+status-saved = Gespeichert unter { $path }
+status-save-failed = Speichern fehlgeschlagen: { $error }
+status-saving = Speichere unter { $path }...
+status-save-in-progress = Es wird bereits gespeichert
+status-save-indicator = Speichert...
+status-opening-save-dialog = Speicherdialog wird geöffnet...
+status-save-cancelled = Speichern abgebrochen
+status-loaded = { $path } geladen
+status-load-failed = Laden fehlgeschlagen: { $error }
+status-load-cancelled = Laden abgebrochen
+status-versions-need-save = Projekt zuerst speichern; Versionen werden bei jedem Speichern angelegt
+status-no-versions = Noch keine gespeicherten Versionen
+status-version-restored = Version { $version } wiederhergestellt; speichern, um sie zu behalten
+status-paths-remapped = { $count } Medienpfad(e) umgeleitet
+status-remap-nothing-found = Unter dem neuen Pfad existiert keine passende Datei
+status-preferences-saved = Einstellungen gespeichert
+status-preference-invalid = Ungültiger Wert für { $field }
+status-thumbnails = Vorschaubilder { $done }/{ $total }
+status-new-project = Neues Projekt erstellt
+status-rendering = Rendere...
+status-render-cancelled = Rendern abgebrochen
+status-render-output-unusable = Rendern nach { $path } nicht möglich: { $error }
+status-rendered = Gerendert nach { $path } | Gesamtzeit: { $elapsed }
+status-render-failed = Rendern fehlgeschlagen: { $error }
+status-render-range-needs-flags = Zuerst einen In- und einen Out-Punkt markieren
+status-render-range-invalid = Der Out-Punkt muss hinter dem In-Punkt liegen
+status-render-range-added = Renderbereich { $name } hinzugefügt
+status-render-range-deleted = Renderbereich { $name } gelöscht
+status-no-render-ranges = Keine Renderbereiche zum Exportieren
+status-render-ranges-queued = { $count } Renderbereiche zum Export eingereiht
+status-opening-load-dialog = Ladedialog wird geöffnet...
+status-opening-render-dialog = Renderdialog wird geöffnet...
+status-track-update-failed = Spur aktualisieren fehlgeschlagen: { $error }
+status-video-track-added = Videospur hinzugefügt
+status-audio-track-added = Audiospur hinzugefügt
+status-add-track-failed = Spur hinzufügen fehlgeschlagen: { $error }
+status-track-deleted = Spur gelöscht
+status-delete-track-failed = Spur löschen fehlgeschlagen: { $error }
+status-effect-added = Effekt hinzugefügt
+status-effect-added-to-clips = Effekt zu { $count } Clips hinzugefügt
+status-effect-needs-video-clips = Wähle Clips auf einer Videospur, um einen Effekt hinzuzufügen
+status-add-effect-failed = Effekt hinzufügen fehlgeschlagen: { $error }
+status-effect-removed = Effekt entfernt
+status-remove-effect-failed = Effekt entfernen fehlgeschlagen: { $error }
+status-update-effect-failed = Effekt aktualisieren fehlgeschlagen: { $error }
+status-effects-bypassed = Effekte in der Vorschau umgangen (\ zum Vergleichen)
+status-effects-restored = Effekte in der Vorschau aktiv
+status-shuttle = Shuttle { $rate }x
+
+## Notifications
+notify-preferences-save-failed = Einstellungen konnten nicht gespeichert werden: { $error }
+notify-decode-failed = Dekodieren fehlgeschlagen: { $error }
+notify-display-lut-failed = Anzeige-LUT { $path } konnte nicht geladen werden: { $error }
+notify-replace-fps-changed = Die neue Datei für { $name } hat eine andere Bildrate; Schnittpunkte prüfen
+notify-replace-clips-past-end = { $count } Clip(s) reichen jetzt über das Ende der neuen Datei hinaus
@@ -0,0 +1,451 @@
+# English (source) strings. Other locales fall back to these for missing keys.
+# Syntax: `key = value`, with `{ $name }` placeables filled in by the caller.
+
+## Menus
+menu-file = File
+menu-edit = Edit
+menu-view = View
+menu-new-project = New Project
+menu-load-project = Load Project
+menu-save = Save
+menu-save-as = Save As...
+menu-restore-version = Restore Version...
+menu-import-folder = Import Folder...
+menu-remap-paths = Remap Media Paths...
+menu-render = Render
+menu-export-stems = Export Audio Stems
+menu-export-description = Export Description...
+menu-exit = Exit
+menu-undo = Undo
+menu-redo = Redo
+menu-group-clips = Group Clips
+menu-ungroup-clips = Ungroup Clips
+menu-sync-clips = Synchronize Clips
+menu-split-dual-mono = Split to Dual Mono
+menu-fit-to-fill = Fit to Fill
+menu-create-slideshow = Create Slideshow...
+menu-transcribe-clip = Transcribe Audio
+menu-detect-beats = Detect Beats
+menu-auto-reframe = Auto Reframe
+menu-replace-media = Replace Media...
+menu-relink-media = Relink Media...
+menu-reveal-in-timeline = Reveal in Timeline
+menu-ripple-edits = Ripple Edits
+menu-snap-to-markers = Snap to Markers
+menu-snap-to-playhead = Snap to Playhead
+menu-snap-quiet-audio = Snap Audio Trims to Quiet Points
+menu-preferences = Preferences...
+menu-ui-scale = UI Scale { $percent }%
+menu-add-video-track-above = Add Video Track Above
+menu-add-video-track-below = Add Video Track Below
+menu-add-audio-track-above = Add Audio Track Above
+menu-add-audio-track-below = Add Audio Track Below
+menu-delete-track = Delete Track
+
+## Dialogs
+dialog-cancel = Cancel
+dialog-apply = Apply
+dialog-delete = Delete
+dialog-replay-journal = Replay journal
+dialog-relink = Relink
+dialog-save = Save
+dialog-discard = Discard
+dialog-overwrite = Overwrite
+dialog-keep-both = Keep both
+dialog-asset-in-use = This asset is used by { $count } clip(s) in the timeline. Delete the asset and all its clips?
+dialog-recovery-journal = Found a recovery journal with { $count } unsaved operation(s) from a previous session. Replay it?
+dialog-delete-track = Track { $name } holds { $count } clip(s). Delete the track and its clips?
+dialog-relink-media = New path for { $name }:
+dialog-unsaved-changes = This project has unsaved changes. Save them first?
+dialog-render-output-exists = { $name } already exists. Overwrite it, or render to a new numbered file next to it?
+dialog-render-outputs-exist = { $count } of these outputs already exist. Overwrite them, or render to new numbered files next to them?
+dialog-restore-version = Restore a saved version of this project:
+dialog-remap-paths = Replace a path prefix in all media paths:
+dialog-remap-from = Replace
+dialog-remap-to = With
+dialog-remap-summary = { $found } of { $count } matching file(s) found at the new path
+dialog-create-slideshow = Create a slideshow from the selected assets:
+dialog-slideshow-summary = { $count } asset(s), appended to the first video track
+dialog-slideshow-clip-secs = Seconds per clip
+dialog-slideshow-shuffle = Shuffle order
+dialog-create = Create
+
+## Preferences dialog
+prefs-title = Preferences
+prefs-canvas-width = New project width (px)
+prefs-canvas-height = New project height (px)
+prefs-fps = New project frame rate
+prefs-autosave-minutes = Autosave every (minutes, 0 = off)
+prefs-snap-threshold = Snap distance (ms)
+prefs-still-duration = Still image duration (s)
+prefs-render-segments = Parallel export segments
+prefs-cache-dir = Cache directory (empty = default)
+prefs-display-lut = Preview display LUT (.cube, empty = none)
+prefs-preview-quality = Preview quality
+prefs-canvas-preset = Canvas shape
+prefs-preview-draft = Draft
+prefs-preview-full = Full
+
+## Timeline toolbar
+timeline-insert-time = Insert seconds
+timeline-fit = Fit
+timeline-find = Find in timeline...
+timeline-match-position = { $current } of { $count }
+timeline-matches-found = { $count } found
+timeline-guides-every = Guides every
+timeline-guide-frames = frames
+timeline-guide-seconds = sec
+timeline-add-guides = Add
+timeline-clear-guides = Clear
+
+## Left panel
+tab-library = Project Library
+tab-effects = Effects
+tab-health = Health
+tab-transcript = Transcript
+tab-snapshots = Snapshots
+tab-exports = Exports
+tab-stats = Stats
+
+## Project library
+library-import = Import
+library-search = Search media...
+library-filter-video = Video
+library-filter-audio = Audio
+library-filter-image = Image
+library-filter-used = Used
+library-filter-unused = Unused
+library-clear-filters = Clear
+library-view-list = List
+library-view-grid = Grid
+library-sort-date = Date
+library-sort-name = Name
+library-sort-length = Length
+library-sort-resolution = Res
+library-no-matches = No matching media
+library-column-name = Name
+library-column-duration = Duration
+library-column-size = Size
+library-column-codec = Codec
+library-column-uses = Uses
+library-info = Info { $arrow }
+library-loading = Loading...
+library-shots = Shots ({ $count })
+library-shot = Shot { $number }  { $start } - { $end }
+library-importing-folder = Importing "{ $name }"
+library-import-progress = { $done } / { $total } files
+info-container = Container
+info-video = Video
+info-resolution = Resolution
+info-frame-rate = Frame rate
+info-bit-depth = Bit depth
+info-pixel-format = Pixel format
+info-audio = Audio
+info-channels = Channels
+info-bit-rate = Bit rate
+info-file-size = File size
+info-created = Created
+
+## Effects browser
+effects-search = Search effects...
+effects-add-to-clip = Add to Clip
+effects-none-available = No effects available
+effects-no-matches = No matching effects
+
+## Timeline health
+health-no-issues = No issues found
+health-gap = Gap of { $duration }
+health-overlap = Overlap of { $duration }
+health-source-past-end = Source { $duration } past media end
+health-missing-asset = Asset missing from library
+health-missing-file = File missing: { $name }
+
+## Statistics
+stats-timeline-duration = Timeline duration
+stats-source-used = Source used
+stats-source-imported = Source imported
+stats-export-size = Estimated export size
+stats-clips = Clips
+stats-total = Total
+stats-effects = Effects
+stats-no-effects = No effects applied
+
+## Snapshots
+snapshots-name = Snapshot name
+snapshots-take = Take Snapshot
+snapshots-empty = No snapshots. Take one to keep this cut while you experiment.
+snapshots-clip-count = { $count } clips
+snapshots-compare = Compare
+snapshots-hide = Hide
+snapshots-restore = Restore
+snapshots-delete = Delete
+snapshots-no-changes = No changes since this snapshot
+snapshots-added = Added
+snapshots-removed = Removed
+snapshots-moved = moved
+snapshots-trimmed = trimmed
+snapshots-effects-changed = effects changed
+
+## Render ranges
+ranges-flag-in = Flag In
+ranges-flag-out = Flag Out
+ranges-name = Range name
+ranges-add = Add Range
+ranges-export-all = Export All
+ranges-pattern-help = File names: {project}, {name} and {index} are replaced per range
+ranges-empty = No render ranges. Flag an in and out point at the playhead to add one.
+ranges-loop = Loop
+ranges-delete = Delete
+
+## Transcript
+transcript-empty = No transcribed clips. Select a clip and use Edit > Transcribe Audio.
+transcript-cut = Cut Selected Words
+transcript-export-srt = Export SRT...
+
+## Clip inspector
+inspector-no-clip = No clip selected
+inspector-clip-not-found = Clip not found
+inspector-title = Clip Effects
+inspector-speed = Speed: { $percent }%
+inspector-keep-pitch = Keep Pitch
+inspector-channels = Channels: { $map }
+inspector-fit = Fit: { $fit }
+inspector-reframed = Reframed: { $count } pan keys
+inspector-clear-reframe = Clear
+inspector-trim-source = Source: { $start }s - { $end }s (drag the handles to trim)
+inspector-show-waveform = Show Waveform
+inspector-markers = Markers
+inspector-add-marker = Add at Playhead
+inspector-marker-name = Marker name
+inspector-no-effects = No effects
+inspector-effects-bypassed = Effects bypassed
+inspector-effects-on = Effects on
+inspector-remove-effect = Remove
+inspector-reset-parameter = Reset
+inspector-parameter-mixed = { $label }: mixed
+
+## Source monitor
+source-title = Source: { $name }
+source-close = Close
+source-loading = Loading...
+source-mark-in = Mark In
+source-mark-out = Mark Out
+source-clear-marks = Clear
+source-marks = In { $in } | Out { $out }
+source-subclip-name = Sub-clip name
+source-save-subclip = Save Sub-clip
+
+## Preview
+playback-play = Play
+playback-pause = Pause
+preview-no-video = No video
+preview-outgoing = Outgoing
+preview-incoming = Incoming
+preview-loop-on = Loop: On
+preview-loop-off = Loop: Off
+preview-safe-areas = Safe
+preview-thirds-grid = Grid
+preview-center-cross = Center
+preview-canvas = Canvas: { $canvas }
+preview-background = Bg: { $background }
+preview-background-checker = Checker
+preview-background-black = Black
+preview-background-gray = Gray
+preview-background-white = White
+preview-stats = Stats
+preview-memory = Mem
+preview-play-around = Play Around
+preview-preroll = Pre { $secs }s
+preview-postroll = Post { $secs }s
+preview-prerender = Pre-render
+preview-background-render = Background Render
+
+## Notifications panel
+notifications-clear = Clear
+notifications-close = Close
+notifications-empty = No notifications
+notifications-info = Info
+notifications-success = Done
+notifications-warning = Warning
+notifications-error = Error
+
+## Status bar
+status-none = No system messages
+status-notifications = Notifications
+status-notifications-count = Notifications ({ $count })
+status-rendering-progress = Rendering: { $current }/{ $total } frames ({ $percent }%) | Elapsed: { $elapsed }
+status-playing = Playing
+status-stopped = Stopped
+
+## Status messages
+status-opening-file-dialog = Opening file dialog...
+status-import-cancelled = Import cancelled
+status-importing = Importing...
+status-imported = Imported: { $name }
+status-import-failed = Import failed: { $error }
+status-folder-empty = No supported media in { $name }
+status-folder-scan-failed = Could not read folder: { $error }
+status-folder-imported = Imported { $count } files into { $name } ({ $skipped } already imported, { $failed } failed)
+status-already-imported = Already imported: { $name }
+status-replace-needs-asset = Select a library asset to replace
+status-replace-cancelled = Replace cancelled
+status-replace-failed = Replace failed: { $error }
+status-media-replaced = Replaced media of { $name }
+status-reveal-needs-asset = Select a library asset to reveal
+status-asset-unused = The asset isn't used in the timeline
+status-asset-usage = Use { $index } of { $count }
+status-shot-detection-failed = Shot detection failed: { $error }
+status-removed = Removed: { $name }
+status-remove-failed = Remove failed: { $error }
+status-journal-recovered = Recovered { $count } operation(s) from journal
+status-journal-replay-failed = Journal replay failed: { $error }
+status-journal-discarded = Recovery journal discarded
+status-clip-removed = Clip removed
+status-remove-clip-failed = Remove clip failed: { $error }
+status-drop-effect-on-clip = Drop effects onto a clip
+status-asset-not-found = Asset not found
+status-clips-added = Added { $count } clips
+status-slideshow-created = Created a slideshow of { $count } clips
+status-canvas-changed = Canvas set to { $width }×{ $height }
+status-slideshow-no-assets = Select assets in the library to create a slideshow
+status-slideshow-invalid-duration = Enter a clip duration between 0 and 3600 seconds
+status-no-video-track = The timeline has no video track
+status-add-clips-failed = Add clips failed: { $error }
+status-clip-added = Clip added
+status-add-clip-failed = Add clip failed: { $error }
+status-move-failed = Move failed: { $error }
+status-clip-cut = Clip cut
+status-clips-grouped = Grouped { $count } clips
+status-clips-ungrouped = Ungrouped { $count } clips
+status-group-failed = Grouping failed: { $error }
+status-sync-needs-two-clips = Select two clips to synchronize
+status-sync-started = Analyzing audio to synchronize clips...
+status-clips-synced = Clips synchronized (offset { $offset }s)
+status-sync-failed = Synchronization failed: { $error }
+status-dual-mono-needs-clip = Select a clip to split its audio
+status-dual-mono-done = Split audio: left channel kept, right channel on { $track }
+status-dual-mono-failed = Cannot split to dual mono: { $error }
+status-transcribe-needs-clip = Select a clip with audio to transcribe
+status-transcription-started = Transcribing { $name }...
+status-transcription-done = Transcription finished ({ $count } words)
+status-transcription-failed = Transcription failed: { $error }
+status-beats-need-clip = Select a clip with audio to detect beats
+status-beats-started = Detecting beats...
+status-beats-none = No steady beat found
+status-beats-done = Added { $count } beat guides ({ $bpm } BPM)
+status-beats-failed = Beat detection failed: { $error }
+status-reframe-need-clip = Select a video clip to reframe
+status-reframe-fits = The clip already fits the canvas width
+status-reframe-started = Analysing clip for reframing...
+status-reframe-done = Reframed clip with { $count } pan keys
+status-reframe-failed = Auto reframe failed: { $error }
+status-words-cut = Cut selected words
+status-cut-words-failed = Cannot cut words: { $error }
+status-srt-exported = Exported subtitles to { $path }
+status-srt-export-failed = Subtitle export failed: { $error }
+status-invalid-timecode = Not a timecode: { $input }
+status-marker-added = Added { $name }
+status-clip-marker-needs-clip = Select a clip to mark
+status-clip-marker-failed = Clip marker failed: { $error }
+status-guide-added = Added guide
+status-guide-interval-invalid = Guide interval must be a positive number
+status-guides-need-clips = Add clips to the timeline before generating guides
+status-guides-generated = Added { $count } guides
+status-insert-time-invalid = Insert time must be a positive number of seconds
+status-time-inserted = Inserted { $secs }s at the playhead
+status-insert-time-failed = Cannot insert time: { $error }
+status-description-exported = Exported description to { $path }
+status-description-export-failed = Description export failed: { $error }
+status-timeline-search-no-match = No clips match the search
+status-fit-to-fill-needs-source = Load a clip in the source monitor to fit
+status-fit-to-fill-no-gap = No gap under the playhead to fill
+status-fit-to-fill-done = Filled gap at { $speed }% speed
+status-fit-to-fill-failed = Cannot fit to fill: { $error }
+status-cut-failed = Cut failed: { $error }
+status-resize-failed = Resize failed: { $error }
+status-ripple-edits-on = Ripple edits on
+status-ripple-edits-off = Ripple edits off
+status-export-stems-on = Renders will also write a WAV per audio track
+status-export-stems-off = Renders will write the main mix only
+status-snap-target-on = { $target }: on
+status-snap-target-off = { $target }: off
+status-speed-failed = Change speed failed: { $error }
+status-channel-map-failed = Change channel routing failed: { $error }
+status-preserve-pitch-failed = Change pitch preservation failed: { $error }
+status-rate-conform-failed = Change rate conform failed: { $error }
+status-canvas-fit-failed = Change canvas fit failed: { $error }
+status-volume-envelope-failed = Change volume envelope failed: { $error }
+status-waveform-needs-audio = Select a clip with audio to show its waveform
+status-waveform-failed = Loading waveform failed: { $error }
+status-source-trim-failed = Trim failed: { $error }
+status-nothing-to-fit = Nothing to fit: the timeline is empty
+status-ui-scale = UI scale { $percent }%
+status-language = Language: { $language }
+status-loop-on = Loop playback on
+status-loop-off = Loop playback off
+status-source-monitor = Source monitor: { $name }
+status-subclip-saved = Saved sub-clip: { $name }
+status-subclip-failed = Could not save sub-clip: { $error }
+status-undone = Undone
+status-snapshot-taken = Saved snapshot: { $name }
+status-snapshot-restored = Restored snapshot: { $name }
+status-snapshot-restore-failed = Restore snapshot failed: { $error }
+status-snapshot-deleted = Deleted snapshot: { $name }
+status-undo-failed = Undo failed: { $error }
+status-redone = Redone
+status-redo-failed = Redo failed: { $error }
+status-saved = Saved to { $path }
+status-save-failed = Save failed: { $error }
+status-saving = Saving to { $path }...
+status-save-in-progress = A save is already in progress
+status-save-indicator = Saving...
+status-opening-save-dialog = Opening save dialog...
+status-save-cancelled = Save cancelled
+status-loaded = Loaded { $path }
+status-load-failed = Load failed: { $error }
+status-load-cancelled = Load cancelled
+status-versions-need-save = Save the project first; versions are recorded on every save
+status-no-versions = No saved versions yet
+status-version-restored = Restored version { $version }; save to keep it
+status-paths-remapped = Remapped { $count } media path(s)
+status-remap-nothing-found = No matching file exists at the new path
+status-preferences-saved = Preferences saved
+status-preference-invalid = Invalid value for { $field }
+status-thumbnails = Thumbnails { $done }/{ $total }
+status-new-project = New project created
+status-rendering = Rendering...
+status-render-cancelled = Render cancelled
+status-render-output-unusable = Cannot render to { $path }: { $error }
+status-rendered = Rendered to { $path } | Total time: { $elapsed }
+status-render-failed = Render failed: { $error }
+status-render-range-needs-flags = Flag an in and an out point first
+status-render-range-invalid = The out flag must be after the in flag
+status-render-range-added = Added render range { $name }
+status-render-range-deleted = Deleted render range { $name }
+status-no-render-ranges = No render ranges to export
+status-render-ranges-queued = Queued { $count } render ranges for export
+status-opening-load-dialog = Opening load dialog...
+status-opening-render-dialog = Opening render dialog...
+status-track-update-failed = Track update failed: { $error }
+status-video-track-added = Video track added
+status-audio-track-added = Audio track added
+status-add-track-failed = Add track failed: { $error }
+status-track-deleted = Track deleted
+status-delete-track-failed = Delete track failed: { $error }
+status-effect-added = Effect added
+status-effect-added-to-clips = Effect added to { $count } clips
+status-effect-needs-video-clips = Select clips on a video track to add an effect to
+status-add-effect-failed = Add effect failed: { $error }
+status-effect-removed = Effect removed
+status-remove-effect-failed = Remove effect failed: { $error }
+status-update-effect-failed = Update effect failed: { $error }
+status-effects-bypassed = Effects bypassed in the preview (press \ to compare)
+status-effects-restored = Effects shown in the preview
+status-shuttle = Shuttle { $rate }x
+
+## Notifications
+notify-preferences-save-failed = Failed to save preferences: { $error }
+notify-decode-failed = Decode failed: { $error }
+notify-display-lut-failed = Could not load display LUT { $path }: { $error }
+notify-replace-fps-changed = The new file for { $name } has a different frame rate; check edit points
+notify-replace-clips-past-end = { $count } clip(s) now read past the end of the new file
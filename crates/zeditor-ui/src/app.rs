@@ -1,51 +1,202 @@
-use std::path::PathBuf;
-use std::sync::mpsc;
-use std::time::{Duration, Instant};
-
-use std::collections::HashMap;
+mod dialogs;
+mod export;
+mod import;
+mod library;
+mod library_view;
+mod monitor;
+mod preferences_dialog;
+mod prerender;
+mod slideshow;
+mod transcript;
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant, SystemTime};
+
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use iced::widget::{button, center, column, container, image, mouse_area, opaque, row, scrollable, slider, stack, text, text_input, Space};
 use iced::{event, keyboard, mouse, time, window, Background, Border, Color, Element, Event, Length, Padding, Point, Subscription, Task};
 use uuid::Uuid;
 
-use zeditor_core::effects::{EffectInstance, EffectType};
+use zeditor_core::audio::{ChannelMap, VolumeEnvelope};
+use zeditor_core::color::{self, ColorSpace, CubeLut};
+use zeditor_core::description;
+use zeditor_core::effects::{EffectCategory, EffectInstance, EffectType};
+use zeditor_core::health::{self, HealthIssue, HealthIssueKind};
+use zeditor_core::journal::{self, CommandJournal};
+use zeditor_core::media::{MediaAsset, MediaKind, SourceLibrary, SubClip};
 use zeditor_core::pipeline::{self, EffectContext, EffectRegistry, FrameBuffer};
-use zeditor_core::project::Project;
-use zeditor_core::timeline::{Clip, TimeRange, TimelinePosition, TrackType};
-
+use zeditor_core::project::{CanvasPreset, Project, ProjectFile};
+use zeditor_core::reframe::{self, PanPath};
+use zeditor_core::render_ranges;
+use zeditor_core::scaler::{self, CanvasFit, Placement, ScaleQuality};
+use zeditor_core::snapshots::ClipChangeKind;
+use zeditor_core::stats::{self, ProjectStats};
+use zeditor_core::timecode;
+use zeditor_core::timeline::{Clip, RateConform, TimeRange, Timeline, TimelinePosition, TrackType};
+use zeditor_core::versions::{self, ProjectVersion, ProjectVersions};
+use zeditor_media::output_check;
+use zeditor_media::probe::MediaDetails;
+use zeditor_media::renderer::Retime;
+
+use crate::audio_meters::{self, AudioLevels, AudioMeters};
 use crate::audio_player::AudioPlayer;
-use crate::message::{ConfirmAction, ConfirmDialog, DragPayload, DragState, LeftPanelTab, MenuAction, MenuId, Message, SourceDragPreview, ToolMode, TrackContextMenu};
-use crate::widgets::timeline_canvas::TimelineCanvas;
+use crate::audio_ring::{AUDIO_RING_SAMPLES, AudioRing};
+use crate::gpu_compositor::{GpuCompositor, GpuLayer};
+use crate::i18n::{I18n, Language};
+use crate::library_filter::{self, LibraryFilter, LibrarySort, LibraryViewMode, UsageFilter};
+use crate::media_cache::{self, MediaCache};
+use crate::memory::{self, Evictable, MemoryBudget, MemorySubsystem};
+use crate::playback_stats::PlaybackStats;
+use crate::preferences::{PreferencesForm, PreviewQuality, UiPreferences};
+use crate::preview_cache::{PreviewCacheKey, PreviewFrameCache};
+use crate::source_monitor::SourceMonitor;
+use crate::trim_preview::{self, TrimFrameRequest, TrimTwoUp};
+use crate::notifications::{Notification, NotificationCenter, NotificationLevel};
+use crate::message::{ConfirmAction, ConfirmDialog, DialogAnswer, DialogInput, DragPayload, FolderImport, DragState, LeftPanelTab, MenuAction, MenuId, Message, PanelSplitter, PathRemapDialog, PreferenceField, PreviewBackground, PreviewOverlayKind, SlideshowDialog, SnapTarget, SourceDragPreview, ToolMode, TrackContextMenu, TrackFlag};
+use crate::views::dialog;
+use crate::widgets::checkerboard::PreviewBackdrop;
+use crate::widgets::preview_overlay::PreviewOverlay;
+use crate::widgets::timeline_canvas::{self, TimelineCanvas};
+use crate::widgets::track_header;
+use crate::widgets::waveform::{self as waveform_widget, InspectorWaveform, WaveformEditor};
+
+pub use library_view::{asset_info_rows, format_file_size};
+pub use monitor::format_subclip_range;
+use prerender::prerender_worker;
 
 /// Preview resolution cap. 4K frames are scaled down to this for display.
 const PREVIEW_MAX_WIDTH: u32 = 960;
 const PREVIEW_MAX_HEIGHT: u32 = 540;
 
+/// Fastest JKL shuttle rate (in either direction).
+const MAX_SHUTTLE_RATE: f64 = 4.0;
+
+/// Default preroll and postroll for play-around, in seconds.
+const DEFAULT_PLAY_AROUND_SECS: f64 = 2.0;
+/// Longest configurable preroll or postroll, in seconds.
+const MAX_PLAY_AROUND_SECS: f64 = 10.0;
+
+/// Timeline zoom limits, in pixels per second.
+const MIN_TIMELINE_ZOOM: f32 = 0.1;
+const MAX_TIMELINE_ZOOM: f32 = 1000.0;
+/// Zoom levels visited by Ctrl+= / Ctrl+-, in pixels per second.
+pub const TIMELINE_ZOOM_PRESETS: [f32; 11] =
+    [1.0, 2.0, 5.0, 10.0, 25.0, 50.0, 100.0, 200.0, 400.0, 700.0, 1000.0];
+/// Speeds offered in the clip inspector, as source seconds per timeline second.
+const CLIP_SPEED_PRESETS: [f64; 5] = [0.25, 0.5, 1.0, 2.0, 4.0];
+/// Seconds of a loop export's end dissolved into its start.
+const LOOP_CROSSFADE_SECS: f64 = 1.0;
+/// Fraction of the viewport left empty after the last clip by zoom-to-fit.
+const ZOOM_TO_FIT_MARGIN: f32 = 0.05;
+
+/// Composited frames the timeline decode worker may run ahead of the playhead.
+const DECODE_AHEAD_FRAMES: usize = 8;
+/// How far ahead of a cut the next clips' decoders are opened and seeked.
+const PRELOAD_LEAD_SECS: f64 = 1.0;
+/// Frames a decoder is assumed to keep for reference, for memory accounting.
+const DECODER_REFERENCE_FRAMES: usize = 8;
+/// Longest the worker waits at a cut for a preload that is still in flight.
+const PRELOAD_WAIT: Duration = Duration::from_secs(2);
+
+/// Seconds ahead of / behind the playhead that effect-heavy frames are pre-rendered.
+const PRERENDER_AHEAD_SECS: f64 = 5.0;
+const PRERENDER_BEHIND_SECS: f64 = 1.0;
+/// Most frames handed to the pre-render worker in one plan.
+const PRERENDER_MAX_FRAMES: usize = 900;
+/// Frames kept in the pre-render cache (its memory cap usually bites first).
+const PRERENDER_CACHE_CAPACITY: usize = 1800;
+/// How often the pre-render plan is recomputed.
+const PRERENDER_REPLAN_INTERVAL: Duration = Duration::from_millis(250);
+/// Disk space background-rendered frames may use before the oldest are deleted.
+const BACKGROUND_RENDER_DISK_CAP: u64 = 8 * 1024 * 1024 * 1024;
+/// How long the audio decode worker sleeps when the ring is full.
+const AUDIO_RING_FULL_WAIT: Duration = Duration::from_millis(2);
+
+/// Source-time step between frames when the decode worker feeds in reverse at 1x.
+/// Each reverse frame costs a seek, so this is coarser than the forward frame step.
+const REVERSE_FRAME_STEP_SECS: f64 = 1.0 / 15.0;
+
 /// Info about a single clip to decode for multi-clip compositing.
 #[derive(Clone, Debug)]
 struct ClipDecodeInfo {
     path: PathBuf,
     time: f64,
     effects: Vec<EffectInstance>,
+    /// Colour space of the source, converted to the working space after decode.
+    color: ColorSpace,
+    fit: CanvasFit,
+    /// Displayed frame size of the source at project scale, which 1:1 keeps.
+    source_size: (u32, u32),
+    /// Pan keys of the clip's crop, applied at each decoded frame's time.
+    pan: PanPath,
 }
 
 /// Request sent from UI to the decode thread.
 enum DecodeRequest {
     /// Seek multiple clips for compositing. Clips are ordered bottom-to-top (V1 first).
     SeekMulti {
+        /// Generation from `next_decode_generation`; stale once a newer one exists.
+        generation: u64,
         clips: Vec<ClipDecodeInfo>,
         continuous: bool,
         canvas_w: u32,
         canvas_h: u32,
+        /// Playback rate multiplier for continuous decode. Negative plays in reverse.
+        rate: f64,
+        /// Project frame rate; a seek lands on the frame within half a frame of the target.
+        fps: f64,
+        quality: PreviewQuality,
+        /// Display transform applied to the finished composite.
+        display_lut: Option<Arc<CubeLut>>,
+    },
+    /// Open and seek decoders for clips starting at the next cut, in the
+    /// background, so the switch doesn't wait on them.
+    Preload {
+        clips: Vec<ClipDecodeInfo>,
+        fps: f64,
     },
     Stop,
 }
 
+/// A composited frame for the pre-render worker to produce.
+struct PrerenderFrame {
+    key: PreviewCacheKey,
+    /// Clips under the frame, bottom-to-top, at their source times.
+    clips: Vec<ClipDecodeInfo>,
+    /// Planned by background render only: written to disk but not sent back.
+    background: bool,
+}
+
+/// Request sent from UI to the pre-render thread. Each request replaces what is
+/// left of the previous one.
+struct PrerenderRequest {
+    frames: Vec<PrerenderFrame>,
+    canvas_w: u32,
+    canvas_h: u32,
+    fps: f64,
+    display_lut: Option<Arc<CubeLut>>,
+    /// Disk cache frames are read from before rendering and written to after,
+    /// tagged with the display LUT variant. `None` unless background render is on.
+    frame_store: Option<(MediaCache, u64)>,
+}
+
 /// Info about a single audio clip to decode for multi-clip mixing.
 #[derive(Clone, Debug)]
 struct AudioClipInfo {
     path: PathBuf,
     time: f64,
+    /// Track the clip is on, for its level meter.
+    track_index: usize,
+    channel_map: ChannelMap,
+    volume_envelope: VolumeEnvelope,
+    /// Start of the clip's source range, where retiming is measured from.
+    source_start: f64,
+    /// Source seconds per timeline second, for retimed clips.
+    speed: Option<f64>,
+    preserve_pitch: bool,
 }
 
 /// Request sent from UI to the audio decode thread.
@@ -54,16 +205,45 @@ enum AudioDecodeRequest {
     SeekMulti {
         clips: Vec<AudioClipInfo>,
         continuous: bool,
+        /// Ring epoch to tag the decoded audio with (see `AudioRing::flush`).
+        epoch: u64,
     },
     Stop,
 }
 
-/// Decoded audio sent from the audio decode thread to the UI.
-pub(crate) struct DecodedAudio {
-    pub(crate) samples: Vec<f32>,
-    pub(crate) sample_rate: u32,
-    pub(crate) channels: u16,
-    pub(crate) pts_secs: f64,
+/// A project save running on a background thread.
+pub(crate) struct PendingSave {
+    pub(crate) path: PathBuf,
+    /// The timeline and library as written, to tell which edits the file
+    /// is missing.
+    pub(crate) timeline: Timeline,
+    pub(crate) library: SourceLibrary,
+    pub(crate) rx: mpsc::Receiver<Result<(), String>>,
+}
+
+/// A render waiting in (or taken from) the render queue.
+pub struct RenderJob {
+    pub timeline: Timeline,
+    pub source_library: SourceLibrary,
+    pub output_path: PathBuf,
+    /// Render as a seamless loop, dissolving this many seconds of the end into the start.
+    pub loop_crossfade: Option<f64>,
+}
+
+/// An active play-around: playback stops at `stop_at` and the playhead returns to `origin`.
+#[derive(Debug, Clone, Copy)]
+struct PlayAroundState {
+    origin: TimelinePosition,
+    stop_at: TimelinePosition,
+}
+
+/// An in-progress splitter drag. The first cursor event sets `origin`; later ones
+/// resize the panel by the distance moved from it.
+#[derive(Debug, Clone, Copy)]
+struct SplitterDrag {
+    splitter: PanelSplitter,
+    origin: Option<Point>,
+    start_size: f32,
 }
 
 /// Decoded frame sent from the decode thread to the UI.
@@ -81,49 +261,205 @@ pub struct App {
     pub playback_position: TimelinePosition,
     pub is_playing: bool,
     pub status_message: String,
+    pub notifications: NotificationCenter,
+    pub show_notification_history: bool,
+    /// Primary selected asset; also the anchor for Shift range selection.
     pub selected_asset_id: Option<Uuid>,
+    /// All selected assets in selection order (includes the primary one).
+    pub selected_asset_ids: Vec<Uuid>,
+    /// The library's info section for the selected asset is expanded.
+    pub show_asset_info: bool,
+    /// Extended probe of the last asset shown in the info section.
+    pub asset_details: Option<(Uuid, Result<MediaDetails, String>)>,
+    /// Asset whose details are being probed.
+    asset_details_loading: Option<Uuid>,
+    /// Modifier keys currently held, for Ctrl/Shift-click selection.
+    pub keyboard_modifiers: keyboard::Modifiers,
+    /// The Space press still held toggled playback; undone if it turns into a pan.
+    space_toggled_playback: bool,
+    /// Search text and filter chips for the source library grid.
+    pub library_filter: LibraryFilter,
+    /// Per-user UI preferences (library sort and view mode).
+    pub preferences: UiPreferences,
+    /// User-facing strings for `preferences.language`.
+    pub i18n: I18n,
+    /// Where preferences are saved; `None` keeps them in memory only (tests).
+    pub preferences_path: Option<PathBuf>,
     pub current_frame: Option<iced::widget::image::Handle>,
     pub playback_start_wall: Option<Instant>,
     pub playback_start_pos: TimelinePosition,
+    /// When true, playback wraps back to the start of the loop range instead of
+    /// running past the end of the timeline.
+    pub loop_playback: bool,
+    /// Playback clock rate multiplier: 1.0 is normal, 2.0/4.0 fast-forward,
+    /// negative values play in reverse (JKL shuttle).
+    pub playback_rate: f64,
+    /// Seconds played before the playhead by play-around.
+    pub play_around_preroll: f64,
+    /// Seconds played after the playhead by play-around.
+    pub play_around_postroll: f64,
+    /// Framing guides drawn over the preview (safe areas, thirds grid, center cross).
+    pub preview_overlay: PreviewOverlay,
+    /// Whether the playback performance overlay is shown on the preview.
+    pub show_playback_stats: bool,
+    /// What is shown behind transparent areas of the preview frame.
+    pub preview_background: PreviewBackground,
+    pub playback_stats: PlaybackStats,
+    /// Show the memory usage overlay on the preview.
+    pub show_memory_overlay: bool,
+    /// Memory accounting shared with the decode workers.
+    pub memory: MemoryBudget,
+    prerender_tx: Option<mpsc::Sender<PrerenderRequest>>,
+    /// Finished frames; `None` for a background frame that was only written to disk.
+    prerender_rx: Option<mpsc::Receiver<(PreviewCacheKey, Option<DecodedFrame>)>>,
+    /// Composited frames of effect-heavy regions, rendered in the background.
+    pub prerender_cache: PreviewFrameCache,
+    /// Frames the pre-render worker has been asked for.
+    prerender_planned: HashSet<PreviewCacheKey>,
+    last_prerender_plan: Option<Instant>,
+    /// Frame on screen while playback is served from the pre-render cache (the
+    /// decode worker is stopped meanwhile).
+    pub(crate) serving_prerender: Option<PreviewCacheKey>,
+    /// Timeline ranges marked for pre-rendering besides the playhead window.
+    pub prerender_sections: Vec<TimeRange>,
+    /// Frames known to be in the disk cache from background render.
+    pub background_rendered: HashSet<PreviewCacheKey>,
+    /// Frames with effects on the timeline, and how many of them are rendered,
+    /// as of the last background render plan.
+    pub background_render_progress: (usize, usize),
+    /// Second player for previewing a single library asset in source time.
+    pub source_monitor: SourceMonitor,
     pub timeline_zoom: f32,
     pub timeline_scroll: f32,
+    /// Pixels the track lanes (and headers) are scrolled down.
+    pub timeline_vertical_scroll: f32,
+    /// Last known window size, used to work out the visible timeline width.
+    pub window_size: iced::Size,
     pub tool_mode: ToolMode,
     pub open_menu: Option<MenuId>,
     pub thumbnails: HashMap<Uuid, iced::widget::image::Handle>,
+    /// Persistent thumbnail store; `None` when there is no user cache directory.
+    media_cache: Option<MediaCache>,
+    /// Parsed `preferences.display_lut`, applied to preview frames only.
+    pub display_lut: Option<Arc<CubeLut>>,
+    /// Assets whose thumbnails are still being loaded after opening a project,
+    /// out of `thumbnail_total`.
+    thumbnail_pending: HashSet<Uuid>,
+    thumbnail_total: usize,
     pub drag_state: Option<DragState>,
     pub hovered_asset_id: Option<Uuid>,
     pub selected_clip: Option<(usize, Uuid)>,
+    /// Clips added to the selection with Shift/Ctrl+click, on top of
+    /// `selected_clip`.
+    pub selected_clips: Vec<(usize, Uuid)>,
+    /// Words selected in the Transcript tab, as `(anchor, end)` indices into
+    /// `transcript_words()`.
+    pub transcript_selection: Option<(usize, usize)>,
+    /// Waveform shown in the clip inspector, for the clip it was loaded for.
+    pub inspector_waveform: Option<InspectorWaveform>,
+    /// Outgoing/incoming frames shown in the viewport while a clip end is dragged.
+    pub trim_two_up: Option<TrimTwoUp>,
     pub confirm_dialog: Option<ConfirmDialog>,
+    /// Folder import in progress.
+    pub folder_import: Option<FolderImport>,
+    /// Name field for sub-clips saved from the source monitor.
+    pub subclip_name: String,
+    /// Clip marker being renamed in the inspector, with the name typed so far.
+    pub clip_marker_edit: Option<(Uuid, String)>,
+    /// Timecode being typed into the playhead field; `None` shows the playhead.
+    pub timecode_input: Option<String>,
+    /// Field values of the open Preferences dialog.
+    pub preferences_form: Option<PreferencesForm>,
+    /// Saved versions listed by the open "Restore Version" dialog, newest first.
+    pub version_dialog: Option<Vec<ProjectVersion>>,
+    pub path_remap_dialog: Option<PathRemapDialog>,
+    pub slideshow_dialog: Option<SlideshowDialog>,
     pub left_panel_tab: LeftPanelTab,
+    /// Name typed for the next timeline snapshot.
+    pub snapshot_name: String,
+    /// Snapshot whose differences from the timeline are listed in the Snapshots tab.
+    pub compared_snapshot: Option<Uuid>,
+    /// In and out flags for the next render range.
+    pub render_range_in: Option<TimelinePosition>,
+    pub render_range_out: Option<TimelinePosition>,
+    /// Name typed for the next render range.
+    pub render_range_name: String,
+    /// Interval typed for generated timeline guides.
+    pub guide_interval: String,
+    /// `guide_interval` counts frames at the project frame rate instead of seconds.
+    pub guide_interval_in_frames: bool,
+    /// Seconds typed for Insert Time.
+    pub insert_time_secs: String,
+    /// Search text for the effects browser.
+    pub effect_search: String,
+    /// "Find in timeline" query, matched against clips' asset names.
+    pub timeline_search: String,
+    /// Index into `timeline_search_matches()` of the match last jumped to.
+    pub timeline_search_cursor: Option<usize>,
     pub track_context_menu: Option<TrackContextMenu>,
     /// Text input state for effect parameters with wide ranges (e.g. transform offset).
     /// Key: (effect_id, param_name), Value: current text string in the input field.
     pub effect_param_texts: HashMap<(Uuid, String), String>,
+    /// A/B compare: the preview shows every clip without its effects. View
+    /// state only; never saved or rendered.
+    pub effects_bypassed: bool,
     decode_tx: Option<mpsc::Sender<DecodeRequest>>,
     pub(crate) decode_rx: Option<mpsc::Receiver<DecodedFrame>>,
+    /// Latest decode generation, shared with the timeline decode worker.
+    pub(crate) decode_generation: Arc<AtomicU64>,
     pub(crate) decode_clip_id: Option<Uuid>,
     /// IDs of all video clips currently being decoded (for multi-track change detection).
     decode_clip_ids: Vec<Uuid>,
+    /// Clips starting at the next cut that the worker was asked to preload.
+    pub(crate) preloaded_clip_ids: Vec<Uuid>,
     /// Offset to convert source PTS → timeline time: timeline_time = pts + offset.
     pub(crate) decode_time_offset: f64,
     /// Frame received from decode thread but not yet displayed (PTS ahead of playback).
     pending_frame: Option<DecodedFrame>,
     /// After a decode transition, discard frames that are too far ahead (stale from old context).
     drain_stale: bool,
+    /// Recently composited scrub frames, so revisiting a position shows instantly.
+    pub preview_cache: PreviewFrameCache,
+    /// Cache key for the scrub frame currently being decoded, stored when it arrives.
+    pending_cache_key: Option<PreviewCacheKey>,
+    // Source monitor decoding (separate worker so it never disturbs the timeline preview)
+    source_decode_tx: Option<mpsc::Sender<DecodeRequest>>,
+    source_decode_generation: Arc<AtomicU64>,
+    pub(crate) source_decode_rx: Option<mpsc::Receiver<DecodedFrame>>,
+    /// Errors reported by the decode workers, surfaced as notifications.
+    pub(crate) decode_error_rx: Option<mpsc::Receiver<String>>,
+    /// Source monitor frame received but not yet due.
+    source_pending_frame: Option<DecodedFrame>,
     // Audio playback
     audio_player: Option<AudioPlayer>,
     audio_decode_tx: Option<mpsc::Sender<AudioDecodeRequest>>,
-    pub(crate) audio_decode_rx: Option<mpsc::Receiver<DecodedAudio>>,
+    /// Decoded audio, written by the audio decode thread and read by the output
+    /// device; the UI only flushes it and reads the clock anchor.
+    pub(crate) audio_ring: Arc<AudioRing>,
     /// ID of the audio clip currently being decoded (for change detection).
     pub(crate) audio_decode_clip_id: Option<Uuid>,
     pub(crate) audio_decode_time_offset: f64,
+    /// Per-track peaks sent by the audio decode thread with the audio it mixes.
+    pub(crate) audio_levels_rx: Option<mpsc::Receiver<AudioLevels>>,
+    /// Feeds the level meters in the audio track headers.
+    pub audio_meters: AudioMeters,
+    /// Set while a play-around is running.
+    play_around: Option<PlayAroundState>,
+    splitter_drag: Option<SplitterDrag>,
     // Render progress state
     pub is_rendering: bool,
     render_progress_rx: Option<mpsc::Receiver<zeditor_media::render_profile::RenderProgress>>,
+    /// Save in progress; polled on each tick.
+    pub(crate) pending_save: Option<PendingSave>,
     pub render_current_frame: u64,
     pub render_total_frames: u64,
     pub render_elapsed: Duration,
     pub render_start: Option<Instant>,
+    /// Renders waiting for the current one to finish, in order.
+    pub render_queue: VecDeque<RenderJob>,
+    /// Renders held back until the user says whether to overwrite the
+    /// outputs already on disk (`ConfirmAction::RenderOutputExists`).
+    pub renders_awaiting_overwrite: Vec<RenderJob>,
 }
 
 impl Default for App {
@@ -134,40 +470,119 @@ impl Default for App {
             playback_position: TimelinePosition::zero(),
             is_playing: false,
             status_message: String::new(),
+            notifications: NotificationCenter::new(),
+            show_notification_history: false,
             selected_asset_id: None,
+            selected_asset_ids: Vec::new(),
+            show_asset_info: false,
+            asset_details: None,
+            asset_details_loading: None,
+            keyboard_modifiers: keyboard::Modifiers::default(),
+            space_toggled_playback: false,
+            library_filter: LibraryFilter::default(),
+            preferences: UiPreferences::default(),
+            i18n: I18n::default(),
+            preferences_path: None,
             current_frame: None,
             playback_start_wall: None,
             playback_start_pos: TimelinePosition::zero(),
+            loop_playback: false,
+            playback_rate: 1.0,
+            play_around_preroll: DEFAULT_PLAY_AROUND_SECS,
+            play_around_postroll: DEFAULT_PLAY_AROUND_SECS,
+            preview_overlay: PreviewOverlay::default(),
+            show_playback_stats: false,
+            preview_background: PreviewBackground::default(),
+            playback_stats: PlaybackStats::new(),
+            show_memory_overlay: false,
+            memory: MemoryBudget::default(),
+            prerender_tx: None,
+            prerender_rx: None,
+            prerender_cache: PreviewFrameCache::new(PRERENDER_CACHE_CAPACITY),
+            prerender_planned: HashSet::new(),
+            last_prerender_plan: None,
+            serving_prerender: None,
+            prerender_sections: Vec::new(),
+            background_rendered: HashSet::new(),
+            background_render_progress: (0, 0),
+            source_monitor: SourceMonitor::new(),
             timeline_zoom: 100.0,
             timeline_scroll: 0.0,
+            timeline_vertical_scroll: 0.0,
+            // iced's default window size, until the first resize event arrives
+            window_size: iced::Size::new(1024.0, 768.0),
             tool_mode: ToolMode::default(),
             open_menu: None,
             thumbnails: HashMap::new(),
+            media_cache: None,
+            display_lut: None,
+            thumbnail_pending: HashSet::new(),
+            thumbnail_total: 0,
             drag_state: None,
             hovered_asset_id: None,
             selected_clip: None,
+            selected_clips: Vec::new(),
+            transcript_selection: None,
+            inspector_waveform: None,
+            trim_two_up: None,
             confirm_dialog: None,
+            folder_import: None,
+            subclip_name: String::new(),
+            clip_marker_edit: None,
+            timecode_input: None,
+            version_dialog: None,
+            path_remap_dialog: None,
+            slideshow_dialog: None,
+            preferences_form: None,
             left_panel_tab: LeftPanelTab::default(),
+            snapshot_name: String::new(),
+            compared_snapshot: None,
+            render_range_in: None,
+            render_range_out: None,
+            render_range_name: String::new(),
+            guide_interval: "1".to_string(),
+            guide_interval_in_frames: false,
+            insert_time_secs: "1".to_string(),
+            effect_search: String::new(),
+            timeline_search: String::new(),
+            timeline_search_cursor: None,
             track_context_menu: None,
             effect_param_texts: HashMap::new(),
+            effects_bypassed: false,
             decode_tx: None,
             decode_rx: None,
+            decode_generation: Arc::new(AtomicU64::new(0)),
             decode_clip_id: None,
             decode_clip_ids: Vec::new(),
+            preloaded_clip_ids: Vec::new(),
             decode_time_offset: 0.0,
             pending_frame: None,
             drain_stale: false,
+            preview_cache: PreviewFrameCache::default(),
+            pending_cache_key: None,
+            source_decode_tx: None,
+            source_decode_generation: Arc::new(AtomicU64::new(0)),
+            source_decode_rx: None,
+            decode_error_rx: None,
+            source_pending_frame: None,
             audio_player: None,
             audio_decode_tx: None,
-            audio_decode_rx: None,
+            audio_ring: Arc::new(AudioRing::new(AUDIO_RING_SAMPLES)),
             audio_decode_clip_id: None,
             audio_decode_time_offset: 0.0,
+            audio_levels_rx: None,
+            audio_meters: AudioMeters::new(),
+            play_around: None,
+            splitter_drag: None,
             is_rendering: false,
             render_progress_rx: None,
+            pending_save: None,
             render_current_frame: 0,
             render_total_frames: 0,
             render_elapsed: Duration::ZERO,
             render_start: None,
+            render_queue: VecDeque::new(),
+            renders_awaiting_overwrite: Vec::new(),
         }
     }
 }
@@ -188,277 +603,1192 @@ impl App {
         self.is_playing = false;
         self.playback_start_wall = None;
         self.playback_start_pos = TimelinePosition::zero();
+        self.playback_rate = 1.0;
         self.current_frame = None;
         self.selected_asset_id = None;
+        self.selected_asset_ids.clear();
+        self.asset_details = None;
+        self.asset_details_loading = None;
+        self.library_filter = LibraryFilter::default();
         self.hovered_asset_id = None;
         self.selected_clip = None;
+        self.selected_clips.clear();
+        self.transcript_selection = None;
+        self.inspector_waveform = None;
+        self.trim_two_up = None;
         self.confirm_dialog = None;
+        self.folder_import = None;
+        self.subclip_name.clear();
+        self.clip_marker_edit = None;
+        self.timecode_input = None;
+        self.version_dialog = None;
+        self.path_remap_dialog = None;
+        self.slideshow_dialog = None;
+        self.preferences_form = None;
         self.left_panel_tab = LeftPanelTab::default();
+        self.snapshot_name.clear();
+        self.compared_snapshot = None;
+        self.render_range_in = None;
+        self.render_range_out = None;
+        self.render_range_name.clear();
+        self.effect_search.clear();
+        self.timeline_search.clear();
+        self.timeline_search_cursor = None;
         self.track_context_menu = None;
         self.thumbnails.clear();
+        self.thumbnail_pending.clear();
+        self.thumbnail_total = 0;
         self.drag_state = None;
         self.timeline_zoom = 100.0;
         self.timeline_scroll = 0.0;
+        self.timeline_vertical_scroll = 0.0;
         self.tool_mode = ToolMode::default();
         self.open_menu = None;
         self.decode_clip_id = None;
         self.decode_clip_ids.clear();
+        self.preloaded_clip_ids.clear();
         self.decode_time_offset = 0.0;
         self.pending_frame = None;
         self.drain_stale = false;
+        self.preview_cache.clear();
+        self.prerender_cache.clear();
+        self.prerender_planned.clear();
+        self.last_prerender_plan = None;
+        self.serving_prerender = None;
+        self.prerender_sections.clear();
+        self.background_rendered.clear();
+        self.background_render_progress = (0, 0);
+        self.pending_cache_key = None;
         self.audio_decode_clip_id = None;
         self.audio_decode_time_offset = 0.0;
+        self.audio_meters.clear();
+        self.play_around = None;
+        self.source_monitor.close();
+        self.source_pending_frame = None;
         self.send_decode_stop();
+        self.send_source_decode_stop();
         self.send_audio_decode_stop();
-        if let Some(player) = &self.audio_player {
-            player.stop();
+    }
+
+    /// Write a snapshot of the project to `path` on a background thread. The
+    /// project only moves to `path`, and its journal only restarts, once
+    /// `finish_save` sees the write succeed.
+    fn start_save(&mut self, path: PathBuf) {
+        if self.pending_save.is_some() {
+            self.status_message = self.i18n.tr("status-save-in-progress");
+            return;
+        }
+        let snapshot = self.project.save_snapshot();
+        let timeline = snapshot.project.timeline.clone();
+        let library = snapshot.project.source_library.clone();
+        let (tx, rx) = mpsc::channel();
+        let thread_path = path.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(write_project(&snapshot, &thread_path));
+        });
+        self.status_message = self.i18n.tr_args("status-saving", &[("path", &path.display())]);
+        self.pending_save = Some(PendingSave { path, timeline, library, rx });
+    }
+
+    /// Report the outcome of the background save once it has finished.
+    fn poll_pending_save(&mut self) {
+        let Some(save) = self.pending_save.as_ref() else {
+            return;
+        };
+        let result = match save.rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => Err("save thread exited".to_string()),
+        };
+        if let Some(save) = self.pending_save.take() {
+            self.finish_save(save.path, &save.timeline, &save.library, result);
         }
     }
 
-    /// Generate thumbnail tasks for all assets in the source library.
-    fn regenerate_all_thumbnails(&self) -> Task<Message> {
-        let tasks: Vec<Task<Message>> = self
-            .project
-            .source_library
-            .assets()
-            .iter()
-            .map(|asset| {
-                let asset_id = asset.id;
-                let path = asset.path.clone();
+    /// Apply a finished save of `saved` and `saved_library` to `path`. Only
+    /// a successful write makes `path` the project file and restarts the
+    /// journal; timeline and library edits made while it was written are
+    /// journaled again, since the file lacks them.
+    pub(crate) fn finish_save(
+        &mut self,
+        path: PathBuf,
+        saved: &Timeline,
+        saved_library: &SourceLibrary,
+        result: Result<(), String>,
+    ) {
+        self.pending_save = None;
+        match result {
+            Ok(()) => {
+                self.status_message = self.i18n.tr_args("status-saved", &[("path", &path.display())]);
+                self.project_path = Some(path);
+                self.reset_journal();
+                if let Some(journal) = self.project.command_history.journal() {
+                    let description = self.project.command_history.undo_description().unwrap_or("Edit");
+                    let entries = journal::catch_up(
+                        description,
+                        saved,
+                        saved_library,
+                        &self.project.timeline,
+                        &self.project.source_library,
+                    );
+                    for entry in &entries {
+                        journal.append_entry(entry).ok();
+                    }
+                }
+            }
+            Err(e) => {
+                self.status_message = self.i18n.tr_args("status-save-failed", &[("error", &e)]);
+            }
+        }
+    }
+
+    pub fn is_saving(&self) -> bool {
+        self.pending_save.is_some()
+    }
+
+    /// Edits since the last save: journaled ones for a saved project, any
+    /// undoable ones for an untitled one.
+    pub fn has_unsaved_changes(&self) -> bool {
+        match &self.project_path {
+            Some(path) => CommandJournal::for_project(path)
+                .read_entries()
+                .is_ok_and(|entries| !entries.is_empty()),
+            None => self.project.command_history.can_undo(),
+        }
+    }
+
+    /// Start a new project, pick one to load, or exit, without asking about
+    /// unsaved changes.
+    fn leave_project(&mut self, action: MenuAction) -> Task<Message> {
+        match action {
+            MenuAction::NewProject => self.update(Message::NewProject),
+            MenuAction::LoadProject => {
+                self.status_message = self.i18n.tr("status-opening-load-dialog");
                 Task::perform(
-                    async move {
-                        let result =
-                            zeditor_media::thumbnail::generate_thumbnail_rgba_scaled(
-                                &path, 160, 160,
-                            )
-                            .map(|frame| (frame.data, frame.width, frame.height))
-                            .map_err(|e| format!("{e}"));
-                        (asset_id, result)
+                    async {
+                        let handle = rfd::AsyncFileDialog::new()
+                            .add_filter("Zeditor Project", &["zpf"])
+                            .set_title("Load Project")
+                            .pick_file()
+                            .await;
+                        handle.map(|f| f.path().to_path_buf())
                     },
-                    |(asset_id, result)| Message::ThumbnailGenerated { asset_id, result },
+                    Message::LoadFileDialogResult,
                 )
-            })
+            }
+            MenuAction::Exit => self.update(Message::Exit),
+            _ => Task::none(),
+        }
+    }
+
+    fn replay_journal(&mut self) -> Task<Message> {
+        let Some(path) = self.project_path.clone() else {
+            return Task::none();
+        };
+        let journal = CommandJournal::for_project(&path);
+        let assets_before = self.asset_ids();
+        let result = journal.read_entries().and_then(|entries| {
+            // Cut off whatever a crash left after the last readable entry,
+            // or nothing appended from here on could be read back
+            journal.rewrite(&entries)?;
+            let project = &mut self.project;
+            journal::replay(
+                &mut project.command_history,
+                &mut project.timeline,
+                &mut project.source_library,
+                entries,
+            )
+        });
+        // Keep journaling onto the same file: saved project + journal
+        // still reconstructs the current state.
+        self.project.command_history.set_journal(Some(journal));
+        match result {
+            Ok(n) => {
+                self.status_message = self.i18n.tr_args("status-journal-recovered", &[("count", &n)]);
+            }
+            Err(e) => {
+                self.status_message = self.i18n.tr_args("status-journal-replay-failed", &[("error", &e)]);
+            }
+        }
+        self.sync_library_view(&assets_before)
+    }
+
+    fn delete_track(&mut self, track_index: usize) -> Task<Message> {
+        let result = self.project.command_history.execute(
+            &mut self.project.timeline,
+            "Delete track",
+            |tl| tl.remove_track(track_index).map(|_| ()),
+        );
+        match result {
+            Ok(()) => {
+                self.status_message = self.i18n.tr("status-track-deleted");
+                // Indices past the deleted track have shifted
+                self.selected_clip = None;
+                self.selected_clips.clear();
+                self.send_decode_seek(false);
+            }
+            Err(e) => self.status_message = self.i18n.tr_args("status-delete-track-failed", &[("error", &e)]),
+        }
+        Task::none()
+    }
+
+    /// Attach a fresh crash-recovery journal for the current project path.
+    /// Any stale journal on disk is discarded, since the project file is now authoritative.
+    fn reset_journal(&mut self) {
+        let journal = self.project_path.as_deref().map(CommandJournal::for_project);
+        if let Some(journal) = &journal {
+            journal.clear().ok();
+        }
+        self.project.command_history.set_journal(journal);
+    }
+
+    /// Furthest the track lanes can scroll: the last track at the top.
+    fn max_vertical_scroll(&self) -> f32 {
+        let last = self.project.timeline.tracks.len().saturating_sub(1);
+        timeline_canvas::track_offset(&self.project.timeline, last)
+    }
+
+    /// Vertical scroll clamped to the current track count (tracks may have been removed).
+    pub fn effective_vertical_scroll(&self) -> f32 {
+        self.timeline_vertical_scroll.min(self.max_vertical_scroll())
+    }
+
+    /// Width of the visible timeline canvas: the window minus the outer padding,
+    /// inspector, splitter and track headers (mirrors the layout in `view`).
+    pub fn timeline_viewport_width(&self) -> f32 {
+        // Layout sizes are in scaled units, the window size is not
+        let window_width = self.window_size.width / self.preferences.ui_scale;
+        let chrome = 8.0 + 2.0 * 4.0 + 4.0 + self.preferences.layout.inspector_width;
+        let header_width = track_header::header_width(&self.project.timeline);
+        (window_width - chrome - header_width).max(100.0)
+    }
+
+    /// Scale factor for the whole UI, from the user's preferences.
+    pub fn scale_factor(&self) -> f64 {
+        self.preferences.ui_scale as f64
+    }
+
+    /// Set the zoom, scrolling so the playhead stays at the same screen x.
+    fn zoom_around_playhead(&mut self, zoom: f32) {
+        let playhead_secs = self.playback_position.as_secs_f64() as f32;
+        let playhead_px = playhead_secs * self.timeline_zoom - self.timeline_scroll;
+        self.timeline_zoom = zoom.clamp(MIN_TIMELINE_ZOOM, MAX_TIMELINE_ZOOM);
+        self.timeline_scroll = (playhead_secs * self.timeline_zoom - playhead_px).max(0.0);
+    }
+
+    /// Effects matching the browser search, grouped by category in display order.
+    /// Categories with no matches are left out.
+    pub fn visible_effects(&self) -> Vec<(EffectCategory, Vec<EffectType>)> {
+        let matching: Vec<EffectType> = EffectType::all_builtin()
+            .into_iter()
+            .filter(|e| e.matches_search(&self.effect_search))
             .collect();
-        Task::batch(tasks)
+        EffectCategory::all()
+            .into_iter()
+            .map(|category| {
+                let effects: Vec<EffectType> = matching
+                    .iter()
+                    .copied()
+                    .filter(|e| e.category() == category)
+                    .collect();
+                (category, effects)
+            })
+            .filter(|(_, effects)| !effects.is_empty())
+            .collect()
+    }
+
+    /// Media cache location: the user's chosen directory, else the platform default.
+    fn media_cache_dir(&self) -> Option<PathBuf> {
+        self.preferences.cache_dir.clone().or_else(MediaCache::default_dir)
+    }
+
+    /// Place `(asset, has_audio, source_range)` items back-to-back on a track
+    /// from `position`, with linked audio on the paired audio track where the
+    /// asset has sound, as one undo step.
+    fn add_clip_sequence(
+        &mut self,
+        description: &str,
+        items: &[(Uuid, bool, TimeRange)],
+        track_index: usize,
+        position: TimelinePosition,
+    ) -> Result<(), zeditor_core::error::CoreError> {
+        let audio_track = self.project.timeline.find_paired_audio_track(track_index);
+        self.project.command_history.execute(&mut self.project.timeline, description, |tl| {
+            let mut pos = position;
+            for (asset_id, has_audio, source_range) in items {
+                match audio_track.filter(|_| *has_audio) {
+                    Some(audio_track) => {
+                        tl.add_clip_with_audio(track_index, audio_track, *asset_id, pos, *source_range)?;
+                    }
+                    None => {
+                        let clip = Clip::new(*asset_id, pos, *source_range);
+                        tl.add_clip_trimming_overlaps(track_index, clip)?;
+                    }
+                }
+                pos = TimelinePosition::from_secs_f64(pos.as_secs_f64() + source_range.duration().as_secs_f64());
+            }
+            Ok(())
+        })
+    }
+
+    /// (Re)load the display LUT named in the preferences. A file that can't be
+    /// read or parsed leaves the preview untransformed and warns.
+    fn load_display_lut(&mut self) {
+        self.display_lut = None;
+        let Some(path) = self.preferences.display_lut.clone() else {
+            return;
+        };
+        match CubeLut::load(&path) {
+            Ok(lut) => self.display_lut = Some(Arc::new(lut)),
+            Err(e) => {
+                let path = path.display().to_string();
+                self.notify(
+                    NotificationLevel::Warning,
+                    self.i18n.tr_args("notify-display-lut-failed", &[("path", &path), ("error", &e.to_string())]),
+                );
+            }
+        }
+    }
+
+    /// Persist UI preferences to the user config, if a config path is known.
+    fn save_preferences(&mut self) {
+        let Some(path) = &self.preferences_path else {
+            return;
+        };
+        if let Err(e) = self.preferences.save(path) {
+            self.notify(NotificationLevel::Warning, self.i18n.tr_args("notify-preferences-save-failed", &[("error", &e)]));
+        }
+    }
+
+    /// Show `message` in the status bar and record it in the notification center.
+    pub fn notify(&mut self, level: NotificationLevel, message: String) {
+        self.notifications.push(level, message.clone(), Instant::now());
+        self.status_message = message;
+    }
+
+    /// Turn errors reported by the decode workers into notifications.
+    fn poll_decode_errors(&mut self) {
+        let Some(rx) = &self.decode_error_rx else {
+            return;
+        };
+        let errors: Vec<String> = rx.try_iter().collect();
+        for error in errors {
+            self.notify(NotificationLevel::Error, self.i18n.tr_args("notify-decode-failed", &[("error", &error)]));
+        }
+    }
+
+    /// Place `source_range` of an asset on track `track_index` at `position`
+    /// as one undo step, with its audio on the paired audio track if any.
+    fn add_source_clip(
+        &mut self,
+        asset_id: Uuid,
+        source_range: TimeRange,
+        track_index: usize,
+        position: TimelinePosition,
+    ) {
+        let has_audio = self.project.source_library.get(asset_id).is_some_and(|a| a.has_audio);
+        let audio_track = self.project.timeline.find_paired_audio_track(track_index);
+
+        if has_audio && audio_track.is_some() {
+            let audio_track = audio_track.unwrap();
+            let result = self.project.command_history.execute(
+                &mut self.project.timeline,
+                "Add clip",
+                |tl| {
+                    tl.add_clip_with_audio(track_index, audio_track, asset_id, position, source_range)
+                },
+            );
+            match result {
+                Ok(_) => {
+                    self.status_message = self.i18n.tr("status-clip-added");
+                }
+                Err(e) => {
+                    self.status_message = self.i18n.tr_args("status-add-clip-failed", &[("error", &e)]);
+                }
+            }
+        } else {
+            let clip = Clip::new(asset_id, position, source_range);
+            let result = self.project.command_history.execute(
+                &mut self.project.timeline,
+                "Add clip",
+                |tl| tl.add_clip_trimming_overlaps(track_index, clip),
+            );
+            match result {
+                Ok(()) => {
+                    self.status_message = self.i18n.tr("status-clip-added");
+                }
+                Err(e) => {
+                    self.status_message = self.i18n.tr_args("status-add-clip-failed", &[("error", &e)]);
+                }
+            }
+        }
+    }
+
+    /// Background task decoding, in scrub mode, the frames that meet at the
+    /// trim preview's edge.
+    fn trim_frames_task(&self, edge_secs: f64) -> Task<Message> {
+        let Some(two_up) = &self.trim_two_up else {
+            return Task::none();
+        };
+        let clip_id = two_up.clip_id;
+        let frames = trim_preview::trim_frames(
+            &self.project.timeline,
+            &self.project.source_library,
+            two_up.track_index,
+            clip_id,
+            edge_secs,
+            self.project.settings.fps,
+        );
+        let (outgoing, incoming) = frames.map(|f| (f.outgoing, f.incoming)).unwrap_or_default();
+        Task::perform(
+            async move {
+                let decode = |request: Option<TrimFrameRequest>| {
+                    let request = request?;
+                    let frame = zeditor_media::thumbnail::frame_rgba_scaled_at(
+                        &request.path,
+                        request.source_secs,
+                        trim_preview::TRIM_FRAME_MAX_WIDTH,
+                        trim_preview::TRIM_FRAME_MAX_HEIGHT,
+                    )
+                    .ok()?;
+                    Some(iced::widget::image::Handle::from_rgba(frame.width, frame.height, frame.data))
+                };
+                (decode(outgoing), decode(incoming))
+            },
+            move |(outgoing, incoming)| Message::TrimFramesDecoded { clip_id, outgoing, incoming },
+        )
     }
 
     pub fn boot() -> (Self, Task<Message>) {
+        let preferences_path = UiPreferences::default_path();
+        let preferences = preferences_path.as_deref().map(UiPreferences::load).unwrap_or_default();
+        let memory = MemoryBudget::new(preferences.memory_caps);
+
         let (req_tx, req_rx) = mpsc::channel::<DecodeRequest>();
-        let (frame_tx, frame_rx) = mpsc::sync_channel::<DecodedFrame>(1);
+        let (frame_tx, frame_rx) = mpsc::sync_channel::<DecodedFrame>(DECODE_AHEAD_FRAMES);
+        let (decode_error_tx, decode_error_rx) = mpsc::channel::<String>();
+
+        let error_tx = decode_error_tx.clone();
+        let worker_memory = memory.clone();
+        let decode_generation = Arc::new(AtomicU64::new(0));
+        let worker_generation = decode_generation.clone();
+        std::thread::spawn(move || {
+            decode_worker(req_rx, frame_tx, error_tx, worker_memory, worker_generation);
+        });
+
+        // Source monitor decode thread
+        let (source_req_tx, source_req_rx) = mpsc::channel::<DecodeRequest>();
+        let (source_frame_tx, source_frame_rx) = mpsc::sync_channel::<DecodedFrame>(1);
+
+        let worker_memory = memory.clone();
+        let source_decode_generation = Arc::new(AtomicU64::new(0));
+        let worker_generation = source_decode_generation.clone();
+        std::thread::spawn(move || {
+            decode_worker(source_req_rx, source_frame_tx, decode_error_tx, worker_memory, worker_generation);
+        });
 
+        // Pre-render thread for effect-heavy regions
+        let (prerender_req_tx, prerender_req_rx) = mpsc::channel::<PrerenderRequest>();
+        let (prerender_frame_tx, prerender_frame_rx) =
+            mpsc::sync_channel::<(PreviewCacheKey, Option<DecodedFrame>)>(DECODE_AHEAD_FRAMES);
+        let worker_memory = memory.clone();
         std::thread::spawn(move || {
-            decode_worker(req_rx, frame_tx);
+            prerender_worker(prerender_req_rx, prerender_frame_tx, worker_memory);
         });
 
         // Audio decode thread
         let (audio_req_tx, audio_req_rx) = mpsc::channel::<AudioDecodeRequest>();
-        let (audio_frame_tx, audio_frame_rx) = mpsc::sync_channel::<DecodedAudio>(16);
-
+        let (audio_levels_tx, audio_levels_rx) = mpsc::channel::<AudioLevels>();
+        let audio_ring = Arc::new(AudioRing::new(AUDIO_RING_SAMPLES));
+        let worker_ring = Arc::clone(&audio_ring);
         std::thread::spawn(move || {
-            audio_decode_worker(audio_req_rx, audio_frame_tx);
+            audio_decode_worker(audio_req_rx, worker_ring, audio_levels_tx);
         });
 
         let mut app = Self::default();
         app.decode_tx = Some(req_tx);
         app.decode_rx = Some(frame_rx);
+        app.decode_generation = decode_generation;
+        app.source_decode_generation = source_decode_generation;
+        app.source_decode_tx = Some(source_req_tx);
+        app.source_decode_rx = Some(source_frame_rx);
+        app.prerender_tx = Some(prerender_req_tx);
+        app.prerender_rx = Some(prerender_frame_rx);
         app.audio_decode_tx = Some(audio_req_tx);
-        app.audio_decode_rx = Some(audio_frame_rx);
-        app.audio_player = AudioPlayer::new();
+        app.audio_player = AudioPlayer::new(Arc::clone(&audio_ring));
+        app.audio_ring = audio_ring;
+        app.audio_levels_rx = Some(audio_levels_rx);
+        app.decode_error_rx = Some(decode_error_rx);
+        app.preferences_path = preferences_path;
+        app.preferences = preferences;
+        app.memory = memory;
+        app.media_cache = app.media_cache_dir().map(MediaCache::new);
+        app.project.settings = app.preferences.new_project.clone();
+        app.i18n = I18n::new(app.preferences.language);
+        app.load_display_lut();
+        if app.preferences.background_render {
+            app.prune_background_render();
+        }
         (app, Task::none())
     }
 
     pub fn subscription(&self) -> Subscription<Message> {
-        let mut subs: Vec<Subscription<Message>> =
-            vec![keyboard::listen().map(Message::KeyboardEvent)];
+        let mut subs: Vec<Subscription<Message>> = vec![
+            keyboard::listen().map(Message::KeyboardEvent),
+            window::resize_events().map(|(_id, size)| Message::WindowResized(size)),
+        ];
 
         // Always tick: 16ms when playing (60fps), 100ms when paused (for scrub frames)
-        let tick_ms = if self.is_playing { 16 } else { 100 };
+        let tick_ms = if self.is_playing || self.source_monitor.is_playing { 16 } else { 100 };
         subs.push(time::every(Duration::from_millis(tick_ms)).map(|_| Message::PlaybackTick));
 
+        if self.preferences.autosave_minutes > 0 && self.project_path.is_some() {
+            let interval = Duration::from_secs(u64::from(self.preferences.autosave_minutes) * 60);
+            subs.push(time::every(interval).map(|_| Message::AutosaveTick));
+        }
+
         // Global mouse tracking during drag
         if self.drag_state.is_some() {
             subs.push(event::listen_with(drag_event_filter));
         }
+        if self.splitter_drag.is_some() {
+            subs.push(event::listen_with(splitter_event_filter));
+        }
 
         Subscription::batch(subs)
     }
 
     pub fn update(&mut self, message: Message) -> Task<Message> {
         match message {
-            Message::OpenFileDialog => {
-                self.status_message = "Opening file dialog...".into();
-                Task::perform(
-                    async {
-                        let handle = rfd::AsyncFileDialog::new()
-                            .add_filter("Video", &["mp4", "mov", "avi", "mkv", "webm"])
-                            .set_title("Import Media")
-                            .pick_files()
-                            .await;
-                        match handle {
-                            Some(files) => {
-                                files.into_iter().map(|f| f.path().to_path_buf()).collect()
-                            }
-                            None => Vec::new(),
-                        }
-                    },
-                    Message::FileDialogResult,
-                )
+            message @ (Message::OpenFileDialog
+            | Message::FileDialogResult(..)
+            | Message::ImportMedia(..)
+            | Message::MediaImported(..)
+            | Message::ImportFolder
+            | Message::ImportFolderChosen(..)
+            | Message::FolderScanned { .. }
+            | Message::FolderMediaImported { .. }
+            | Message::ImportHashed { .. }
+            | Message::ThumbnailGenerated { .. }
+            | Message::ReplaceSelectedMedia
+            | Message::ReplaceMediaFileChosen { .. }
+            | Message::ReplacementProbed { .. }) => self.update_import(message),
+            message @ (Message::RemoveAsset(..)
+            | Message::ConfirmRemoveAsset(..)
+            | Message::SelectSourceAsset(..)
+            | Message::SourceCardHovered(..)
+            | Message::RevealAssetInTimeline(..)
+            | Message::LibrarySearchChanged(..)
+            | Message::ToggleLibraryKindFilter(..)
+            | Message::SetLibraryBinFilter(..)
+            | Message::SetLibraryUsageFilter(..)
+            | Message::ClearLibraryFilters
+            | Message::SetLibrarySort(..)
+            | Message::SetLibraryViewMode(..)
+            | Message::ToggleAssetInfo
+            | Message::AssetDetailsLoaded { .. }
+            | Message::ShotsDetected { .. }) => self.update_library(message),
+            message @ (Message::OpenInSourceMonitor(..)
+            | Message::OpenShotInSourceMonitor { .. }
+            | Message::OpenSubClipInSourceMonitor(..)
+            | Message::SubClipNameChanged(..)
+            | Message::SaveSubClip
+            | Message::RemoveSubClip { .. }
+            | Message::CloseSourceMonitor
+            | Message::SourceMonitorTogglePlay
+            | Message::SourceMonitorSeek(..)
+            | Message::SourceMonitorMarkIn
+            | Message::SourceMonitorMarkOut
+            | Message::SourceMonitorClearMarks) => self.update_source_monitor(message),
+            message @ (Message::TranscribeSelectedClip
+            | Message::TranscriptReady { .. }
+            | Message::SelectTranscriptWord(..)
+            | Message::CutTranscriptSelection
+            | Message::ExportTranscriptSrt
+            | Message::SrtFileDialogResult(..)) => self.update_transcript(message),
+            message @ (Message::ExportTimelineDescription
+            | Message::DescriptionFileDialogResult(..)
+            | Message::ToggleExportStems
+            | Message::RenderFileDialogResult(..)
+            | Message::RenderComplete(..)
+            | Message::RenderError(..)
+            | Message::SetRenderRangeIn
+            | Message::SetRenderRangeOut
+            | Message::RenderRangeNameChanged(..)
+            | Message::AddRenderRange
+            | Message::DeleteRenderRange(..)
+            | Message::RenderFilePatternChanged(..)
+            | Message::ExportRenderRanges
+            | Message::ExportRenderRangeLoop(..)
+            | Message::RenderRangeLoopFileResult { .. }
+            | Message::RenderRangesFolderResult(..)) => self.update_export(message),
+            message @ (Message::TogglePrerenderSelectedClip
+            | Message::ToggleBackgroundRender) => self.update_prerender(message),
+            message @ (Message::ConfirmDialogAccepted
+            | Message::ConfirmDialogTextChanged(..)
+            | Message::ConfirmDialogChoice(..)
+            | Message::ConfirmDialogDismissed
+            | Message::ShowProjectVersions
+            | Message::RestoreProjectVersion(..)
+            | Message::ProjectVersionsDismissed
+            | Message::OpenPathRemap
+            | Message::PathRemapFromChanged(..)
+            | Message::PathRemapToChanged(..)
+            | Message::ApplyPathRemap
+            | Message::PathRemapDismissed) => self.update_dialogs(message),
+            message @ (Message::OpenSlideshow
+            | Message::SlideshowDurationChanged(..)
+            | Message::SlideshowShuffleToggled(..)
+            | Message::CreateSlideshow
+            | Message::SlideshowDismissed) => self.update_slideshow(message),
+            message @ (Message::OpenPreferences
+            | Message::PreferenceFieldChanged(..)
+            | Message::SetPreviewQuality(..)
+            | Message::PreferencesCanvasPreset(..)
+            | Message::ApplyPreferences
+            | Message::PreferencesDismissed) => self.update_preferences(message),
+            Message::SelectTimelineClip(selection) => {
+                self.selected_clip = selection;
+                self.selected_clips.clear();
+                Task::none()
             }
-            Message::FileDialogResult(paths) => {
-                if paths.is_empty() {
-                    self.status_message = "Import cancelled".into();
-                    return Task::none();
+            Message::ToggleClipSelection(clip) => {
+                if self.selected_clip.is_none() {
+                    self.selected_clip = Some(clip);
+                } else if self.selected_clip == Some(clip) {
+                    // Promote the next extra clip to primary selection
+                    self.selected_clip = (!self.selected_clips.is_empty())
+                        .then(|| self.selected_clips.remove(0));
+                } else if let Some(pos) = self.selected_clips.iter().position(|c| *c == clip) {
+                    self.selected_clips.remove(pos);
+                } else {
+                    self.selected_clips.push(clip);
                 }
-                let tasks: Vec<Task<Message>> = paths
-                    .into_iter()
-                    .map(|path| {
-                        Task::perform(
-                            async move {
-                                zeditor_media::probe::probe(&path)
-                                    .map_err(|e| format!("{e}"))
-                            },
-                            Message::MediaImported,
-                        )
-                    })
-                    .collect();
-                Task::batch(tasks)
+                Task::none()
+            }
+            Message::GroupSelectedClips => {
+                let clips = self.clip_selection();
+                let result = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Group clips",
+                    |tl| tl.group_clips(&clips),
+                );
+                match result {
+                    Ok(_) => {
+                        let count = clips.len().to_string();
+                        self.status_message = self.i18n.tr_args("status-clips-grouped", &[("count", &count)]);
+                    }
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-group-failed", &[("error", &e)]);
+                    }
+                }
+                Task::none()
             }
-            Message::ImportMedia(path) => {
-                self.status_message = "Importing...".into();
+            Message::SynchronizeSelectedClips => {
+                let Some((reference, other)) = self.sync_pair() else {
+                    self.status_message = self.i18n.tr("status-sync-needs-two-clips");
+                    return Task::none();
+                };
+                let source = |(track_index, clip_id): (usize, Uuid)| {
+                    let clip = self.project.timeline.track(track_index).ok()?.get_clip(clip_id)?;
+                    let asset = self.project.source_library.get(clip.asset_id)?;
+                    let range = (clip.source_range.start.as_secs_f64(), clip.duration().as_secs_f64());
+                    Some((asset.path.clone(), range))
+                };
+                let (Some((reference_path, reference_range)), Some((other_path, other_range))) =
+                    (source(reference), source(other))
+                else {
+                    self.status_message = self.i18n.tr("status-asset-not-found");
+                    return Task::none();
+                };
+                self.status_message = self.i18n.tr("status-sync-started");
                 Task::perform(
                     async move {
-                        zeditor_media::probe::probe(&path).map_err(|e| format!("{e}"))
+                        zeditor_media::audio_sync::sync_offset(&reference_path, reference_range, &other_path, other_range)
+                            .map(|found| found.offset_secs)
+                            .map_err(|e| format!("{e}"))
                     },
-                    Message::MediaImported,
+                    move |result| Message::ClipSyncAnalyzed { reference, other, result },
                 )
             }
-            Message::MediaImported(result) => {
+            Message::ClipSyncAnalyzed { reference, other, result } => {
+                let result = result.and_then(|offset_secs| {
+                    self.project
+                        .command_history
+                        .execute(&mut self.project.timeline, "Synchronize clips", |tl| {
+                            tl.sync_clip(reference, other, offset_secs)
+                        })
+                        .map(|_| offset_secs)
+                        .map_err(|e| format!("{e}"))
+                });
                 match result {
-                    Ok(asset) => {
-                        self.status_message = format!("Imported: {}", asset.name);
-                        let asset_id = asset.id;
-                        let path = asset.path.clone();
-                        self.project.source_library.import(asset);
-                        // Spawn thumbnail generation in the background
-                        return Task::perform(
-                            async move {
-                                let result = zeditor_media::thumbnail::generate_thumbnail_rgba_scaled(
-                                    &path, 160, 160,
-                                )
-                                .map(|frame| (frame.data, frame.width, frame.height))
-                                .map_err(|e| format!("{e}"));
-                                (asset_id, result)
-                            },
-                            |(asset_id, result)| Message::ThumbnailGenerated { asset_id, result },
-                        );
+                    Ok(offset_secs) => {
+                        let offset = format!("{offset_secs:+.2}");
+                        self.status_message = self.i18n.tr_args("status-clips-synced", &[("offset", &offset)]);
                     }
                     Err(e) => {
-                        self.status_message = format!("Import failed: {e}");
+                        self.status_message = self.i18n.tr_args("status-sync-failed", &[("error", &e)]);
                     }
                 }
                 Task::none()
             }
-            Message::ThumbnailGenerated { asset_id, result } => {
-                if let Ok((data, width, height)) = result {
-                    let handle = iced::widget::image::Handle::from_rgba(width, height, data);
-                    self.thumbnails.insert(asset_id, handle);
-                }
-                Task::none()
-            }
-            Message::RemoveAsset(id) => {
-                // Remove all clips using this asset (via command history for undo)
-                let clips_using = self.project.timeline.clips_using_asset(id);
-                if !clips_using.is_empty() {
-                    self.project.command_history.execute(
-                        &mut self.project.timeline,
-                        "Remove clips for asset",
-                        |tl| { tl.remove_clips_by_asset(id); Ok(()) },
-                    ).ok();
-                }
-                match self.project.source_library.remove(id) {
-                    Ok(asset) => {
-                        self.status_message = format!("Removed: {}", asset.name);
-                        self.thumbnails.remove(&id);
-                        if self.selected_asset_id == Some(id) {
-                            self.selected_asset_id = None;
-                        }
+            Message::SplitSelectedDualMono => {
+                let Some((track_index, clip_id)) = self.selected_clip else {
+                    self.status_message = self.i18n.tr("status-dual-mono-needs-clip");
+                    return Task::none();
+                };
+                let result = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Split to dual mono",
+                    |tl| tl.split_dual_mono(track_index, clip_id),
+                );
+                match result {
+                    Ok((right_track, _)) => {
+                        let track = self.project.timeline.tracks[right_track].name.clone();
+                        self.status_message = self.i18n.tr_args("status-dual-mono-done", &[("track", &track)]);
                     }
                     Err(e) => {
-                        self.status_message = format!("Remove failed: {e}");
+                        self.status_message = self.i18n.tr_args("status-dual-mono-failed", &[("error", &e)]);
                     }
                 }
                 Task::none()
             }
-            Message::ConfirmRemoveAsset(asset_id) => {
-                let clips_using = self.project.timeline.clips_using_asset(asset_id);
-                if clips_using.is_empty() {
-                    // No clips in use — remove directly
-                    return self.update(Message::RemoveAsset(asset_id));
-                }
-                // Clips in use — show confirmation dialog
-                let count = clips_using.len();
-                self.confirm_dialog = Some(ConfirmDialog {
-                    message: format!(
-                        "This asset is used by {count} clip(s) in the timeline. Delete the asset and all its clips?"
-                    ),
-                    action: ConfirmAction::RemoveAsset { asset_id },
+            Message::FitToFill => {
+                let source = self.source_monitor.asset_id.and_then(|asset_id| {
+                    let asset = self.project.source_library.get(asset_id)?;
+                    Some((asset_id, asset.kind(), asset.has_audio, self.source_monitor.marked_range()?))
                 });
-                Task::none()
-            }
-            Message::ConfirmDialogAccepted => {
-                if let Some(dialog) = self.confirm_dialog.take() {
-                    match dialog.action {
-                        ConfirmAction::RemoveAsset { asset_id } => {
-                            return self.update(Message::RemoveAsset(asset_id));
-                        }
+                let Some((asset_id, kind, has_audio, source_range)) = source else {
+                    self.status_message = self.i18n.tr("status-fit-to-fill-needs-source");
+                    return Task::none();
+                };
+                // The gap under the playhead, on the lowest track that has one
+                let timeline = &self.project.timeline;
+                let candidates = if kind == MediaKind::Audio {
+                    timeline.audio_track_indices()
+                } else {
+                    timeline.video_track_indices().into_iter().rev().collect()
+                };
+                let target = candidates
+                    .into_iter()
+                    .find_map(|i| timeline.tracks[i].gap_at(self.playback_position).map(|gap| (i, gap)));
+                let Some((track_index, gap)) = target else {
+                    self.status_message = self.i18n.tr("status-fit-to-fill-no-gap");
+                    return Task::none();
+                };
+                let audio_track = if kind == MediaKind::Video && has_audio {
+                    timeline.mirror_audio_track_for_video(track_index)
+                } else {
+                    None
+                };
+                let result = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Fit to fill",
+                    |tl| tl.fit_to_fill(track_index, audio_track, asset_id, source_range, gap),
+                );
+                match result {
+                    Ok(clip_id) => {
+                        self.selected_clip = Some((track_index, clip_id));
+                        self.selected_clips.clear();
+                        let speed = self
+                            .project
+                            .timeline
+                            .track(track_index)
+                            .ok()
+                            .and_then(|t| t.get_clip(clip_id))
+                            .map_or(100.0, |c| c.speed() * 100.0);
+                        let speed = format!("{speed:.0}");
+                        self.status_message = self.i18n.tr_args("status-fit-to-fill-done", &[("speed", &speed)]);
+                    }
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-fit-to-fill-failed", &[("error", &e)]);
                     }
                 }
                 Task::none()
             }
-            Message::ConfirmDialogDismissed => {
-                self.confirm_dialog = None;
-                Task::none()
-            }
-            Message::SelectTimelineClip(selection) => {
-                self.selected_clip = selection;
-                Task::none()
+            Message::AutoReframeSelectedClip => {
+                let (canvas_w, canvas_h) = (self.project.settings.canvas_width, self.project.settings.canvas_height);
+                let source = self.selected_clip.and_then(|(track_index, clip_id)| {
+                    let track = self.project.timeline.track(track_index).ok()?;
+                    if track.track_type != TrackType::Video {
+                        return None;
+                    }
+                    let clip = track.get_clip(clip_id)?;
+                    let asset = self.project.source_library.get(clip.asset_id).filter(|a| a.kind() == MediaKind::Video)?;
+                    let visible = reframe::visible_width_fraction(asset.display_width(), asset.display_height(), canvas_w, canvas_h);
+                    Some((track_index, clip_id, clip.source_range, asset.path.clone(), visible))
+                });
+                let Some((track_index, clip_id, range, path, visible)) = source else {
+                    self.status_message = self.i18n.tr("status-reframe-need-clip");
+                    return Task::none();
+                };
+                // Filling a canvas at least as wide as the frame crops nothing sideways
+                if visible >= 0.99 {
+                    self.status_message = self.i18n.tr("status-reframe-fits");
+                    return Task::none();
+                }
+                self.status_message = self.i18n.tr("status-reframe-started");
+                Task::perform(
+                    async move {
+                        zeditor_media::reframe::analyze_pan(&path, range.start.as_secs_f64(), range.end.as_secs_f64(), visible)
+                            .map_err(|e| format!("{e}"))
+                    },
+                    move |result| Message::AutoReframeAnalyzed { track_index, clip_id, result },
+                )
             }
-            Message::RemoveClip {
-                track_index,
-                clip_id,
-            } => {
+            Message::AutoReframeAnalyzed { track_index, clip_id, result } => {
+                let pan = match result {
+                    Ok(pan) => pan,
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-reframe-failed", &[("error", &e)]);
+                        return Task::none();
+                    }
+                };
+                let count = pan.keys.len();
+                // The pan only moves a crop, so the clip fills the canvas
                 let result = self.project.command_history.execute(
                     &mut self.project.timeline,
-                    "Remove clip",
-                    |tl| tl.remove_clip_grouped(track_index, clip_id),
+                    "Auto reframe",
+                    |tl| {
+                        tl.set_canvas_fit(track_index, clip_id, CanvasFit::Fill)?;
+                        tl.set_pan(track_index, clip_id, pan)
+                    },
                 );
                 match result {
                     Ok(()) => {
-                        self.status_message = "Clip removed".into();
-                        self.selected_clip = None;
+                        self.status_message = self.i18n.tr_args("status-reframe-done", &[("count", &count)]);
+                        self.send_decode_seek(self.is_playing);
                     }
                     Err(e) => {
-                        self.status_message = format!("Remove clip failed: {e}");
+                        self.status_message = self.i18n.tr_args("status-reframe-failed", &[("error", &e)]);
                     }
                 }
                 Task::none()
             }
-            Message::SelectSourceAsset(id) => {
-                self.selected_asset_id = id;
+            Message::ClearClipReframe { track_index, clip_id } => {
+                let result = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Clear reframe",
+                    |tl| tl.set_pan(track_index, clip_id, PanPath::default()),
+                );
+                match result {
+                    Ok(()) => self.send_decode_seek(self.is_playing),
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-reframe-failed", &[("error", &e)]);
+                    }
+                }
                 Task::none()
             }
-            Message::SourceCardHovered(id) => {
-                self.hovered_asset_id = id;
-                Task::none()
+            Message::DetectBeatsSelectedClip => {
+                let source = self
+                    .selected_clip
+                    .and_then(|(track_index, clip_id)| {
+                        let clip = self.project.timeline.track(track_index).ok()?.get_clip(clip_id)?;
+                        let asset = self.project.source_library.get(clip.asset_id).filter(|a| a.has_audio)?;
+                        Some((track_index, clip_id, clip.source_range, asset.path.clone()))
+                    });
+                let Some((track_index, clip_id, range, path)) = source else {
+                    self.status_message = self.i18n.tr("status-beats-need-clip");
+                    return Task::none();
+                };
+                self.status_message = self.i18n.tr("status-beats-started");
+                Task::perform(
+                    async move {
+                        let start = range.start.as_secs_f64();
+                        zeditor_media::beats::detect_beats_in_file(&path, start, range.end.as_secs_f64() - start)
+                            .map_err(|e| format!("{e}"))
+                    },
+                    move |result| Message::BeatsDetected { track_index, clip_id, result },
+                )
+            }
+            Message::BeatsDetected { track_index, clip_id, result } => {
+                let analysis = match result {
+                    Ok(Some(analysis)) => analysis,
+                    Ok(None) => {
+                        self.status_message = self.i18n.tr("status-beats-none");
+                        return Task::none();
+                    }
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-beats-failed", &[("error", &e)]);
+                        return Task::none();
+                    }
+                };
+                let Some(clip) = self.project.timeline.track(track_index).ok().and_then(|t| t.get_clip(clip_id)) else {
+                    return Task::none();
+                };
+                // Source seconds to timeline positions, through the clip's trim and speed
+                let (start, source_start, speed) = (
+                    clip.timeline_range.start.as_secs_f64(),
+                    clip.source_range.start.as_secs_f64(),
+                    clip.speed(),
+                );
+                let positions: Vec<TimelinePosition> = analysis
+                    .beats
+                    .iter()
+                    .map(|beat| TimelinePosition::from_secs_f64(start + (beat - source_start).max(0.0) / speed))
+                    .filter(|&position| clip.timeline_range.contains(position))
+                    .collect();
+                let result = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Add beat guides",
+                    |tl| Ok(positions.into_iter().filter(|&position| tl.add_guide(position)).count()),
+                );
+                if let Ok(count) = result {
+                    let bpm = format!("{:.1}", analysis.bpm);
+                    self.status_message =
+                        self.i18n.tr_args("status-beats-done", &[("count", &count), ("bpm", &bpm)]);
+                }
+                Task::none()
+            }
+            Message::AddMarker => {
+                let position = self.playback_position;
+                let name = format!("Marker {}", self.project.timeline.markers.len() + 1);
+                let _ = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Add marker",
+                    |tl| Ok(tl.add_marker(position, name.clone())),
+                );
+                self.status_message = self.i18n.tr_args("status-marker-added", &[("name", &name)]);
+                Task::none()
+            }
+            Message::AddClipMarker => {
+                let Some((track_index, clip_id)) = self.selected_clip else {
+                    self.status_message = self.i18n.tr("status-clip-marker-needs-clip");
+                    return Task::none();
+                };
+                let position = self.playback_position;
+                let count = self
+                    .project
+                    .timeline
+                    .track(track_index)
+                    .ok()
+                    .and_then(|t| t.get_clip(clip_id))
+                    .map_or(0, |c| c.markers.len());
+                let name = format!("Marker {}", count + 1);
+                let result = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Add clip marker",
+                    |tl| tl.add_clip_marker(track_index, clip_id, position, name.clone()),
+                );
+                self.status_message = match result {
+                    Ok(_) => self.i18n.tr_args("status-marker-added", &[("name", &name)]),
+                    Err(e) => self.i18n.tr_args("status-clip-marker-failed", &[("error", &e)]),
+                };
+                Task::none()
+            }
+            Message::RemoveClipMarker { track_index, clip_id, marker_id } => {
+                let result = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Remove clip marker",
+                    |tl| tl.remove_clip_marker(track_index, clip_id, marker_id),
+                );
+                if let Err(e) = result {
+                    self.status_message = self.i18n.tr_args("status-clip-marker-failed", &[("error", &e)]);
+                }
+                Task::none()
+            }
+            Message::ClipMarkerNameEdited { marker_id, name } => {
+                self.clip_marker_edit = Some((marker_id, name));
+                Task::none()
+            }
+            Message::RenameClipMarker { track_index, clip_id, marker_id } => {
+                let Some((_, name)) = self.clip_marker_edit.take_if(|(id, _)| *id == marker_id) else {
+                    return Task::none();
+                };
+                let result = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Rename clip marker",
+                    |tl| tl.rename_clip_marker(track_index, clip_id, marker_id, name.trim()),
+                );
+                if let Err(e) = result {
+                    self.status_message = self.i18n.tr_args("status-clip-marker-failed", &[("error", &e)]);
+                }
+                Task::none()
+            }
+            Message::AddGuide => {
+                let position = self.playback_position;
+                if self.project.timeline.guides.contains(&position) {
+                    return Task::none();
+                }
+                let _ = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Add guide",
+                    |tl| Ok(tl.add_guide(position)),
+                );
+                self.status_message = self.i18n.tr("status-guide-added");
+                Task::none()
+            }
+            Message::GuideIntervalChanged(interval) => {
+                self.guide_interval = interval;
+                Task::none()
+            }
+            Message::InsertTimeDurationChanged(secs) => {
+                self.insert_time_secs = secs;
+                Task::none()
+            }
+            Message::InsertTimeAtPlayhead => {
+                let duration = match self.insert_time_secs.trim().parse::<f64>() {
+                    Ok(n) if n.is_finite() && n > 0.0 => Duration::from_secs_f64(n),
+                    _ => {
+                        self.status_message = self.i18n.tr("status-insert-time-invalid");
+                        return Task::none();
+                    }
+                };
+                let position = self.playback_position;
+                let result = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Insert time",
+                    |tl| tl.insert_time(position, duration),
+                );
+                match result {
+                    Ok(()) => {
+                        // Cut clips get new ids
+                        self.selected_clip = None;
+                        self.selected_clips.clear();
+                        let secs = format!("{:.2}", duration.as_secs_f64());
+                        self.status_message = self.i18n.tr_args("status-time-inserted", &[("secs", &secs)]);
+                        return self.update(Message::SeekTo(position));
+                    }
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-insert-time-failed", &[("error", &e)]);
+                    }
+                }
+                Task::none()
+            }
+            Message::ToggleGuideIntervalUnit => {
+                self.guide_interval_in_frames = !self.guide_interval_in_frames;
+                Task::none()
+            }
+            Message::GenerateGuides => {
+                let interval = match self.guide_interval.trim().parse::<f64>() {
+                    Ok(n) if n.is_finite() && n > 0.0 => {
+                        if self.guide_interval_in_frames {
+                            n / self.project.settings.fps
+                        } else {
+                            n
+                        }
+                    }
+                    _ => {
+                        self.status_message = self.i18n.tr("status-guide-interval-invalid");
+                        return Task::none();
+                    }
+                };
+                let end = TimelinePosition::from_secs_f64(self.project.timeline.duration().as_secs_f64());
+                if end == TimelinePosition::zero() {
+                    self.status_message = self.i18n.tr("status-guides-need-clips");
+                    return Task::none();
+                }
+                let interval = Duration::from_secs_f64(interval);
+                let result = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Generate guides",
+                    |tl| Ok(tl.generate_guides(TimelinePosition::zero(), end, interval)),
+                );
+                if let Ok(count) = result {
+                    self.status_message = self.i18n.tr_args("status-guides-generated", &[("count", &count)]);
+                }
+                Task::none()
+            }
+            Message::ClearGuides => {
+                if self.project.timeline.guides.is_empty() {
+                    return Task::none();
+                }
+                let _ = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Clear guides",
+                    |tl| {
+                        tl.guides.clear();
+                        Ok(())
+                    },
+                );
+                Task::none()
+            }
+            Message::UngroupSelectedClips => {
+                let clips = self.clip_selection();
+                let result = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Ungroup clips",
+                    |tl| tl.ungroup_clips(&clips),
+                );
+                match result {
+                    Ok(count) => {
+                        let count = count.to_string();
+                        self.status_message = self.i18n.tr_args("status-clips-ungrouped", &[("count", &count)]);
+                    }
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-group-failed", &[("error", &e)]);
+                    }
+                }
+                Task::none()
+            }
+            Message::SelectAdjacentClip(direction) => {
+                if let Some((track_index, clip_id)) = self.adjacent_clip(direction) {
+                    self.selected_clip = Some((track_index, clip_id));
+                    self.reveal_clip(track_index, clip_id);
+                }
+                Task::none()
+            }
+            Message::SelectClipOnAdjacentTrack(direction) => {
+                if let Some((track_index, clip_id)) = self.clip_on_adjacent_track(direction) {
+                    self.selected_clip = Some((track_index, clip_id));
+                    self.reveal_clip(track_index, clip_id);
+                }
+                Task::none()
+            }
+            Message::RemoveClip {
+                track_index,
+                clip_id,
+            } => {
+                let ripple = self.preferences.ripple_edits;
+                let result = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Remove clip",
+                    |tl| {
+                        if ripple {
+                            tl.remove_clip_ripple(track_index, clip_id)
+                        } else {
+                            tl.remove_clip_grouped(track_index, clip_id)
+                        }
+                    },
+                );
+                match result {
+                    Ok(()) => {
+                        self.status_message = self.i18n.tr("status-clip-removed");
+                        self.selected_clip = None;
+                    }
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-remove-clip-failed", &[("error", &e)]);
+                    }
+                }
+                Task::none()
             }
             Message::StartDragFromSource(asset_id) => {
                 if let Some(asset) = self.project.source_library.get(asset_id) {
                     let thumbnail = self.thumbnails.get(&asset_id).cloned();
+                    // Dragging part of a multi-selection drags the whole selection,
+                    // in library display order starting from the grabbed asset.
+                    let additional_asset_ids: Vec<Uuid> =
+                        if self.selected_asset_ids.len() > 1 && self.selected_asset_ids.contains(&asset_id) {
+                            self.visible_library_assets()
+                                .iter()
+                                .map(|a| a.id)
+                                .filter(|id| *id != asset_id && self.selected_asset_ids.contains(id))
+                                .collect()
+                        } else {
+                            Vec::new()
+                        };
+                    let name = if additional_asset_ids.is_empty() {
+                        asset.name.clone()
+                    } else {
+                        format!("{} +{}", asset.name, additional_asset_ids.len())
+                    };
                     self.drag_state = Some(DragState {
                         payload: DragPayload::SourceAsset {
                             asset_id,
                             thumbnail,
-                            name: asset.name.clone(),
+                            name,
+                            additional_asset_ids,
+                            subclip_id: None,
                         },
                         cursor_position: Point::ORIGIN,
                         over_timeline: false,
@@ -468,6 +1798,34 @@ impl App {
                 }
                 Task::none()
             }
+            Message::StartDragFromSubClip(subclip_id) => {
+                if let Some((asset, subclip)) = self.project.source_library.find_subclip(subclip_id) {
+                    self.drag_state = Some(DragState {
+                        payload: DragPayload::SourceAsset {
+                            asset_id: asset.id,
+                            thumbnail: self.thumbnails.get(&asset.id).cloned(),
+                            name: subclip.name.clone(),
+                            additional_asset_ids: Vec::new(),
+                            subclip_id: Some(subclip_id),
+                        },
+                        cursor_position: Point::ORIGIN,
+                        over_timeline: false,
+                        timeline_track: None,
+                        timeline_position: None,
+                    });
+                }
+                Task::none()
+            }
+            Message::StartDragEffect(effect_type) => {
+                self.drag_state = Some(DragState {
+                    payload: DragPayload::Effect { effect_type },
+                    cursor_position: Point::ORIGIN,
+                    over_timeline: false,
+                    timeline_track: None,
+                    timeline_position: None,
+                });
+                Task::none()
+            }
             Message::DragMoved(position) => {
                 if let Some(drag) = &mut self.drag_state {
                     drag.cursor_position = position;
@@ -477,15 +1835,57 @@ impl App {
             Message::DragReleased => {
                 if let Some(drag) = self.drag_state.take() {
                     if drag.over_timeline {
-                        let DragPayload::SourceAsset { asset_id, .. } = &drag.payload;
                         if let (Some(track_index), Some(position)) =
                             (drag.timeline_track, drag.timeline_position)
                         {
-                            return self.update(Message::AddClipToTimeline {
-                                asset_id: *asset_id,
-                                track_index,
-                                position,
-                            });
+                            match drag.payload {
+                                DragPayload::SourceAsset { asset_id, additional_asset_ids, subclip_id, .. } => {
+                                    if let Some(subclip_id) = subclip_id {
+                                        return self.update(Message::AddSubClipToTimeline {
+                                            subclip_id,
+                                            track_index,
+                                            position,
+                                        });
+                                    }
+                                    if !additional_asset_ids.is_empty() {
+                                        let mut asset_ids = vec![asset_id];
+                                        asset_ids.extend(additional_asset_ids);
+                                        return self.update(Message::AddClipsToTimeline {
+                                            asset_ids,
+                                            track_index,
+                                            position,
+                                        });
+                                    }
+                                    return self.update(Message::AddClipToTimeline {
+                                        asset_id,
+                                        track_index,
+                                        position,
+                                    });
+                                }
+                                DragPayload::Effect { effect_type } => {
+                                    let clip_id = self
+                                        .project
+                                        .timeline
+                                        .track(track_index)
+                                        .ok()
+                                        .and_then(|t| t.clip_at(position))
+                                        .map(|c| c.id);
+                                    let Some(clip_id) = clip_id else {
+                                        self.status_message = self.i18n.tr("status-drop-effect-on-clip");
+                                        return Task::none();
+                                    };
+                                    // Dropping onto part of a multi-selection applies to all of it
+                                    let selection = self.clip_selection();
+                                    if selection.len() > 1 && selection.contains(&(track_index, clip_id)) {
+                                        return self.add_effect_to_clips(&selection, effect_type);
+                                    }
+                                    return self.update(Message::AddEffectToClip {
+                                        track_index,
+                                        clip_id,
+                                        effect_type,
+                                    });
+                                }
+                            }
                         }
                     }
                 }
@@ -497,6 +1897,49 @@ impl App {
                 }
                 Task::none()
             }
+            Message::DismissNotification(id) => {
+                self.notifications.dismiss(id);
+                Task::none()
+            }
+            Message::ToggleNotificationHistory => {
+                self.show_notification_history = !self.show_notification_history;
+                Task::none()
+            }
+            Message::ClearNotifications => {
+                self.notifications.clear();
+                Task::none()
+            }
+            Message::StartSplitterDrag(splitter) => {
+                self.splitter_drag = Some(SplitterDrag {
+                    splitter,
+                    origin: None,
+                    start_size: self.preferences.layout.size(splitter),
+                });
+                Task::none()
+            }
+            Message::SplitterMoved(position) => {
+                let Some(drag) = &mut self.splitter_drag else {
+                    return Task::none();
+                };
+                let Some(origin) = drag.origin else {
+                    drag.origin = Some(position);
+                    return Task::none();
+                };
+                // The inspector sits right of its splitter, so it grows as the cursor moves left.
+                let delta = match drag.splitter {
+                    PanelSplitter::LeftPanel => position.x - origin.x,
+                    PanelSplitter::Inspector => origin.x - position.x,
+                    PanelSplitter::Preview => position.y - origin.y,
+                };
+                self.preferences.layout.set_size(drag.splitter, drag.start_size + delta);
+                Task::none()
+            }
+            Message::SplitterReleased => {
+                if self.splitter_drag.take().is_some() {
+                    self.save_preferences();
+                }
+                Task::none()
+            }
             Message::DragExitedTimeline => {
                 if let Some(drag) = &mut self.drag_state {
                     drag.over_timeline = false;
@@ -506,25 +1949,33 @@ impl App {
                 Task::none()
             }
             Message::DragOverTimeline(point) => {
+                let vertical_scroll = self.effective_vertical_scroll();
                 if let Some(drag) = &mut self.drag_state {
-                    // Account for controls row height (~30px), header column, and ruler height (20px)
+                    // Account for controls row height (~30px), header column, ruler height,
+                    // and the vertical scroll of the track lanes
                     let controls_height = 30.0_f32;
-                    let ruler_height = 20.0_f32;
-                    let track_height = 50.0_f32;
-                    let header_width = 60.0_f32;
+                    let ruler_height = timeline_canvas::RULER_HEIGHT;
+                    let header_width = track_header::header_width(&self.project.timeline);
 
                     let canvas_x = (point.x - header_width).max(0.0);
                     let secs = ((canvas_x + self.timeline_scroll) / self.timeline_zoom) as f64;
                     let secs = secs.max(0.0);
 
-                    let track_y = point.y - controls_height - ruler_height;
+                    let track_y = point.y - controls_height - ruler_height + vertical_scroll;
                     let track_index = if track_y < 0.0 {
                         0
                     } else {
-                        let idx = (track_y / track_height) as usize;
-                        idx.min(self.project.timeline.tracks.len().saturating_sub(1))
+                        timeline_canvas::track_index_at_offset(&self.project.timeline, track_y)
+                            .unwrap_or(self.project.timeline.tracks.len().saturating_sub(1))
                     };
 
+                    // Effects land on the clip under the cursor, whatever the track type
+                    if matches!(drag.payload, DragPayload::Effect { .. }) {
+                        drag.timeline_track = Some(track_index);
+                        drag.timeline_position = Some(TimelinePosition::from_secs_f64(secs));
+                        return Task::none();
+                    }
+
                     // Snap to nearest video track (for assets with video)
                     let video_track_index = self
                         .project
@@ -545,6 +1996,39 @@ impl App {
                 }
                 Task::none()
             }
+            Message::AddClipsToTimeline {
+                asset_ids,
+                track_index,
+                position,
+            } => {
+                let items: Vec<(Uuid, bool, TimeRange)> = asset_ids
+                    .iter()
+                    .filter_map(|id| self.project.source_library.get(*id))
+                    .map(|asset| {
+                        let source_range = TimeRange {
+                            start: TimelinePosition::zero(),
+                            end: TimelinePosition::from_secs_f64(
+                                self.preferences.placed_duration(asset).as_secs_f64(),
+                            ),
+                        };
+                        (asset.id, asset.has_audio, source_range)
+                    })
+                    .collect();
+                if items.is_empty() {
+                    self.status_message = self.i18n.tr("status-asset-not-found");
+                    return Task::none();
+                }
+                let count = items.len();
+                match self.add_clip_sequence("Add clips", &items, track_index, position) {
+                    Ok(()) => {
+                        self.status_message = self.i18n.tr_args("status-clips-added", &[("count", &count)]);
+                    }
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-add-clips-failed", &[("error", &e)]);
+                    }
+                }
+                Task::none()
+            }
             Message::AddClipToTimeline {
                 asset_id,
                 track_index,
@@ -553,46 +2037,25 @@ impl App {
                 if let Some(asset) = self.project.source_library.get(asset_id) {
                     let source_range = TimeRange {
                         start: TimelinePosition::zero(),
-                        end: TimelinePosition::from_secs_f64(asset.duration.as_secs_f64()),
+                        end: TimelinePosition::from_secs_f64(self.preferences.placed_duration(asset).as_secs_f64()),
                     };
-                    let has_audio = asset.has_audio;
-                    let audio_track = self.project.timeline.find_paired_audio_track(track_index);
-
-                    if has_audio && audio_track.is_some() {
-                        let audio_track = audio_track.unwrap();
-                        let result = self.project.command_history.execute(
-                            &mut self.project.timeline,
-                            "Add clip",
-                            |tl| {
-                                tl.add_clip_with_audio(track_index, audio_track, asset_id, position, source_range)
-                            },
-                        );
-                        match result {
-                            Ok(_) => {
-                                self.status_message = "Clip added".into();
-                            }
-                            Err(e) => {
-                                self.status_message = format!("Add clip failed: {e}");
-                            }
-                        }
-                    } else {
-                        let clip = Clip::new(asset_id, position, source_range);
-                        let result = self.project.command_history.execute(
-                            &mut self.project.timeline,
-                            "Add clip",
-                            |tl| tl.add_clip_trimming_overlaps(track_index, clip),
-                        );
-                        match result {
-                            Ok(()) => {
-                                self.status_message = "Clip added".into();
-                            }
-                            Err(e) => {
-                                self.status_message = format!("Add clip failed: {e}");
-                            }
-                        }
-                    }
+                    self.add_source_clip(asset_id, source_range, track_index, position);
                 } else {
-                    self.status_message = "Asset not found".into();
+                    self.status_message = self.i18n.tr("status-asset-not-found");
+                }
+                Task::none()
+            }
+            Message::AddSubClipToTimeline {
+                subclip_id,
+                track_index,
+                position,
+            } => {
+                match self.project.source_library.find_subclip(subclip_id) {
+                    Some((asset, subclip)) => {
+                        let (asset_id, source_range) = (asset.id, subclip.range);
+                        self.add_source_clip(asset_id, source_range, track_index, position);
+                    }
+                    None => self.status_message = self.i18n.tr("status-asset-not-found"),
                 }
                 Task::none()
             }
@@ -602,12 +2065,8 @@ impl App {
                 dest_track,
                 position,
             } => {
-                let snap_threshold = Duration::from_millis(200);
-                let has_link = self.project.timeline.track(source_track)
-                    .ok()
-                    .and_then(|t| t.get_clip(clip_id))
-                    .and_then(|c| c.link_id)
-                    .is_some();
+                let snap_threshold = self.preferences.snap_threshold();
+                let has_link = !self.project.timeline.clip_companions(source_track, clip_id).is_empty();
 
                 let result = self.project.command_history.execute(
                     &mut self.project.timeline,
@@ -623,7 +2082,7 @@ impl App {
                     },
                 );
                 if let Err(e) = result {
-                    self.status_message = format!("Move failed: {e}");
+                    self.status_message = self.i18n.tr_args("status-move-failed", &[("error", &e)]);
                 }
                 Task::none()
             }
@@ -652,10 +2111,10 @@ impl App {
                 };
                 match result {
                     Ok(_) => {
-                        self.status_message = "Clip cut".into();
+                        self.status_message = self.i18n.tr("status-clip-cut");
                     }
                     Err(e) => {
-                        self.status_message = format!("Cut failed: {e}");
+                        self.status_message = self.i18n.tr_args("status-cut-failed", &[("error", &e)]);
                     }
                 }
                 Task::none()
@@ -665,46 +2124,403 @@ impl App {
                 clip_id,
                 new_end,
             } => {
-                let has_link = self.project.timeline.track(track_index)
-                    .ok()
-                    .and_then(|t| t.get_clip(clip_id))
-                    .and_then(|c| c.link_id)
-                    .is_some();
+                let has_link = !self.project.timeline.clip_companions(track_index, clip_id).is_empty();
+                let ripple = self.preferences.ripple_edits;
 
                 let result = self.project.command_history.execute(
                     &mut self.project.timeline,
                     "Resize clip",
                     |tl| {
-                        if has_link {
+                        if ripple {
+                            tl.resize_clip_ripple(track_index, clip_id, new_end)
+                        } else if has_link {
                             tl.resize_clip_grouped(track_index, clip_id, new_end)
                         } else {
                             tl.resize_clip(track_index, clip_id, new_end)
                         }
                     },
                 );
+                self.trim_two_up = None;
+                if let Err(e) = result {
+                    self.status_message = self.i18n.tr_args("status-resize-failed", &[("error", &e)]);
+                }
+                Task::none()
+            }
+            Message::SnapAudioTrim { track_index, clip_id, new_end } => {
+                let target = self
+                    .project
+                    .timeline
+                    .track(track_index)
+                    .ok()
+                    .and_then(|t| t.get_clip(clip_id))
+                    .and_then(|clip| {
+                        let asset = self.project.source_library.get(clip.asset_id).filter(|a| a.has_audio)?;
+                        Some((asset.path.clone(), asset.duration.as_secs_f64(), clip.source_secs_at(new_end), clip.speed()))
+                    });
+                let Some((path, max_secs, source_secs, speed)) = target else {
+                    return self.update(Message::ResizeClip { track_index, clip_id, new_end });
+                };
+                Task::perform(
+                    async move { zeditor_media::waveform::quiet_cut_near(&path, source_secs, max_secs) },
+                    move |result| {
+                        // Audio that can't be read is trimmed where it was released
+                        let shift = result.map_or(0.0, |cut| (cut - source_secs) / speed);
+                        Message::ResizeClip {
+                            track_index,
+                            clip_id,
+                            new_end: TimelinePosition::from_secs_f64((new_end.as_secs_f64() + shift).max(0.0)),
+                        }
+                    },
+                )
+            }
+            Message::TrimEdgeMoved {
+                track_index,
+                clip_id,
+                edge,
+            } => {
+                // Decode on the frame grid, so small moves don't refetch
+                let fps = self.project.settings.fps;
+                let edge_secs = if fps > 0.0 {
+                    (edge.as_secs_f64() * fps).round() / fps
+                } else {
+                    edge.as_secs_f64()
+                };
+                let two_up = match &mut self.trim_two_up {
+                    Some(two_up) if two_up.clip_id == clip_id => two_up,
+                    slot => slot.insert(TrimTwoUp::new(track_index, clip_id)),
+                };
+                match two_up.move_edge(edge_secs) {
+                    Some(edge_secs) => self.trim_frames_task(edge_secs),
+                    None => Task::none(),
+                }
+            }
+            Message::TrimFramesDecoded {
+                clip_id,
+                outgoing,
+                incoming,
+            } => {
+                let next = match &mut self.trim_two_up {
+                    Some(two_up) if two_up.clip_id == clip_id => two_up.finish(outgoing, incoming),
+                    _ => None,
+                };
+                match next {
+                    Some(edge_secs) => self.trim_frames_task(edge_secs),
+                    None => Task::none(),
+                }
+            }
+            Message::SetClipSpeed {
+                track_index,
+                clip_id,
+                speed,
+            } => {
+                let ripple = self.preferences.ripple_edits;
+                let result = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Change clip speed",
+                    |tl| {
+                        if ripple {
+                            tl.set_clip_speed_ripple(track_index, clip_id, speed)
+                        } else {
+                            tl.set_clip_speed_grouped(track_index, clip_id, speed)
+                        }
+                    },
+                );
                 if let Err(e) = result {
-                    self.status_message = format!("Resize failed: {e}");
+                    self.status_message = self.i18n.tr_args("status-speed-failed", &[("error", &e)]);
                 }
                 Task::none()
             }
+            Message::SetClipPreservePitch {
+                track_index,
+                clip_id,
+                preserve,
+            } => {
+                let result = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Change pitch preservation",
+                    |tl| tl.set_preserve_pitch(track_index, clip_id, preserve),
+                );
+                match result {
+                    Ok(()) => {
+                        if self.is_playing {
+                            self.send_audio_decode_seek(true);
+                        }
+                    }
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-preserve-pitch-failed", &[("error", &e)]);
+                    }
+                }
+                Task::none()
+            }
+            Message::SetClipChannelMap {
+                track_index,
+                clip_id,
+                map,
+            } => {
+                let result = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Change channel routing",
+                    |tl| tl.set_channel_map(track_index, clip_id, map),
+                );
+                match result {
+                    Ok(()) => {
+                        if self.is_playing {
+                            self.send_audio_decode_seek(true);
+                        }
+                    }
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-channel-map-failed", &[("error", &e)]);
+                    }
+                }
+                Task::none()
+            }
+            Message::SetClipRateConform {
+                track_index,
+                clip_id,
+                conform,
+            } => {
+                let source_fps = self
+                    .project
+                    .timeline
+                    .track(track_index)
+                    .ok()
+                    .and_then(|t| t.get_clip(clip_id))
+                    .and_then(|c| self.project.source_library.get(c.asset_id))
+                    .map_or(0.0, |a| a.fps);
+                let project_fps = self.project.settings.fps;
+                let result = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Change rate conform",
+                    |tl| tl.set_rate_conform(track_index, clip_id, conform, source_fps, project_fps),
+                );
+                match result {
+                    Ok(()) => {
+                        self.send_decode_seek(self.is_playing);
+                        if self.is_playing {
+                            self.send_audio_decode_seek(true);
+                        }
+                    }
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-rate-conform-failed", &[("error", &e)]);
+                    }
+                }
+                Task::none()
+            }
+            Message::SetClipCanvasFit {
+                track_index,
+                clip_id,
+                fit,
+            } => {
+                let result = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Change canvas fit",
+                    |tl| tl.set_canvas_fit(track_index, clip_id, fit),
+                );
+                match result {
+                    Ok(()) => self.send_decode_seek(self.is_playing),
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-canvas-fit-failed", &[("error", &e)]);
+                    }
+                }
+                Task::none()
+            }
+            Message::SetClipVolumeEnvelope {
+                track_index,
+                clip_id,
+                envelope,
+            } => {
+                let result = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Change volume envelope",
+                    |tl| tl.set_volume_envelope(track_index, clip_id, envelope),
+                );
+                match result {
+                    Ok(()) => {
+                        if self.is_playing {
+                            self.send_audio_decode_seek(true);
+                        }
+                    }
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-volume-envelope-failed", &[("error", &e)]);
+                    }
+                }
+                Task::none()
+            }
+            Message::LoadInspectorWaveform { track_index, clip_id } => {
+                let source = self
+                    .project
+                    .timeline
+                    .track(track_index)
+                    .ok()
+                    .and_then(|t| t.get_clip(clip_id))
+                    .and_then(|clip| Some((clip.source_range, self.project.source_library.get(clip.asset_id)?)))
+                    .filter(|(_, asset)| asset.has_audio)
+                    .map(|(range, asset)| (range, asset.path.clone(), asset.duration.as_secs_f64()));
+                let Some((range, path, asset_secs)) = source else {
+                    self.status_message = self.i18n.tr("status-waveform-needs-audio");
+                    return Task::none();
+                };
+                let window = waveform_widget::waveform_window(range, asset_secs);
+                Task::perform(
+                    async move {
+                        zeditor_media::waveform::waveform_peaks(
+                            &path,
+                            window.0,
+                            window.1 - window.0,
+                            waveform_widget::WAVEFORM_BUCKETS,
+                        )
+                        .map_err(|e| format!("{e}"))
+                    },
+                    move |result| Message::InspectorWaveformLoaded { clip_id, window, result },
+                )
+            }
+            Message::InspectorWaveformLoaded { clip_id, window, result } => {
+                match result {
+                    Ok(peaks) => {
+                        self.inspector_waveform = Some(InspectorWaveform {
+                            clip_id,
+                            window_start: window.0,
+                            window_end: window.1,
+                            peaks,
+                        });
+                    }
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-waveform-failed", &[("error", &e)]);
+                    }
+                }
+                Task::none()
+            }
+            Message::SetClipSourceRange {
+                track_index,
+                clip_id,
+                source,
+            } => {
+                let result = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Trim clip source",
+                    |tl| tl.set_source_range(track_index, clip_id, source),
+                );
+                match result {
+                    Ok(()) => {
+                        if self.is_playing {
+                            self.send_audio_decode_seek(true);
+                        }
+                    }
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-source-trim-failed", &[("error", &e)]);
+                    }
+                }
+                Task::none()
+            }
+            Message::ToggleRippleEdits => {
+                self.preferences.ripple_edits = !self.preferences.ripple_edits;
+                self.save_preferences();
+                self.status_message = self.i18n.tr(if self.preferences.ripple_edits {
+                    "status-ripple-edits-on"
+                } else {
+                    "status-ripple-edits-off"
+                });
+                Task::none()
+            }
+            Message::ToggleSnapTarget(target) => {
+                let (enabled, key) = match target {
+                    SnapTarget::Markers => (&mut self.preferences.snap_to_markers, "menu-snap-to-markers"),
+                    SnapTarget::Playhead => (&mut self.preferences.snap_to_playhead, "menu-snap-to-playhead"),
+                    SnapTarget::QuietAudio => (&mut self.preferences.snap_audio_trims, "menu-snap-quiet-audio"),
+                };
+                *enabled = !*enabled;
+                let status = if *enabled { "status-snap-target-on" } else { "status-snap-target-off" };
+                self.save_preferences();
+                let target = self.i18n.tr(key);
+                self.status_message = self.i18n.tr_args(status, &[("target", &target)]);
+                Task::none()
+            }
             Message::TimelineZoom { delta, cursor_secs } => {
                 // Zoom centered on cursor position
-                let old_zoom = self.timeline_zoom;
-                let factor = 1.0 + delta * 0.15;
+                let cursor_px = cursor_secs as f32 * self.timeline_zoom - self.timeline_scroll;
+                self.update(Message::TimelineZoomBy {
+                    factor: 1.0 + delta * 0.15,
+                    anchor_secs: cursor_secs,
+                    anchor_px: cursor_px,
+                })
+            }
+            Message::TimelineZoomBy { factor, anchor_secs, anchor_px } => {
                 self.timeline_zoom =
-                    (self.timeline_zoom * factor).clamp(0.1, 1000.0);
-
-                // Adjust scroll so the point under the cursor stays put
-                let cursor_px_before = cursor_secs as f32 * old_zoom - self.timeline_scroll;
-                let cursor_px_after = cursor_secs as f32 * self.timeline_zoom - self.timeline_scroll;
-                self.timeline_scroll += cursor_px_after - cursor_px_before;
-                self.timeline_scroll = self.timeline_scroll.max(0.0);
+                    (self.timeline_zoom * factor).clamp(MIN_TIMELINE_ZOOM, MAX_TIMELINE_ZOOM);
+                self.timeline_scroll = (anchor_secs as f32 * self.timeline_zoom - anchor_px).max(0.0);
+                Task::none()
+            }
+            Message::TimelineZoomStep(steps) => {
+                // Tolerance so a zoom that sits on a preset moves past it
+                let zoom = self.timeline_zoom;
+                let target = match steps.cmp(&0) {
+                    std::cmp::Ordering::Greater => {
+                        TIMELINE_ZOOM_PRESETS.iter().copied().find(|p| *p > zoom * 1.001)
+                    }
+                    std::cmp::Ordering::Less => {
+                        TIMELINE_ZOOM_PRESETS.iter().rev().copied().find(|p| *p < zoom * 0.999)
+                    }
+                    std::cmp::Ordering::Equal => None,
+                };
+                if let Some(target) = target {
+                    self.zoom_around_playhead(target);
+                }
+                Task::none()
+            }
+            Message::ZoomToFit => {
+                let extent = self.project.timeline.duration().as_secs_f64() as f32;
+                if extent <= 0.0 {
+                    self.status_message = self.i18n.tr("status-nothing-to-fit");
+                    return Task::none();
+                }
+                let usable = self.timeline_viewport_width() * (1.0 - ZOOM_TO_FIT_MARGIN);
+                self.timeline_zoom = (usable / extent).clamp(MIN_TIMELINE_ZOOM, MAX_TIMELINE_ZOOM);
+                self.timeline_scroll = 0.0;
+                Task::none()
+            }
+            Message::WindowResized(size) => {
+                self.window_size = size;
+                Task::none()
+            }
+            Message::SetUiScale(scale) => {
+                self.preferences.ui_scale = crate::preferences::clamp_ui_scale(scale);
+                self.save_preferences();
+                self.status_message = self.i18n.tr_args(
+                    "status-ui-scale",
+                    &[("percent", &format!("{:.0}", self.preferences.ui_scale * 100.0))],
+                );
+                Task::none()
+            }
+            Message::SetLanguage(language) => {
+                self.preferences.language = language;
+                self.i18n = I18n::new(language);
+                self.save_preferences();
+                self.status_message =
+                    self.i18n.tr_args("status-language", &[("language", &language.native_name())]);
                 Task::none()
             }
             Message::TimelineScroll(delta_px) => {
                 self.timeline_scroll = (self.timeline_scroll + delta_px).max(0.0);
                 Task::none()
             }
+            Message::TimelineVerticalScroll(delta_px) => {
+                self.timeline_vertical_scroll = (self.effective_vertical_scroll() + delta_px)
+                    .clamp(0.0, self.max_vertical_scroll());
+                Task::none()
+            }
+            Message::TimelinePan { dx, dy } => {
+                self.timeline_scroll = (self.timeline_scroll + dx).max(0.0);
+                self.timeline_vertical_scroll = (self.effective_vertical_scroll() + dy)
+                    .clamp(0.0, self.max_vertical_scroll());
+                Task::none()
+            }
+            Message::TimelinePanStarted => {
+                // Holding Space to pan shouldn't leave playback toggled
+                if std::mem::take(&mut self.space_toggled_playback) {
+                    return self.update(Message::TogglePlayback);
+                }
+                Task::none()
+            }
             Message::TimelineClickEmpty(pos) => {
                 self.selected_clip = None;
                 if self.is_playing {
@@ -720,26 +2536,90 @@ impl App {
                 Task::none()
             }
             Message::Play => {
+                // Only one player runs at a time.
+                if self.source_monitor.is_playing {
+                    self.source_monitor.pause();
+                    self.send_source_decode_stop();
+                }
                 self.is_playing = true;
+                self.playback_rate = 1.0;
+                self.playback_stats.reset();
                 self.playback_start_wall = Some(Instant::now());
                 self.playback_start_pos = self.playback_position;
                 self.send_decode_seek(true);
                 self.send_audio_decode_seek(true);
                 if let Some(player) = &self.audio_player {
+                    player.set_speed(1.0);
                     player.play();
                 }
                 Task::none()
             }
+            Message::ShuttleForward => {
+                let rate = if self.is_playing && self.playback_rate > 0.0 {
+                    (self.playback_rate * 2.0).min(MAX_SHUTTLE_RATE)
+                } else {
+                    1.0
+                };
+                self.set_shuttle_rate(rate);
+                Task::none()
+            }
+            Message::ShuttleReverse => {
+                let rate = if self.is_playing && self.playback_rate < 0.0 {
+                    (self.playback_rate * 2.0).max(-MAX_SHUTTLE_RATE)
+                } else {
+                    -1.0
+                };
+                self.set_shuttle_rate(rate);
+                Task::none()
+            }
+            Message::ShuttleStop => {
+                self.playback_rate = 1.0;
+                if self.is_playing {
+                    return self.update(Message::Pause);
+                }
+                Task::none()
+            }
+            Message::StepFrameForward => {
+                self.step_frames(1);
+                Task::none()
+            }
+            Message::StepFrameBackward => {
+                self.step_frames(-1);
+                Task::none()
+            }
             Message::Pause => {
                 self.is_playing = false;
+                self.play_around = None;
                 self.playback_start_wall = None;
                 self.send_decode_stop();
+                self.serving_prerender = None;
                 self.send_audio_decode_stop();
                 if let Some(player) = &self.audio_player {
                     player.pause();
                 }
                 Task::none()
             }
+            Message::PlayAround => {
+                let origin = self.playback_position;
+                let start = (origin.as_secs_f64() - self.play_around_preroll).max(0.0);
+                let stop_at =
+                    TimelinePosition::from_secs_f64(origin.as_secs_f64() + self.play_around_postroll);
+                if self.is_playing {
+                    self.update(Message::Pause);
+                }
+                self.playback_position = TimelinePosition::from_secs_f64(start);
+                let task = self.update(Message::Play);
+                self.play_around = Some(PlayAroundState { origin, stop_at });
+                task
+            }
+            Message::SetPlayAroundPreroll(secs) => {
+                self.play_around_preroll = secs.clamp(0.0, MAX_PLAY_AROUND_SECS);
+                Task::none()
+            }
+            Message::SetPlayAroundPostroll(secs) => {
+                self.play_around_postroll = secs.clamp(0.0, MAX_PLAY_AROUND_SECS);
+                Task::none()
+            }
             Message::TogglePlayback => {
                 if self.is_playing {
                     self.update(Message::Pause)
@@ -747,41 +2627,144 @@ impl App {
                     self.update(Message::Play)
                 }
             }
+            Message::ToggleLoopPlayback => {
+                self.loop_playback = !self.loop_playback;
+                self.status_message = if self.loop_playback {
+                    self.i18n.tr("status-loop-on")
+                } else {
+                    self.i18n.tr("status-loop-off")
+                };
+                Task::none()
+            }
+            Message::SetPreviewBackground(background) => {
+                self.preview_background = background;
+                Task::none()
+            }
+            Message::SetCanvasPreset(preset) => {
+                self.project.settings = self.project.settings.with_canvas_preset(preset);
+                self.status_message = self.i18n.tr_args(
+                    "status-canvas-changed",
+                    &[
+                        ("width", &self.project.settings.canvas_width),
+                        ("height", &self.project.settings.canvas_height),
+                    ],
+                );
+                self.send_decode_seek(false);
+                Task::none()
+            }
+            Message::ToggleMemoryOverlay => {
+                self.show_memory_overlay = !self.show_memory_overlay;
+                Task::none()
+            }
+            Message::TogglePlaybackStats => {
+                self.show_playback_stats = !self.show_playback_stats;
+                Task::none()
+            }
+            Message::TogglePreviewOverlay(kind) => {
+                self.preview_overlay.toggle(kind);
+                Task::none()
+            }
             Message::PlaybackTick => {
+                self.poll_decode_errors();
                 if let Some(start_wall) = self.playback_start_wall {
-                    let elapsed = start_wall.elapsed().as_secs_f64();
-                    let new_pos = self.playback_start_pos.as_secs_f64() + elapsed;
+                    // Audio is the master clock while it's playing; the wall clock is
+                    // re-anchored to it every tick so falling back (gaps, no audio
+                    // device, reverse shuttle) continues seamlessly.
+                    let mut new_pos = match self.audio_clock_position() {
+                        Some(audio_secs) => {
+                            self.playback_start_wall = Some(Instant::now());
+                            self.playback_start_pos = TimelinePosition::from_secs_f64(audio_secs);
+                            audio_secs
+                        }
+                        None => {
+                            let elapsed = start_wall.elapsed().as_secs_f64();
+                            self.playback_start_pos.as_secs_f64() + elapsed * self.playback_rate
+                        }
+                    };
+
+                    // Play-around ends at the postroll and returns to where it started.
+                    let finished_around = self.play_around
+                        .filter(|around| new_pos >= around.stop_at.as_secs_f64());
+                    if let Some(around) = finished_around {
+                        self.update(Message::Pause);
+                        return self.update(Message::SeekTo(around.origin));
+                    }
+
+                    // Reverse playback stops at the start of the timeline.
+                    if new_pos <= 0.0 && self.playback_rate < 0.0 {
+                        self.playback_position = TimelinePosition::zero();
+                        return self.update(Message::ShuttleStop);
+                    }
+
+                    // Loop: once the playhead reaches the loop end, restart the
+                    // clock and both decoders from the loop start.
+                    let wrap_to = if self.loop_playback && self.playback_rate > 0.0 {
+                        self.loop_range()
+                            .filter(|(_, end)| new_pos >= end.as_secs_f64())
+                            .map(|(start, _)| start)
+                    } else {
+                        None
+                    };
+                    if let Some(loop_start) = wrap_to {
+                        new_pos = loop_start.as_secs_f64();
+                        self.playback_position = loop_start;
+                        self.playback_start_pos = loop_start;
+                        self.playback_start_wall = Some(Instant::now());
+                        self.timeline_scroll =
+                            (new_pos as f32 * self.timeline_zoom - 100.0).max(0.0);
+                        self.send_decode_seek(true);
+                        self.drain_stale = true;
+                        self.send_audio_decode_seek(true);
+                    }
                     self.playback_position = TimelinePosition::from_secs_f64(new_pos);
 
                     // Auto-scroll: keep playhead visible
                     let playhead_px =
                         new_pos as f32 * self.timeline_zoom - self.timeline_scroll;
                     let visible_width = 800.0; // approximate
-                    if playhead_px > visible_width * 0.8 {
-                        self.timeline_scroll =
-                            new_pos as f32 * self.timeline_zoom - visible_width * 0.5;
+                    if playhead_px > visible_width * 0.8 || playhead_px < 0.0 {
+                        self.timeline_scroll = (new_pos as f32 * self.timeline_zoom
+                            - visible_width * 0.5)
+                            .max(0.0);
                     }
 
+                    // Pre-rendered stretches are shown straight from the cache.
+                    let served = self.playback_rate > 0.0 && self.serve_prerendered_frame();
+
                     // Check if we've crossed into a different set of video clips
                     let current_clip_ids: Vec<Uuid> =
                         self.all_video_clips_at_position(self.playback_position)
                             .iter().map(|(_, c)| c.id).collect();
-                    if current_clip_ids != self.decode_clip_ids {
+                    if !served && current_clip_ids != self.decode_clip_ids {
                         self.send_decode_seek(true);
                         self.drain_stale = true;
                     }
+                    if self.playback_rate > 0.0 {
+                        self.preload_upcoming_clips();
+                    }
 
-                    // Check if the primary audio clip changed
+                    // Check if the primary audio clip changed (reverse playback is silent)
                     let current_audio_id = self.audio_clip_at_position(self.playback_position)
                         .map(|(_, c)| c.id);
-                    if current_audio_id != self.audio_decode_clip_id {
+                    if self.playback_rate > 0.0 && current_audio_id != self.audio_decode_clip_id {
                         self.send_audio_decode_seek(true);
                     }
                 }
 
                 // Drain decoded frames from the channels
                 self.poll_decoded_frame();
-                self.poll_decoded_audio();
+                self.poll_audio_levels();
+                self.playback_stats.audio_buffered = self.audio_ring.buffered();
+
+                // Source monitor runs on its own clock; stop decoding once it hits the end.
+                if self.source_monitor.tick(Instant::now()) && !self.source_monitor.is_playing {
+                    self.send_source_decode_stop();
+                }
+                self.poll_source_frame();
+                self.poll_pending_save();
+                self.poll_prerendered_frames();
+                self.plan_prerender();
+                self.enforce_memory_budget();
 
                 // Poll render progress if rendering
                 if self.is_rendering {
@@ -798,30 +2781,79 @@ impl App {
                             let elapsed = self.render_elapsed.as_secs();
                             let mins = elapsed / 60;
                             let secs = elapsed % 60;
-                            self.status_message = format!(
-                                "Rendering: {}/{} frames ({:.1}%) | Elapsed: {}:{:02}",
-                                self.render_current_frame,
-                                self.render_total_frames,
-                                pct,
-                                mins,
-                                secs,
+                            self.status_message = self.i18n.tr_args(
+                                "status-rendering-progress",
+                                &[
+                                    ("current", &self.render_current_frame),
+                                    ("total", &self.render_total_frames),
+                                    ("percent", &format!("{pct:.1}")),
+                                    ("elapsed", &format!("{mins}:{secs:02}")),
+                                ],
                             );
                         }
                     }
                 }
                 Task::none()
             }
+            Message::TimelineSearchChanged(query) => {
+                self.timeline_search = query;
+                self.timeline_search_cursor = None;
+                if self.timeline_search.trim().is_empty() {
+                    return Task::none();
+                }
+                self.update(Message::TimelineSearchNext)
+            }
+            Message::TimelineSearchNext => {
+                let matches = self.timeline_search_matches();
+                if matches.is_empty() {
+                    self.timeline_search_cursor = None;
+                    self.status_message = self.i18n.tr("status-timeline-search-no-match");
+                    return Task::none();
+                }
+                let cursor = self.timeline_search_cursor.map_or(0, |c| (c + 1) % matches.len());
+                self.timeline_search_cursor = Some(cursor);
+                let (track_index, clip_id) = matches[cursor];
+                self.focus_clip(track_index, clip_id)
+            }
             Message::SeekTo(pos) => {
+                self.play_around = None;
                 self.playback_position = pos;
                 if self.is_playing {
                     self.playback_start_wall = Some(Instant::now());
                     self.playback_start_pos = pos;
                 }
                 self.send_decode_seek(self.is_playing);
+                if self.is_playing && self.playback_rate > 0.0 {
+                    self.send_audio_decode_seek(true);
+                }
+                Task::none()
+            }
+            Message::TimecodeInputChanged(input) => {
+                self.timecode_input = Some(input);
                 Task::none()
             }
+            Message::TimecodeSubmitted => {
+                let Some(input) = self.timecode_input.take() else {
+                    return Task::none();
+                };
+                match timecode::parse_timecode(&input, self.playback_position, self.project.settings.fps) {
+                    Some(position) => self.update(Message::SeekTo(position)),
+                    None => {
+                        self.status_message = self.i18n.tr_args("status-invalid-timecode", &[("input", &input)]);
+                        Task::none()
+                    }
+                }
+            }
             Message::KeyboardEvent(event) => {
-                if let keyboard::Event::KeyPressed { key, .. } = event {
+                if let keyboard::Event::ModifiersChanged(modifiers) = event {
+                    self.keyboard_modifiers = modifiers;
+                    return Task::none();
+                }
+                if let keyboard::Event::KeyReleased { key: keyboard::Key::Named(keyboard::key::Named::Space), .. } = &event {
+                    self.space_toggled_playback = false;
+                    return Task::none();
+                }
+                if let keyboard::Event::KeyPressed { key, modifiers, repeat, .. } = event {
                     // Escape cancels drag
                     if self.drag_state.is_some() {
                         if matches!(key.as_ref(), keyboard::Key::Named(keyboard::key::Named::Escape)) {
@@ -839,7 +2871,31 @@ impl App {
                     // When a confirm dialog is open, Escape dismisses it and all other keys are swallowed
                     if self.confirm_dialog.is_some() {
                         if matches!(key.as_ref(), keyboard::Key::Named(keyboard::key::Named::Escape)) {
-                            self.confirm_dialog = None;
+                            return self.update(Message::ConfirmDialogDismissed);
+                        }
+                        return Task::none();
+                    }
+                    if self.version_dialog.is_some() {
+                        if matches!(key.as_ref(), keyboard::Key::Named(keyboard::key::Named::Escape)) {
+                            return self.update(Message::ProjectVersionsDismissed);
+                        }
+                        return Task::none();
+                    }
+                    if self.preferences_form.is_some() {
+                        if matches!(key.as_ref(), keyboard::Key::Named(keyboard::key::Named::Escape)) {
+                            return self.update(Message::PreferencesDismissed);
+                        }
+                        return Task::none();
+                    }
+                    if self.path_remap_dialog.is_some() {
+                        if matches!(key.as_ref(), keyboard::Key::Named(keyboard::key::Named::Escape)) {
+                            return self.update(Message::PathRemapDismissed);
+                        }
+                        return Task::none();
+                    }
+                    if self.slideshow_dialog.is_some() {
+                        if matches!(key.as_ref(), keyboard::Key::Named(keyboard::key::Named::Escape)) {
+                            return self.update(Message::SlideshowDismissed);
                         }
                         return Task::none();
                     }
@@ -850,8 +2906,17 @@ impl App {
                         }
                         return Task::none();
                     }
+                    // Ctrl (Cmd on macOS) shortcuts never fall through to the plain-key bindings
+                    if modifiers.command() {
+                        return self.handle_command_shortcut(key.as_ref(), modifiers.shift());
+                    }
                     match key.as_ref() {
                         keyboard::Key::Named(keyboard::key::Named::Space) => {
+                            // Auto-repeat while Space is held for panning mustn't keep toggling
+                            if repeat {
+                                return Task::none();
+                            }
+                            self.space_toggled_playback = true;
                             return self.update(Message::TogglePlayback);
                         }
                         keyboard::Key::Named(keyboard::key::Named::Delete) | keyboard::Key::Named(keyboard::key::Named::Backspace) => {
@@ -871,69 +2936,123 @@ impl App {
                         keyboard::Key::Character("b") => {
                             self.tool_mode = ToolMode::Blade;
                         }
+                        keyboard::Key::Character("j") => {
+                            return self.update(Message::ShuttleReverse);
+                        }
+                        keyboard::Key::Character("k") => {
+                            return self.update(Message::ShuttleStop);
+                        }
+                        keyboard::Key::Character("l") => {
+                            return self.update(Message::ShuttleForward);
+                        }
+                        keyboard::Key::Character("/") => {
+                            return self.update(Message::PlayAround);
+                        }
+                        keyboard::Key::Character("\\") => {
+                            return self.update(Message::ToggleEffectsBypass);
+                        }
+                        keyboard::Key::Character("m" | "M") if modifiers.shift() => {
+                            return self.update(Message::AddClipMarker);
+                        }
+                        keyboard::Key::Character("m") => {
+                            return self.update(Message::AddMarker);
+                        }
+                        keyboard::Key::Character("g") => {
+                            return self.update(Message::AddGuide);
+                        }
+                        keyboard::Key::Named(keyboard::key::Named::PageUp) => {
+                            return self.update(Message::TimelineVerticalScroll(
+                                -3.0 * timeline_canvas::TRACK_HEIGHT,
+                            ));
+                        }
+                        keyboard::Key::Named(keyboard::key::Named::PageDown) => {
+                            return self.update(Message::TimelineVerticalScroll(
+                                3.0 * timeline_canvas::TRACK_HEIGHT,
+                            ));
+                        }
+                        keyboard::Key::Named(keyboard::key::Named::Tab) => {
+                            let direction = if modifiers.shift() { -1 } else { 1 };
+                            return self.update(Message::SelectAdjacentClip(direction));
+                        }
+                        keyboard::Key::Named(keyboard::key::Named::ArrowLeft) if modifiers.alt() => {
+                            return self.update(Message::SelectAdjacentClip(-1));
+                        }
+                        keyboard::Key::Named(keyboard::key::Named::ArrowRight) if modifiers.alt() => {
+                            return self.update(Message::SelectAdjacentClip(1));
+                        }
+                        keyboard::Key::Named(keyboard::key::Named::ArrowUp) => {
+                            return self.update(Message::SelectClipOnAdjacentTrack(-1));
+                        }
+                        keyboard::Key::Named(keyboard::key::Named::ArrowDown) => {
+                            return self.update(Message::SelectClipOnAdjacentTrack(1));
+                        }
+                        keyboard::Key::Named(keyboard::key::Named::ArrowLeft)
+                        | keyboard::Key::Character(",")
+                            if self.selected_clip.is_none() =>
+                        {
+                            return self.update(Message::StepFrameBackward);
+                        }
+                        keyboard::Key::Named(keyboard::key::Named::ArrowRight)
+                        | keyboard::Key::Character(".")
+                            if self.selected_clip.is_none() =>
+                        {
+                            return self.update(Message::StepFrameForward);
+                        }
                         _ => {}
                     }
                 }
                 Task::none()
             }
             Message::Undo => {
-                match self
-                    .project
-                    .command_history
-                    .undo(&mut self.project.timeline)
-                {
+                let assets_before = self.asset_ids();
+                match self.project.undo() {
                     Ok(()) => {
-                        self.status_message = "Undone".into();
+                        self.status_message = self.i18n.tr("status-undone");
+                        return self.sync_library_view(&assets_before);
                     }
                     Err(e) => {
-                        self.status_message = format!("Undo failed: {e}");
+                        self.status_message = self.i18n.tr_args("status-undo-failed", &[("error", &e)]);
                     }
                 }
                 Task::none()
             }
             Message::Redo => {
-                match self
-                    .project
-                    .command_history
-                    .redo(&mut self.project.timeline)
-                {
+                let assets_before = self.asset_ids();
+                match self.project.redo() {
                     Ok(()) => {
-                        self.status_message = "Redone".into();
+                        self.status_message = self.i18n.tr("status-redone");
+                        return self.sync_library_view(&assets_before);
                     }
                     Err(e) => {
-                        self.status_message = format!("Redo failed: {e}");
+                        self.status_message = self.i18n.tr_args("status-redo-failed", &[("error", &e)]);
                     }
                 }
                 Task::none()
             }
             Message::SaveProject => {
-                if let Some(path) = &self.project_path {
+                if let Some(path) = self.project_path.clone() {
                     // Save directly to the known path
-                    match self.project.save(path) {
-                        Ok(()) => {
-                            self.status_message = format!("Saved to {}", path.display());
-                        }
-                        Err(e) => {
-                            self.status_message = format!("Save failed: {e}");
-                        }
-                    }
+                    self.start_save(path);
                     Task::none()
                 } else {
                     // No path yet — open save dialog
-                    self.status_message = "Opening save dialog...".into();
-                    Task::perform(
-                        async {
-                            let handle = rfd::AsyncFileDialog::new()
-                                .add_filter("Zeditor Project", &["zpf"])
-                                .set_title("Save Project")
-                                .save_file()
-                                .await;
-                            handle.map(|f| f.path().to_path_buf())
-                        },
-                        Message::SaveFileDialogResult,
-                    )
+                    self.update(Message::SaveProjectAs)
                 }
             }
+            Message::SaveProjectAs => {
+                self.status_message = self.i18n.tr("status-opening-save-dialog");
+                Task::perform(
+                    async {
+                        let handle = rfd::AsyncFileDialog::new()
+                            .add_filter("Zeditor Project", &["zpf"])
+                            .set_title("Save Project")
+                            .save_file()
+                            .await;
+                        handle.map(|f| f.path().to_path_buf())
+                    },
+                    Message::SaveFileDialogResult,
+                )
+            }
             Message::SaveFileDialogResult(path) => {
                 match path {
                     Some(mut path) => {
@@ -945,18 +3064,10 @@ impl App {
                         if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
                             self.project.name = stem.to_string();
                         }
-                        match self.project.save(&path) {
-                            Ok(()) => {
-                                self.status_message = format!("Saved to {}", path.display());
-                                self.project_path = Some(path);
-                            }
-                            Err(e) => {
-                                self.status_message = format!("Save failed: {e}");
-                            }
-                        }
+                        self.start_save(path);
                     }
                     None => {
-                        self.status_message = "Save cancelled".into();
+                        self.status_message = self.i18n.tr("status-save-cancelled");
                     }
                 }
                 Task::none()
@@ -967,11 +3078,25 @@ impl App {
                         self.reset_ui_state();
                         self.project = project;
                         self.project_path = Some(path.clone());
-                        self.status_message = format!("Loaded {}", path.display());
+                        self.status_message = self.i18n.tr_args("status-loaded", &[("path", &path.display())]);
+                        // A leftover journal means the last session ended without saving.
+                        let pending = CommandJournal::for_project(&path)
+                            .read_entries()
+                            .map(|entries| entries.len())
+                            .unwrap_or(0);
+                        if pending > 0 {
+                            self.confirm_dialog = Some(ConfirmDialog::confirm(
+                                self.i18n.tr_args("dialog-recovery-journal", &[("count", &pending)]),
+                                ConfirmAction::ReplayJournal { entry_count: pending },
+                            ));
+                        } else {
+                            self.reset_journal();
+                        }
+                        self.build_seek_indexes(self.video_paths());
                         self.regenerate_all_thumbnails()
                     }
                     Err(e) => {
-                        self.status_message = format!("Load failed: {e}");
+                        self.status_message = self.i18n.tr_args("status-load-failed", &[("error", &e)]);
                         Task::none()
                     }
                 }
@@ -982,90 +3107,27 @@ impl App {
                         return self.update(Message::LoadProject(path));
                     }
                     None => {
-                        self.status_message = "Load cancelled".into();
+                        self.status_message = self.i18n.tr("status-load-cancelled");
                     }
                 }
                 Task::none()
             }
+            Message::AutosaveTick => {
+                // Only projects with a file to save to and edits since the last save
+                let Some(path) = self.project_path.clone() else {
+                    return Task::none();
+                };
+                if self.has_unsaved_changes() && self.pending_save.is_none() {
+                    self.start_save(path);
+                }
+                Task::none()
+            }
             Message::NewProject => {
                 self.reset_ui_state();
                 self.project = Project::default();
+                self.project.settings = self.preferences.new_project.clone();
                 self.project_path = None;
-                self.status_message = "New project created".into();
-                Task::none()
-            }
-            Message::RenderFileDialogResult(path) => {
-                match path {
-                    Some(mut path) => {
-                        // Ensure .mkv extension
-                        if path.extension().is_none_or(|e| e != "mkv") {
-                            path.set_extension("mkv");
-                        }
-                        self.status_message = "Rendering...".into();
-                        let timeline = self.project.timeline.clone();
-                        let source_library = self.project.source_library.clone();
-                        let config = zeditor_media::renderer::derive_render_config(
-                            &timeline,
-                            &source_library,
-                            &self.project.settings,
-                            path,
-                        );
-                        // Create a progress channel for render progress updates
-                        let (ptx, prx) = std::sync::mpsc::channel();
-                        self.is_rendering = true;
-                        self.render_start = Some(Instant::now());
-                        self.render_current_frame = 0;
-                        self.render_total_frames = 0;
-                        self.render_elapsed = Duration::ZERO;
-                        self.render_progress_rx = Some(prx);
-
-                        Task::perform(
-                            async move {
-                                zeditor_media::renderer::render_timeline(
-                                    &timeline,
-                                    &source_library,
-                                    &config,
-                                    Some(ptx),
-                                )
-                                .map(|()| config.output_path.clone())
-                                .map_err(|e| format!("{e}"))
-                            },
-                            |result| match result {
-                                Ok(path) => Message::RenderComplete(path),
-                                Err(e) => Message::RenderError(e),
-                            },
-                        )
-                    }
-                    None => {
-                        self.status_message = "Render cancelled".into();
-                        Task::none()
-                    }
-                }
-            }
-            Message::RenderComplete(path) => {
-                let total_time = self
-                    .render_start
-                    .map(|s| s.elapsed())
-                    .unwrap_or_default();
-                let total_secs = total_time.as_secs();
-                let total_ms = total_time.subsec_millis();
-                self.status_message = format!(
-                    "Rendered to {} | Total time: {}:{:02}.{:03}",
-                    path.display(),
-                    total_secs / 60,
-                    total_secs % 60,
-                    total_ms,
-                );
-                self.is_rendering = false;
-                self.render_progress_rx = None;
-                self.render_start = None;
-                Task::none()
-            }
-            Message::RenderError(msg) => {
-                self.status_message = format!("Render failed: {msg}");
-                self.is_rendering = false;
-                self.render_progress_rx = None;
-                self.render_start = None;
+                self.status_message = self.i18n.tr("status-new-project");
                 Task::none()
             }
             Message::MenuButtonClicked(id) => {
@@ -1091,25 +3153,63 @@ impl App {
                 match action {
                     MenuAction::Undo => self.update(Message::Undo),
                     MenuAction::Redo => self.update(Message::Redo),
-                    MenuAction::Exit => self.update(Message::Exit),
-                    MenuAction::NewProject => self.update(Message::NewProject),
-                    MenuAction::LoadProject => {
-                        self.status_message = "Opening load dialog...".into();
-                        Task::perform(
-                            async {
-                                let handle = rfd::AsyncFileDialog::new()
-                                    .add_filter("Zeditor Project", &["zpf"])
-                                    .set_title("Load Project")
-                                    .pick_file()
-                                    .await;
-                                handle.map(|f| f.path().to_path_buf())
-                            },
-                            Message::LoadFileDialogResult,
-                        )
+                    MenuAction::GroupClips => self.update(Message::GroupSelectedClips),
+                    MenuAction::UngroupClips => self.update(Message::UngroupSelectedClips),
+                    MenuAction::SynchronizeClips => self.update(Message::SynchronizeSelectedClips),
+                    MenuAction::SplitDualMono => self.update(Message::SplitSelectedDualMono),
+                    MenuAction::TranscribeClip => self.update(Message::TranscribeSelectedClip),
+                    MenuAction::DetectBeats => self.update(Message::DetectBeatsSelectedClip),
+                    MenuAction::AutoReframe => self.update(Message::AutoReframeSelectedClip),
+                    MenuAction::FitToFill => self.update(Message::FitToFill),
+                    MenuAction::CreateSlideshow => self.update(Message::OpenSlideshow),
+                    MenuAction::SetUiScale(percent) => {
+                        self.update(Message::SetUiScale(percent as f32 / 100.0))
+                    }
+                    MenuAction::SetLanguage(language) => self.update(Message::SetLanguage(language)),
+                    MenuAction::NewProject | MenuAction::LoadProject | MenuAction::Exit
+                        if self.has_unsaved_changes() =>
+                    {
+                        self.confirm_dialog = Some(ConfirmDialog::choice(
+                            self.i18n.tr("dialog-unsaved-changes"),
+                            vec!["dialog-save", "dialog-discard"],
+                            ConfirmAction::UnsavedChanges { then: action },
+                        ));
+                        Task::none()
                     }
+                    MenuAction::NewProject | MenuAction::LoadProject | MenuAction::Exit => self.leave_project(action),
                     MenuAction::Save => self.update(Message::SaveProject),
+                    MenuAction::SaveAs => self.update(Message::SaveProjectAs),
+                    MenuAction::RestoreVersion => self.update(Message::ShowProjectVersions),
+                    MenuAction::RemapMediaPaths => self.update(Message::OpenPathRemap),
+                    MenuAction::ImportFolder => self.update(Message::ImportFolder),
+                    MenuAction::ReplaceMedia => self.update(Message::ReplaceSelectedMedia),
+                    MenuAction::RelinkMedia => {
+                        match self.selected_asset_id.and_then(|id| self.project.source_library.get(id)) {
+                            Some(asset) => {
+                                self.confirm_dialog = Some(ConfirmDialog::prompt(
+                                    self.i18n.tr_args("dialog-relink-media", &[("name", &asset.name)]),
+                                    asset.path.display().to_string(),
+                                    ConfirmAction::RelinkAsset { asset_id: asset.id },
+                                ));
+                            }
+                            None => self.status_message = self.i18n.tr("status-replace-needs-asset"),
+                        }
+                        Task::none()
+                    }
+                    MenuAction::RevealInTimeline => match self.selected_asset_id {
+                        Some(asset_id) => self.update(Message::RevealAssetInTimeline(asset_id)),
+                        None => {
+                            self.status_message = self.i18n.tr("status-reveal-needs-asset");
+                            Task::none()
+                        }
+                    },
+                    MenuAction::ToggleRippleEdits => self.update(Message::ToggleRippleEdits),
+                    MenuAction::ToggleExportStems => self.update(Message::ToggleExportStems),
+                    MenuAction::ToggleSnapTarget(target) => self.update(Message::ToggleSnapTarget(target)),
+                    MenuAction::ExportDescription => self.update(Message::ExportTimelineDescription),
+                    MenuAction::Preferences => self.update(Message::OpenPreferences),
                     MenuAction::Render => {
-                        self.status_message = "Opening render dialog...".into();
+                        self.status_message = self.i18n.tr("status-opening-render-dialog");
                         Task::perform(
                             async {
                                 let handle = rfd::AsyncFileDialog::new()
@@ -1139,6 +3239,55 @@ impl App {
                 self.track_context_menu = None;
                 Task::none()
             }
+            Message::SetTrackHeight { track_index, height } => {
+                let result = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Change track height",
+                    |tl| {
+                        tl.track_mut(track_index)?.height = height;
+                        Ok(())
+                    },
+                );
+                if let Err(e) = result {
+                    self.status_message = self.i18n.tr_args("status-track-update-failed", &[("error", &e)]);
+                }
+                Task::none()
+            }
+            Message::ToggleTrackFlag { track_index, flag } => {
+                let description = match flag {
+                    TrackFlag::Mute => "Toggle track mute",
+                    TrackFlag::Solo => "Toggle track solo",
+                    TrackFlag::Visible => "Toggle track visibility",
+                    TrackFlag::Lock => "Toggle track lock",
+                };
+                let result = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    description,
+                    |tl| {
+                        let track = tl.track_mut(track_index)?;
+                        match flag {
+                            TrackFlag::Mute => track.muted = !track.muted,
+                            TrackFlag::Solo => track.solo = !track.solo,
+                            TrackFlag::Visible => track.hidden = !track.hidden,
+                            TrackFlag::Lock => track.locked = !track.locked,
+                        }
+                        Ok(())
+                    },
+                );
+                if let Err(e) = result {
+                    self.status_message = self.i18n.tr_args("status-track-update-failed", &[("error", &e)]);
+                    return Task::none();
+                }
+                // Re-seek so the preview and audio pick up the new mix.
+                match flag {
+                    TrackFlag::Visible => self.send_decode_seek(self.is_playing),
+                    TrackFlag::Mute | TrackFlag::Solo if self.is_playing => {
+                        self.send_audio_decode_seek(true);
+                    }
+                    _ => {}
+                }
+                Task::none()
+            }
             Message::AddVideoTrackAbove(ref_idx) => {
                 self.track_context_menu = None;
                 let result = self.project.command_history.execute(
@@ -1148,11 +3297,11 @@ impl App {
                 );
                 match result {
                     Ok(()) => {
-                        self.status_message = "Video track added".into();
+                        self.status_message = self.i18n.tr("status-video-track-added");
                         // Clear selected clip since indices may have shifted
                         self.selected_clip = None;
                     }
-                    Err(e) => self.status_message = format!("Add track failed: {e}"),
+                    Err(e) => self.status_message = self.i18n.tr_args("status-add-track-failed", &[("error", &e)]),
                 }
                 Task::none()
             }
@@ -1165,10 +3314,10 @@ impl App {
                 );
                 match result {
                     Ok(()) => {
-                        self.status_message = "Video track added".into();
+                        self.status_message = self.i18n.tr("status-video-track-added");
                         self.selected_clip = None;
                     }
-                    Err(e) => self.status_message = format!("Add track failed: {e}"),
+                    Err(e) => self.status_message = self.i18n.tr_args("status-add-track-failed", &[("error", &e)]),
                 }
                 Task::none()
             }
@@ -1181,10 +3330,10 @@ impl App {
                 );
                 match result {
                     Ok(()) => {
-                        self.status_message = "Audio track added".into();
+                        self.status_message = self.i18n.tr("status-audio-track-added");
                         self.selected_clip = None;
                     }
-                    Err(e) => self.status_message = format!("Add track failed: {e}"),
+                    Err(e) => self.status_message = self.i18n.tr_args("status-add-track-failed", &[("error", &e)]),
                 }
                 Task::none()
             }
@@ -1197,73 +3346,161 @@ impl App {
                 );
                 match result {
                     Ok(()) => {
-                        self.status_message = "Audio track added".into();
+                        self.status_message = self.i18n.tr("status-audio-track-added");
                         self.selected_clip = None;
                     }
-                    Err(e) => self.status_message = format!("Add track failed: {e}"),
+                    Err(e) => self.status_message = self.i18n.tr_args("status-add-track-failed", &[("error", &e)]),
                 }
                 Task::none()
             }
+            Message::DeleteTrack(track_index) => {
+                self.track_context_menu = None;
+                let Some(track) = self.project.timeline.tracks.get(track_index) else {
+                    return Task::none();
+                };
+                if track.clips.is_empty() {
+                    return self.delete_track(track_index);
+                }
+                self.confirm_dialog = Some(ConfirmDialog::confirm(
+                    self.i18n.tr_args("dialog-delete-track", &[("name", &track.name), ("count", &track.clips.len())]),
+                    ConfirmAction::DeleteTrack { track_index },
+                ));
+                Task::none()
+            }
             Message::SwitchLeftPanelTab(tab) => {
                 self.left_panel_tab = tab;
                 Task::none()
             }
+            Message::JumpToHealthIssue {
+                track_index,
+                clip_id,
+                position,
+            } => {
+                if let Some(clip_id) = clip_id {
+                    self.selected_clip = Some((track_index, clip_id));
+                    self.selected_clips.clear();
+                }
+                self.reveal_position(position);
+                self.update(Message::SeekTo(position))
+            }
+            Message::SnapshotNameChanged(name) => {
+                self.snapshot_name = name;
+                Task::none()
+            }
+            Message::TakeTimelineSnapshot => {
+                let name = match self.snapshot_name.trim() {
+                    "" => format!("Snapshot {}", self.project.snapshots.len() + 1),
+                    name => name.to_string(),
+                };
+                self.project.take_snapshot(name.clone());
+                self.snapshot_name.clear();
+                self.status_message = self.i18n.tr_args("status-snapshot-taken", &[("name", &name)]);
+                Task::none()
+            }
+            Message::CompareTimelineSnapshot(id) => {
+                self.compared_snapshot = (self.compared_snapshot != Some(id)).then_some(id);
+                Task::none()
+            }
+            Message::RestoreTimelineSnapshot(id) => {
+                let Some(name) = self.project.snapshot(id).map(|s| s.name.clone()) else {
+                    return Task::none();
+                };
+                match self.project.restore_snapshot(id) {
+                    Ok(()) => {
+                        // Clips of the replaced timeline may no longer exist
+                        self.selected_clip = None;
+                        self.selected_clips.clear();
+                        self.status_message = self.i18n.tr_args("status-snapshot-restored", &[("name", &name)]);
+                        self.send_decode_seek(false);
+                    }
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-snapshot-restore-failed", &[("error", &e)]);
+                    }
+                }
+                Task::none()
+            }
+            Message::DeleteTimelineSnapshot(id) => {
+                if let Ok(snapshot) = self.project.remove_snapshot(id) {
+                    if self.compared_snapshot == Some(id) {
+                        self.compared_snapshot = None;
+                    }
+                    self.status_message = self.i18n.tr_args("status-snapshot-deleted", &[("name", &snapshot.name)]);
+                }
+                Task::none()
+            }
+            Message::EffectSearchChanged(query) => {
+                self.effect_search = query;
+                Task::none()
+            }
             Message::AddEffectToSelectedClip(effect_type) => {
+                let selection = self.clip_selection();
+                if selection.len() > 1 {
+                    return self.add_effect_to_clips(&selection, effect_type);
+                }
                 if let Some((track_index, clip_id)) = self.selected_clip {
-                    let result = self.project.command_history.execute(
-                        &mut self.project.timeline,
-                        "Add effect",
-                        |tl| {
-                            let clip = tl.track_mut(track_index)?
-                                .get_clip_mut(clip_id)
-                                .ok_or(zeditor_core::error::CoreError::ClipNotFound(clip_id))?;
-                            clip.effects.push(EffectInstance::new(effect_type));
-                            Ok(())
-                        },
-                    );
-                    match result {
-                        Ok(()) => self.status_message = "Effect added".into(),
-                        Err(e) => self.status_message = format!("Add effect failed: {e}"),
-                    }
+                    return self.update(Message::AddEffectToClip {
+                        track_index,
+                        clip_id,
+                        effect_type,
+                    });
                 }
                 Task::none()
             }
-            Message::RemoveEffectFromClip { track_index, clip_id, effect_id } => {
+            Message::AddEffectToClip { track_index, clip_id, effect_type } => {
                 let result = self.project.command_history.execute(
                     &mut self.project.timeline,
-                    "Remove effect",
+                    "Add effect",
                     |tl| {
                         let clip = tl.track_mut(track_index)?
                             .get_clip_mut(clip_id)
                             .ok_or(zeditor_core::error::CoreError::ClipNotFound(clip_id))?;
-                        clip.effects.retain(|e| e.id != effect_id);
+                        clip.effects.push(EffectInstance::new(effect_type));
                         Ok(())
                     },
                 );
                 match result {
-                    Ok(()) => self.status_message = "Effect removed".into(),
-                    Err(e) => self.status_message = format!("Remove effect failed: {e}"),
+                    Ok(()) => {
+                        self.selected_clip = Some((track_index, clip_id));
+                        self.status_message = self.i18n.tr("status-effect-added");
+                    }
+                    Err(e) => self.status_message = self.i18n.tr_args("status-add-effect-failed", &[("error", &e)]),
                 }
                 Task::none()
             }
-            Message::UpdateEffectParameter { track_index, clip_id, effect_id, param_name, value } => {
-                // Clear text input state when slider updates the value
-                self.effect_param_texts.remove(&(effect_id, param_name.clone()));
+            Message::RemoveEffectFromClip { track_index, clip_id, effect_id } => {
                 let result = self.project.command_history.execute(
                     &mut self.project.timeline,
-                    "Update effect parameter",
+                    "Remove effect",
                     |tl| {
                         let clip = tl.track_mut(track_index)?
                             .get_clip_mut(clip_id)
                             .ok_or(zeditor_core::error::CoreError::ClipNotFound(clip_id))?;
-                        if let Some(effect) = clip.effects.iter_mut().find(|e| e.id == effect_id) {
-                            effect.set_float(&param_name, value);
-                        }
+                        clip.effects.retain(|e| e.id != effect_id);
                         Ok(())
                     },
                 );
+                match result {
+                    Ok(()) => self.status_message = self.i18n.tr("status-effect-removed"),
+                    Err(e) => self.status_message = self.i18n.tr_args("status-remove-effect-failed", &[("error", &e)]),
+                }
+                Task::none()
+            }
+            Message::ToggleEffectsBypass => {
+                self.effects_bypassed = !self.effects_bypassed;
+                self.status_message = self.i18n.tr(if self.effects_bypassed {
+                    "status-effects-bypassed"
+                } else {
+                    "status-effects-restored"
+                });
+                self.send_decode_seek(self.is_playing);
+                Task::none()
+            }
+            Message::UpdateEffectParameter { track_index, clip_id, effect_id, param_name, value } => {
+                // Clear text input state when slider updates the value
+                self.effect_param_texts.remove(&(effect_id, param_name.clone()));
+                let result = self.set_effect_parameter(track_index, clip_id, effect_id, &param_name, value);
                 if let Err(e) = result {
-                    self.status_message = format!("Update effect failed: {e}");
+                    self.status_message = self.i18n.tr_args("status-update-effect-failed", &[("error", &e)]);
                 }
                 // Trigger decode refresh to update preview
                 self.send_decode_seek(false);
@@ -1293,21 +3530,9 @@ impl App {
                         .unwrap_or(false);
 
                     if in_bounds {
-                        let result = self.project.command_history.execute(
-                            &mut self.project.timeline,
-                            "Update effect parameter",
-                            |tl| {
-                                let clip = tl.track_mut(track_index)?
-                                    .get_clip_mut(clip_id)
-                                    .ok_or(zeditor_core::error::CoreError::ClipNotFound(clip_id))?;
-                                if let Some(effect) = clip.effects.iter_mut().find(|e| e.id == effect_id) {
-                                    effect.set_float(&param_name, value);
-                                }
-                                Ok(())
-                            },
-                        );
+                        let result = self.set_effect_parameter(track_index, clip_id, effect_id, &param_name, value);
                         if let Err(e) = result {
-                            self.status_message = format!("Update effect failed: {e}");
+                            self.status_message = self.i18n.tr_args("status-update-effect-failed", &[("error", &e)]);
                         }
                         self.send_decode_seek(false);
                     }
@@ -1339,7 +3564,7 @@ impl App {
             seconds,
             millis,
             self.timeline_zoom,
-            if self.is_playing { "Playing" } else { "Stopped" }
+            self.i18n.tr(if self.is_playing { "status-playing" } else { "status-stopped" })
         ))
         .size(14);
 
@@ -1352,9 +3577,14 @@ impl App {
             let elapsed = self.render_elapsed.as_secs();
             let mins = elapsed / 60;
             let secs = elapsed % 60;
-            let progress_text = format!(
-                "Rendering: {}/{} frames ({:.1}%) | Elapsed: {}:{:02}",
-                self.render_current_frame, self.render_total_frames, pct, mins, secs,
+            let progress_text = self.i18n.tr_args(
+                "status-rendering-progress",
+                &[
+                    ("current", &self.render_current_frame),
+                    ("total", &self.render_total_frames),
+                    ("percent", &format!("{pct:.1}")),
+                    ("elapsed", &format!("{mins}:{secs:02}")),
+                ],
             );
 
             let pct_frac = (pct / 100.0).min(1.0) as f32;
@@ -1409,14 +3639,36 @@ impl App {
             .into()
         } else {
             let status_message = if self.status_message.is_empty() {
-                "No system messages"
+                self.i18n.tr("status-none")
             } else {
-                &self.status_message
+                self.status_message.clone()
+            };
+            let unresolved = self.notifications.unresolved_count();
+            let history_label = if unresolved > 0 {
+                self.i18n.tr_args("status-notifications-count", &[("count", &unresolved)])
+            } else {
+                self.i18n.tr("status-notifications")
+            };
+            let saving_label = if self.is_saving() {
+                self.i18n.tr("status-save-indicator")
+            } else {
+                String::new()
             };
             container(
-                text(status_message)
-                    .size(13)
-                    .color(Color::from_rgb(0.7, 0.7, 0.7)),
+                row![
+                    text(status_message)
+                        .size(13)
+                        .color(Color::from_rgb(0.7, 0.7, 0.7)),
+                    Space::new().width(Length::Fill),
+                    text(saving_label)
+                        .size(12)
+                        .color(Color::from_rgb(1.0, 0.9, 0.3)),
+                    button(text(history_label).size(12))
+                        .on_press(Message::ToggleNotificationHistory)
+                        .padding([1, 8]),
+                ]
+                .spacing(8)
+                .align_y(iced::Alignment::Center),
             )
             .padding([4, 8])
             .width(Length::Fill)
@@ -1432,27 +3684,38 @@ impl App {
             .into()
         };
 
-        let top_row = row![source_panel, video_viewport].spacing(4);
+        let top_row = if self.source_monitor.is_loaded() {
+            row![
+                source_panel,
+                view_splitter(PanelSplitter::LeftPanel),
+                self.view_source_monitor(),
+                video_viewport
+            ]
+            .spacing(4)
+        } else {
+            row![source_panel, view_splitter(PanelSplitter::LeftPanel), video_viewport].spacing(4)
+        };
 
         // Timeline row: timeline panel + effects inspector
-        let timeline_row: Element<'_, Message> = row![timeline_panel, effects_inspector]
-            .spacing(4)
-            .height(Length::Fill)
-            .into();
+        let timeline_row: Element<'_, Message> =
+            row![timeline_panel, view_splitter(PanelSplitter::Inspector), effects_inspector]
+                .spacing(4)
+                .height(Length::Fill)
+                .into();
 
         // Main content area (without status bar)
         let main_content: Element<'_, Message> = if self.open_menu.is_some() {
-            let click_off: Element<'_, Message> = mouse_area(
-                container("")
-                    .width(Length::Fill)
-                    .height(Length::Fill),
-            )
-            .on_press(Message::CloseMenu)
-            .into();
+            let click_off = dialog::click_off(Message::CloseMenu);
 
             let dropdown = self.view_dropdown();
 
-            let content_below = column![top_row, timeline_row, playback_info].spacing(4);
+            let content_below = column![
+                top_row,
+                view_splitter(PanelSplitter::Preview),
+                timeline_row,
+                playback_info
+            ]
+            .spacing(4);
 
             let stacked_content = stack![content_below, click_off, opaque(dropdown)]
                 .width(Length::Fill)
@@ -1463,10 +3726,16 @@ impl App {
                 .padding(4)
                 .into()
         } else {
-            column![menu_bar, top_row, timeline_row, playback_info]
-                .spacing(4)
-                .padding(4)
-                .into()
+            column![
+                menu_bar,
+                top_row,
+                view_splitter(PanelSplitter::Preview),
+                timeline_row,
+                playback_info
+            ]
+            .spacing(4)
+            .padding(4)
+            .into()
         };
 
         // Wrap main content in a container that takes up remaining space
@@ -1480,73 +3749,19 @@ impl App {
             .height(Length::Fill)
             .into();
 
-        // Add confirmation dialog overlay if present
-        let base_layout: Element<'_, Message> = if let Some(dialog) = &self.confirm_dialog {
-            let click_off: Element<'_, Message> = mouse_area(
-                container("")
-                    .width(Length::Fill)
-                    .height(Length::Fill),
-            )
-            .on_press(Message::ConfirmDialogDismissed)
-            .into();
-
-            let dialog_card = container(
-                column![
-                    text(&dialog.message).size(14).color(Color::WHITE),
-                    row![
-                        button(text("Delete").size(14).color(Color::WHITE))
-                            .on_press(Message::ConfirmDialogAccepted)
-                            .padding([6, 16])
-                            .style(|_theme, _status| button::Style {
-                                background: Some(Background::Color(Color::from_rgb(0.8, 0.2, 0.2))),
-                                text_color: Color::WHITE,
-                                border: Border {
-                                    radius: 4.0.into(),
-                                    ..Default::default()
-                                },
-                                ..Default::default()
-                            }),
-                        button(text("Cancel").size(14).color(Color::WHITE))
-                            .on_press(Message::ConfirmDialogDismissed)
-                            .padding([6, 16])
-                            .style(|_theme, _status| button::Style {
-                                background: Some(Background::Color(Color::from_rgb(0.3, 0.3, 0.33))),
-                                text_color: Color::WHITE,
-                                border: Border {
-                                    radius: 4.0.into(),
-                                    ..Default::default()
-                                },
-                                ..Default::default()
-                            }),
-                    ]
-                    .spacing(8)
-                ]
-                .spacing(12),
-            )
-            .padding(20)
-            .width(400)
-            .style(|_theme| container::Style {
-                background: Some(Background::Color(Color::from_rgb(0.22, 0.22, 0.25))),
-                border: Border {
-                    color: Color::from_rgb(0.4, 0.4, 0.45),
-                    width: 1.0,
-                    radius: 8.0.into(),
-                },
-                ..Default::default()
-            });
-
-            let centered_dialog = center(dialog_card)
-                .width(Length::Fill)
-                .height(Length::Fill);
-
-            stack![base_layout, click_off, opaque(centered_dialog)]
+        // Toasts, or the history panel when open, float above the bottom-right corner
+        let base_layout: Element<'_, Message> = match self.view_notifications() {
+            Some(overlay) => stack![base_layout, overlay]
                 .width(Length::Fill)
                 .height(Length::Fill)
-                .into()
-        } else {
-            base_layout
+                .into(),
+            None => base_layout,
         };
 
+        let base_layout = self.view_dialogs(base_layout);
+        let base_layout = self.view_slideshow_dialog(base_layout);
+        let base_layout = self.view_preferences_dialog(base_layout);
+
         // Add drag ghost overlay if dragging
         let content: Element<'_, Message> = if let Some(drag) = &self.drag_state {
             let ghost = self.view_drag_overlay(drag);
@@ -1574,7 +3789,8 @@ impl App {
     }
 
     fn view_left_panel(&self) -> Element<'_, Message> {
-        let tab_button = |label: &'static str, tab: LeftPanelTab| -> Element<'_, Message> {
+        let tab_button = |key: &str, tab: LeftPanelTab| -> Element<'_, Message> {
+            let label = self.i18n.tr(key);
             let is_active = self.left_panel_tab == tab;
             button(text(label).size(13).color(Color::WHITE))
                 .on_press(Message::SwitchLeftPanelTab(tab))
@@ -1601,79 +3817,323 @@ impl App {
         };
 
         let tabs = row![
-            tab_button("Project Library", LeftPanelTab::ProjectLibrary),
-            tab_button("Effects", LeftPanelTab::Effects),
+            tab_button("tab-library", LeftPanelTab::ProjectLibrary),
+            tab_button("tab-effects", LeftPanelTab::Effects),
+            tab_button("tab-health", LeftPanelTab::TimelineHealth),
+            tab_button("tab-transcript", LeftPanelTab::Transcript),
+            tab_button("tab-snapshots", LeftPanelTab::Snapshots),
+            tab_button("tab-exports", LeftPanelTab::RenderRanges),
+            tab_button("tab-stats", LeftPanelTab::Statistics),
         ]
         .spacing(4);
 
         let content: Element<'_, Message> = match self.left_panel_tab {
             LeftPanelTab::ProjectLibrary => self.view_source_library_content(),
             LeftPanelTab::Effects => self.view_effects_browser(),
+            LeftPanelTab::TimelineHealth => self.view_timeline_health(),
+            LeftPanelTab::Transcript => self.view_transcript(),
+            LeftPanelTab::Snapshots => self.view_snapshots(),
+            LeftPanelTab::RenderRanges => self.view_render_ranges(),
+            LeftPanelTab::Statistics => self.view_project_stats(),
         };
 
         column![tabs, content]
             .spacing(8)
-            .width(300)
+            .width(self.preferences.layout.left_panel_width)
             .into()
     }
 
-    fn view_source_library_content(&self) -> Element<'_, Message> {
-        let import_btn =
-            button(text("Import").size(14)).on_press(Message::OpenFileDialog);
-
-        let assets = self.project.source_library.assets();
-        let mut grid_rows: Vec<Element<'_, Message>> = Vec::new();
+    fn view_effects_browser(&self) -> Element<'_, Message> {
+        let has_selection = self.selected_clip.is_some();
+        let search = text_input(&self.i18n.tr("effects-search"), &self.effect_search)
+            .on_input(Message::EffectSearchChanged)
+            .size(13)
+            .padding(4);
+        let mut items: Vec<Element<'_, Message>> = Vec::new();
 
-        // Build 2-column grid
-        let mut i = 0;
-        while i < assets.len() {
-            let card1 = self.view_source_card(&assets[i]);
-            if i + 1 < assets.len() {
-                let card2 = self.view_source_card(&assets[i + 1]);
-                grid_rows.push(row![card1, card2].spacing(6).into());
-            } else {
-                grid_rows.push(
-                    row![card1, Space::new().width(130)]
-                        .spacing(6)
+        for (category, effects) in self.visible_effects() {
+            items.push(
+                text(category.display_name())
+                    .size(12)
+                    .color(Color::from_rgb(0.6, 0.6, 0.65))
+                    .into(),
+            );
+            for effect_type in effects {
+                // Dragging the name onto a timeline clip applies the effect to it
+                let label = mouse_area(text(effect_type.display_name()).size(14).color(Color::WHITE))
+                    .on_press(Message::StartDragEffect(effect_type))
+                    .interaction(mouse::Interaction::Grab);
+                let mut add_btn = button(text(self.i18n.tr("effects-add-to-clip")).size(12))
+                    .padding([4, 8]);
+                if has_selection {
+                    add_btn = add_btn.on_press(Message::AddEffectToSelectedClip(effect_type));
+                }
+                items.push(
+                    row![label, Space::new().width(Length::Fill), add_btn]
+                        .spacing(8)
+                        .align_y(iced::Alignment::Center)
+                        .padding(Padding { top: 4.0, right: 0.0, bottom: 4.0, left: 8.0 })
                         .into(),
                 );
             }
-            i += 2;
         }
 
-        let asset_grid = scrollable(column(grid_rows).spacing(6));
+        let list: Element<'_, Message> = if items.is_empty() {
+            let message = if self.effect_search.trim().is_empty() {
+                "effects-none-available"
+            } else {
+                "effects-no-matches"
+            };
+            text(self.i18n.tr(message)).size(14).color(Color::from_rgb(0.5, 0.5, 0.5)).into()
+        } else {
+            scrollable(column(items).spacing(4)).into()
+        };
+
+        column![search, list].spacing(8).into()
+    }
+
+    /// Timeline Health tab: one row per issue, clicking a row jumps to it.
+    fn view_timeline_health(&self) -> Element<'_, Message> {
+        let issues = self.timeline_health();
+        if issues.is_empty() {
+            return text(self.i18n.tr("health-no-issues")).size(14).color(Color::from_rgb(0.5, 0.5, 0.5)).into();
+        }
+
+        let fps = self.project.settings.fps;
+        let frames = |d: Duration| format!("{:.1}f", d.as_secs_f64() * fps);
+        let mut rows: Vec<Element<'_, Message>> = Vec::new();
+        for issue in issues {
+            let (label, color) = match &issue.kind {
+                HealthIssueKind::Gap { duration } => {
+                    (self.i18n.tr_args("health-gap", &[("duration", &frames(*duration))]), Color::from_rgb(0.9, 0.8, 0.4))
+                }
+                HealthIssueKind::Overlap { duration, .. } => {
+                    (self.i18n.tr_args("health-overlap", &[("duration", &frames(*duration))]), Color::from_rgb(0.9, 0.6, 0.3))
+                }
+                HealthIssueKind::SourceOutOfRange { overrun } => {
+                    let label = self.i18n.tr_args("health-source-past-end", &[("duration", &frames(*overrun))]);
+                    (label, Color::from_rgb(0.9, 0.4, 0.4))
+                }
+                HealthIssueKind::MissingAsset { .. } => {
+                    (self.i18n.tr("health-missing-asset"), Color::from_rgb(0.9, 0.4, 0.4))
+                }
+                HealthIssueKind::MissingFile { path } => {
+                    let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+                    (self.i18n.tr_args("health-missing-file", &[("name", &name)]), Color::from_rgb(0.9, 0.4, 0.4))
+                }
+            };
+            let track_name = self
+                .project
+                .timeline
+                .tracks
+                .get(issue.track_index)
+                .map(|t| t.name.as_str())
+                .unwrap_or("?");
+            let secs = issue.position.as_secs_f64();
+            let location = format!("{track_name}  {:02}:{:05.2}", (secs / 60.0) as u64, secs % 60.0);
+            rows.push(
+                button(
+                    column![
+                        text(label).size(13).color(color),
+                        text(location).size(11).color(Color::from_rgb(0.6, 0.6, 0.65)),
+                    ]
+                    .spacing(2),
+                )
+                .on_press(Message::JumpToHealthIssue {
+                    track_index: issue.track_index,
+                    clip_id: issue.clip_id,
+                    position: issue.position,
+                })
+                .width(Length::Fill)
+                .padding([4, 8])
+                .style(|_theme, status| button::Style {
+                    background: Some(Background::Color(if matches!(status, button::Status::Hovered) {
+                        Color::from_rgb(0.25, 0.25, 0.28)
+                    } else {
+                        Color::from_rgb(0.2, 0.2, 0.22)
+                    })),
+                    text_color: Color::WHITE,
+                    border: Border { radius: 4.0.into(), ..Default::default() },
+                    ..Default::default()
+                })
+                .into(),
+            );
+        }
+
+        scrollable(column(rows).spacing(4)).into()
+    }
 
-        column![import_btn, asset_grid]
+    /// Statistics tab: a read-only summary of the project.
+    fn view_project_stats(&self) -> Element<'_, Message> {
+        let stats = self.project_stats();
+        let hms = |d: Duration| {
+            let secs = d.as_secs();
+            format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+        };
+        let heading = |key: &str| text(self.i18n.tr(key)).size(13).color(Color::from_rgb(0.6, 0.6, 0.65));
+        let stat_row = |label: String, value: String| -> Element<'_, Message> {
+            row![
+                text(label).size(13).color(Color::WHITE).width(Length::Fill),
+                text(value).size(13).color(Color::WHITE),
+            ]
             .spacing(8)
             .into()
+        };
+
+        let mut rows: Vec<Element<'_, Message>> = vec![
+            stat_row(self.i18n.tr("stats-timeline-duration"), hms(stats.duration)),
+            stat_row(self.i18n.tr("stats-source-used"), hms(stats.source_used)),
+            stat_row(self.i18n.tr("stats-source-imported"), hms(stats.source_imported)),
+            stat_row(self.i18n.tr("stats-export-size"), format_file_size(self.estimated_export_bytes())),
+            heading("stats-clips").into(),
+        ];
+        rows.extend(
+            stats
+                .tracks
+                .iter()
+                .map(|track| stat_row(track.name.clone(), track.clip_count.to_string())),
+        );
+        rows.push(stat_row(self.i18n.tr("stats-total"), stats.clip_count().to_string()));
+        rows.push(heading("stats-effects").into());
+        if stats.effect_counts.is_empty() {
+            rows.push(text(self.i18n.tr("stats-no-effects")).size(13).color(Color::from_rgb(0.5, 0.5, 0.5)).into());
+        }
+        rows.extend(
+            stats
+                .effect_counts
+                .iter()
+                .map(|(effect, count)| stat_row(effect.display_name().into(), count.to_string())),
+        );
+
+        scrollable(column(rows).spacing(6)).into()
     }
 
-    fn view_effects_browser(&self) -> Element<'_, Message> {
-        let has_selection = self.selected_clip.is_some();
-        let mut items: Vec<Element<'_, Message>> = Vec::new();
+    /// Snapshots tab: take a named snapshot, then compare the timeline
+    /// against it or restore it. Clicking a change seeks to it.
+    fn view_snapshots(&self) -> Element<'_, Message> {
+        let take = row![
+            text_input(&self.i18n.tr("snapshots-name"), &self.snapshot_name)
+                .on_input(Message::SnapshotNameChanged)
+                .on_submit(Message::TakeTimelineSnapshot)
+                .size(13)
+                .padding(4),
+            button(text(self.i18n.tr("snapshots-take")).size(13)).on_press(Message::TakeTimelineSnapshot),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center);
 
-        for effect_type in EffectType::all_builtin() {
-            let label = text(effect_type.display_name()).size(14).color(Color::WHITE);
-            let mut add_btn = button(text("Add to Clip").size(12))
-                .padding([4, 8]);
-            if has_selection {
-                add_btn = add_btn.on_press(Message::AddEffectToSelectedClip(effect_type));
-            }
-            items.push(
-                row![label, Space::new().width(Length::Fill), add_btn]
-                    .spacing(8)
-                    .align_y(iced::Alignment::Center)
-                    .padding([4, 0])
-                    .into(),
+        if self.project.snapshots.is_empty() {
+            return column![
+                take,
+                text(self.i18n.tr("snapshots-empty"))
+                    .size(14)
+                    .color(Color::from_rgb(0.5, 0.5, 0.5)),
+            ]
+            .spacing(8)
+            .into();
+        }
+
+        let mut rows: Vec<Element<'_, Message>> = Vec::new();
+        for snapshot in self.project.snapshots.iter().rev() {
+            let comparing = self.compared_snapshot == Some(snapshot.id);
+            let clip_count: usize = snapshot.timeline.tracks.iter().map(|t| t.clips.len()).sum();
+            rows.push(
+                row![
+                    column![
+                        text(snapshot.name.clone()).size(13).color(Color::WHITE),
+                        text(self.i18n.tr_args("snapshots-clip-count", &[("count", &clip_count)])).size(11).color(Color::from_rgb(0.6, 0.6, 0.65)),
+                    ]
+                    .width(Length::Fill),
+                    button(text(self.i18n.tr(if comparing { "snapshots-hide" } else { "snapshots-compare" })).size(12))
+                        .on_press(Message::CompareTimelineSnapshot(snapshot.id)),
+                    button(text(self.i18n.tr("snapshots-restore")).size(12))
+                        .on_press(Message::RestoreTimelineSnapshot(snapshot.id)),
+                    button(text(self.i18n.tr("snapshots-delete")).size(12))
+                        .on_press(Message::DeleteTimelineSnapshot(snapshot.id)),
+                ]
+                .spacing(4)
+                .align_y(iced::Alignment::Center)
+                .into(),
             );
+            if comparing {
+                rows.push(self.view_snapshot_changes(snapshot.id));
+            }
         }
 
-        if items.is_empty() {
-            column![text("No effects available").size(14).color(Color::from_rgb(0.5, 0.5, 0.5))]
-                .into()
-        } else {
-            scrollable(column(items).spacing(4)).into()
+        column![take, scrollable(column(rows).spacing(6))].spacing(8).into()
+    }
+
+    /// Differences between the timeline and snapshot `id`, one button per clip.
+    fn view_snapshot_changes(&self, id: Uuid) -> Element<'_, Message> {
+        let changes = self.project.diff_snapshot(id).unwrap_or_default();
+        if changes.is_empty() {
+            return text(self.i18n.tr("snapshots-no-changes")).size(12).color(Color::from_rgb(0.5, 0.5, 0.5)).into();
+        }
+        let snapshot_tracks = self.project.snapshot(id).map(|s| &s.timeline.tracks);
+        let mut rows: Vec<Element<'_, Message>> = Vec::new();
+        for change in changes {
+            let (label, color) = match change.kind {
+                ClipChangeKind::Added => (self.i18n.tr("snapshots-added"), Color::from_rgb(0.45, 0.8, 0.45)),
+                ClipChangeKind::Removed => (self.i18n.tr("snapshots-removed"), Color::from_rgb(0.9, 0.4, 0.4)),
+                ClipChangeKind::Changed { moved, trimmed, effects } => {
+                    let parts: Vec<String> = [
+                        (moved, "snapshots-moved"),
+                        (trimmed, "snapshots-trimmed"),
+                        (effects, "snapshots-effects-changed"),
+                    ]
+                    .into_iter()
+                    .filter_map(|(changed, key)| changed.then(|| self.i18n.tr(key)))
+                    .collect();
+                    let joined = parts.join(", ");
+                    let mut chars = joined.chars();
+                    let label = chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default();
+                    (label, Color::from_rgb(0.9, 0.8, 0.4))
+                }
+            };
+            let removed = change.kind == ClipChangeKind::Removed;
+            // Removed clips are only in the snapshot, so name their track from there
+            let tracks = if removed { snapshot_tracks } else { Some(&self.project.timeline.tracks) };
+            let track_name = tracks
+                .and_then(|t| t.get(change.track_index))
+                .map(|t| t.name.as_str())
+                .unwrap_or("?");
+            let asset_name = self
+                .project
+                .source_library
+                .get(change.asset_id)
+                .map(|a| a.name.as_str())
+                .unwrap_or("?");
+            let secs = change.position.as_secs_f64();
+            let location = format!("{asset_name}  {track_name}  {:02}:{:05.2}", (secs / 60.0) as u64, secs % 60.0);
+            rows.push(
+                button(
+                    column![
+                        text(label).size(12).color(color),
+                        text(location).size(11).color(Color::from_rgb(0.6, 0.6, 0.65)),
+                    ]
+                    .spacing(2),
+                )
+                .on_press(Message::JumpToHealthIssue {
+                    track_index: change.track_index,
+                    clip_id: (!removed).then_some(change.clip_id),
+                    position: change.position,
+                })
+                .width(Length::Fill)
+                .padding([4, 8])
+                .style(|_theme, status| button::Style {
+                    background: Some(Background::Color(if matches!(status, button::Status::Hovered) {
+                        Color::from_rgb(0.25, 0.25, 0.28)
+                    } else {
+                        Color::from_rgb(0.2, 0.2, 0.22)
+                    })),
+                    text_color: Color::WHITE,
+                    border: Border { radius: 4.0.into(), ..Default::default() },
+                    ..Default::default()
+                })
+                .into(),
+            );
         }
+        column(rows).spacing(4).padding([0, 8]).into()
     }
 
     fn view_clip_effects_inspector(&self) -> Element<'_, Message> {
@@ -1681,9 +4141,9 @@ impl App {
             Some(sel) => sel,
             None => {
                 return container(
-                    text("No clip selected").size(13).color(Color::from_rgb(0.5, 0.5, 0.5))
+                    text(self.i18n.tr("inspector-no-clip")).size(13).color(Color::from_rgb(0.5, 0.5, 0.5))
                 )
-                .width(250)
+                .width(self.preferences.layout.inspector_width)
                 .padding(8)
                 .into();
             }
@@ -1697,28 +4157,234 @@ impl App {
             Some(c) => c,
             None => {
                 return container(
-                    text("Clip not found").size(13).color(Color::from_rgb(0.5, 0.5, 0.5))
+                    text(self.i18n.tr("inspector-clip-not-found")).size(13).color(Color::from_rgb(0.5, 0.5, 0.5))
                 )
-                .width(250)
+                .width(self.preferences.layout.inspector_width)
                 .padding(8)
                 .into();
             }
         };
 
-        let title = text("Clip Effects").size(16).color(Color::WHITE);
+        let title = text(self.i18n.tr("inspector-title")).size(16).color(Color::WHITE);
         let mut items: Vec<Element<'_, Message>> = vec![title.into()];
 
+        let mut speed_row = row![
+            text(self.i18n.tr_args("inspector-speed", &[("percent", &format!("{:.0}", clip.speed() * 100.0))]))
+                .size(12)
+                .color(Color::from_rgb(0.7, 0.7, 0.7)),
+            Space::new().width(Length::Fill),
+        ]
+        .spacing(4)
+        .align_y(iced::Alignment::Center);
+        for speed in CLIP_SPEED_PRESETS {
+            speed_row = speed_row.push(
+                button(text(format!("{:.0}%", speed * 100.0)).size(11))
+                    .on_press(Message::SetClipSpeed { track_index, clip_id, speed })
+                    .padding([2, 4]),
+            );
+        }
+        let keep_pitch = if clip.preserve_pitch {
+            format!("{} \u{2713}", self.i18n.tr("inspector-keep-pitch"))
+        } else {
+            self.i18n.tr("inspector-keep-pitch")
+        };
+        speed_row = speed_row.push(
+            button(text(keep_pitch).size(11))
+                .on_press(Message::SetClipPreservePitch {
+                    track_index,
+                    clip_id,
+                    preserve: !clip.preserve_pitch,
+                })
+                .padding([2, 4]),
+        );
+        items.push(speed_row.into());
+
+        let mut channel_row = row![
+            text(self.i18n.tr_args("inspector-channels", &[("map", &clip.channel_map.display_name())]))
+                .size(12)
+                .color(Color::from_rgb(0.7, 0.7, 0.7)),
+            Space::new().width(Length::Fill),
+        ]
+        .spacing(4)
+        .align_y(iced::Alignment::Center);
+        for map in ChannelMap::ALL {
+            channel_row = channel_row.push(
+                button(text(map.display_name()).size(11))
+                    .on_press(Message::SetClipChannelMap { track_index, clip_id, map })
+                    .padding([2, 4]),
+            );
+        }
+        items.push(channel_row.into());
+
+        let is_video = self
+            .project
+            .timeline
+            .track(track_index)
+            .is_ok_and(|t| t.track_type == TrackType::Video);
+        if is_video {
+            let mut fit_row = row![
+                text(self.i18n.tr_args("inspector-fit", &[("fit", &clip.canvas_fit.display_name())]))
+                    .size(12)
+                    .color(Color::from_rgb(0.7, 0.7, 0.7)),
+                Space::new().width(Length::Fill),
+            ]
+            .spacing(4)
+            .align_y(iced::Alignment::Center);
+            for fit in CanvasFit::ALL {
+                fit_row = fit_row.push(
+                    button(text(fit.display_name()).size(11))
+                        .on_press(Message::SetClipCanvasFit { track_index, clip_id, fit })
+                        .padding([2, 4]),
+                );
+            }
+            items.push(fit_row.into());
+            if !clip.pan.is_empty() {
+                items.push(
+                    row![
+                        text(self.i18n.tr_args("inspector-reframed", &[("count", &clip.pan.keys.len())]))
+                            .size(12)
+                            .color(Color::from_rgb(0.7, 0.7, 0.7)),
+                        Space::new().width(Length::Fill),
+                        button(text(self.i18n.tr("inspector-clear-reframe")).size(11))
+                            .on_press(Message::ClearClipReframe { track_index, clip_id })
+                            .padding([2, 4]),
+                    ]
+                    .spacing(4)
+                    .align_y(iced::Alignment::Center)
+                    .into(),
+                );
+            }
+        }
+
+        if self.project.has_mismatched_fps(clip.asset_id) {
+            let source_fps = self.project.source_library.get(clip.asset_id).map_or(0.0, |a| a.fps);
+            let mut conform_row = row![
+                text(format!("{source_fps:.3} fps: {}", clip.rate_conform.display_name()))
+                    .size(12)
+                    .color(Color::from_rgb(0.7, 0.7, 0.7)),
+                Space::new().width(Length::Fill),
+            ]
+            .spacing(4)
+            .align_y(iced::Alignment::Center);
+            for conform in RateConform::ALL {
+                conform_row = conform_row.push(
+                    button(text(conform.display_name()).size(11))
+                        .on_press(Message::SetClipRateConform { track_index, clip_id, conform })
+                        .padding([2, 4]),
+                );
+            }
+            items.push(conform_row.into());
+        }
+
+        match self.inspector_waveform.as_ref().filter(|w| w.clip_id == clip_id) {
+            Some(waveform) => {
+                items.push(
+                    text(self.i18n.tr_args(
+                        "inspector-trim-source",
+                        &[
+                            ("start", &format!("{:.2}", clip.source_range.start.as_secs_f64())),
+                            ("end", &format!("{:.2}", clip.source_range.end.as_secs_f64())),
+                        ],
+                    ))
+                    .size(12)
+                    .color(Color::from_rgb(0.7, 0.7, 0.7))
+                    .into(),
+                );
+                items.push(
+                    iced::widget::canvas(WaveformEditor {
+                        waveform,
+                        track_index,
+                        clip_id,
+                        source: clip.source_range,
+                    })
+                    .width(Length::Fill)
+                    .height(80)
+                    .into(),
+                );
+            }
+            None => items.push(
+                button(text(self.i18n.tr("inspector-show-waveform")).size(11))
+                    .on_press(Message::LoadInspectorWaveform { track_index, clip_id })
+                    .padding([2, 6])
+                    .into(),
+            ),
+        }
+
+        // Clip markers: click the time to seek, edit the name and press Enter
+        items.push(
+            row![
+                text(self.i18n.tr("inspector-markers")).size(12).color(Color::from_rgb(0.7, 0.7, 0.7)),
+                Space::new().width(Length::Fill),
+                button(text(self.i18n.tr("inspector-add-marker")).size(11))
+                    .on_press(Message::AddClipMarker)
+                    .padding([2, 6]),
+            ]
+            .align_y(iced::Alignment::Center)
+            .into(),
+        );
+        for marker in &clip.markers {
+            let marker_id = marker.id;
+            let timeline_secs = clip.timeline_secs_at(marker.source_time.as_secs_f64());
+            let name = match &self.clip_marker_edit {
+                Some((id, name)) if *id == marker_id => name.as_str(),
+                _ => marker.name.as_str(),
+            };
+            items.push(
+                row![
+                    button(text(format!("{timeline_secs:.2}s")).size(11))
+                        .on_press(Message::SeekTo(TimelinePosition::from_secs_f64(timeline_secs)))
+                        .padding([2, 4]),
+                    text_input(&self.i18n.tr("inspector-marker-name"), name)
+                        .on_input(move |name| Message::ClipMarkerNameEdited { marker_id, name })
+                        .on_submit(Message::RenameClipMarker { track_index, clip_id, marker_id })
+                        .size(11)
+                        .padding(2),
+                    button(text("x").size(11))
+                        .on_press(Message::RemoveClipMarker { track_index, clip_id, marker_id })
+                        .padding([2, 6]),
+                ]
+                .spacing(4)
+                .align_y(iced::Alignment::Center)
+                .into(),
+            );
+        }
+
         if clip.effects.is_empty() {
             items.push(
-                text("No effects").size(13).color(Color::from_rgb(0.5, 0.5, 0.5)).into()
+                text(self.i18n.tr("inspector-no-effects")).size(13).color(Color::from_rgb(0.5, 0.5, 0.5)).into()
             );
         } else {
+            // A/B compare against the unprocessed frame (the \ key toggles it too)
+            let bypassed = self.effects_bypassed;
+            items.push(
+                row![
+                    text(self.i18n.tr(if bypassed { "inspector-effects-bypassed" } else { "inspector-effects-on" }))
+                        .size(12)
+                        .color(Color::from_rgb(0.7, 0.7, 0.7)),
+                    Space::new().width(Length::Fill),
+                    button(text("A/B").size(11))
+                        .on_press(Message::ToggleEffectsBypass)
+                        .padding([2, 6])
+                        .style(move |_theme, _status| button::Style {
+                            background: Some(Background::Color(if bypassed {
+                                Color::from_rgb(0.85, 0.6, 0.15)
+                            } else {
+                                Color::from_rgb(0.25, 0.25, 0.28)
+                            })),
+                            text_color: Color::WHITE,
+                            border: Border { radius: 3.0.into(), ..Default::default() },
+                            ..Default::default()
+                        }),
+                ]
+                .align_y(iced::Alignment::Center)
+                .into(),
+            );
             for effect in &clip.effects {
                 let effect_name = text(effect.effect_type.display_name())
                     .size(14)
                     .color(Color::from_rgb(0.9, 0.9, 0.9));
 
-                let remove_btn = button(text("Remove").size(11))
+                let remove_btn = button(text(self.i18n.tr("inspector-remove-effect")).size(11))
                     .on_press(Message::RemoveEffectFromClip {
                         track_index,
                         clip_id,
@@ -1744,13 +4410,18 @@ impl App {
                     let effect_id = effect.id;
                     let param_name = def.name.clone();
 
-                    let zeditor_core::effects::ParameterType::Float { min, max, .. } = def.param_type;
+                    let zeditor_core::effects::ParameterType::Float { default, min, max } = def.param_type;
 
                     // Use percentage display for 0-1 range parameters
                     let is_percentage = min == 0.0 && max == 1.0;
                     let wide_range = (max - min) > 100.0;
 
-                    let param_label = if is_percentage {
+                    let param_label = if self.effect_param_mixed(effect_id, &def.name, current_val) {
+                        // The other selected clips differ; editing sets them all
+                        text(self.i18n.tr_args("inspector-parameter-mixed", &[("label", &def.label)]))
+                            .size(12)
+                            .color(Color::from_rgb(0.85, 0.6, 0.15))
+                    } else if is_percentage {
                         text(format!("{}: {:.0}%", def.label, current_val * 100.0))
                             .size(12)
                             .color(Color::from_rgb(0.7, 0.7, 0.7))
@@ -1768,6 +4439,19 @@ impl App {
                         text(val_str).size(12).color(Color::from_rgb(0.7, 0.7, 0.7))
                     };
 
+                    // Enabled only once the value has moved off its default
+                    let reset_btn = button(text(self.i18n.tr("inspector-reset-parameter")).size(10))
+                        .on_press_maybe((current_val != default).then(|| Message::UpdateEffectParameter {
+                            track_index,
+                            clip_id,
+                            effect_id,
+                            param_name: param_name.clone(),
+                            value: default,
+                        }))
+                        .padding([1, 4]);
+                    let param_header = row![param_label, Space::new().width(Length::Fill), reset_btn]
+                        .align_y(iced::Alignment::Center);
+
                     let param_control: Element<'_, Message> = if wide_range {
                         // Wide-range params get a text input for precise entry
                         let text_key = (effect_id, def.name.clone());
@@ -1811,7 +4495,7 @@ impl App {
                     };
 
                     items.push(
-                        column![param_label, param_control]
+                        column![param_header, param_control]
                             .spacing(2)
                             .padding(Padding { top: 0.0, right: 0.0, bottom: 0.0, left: 8.0 })
                             .into()
@@ -1821,7 +4505,7 @@ impl App {
         }
 
         container(scrollable(column(items).spacing(6)))
-            .width(250)
+            .width(self.preferences.layout.inspector_width)
             .padding(8)
             .style(|_theme| container::Style {
                 background: Some(Background::Color(Color::from_rgb(0.16, 0.16, 0.18))),
@@ -1835,71 +4519,11 @@ impl App {
             .into()
     }
 
-    fn view_source_card<'a>(&'a self, asset: &'a zeditor_core::media::MediaAsset) -> Element<'a, Message> {
-        let is_hovered = self.hovered_asset_id == Some(asset.id);
-        let is_selected = self.selected_asset_id == Some(asset.id);
-        let asset_id = asset.id;
-
-        // Thumbnail or placeholder
-        let thumb_content: Element<'_, Message> = if let Some(handle) = self.thumbnails.get(&asset_id) {
-            image(handle.clone())
-                .width(120)
-                .height(68)
-                .content_fit(iced::ContentFit::Contain)
-                .into()
-        } else {
-            container(center(text("...").size(14).color(Color::from_rgb(0.5, 0.5, 0.5))))
-                .width(120)
-                .height(68)
-                .style(|_theme| container::Style {
-                    background: Some(Background::Color(Color::from_rgb(0.2, 0.2, 0.22))),
-                    ..Default::default()
-                })
-                .into()
-        };
-
-        let border_color = if is_selected {
-            Color::from_rgb(1.0, 0.2, 0.2)
-        } else if is_hovered {
-            Color::from_rgb(0.3, 0.5, 0.9)
-        } else {
-            Color::TRANSPARENT
-        };
-
-        let name_label = text(&asset.name)
-            .size(11)
-            .color(Color::WHITE)
-            .width(120)
-            .center();
-
-        let card = container(
-            column![thumb_content, name_label].spacing(2).align_x(iced::Alignment::Center),
-        )
-        .padding(4)
-        .width(130)
-        .style(move |_theme| container::Style {
-            background: Some(Background::Color(Color::from_rgb(0.18, 0.18, 0.20))),
-            border: Border {
-                color: border_color,
-                width: 2.0,
-                radius: 6.0.into(),
-            },
-            ..Default::default()
-        });
-
-        mouse_area(card)
-            .on_enter(Message::SourceCardHovered(Some(asset_id)))
-            .on_exit(Message::SourceCardHovered(None))
-            .on_press(Message::StartDragFromSource(asset_id))
-            .on_release(Message::SelectSourceAsset(Some(asset_id)))
-            .into()
-    }
-
     fn view_video_viewport(&self) -> Element<'_, Message> {
         let play_pause = if self.is_playing {
-            button(text("Pause").size(14)).on_press(Message::Pause)
+            button(text(self.i18n.tr("playback-pause")).size(14)).on_press(Message::Pause)
         } else {
-            button(text("Play").size(14)).on_press(Message::Play)
+            button(text(self.i18n.tr("playback-play")).size(14)).on_press(Message::Play)
         };
 
         let position = text(format!(
@@ -1910,59 +4534,252 @@ impl App {
 
         // Compute inner canvas width from project aspect ratio to show
         // letterboxing/pillarboxing that approximates the rendered output.
-        let viewport_height: f32 = 300.0;
+        let viewport_height = self.preferences.layout.preview_height;
         let canvas_aspect = self.project.settings.canvas_width as f32
             / self.project.settings.canvas_height as f32;
         let canvas_preview_width = (viewport_height * canvas_aspect).round();
 
-        let video_area: Element<'_, Message> = if let Some(handle) = &self.current_frame {
-            // Black inner container at canvas aspect ratio with the video inside
-            let inner = container(
-                iced::widget::image(handle.clone())
-                    .content_fit(iced::ContentFit::Contain)
+        let frame_content: Element<'_, Message> = if let Some(handle) = &self.current_frame {
+            iced::widget::image(handle.clone())
+                .content_fit(iced::ContentFit::Contain)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into()
+        } else {
+            center(text(self.i18n.tr("preview-no-video")).size(16)).into()
+        };
+
+        // While a clip end is dragged, the frames either side of the edit
+        // replace the preview: outgoing on the left, incoming on the right
+        let frame_content: Element<'_, Message> = match &self.trim_two_up {
+            Some(two_up) => {
+                let side = |handle: &Option<iced::widget::image::Handle>, key: &str| -> Element<'_, Message> {
+                    let picture: Element<'_, Message> = match handle {
+                        Some(handle) => iced::widget::image(handle.clone())
+                            .content_fit(iced::ContentFit::Contain)
+                            .width(Length::Fill)
+                            .height(Length::Fill)
+                            .into(),
+                        None => center(text("-").size(16)).into(),
+                    };
+                    column![picture, text(self.i18n.tr(key)).size(11).color(Color::WHITE)]
+                        .align_x(iced::Alignment::Center)
+                        .width(Length::Fill)
+                        .into()
+                };
+                row![side(&two_up.outgoing, "preview-outgoing"), side(&two_up.incoming, "preview-incoming")]
+                    .spacing(2)
+                    .into()
+            }
+            None => frame_content,
+        };
+
+        // Transparent regions of the frame show the chosen backdrop behind them.
+        let frame_content: Element<'_, Message> = stack![
+            iced::widget::canvas(PreviewBackdrop::new(self.preview_background))
+                .width(Length::Fill)
+                .height(Length::Fill),
+            frame_content,
+        ]
+        .into();
+
+        // Playback stats and memory usage sit in the top-left corner of the frame.
+        let mut debug_lines = Vec::new();
+        if self.show_playback_stats {
+            debug_lines.push(self.playback_stats.summary(self.is_playing));
+        }
+        if self.show_memory_overlay {
+            debug_lines.push(self.memory.summary());
+        }
+        let frame_content: Element<'_, Message> = if debug_lines.is_empty() {
+            frame_content
+        } else {
+            let stats = container(
+                text(debug_lines.join("\n"))
+                    .size(11)
+                    .color(Color::WHITE),
+            )
+            .padding([2, 6])
+            .style(|_theme| container::Style {
+                background: Some(Background::Color(Color::from_rgba(0.0, 0.0, 0.0, 0.6))),
+                ..Default::default()
+            });
+            stack![frame_content, stats].into()
+        };
+
+        // Framing guides are drawn above the frame, covering the whole canvas box.
+        let frame_content: Element<'_, Message> = if self.preview_overlay.any() {
+            stack![
+                frame_content,
+                iced::widget::canvas(self.preview_overlay)
                     .width(Length::Fill)
                     .height(Length::Fill),
-            )
+            ]
+            .into()
+        } else {
+            frame_content
+        };
+
+        // Black inner container at canvas aspect ratio with the video inside
+        let inner = container(frame_content)
             .width(canvas_preview_width)
             .height(viewport_height)
             .style(|_theme| container::Style {
                 background: Some(Background::Color(Color::BLACK)),
                 ..Default::default()
             });
-            // Outer dark container centers the inner canvas box
-            container(center(inner))
-                .width(Length::Fill)
-                .height(viewport_height)
-                .style(container::dark)
-                .into()
-        } else {
-            // Black canvas box with "No video" text
-            let inner = container(center(text("No video").size(16)))
-                .width(canvas_preview_width)
-                .height(viewport_height)
-                .style(|_theme| container::Style {
-                    background: Some(Background::Color(Color::BLACK)),
-                    ..Default::default()
-                });
-            container(center(inner))
-                .width(Length::Fill)
-                .height(viewport_height)
-                .style(container::dark)
-                .into()
+        // Outer dark container centers the inner canvas box
+        let video_area: Element<'_, Message> = container(center(inner))
+            .width(Length::Fill)
+            .height(viewport_height)
+            .style(container::dark)
+            .into();
+
+        let loop_label = if self.loop_playback { "preview-loop-on" } else { "preview-loop-off" };
+        let loop_btn = button(text(self.i18n.tr(loop_label)).size(14)).on_press(Message::ToggleLoopPlayback);
+
+        let checked = |label: String, on: bool| if on { format!("{label} \u{2713}") } else { label };
+        let overlay_btn = |label: String, kind: PreviewOverlayKind| {
+            let label = checked(label, self.preview_overlay.is_enabled(kind));
+            button(text(label).size(12)).on_press(Message::TogglePreviewOverlay(kind))
         };
 
-        let controls = row![play_pause, position].spacing(8);
+        // The canvas button cycles through the presets, starting from the one
+        // matching the current shape (or 16:9 for a custom canvas)
+        let (canvas_width, canvas_height) = (self.project.settings.canvas_width, self.project.settings.canvas_height);
+        let current_preset = CanvasPreset::matching(canvas_width, canvas_height);
+        let canvas_label = match current_preset {
+            Some(preset) => preset.label().to_string(),
+            None => format!("{canvas_width}x{canvas_height}"),
+        };
+        let next_canvas = current_preset.map_or(CanvasPreset::Widescreen, CanvasPreset::next);
+
+        let timecode_field = text_input(
+            "00:00:00:00",
+            self.timecode_input.as_deref().unwrap_or(&timecode::to_timecode(
+                self.playback_position,
+                self.project.settings.fps,
+            )),
+        )
+        .on_input(Message::TimecodeInputChanged)
+        .on_submit(Message::TimecodeSubmitted)
+        .size(13)
+        .padding(2)
+        .width(100);
+
+        let controls = row![
+            play_pause,
+            loop_btn,
+            position,
+            timecode_field,
+            Space::new().width(Length::Fill),
+            overlay_btn(self.i18n.tr("preview-safe-areas"), PreviewOverlayKind::SafeAreas),
+            overlay_btn(self.i18n.tr("preview-thirds-grid"), PreviewOverlayKind::ThirdsGrid),
+            overlay_btn(self.i18n.tr("preview-center-cross"), PreviewOverlayKind::CenterCross),
+            overlay_btn("9:16".to_string(), PreviewOverlayKind::VerticalCrop),
+            button(text(self.i18n.tr_args("preview-canvas", &[("canvas", &canvas_label)])).size(12))
+                .on_press(Message::SetCanvasPreset(next_canvas)),
+            button(text(self.i18n.tr_args(
+                "preview-background",
+                &[("background", &self.i18n.tr(self.preview_background.label_key()))],
+            )).size(12))
+                .on_press(Message::SetPreviewBackground(self.preview_background.next())),
+            button(text(checked(self.i18n.tr("preview-stats"), self.show_playback_stats)).size(12))
+                .on_press(Message::TogglePlaybackStats),
+            button(text(checked(self.i18n.tr("preview-memory"), self.show_memory_overlay)).size(12))
+                .on_press(Message::ToggleMemoryOverlay),
+        ]
+        .spacing(8);
+
+        // Play-around (/ key): preroll and postroll around the playhead
+        let play_around = row![
+            button(text(self.i18n.tr("preview-play-around")).size(12)).on_press(Message::PlayAround),
+            text(self.i18n.tr_args("preview-preroll", &[("secs", &format!("{:.1}", self.play_around_preroll))])).size(12),
+            slider(0.0..=MAX_PLAY_AROUND_SECS, self.play_around_preroll, Message::SetPlayAroundPreroll)
+                .step(0.5)
+                .width(80),
+            text(self.i18n.tr_args("preview-postroll", &[("secs", &format!("{:.1}", self.play_around_postroll))])).size(12),
+            slider(0.0..=MAX_PLAY_AROUND_SECS, self.play_around_postroll, Message::SetPlayAroundPostroll)
+                .step(0.5)
+                .width(80),
+            Space::new().width(Length::Fill),
+            button(text(checked(self.i18n.tr("preview-prerender"), self.selected_clip_prerender_marked())).size(12))
+                .on_press(Message::TogglePrerenderSelectedClip),
+            button(text(self.background_render_label()).size(12)).on_press(Message::ToggleBackgroundRender),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center);
 
-        column![video_area, controls]
+        column![video_area, controls, play_around]
             .spacing(4)
             .width(Length::Fill)
             .into()
     }
 
     fn view_timeline(&self) -> Element<'_, Message> {
-        let undo_btn = button(text("Undo").size(12)).on_press(Message::Undo);
-        let redo_btn = button(text("Redo").size(12)).on_press(Message::Redo);
-        let controls = row![undo_btn, redo_btn].spacing(5);
+        let undo_btn = button(text(self.i18n.tr("menu-undo")).size(12)).on_press(Message::Undo);
+        let redo_btn = button(text(self.i18n.tr("menu-redo")).size(12)).on_press(Message::Redo);
+        let zoom_out_btn = button(text("-").size(12)).on_press(Message::TimelineZoomStep(-1));
+        let zoom_in_btn = button(text("+").size(12)).on_press(Message::TimelineZoomStep(1));
+        let fit_btn = button(text(self.i18n.tr("timeline-fit")).size(12)).on_press(Message::ZoomToFit);
+        let find = text_input(&self.i18n.tr("timeline-find"), &self.timeline_search)
+            .on_input(Message::TimelineSearchChanged)
+            .on_submit(Message::TimelineSearchNext)
+            .size(12)
+            .padding(3)
+            .width(200);
+        let match_count = if self.timeline_search.trim().is_empty() {
+            String::new()
+        } else {
+            let count = self.timeline_search_matches().len();
+            match self.timeline_search_cursor {
+                Some(cursor) if count > 0 => {
+                    self.i18n.tr_args("timeline-match-position", &[("current", &(cursor + 1)), ("count", &count)])
+                }
+                _ => self.i18n.tr_args("timeline-matches-found", &[("count", &count)]),
+            }
+        };
+        let guides = row![
+            text(self.i18n.tr("timeline-guides-every")).size(12),
+            text_input("1", &self.guide_interval)
+                .on_input(Message::GuideIntervalChanged)
+                .on_submit(Message::GenerateGuides)
+                .size(12)
+                .padding(3)
+                .width(50),
+            button(text(self.i18n.tr(if self.guide_interval_in_frames { "timeline-guide-frames" } else { "timeline-guide-seconds" })).size(12))
+                .on_press(Message::ToggleGuideIntervalUnit),
+            button(text(self.i18n.tr("timeline-add-guides")).size(12)).on_press(Message::GenerateGuides),
+            button(text(self.i18n.tr("timeline-clear-guides")).size(12))
+                .on_press_maybe((!self.project.timeline.guides.is_empty()).then_some(Message::ClearGuides)),
+        ]
+        .spacing(4)
+        .align_y(iced::Alignment::Center);
+        let insert_time = row![
+            text_input("1", &self.insert_time_secs)
+                .on_input(Message::InsertTimeDurationChanged)
+                .on_submit(Message::InsertTimeAtPlayhead)
+                .size(12)
+                .padding(3)
+                .width(50),
+            button(text(self.i18n.tr("timeline-insert-time")).size(12)).on_press(Message::InsertTimeAtPlayhead),
+        ]
+        .spacing(4)
+        .align_y(iced::Alignment::Center);
+        let controls = row![
+            undo_btn,
+            redo_btn,
+            guides,
+            insert_time,
+            Space::new().width(Length::Fill),
+            find,
+            text(match_count).size(12).color(Color::from_rgb(0.6, 0.6, 0.65)),
+            Space::new().width(Length::Fill),
+            zoom_out_btn,
+            zoom_in_btn,
+            fit_btn
+        ]
+        .spacing(5);
 
         // Compute source drag preview for the canvas
         let source_drag = self.compute_source_drag_preview();
@@ -1971,18 +4788,30 @@ impl App {
             timeline: &self.project.timeline,
             playback_position: self.playback_position,
             selected_clip: self.selected_clip,
+            selected_clips: &self.selected_clips,
+            source_library: Some(&self.project.source_library),
             zoom: self.timeline_zoom,
             scroll_offset: self.timeline_scroll,
+            vertical_scroll: self.effective_vertical_scroll(),
             tool_mode: self.tool_mode,
             source_drag,
+            snap_threshold_secs: self.preferences.snap_threshold().as_secs_f64(),
+            snap_to_markers: self.preferences.snap_to_markers,
+            snap_to_playhead: self.preferences.snap_to_playhead,
+            snap_audio_trims: self.preferences.snap_audio_trims,
         })
         .width(Length::Fill)
         .height(Length::Fill);
 
-        // Track headers column (fixed width, left of canvas)
-        let header_width: f32 = 60.0;
-        let ruler_height: f32 = 20.0;
-        let track_height: f32 = 50.0;
+        // Track headers column (sized to the longest track name, left of canvas)
+        let header_width = track_header::header_width(&self.project.timeline);
+        let ruler_height = timeline_canvas::RULER_HEIGHT;
+        let v_scroll = self.effective_vertical_scroll();
+        // Headers scrolled fully out of view are skipped; the first visible one may be cut off.
+        let timeline = &self.project.timeline;
+        let first_visible =
+            timeline_canvas::track_index_at_offset(timeline, v_scroll).unwrap_or(timeline.tracks.len());
+        let first_hidden_px = v_scroll - timeline_canvas::track_offset(timeline, first_visible);
 
         let mut header_items: Vec<Element<'_, Message>> = Vec::new();
         // Ruler-height spacer at top to align with canvas ruler
@@ -1993,42 +4822,43 @@ impl App {
                 .into()
         );
 
-        for (i, track) in self.project.timeline.tracks.iter().enumerate() {
-            let bg_color = match track.track_type {
-                TrackType::Video => Color::from_rgb(0.16, 0.18, 0.16),
-                TrackType::Audio => Color::from_rgb(0.14, 0.16, 0.20),
-            };
-            let header = container(
-                text(&track.name)
-                    .size(12)
-                    .color(Color::from_rgb(0.8, 0.8, 0.8))
-            )
-            .padding([4, 6])
-            .width(header_width)
-            .height(track_height)
-            .style(move |_theme| container::Style {
-                background: Some(Background::Color(bg_color)),
-                border: Border {
-                    color: Color::from_rgb(0.3, 0.3, 0.35),
-                    width: 0.0,
-                    radius: 0.0.into(),
-                },
-                ..Default::default()
-            });
+        for (i, track) in self.project.timeline.tracks.iter().enumerate().skip(first_visible) {
+            let track_height = timeline_canvas::track_height(track);
+            if i == first_visible && first_hidden_px > 0.0 {
+                header_items.push(track_header::view_partial(
+                    track,
+                    header_width,
+                    track_height - first_hidden_px,
+                ));
+                continue;
+            }
+            let level = self.is_playing.then(|| self.audio_meter_level(i));
+            let header = track_header::view(i, track, header_width, track_height, level);
             // Wrap in mouse_area to detect right-click for context menu
             let header_with_menu: Element<'_, Message> = mouse_area(header)
                 .on_right_press(Message::ShowTrackContextMenu {
                     track_index: i,
                     screen_position: Point::new(
                         header_width,
-                        ruler_height + i as f32 * track_height,
+                        ruler_height + timeline_canvas::track_offset(timeline, i) - v_scroll,
                     ),
                 })
                 .into();
             header_items.push(header_with_menu);
         }
 
-        let header_col: Element<'_, Message> = column(header_items).into();
+        // Wheel over the headers scrolls the tracks vertically.
+        let header_col: Element<'_, Message> = mouse_area(
+            container(column(header_items)).height(Length::Fill).clip(true),
+        )
+        .on_scroll(|delta| {
+            let dy = match delta {
+                mouse::ScrollDelta::Lines { y, .. } => y * 20.0,
+                mouse::ScrollDelta::Pixels { y, .. } => y,
+            };
+            Message::TimelineVerticalScroll(-dy)
+        })
+        .into();
 
         let timeline_row: Element<'_, Message> = row![header_col, canvas]
             .height(Length::Fill)
@@ -2057,22 +4887,18 @@ impl App {
             let menu_y = ctx.position.y;
             let menu_x = ctx.position.x;
 
-            let click_off: Element<'_, Message> = mouse_area(
-                container("")
-                    .width(Length::Fill)
-                    .height(Length::Fill),
-            )
-            .on_press(Message::DismissTrackContextMenu)
-            .into();
+            let click_off = dialog::click_off(Message::DismissTrackContextMenu);
 
             let menu_items: Vec<Element<'_, Message>> = match track_type {
                 TrackType::Video => vec![
-                    self.context_menu_item("Add Video Track Above", Message::AddVideoTrackAbove(track_index)),
-                    self.context_menu_item("Add Video Track Below", Message::AddVideoTrackBelow(track_index)),
+                    self.context_menu_item(self.i18n.tr("menu-add-video-track-above"), Message::AddVideoTrackAbove(track_index)),
+                    self.context_menu_item(self.i18n.tr("menu-add-video-track-below"), Message::AddVideoTrackBelow(track_index)),
+                    self.context_menu_item(self.i18n.tr("menu-delete-track"), Message::DeleteTrack(track_index)),
                 ],
                 TrackType::Audio => vec![
-                    self.context_menu_item("Add Audio Track Above", Message::AddAudioTrackAbove(track_index)),
-                    self.context_menu_item("Add Audio Track Below", Message::AddAudioTrackBelow(track_index)),
+                    self.context_menu_item(self.i18n.tr("menu-add-audio-track-above"), Message::AddAudioTrackAbove(track_index)),
+                    self.context_menu_item(self.i18n.tr("menu-add-audio-track-below"), Message::AddAudioTrackBelow(track_index)),
+                    self.context_menu_item(self.i18n.tr("menu-delete-track"), Message::DeleteTrack(track_index)),
                 ],
             };
 
@@ -2108,7 +4934,7 @@ impl App {
         }
     }
 
-    fn context_menu_item<'a>(&self, label: &'a str, msg: Message) -> Element<'a, Message> {
+    fn context_menu_item<'a>(&self, label: String, msg: Message) -> Element<'a, Message> {
         button(
             text(label).size(13).color(Color::WHITE).width(Length::Fill),
         )
@@ -2134,27 +4960,44 @@ impl App {
         .into()
     }
 
-    fn compute_source_drag_preview(&self) -> Option<SourceDragPreview> {
+    pub fn compute_source_drag_preview(&self) -> Option<SourceDragPreview> {
         let drag = self.drag_state.as_ref()?;
         if !drag.over_timeline {
             return None;
         }
-        let (asset_id, track_index, position) = match &drag.payload {
-            DragPayload::SourceAsset { asset_id, .. } => {
+        let (asset_id, additional_asset_ids, subclip_id, name, thumbnail, track_index, position) = match &drag.payload {
+            DragPayload::SourceAsset { asset_id, additional_asset_ids, subclip_id, name, thumbnail } => {
                 let track_index = drag.timeline_track?;
                 let position = drag.timeline_position?;
-                (*asset_id, track_index, position)
+                (*asset_id, additional_asset_ids, *subclip_id, name, thumbnail, track_index, position)
             }
+            DragPayload::Effect { .. } => return None,
         };
         let asset = self.project.source_library.get(asset_id)?;
-        let audio_track_index = if asset.has_audio {
+        let additional: Vec<_> = additional_asset_ids
+            .iter()
+            .filter_map(|id| self.project.source_library.get(*id))
+            .collect();
+        // A multi-asset drop previews as one block spanning all the clips.
+        let asset_secs = match subclip_id.and_then(|id| asset.subclip(id)) {
+            Some(subclip) => subclip.range.duration().as_secs_f64(),
+            None => self.preferences.placed_duration(asset).as_secs_f64(),
+        };
+        let duration_secs = asset_secs
+            + additional
+                .iter()
+                .map(|a| self.preferences.placed_duration(a).as_secs_f64())
+                .sum::<f64>();
+        let audio_track_index = if asset.has_audio || additional.iter().any(|a| a.has_audio) {
             self.project.timeline.find_paired_audio_track(track_index)
         } else {
             None
         };
         Some(SourceDragPreview {
             asset_id,
-            duration_secs: asset.duration.as_secs_f64(),
+            name: name.clone(),
+            thumbnail: thumbnail.clone(),
+            duration_secs,
             track_index,
             position,
             audio_track_index,
@@ -2162,11 +5005,12 @@ impl App {
     }
 
     fn view_menu_bar(&self) -> Element<'_, Message> {
-        let file_btn = self.menu_bar_button("File", MenuId::File);
-        let edit_btn = self.menu_bar_button("Edit", MenuId::Edit);
+        let file_btn = self.menu_bar_button(self.i18n.tr("menu-file"), MenuId::File);
+        let edit_btn = self.menu_bar_button(self.i18n.tr("menu-edit"), MenuId::Edit);
+        let view_btn = self.menu_bar_button(self.i18n.tr("menu-view"), MenuId::View);
 
         container(
-            row![file_btn, edit_btn].spacing(2).padding([2, 4]),
+            row![file_btn, edit_btn, view_btn].spacing(2).padding([2, 4]),
         )
         .width(Length::Fill)
         .style(|_theme| container::Style {
@@ -2181,7 +5025,7 @@ impl App {
         .into()
     }
 
-    fn menu_bar_button<'a>(&self, label: &'a str, menu_id: MenuId) -> Element<'a, Message> {
+    fn menu_bar_button<'a>(&self, label: String, menu_id: MenuId) -> Element<'a, Message> {
         let is_active = self.open_menu == Some(menu_id);
 
         let btn = button(text(label).size(14).color(Color::WHITE))
@@ -2211,26 +5055,192 @@ impl App {
             .into()
     }
 
+    /// Toast stack or notification history, anchored bottom-right. `None` if there is nothing to show.
+    fn view_notifications(&self) -> Option<Element<'_, Message>> {
+        let entry = |n: &Notification, dismissable: bool| -> Element<'_, Message> {
+            let level_color = match n.level {
+                NotificationLevel::Info => Color::from_rgb(0.6, 0.7, 0.9),
+                NotificationLevel::Success => Color::from_rgb(0.3, 0.8, 0.4),
+                NotificationLevel::Warning => Color::from_rgb(1.0, 0.75, 0.2),
+                NotificationLevel::Error => Color::from_rgb(1.0, 0.35, 0.35),
+            };
+            let message = if n.count > 1 {
+                format!("{} (x{})", n.message, n.count)
+            } else {
+                n.message.clone()
+            };
+            let mut line = row![
+                text(self.i18n.tr(n.level.label_key())).size(12).color(level_color),
+                text(message).size(12).color(Color::WHITE).width(Length::Fill),
+            ]
+            .spacing(8);
+            if dismissable {
+                line = line.push(
+                    button(text("x").size(11))
+                        .on_press(Message::DismissNotification(n.id))
+                        .padding([0, 6]),
+                );
+            }
+            line.into()
+        };
+        let card_style = |_theme: &iced::Theme| container::Style {
+            background: Some(Background::Color(Color::from_rgb(0.2, 0.2, 0.23))),
+            border: Border {
+                color: Color::from_rgb(0.4, 0.4, 0.45),
+                width: 1.0,
+                radius: 6.0.into(),
+            },
+            ..Default::default()
+        };
+
+        let panel: Element<'_, Message> = if self.show_notification_history {
+            let header = row![
+                text(self.i18n.tr("status-notifications")).size(14).color(Color::WHITE),
+                Space::new().width(Length::Fill),
+                button(text(self.i18n.tr("notifications-clear")).size(11))
+                    .on_press(Message::ClearNotifications)
+                    .padding([1, 6]),
+                button(text(self.i18n.tr("notifications-close")).size(11))
+                    .on_press(Message::ToggleNotificationHistory)
+                    .padding([1, 6]),
+            ]
+            .spacing(4);
+            let items: Vec<Element<'_, Message>> = if self.notifications.is_empty() {
+                vec![text(self.i18n.tr("notifications-empty")).size(12).color(Color::from_rgb(0.6, 0.6, 0.6)).into()]
+            } else {
+                self.notifications
+                    .history()
+                    .map(|n| entry(n, n.level.is_persistent() && !n.dismissed))
+                    .collect()
+            };
+            container(
+                column![header, scrollable(column(items).spacing(6)).height(240)].spacing(8),
+            )
+            .padding(10)
+            .width(380)
+            .style(card_style)
+            .into()
+        } else {
+            let toasts: Vec<Element<'_, Message>> = self
+                .notifications
+                .toasts(Instant::now())
+                .take(4)
+                .map(|n| container(entry(n, true)).padding(8).width(340).style(card_style).into())
+                .collect();
+            if toasts.is_empty() {
+                return None;
+            }
+            column(toasts).spacing(6).into()
+        };
+
+        Some(
+            container(panel)
+                .padding(Padding { top: 0.0, right: 12.0, bottom: 40.0, left: 0.0 })
+                .align_right(Length::Fill)
+                .align_bottom(Length::Fill)
+                .into(),
+        )
+    }
+
     fn view_dropdown(&self) -> Element<'_, Message> {
         let menu_id = self.open_menu.unwrap_or(MenuId::File);
 
         let items: Vec<Element<'_, Message>> = match menu_id {
             MenuId::File => vec![
-                self.menu_item("New Project", MenuAction::NewProject),
-                self.menu_item("Load Project", MenuAction::LoadProject),
-                self.menu_item("Save", MenuAction::Save),
-                self.menu_item("Render", MenuAction::Render),
-                self.menu_item("Exit", MenuAction::Exit),
+                self.menu_item(self.i18n.tr("menu-new-project"), MenuAction::NewProject),
+                self.menu_item(self.i18n.tr("menu-load-project"), MenuAction::LoadProject),
+                self.menu_item(self.i18n.tr("menu-save"), MenuAction::Save),
+                self.menu_item(self.i18n.tr("menu-save-as"), MenuAction::SaveAs),
+                self.menu_item(self.i18n.tr("menu-restore-version"), MenuAction::RestoreVersion),
+                self.menu_item(self.i18n.tr("menu-import-folder"), MenuAction::ImportFolder),
+                self.menu_item(self.i18n.tr("menu-remap-paths"), MenuAction::RemapMediaPaths),
+                self.menu_item(self.i18n.tr("menu-render"), MenuAction::Render),
+                self.menu_item(
+                    if self.preferences.export_stems {
+                        format!("{} \u{2713}", self.i18n.tr("menu-export-stems"))
+                    } else {
+                        self.i18n.tr("menu-export-stems")
+                    },
+                    MenuAction::ToggleExportStems,
+                ),
+                self.menu_item(self.i18n.tr("menu-export-description"), MenuAction::ExportDescription),
+                self.menu_item(self.i18n.tr("menu-exit"), MenuAction::Exit),
             ],
             MenuId::Edit => vec![
-                self.menu_item("Undo", MenuAction::Undo),
-                self.menu_item("Redo", MenuAction::Redo),
+                self.menu_item(self.i18n.tr("menu-undo"), MenuAction::Undo),
+                self.menu_item(self.i18n.tr("menu-redo"), MenuAction::Redo),
+                self.menu_item(self.i18n.tr("menu-group-clips"), MenuAction::GroupClips),
+                self.menu_item(self.i18n.tr("menu-ungroup-clips"), MenuAction::UngroupClips),
+                self.menu_item(self.i18n.tr("menu-sync-clips"), MenuAction::SynchronizeClips),
+                self.menu_item(self.i18n.tr("menu-split-dual-mono"), MenuAction::SplitDualMono),
+                self.menu_item(self.i18n.tr("menu-fit-to-fill"), MenuAction::FitToFill),
+                self.menu_item(self.i18n.tr("menu-create-slideshow"), MenuAction::CreateSlideshow),
+                self.menu_item(self.i18n.tr("menu-transcribe-clip"), MenuAction::TranscribeClip),
+                self.menu_item(self.i18n.tr("menu-detect-beats"), MenuAction::DetectBeats),
+                self.menu_item(self.i18n.tr("menu-auto-reframe"), MenuAction::AutoReframe),
+                self.menu_item(self.i18n.tr("menu-replace-media"), MenuAction::ReplaceMedia),
+                self.menu_item(self.i18n.tr("menu-relink-media"), MenuAction::RelinkMedia),
+                self.menu_item(self.i18n.tr("menu-reveal-in-timeline"), MenuAction::RevealInTimeline),
+                self.menu_item(
+                    if self.preferences.ripple_edits {
+                        format!("{} \u{2713}", self.i18n.tr("menu-ripple-edits"))
+                    } else {
+                        self.i18n.tr("menu-ripple-edits")
+                    },
+                    MenuAction::ToggleRippleEdits,
+                ),
+                self.menu_item(
+                    if self.preferences.snap_to_markers {
+                        format!("{} \u{2713}", self.i18n.tr("menu-snap-to-markers"))
+                    } else {
+                        self.i18n.tr("menu-snap-to-markers")
+                    },
+                    MenuAction::ToggleSnapTarget(SnapTarget::Markers),
+                ),
+                self.menu_item(
+                    if self.preferences.snap_to_playhead {
+                        format!("{} \u{2713}", self.i18n.tr("menu-snap-to-playhead"))
+                    } else {
+                        self.i18n.tr("menu-snap-to-playhead")
+                    },
+                    MenuAction::ToggleSnapTarget(SnapTarget::Playhead),
+                ),
+                self.menu_item(
+                    if self.preferences.snap_audio_trims {
+                        format!("{} \u{2713}", self.i18n.tr("menu-snap-quiet-audio"))
+                    } else {
+                        self.i18n.tr("menu-snap-quiet-audio")
+                    },
+                    MenuAction::ToggleSnapTarget(SnapTarget::QuietAudio),
+                ),
+                self.menu_item(self.i18n.tr("menu-preferences"), MenuAction::Preferences),
             ],
+            MenuId::View => {
+                let scales = crate::preferences::UI_SCALE_PRESETS.iter().map(|&scale| {
+                    let percent = (scale * 100.0).round() as u16;
+                    let current = (self.preferences.ui_scale - scale).abs() < 0.001;
+                    let label = self.i18n.tr_args("menu-ui-scale", &[("percent", &percent)]);
+                    let label = if current { format!("{label} \u{2713}") } else { label };
+                    self.menu_item(label, MenuAction::SetUiScale(percent))
+                });
+                // Language names are shown in their own language, untranslated
+                let languages = Language::all().into_iter().map(|language| {
+                    let name = language.native_name().to_string();
+                    let label = if language == self.i18n.language() {
+                        format!("{name} \u{2713}")
+                    } else {
+                        name
+                    };
+                    self.menu_item(label, MenuAction::SetLanguage(language))
+                });
+                scales.chain(languages).collect()
+            }
         };
 
         let left_offset: f32 = match menu_id {
             MenuId::File => 8.0,
             MenuId::Edit => 58.0,
+            MenuId::View => 108.0,
         };
 
         let dropdown = container(column(items).spacing(0))
@@ -2257,7 +5267,7 @@ impl App {
             .into()
     }
 
-    fn menu_item<'a>(&self, label: &'a str, action: MenuAction) -> Element<'a, Message> {
+    fn menu_item<'a>(&self, label: String, action: MenuAction) -> Element<'a, Message> {
         button(
             text(label).size(14).color(Color::WHITE).width(Length::Fill),
         )
@@ -2283,23 +5293,36 @@ impl App {
         .into()
     }
 
+    /// Width of the drag ghost card: the drop's length at the current zoom
+    /// while over the timeline, so its fit can be judged, else a fixed card.
+    pub fn drag_ghost_width(&self) -> f32 {
+        match self.compute_source_drag_preview() {
+            Some(preview) => (preview.duration_secs as f32 * self.timeline_zoom).clamp(24.0, 2000.0),
+            None => 130.0,
+        }
+    }
+
     fn view_drag_overlay<'a>(&'a self, drag: &'a DragState) -> Element<'a, Message> {
         let (thumbnail, name) = match &drag.payload {
             DragPayload::SourceAsset { thumbnail, name, .. } => (thumbnail.clone(), name.as_str()),
+            DragPayload::Effect { effect_type } => (None, effect_type.display_name()),
         };
 
         let ghost_alpha = 0.3;
+        let card_width = self.drag_ghost_width();
+        let inner_width = (card_width - 10.0).max(14.0);
+        let scaled = self.compute_source_drag_preview().is_some();
 
         let thumb_content: Element<'_, Message> = if let Some(handle) = thumbnail {
             image(handle)
-                .width(120)
+                .width(inner_width)
                 .height(68)
                 .content_fit(iced::ContentFit::Contain)
                 .opacity(ghost_alpha)
                 .into()
         } else {
             container(center(text("...").size(14).color(Color { r: 0.5, g: 0.5, b: 0.5, a: ghost_alpha })))
-                .width(120)
+                .width(inner_width)
                 .height(68)
                 .style(move |_theme| container::Style {
                     background: Some(Background::Color(Color { r: 0.2, g: 0.2, b: 0.22, a: ghost_alpha })),
@@ -2311,14 +5334,14 @@ impl App {
         let name_label = text(name)
             .size(11)
             .color(Color { r: 1.0, g: 1.0, b: 1.0, a: ghost_alpha })
-            .width(120)
+            .width(inner_width)
             .center();
 
         let card = container(
             column![thumb_content, name_label].spacing(2).align_x(iced::Alignment::Center),
         )
         .padding(4)
-        .width(130)
+        .width(card_width)
         .style(move |_theme| container::Style {
             background: Some(Background::Color(Color {
                 r: 0.18,
@@ -2334,12 +5357,14 @@ impl App {
             ..Default::default()
         });
 
-        // Position at cursor with offset so card is centered on cursor
+        // Position at cursor with offset so card is centered on cursor; over
+        // the timeline the drop starts at the cursor, so the card does too
         let cursor = drag.cursor_position;
+        let left = if scaled { cursor.x } else { cursor.x - card_width / 2.0 };
         container(card)
             .padding(Padding {
                 top: (cursor.y - 45.0).max(0.0),
-                left: (cursor.x - 65.0).max(0.0),
+                left: left.max(0.0),
                 right: 0.0,
                 bottom: 0.0,
             })
@@ -2348,7 +5373,421 @@ impl App {
             .into()
     }
 
+    /// Start (or re-anchor) playback at the given shuttle rate. The playback clock
+    /// restarts from the current position so the rate change takes effect without a jump.
+    /// Audio follows forward rates with a sped-up sink; reverse playback is silent.
+    fn set_shuttle_rate(&mut self, rate: f64) {
+        self.play_around = None;
+        self.playback_rate = rate;
+        self.is_playing = true;
+        self.playback_start_wall = Some(Instant::now());
+        self.playback_start_pos = self.playback_position;
+        self.send_decode_seek(true);
+        self.drain_stale = true;
+        if rate > 0.0 {
+            if let Some(player) = &self.audio_player {
+                player.set_speed(rate as f32);
+            }
+            self.send_audio_decode_seek(true);
+        } else {
+            self.send_audio_decode_stop();
+            self.audio_decode_clip_id = None;
+        }
+        self.status_message = self.i18n.tr_args("status-shuttle", &[("rate", &rate)]);
+    }
+
+    /// File and edit shortcuts: Ctrl+S save, Ctrl+Shift+S save as, Ctrl+O open,
+    /// Ctrl+N new project, Ctrl+Z undo, Ctrl+Shift+Z / Ctrl+Y redo, Ctrl+G group,
+    /// Ctrl+Shift+G ungroup.
+    fn handle_command_shortcut(&mut self, key: keyboard::Key<&str>, shift: bool) -> Task<Message> {
+        let keyboard::Key::Character(c) = key else {
+            return Task::none();
+        };
+        match (c.to_ascii_lowercase().as_str(), shift) {
+            ("s", false) => self.update(Message::SaveProject),
+            ("s", true) => self.update(Message::SaveProjectAs),
+            ("o", _) => self.update(Message::MenuAction(MenuAction::LoadProject)),
+            ("n", _) => self.update(Message::MenuAction(MenuAction::NewProject)),
+            ("z", false) => self.update(Message::Undo),
+            ("z", true) | ("y", _) => self.update(Message::Redo),
+            ("g", false) => self.update(Message::GroupSelectedClips),
+            ("g", true) => self.update(Message::UngroupSelectedClips),
+            // Shift+= arrives as "+" on most layouts
+            ("=", _) | ("+", _) => self.update(Message::TimelineZoomStep(1)),
+            ("-", _) | ("_", _) => self.update(Message::TimelineZoomStep(-1)),
+            ("0", _) => self.update(Message::ZoomToFit),
+            _ => Task::none(),
+        }
+    }
+
+    /// Move the playhead by `frames` whole frames at the project fps, snapping to
+    /// the frame grid. Pauses playback and requests the exact frame in scrub mode.
+    fn step_frames(&mut self, frames: i64) {
+        let fps = self.project.settings.fps;
+        if fps <= 0.0 {
+            return;
+        }
+        if self.is_playing {
+            let _ = self.update(Message::Pause);
+        }
+        let current = (self.playback_position.as_secs_f64() * fps).round() as i64;
+        let target = (current + frames).max(0);
+        self.playback_position = TimelinePosition::from_secs_f64(target as f64 / fps);
+        self.send_decode_seek(false);
+    }
+
+    /// Current timeline position according to the audio master clock: the anchor
+    /// plus however much audio the device has consumed since. Returns `None` (use
+    /// the wall clock) when not playing forward, before the device has started
+    /// the audio for the current seek, or once the ring has run dry.
+    fn audio_clock_position(&self) -> Option<f64> {
+        if !self.is_playing || self.playback_rate <= 0.0 {
+            return None;
+        }
+        let player = self.audio_player.as_ref()?;
+        let anchor = self.audio_ring.anchor()?;
+        if self.audio_ring.buffered().is_zero() {
+            return None;
+        }
+        let consumed = player.consumed().saturating_sub(anchor.consumed_at);
+        Some(anchor.pts_secs + self.audio_decode_time_offset + consumed.as_secs_f64())
+    }
+
+    /// Take the audio decode thread's level blocks for the current seek and
+    /// forget those already heard. Meters are cleared while stopped.
+    fn poll_audio_levels(&mut self) {
+        let Some(rx) = &self.audio_levels_rx else {
+            return;
+        };
+        let epoch = self.audio_ring.requested_epoch();
+        while let Ok(levels) = rx.try_recv() {
+            if levels.epoch == epoch {
+                self.audio_meters.push(levels);
+            }
+        }
+        if self.is_playing {
+            self.audio_meters.prune(self.audio_meter_pts());
+        } else {
+            self.audio_meters.clear();
+        }
+    }
+
+    /// Source time the audio clock is at, which is what level blocks are stamped with.
+    fn audio_meter_pts(&self) -> f64 {
+        self.playback_position.as_secs_f64() - self.audio_decode_time_offset
+    }
+
+    /// Fill (0.0 to 1.0) of a track header's level meter; 0.0 while stopped.
+    pub fn audio_meter_level(&self, track_index: usize) -> f32 {
+        if !self.is_playing {
+            return 0.0;
+        }
+        audio_meters::meter_fraction(self.audio_meters.level(track_index, self.audio_meter_pts()))
+    }
+
+    /// Summary shown in the Statistics tab.
+    pub fn project_stats(&self) -> ProjectStats {
+        stats::project_stats(&self.project.timeline, &self.project.source_library)
+    }
+
+    /// Issues shown in the Timeline Health tab.
+    pub fn timeline_health(&self) -> Vec<HealthIssue> {
+        health::inspect_timeline(&self.project.timeline, &self.project.source_library)
+    }
+
+    /// Clips whose asset name contains the "Find in timeline" query
+    /// (ignoring case), in timeline order, linked audio folded into its
+    /// video. Empty for a blank query.
+    pub fn timeline_search_matches(&self) -> Vec<(usize, Uuid)> {
+        let query = self.timeline_search.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        self.clips_in_timeline_order(|clip| {
+            self.project
+                .source_library
+                .get(clip.asset_id)
+                .is_some_and(|a| a.name.to_lowercase().contains(&query))
+        })
+    }
+
+    /// Clips matching `predicate` as `(track, clip)`, sorted by start then
+    /// track, with linked clips collapsed into the first of them.
+    fn clips_in_timeline_order(&self, predicate: impl Fn(&Clip) -> bool) -> Vec<(usize, Uuid)> {
+        let mut matches = Vec::new();
+        for (track_index, track) in self.project.timeline.tracks.iter().enumerate() {
+            for clip in track.clips.iter().filter(|c| predicate(c)) {
+                matches.push((clip.timeline_range.start, track_index, clip.id, clip.link_id));
+            }
+        }
+        matches.sort();
+        let mut seen_links = HashSet::new();
+        matches
+            .into_iter()
+            .filter(|(_, _, _, link_id)| link_id.is_none_or(|id| seen_links.insert(id)))
+            .map(|(_, track_index, clip_id, _)| (track_index, clip_id))
+            .collect()
+    }
+
+    /// Range that loop playback wraps within: the full timeline content.
+    /// Returns `None` for an empty timeline.
+    pub fn loop_range(&self) -> Option<(TimelinePosition, TimelinePosition)> {
+        let end = self.project.timeline.duration();
+        if end.is_zero() {
+            return None;
+        }
+        Some((TimelinePosition::zero(), TimelinePosition::from_secs_f64(end.as_secs_f64())))
+    }
+
+    /// The selected clip followed by any Shift/Ctrl+clicked ones that still
+    /// exist. Empty when no clip is selected.
+    pub fn clip_selection(&self) -> Vec<(usize, Uuid)> {
+        let Some(primary) = self.selected_clip else {
+            return Vec::new();
+        };
+        let mut clips = vec![primary];
+        for &(track_index, clip_id) in &self.selected_clips {
+            let exists = self.project.timeline.track(track_index)
+                .is_ok_and(|t| t.get_clip(clip_id).is_some());
+            if exists && !clips.contains(&(track_index, clip_id)) {
+                clips.push((track_index, clip_id));
+            }
+        }
+        clips
+    }
+
+    /// Add `effect_type` to each of `clips` on a video track as one undoable
+    /// command.
+    fn add_effect_to_clips(&mut self, clips: &[(usize, Uuid)], effect_type: EffectType) -> Task<Message> {
+        let targets: Vec<(usize, Uuid)> = clips
+            .iter()
+            .copied()
+            .filter(|&(track_index, _)| {
+                self.project.timeline.track(track_index).is_ok_and(|t| t.track_type == TrackType::Video)
+            })
+            .collect();
+        if targets.is_empty() {
+            self.status_message = self.i18n.tr("status-effect-needs-video-clips");
+            return Task::none();
+        }
+        let result = self.project.command_history.execute(
+            &mut self.project.timeline,
+            "Add effect to clips",
+            |tl| {
+                for &(track_index, clip_id) in &targets {
+                    tl.track_mut(track_index)?
+                        .get_clip_mut(clip_id)
+                        .ok_or(zeditor_core::error::CoreError::ClipNotFound(clip_id))?
+                        .effects
+                        .push(EffectInstance::new(effect_type));
+                }
+                Ok(())
+            },
+        );
+        match result {
+            Ok(()) => {
+                let count = targets.len();
+                self.status_message = self.i18n.tr_args("status-effect-added-to-clips", &[("count", &count)]);
+                self.send_decode_seek(false);
+            }
+            Err(e) => self.status_message = self.i18n.tr_args("status-add-effect-failed", &[("error", &e)]),
+        }
+        Task::none()
+    }
+
+    /// Effects on the other selected clips that pair with `effect_id` on the
+    /// primary selected clip: the same type, at the same place among each
+    /// clip's effects of that type. Empty unless several clips are selected.
+    pub fn effect_counterparts(&self, effect_id: Uuid) -> Vec<(usize, Uuid, Uuid)> {
+        let selection = self.clip_selection();
+        let [primary, others @ ..] = &selection[..] else {
+            return Vec::new();
+        };
+        let clip_effects = |(track_index, clip_id): (usize, Uuid)| {
+            self.project.timeline.track(track_index).ok()?.get_clip(clip_id).map(|c| &c.effects)
+        };
+        let Some(effects) = clip_effects(*primary) else {
+            return Vec::new();
+        };
+        let Some(effect_type) = effects.iter().find(|e| e.id == effect_id).map(|e| e.effect_type) else {
+            return Vec::new();
+        };
+        let Some(nth) = effects.iter().filter(|e| e.effect_type == effect_type).position(|e| e.id == effect_id) else {
+            return Vec::new();
+        };
+        others
+            .iter()
+            .filter_map(|&(track_index, clip_id)| {
+                let counterpart = clip_effects((track_index, clip_id))?
+                    .iter()
+                    .filter(|e| e.effect_type == effect_type)
+                    .nth(nth)?;
+                Some((track_index, clip_id, counterpart.id))
+            })
+            .collect()
+    }
+
+    /// Whether the selected clips disagree on `param_name` of `effect_id`,
+    /// whose value on the primary clip is `value`.
+    pub fn effect_param_mixed(&self, effect_id: Uuid, param_name: &str, value: f64) -> bool {
+        self.effect_counterparts(effect_id).into_iter().any(|(track_index, clip_id, counterpart_id)| {
+            let other = self
+                .project
+                .timeline
+                .track(track_index)
+                .ok()
+                .and_then(|t| t.get_clip(clip_id))
+                .and_then(|c| c.effects.iter().find(|e| e.id == counterpart_id))
+                .and_then(|e| e.get_float(param_name));
+            other != Some(value)
+        })
+    }
+
+    /// Set an effect parameter as one undoable command. Edits to the primary
+    /// selected clip also set the matching effects on the rest of the
+    /// selection.
+    fn set_effect_parameter(
+        &mut self,
+        track_index: usize,
+        clip_id: Uuid,
+        effect_id: Uuid,
+        param_name: &str,
+        value: f64,
+    ) -> Result<(), zeditor_core::error::CoreError> {
+        let mut targets = vec![(track_index, clip_id, effect_id)];
+        if self.selected_clip == Some((track_index, clip_id)) {
+            targets.extend(self.effect_counterparts(effect_id));
+        }
+        self.project.command_history.execute(
+            &mut self.project.timeline,
+            "Update effect parameter",
+            |tl| {
+                for &(track_index, clip_id, effect_id) in &targets {
+                    let clip = tl.track_mut(track_index)?
+                        .get_clip_mut(clip_id)
+                        .ok_or(zeditor_core::error::CoreError::ClipNotFound(clip_id))?;
+                    if let Some(effect) = clip.effects.iter_mut().find(|e| e.id == effect_id) {
+                        effect.set_float(param_name, value);
+                    }
+                }
+                Ok(())
+            },
+        )
+    }
+
+    /// The two selected clips to synchronize as `(reference, other)`. `other`
+    /// is the one that moves: an audio-only recording if exactly one of them
+    /// is, otherwise the clip added to the selection second.
+    pub fn sync_pair(&self) -> Option<((usize, Uuid), (usize, Uuid))> {
+        let selection = self.clip_selection();
+        let [first, second] = selection[..] else {
+            return None;
+        };
+        let is_audio_only = |(track_index, clip_id): (usize, Uuid)| {
+            self.project
+                .timeline
+                .track(track_index)
+                .ok()
+                .and_then(|t| t.get_clip(clip_id))
+                .and_then(|c| self.project.source_library.get(c.asset_id))
+                .is_some_and(|a| a.kind() == MediaKind::Audio)
+        };
+        if is_audio_only(first) && !is_audio_only(second) {
+            Some((second, first))
+        } else {
+            Some((first, second))
+        }
+    }
+
+    /// Clip after (`direction` > 0) or before the selected one on its track. With
+    /// nothing selected, starts from the playhead on the first track that has clips.
+    fn adjacent_clip(&self, direction: i32) -> Option<(usize, Uuid)> {
+        let timeline = &self.project.timeline;
+        let Some((track_index, clip_id)) = self.selected_clip else {
+            let (track_index, track) = timeline
+                .tracks
+                .iter()
+                .enumerate()
+                .find(|(_, t)| !t.clips.is_empty())?;
+            let playhead = self.playback_position;
+            let clip = if direction > 0 {
+                track.clips.iter().find(|c| c.timeline_range.end > playhead)
+            } else {
+                track.clips.iter().rev().find(|c| c.timeline_range.start < playhead)
+            };
+            let clip = clip.or(if direction > 0 { track.clips.last() } else { track.clips.first() })?;
+            return Some((track_index, clip.id));
+        };
+        let clips = &timeline.track(track_index).ok()?.clips;
+        let idx = clips.iter().position(|c| c.id == clip_id)?;
+        let next = if direction > 0 { idx + 1 } else { idx.checked_sub(1)? };
+        clips.get(next).map(|c| (track_index, c.id))
+    }
+
+    /// Clip on the nearest non-empty track below (`direction` > 0) or above the
+    /// selected clip: the one under the selected clip's start, else the closest.
+    fn clip_on_adjacent_track(&self, direction: i32) -> Option<(usize, Uuid)> {
+        let (track_index, clip_id) = self.selected_clip?;
+        let timeline = &self.project.timeline;
+        let anchor = timeline.track(track_index).ok()?.get_clip(clip_id)?.timeline_range.start;
+        let distance = |c: &Clip| (c.timeline_range.start.as_secs_f64() - anchor.as_secs_f64()).abs();
+        let mut i = track_index;
+        loop {
+            i = if direction > 0 { i + 1 } else { i.checked_sub(1)? };
+            let track = timeline.tracks.get(i)?;
+            let clip = track
+                .clip_at(anchor)
+                .or_else(|| track.clips.iter().min_by(|a, b| distance(a).total_cmp(&distance(b))));
+            if let Some(clip) = clip {
+                return Some((i, clip.id));
+            }
+        }
+    }
+
+    /// Scroll horizontally so the clip's start is on screen.
+    /// Select a clip on its own, scroll it into view and move the playhead to its start.
+    fn focus_clip(&mut self, track_index: usize, clip_id: Uuid) -> Task<Message> {
+        let Some(start) = self
+            .project
+            .timeline
+            .track(track_index)
+            .ok()
+            .and_then(|t| t.get_clip(clip_id))
+            .map(|c| c.timeline_range.start)
+        else {
+            return Task::none();
+        };
+        self.selected_clip = Some((track_index, clip_id));
+        self.selected_clips.clear();
+        self.reveal_clip(track_index, clip_id);
+        self.update(Message::SeekTo(start))
+    }
+
+    fn reveal_clip(&mut self, track_index: usize, clip_id: Uuid) {
+        let Some(start) = self
+            .project
+            .timeline
+            .track(track_index)
+            .ok()
+            .and_then(|t| t.get_clip(clip_id))
+            .map(|c| c.timeline_range.start)
+        else {
+            return;
+        };
+        self.reveal_position(start);
+    }
+
+    /// Scroll the timeline so `position` is in view.
+    fn reveal_position(&mut self, position: TimelinePosition) {
+        let start_px = position.as_secs_f64() as f32 * self.timeline_zoom;
+        let viewport = self.timeline_viewport_width();
+        if start_px < self.timeline_scroll || start_px > self.timeline_scroll + viewport * 0.9 {
+            self.timeline_scroll = (start_px - viewport * 0.1).max(0.0);
+        }
+    }
+
     /// Find the video clip at the given playback position (searches only video tracks).
+
     pub fn clip_at_position(&self, pos: TimelinePosition) -> Option<(usize, &Clip)> {
         for (i, track) in self.project.timeline.tracks.iter().enumerate() {
             if track.track_type == TrackType::Video {
@@ -2365,7 +5804,7 @@ impl App {
     pub fn all_video_clips_at_position(&self, pos: TimelinePosition) -> Vec<(usize, &Clip)> {
         let mut clips = Vec::new();
         let video_tracks: Vec<_> = self.project.timeline.tracks.iter().enumerate()
-            .filter(|(_, t)| t.track_type == TrackType::Video)
+            .filter(|(i, _)| self.project.timeline.is_track_visible(*i))
             .collect();
         for (i, track) in video_tracks.iter().rev() {
             if let Some(clip) = track.clip_at(pos) {
@@ -2375,11 +5814,11 @@ impl App {
         clips
     }
 
-    /// Find ALL audio clips at the given playback position, from all audio tracks.
+    /// Find ALL audio clips at the given playback position, from all audible audio tracks.
     pub fn all_audio_clips_at_position(&self, pos: TimelinePosition) -> Vec<(usize, &Clip)> {
         let mut clips = Vec::new();
         for (i, track) in self.project.timeline.tracks.iter().enumerate() {
-            if track.track_type == TrackType::Audio {
+            if self.project.timeline.is_track_audible(i) {
                 if let Some(clip) = track.clip_at(pos) {
                     clips.push((i, clip));
                 }
@@ -2388,63 +5827,38 @@ impl App {
         clips
     }
 
-    /// Find the audio clip at the given playback position (searches only audio tracks).
+    /// Find the audio clip at the given playback position (searches only audible audio tracks).
     pub fn audio_clip_at_position(&self, pos: TimelinePosition) -> Option<(usize, &Clip)> {
         for (i, track) in self.project.timeline.tracks.iter().enumerate() {
-            if track.track_type == TrackType::Audio {
+            if self.project.timeline.is_track_audible(i) {
                 if let Some(clip) = track.clip_at(pos) {
                     return Some((i, clip));
                 }
             }
         }
-        None
-    }
-
-    /// Send a seek request to the decode thread for the current playback position.
-    /// If `continuous` is true, the thread decodes ahead (playback mode).
-    /// If false, it decodes one target frame and stops (scrub mode).
-    fn send_decode_seek(&mut self, continuous: bool) {
-        // Drain stale frames from the channel and discard pending
-        self.pending_frame = None;
-        if let Some(rx) = &self.decode_rx {
-            while rx.try_recv().is_ok() {}
-        }
-
-        // Collect clip info upfront to avoid borrow conflicts with self.
-        // Video tracks are stored top-to-bottom (VN...V1), iterate in reverse for bottom-to-top.
-        let playback_pos = self.playback_position.as_secs_f64();
-        let mut clip_infos = Vec::new();
-        let mut clip_ids = Vec::new();
-        let mut first_clip_id = None;
-        let mut first_time_offset = 0.0;
-
-        let video_track_indices: Vec<usize> = self.project.timeline.tracks.iter()
-            .enumerate()
-            .filter(|(_, t)| t.track_type == TrackType::Video)
-            .map(|(i, _)| i)
-            .collect();
-
-        for &idx in video_track_indices.iter().rev() {
-            let track = &self.project.timeline.tracks[idx];
-            if let Some(clip) = track.clip_at(self.playback_position) {
-                let clip_tl_start = clip.timeline_range.start.as_secs_f64();
-                let clip_src_start = clip.source_range.start.as_secs_f64();
-                let source_time = clip_src_start + (playback_pos - clip_tl_start);
-                if let Some(asset) = self.project.source_library.get(clip.asset_id) {
-                    if first_clip_id.is_none() {
-                        first_clip_id = Some(clip.id);
-                        first_time_offset = clip_tl_start - clip_src_start;
-                    }
-                    clip_ids.push(clip.id);
-                    clip_infos.push(ClipDecodeInfo {
-                        path: asset.path.clone(),
-                        time: source_time,
-                        effects: clip.effects.clone(),
-                    });
-                }
-            }
-        }
+        None
+    }
+
+    /// Send a seek request to the decode thread for the current playback position.
+    /// If `continuous` is true, the thread decodes ahead (playback mode).
+    /// If false, it decodes one target frame and stops (scrub mode).
+    fn send_decode_seek(&mut self, continuous: bool) {
+        // Drain stale frames from the channel and discard pending
+        self.pending_frame = None;
+        if let Some(rx) = &self.decode_rx {
+            while rx.try_recv().is_ok() {}
+        }
 
+        // Any preload has been picked up by this seek (or is no longer wanted).
+        self.preloaded_clip_ids.clear();
+        self.serving_prerender = None;
+        let layers = self.video_decode_clips_at(self.playback_position);
+        let first_clip_id = layers.first().map(|(id, _, _)| *id);
+        let first_time_offset = layers.first().map(|(_, _, offset)| *offset).unwrap_or(0.0);
+        let clip_ids: Vec<Uuid> = layers.iter().map(|(id, _, _)| *id).collect();
+        let clip_infos: Vec<ClipDecodeInfo> = layers.into_iter().map(|(_, info, _)| info).collect();
+
+        self.pending_cache_key = None;
         if clip_infos.is_empty() {
             self.decode_clip_id = None;
             self.decode_clip_ids.clear();
@@ -2458,18 +5872,128 @@ impl App {
         self.decode_clip_ids = clip_ids;
         self.decode_time_offset = first_time_offset;
 
+        // Scrubbing: serve a cached composite if this exact frame was seen before,
+        // otherwise remember the key so the decoded frame gets cached.
+        if !continuous {
+            let key = self.preview_key(self.playback_position);
+            if let Some(handle) = self.preview_cache.get(&key).or_else(|| self.prerender_cache.get(&key)) {
+                self.current_frame = Some(handle);
+                self.send_decode_stop();
+                return;
+            }
+            self.pending_cache_key = Some(key);
+        }
+
+        self.playback_stats.record_decode_requested(Instant::now());
+        let generation = next_decode_generation(&self.decode_generation);
         if let Some(tx) = &self.decode_tx {
             let _ = tx.send(DecodeRequest::SeekMulti {
+                generation,
                 clips: clip_infos,
                 continuous,
                 canvas_w: self.project.settings.canvas_width,
                 canvas_h: self.project.settings.canvas_height,
+                rate: if continuous { self.playback_rate } else { 1.0 },
+                fps: self.project.settings.fps,
+                quality: self.preferences.preview_quality,
+                display_lut: self.display_lut.clone(),
+            });
+        }
+    }
+
+    /// Decode info for the video clips under `pos`, bottom-to-top, with each
+    /// clip's ID and its timeline-minus-source time offset.
+    fn video_decode_clips_at(&self, pos: TimelinePosition) -> Vec<(Uuid, ClipDecodeInfo, f64)> {
+        // Video tracks are stored top-to-bottom (VN...V1), iterate in reverse for bottom-to-top.
+        let timeline = &self.project.timeline;
+        (0..timeline.tracks.len())
+            .rev()
+            .filter(|&i| timeline.is_track_visible(i))
+            .filter_map(|i| timeline.tracks[i].clip_at(pos))
+            .filter_map(|clip| {
+                let asset = self.project.source_library.get(clip.asset_id)?;
+                let clip_tl_start = clip.timeline_range.start.as_secs_f64();
+                let clip_src_start = clip.source_range.start.as_secs_f64();
+                let info = ClipDecodeInfo {
+                    path: asset.path.clone(),
+                    time: clip.source_frame_secs_at(pos, asset.fps, self.project.settings.fps),
+                    effects: if self.effects_bypassed { Vec::new() } else { clip.effects.clone() },
+                    color: asset.color,
+                    fit: clip.canvas_fit,
+                    source_size: (asset.display_width(), asset.display_height()),
+                    pan: clip.pan.clone(),
+                };
+                Some((clip.id, info, clip_tl_start - clip_src_start))
+            })
+            .collect()
+    }
+
+    /// The next video cut (a clip start on a visible track) after the playhead,
+    /// if one falls within `lead_secs`.
+    pub fn upcoming_video_cut(&self, lead_secs: f64) -> Option<TimelinePosition> {
+        let pos = self.playback_position.as_secs_f64();
+        let timeline = &self.project.timeline;
+        (0..timeline.tracks.len())
+            .filter(|&i| timeline.is_track_visible(i))
+            .flat_map(|i| timeline.tracks[i].clips.iter())
+            .map(|clip| clip.timeline_range.start)
+            .filter(|start| {
+                let start = start.as_secs_f64();
+                start > pos && start <= pos + lead_secs
+            })
+            .min_by(|a, b| a.as_secs_f64().total_cmp(&b.as_secs_f64()))
+    }
+
+    /// Ask the decode worker to open and seek decoders for the clips at the
+    /// next cut, so crossing it doesn't stall on opening files.
+    fn preload_upcoming_clips(&mut self) {
+        let Some(cut) = self.upcoming_video_cut(PRELOAD_LEAD_SECS) else {
+            return;
+        };
+        let (ids, clips): (Vec<Uuid>, Vec<ClipDecodeInfo>) = self
+            .video_decode_clips_at(cut)
+            .into_iter()
+            .filter(|(id, _, _)| !self.decode_clip_ids.contains(id))
+            .map(|(id, info, _)| (id, info))
+            .unzip();
+        if clips.is_empty() || ids == self.preloaded_clip_ids {
+            return;
+        }
+        self.preloaded_clip_ids = ids;
+        if let Some(tx) = &self.decode_tx {
+            let _ = tx.send(DecodeRequest::Preload {
+                clips,
+                fps: self.project.settings.fps,
             });
         }
     }
 
+    /// True if a visible video clip at `pos` has effects, i.e. its frames go
+    /// through the pixel pipeline rather than the direct blit.
+    /// Preview cache key for the frame at `pos`, which differs while effects
+    /// are bypassed so compared frames never stand in for each other.
+    fn preview_key(&self, pos: TimelinePosition) -> PreviewCacheKey {
+        let key = PreviewCacheKey::at(&self.project, pos);
+        if self.effects_bypassed { key.without_effects() } else { key }
+    }
+
+    /// Measure the UI-side caches, shrinking any that are over their cap.
+    pub(crate) fn enforce_memory_budget(&mut self) {
+        self.memory.enforce(MemorySubsystem::PreviewCache, &mut self.preview_cache);
+        self.memory.enforce(MemorySubsystem::Prerender, &mut self.prerender_cache);
+        self.memory.record(
+            MemorySubsystem::Thumbnails,
+            self.thumbnails.values().map(memory::handle_bytes).sum(),
+        );
+        self.memory.record(
+            MemorySubsystem::PendingFrames,
+            self.pending_frame.as_ref().map_or(0, |f| f.rgba.len()),
+        );
+    }
+
     /// Tell the decode thread to stop decoding.
     fn send_decode_stop(&self) {
+        next_decode_generation(&self.decode_generation);
         if let Some(tx) = &self.decode_tx {
             let _ = tx.send(DecodeRequest::Stop);
         }
@@ -2490,7 +6014,18 @@ impl App {
             return;
         }
 
+        // Frames still in flight from before playback switched to pre-rendered frames.
+        if self.serving_prerender.is_some() {
+            self.pending_frame = None;
+            if let Some(rx) = &self.decode_rx {
+                while rx.try_recv().is_ok() {}
+            }
+            return;
+        }
+
         let playback_secs = self.playback_position.as_secs_f64();
+        // Frames put on screen this poll; all but the last were never actually seen.
+        let mut shown: u64 = 0;
 
         loop {
             // Get a frame: either from pending or from channel
@@ -2498,59 +6033,81 @@ impl App {
                 self.pending_frame.take().unwrap()
             } else if let Some(rx) = &self.decode_rx {
                 match rx.try_recv() {
-                    Ok(f) => f,
-                    Err(_) => return,
+                    Ok(f) => {
+                        self.playback_stats.record_frame_received(Instant::now());
+                        f
+                    }
+                    Err(_) => break,
                 }
             } else {
-                return;
+                break;
             };
 
             // Convert source PTS to timeline time
             let frame_timeline_time = frame.pts_secs + self.decode_time_offset;
 
             // When paused (scrubbing), always display immediately.
-            // When playing, only display if the frame's time has arrived.
-            if !self.is_playing || frame_timeline_time <= playback_secs + 0.02 {
+            // When playing, only display if the frame's time has arrived
+            // (in reverse, "arrived" means the playhead has fallen back to it).
+            let due = if self.playback_rate < 0.0 {
+                frame_timeline_time >= playback_secs - 0.02
+            } else {
+                frame_timeline_time <= playback_secs + 0.02
+            };
+            if !self.is_playing || due {
                 // Frame is already composited by the decode worker (multi-clip + transforms)
-                self.current_frame = Some(iced::widget::image::Handle::from_rgba(
+                let handle = iced::widget::image::Handle::from_rgba(
                     frame.width, frame.height, frame.rgba,
-                ));
+                );
+                let cache_key = if self.is_playing { None } else { self.pending_cache_key.take() };
+                if let Some(key) = cache_key {
+                    self.preview_cache.insert(key, handle.clone());
+                }
+                self.current_frame = Some(handle);
                 self.drain_stale = false;
+                shown += 1;
                 // Loop to check if there's an even more recent frame also due
             } else if self.drain_stale {
                 // After a decode transition, frames that are too far ahead are
                 // stale leftovers from the old context (wrong time offset).
                 // Discard them and try the next frame from the channel.
+                self.playback_stats.record_frame_dropped();
                 continue;
             } else {
                 // Frame is ahead of playback — hold it for a future tick
                 self.pending_frame = Some(frame);
-                return;
+                break;
+            }
+        }
+
+        if shown > 0 {
+            self.playback_stats.record_frame_displayed(Instant::now());
+            if self.is_playing {
+                self.playback_stats.dropped_frames += shown - 1;
             }
         }
     }
+
     /// Send a seek request to the audio decode thread for the current playback position.
     fn send_audio_decode_seek(&mut self, continuous: bool) {
-        // Drain stale audio from the channel
-        if let Some(rx) = &self.audio_decode_rx {
-            while rx.try_recv().is_ok() {}
-        }
+        // Drop audio buffered for the old position; the clock re-anchors once the
+        // device starts playing audio for the new one.
+        let epoch = self.audio_ring.flush();
 
         // Collect clip info upfront to avoid borrow conflicts with self
-        let playback_pos = self.playback_position.as_secs_f64();
         let mut audio_infos = Vec::new();
         let mut clip_ids = Vec::new();
         let mut first_time_offset = 0.0;
         let mut got_first = false;
 
-        for (_, track) in self.project.timeline.tracks.iter().enumerate() {
-            if track.track_type != TrackType::Audio {
+        for (i, track) in self.project.timeline.tracks.iter().enumerate() {
+            if !self.project.timeline.is_track_audible(i) {
                 continue;
             }
             if let Some(clip) = track.clip_at(self.playback_position) {
                 let clip_tl_start = clip.timeline_range.start.as_secs_f64();
                 let clip_src_start = clip.source_range.start.as_secs_f64();
-                let source_time = clip_src_start + (playback_pos - clip_tl_start);
+                let source_time = clip.source_secs_at(self.playback_position);
 
                 if let Some(asset) = self.project.source_library.get(clip.asset_id) {
                     if !got_first {
@@ -2561,6 +6118,12 @@ impl App {
                     audio_infos.push(AudioClipInfo {
                         path: asset.path.clone(),
                         time: source_time,
+                        track_index: i,
+                        channel_map: clip.channel_map,
+                        volume_envelope: clip.volume_envelope.clone(),
+                        source_start: clip_src_start,
+                        speed: clip.is_retimed().then(|| clip.speed()),
+                        preserve_pitch: clip.preserve_pitch,
                     });
                 }
             }
@@ -2569,15 +6132,10 @@ impl App {
         if audio_infos.is_empty() {
             self.audio_decode_clip_id = None;
             self.send_audio_decode_stop();
-            if let Some(player) = &self.audio_player {
-                player.stop();
-            }
             return;
         }
 
-        // Clear buffered audio from previous clips before starting new ones.
         if let Some(player) = &self.audio_player {
-            player.clear();
             player.play();
         }
         self.audio_decode_clip_id = clip_ids.into_iter().next();
@@ -2587,34 +6145,18 @@ impl App {
             let _ = tx.send(AudioDecodeRequest::SeekMulti {
                 clips: audio_infos,
                 continuous,
+                epoch,
             });
         }
     }
 
-    /// Tell the audio decode thread to stop.
+    /// Tell the audio decode thread to stop and silence whatever is buffered.
     fn send_audio_decode_stop(&self) {
+        self.audio_ring.flush();
         if let Some(tx) = &self.audio_decode_tx {
             let _ = tx.send(AudioDecodeRequest::Stop);
         }
     }
-
-    /// Drain decoded audio frames and feed them to the audio player.
-    fn poll_decoded_audio(&mut self) {
-        if self.audio_decode_clip_id.is_none() {
-            if let Some(rx) = &self.audio_decode_rx {
-                while rx.try_recv().is_ok() {}
-            }
-            return;
-        }
-
-        if let Some(rx) = &self.audio_decode_rx {
-            while let Ok(audio) = rx.try_recv() {
-                if let Some(player) = &self.audio_player {
-                    player.queue_audio(audio.samples, audio.sample_rate, audio.channels);
-                }
-            }
-        }
-    }
 }
 
 /// Convert RGB24 pixel data to RGBA32 (adds alpha=255).
@@ -2630,51 +6172,18 @@ pub fn rgb24_to_rgba32(rgb: &[u8], width: u32, height: u32) -> Vec<u8> {
     rgba
 }
 
-/// Nearest-neighbor scale + blit RGBA pixels onto a destination buffer.
-/// Handles negative offsets and out-of-bounds clipping.
-pub fn blit_rgba_scaled(
-    src: &[u8],
-    src_w: u32,
-    src_h: u32,
-    dst: &mut [u8],
-    dst_w: u32,
-    dst_h: u32,
-    offset_x: i32,
-    offset_y: i32,
-    clip_w: u32,
-    clip_h: u32,
-) {
-    if clip_w == 0 || clip_h == 0 {
-        return;
-    }
-
-    // Visible region in dst coords.
-    let x0 = offset_x.max(0) as u32;
-    let y0 = offset_y.max(0) as u32;
-    let x1 = ((offset_x + clip_w as i32) as u32).min(dst_w);
-    let y1 = ((offset_y + clip_h as i32) as u32).min(dst_h);
-
-    if x0 >= x1 || y0 >= y1 {
-        return;
-    }
-
-    for dy in y0..y1 {
-        // Map dst y to src y via nearest-neighbor.
-        let local_y = (dy as i32 - offset_y) as u32;
-        let sy = ((local_y as u64 * src_h as u64) / clip_h as u64).min(src_h as u64 - 1) as u32;
-        for dx in x0..x1 {
-            let local_x = (dx as i32 - offset_x) as u32;
-            let sx =
-                ((local_x as u64 * src_w as u64) / clip_w as u64).min(src_w as u64 - 1) as u32;
-            let si = (sy * src_w + sx) as usize * 4;
-            let di = (dy * dst_w + dx) as usize * 4;
-            dst[di..di + 4].copy_from_slice(&src[si..si + 4]);
-        }
-    }
-}
-
 /// Event filter for global mouse tracking during drag operations.
 /// Plain function pointer (not closure) as required by `event::listen_with`.
+/// Write a project file and keep a restore point of it.
+fn write_project(snapshot: &ProjectFile, path: &Path) -> Result<(), String> {
+    snapshot.write(path).map_err(|e| e.to_string())?;
+    // A restore point that fails to copy shouldn't fail the save itself
+    ProjectVersions::for_project(path)
+        .record(path, SystemTime::now(), versions::DEFAULT_KEPT_VERSIONS)
+        .ok();
+    Ok(())
+}
+
 fn drag_event_filter(event: Event, _status: event::Status, _window: window::Id) -> Option<Message> {
     match event {
         Event::Mouse(mouse::Event::CursorMoved { position }) => {
@@ -2687,20 +6196,131 @@ fn drag_event_filter(event: Event, _status: event::Status, _window: window::Id)
     }
 }
 
+fn splitter_event_filter(event: Event, _status: event::Status, _window: window::Id) -> Option<Message> {
+    match event {
+        Event::Mouse(mouse::Event::CursorMoved { position }) => {
+            Some(Message::SplitterMoved(position))
+        }
+        Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+            Some(Message::SplitterReleased)
+        }
+        _ => None,
+    }
+}
+
+/// Thin draggable divider between two panels.
+fn view_splitter<'a>(splitter: PanelSplitter) -> Element<'a, Message> {
+    const THICKNESS: f32 = 4.0;
+    let (bar, interaction) = match splitter {
+        PanelSplitter::LeftPanel | PanelSplitter::Inspector => (
+            container(Space::new()).width(THICKNESS).height(Length::Fill),
+            mouse::Interaction::ResizingHorizontally,
+        ),
+        PanelSplitter::Preview => (
+            container(Space::new()).width(Length::Fill).height(THICKNESS),
+            mouse::Interaction::ResizingVertically,
+        ),
+    };
+    mouse_area(bar.style(|_theme| container::Style {
+        background: Some(Background::Color(Color::from_rgb(0.22, 0.22, 0.25))),
+        ..Default::default()
+    }))
+    .on_press(Message::StartSplitterDrag(splitter))
+    .interaction(interaction)
+    .into()
+}
+
 /// Cached decoder state for the decode worker thread.
 struct CachedDecoder {
     decoder: zeditor_media::decoder::FfmpegDecoder,
     last_pts: f64,
+    /// First frame of a preloaded clip, decoded before its cut was reached.
+    preroll: Option<zeditor_media::decoder::VideoFrame>,
+}
+
+impl CachedDecoder {
+    /// Rough footprint: the codec's reference frames in YUV 4:2:0, plus the
+    /// preroll frame if one is held.
+    fn memory_bytes(&self) -> usize {
+        use zeditor_media::decoder::VideoDecoder;
+
+        let info = self.decoder.stream_info();
+        let surface = info.width as usize * info.height as usize * 3 / 2;
+        surface * DECODER_REFERENCE_FRAMES + self.preroll.as_ref().map_or(0, |f| f.data.len())
+    }
+}
+
+/// A decode worker's decoders as seen by the memory budget. Only decoders the
+/// current clips aren't using can be closed.
+struct DecoderPool<'a> {
+    decoders: &'a mut HashMap<PathBuf, CachedDecoder>,
+    active: &'a [ClipDecodeInfo],
+}
+
+impl Evictable for DecoderPool<'_> {
+    fn memory_bytes(&self) -> usize {
+        self.decoders.values().map(CachedDecoder::memory_bytes).sum()
+    }
+
+    fn evict_to(&mut self, max_bytes: usize) -> usize {
+        let before = self.memory_bytes();
+        let idle: Vec<PathBuf> = self
+            .decoders
+            .keys()
+            .filter(|path| !self.active.iter().any(|c| &c.path == *path))
+            .cloned()
+            .collect();
+        for path in idle {
+            if self.memory_bytes() <= max_bytes {
+                break;
+            }
+            self.decoders.remove(&path);
+        }
+        before - self.memory_bytes()
+    }
+}
+
+/// Start a new decode generation. A worker drops queued seeks from older
+/// generations (coalescing scrub bursts to the latest position) and abandons
+/// the request it is decoding once a newer generation exists.
+fn next_decode_generation(latest: &AtomicU64) -> u64 {
+    latest.fetch_add(1, Ordering::AcqRel) + 1
+}
+
+/// Lets decoding notice that the request it serves has been superseded.
+#[derive(Clone, Copy)]
+struct CancelToken<'a> {
+    latest: &'a AtomicU64,
+    generation: u64,
+}
+
+/// Backs tokens for work that is never superseded (preloads, pre-rendering).
+static NEVER_SUPERSEDED: AtomicU64 = AtomicU64::new(0);
+
+impl CancelToken<'_> {
+    fn never() -> CancelToken<'static> {
+        CancelToken {
+            latest: &NEVER_SUPERSEDED,
+            generation: 0,
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.latest.load(Ordering::Acquire) > self.generation
+    }
 }
 
 /// Background decode worker thread. Owns the FFmpeg decoder and runs ahead of playback.
 /// Supports both single-clip (legacy Seek) and multi-clip (SeekMulti) decode modes.
+/// `latest_generation` is bumped by the UI on every seek and stop; see
+/// `next_decode_generation`.
 fn decode_worker(
     request_rx: mpsc::Receiver<DecodeRequest>,
     frame_tx: mpsc::SyncSender<DecodedFrame>,
+    error_tx: mpsc::Sender<String>,
+    memory: MemoryBudget,
+    latest_generation: Arc<AtomicU64>,
 ) {
-    use zeditor_media::decoder::{FfmpegDecoder, VideoDecoder};
-
     let registry = EffectRegistry::with_builtins();
     let mut decoders: HashMap<PathBuf, CachedDecoder> = HashMap::new();
     let mut running = false;
@@ -2710,8 +6330,26 @@ fn decode_worker(
     let mut multi_clips: Vec<ClipDecodeInfo> = Vec::new();
     let mut multi_canvas_w: u32 = 1920;
     let mut multi_canvas_h: u32 = 1080;
+    let mut playback_rate: f64 = 1.0;
+    let mut multi_fps: f64 = 30.0;
+    let mut preview_quality = PreviewQuality::default();
+    let mut display_lut: Option<Arc<CubeLut>> = None;
+    // Preloads run on their own threads and hand the seeked decoder back here.
+    let (preload_tx, preload_rx) = mpsc::channel::<(PathBuf, Option<CachedDecoder>)>();
+    let mut preloading: HashSet<PathBuf> = HashSet::new();
+    // This worker's share of the decoder memory.
+    let mut decoder_bytes = 0;
+    let mut generation = 0;
+    let mut gpu = GpuCompositor::new();
 
     loop {
+        while let Ok((path, cached)) = preload_rx.try_recv() {
+            preloading.remove(&path);
+            if let Some(cached) = cached {
+                decoders.entry(path).or_insert(cached);
+            }
+        }
+
         let request = if running {
             match request_rx.try_recv() {
                 Ok(req) => Some(req),
@@ -2728,38 +6366,50 @@ fn decode_worker(
         if let Some(request) = request {
             match request {
                 DecodeRequest::SeekMulti {
+                    generation: request_generation,
                     clips,
                     continuous,
                     canvas_w,
                     canvas_h,
+                    rate,
+                    fps,
+                    quality,
+                    display_lut: lut,
                 } => {
+                    if request_generation < latest_generation.load(Ordering::Acquire) {
+                        // A newer seek or stop is queued behind this one.
+                        continue;
+                    }
+                    generation = request_generation;
                     multi_canvas_w = canvas_w;
                     multi_canvas_h = canvas_h;
+                    playback_rate = rate;
+                    multi_fps = fps;
+                    preview_quality = quality;
+                    display_lut = lut;
+
+                    // A cut reached before its preload finished: take the
+                    // preloaded decoder rather than opening a second one.
+                    let deadline = Instant::now() + PRELOAD_WAIT;
+                    while clips.iter().any(|c| preloading.contains(&c.path)) {
+                        let wait = deadline.saturating_duration_since(Instant::now());
+                        let Ok((path, cached)) = preload_rx.recv_timeout(wait) else {
+                            break;
+                        };
+                        preloading.remove(&path);
+                        if let Some(cached) = cached {
+                            decoders.entry(path).or_insert(cached);
+                        }
+                    }
 
                     // Open/seek all decoders
                     let mut ok = true;
+                    let tolerance = seek_tolerance(fps);
                     for clip in &clips {
-                        if !decoders.contains_key(&clip.path) {
-                            match FfmpegDecoder::open(&clip.path) {
-                                Ok(decoder) => {
-                                    decoders.insert(clip.path.clone(), CachedDecoder {
-                                        decoder,
-                                        last_pts: -1.0,
-                                    });
-                                }
-                                Err(_) => { ok = false; break; }
-                            }
-                        }
-                        let cached = decoders.get_mut(&clip.path).unwrap();
-                        let needs_seek = clip.time < cached.last_pts
-                            || (clip.time - cached.last_pts) > 2.0
-                            || cached.last_pts < 0.0;
-                        if needs_seek {
-                            if cached.decoder.seek_to(clip.time).is_err() {
-                                ok = false;
-                                break;
-                            }
-                            cached.last_pts = -1.0;
+                        if let Err(e) = prepare_decoder(&mut decoders, clip, tolerance) {
+                            let _ = error_tx.send(e);
+                            ok = false;
+                            break;
                         }
                     }
                     if !ok {
@@ -2772,6 +6422,20 @@ fn decode_worker(
                     is_continuous = continuous;
                     running = true;
                 }
+                DecodeRequest::Preload { clips, fps } => {
+                    for clip in clips {
+                        if preloading.contains(&clip.path) || multi_clips.iter().any(|c| c.path == clip.path) {
+                            continue;
+                        }
+                        let existing = decoders.remove(&clip.path);
+                        preloading.insert(clip.path.clone());
+                        let tx = preload_tx.clone();
+                        std::thread::spawn(move || {
+                            let cached = preload_decoder(existing, &clip, fps);
+                            let _ = tx.send((clip.path, cached));
+                        });
+                    }
+                }
                 DecodeRequest::Stop => {
                     running = false;
                     continue;
@@ -2779,11 +6443,18 @@ fn decode_worker(
             }
         }
 
+        memory.enforce_share(
+            MemorySubsystem::Decoders,
+            &mut decoder_bytes,
+            &mut DecoderPool { decoders: &mut decoders, active: &multi_clips },
+        );
+
         if !running {
             continue;
         }
 
         // Decode one frame from each clip, composite, and send
+        let cancel = CancelToken { latest: &latest_generation, generation };
         let result = decode_and_composite_multi(
             &multi_clips,
             &mut decoders,
@@ -2791,8 +6462,17 @@ fn decode_worker(
             multi_canvas_h,
             target_time,
             seeking_to_target,
+            multi_fps,
+            preview_scale_quality(is_continuous, preview_quality),
             &registry,
+            cancel,
+            gpu.as_mut(),
+            display_lut.as_deref(),
         );
+        if cancel.is_cancelled() {
+            // Superseded mid-decode: pick up the newer request instead.
+            continue;
+        }
         match result {
             Ok(Some(frame)) => {
                 seeking_to_target = false;
@@ -2801,6 +6481,21 @@ fn decode_worker(
                 }
                 if !is_continuous {
                     running = false;
+                } else if playback_rate < 0.0 {
+                    // Reverse: decoders only run forward, so step every clip back
+                    // and re-seek for each frame.
+                    let step = REVERSE_FRAME_STEP_SECS * playback_rate;
+                    target_time = (target_time + step).max(0.0);
+                    if !advance_clip_times(&mut multi_clips, &mut decoders, step, true) {
+                        running = false;
+                    }
+                    seeking_to_target = true;
+                } else if playback_rate > 1.0 {
+                    // Fast-forward: skip the frames in between displayed ones.
+                    let step = playback_rate / 30.0;
+                    target_time += step;
+                    advance_clip_times(&mut multi_clips, &mut decoders, step, false);
+                    seeking_to_target = true;
                 } else {
                     // Advance target time slightly for next frame
                     target_time += 1.0 / 30.0; // approximate frame step
@@ -2814,28 +6509,156 @@ fn decode_worker(
                 running = false;
             }
             Err(_) => {
+                let _ = error_tx.send("could not decode frame".to_string());
                 running = false;
             }
         }
     }
 }
 
+/// Make sure `clip` has an open decoder positioned to reach `clip.time` by
+/// decoding forward: opens one if needed and seeks unless the decoder (or its
+/// preroll frame) is already just before the target.
+fn prepare_decoder(
+    decoders: &mut HashMap<PathBuf, CachedDecoder>,
+    clip: &ClipDecodeInfo,
+    tolerance: f64,
+) -> std::result::Result<(), String> {
+    use zeditor_media::decoder::{FfmpegDecoder, VideoDecoder};
+
+    if !decoders.contains_key(&clip.path) {
+        let decoder = FfmpegDecoder::open(&clip.path)
+            .map_err(|e| format!("cannot open {}: {e}", clip.path.display()))?;
+        decoders.insert(clip.path.clone(), CachedDecoder {
+            decoder,
+            last_pts: -1.0,
+            preroll: None,
+        });
+    }
+    let cached = decoders.get_mut(&clip.path).unwrap();
+    let needs_seek = match &cached.preroll {
+        Some(frame) => clip.time + tolerance < frame.pts_secs || (clip.time - frame.pts_secs) > 2.0,
+        None => clip.time < cached.last_pts || (clip.time - cached.last_pts) > 2.0 || cached.last_pts < 0.0,
+    };
+    if needs_seek {
+        cached
+            .decoder
+            .seek_to(clip.time)
+            .map_err(|e| format!("seek failed in {}: {e}", clip.path.display()))?;
+        cached.last_pts = -1.0;
+        cached.preroll = None;
+    }
+    Ok(())
+}
+
+/// Open (or reuse) a decoder for a clip that starts at the next cut, seek it
+/// to the clip's first frame and decode that frame into `preroll`. Runs off
+/// the decode worker thread so playback of the current clip isn't held up.
+fn preload_decoder(existing: Option<CachedDecoder>, clip: &ClipDecodeInfo, fps: f64) -> Option<CachedDecoder> {
+    use zeditor_media::decoder::{FfmpegDecoder, VideoDecoder};
+
+    let mut cached = match existing {
+        Some(cached) => cached,
+        None => CachedDecoder {
+            decoder: FfmpegDecoder::open(&clip.path).ok()?,
+            last_pts: -1.0,
+            preroll: None,
+        },
+    };
+    cached.decoder.seek_to(clip.time).ok()?;
+    cached.last_pts = -1.0;
+    cached.preroll = None;
+    cached.preroll = decode_clip_frame(&mut cached, clip, true, fps, preview_scale_quality(true, PreviewQuality::Full), CancelToken::never());
+    Some(cached)
+}
+
+/// Bilinear keeps playback real-time; a paused or scrubbed frame stays on
+/// screen, so it gets the sharper Lanczos filter unless the user prefers
+/// draft previews.
+fn preview_scale_quality(continuous: bool, quality: PreviewQuality) -> ScaleQuality {
+    if continuous || quality == PreviewQuality::Draft {
+        ScaleQuality::Bilinear
+    } else {
+        ScaleQuality::Lanczos
+    }
+}
+
+/// Move each clip's decode target by `step` seconds of source time, relative to
+/// the last frame its decoder produced. With `reseek`, decoders are seeked to the
+/// new target (needed when stepping backwards). Returns false if a seek failed.
+fn advance_clip_times(
+    clips: &mut [ClipDecodeInfo],
+    decoders: &mut HashMap<PathBuf, CachedDecoder>,
+    step: f64,
+    reseek: bool,
+) -> bool {
+    use zeditor_media::decoder::VideoDecoder;
+
+    for clip in clips.iter_mut() {
+        let Some(cached) = decoders.get_mut(&clip.path) else {
+            continue;
+        };
+        let base = if cached.last_pts >= 0.0 { cached.last_pts } else { clip.time };
+        clip.time = (base + step).max(0.0);
+        if reseek {
+            if cached.decoder.seek_to(clip.time).is_err() {
+                return false;
+            }
+            cached.last_pts = -1.0;
+        }
+    }
+    true
+}
+
+/// A decoded clip frame, ready to composite onto the preview canvas.
+enum DecodedLayer {
+    /// No effects: blitted straight onto the canvas.
+    Plain(zeditor_media::decoder::VideoFrame),
+    /// Already run through the effect pipeline at canvas size.
+    Processed(pipeline::PipelineResult),
+    /// Effects that only move and fade the clip, left for the GPU compositor.
+    Positioned(zeditor_media::decoder::VideoFrame, pipeline::LayerGeometry),
+}
+
 /// Decode one frame from each clip and composite them into a single RGBA frame.
 /// Clips are ordered bottom-to-top (V1 first, VN last).
 /// Returns Ok(None) if all clips are at EOF.
 ///
+/// Each source decoder runs on its own rayon task, so overlapping tracks decode
+/// (and run their effects) in parallel; clips that share a source file share its
+/// decoder and are decoded in order on the same task. Layers are composited
+/// once all of them have arrived.
+///
 /// Clips with effects go through the pixel pipeline (decode → canvas buffer →
 /// effects → alpha composite). Clips without effects use the fast path
-/// (decode → direct blit, same as before).
+/// (decode → direct blit). Both scale with `quality`.
+///
+/// With a `gpu` compositor and bilinear quality (playback), the layers are
+/// composited on the GPU instead, and clips whose effects are only Transform
+/// and Opacity skip the pixel pipeline entirely. Lanczos (paused preview)
+/// stays on the CPU, as does everything if the GPU composite fails.
+///
+/// Each decoded frame is converted to the working colour space before its
+/// effects run; `display_lut` then applies to the finished composite only.
 fn decode_and_composite_multi(
     clips: &[ClipDecodeInfo],
     decoders: &mut HashMap<PathBuf, CachedDecoder>,
     canvas_w: u32,
     canvas_h: u32,
-    _target_time: f64,
+    target_time: f64,
     seeking: bool,
+    fps: f64,
+    quality: ScaleQuality,
     registry: &EffectRegistry,
+    cancel: CancelToken<'_>,
+    gpu: Option<&mut GpuCompositor>,
+    display_lut: Option<&CubeLut>,
 ) -> std::result::Result<Option<DecodedFrame>, ()> {
+    use rayon::prelude::*;
+
+    let gpu = gpu.filter(|_| quality == ScaleQuality::Bilinear);
+    let positioned_on_gpu = gpu.is_some();
+
     // Determine preview canvas size (fit canvas aspect ratio within PREVIEW_MAX).
     let scale_x = PREVIEW_MAX_WIDTH as f64 / canvas_w as f64;
     let scale_y = PREVIEW_MAX_HEIGHT as f64 / canvas_h as f64;
@@ -2843,97 +6666,228 @@ fn decode_and_composite_multi(
     let pw = (canvas_w as f64 * preview_scale).round() as u32;
     let ph = (canvas_h as f64 * preview_scale).round() as u32;
 
-    // Output canvas (black, opaque for background)
-    let mut canvas_buf = FrameBuffer::new(pw, ph);
-    // Fill with opaque black background
-    for pixel in canvas_buf.data.chunks_exact_mut(4) {
-        pixel[3] = 255;
-    }
-    let mut any_decoded = false;
-    let mut first_pts = 0.0_f64;
-
     let ctx = EffectContext {
-        time_secs: _target_time,
+        time_secs: target_time,
         frame_number: 0,
-        fps: 30.0,
+        fps,
+        scale_quality: quality,
     };
 
-    for (i, clip) in clips.iter().enumerate() {
-        let cached = match decoders.get_mut(&clip.path) {
-            Some(c) => c,
-            None => continue,
-        };
+    // Where a clip's decoded frame lands on the preview canvas, with 1:1
+    // sources scaled down like the canvas is.
+    let pipeline_placement = |clip: &ClipDecodeInfo, width: u32, height: u32, pts_secs: f64| {
+        let (source_w, source_h) = clip.source_size;
+        let original = (
+            (source_w as f64 * preview_scale).round() as u32,
+            (source_h as f64 * preview_scale).round() as u32,
+        );
+        clip.pan.apply(clip.fit.placement(width, height, original, pw, ph), pw, pts_secs)
+    };
+
+    // One job per decoder, holding the indices of the clips it feeds.
+    let mut jobs: Vec<(&mut CachedDecoder, Vec<usize>)> = decoders
+        .iter_mut()
+        .map(|(path, cached)| {
+            let indices = clips
+                .iter()
+                .enumerate()
+                .filter(|(_, clip)| &clip.path == path)
+                .map(|(i, _)| i)
+                .collect::<Vec<_>>();
+            (cached, indices)
+        })
+        .filter(|(_, indices)| !indices.is_empty())
+        .collect();
+
+    let mut layers: Vec<(usize, f64, DecodedLayer)> = jobs
+        .par_iter_mut()
+        .flat_map_iter(|(cached, indices)| {
+            indices
+                .iter()
+                .filter_map(|&i| {
+                    let mut frame = decode_clip_frame(cached, &clips[i], seeking, fps, quality, cancel)?;
+                    color::to_working_space(&mut frame.data, clips[i].color);
+                    let pts_secs = frame.pts_secs;
+                    let geometry = positioned_on_gpu
+                        .then(|| pipeline::layer_geometry(&clips[i].effects, registry))
+                        .flatten();
+                    let layer = if clips[i].effects.is_empty() {
+                        DecodedLayer::Plain(frame)
+                    } else if let Some(geometry) = geometry {
+                        DecodedLayer::Positioned(frame, geometry)
+                    } else {
+                        // Effect path: decode → pipeline (canvas buffer + effects)
+                        let clip_frame =
+                            FrameBuffer::from_rgba_vec(frame.width, frame.height, frame.data);
+                        let placement = pipeline_placement(&clips[i], clip_frame.width, clip_frame.height, pts_secs);
+                        DecodedLayer::Processed(pipeline::run_effect_pipeline(
+                            clip_frame, pw, ph, placement, &clips[i].effects, registry, &ctx,
+                        ))
+                    };
+                    Some((i, pts_secs, layer))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    if layers.is_empty() {
+        return Ok(None);
+    }
+    layers.sort_by_key(|(i, _, _)| *i);
+    let first_pts = layers
+        .iter()
+        .find(|(i, _, _)| *i == 0)
+        .map_or(0.0, |(_, pts_secs, _)| *pts_secs);
+
+    let plain_placement = |clip: &ClipDecodeInfo, width: u32, height: u32, pts_secs: f64| {
+        if clip.fit != CanvasFit::Fit {
+            return pipeline_placement(clip, width, height, pts_secs);
+        }
+        let fit_scale_x = canvas_w as f64 / width as f64;
+        let fit_scale_y = canvas_h as f64 / height as f64;
+        let fit_scale = fit_scale_x.min(fit_scale_y);
+        let clip_w_canvas = (width as f64 * fit_scale).round();
+        let clip_h_canvas = (height as f64 * fit_scale).round();
+        let center_x_canvas = (canvas_w as f64 - clip_w_canvas) / 2.0;
+        let center_y_canvas = (canvas_h as f64 - clip_h_canvas) / 2.0;
+
+        Placement {
+            x: (center_x_canvas * preview_scale).round() as i32,
+            y: (center_y_canvas * preview_scale).round() as i32,
+            width: (clip_w_canvas * preview_scale).round() as u32,
+            height: (clip_h_canvas * preview_scale).round() as u32,
+        }
+    };
 
-        // Decode frames until we get one at or past target
-        let frame = loop {
-            match cached.decoder.decode_next_frame_rgba_scaled(PREVIEW_MAX_WIDTH, PREVIEW_MAX_HEIGHT) {
-                Ok(Some(f)) => {
-                    cached.last_pts = f.pts_secs;
-                    if seeking && f.pts_secs < clip.time - 0.05 {
-                        continue; // skip pre-target frames
+    if let Some(gpu) = gpu {
+        let gpu_layers: Vec<GpuLayer<'_>> = layers
+            .iter()
+            .map(|(i, pts_secs, layer)| match layer {
+                DecodedLayer::Plain(frame) => GpuLayer {
+                    rgba: &frame.data,
+                    width: frame.width,
+                    height: frame.height,
+                    dest: plain_placement(&clips[*i], frame.width, frame.height, *pts_secs),
+                    opacity: 1.0,
+                },
+                DecodedLayer::Positioned(frame, geometry) => {
+                    // Same placement as the pixel pipeline, then its whole-pixel shift.
+                    let fit = pipeline_placement(&clips[*i], frame.width, frame.height, *pts_secs);
+                    GpuLayer {
+                        rgba: &frame.data,
+                        width: frame.width,
+                        height: frame.height,
+                        dest: Placement {
+                            x: (fit.x as i64 + geometry.x_offset) as i32,
+                            y: (fit.y as i64 + geometry.y_offset) as i32,
+                            ..fit
+                        },
+                        opacity: geometry.opacity as f32,
                     }
-                    break Some(f);
                 }
-                Ok(None) => break None,
-                Err(_) => break None,
+                DecodedLayer::Processed(result) => GpuLayer {
+                    rgba: &result.frame.data,
+                    width: result.frame.width,
+                    height: result.frame.height,
+                    dest: Placement { x: 0, y: 0, width: pw, height: ph },
+                    opacity: 1.0,
+                },
+            })
+            .collect();
+        if let Some(mut rgba) = gpu.composite(&gpu_layers, pw, ph) {
+            if let Some(lut) = display_lut {
+                lut.apply(&mut rgba);
             }
-        };
+            return Ok(Some(DecodedFrame {
+                rgba,
+                width: pw,
+                height: ph,
+                pts_secs: first_pts,
+            }));
+        }
+    }
 
-        if let Some(frame) = frame {
-            if i == 0 {
-                first_pts = frame.pts_secs;
-            }
+    // Output canvas starts fully transparent; the viewport draws its
+    // backdrop (checkerboard or solid color) behind uncovered areas.
+    let mut canvas_buf = FrameBuffer::new(pw, ph);
 
-            if clip.effects.is_empty() {
+    for (i, pts_secs, layer) in layers {
+        match layer {
+            DecodedLayer::Plain(frame) => {
                 // Fast path: no effects, direct blit (opaque overwrite)
-                let fit_scale_x = canvas_w as f64 / frame.width as f64;
-                let fit_scale_y = canvas_h as f64 / frame.height as f64;
-                let fit_scale = fit_scale_x.min(fit_scale_y);
-                let clip_w_canvas = (frame.width as f64 * fit_scale).round();
-                let clip_h_canvas = (frame.height as f64 * fit_scale).round();
-                let center_x_canvas = (canvas_w as f64 - clip_w_canvas) / 2.0;
-                let center_y_canvas = (canvas_h as f64 - clip_h_canvas) / 2.0;
-
-                let offset_x = (center_x_canvas * preview_scale).round() as i32;
-                let offset_y = (center_y_canvas * preview_scale).round() as i32;
-                let clip_w = (clip_w_canvas * preview_scale).round() as u32;
-                let clip_h = (clip_h_canvas * preview_scale).round() as u32;
-
-                blit_rgba_scaled(
-                    &frame.data, frame.width, frame.height,
-                    &mut canvas_buf.data, pw, ph,
-                    offset_x, offset_y, clip_w, clip_h,
-                );
-            } else {
-                // Effect path: decode → pipeline (canvas buffer + effects) → smart composite
-                let clip_frame = FrameBuffer::from_rgba_vec(
-                    frame.width, frame.height, frame.data,
-                );
+                let placement = plain_placement(&clips[i], frame.width, frame.height, pts_secs);
+                let clip_frame = FrameBuffer::from_rgba_vec(frame.width, frame.height, frame.data);
+                scaler::blit_scaled(&clip_frame, &mut canvas_buf, placement, quality);
+            }
+            DecodedLayer::Positioned(frame, _) => {
+                // The GPU composite failed; run the effects after all.
+                let clip_frame = FrameBuffer::from_rgba_vec(frame.width, frame.height, frame.data);
+                let placement = pipeline_placement(&clips[i], clip_frame.width, clip_frame.height, pts_secs);
                 let result = pipeline::run_effect_pipeline(
-                    clip_frame, pw, ph, &clip.effects, registry, &ctx,
+                    clip_frame, pw, ph, placement, &clips[i].effects, registry, &ctx,
                 );
+                pipeline::alpha_composite_rgba(&result.frame, &mut canvas_buf);
+            }
+            DecodedLayer::Processed(result) => {
                 if !result.may_have_transparency && result.fills_canvas {
                     pipeline::composite_opaque(&result.frame, &mut canvas_buf);
                 } else {
                     pipeline::alpha_composite_rgba(&result.frame, &mut canvas_buf);
                 }
             }
-            any_decoded = true;
         }
     }
+    if let Some(lut) = display_lut {
+        lut.apply(&mut canvas_buf.data);
+    }
 
-    if any_decoded {
-        Ok(Some(DecodedFrame {
-            rgba: canvas_buf.data,
-            width: pw,
-            height: ph,
-            pts_secs: first_pts,
-        }))
-    } else {
-        Ok(None)
+    Ok(Some(DecodedFrame {
+        rgba: canvas_buf.data,
+        width: pw,
+        height: ph,
+        pts_secs: first_pts,
+    }))
+}
+
+/// Decode frames from `cached` until one lands at or past the clip's target
+/// (while seeking), or just the next frame during playback. Gives up with
+/// `None` once `cancel` reports that the request was superseded.
+fn decode_clip_frame(
+    cached: &mut CachedDecoder,
+    clip: &ClipDecodeInfo,
+    seeking: bool,
+    fps: f64,
+    quality: ScaleQuality,
+    cancel: CancelToken<'_>,
+) -> Option<zeditor_media::decoder::VideoFrame> {
+    let tolerance = seek_tolerance(fps);
+    if let Some(frame) = cached.preroll.take().filter(|f| !seeking || f.pts_secs >= clip.time - tolerance) {
+        return Some(frame);
+    }
+    cached.decoder.set_scale_quality(quality);
+    loop {
+        if cancel.is_cancelled() {
+            return None;
+        }
+        match cached.decoder.decode_next_frame_rgba_scaled(PREVIEW_MAX_WIDTH, PREVIEW_MAX_HEIGHT) {
+            Ok(Some(f)) => {
+                cached.last_pts = f.pts_secs;
+                if seeking && f.pts_secs < clip.time - tolerance {
+                    continue; // skip pre-target frames
+                }
+                return Some(f);
+            }
+            Ok(None) | Err(_) => return None,
+        }
     }
 }
 
+/// Frames more than half a frame before the target are skipped while seeking,
+/// so a scrub lands on exactly the requested frame.
+fn seek_tolerance(fps: f64) -> f64 {
+    if fps > 0.0 { 0.5 / fps } else { 0.05 }
+}
+
 /// Cached audio decoder state for the audio decode worker thread.
 struct CachedAudioDecoder {
     decoder: zeditor_media::audio_decoder::FfmpegAudioDecoder,
@@ -2943,18 +6897,24 @@ struct CachedAudioDecoder {
 
 /// Background audio decode worker thread. Supports multi-clip mixing.
 /// Uses per-clip-index decoders (not per-path) so overlapping clips from the
-/// same source file each get their own independent decoder.
+/// same source file each get their own independent decoder. Mixed audio goes
+/// straight into the ring the output device reads; a full ring throttles the
+/// worker, and a flush (every seek or stop) abandons the audio being written.
 fn audio_decode_worker(
     request_rx: mpsc::Receiver<AudioDecodeRequest>,
-    audio_tx: mpsc::SyncSender<DecodedAudio>,
+    ring: Arc<AudioRing>,
+    levels_tx: mpsc::Sender<AudioLevels>,
 ) {
     use zeditor_media::audio_decoder::FfmpegAudioDecoder;
 
     let mut audio_decoders: Vec<Option<CachedAudioDecoder>> = Vec::new();
+    // Per clip index, restarted on every seek
+    let mut retimes: Vec<Option<Retime>> = Vec::new();
     let mut running = false;
     let mut is_continuous = false;
     let mut seeking_to_target = false;
     let mut multi_clips: Vec<AudioClipInfo> = Vec::new();
+    let mut epoch = 0;
 
     loop {
         let request = if running {
@@ -2975,7 +6935,9 @@ fn audio_decode_worker(
                 AudioDecodeRequest::SeekMulti {
                     clips,
                     continuous,
+                    epoch: request_epoch,
                 } => {
+                    epoch = request_epoch;
                     // Resize decoder vec to match clip count
                     audio_decoders.resize_with(clips.len(), || None);
                     audio_decoders.truncate(clips.len());
@@ -3016,6 +6978,10 @@ fn audio_decode_worker(
                         running = false;
                         continue;
                     }
+                    retimes = clips
+                        .iter()
+                        .map(|clip| clip.speed.map(|speed| Retime::new(speed, clip.preserve_pitch)))
+                        .collect();
                     multi_clips = clips;
                     seeking_to_target = true;
                     is_continuous = continuous;
@@ -3038,6 +7004,7 @@ fn audio_decode_worker(
         let mut sample_rate = 48000u32;
         let mut channels = 2u16;
         let mut any_decoded = false;
+        let mut peaks = Vec::with_capacity(multi_clips.len());
 
         for (i, clip) in multi_clips.iter().enumerate() {
             let cached = match audio_decoders.get_mut(i).and_then(|c| c.as_mut()) {
@@ -3059,13 +7026,23 @@ fn audio_decode_worker(
                 }
             };
 
-            if let Some(frame) = frame {
+            if let Some(mut frame) = frame {
+                clip.channel_map.apply(&mut frame.samples, frame.channels as usize);
+                clip.volume_envelope.apply(&mut frame.samples, frame.channels as usize, frame.sample_rate, frame.pts_secs);
+                // Retimed stereo plays at its speed; the PTS is scaled to
+                // match so the clock's source-to-timeline offset still holds.
+                let mut pts_secs = frame.pts_secs;
+                if let (Some(speed), Some(retime), 2) = (clip.speed, retimes.get_mut(i).and_then(|r| r.as_mut()), frame.channels) {
+                    frame.samples = retime.process(&frame.samples);
+                    pts_secs = clip.source_start + (pts_secs - clip.source_start) / speed;
+                }
                 if i == 0 {
-                    first_pts = frame.pts_secs;
+                    first_pts = pts_secs;
                     sample_rate = frame.sample_rate;
                     channels = frame.channels;
                 }
                 any_decoded = true;
+                peaks.push((clip.track_index, audio_meters::peak(&frame.samples)));
 
                 match &mut mixed_samples {
                     None => {
@@ -3089,14 +7066,23 @@ fn audio_decode_worker(
         seeking_to_target = false;
 
         if any_decoded {
-            let decoded = DecodedAudio {
-                samples: mixed_samples.unwrap_or_default(),
-                sample_rate,
-                channels,
+            let samples = mixed_samples.unwrap_or_default();
+            let frame_samples = sample_rate as f64 * channels.max(1) as f64;
+            // The UI drops these if it has gone away.
+            let _ = levels_tx.send(AudioLevels {
+                epoch,
                 pts_secs: first_pts,
-            };
-            if audio_tx.send(decoded).is_err() {
-                return;
+                duration_secs: samples.len() as f64 / frame_samples,
+                peaks,
+            });
+            let mut written = 0;
+            while written < samples.len() {
+                match ring.push(epoch, first_pts, sample_rate, channels, &samples[written..]) {
+                    Some(0) => std::thread::sleep(AUDIO_RING_FULL_WAIT),
+                    Some(n) => written += n,
+                    // Superseded by a seek or stop; pick up the new request.
+                    None => break,
+                }
             }
             if !is_continuous {
                 running = false;
@@ -0,0 +1,423 @@
+//! Modal dialogs: confirm, prompt and choice dialogs, project versions and path remapping.
+
+use super::*;
+
+impl App {
+    /// Answers to the confirm dialog and the version and path remap dialogs.
+    pub(super) fn update_dialogs(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::ConfirmDialogAccepted => {
+                let Some(dialog) = self.confirm_dialog.take() else {
+                    return Task::none();
+                };
+                let answer = match &dialog.input {
+                    DialogInput::Confirm => DialogAnswer::Accepted,
+                    DialogInput::Text(text) => DialogAnswer::Text(text.clone()),
+                    // Choices are answered by their own buttons
+                    DialogInput::Choice(_) => {
+                        self.confirm_dialog = Some(dialog);
+                        return Task::none();
+                    }
+                };
+                self.answer_dialog(dialog.action, answer)
+            }
+            Message::ConfirmDialogTextChanged(text) => {
+                if let Some(ConfirmDialog { input: DialogInput::Text(value), .. }) = &mut self.confirm_dialog {
+                    *value = text;
+                }
+                Task::none()
+            }
+            Message::ConfirmDialogChoice(index) => match self.confirm_dialog.take() {
+                Some(dialog) => self.answer_dialog(dialog.action, DialogAnswer::Choice(index)),
+                None => Task::none(),
+            },
+            Message::ConfirmDialogDismissed => {
+                match self.confirm_dialog.take().map(|d| d.action) {
+                    Some(ConfirmAction::ReplayJournal { .. }) => {
+                        self.reset_journal();
+                        self.status_message = self.i18n.tr("status-journal-discarded");
+                    }
+                    Some(ConfirmAction::RenderOutputExists) => {
+                        self.renders_awaiting_overwrite.clear();
+                        self.status_message = self.i18n.tr("status-render-cancelled");
+                    }
+                    _ => {}
+                }
+                Task::none()
+            }
+            Message::ShowProjectVersions => {
+                let Some(path) = self.project_path.clone() else {
+                    self.status_message = self.i18n.tr("status-versions-need-save");
+                    return Task::none();
+                };
+                match ProjectVersions::for_project(&path).list() {
+                    Ok(list) if list.is_empty() => {
+                        self.status_message = self.i18n.tr("status-no-versions");
+                    }
+                    Ok(list) => self.version_dialog = Some(list),
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-load-failed", &[("error", &e)]);
+                    }
+                }
+                Task::none()
+            }
+            Message::RestoreProjectVersion(version_path) => {
+                self.version_dialog = None;
+                match zeditor_core::project::Project::load(&version_path) {
+                    Ok(project) => {
+                        // `project_path` stays on the original file, so the next
+                        // save overwrites it and records a fresh version.
+                        self.reset_ui_state();
+                        self.project = project;
+                        self.reset_journal();
+                        let label = version_path
+                            .file_stem()
+                            .map(|s| s.to_string_lossy().into_owned())
+                            .unwrap_or_default();
+                        self.status_message = self.i18n.tr_args("status-version-restored", &[("version", &label)]);
+                        self.build_seek_indexes(self.video_paths());
+                        self.regenerate_all_thumbnails()
+                    }
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-load-failed", &[("error", &e)]);
+                        Task::none()
+                    }
+                }
+            }
+            Message::ProjectVersionsDismissed => {
+                self.version_dialog = None;
+                Task::none()
+            }
+            Message::OpenPathRemap => {
+                // Start from the folder of the first missing file, the usual thing to replace
+                let from = self
+                    .project
+                    .source_library
+                    .assets()
+                    .iter()
+                    .find(|asset| !asset.path.exists())
+                    .and_then(|asset| asset.path.parent())
+                    .map(|dir| dir.display().to_string())
+                    .unwrap_or_default();
+                self.path_remap_dialog = Some(PathRemapDialog { from, ..Default::default() });
+                self.refresh_path_remap_preview();
+                Task::none()
+            }
+            Message::PathRemapFromChanged(from) => {
+                if let Some(dialog) = &mut self.path_remap_dialog {
+                    dialog.from = from;
+                }
+                self.refresh_path_remap_preview();
+                Task::none()
+            }
+            Message::PathRemapToChanged(to) => {
+                if let Some(dialog) = &mut self.path_remap_dialog {
+                    dialog.to = to;
+                }
+                self.refresh_path_remap_preview();
+                Task::none()
+            }
+            Message::ApplyPathRemap => {
+                let Some(dialog) = &self.path_remap_dialog else {
+                    return Task::none();
+                };
+                let (from, to) = (PathBuf::from(dialog.from.trim()), PathBuf::from(dialog.to.trim()));
+                if to.as_os_str().is_empty() {
+                    self.status_message = self.i18n.tr("status-remap-nothing-found");
+                    return Task::none();
+                }
+                let applied = self.project.source_library.remap_paths(&from, &to);
+                if applied.is_empty() {
+                    self.status_message = self.i18n.tr("status-remap-nothing-found");
+                    return Task::none();
+                }
+                self.path_remap_dialog = None;
+                self.notify(
+                    NotificationLevel::Success,
+                    self.i18n.tr_args("status-paths-remapped", &[("count", &applied.len())]),
+                );
+
+                // Frames and thumbnails of the relinked assets were never loaded
+                if self.source_monitor.asset_id.is_some_and(|id| applied.iter().any(|r| r.asset_id == id)) {
+                    self.source_monitor.close();
+                    self.source_pending_frame = None;
+                }
+                self.preview_cache.clear();
+                self.prerender_cache.clear();
+                self.prerender_planned.clear();
+                self.send_decode_seek(false);
+                let thumbnails: Vec<Task<Message>> = applied
+                    .into_iter()
+                    .map(|remap| {
+                        self.thumbnails.remove(&remap.asset_id);
+                        self.thumbnail_task(remap.asset_id, remap.new_path)
+                    })
+                    .collect();
+                Task::batch(thumbnails)
+            }
+            Message::PathRemapDismissed => {
+                self.path_remap_dialog = None;
+                Task::none()
+            }
+            _ => Task::none(),
+        }
+    }
+
+    /// Hand a dialog's answer to the action it was opened for.
+    pub(super) fn answer_dialog(&mut self, action: ConfirmAction, answer: DialogAnswer) -> Task<Message> {
+        match (action, answer) {
+            (ConfirmAction::RemoveAsset { asset_id }, _) => self.update(Message::RemoveAsset(asset_id)),
+            (ConfirmAction::ReplayJournal { .. }, _) => self.replay_journal(),
+            (ConfirmAction::DeleteTrack { track_index }, _) => self.delete_track(track_index),
+            (ConfirmAction::RelinkAsset { asset_id }, DialogAnswer::Text(path)) => {
+                let path = path.trim();
+                if path.is_empty() {
+                    return Task::none();
+                }
+                self.update(Message::ReplaceMediaFileChosen { asset_id, path: Some(PathBuf::from(path)) })
+            }
+            // Options are "Save" then "Discard"
+            (ConfirmAction::UnsavedChanges { then }, DialogAnswer::Choice(0)) => {
+                let Some(path) = self.project_path.clone() else {
+                    return self.update(Message::SaveProjectAs);
+                };
+                if self.pending_save.is_some() {
+                    self.status_message = self.i18n.tr("status-save-in-progress");
+                    return Task::none();
+                }
+                // Saved in place: the next step may replace the project or exit
+                let snapshot = self.project.save_snapshot();
+                let result = write_project(&snapshot, &path);
+                let saved = result.is_ok();
+                self.finish_save(path, &snapshot.project.timeline, &snapshot.project.source_library, result);
+                if !saved {
+                    return Task::none();
+                }
+                self.leave_project(then)
+            }
+            (ConfirmAction::UnsavedChanges { then }, DialogAnswer::Choice(_)) => {
+                self.reset_journal();
+                self.leave_project(then)
+            }
+            // Options are "Overwrite" then "Keep both"
+            (ConfirmAction::RenderOutputExists, DialogAnswer::Choice(0)) => {
+                let jobs = std::mem::take(&mut self.renders_awaiting_overwrite);
+                self.render_queue.extend(jobs);
+                self.start_next_render()
+            }
+            (ConfirmAction::RenderOutputExists, DialogAnswer::Choice(_)) => {
+                for mut job in std::mem::take(&mut self.renders_awaiting_overwrite) {
+                    match output_check::next_free_path(&job.output_path) {
+                        Ok(path) => {
+                            job.output_path = path;
+                            self.render_queue.push_back(job);
+                        }
+                        Err(e) => self.notify(
+                            NotificationLevel::Error,
+                            self.i18n.tr_args(
+                                "status-render-output-unusable",
+                                &[("path", &job.output_path.display()), ("error", &e)],
+                            ),
+                        ),
+                    }
+                }
+                self.start_next_render()
+            }
+            _ => Task::none(),
+        }
+    }
+
+    /// Recompute which assets the open path remap dialog would repoint. An
+    /// empty replacement matches nothing rather than making paths relative.
+    pub(super) fn refresh_path_remap_preview(&mut self) {
+        let Some(dialog) = &mut self.path_remap_dialog else {
+            return;
+        };
+        let (from, to) = (dialog.from.trim(), dialog.to.trim());
+        dialog.preview = if to.is_empty() {
+            Vec::new()
+        } else {
+            self.project.source_library.preview_path_remap(Path::new(from), Path::new(to))
+        };
+    }
+
+    /// `base_layout` under the confirm, folder import, version or path remap
+    /// dialog, whichever is open.
+    pub(super) fn view_dialogs<'a>(&'a self, base_layout: Element<'a, Message>) -> Element<'a, Message> {
+        // Add confirmation dialog overlay if present
+        let base_layout: Element<'_, Message> = if let Some(dialog) = &self.confirm_dialog {
+            // Accept stays red as most of these remove something; choices are blue
+            let mut buttons: Vec<Element<'_, Message>> = match &dialog.input {
+                DialogInput::Choice(options) => options
+                    .iter()
+                    .enumerate()
+                    .map(|(index, key)| {
+                        dialog::dialog_button(self.i18n.tr(key), Message::ConfirmDialogChoice(index), dialog::PRIMARY).into()
+                    })
+                    .collect(),
+                DialogInput::Confirm | DialogInput::Text(_) => vec![dialog::dialog_button(
+                    self.i18n.tr(dialog.action.accept_label_key()),
+                    Message::ConfirmDialogAccepted,
+                    dialog::DESTRUCTIVE,
+                )
+                .into()],
+            };
+            buttons.push(
+                dialog::dialog_button(self.i18n.tr("dialog-cancel"), Message::ConfirmDialogDismissed, dialog::SECONDARY)
+                    .into(),
+            );
+
+            let mut dialog_column = column![text(&dialog.message).size(14).color(Color::WHITE)];
+            if let DialogInput::Text(value) = &dialog.input {
+                dialog_column = dialog_column.push(
+                    text_input("", value)
+                        .on_input(Message::ConfirmDialogTextChanged)
+                        .on_submit(Message::ConfirmDialogAccepted)
+                        .size(13)
+                        .padding(4),
+                );
+            }
+            let dialog_card = dialog::dialog_card(dialog_column.push(row(buttons).spacing(8)).spacing(12), 400.0);
+            dialog::dialog_overlay(base_layout, dialog_card, Some(Message::ConfirmDialogDismissed))
+        } else {
+            base_layout
+        };
+
+        // Folder import progress blocks the editor until every file is probed
+        let base_layout: Element<'_, Message> = if let Some(progress) = &self.folder_import {
+            let fraction = progress.done as f32 / progress.total.max(1) as f32;
+            let dialog_card = dialog::dialog_card(
+                column![
+                    text(self.i18n.tr_args("library-importing-folder", &[("name", &progress.bin)]))
+                        .size(14)
+                        .color(Color::WHITE),
+                    iced::widget::progress_bar(0.0..=1.0, fraction),
+                    text(self.i18n.tr_args(
+                        "library-import-progress",
+                        &[("done", &progress.done), ("total", &progress.total)],
+                    ))
+                        .size(12)
+                        .color(Color::from_rgb(0.7, 0.7, 0.7)),
+                ]
+                .spacing(12),
+                400.0,
+            );
+            dialog::dialog_overlay(base_layout, dialog_card, None)
+        } else {
+            base_layout
+        };
+
+        // Add restore-version dialog overlay if open
+        let base_layout: Element<'_, Message> = if let Some(versions) = &self.version_dialog {
+            let entries: Vec<Element<'_, Message>> = versions
+                .iter()
+                .map(|version| {
+                    button(text(version.label.replacen('T', "  ", 1)).size(13).color(Color::WHITE))
+                        .on_press(Message::RestoreProjectVersion(version.path.clone()))
+                        .width(Length::Fill)
+                        .padding([4, 8])
+                        .style(|_theme, status| {
+                            let bg = match status {
+                                button::Status::Hovered => Color::from_rgb(0.3, 0.3, 0.36),
+                                _ => Color::from_rgb(0.26, 0.26, 0.3),
+                            };
+                            button::Style {
+                                background: Some(Background::Color(bg)),
+                                text_color: Color::WHITE,
+                                border: Border {
+                                    radius: 4.0.into(),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            }
+                        })
+                        .into()
+                })
+                .collect();
+
+            let dialog_card = dialog::dialog_card(
+                column![
+                    text(self.i18n.tr("dialog-restore-version")).size(14).color(Color::WHITE),
+                    scrollable(column(entries).spacing(4)).height(Length::Shrink),
+                    dialog::dialog_button(
+                        self.i18n.tr("dialog-cancel"),
+                        Message::ProjectVersionsDismissed,
+                        dialog::SECONDARY,
+                    ),
+                ]
+                .spacing(12),
+                400.0,
+            )
+            .max_height(480);
+            dialog::dialog_overlay(base_layout, dialog_card, Some(Message::ProjectVersionsDismissed))
+        } else {
+            base_layout
+        };
+
+        // Add path remap dialog overlay if open
+        if let Some(dialog) = &self.path_remap_dialog {
+            let field_row = |key: &'static str, value: &str, on_input: fn(String) -> Message| {
+                row![
+                    text(self.i18n.tr(key)).size(13).color(Color::WHITE).width(70),
+                    text_input("", value)
+                        .on_input(on_input)
+                        .on_submit(Message::ApplyPathRemap)
+                        .size(13)
+                        .padding(4),
+                ]
+                .spacing(8)
+                .align_y(iced::Alignment::Center)
+            };
+            // One line per matching asset: found files in green, still missing in red
+            let entries: Vec<Element<'_, Message>> = dialog
+                .preview
+                .iter()
+                .map(|remap| {
+                    let (mark, color) = if remap.resolves {
+                        ("\u{2713}", Color::from_rgb(0.45, 0.8, 0.45))
+                    } else {
+                        ("\u{2717}", Color::from_rgb(0.9, 0.4, 0.4))
+                    };
+                    row![
+                        text(mark).size(12).color(color),
+                        column![
+                            text(remap.old_path.display().to_string()).size(11).color(Color::from_rgb(0.6, 0.6, 0.65)),
+                            text(remap.new_path.display().to_string()).size(12).color(color),
+                        ],
+                    ]
+                    .spacing(6)
+                    .into()
+                })
+                .collect();
+            let found = dialog.preview.iter().filter(|remap| remap.resolves).count();
+            let summary = self.i18n.tr_args(
+                "dialog-remap-summary",
+                &[("found", &found), ("count", &dialog.preview.len())],
+            );
+            let dialog_card = dialog::dialog_card(
+                column![
+                    text(self.i18n.tr("dialog-remap-paths")).size(14).color(Color::WHITE),
+                    field_row("dialog-remap-from", &dialog.from, Message::PathRemapFromChanged),
+                    field_row("dialog-remap-to", &dialog.to, Message::PathRemapToChanged),
+                    text(summary).size(12).color(Color::from_rgb(0.75, 0.75, 0.8)),
+                    scrollable(column(entries).spacing(6)).height(Length::Shrink),
+                    row![
+                        dialog::dialog_button(self.i18n.tr("dialog-apply"), Message::ApplyPathRemap, dialog::PRIMARY),
+                        dialog::dialog_button(
+                            self.i18n.tr("dialog-cancel"),
+                            Message::PathRemapDismissed,
+                            dialog::SECONDARY,
+                        ),
+                    ]
+                    .spacing(8),
+                ]
+                .spacing(10),
+                560.0,
+            )
+            .max_height(520);
+            dialog::dialog_overlay(base_layout, dialog_card, Some(Message::PathRemapDismissed))
+        } else {
+            base_layout
+        }
+    }
+}
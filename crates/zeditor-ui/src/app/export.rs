@@ -0,0 +1,420 @@
+//! Exports: timeline renders, the render queue and render ranges, and the timeline description.
+
+use super::*;
+
+impl App {
+    /// Render and export requests, including the render ranges tab.
+    pub(super) fn update_export(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::ExportTimelineDescription => Task::perform(
+                async {
+                    let handle = rfd::AsyncFileDialog::new()
+                        .add_filter("Text", &["txt"])
+                        .set_title("Export Description")
+                        .save_file()
+                        .await;
+                    handle.map(|f| f.path().to_path_buf())
+                },
+                Message::DescriptionFileDialogResult,
+            ),
+            Message::DescriptionFileDialogResult(path) => {
+                if let Some(mut path) = path {
+                    if path.extension().is_none_or(|e| e != "txt") {
+                        path.set_extension("txt");
+                    }
+                    let summary = description::timeline_description(&self.project.timeline, &self.project.source_library);
+                    self.status_message = match std::fs::write(&path, summary) {
+                        Ok(()) => self.i18n.tr_args("status-description-exported", &[("path", &path.display())]),
+                        Err(e) => self.i18n.tr_args("status-description-export-failed", &[("error", &e)]),
+                    };
+                }
+                Task::none()
+            }
+            Message::ToggleExportStems => {
+                self.preferences.export_stems = !self.preferences.export_stems;
+                self.save_preferences();
+                self.status_message = self.i18n.tr(if self.preferences.export_stems {
+                    "status-export-stems-on"
+                } else {
+                    "status-export-stems-off"
+                });
+                Task::none()
+            }
+            Message::RenderFileDialogResult(path) => {
+                match path {
+                    Some(mut path) => {
+                        // Ensure .mkv extension
+                        if path.extension().is_none_or(|e| e != "mkv") {
+                            path.set_extension("mkv");
+                        }
+                        let job = RenderJob {
+                            timeline: self.project.timeline.clone(),
+                            source_library: self.project.source_library.clone(),
+                            output_path: path,
+                            loop_crossfade: None,
+                        };
+                        self.queue_renders(vec![job])
+                    }
+                    None => {
+                        self.status_message = self.i18n.tr("status-render-cancelled");
+                        Task::none()
+                    }
+                }
+            }
+            Message::RenderComplete(path) => {
+                let total_time = self
+                    .render_start
+                    .map(|s| s.elapsed())
+                    .unwrap_or_default();
+                let total_secs = total_time.as_secs();
+                let total_ms = total_time.subsec_millis();
+                let message = self.i18n.tr_args(
+                    "status-rendered",
+                    &[
+                        ("path", &path.display()),
+                        (
+                            "elapsed",
+                            &format!("{}:{:02}.{:03}", total_secs / 60, total_secs % 60, total_ms),
+                        ),
+                    ],
+                );
+                self.notify(
+                    NotificationLevel::Success,
+                    message,
+                );
+                self.is_rendering = false;
+                self.render_progress_rx = None;
+                self.render_start = None;
+                self.start_next_render()
+            }
+            Message::RenderError(msg) => {
+                self.notify(NotificationLevel::Error, self.i18n.tr_args("status-render-failed", &[("error", &msg)]));
+                self.is_rendering = false;
+                self.render_progress_rx = None;
+                self.render_start = None;
+                self.start_next_render()
+            }
+            Message::SetRenderRangeIn => {
+                self.render_range_in = Some(self.playback_position);
+                Task::none()
+            }
+            Message::SetRenderRangeOut => {
+                self.render_range_out = Some(self.playback_position);
+                Task::none()
+            }
+            Message::RenderRangeNameChanged(name) => {
+                self.render_range_name = name;
+                Task::none()
+            }
+            Message::AddRenderRange => {
+                let (Some(start), Some(end)) = (self.render_range_in, self.render_range_out) else {
+                    self.status_message = self.i18n.tr("status-render-range-needs-flags");
+                    return Task::none();
+                };
+                let Ok(range) = TimeRange::new(start, end) else {
+                    self.status_message = self.i18n.tr("status-render-range-invalid");
+                    return Task::none();
+                };
+                let name = match self.render_range_name.trim() {
+                    "" => format!("Range {}", self.project.render_ranges.len() + 1),
+                    name => name.to_string(),
+                };
+                self.project.add_render_range(name.clone(), range);
+                self.render_range_name.clear();
+                self.render_range_in = None;
+                self.render_range_out = None;
+                self.status_message = self.i18n.tr_args("status-render-range-added", &[("name", &name)]);
+                Task::none()
+            }
+            Message::DeleteRenderRange(id) => {
+                if let Ok(render_range) = self.project.remove_render_range(id) {
+                    self.status_message = self.i18n.tr_args("status-render-range-deleted", &[("name", &render_range.name)]);
+                }
+                Task::none()
+            }
+            Message::RenderFilePatternChanged(pattern) => {
+                self.preferences.render_file_pattern = pattern;
+                Task::none()
+            }
+            Message::ExportRenderRanges => {
+                if self.project.render_ranges.is_empty() {
+                    self.status_message = self.i18n.tr("status-no-render-ranges");
+                    return Task::none();
+                }
+                self.save_preferences();
+                Task::perform(
+                    async {
+                        let handle = rfd::AsyncFileDialog::new()
+                            .set_title("Export Render Ranges")
+                            .pick_folder()
+                            .await;
+                        handle.map(|f| f.path().to_path_buf())
+                    },
+                    Message::RenderRangesFolderResult,
+                )
+            }
+            Message::ExportRenderRangeLoop(id) => Task::perform(
+                async {
+                    let handle = rfd::AsyncFileDialog::new()
+                        .add_filter("MKV Video", &["mkv"])
+                        .set_title("Export Loop")
+                        .save_file()
+                        .await;
+                    handle.map(|f| f.path().to_path_buf())
+                },
+                move |path| Message::RenderRangeLoopFileResult { id, path },
+            ),
+            Message::RenderRangeLoopFileResult { id, path } => {
+                let Some(mut path) = path else {
+                    self.status_message = self.i18n.tr("status-render-cancelled");
+                    return Task::none();
+                };
+                let Some(render_range) = self.project.render_ranges.iter().find(|r| r.id == id) else {
+                    return Task::none();
+                };
+                if path.extension().is_none_or(|e| e != "mkv") {
+                    path.set_extension("mkv");
+                }
+                // Short ranges get a shorter dissolve rather than no loop
+                let crossfade = LOOP_CROSSFADE_SECS.min(render_range.range.duration().as_secs_f64() / 3.0);
+                let job = RenderJob {
+                    timeline: self.project.timeline.extract_range(render_range.range),
+                    source_library: self.project.source_library.clone(),
+                    output_path: path,
+                    loop_crossfade: Some(crossfade),
+                };
+                self.queue_renders(vec![job])
+            }
+            Message::RenderRangesFolderResult(folder) => {
+                let Some(folder) = folder else {
+                    self.status_message = self.i18n.tr("status-render-cancelled");
+                    return Task::none();
+                };
+                let jobs = self.render_range_jobs(&folder);
+                self.status_message = self.i18n.tr_args("status-render-ranges-queued", &[("count", &jobs.len())]);
+                self.queue_renders(jobs)
+            }
+            _ => Task::none(),
+        }
+    }
+
+    /// Size estimate of rendering the timeline with the project's render settings.
+    pub fn estimated_export_bytes(&self) -> u64 {
+        let config = zeditor_media::renderer::derive_render_config(
+            &self.project.timeline,
+            &self.project.source_library,
+            &self.project.settings,
+            PathBuf::new(),
+        );
+        stats::estimate_export_bytes(self.project.timeline.duration(), config.width, config.height, config.fps, config.crf)
+    }
+
+    /// A render of each render range into `folder`, named by the file
+    /// pattern preference.
+    pub fn render_range_jobs(&self, folder: &Path) -> Vec<RenderJob> {
+        let mut used = HashSet::new();
+        let mut jobs = Vec::new();
+        for (index, render_range) in self.project.render_ranges.iter().enumerate() {
+            let mut name = render_ranges::file_name(
+                &self.preferences.render_file_pattern,
+                &self.project.name,
+                &render_range.name,
+                index,
+            );
+            // Keep a pattern without {name} or {index} from overwriting earlier ranges
+            if !used.insert(name.clone()) {
+                name = format!("{name}-{:02}", index + 1);
+                used.insert(name.clone());
+            }
+            jobs.push(RenderJob {
+                timeline: self.project.timeline.extract_range(render_range.range),
+                source_library: self.project.source_library.clone(),
+                output_path: folder.join(format!("{name}.mkv")),
+                loop_crossfade: None,
+            });
+        }
+        jobs
+    }
+
+    /// Queue `jobs` after any queued ones, first asking whether to overwrite
+    /// or keep both when some of their outputs already exist.
+    pub(super) fn queue_renders(&mut self, jobs: Vec<RenderJob>) -> Task<Message> {
+        let existing: Vec<PathBuf> = jobs.iter().map(|job| job.output_path.clone()).filter(|path| path.exists()).collect();
+        let message = match existing.as_slice() {
+            [] => {
+                self.render_queue.extend(jobs);
+                return self.start_next_render();
+            }
+            [path] => {
+                let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+                self.i18n.tr_args("dialog-render-output-exists", &[("name", &name)])
+            }
+            paths => self.i18n.tr_args("dialog-render-outputs-exist", &[("count", &paths.len())]),
+        };
+        self.renders_awaiting_overwrite = jobs;
+        self.confirm_dialog = Some(ConfirmDialog::choice(
+            message,
+            vec!["dialog-overwrite", "dialog-keep-both"],
+            ConfirmAction::RenderOutputExists,
+        ));
+        Task::none()
+    }
+
+    /// Start the next queued render unless one is already running. A job
+    /// whose output can't be written, or wouldn't fit on its disk, is
+    /// reported and skipped.
+    pub(super) fn start_next_render(&mut self) -> Task<Message> {
+        if self.is_rendering {
+            return Task::none();
+        }
+        let Some(job) = self.render_queue.pop_front() else {
+            return Task::none();
+        };
+        self.status_message = self.i18n.tr("status-rendering");
+        let RenderJob { timeline, source_library, output_path, loop_crossfade } = job;
+        let config = zeditor_media::renderer::derive_render_config(
+            &timeline,
+            &source_library,
+            &self.project.settings,
+            output_path,
+        );
+        let segments = self.preferences.render_segments;
+        // Loop renders crossfade their audio, which separate stems wouldn't match
+        let stems = self.preferences.export_stems && loop_crossfade.is_none();
+
+        let mut needed_bytes =
+            stats::estimate_export_bytes(timeline.duration(), config.width, config.height, config.fps, config.crf);
+        if segments > 1 && loop_crossfade.is_none() {
+            // The joined picture sits in a work folder beside the output until the sound is muxed on
+            needed_bytes *= 2;
+        }
+        if let Err(e) = output_check::check_output(&config.output_path, needed_bytes) {
+            self.notify(
+                NotificationLevel::Error,
+                self.i18n.tr_args(
+                    "status-render-output-unusable",
+                    &[("path", &config.output_path.display()), ("error", &e)],
+                ),
+            );
+            return self.start_next_render();
+        }
+        // Create a progress channel for render progress updates
+        let (ptx, prx) = std::sync::mpsc::channel();
+        self.is_rendering = true;
+        self.render_start = Some(Instant::now());
+        self.render_current_frame = 0;
+        self.render_total_frames = 0;
+        self.render_elapsed = Duration::ZERO;
+        self.render_progress_rx = Some(prx);
+
+        Task::perform(
+            async move {
+                match loop_crossfade {
+                    Some(secs) => zeditor_media::renderer::render_loop(&timeline, &source_library, &config, secs, Some(ptx)),
+                    None => zeditor_media::segmented::render_segmented(
+                        &timeline,
+                        &source_library,
+                        &config,
+                        segments,
+                        Some(ptx),
+                    ),
+                }
+                .and_then(|()| {
+                    if stems {
+                        zeditor_media::stems::render_stems(&timeline, &source_library, &config.output_path)?;
+                    }
+                    Ok(())
+                })
+                .map(|()| config.output_path.clone())
+                .map_err(|e| format!("{e}"))
+            },
+            |result| match result {
+                Ok(path) => Message::RenderComplete(path),
+                Err(e) => Message::RenderError(e),
+            },
+        )
+    }
+
+    pub(super) fn view_render_ranges(&self) -> Element<'_, Message> {
+        let format_pos = |pos: Option<TimelinePosition>| match pos {
+            Some(p) => {
+                let secs = p.as_secs_f64();
+                format!("{:02}:{:05.2}", (secs / 60.0) as u64, secs % 60.0)
+            }
+            None => "--:--.--".to_string(),
+        };
+        let flags = row![
+            button(text(self.i18n.tr("ranges-flag-in")).size(12)).on_press(Message::SetRenderRangeIn),
+            text(format_pos(self.render_range_in)).size(12),
+            button(text(self.i18n.tr("ranges-flag-out")).size(12)).on_press(Message::SetRenderRangeOut),
+            text(format_pos(self.render_range_out)).size(12),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center);
+        let add = row![
+            text_input(&self.i18n.tr("ranges-name"), &self.render_range_name)
+                .on_input(Message::RenderRangeNameChanged)
+                .on_submit(Message::AddRenderRange)
+                .size(13)
+                .padding(4),
+            button(text(self.i18n.tr("ranges-add")).size(13)).on_press(Message::AddRenderRange),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center);
+        let export = row![
+            text_input(render_ranges::DEFAULT_FILE_PATTERN, &self.preferences.render_file_pattern)
+                .on_input(Message::RenderFilePatternChanged)
+                .size(13)
+                .padding(4),
+            button(text(self.i18n.tr("ranges-export-all")).size(13)).on_press(Message::ExportRenderRanges),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center);
+        let pattern_help = text(self.i18n.tr("ranges-pattern-help"))
+            .size(11)
+            .color(Color::from_rgb(0.6, 0.6, 0.65));
+
+        if self.project.render_ranges.is_empty() {
+            return column![
+                flags,
+                add,
+                text(self.i18n.tr("ranges-empty"))
+                    .size(14)
+                    .color(Color::from_rgb(0.5, 0.5, 0.5)),
+            ]
+            .spacing(8)
+            .into();
+        }
+
+        let rows: Vec<Element<'_, Message>> = self
+            .project
+            .render_ranges
+            .iter()
+            .map(|render_range| {
+                let span = format!(
+                    "{} - {}",
+                    format_pos(Some(render_range.range.start)),
+                    format_pos(Some(render_range.range.end))
+                );
+                row![
+                    column![
+                        text(render_range.name.clone()).size(13).color(Color::WHITE),
+                        text(span).size(11).color(Color::from_rgb(0.6, 0.6, 0.65)),
+                    ]
+                    .width(Length::Fill),
+                    button(text(self.i18n.tr("ranges-loop")).size(12))
+                        .on_press(Message::ExportRenderRangeLoop(render_range.id)),
+                    button(text(self.i18n.tr("ranges-delete")).size(12))
+                        .on_press(Message::DeleteRenderRange(render_range.id)),
+                ]
+                .spacing(4)
+                .align_y(iced::Alignment::Center)
+                .into()
+            })
+            .collect();
+
+        column![flags, add, export, pattern_help, scrollable(column(rows).spacing(6))]
+            .spacing(8)
+            .into()
+    }
+}
@@ -0,0 +1,427 @@
+//! Importing media: file and folder imports, duplicate checks and replacing an asset's file.
+
+use super::*;
+
+impl App {
+    /// File dialogs, probing, duplicate checks and thumbnails for new or replaced media.
+    pub(super) fn update_import(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::OpenFileDialog => {
+                self.status_message = self.i18n.tr("status-opening-file-dialog");
+                Task::perform(
+                    async {
+                        let handle = rfd::AsyncFileDialog::new()
+                            .add_filter("Video", &["mp4", "mov", "avi", "mkv", "webm"])
+                            .set_title("Import Media")
+                            .pick_files()
+                            .await;
+                        match handle {
+                            Some(files) => {
+                                files.into_iter().map(|f| f.path().to_path_buf()).collect()
+                            }
+                            None => Vec::new(),
+                        }
+                    },
+                    Message::FileDialogResult,
+                )
+            }
+            Message::FileDialogResult(paths) => {
+                if paths.is_empty() {
+                    self.status_message = self.i18n.tr("status-import-cancelled");
+                    return Task::none();
+                }
+                let tasks: Vec<Task<Message>> = paths
+                    .into_iter()
+                    .map(|path| {
+                        Task::perform(
+                            async move { probe_for_import(&path) },
+                            Message::MediaImported,
+                        )
+                    })
+                    .collect();
+                Task::batch(tasks)
+            }
+            Message::ImportMedia(path) => {
+                self.status_message = self.i18n.tr("status-importing");
+                Task::perform(
+                    async move { probe_for_import(&path) },
+                    Message::MediaImported,
+                )
+            }
+            Message::MediaImported(result) => {
+                match result {
+                    Ok(asset) => {
+                        if self.project.source_library.find_duplicate(&asset.path, asset.content_hash).is_none()
+                            && let Some(task) = self.hash_import(&asset, None)
+                        {
+                            return task;
+                        }
+                        return self.finish_import(asset);
+                    }
+                    Err(e) => {
+                        self.notify(NotificationLevel::Error, self.i18n.tr_args("status-import-failed", &[("error", &e)]));
+                    }
+                }
+                Task::none()
+            }
+            Message::ImportFolder => {
+                self.status_message = self.i18n.tr("status-opening-file-dialog");
+                Task::perform(
+                    async {
+                        let handle = rfd::AsyncFileDialog::new()
+                            .set_title("Import Folder")
+                            .pick_folder()
+                            .await;
+                        handle.map(|f| f.path().to_path_buf())
+                    },
+                    Message::ImportFolderChosen,
+                )
+            }
+            Message::ImportFolderChosen(dir) => {
+                let Some(dir) = dir else {
+                    self.status_message = self.i18n.tr("status-import-cancelled");
+                    return Task::none();
+                };
+                let bin = dir
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| dir.display().to_string());
+                self.status_message = self.i18n.tr("status-importing");
+                Task::perform(
+                    async move {
+                        zeditor_core::media::scan_media_folder(&dir).map_err(|e| format!("{e}"))
+                    },
+                    move |result| Message::FolderScanned { bin: bin.clone(), result },
+                )
+            }
+            Message::FolderScanned { bin, result } => {
+                let paths = match result {
+                    Ok(paths) if paths.is_empty() => {
+                        self.notify(NotificationLevel::Info, self.i18n.tr_args("status-folder-empty", &[("name", &bin)]));
+                        return Task::none();
+                    }
+                    Ok(paths) => paths,
+                    Err(e) => {
+                        self.notify(NotificationLevel::Error, self.i18n.tr_args("status-folder-scan-failed", &[("error", &e)]));
+                        return Task::none();
+                    }
+                };
+                self.folder_import = Some(FolderImport {
+                    bin: bin.clone(),
+                    total: paths.len(),
+                    done: 0,
+                    failed: 0,
+                    skipped: 0,
+                });
+                let tasks: Vec<Task<Message>> = paths
+                    .into_iter()
+                    .map(|path| {
+                        let bin = bin.clone();
+                        Task::perform(
+                            async move { probe_for_import(&path) },
+                            move |result| Message::FolderMediaImported { bin: bin.clone(), result },
+                        )
+                    })
+                    .collect();
+                Task::batch(tasks)
+            }
+            Message::FolderMediaImported { bin, result } => {
+                if let Ok(asset) = &result
+                    && self.folder_import.as_ref().is_some_and(|p| p.bin == bin)
+                    && self.project.source_library.find_duplicate(&asset.path, asset.content_hash).is_none()
+                    && let Some(task) = self.hash_import(asset, Some(bin.clone()))
+                {
+                    return task;
+                }
+                self.finish_folder_import(bin, result)
+            }
+            Message::ImportHashed { asset, hashes, bin } => {
+                for (id, hash) in hashes {
+                    if let Some(existing) = self.project.source_library.get_mut(id) {
+                        existing.content_hash = Some(hash);
+                    }
+                }
+                match bin {
+                    Some(bin) => self.finish_folder_import(bin, Ok(asset)),
+                    None => self.finish_import(asset),
+                }
+            }
+            Message::ThumbnailGenerated { asset_id, result } => {
+                if let Ok((data, width, height)) = result {
+                    let handle = iced::widget::image::Handle::from_rgba(width, height, data);
+                    self.thumbnails.insert(asset_id, handle);
+                }
+                if self.thumbnail_pending.remove(&asset_id) {
+                    let done = self.thumbnail_total - self.thumbnail_pending.len();
+                    self.status_message = self.i18n.tr_args(
+                        "status-thumbnails",
+                        &[("done", &done), ("total", &self.thumbnail_total)],
+                    );
+                }
+                Task::none()
+            }
+            Message::ReplaceSelectedMedia => {
+                let Some(asset_id) = self.selected_asset_id else {
+                    self.status_message = self.i18n.tr("status-replace-needs-asset");
+                    return Task::none();
+                };
+                Task::perform(
+                    async move {
+                        let handle = rfd::AsyncFileDialog::new()
+                            .set_title("Replace Media")
+                            .pick_file()
+                            .await;
+                        handle.map(|f| f.path().to_path_buf())
+                    },
+                    move |path| Message::ReplaceMediaFileChosen { asset_id, path },
+                )
+            }
+            Message::ReplaceMediaFileChosen { asset_id, path } => {
+                let Some(path) = path else {
+                    self.status_message = self.i18n.tr("status-replace-cancelled");
+                    return Task::none();
+                };
+                Task::perform(
+                    async move { probe_for_import(&path) },
+                    move |result| Message::ReplacementProbed { asset_id, result },
+                )
+            }
+            Message::ReplacementProbed { asset_id, result } => {
+                let replaced = result.and_then(|replacement| {
+                    self.project
+                        .replace_media(asset_id, replacement)
+                        .map_err(|e| e.to_string())
+                });
+                let report = match replaced {
+                    Ok(report) => report,
+                    Err(e) => {
+                        self.notify(NotificationLevel::Error, self.i18n.tr_args("status-replace-failed", &[("error", &e)]));
+                        return Task::none();
+                    }
+                };
+                let Some(asset) = self.project.source_library.get(asset_id) else {
+                    return Task::none();
+                };
+                let (name, path, is_video) = (asset.name.clone(), asset.path.clone(), asset.kind() == MediaKind::Video);
+                self.notify(NotificationLevel::Success, self.i18n.tr_args("status-media-replaced", &[("name", &name)]));
+                if report.fps_changed {
+                    self.notify(NotificationLevel::Warning, self.i18n.tr_args("notify-replace-fps-changed", &[("name", &name)]));
+                }
+                if !report.clips_past_end.is_empty() {
+                    self.notify(
+                        NotificationLevel::Warning,
+                        self.i18n.tr_args("notify-replace-clips-past-end", &[("count", &report.clips_past_end.len())]),
+                    );
+                }
+
+                // Frames and the source monitor still show the old file
+                if self.source_monitor.asset_id == Some(asset_id) {
+                    self.source_monitor.close();
+                    self.source_pending_frame = None;
+                }
+                self.thumbnails.remove(&asset_id);
+                self.preview_cache.clear();
+                self.prerender_cache.clear();
+                self.prerender_planned.clear();
+                self.send_decode_seek(false);
+                let thumbnail = self.thumbnail_task(asset_id, path.clone());
+                if !is_video {
+                    return thumbnail;
+                }
+                self.build_seek_indexes(vec![path.clone()]);
+                Task::batch([thumbnail, self.shot_detection_task(asset_id, path)])
+            }
+            _ => Task::none(),
+        }
+    }
+
+    /// Load thumbnails for all assets in the source library. Fresh ones come
+    /// from the media cache; only missing or stale ones are decoded. Progress
+    /// is reported in the status bar as each asset completes.
+    pub(super) fn regenerate_all_thumbnails(&mut self) -> Task<Message> {
+        let assets: Vec<(Uuid, PathBuf)> = self
+            .project
+            .source_library
+            .assets()
+            .iter()
+            .map(|asset| (asset.id, asset.path.clone()))
+            .collect();
+        self.thumbnail_pending = assets.iter().map(|(id, _)| *id).collect();
+        self.thumbnail_total = assets.len();
+        Task::batch(assets.into_iter().map(|(asset_id, path)| self.thumbnail_task(asset_id, path)))
+    }
+
+    /// If `asset` was already imported (same path or contents), select the
+    /// existing asset and say so instead of adding another copy.
+    pub(super) fn reveal_duplicate(&mut self, asset: &MediaAsset) -> Option<Task<Message>> {
+        let existing = self.project.source_library.find_duplicate(&asset.path, asset.content_hash)?;
+        let (id, name) = (existing.id, existing.name.clone());
+        self.notify(NotificationLevel::Info, self.i18n.tr_args("status-already-imported", &[("name", &name)]));
+        self.selected_asset_id = Some(id);
+        self.selected_asset_ids = vec![id];
+        Some(self.load_asset_details())
+    }
+
+    /// Hash `asset` and the library assets of the same file size in the
+    /// background, so a copy under another path is caught without reading
+    /// every imported file. `None` if it is hashed already or no size matches.
+    pub(super) fn hash_import(&self, asset: &MediaAsset, bin: Option<String>) -> Option<Task<Message>> {
+        let size = asset.file_size.filter(|_| asset.content_hash.is_none())?;
+        let mut same_size = self.project.source_library.with_file_size(size).peekable();
+        same_size.peek()?;
+        let unhashed: Vec<(Uuid, PathBuf)> = same_size
+            .filter(|a| a.content_hash.is_none())
+            .map(|a| (a.id, a.path.clone()))
+            .collect();
+        let mut asset = asset.clone();
+        Some(Task::perform(
+            async move {
+                asset.content_hash = media_cache::content_hash(&asset.path).ok();
+                let hashes: Vec<(Uuid, u64)> = unhashed
+                    .into_iter()
+                    .filter_map(|(id, path)| Some((id, media_cache::content_hash(&path).ok()?)))
+                    .collect();
+                (asset, hashes)
+            },
+            move |(asset, hashes)| Message::ImportHashed { asset, hashes, bin: bin.clone() },
+        ))
+    }
+
+    /// Add an imported file to the library, or select the asset it duplicates.
+    pub(super) fn finish_import(&mut self, asset: MediaAsset) -> Task<Message> {
+        if let Some(task) = self.reveal_duplicate(&asset) {
+            return task;
+        }
+        self.notify(NotificationLevel::Success, self.i18n.tr_args("status-imported", &[("name", &asset.name)]));
+        self.import_asset(asset)
+    }
+
+    /// Count one file of the running folder import, adding it to `bin`
+    /// unless it failed or is already in the library.
+    pub(super) fn finish_folder_import(&mut self, bin: String, result: Result<MediaAsset, String>) -> Task<Message> {
+        let Some(progress) = self.folder_import.as_mut().filter(|p| p.bin == bin) else {
+            return Task::none();
+        };
+        progress.done += 1;
+        let task = match result {
+            Ok(asset) if self.project.source_library.find_duplicate(&asset.path, asset.content_hash).is_some() => {
+                progress.skipped += 1;
+                Task::none()
+            }
+            Ok(mut asset) => {
+                asset.bin = Some(bin);
+                self.import_asset(asset)
+            }
+            Err(_) => {
+                progress.failed += 1;
+                Task::none()
+            }
+        };
+        if let Some(progress) = self.folder_import.take_if(|p| p.done >= p.total) {
+            let imported = progress.total - progress.failed - progress.skipped;
+            self.notify(
+                if progress.failed == 0 { NotificationLevel::Success } else { NotificationLevel::Warning },
+                self.i18n.tr_args(
+                    "status-folder-imported",
+                    &[
+                        ("count", &imported),
+                        ("skipped", &progress.skipped),
+                        ("failed", &progress.failed),
+                        ("name", &progress.bin),
+                    ],
+                ),
+            );
+        }
+        task
+    }
+
+    /// Add a probed asset to the library and spawn thumbnail generation (and
+    /// shot detection for video) in the background.
+    pub(super) fn import_asset(&mut self, asset: MediaAsset) -> Task<Message> {
+        let asset_id = asset.id;
+        let path = asset.path.clone();
+        let is_video = asset.kind() == MediaKind::Video;
+        self.project.import_asset(asset);
+        let thumbnail = self.thumbnail_task(asset_id, path.clone());
+        if !is_video {
+            return thumbnail;
+        }
+        self.build_seek_indexes(vec![path.clone()]);
+        Task::batch([thumbnail, self.shot_detection_task(asset_id, path)])
+    }
+
+    /// Build the seek index of each video in `paths`, or load it from the
+    /// media cache, on one background thread. Decoders opened before an
+    /// index is ready seek through the container instead.
+    pub(super) fn build_seek_indexes(&self, paths: Vec<PathBuf>) {
+        let cache_dir = self.media_cache.as_ref().map(MediaCache::seek_index_dir);
+        std::thread::spawn(move || {
+            for path in paths {
+                // A file that can't be indexed just keeps container seeking
+                let _ = zeditor_media::seek_index::SeekIndex::load_or_build(&path, cache_dir.as_deref());
+            }
+        });
+    }
+
+    /// Paths of the library's videos, for `build_seek_indexes` after a load.
+    pub(super) fn video_paths(&self) -> Vec<PathBuf> {
+        self.project
+            .source_library
+            .assets()
+            .iter()
+            .filter(|asset| asset.kind() == MediaKind::Video)
+            .map(|asset| asset.path.clone())
+            .collect()
+    }
+
+    pub(super) fn asset_ids(&self) -> HashSet<Uuid> {
+        self.project.source_library.assets().iter().map(|a| a.id).collect()
+    }
+
+    /// Catch the library view up after undo or redo, given the asset ids
+    /// from before it: drop selections of assets that are gone and load
+    /// thumbnails for assets that came back.
+    pub(super) fn sync_library_view(&mut self, assets_before: &HashSet<Uuid>) -> Task<Message> {
+        let library = &self.project.source_library;
+        if self.selected_asset_id.is_some_and(|id| library.get(id).is_none()) {
+            self.selected_asset_id = None;
+        }
+        self.selected_asset_ids.retain(|id| library.get(*id).is_some());
+        let missing: Vec<(Uuid, PathBuf)> = library
+            .assets()
+            .iter()
+            .filter(|asset| !assets_before.contains(&asset.id))
+            .map(|asset| (asset.id, asset.path.clone()))
+            .collect();
+        let thumbnails = Task::batch(missing.into_iter().map(|(asset_id, path)| self.thumbnail_task(asset_id, path)));
+        let monitor_gone = self.source_monitor.asset_id.is_some_and(|id| self.project.source_library.get(id).is_none());
+        if monitor_gone {
+            return Task::batch([thumbnails, self.update(Message::CloseSourceMonitor)]);
+        }
+        thumbnails
+    }
+
+    /// Background task producing the thumbnail for one asset.
+    pub(super) fn thumbnail_task(&self, asset_id: Uuid, path: PathBuf) -> Task<Message> {
+        let cache = self.media_cache.clone();
+        Task::perform(
+            async move {
+                let (result, _) = media_cache::cached_thumbnail(cache.as_ref(), &path, |path| {
+                    zeditor_media::thumbnail::generate_thumbnail_rgba_scaled(path, 160, 160)
+                        .map(|frame| (frame.data, frame.width, frame.height))
+                        .map_err(|e| format!("{e}"))
+                });
+                (asset_id, result)
+            },
+            |(asset_id, result)| Message::ThumbnailGenerated { asset_id, result },
+        )
+    }
+}
+
+/// Probe a file for import, recording a hash of its whole contents so the
+/// same file imported again from elsewhere is recognised.
+pub(super) fn probe_for_import(path: &Path) -> Result<MediaAsset, String> {
+    let mut asset = zeditor_media::probe::probe(path).map_err(|e| format!("{e}"))?;
+    asset.file_size = std::fs::metadata(path).map(|m| m.len()).ok();
+    Ok(asset)
+}
@@ -0,0 +1,229 @@
+//! The project library: removing and selecting assets, filters, sort and the asset info and shot sections.
+
+use super::*;
+
+impl App {
+    /// Selecting, removing, filtering and sorting library assets.
+    pub(super) fn update_library(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::RemoveAsset(id) => {
+                // The asset and the clips using it go in one undoable command
+                match self.project.remove_asset(id) {
+                    Ok(asset) => {
+                        self.status_message = self.i18n.tr_args("status-removed", &[("name", &asset.name)]);
+                        self.thumbnails.remove(&id);
+                        if self.selected_asset_id == Some(id) {
+                            self.selected_asset_id = None;
+                        }
+                        self.selected_asset_ids.retain(|a| *a != id);
+                        if self.source_monitor.asset_id == Some(id) {
+                            return self.update(Message::CloseSourceMonitor);
+                        }
+                    }
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-remove-failed", &[("error", &e)]);
+                    }
+                }
+                Task::none()
+            }
+            Message::ConfirmRemoveAsset(asset_id) => {
+                let clips_using = self.project.timeline.clips_using_asset(asset_id);
+                if clips_using.is_empty() {
+                    // No clips in use — remove directly
+                    return self.update(Message::RemoveAsset(asset_id));
+                }
+                // Clips in use — show confirmation dialog
+                let count = clips_using.len();
+                self.confirm_dialog = Some(ConfirmDialog::confirm(
+                    self.i18n.tr_args("dialog-asset-in-use", &[("count", &count)]),
+                    ConfirmAction::RemoveAsset { asset_id },
+                ));
+                Task::none()
+            }
+            Message::SelectSourceAsset(id) => {
+                let Some(id) = id else {
+                    self.selected_asset_id = None;
+                    self.selected_asset_ids.clear();
+                    return Task::none();
+                };
+                if self.keyboard_modifiers.command() {
+                    if let Some(idx) = self.selected_asset_ids.iter().position(|a| *a == id) {
+                        self.selected_asset_ids.remove(idx);
+                        if self.selected_asset_id == Some(id) {
+                            self.selected_asset_id = self.selected_asset_ids.last().copied();
+                        }
+                    } else {
+                        self.selected_asset_ids.push(id);
+                        self.selected_asset_id = Some(id);
+                    }
+                } else if self.keyboard_modifiers.shift() && self.selected_asset_id.is_some() {
+                    // Range over the library as currently shown (filtered and sorted).
+                    let visible: Vec<Uuid> =
+                        self.visible_library_assets().iter().map(|a| a.id).collect();
+                    let anchor = visible.iter().position(|a| Some(*a) == self.selected_asset_id);
+                    let target = visible.iter().position(|a| *a == id);
+                    if let (Some(anchor), Some(target)) = (anchor, target) {
+                        let (lo, hi) = (anchor.min(target), anchor.max(target));
+                        self.selected_asset_ids = visible[lo..=hi].to_vec();
+                    }
+                } else {
+                    self.selected_asset_id = Some(id);
+                    self.selected_asset_ids = vec![id];
+                }
+                self.load_asset_details()
+            }
+            Message::SourceCardHovered(id) => {
+                self.hovered_asset_id = id;
+                Task::none()
+            }
+            Message::RevealAssetInTimeline(asset_id) => {
+                let usages = self.asset_usages(asset_id);
+                if usages.is_empty() {
+                    self.status_message = self.i18n.tr("status-asset-unused");
+                    return Task::none();
+                }
+                // Revealing again steps on from the usage already selected
+                let index = self
+                    .selected_clip
+                    .and_then(|selected| usages.iter().position(|u| *u == selected))
+                    .map_or(0, |i| (i + 1) % usages.len());
+                let (track_index, clip_id) = usages[index];
+                self.status_message = self.i18n.tr_args(
+                    "status-asset-usage",
+                    &[("index", &(index + 1)), ("count", &usages.len())],
+                );
+                self.focus_clip(track_index, clip_id)
+            }
+            Message::LibrarySearchChanged(query) => {
+                self.library_filter.query = query;
+                Task::none()
+            }
+            Message::ToggleLibraryKindFilter(kind) => {
+                self.library_filter.toggle_kind(kind);
+                Task::none()
+            }
+            Message::SetLibraryBinFilter(bin) => {
+                self.library_filter.bin = if self.library_filter.bin.as_deref() == Some(&bin) {
+                    None
+                } else {
+                    Some(bin)
+                };
+                Task::none()
+            }
+            Message::SetLibraryUsageFilter(usage) => {
+                // Clicking the active usage chip turns it back off.
+                self.library_filter.usage = if self.library_filter.usage == usage {
+                    UsageFilter::All
+                } else {
+                    usage
+                };
+                Task::none()
+            }
+            Message::ClearLibraryFilters => {
+                self.library_filter = LibraryFilter::default();
+                Task::none()
+            }
+            Message::SetLibrarySort(sort) => {
+                if self.preferences.library_sort == sort {
+                    self.preferences.library_sort_descending = !self.preferences.library_sort_descending;
+                } else {
+                    self.preferences.library_sort = sort;
+                    self.preferences.library_sort_descending = false;
+                }
+                self.save_preferences();
+                Task::none()
+            }
+            Message::SetLibraryViewMode(mode) => {
+                self.preferences.library_view = mode;
+                self.save_preferences();
+                Task::none()
+            }
+            Message::ToggleAssetInfo => {
+                self.show_asset_info = !self.show_asset_info;
+                self.load_asset_details()
+            }
+            Message::AssetDetailsLoaded { asset_id, result } => {
+                if self.asset_details_loading == Some(asset_id) {
+                    self.asset_details_loading = None;
+                }
+                self.asset_details = Some((asset_id, result));
+                // The selection may have moved on while probing
+                self.load_asset_details()
+            }
+            Message::ShotsDetected { asset_id, result } => {
+                match result {
+                    Ok(cuts) => {
+                        if let Some(asset) = self.project.source_library.get_mut(asset_id) {
+                            asset.shot_starts = cuts.into_iter().map(Duration::from_secs_f64).collect();
+                        }
+                    }
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-shot-detection-failed", &[("error", &e)]);
+                    }
+                }
+                Task::none()
+            }
+            _ => Task::none(),
+        }
+    }
+
+    /// Library assets as shown: filtered by the search/chips, then sorted.
+    pub fn visible_library_assets(&self) -> Vec<&zeditor_core::media::MediaAsset> {
+        let mut assets = self.library_filter.apply(&self.project.source_library, &self.project.timeline);
+        library_filter::sort_assets(
+            &mut assets,
+            self.preferences.library_sort,
+            self.preferences.library_sort_descending,
+        );
+        assets
+    }
+
+    /// Thumbnails loaded so far after opening a project, out of the total.
+    /// `None` once all of them are done.
+    pub fn thumbnail_progress(&self) -> Option<(usize, usize)> {
+        (!self.thumbnail_pending.is_empty())
+            .then(|| (self.thumbnail_total - self.thumbnail_pending.len(), self.thumbnail_total))
+    }
+
+    /// Timeline uses of a library asset, in timeline order. A video clip and
+    /// its linked audio count as one use.
+    pub fn asset_usages(&self, asset_id: Uuid) -> Vec<(usize, Uuid)> {
+        self.clips_in_timeline_order(|clip| clip.asset_id == asset_id)
+    }
+
+    /// Probe the selected asset for the info section, when it is expanded
+    /// and the asset's details are neither loaded nor loading.
+    pub(super) fn load_asset_details(&mut self) -> Task<Message> {
+        if !self.show_asset_info || self.asset_details_loading.is_some() {
+            return Task::none();
+        }
+        let Some(asset) = self.selected_asset_id.and_then(|id| self.project.source_library.get(id)) else {
+            return Task::none();
+        };
+        let asset_id = asset.id;
+        if self.asset_details.as_ref().is_some_and(|(id, _)| *id == asset_id) {
+            return Task::none();
+        }
+        let path = asset.path.clone();
+        self.asset_details_loading = Some(asset_id);
+        Task::perform(
+            async move { zeditor_media::probe::probe_details(&path).map_err(|e| format!("{e}")) },
+            move |result| Message::AssetDetailsLoaded { asset_id, result },
+        )
+    }
+
+    /// Background task finding the shot cuts in an imported video.
+    pub(super) fn shot_detection_task(&self, asset_id: Uuid, path: PathBuf) -> Task<Message> {
+        Task::perform(
+            async move {
+                let result = zeditor_media::shot_detect::detect_shots(
+                    &path,
+                    zeditor_media::shot_detect::DEFAULT_CUT_THRESHOLD,
+                )
+                .map_err(|e| format!("{e}"));
+                (asset_id, result)
+            },
+            |(asset_id, result)| Message::ShotsDetected { asset_id, result },
+        )
+    }
+}
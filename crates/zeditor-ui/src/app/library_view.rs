@@ -0,0 +1,431 @@
+//! Views of the project library tab: the grid and list of assets, asset info and detected shots.
+
+use super::*;
+
+impl App {
+    pub(super) fn view_source_library_content(&self) -> Element<'_, Message> {
+        let import_btn =
+            button(text(self.i18n.tr("library-import")).size(14)).on_press(Message::OpenFileDialog);
+
+        let search = text_input(&self.i18n.tr("library-search"), &self.library_filter.query)
+            .on_input(Message::LibrarySearchChanged)
+            .size(13)
+            .padding(4);
+
+        let chip = |label: String, active: bool, msg: Message| -> Element<'_, Message> {
+            button(text(label).size(11).color(Color::WHITE))
+                .on_press(msg)
+                .padding([2, 8])
+                .style(move |_theme, _status| button::Style {
+                    background: Some(Background::Color(if active {
+                        Color::from_rgb(0.3, 0.5, 0.9)
+                    } else {
+                        Color::from_rgb(0.22, 0.22, 0.24)
+                    })),
+                    text_color: Color::WHITE,
+                    border: Border {
+                        radius: 10.0.into(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .into()
+        };
+        let filter = &self.library_filter;
+        let mut chips = row![
+            chip(self.i18n.tr("library-filter-video"), filter.kinds.contains(&MediaKind::Video), Message::ToggleLibraryKindFilter(MediaKind::Video)),
+            chip(self.i18n.tr("library-filter-audio"), filter.kinds.contains(&MediaKind::Audio), Message::ToggleLibraryKindFilter(MediaKind::Audio)),
+            chip(self.i18n.tr("library-filter-image"), filter.kinds.contains(&MediaKind::Image), Message::ToggleLibraryKindFilter(MediaKind::Image)),
+            chip(self.i18n.tr("library-filter-used"), filter.usage == UsageFilter::Used, Message::SetLibraryUsageFilter(UsageFilter::Used)),
+            chip(self.i18n.tr("library-filter-unused"), filter.usage == UsageFilter::Unused, Message::SetLibraryUsageFilter(UsageFilter::Unused)),
+        ]
+        .spacing(4);
+        if filter.is_active() {
+            chips = chips.push(
+                button(text(self.i18n.tr("library-clear-filters")).size(11))
+                    .on_press(Message::ClearLibraryFilters)
+                    .padding([2, 8]),
+            );
+        }
+        // Bins created by folder imports get a chip row of their own
+        let bins = self.project.source_library.bins();
+        let chips: Element<'_, Message> = if bins.is_empty() {
+            chips.into()
+        } else {
+            let bin_chips = row(bins.into_iter().map(|bin| {
+                chip(bin.to_string(), filter.bin.as_deref() == Some(bin), Message::SetLibraryBinFilter(bin.to_string()))
+            }))
+            .spacing(4)
+            .wrap();
+            column![chips, bin_chips].spacing(4).into()
+        };
+
+        let prefs = &self.preferences;
+        let sort_label = |key: &str, sort: LibrarySort| -> String {
+            let label = self.i18n.tr(key);
+            match (prefs.library_sort == sort, prefs.library_sort_descending) {
+                (true, false) => format!("{label} \u{25B2}"),
+                (true, true) => format!("{label} \u{25BC}"),
+                (false, _) => label,
+            }
+        };
+        let sort_btn = |key: &str, sort: LibrarySort| -> Element<'_, Message> {
+            button(text(sort_label(key, sort)).size(11))
+                .on_press(Message::SetLibrarySort(sort))
+                .padding([2, 6])
+                .into()
+        };
+        let (view_label, other_view) = match prefs.library_view {
+            LibraryViewMode::Grid => ("library-view-list", LibraryViewMode::List),
+            LibraryViewMode::List => ("library-view-grid", LibraryViewMode::Grid),
+        };
+        let sort_row = row![
+            sort_btn("library-sort-date", LibrarySort::Imported),
+            sort_btn("library-sort-name", LibrarySort::Name),
+            sort_btn("library-sort-length", LibrarySort::Duration),
+            sort_btn("library-sort-resolution", LibrarySort::Resolution),
+            Space::new().width(Length::Fill),
+            button(text(self.i18n.tr(view_label)).size(11))
+                .on_press(Message::SetLibraryViewMode(other_view))
+                .padding([2, 6]),
+        ]
+        .spacing(4);
+
+        let assets = self.visible_library_assets();
+        let mut grid_rows: Vec<Element<'_, Message>> = Vec::new();
+        if assets.is_empty() && !self.project.source_library.is_empty() {
+            grid_rows.push(text(self.i18n.tr("library-no-matches")).size(12).color(Color::from_rgb(0.6, 0.6, 0.6)).into());
+        }
+
+        if prefs.library_view == LibraryViewMode::List {
+            let header_cell = |key: &str, sort: Option<LibrarySort>, width: Length| -> Element<'_, Message> {
+                let label = text(match sort {
+                    Some(sort) => sort_label(key, sort),
+                    None => self.i18n.tr(key),
+                })
+                .size(11)
+                .color(Color::from_rgb(0.7, 0.7, 0.7));
+                match sort {
+                    Some(sort) => mouse_area(container(label).width(width))
+                        .on_press(Message::SetLibrarySort(sort))
+                        .into(),
+                    None => container(label).width(width).into(),
+                }
+            };
+            grid_rows.push(
+                row![
+                    header_cell("library-column-name", Some(LibrarySort::Name), Length::Fill),
+                    header_cell("library-column-duration", Some(LibrarySort::Duration), Length::Fixed(56.0)),
+                    header_cell("library-column-size", Some(LibrarySort::Resolution), Length::Fixed(64.0)),
+                    header_cell("library-column-codec", None, Length::Fixed(44.0)),
+                    header_cell("library-column-uses", None, Length::Fixed(28.0)),
+                ]
+                .spacing(4)
+                .into(),
+            );
+            for asset in assets {
+                grid_rows.push(self.view_source_list_row(asset));
+                for subclip in &asset.subclips {
+                    grid_rows.push(self.view_subclip_list_row(asset, subclip));
+                }
+            }
+            let asset_list = scrollable(column(grid_rows).spacing(2));
+            let mut content = column![import_btn, search, chips, sort_row, asset_list].spacing(8);
+            if let Some(shots) = self.view_shot_list() {
+                content = content.push(shots);
+            }
+            if let Some(info) = self.view_asset_info() {
+                content = content.push(info);
+            }
+            return content.into();
+        }
+
+        // Build 2-column grid; an asset's sub-clips follow its card
+        let mut cards = assets.into_iter().flat_map(|asset| {
+            std::iter::once(self.view_source_card(asset))
+                .chain(asset.subclips.iter().map(move |subclip| self.view_subclip_card(asset, subclip)))
+        });
+        while let Some(card1) = cards.next() {
+            if let Some(card2) = cards.next() {
+                grid_rows.push(row![card1, card2].spacing(6).into());
+            } else {
+                grid_rows.push(
+                    row![card1, Space::new().width(130)]
+                        .spacing(6)
+                        .into(),
+                );
+            }
+        }
+
+        let asset_grid = scrollable(column(grid_rows).spacing(6));
+
+        let mut content = column![import_btn, search, chips, sort_row, asset_grid].spacing(8);
+        if let Some(shots) = self.view_shot_list() {
+            content = content.push(shots);
+        }
+        if let Some(info) = self.view_asset_info() {
+            content = content.push(info);
+        }
+        content.into()
+    }
+
+    /// Expandable technical details of the selected asset. Nothing when no
+    /// asset is selected.
+    pub(super) fn view_asset_info(&self) -> Option<Element<'_, Message>> {
+        let asset_id = self.selected_asset_id.filter(|id| self.project.source_library.get(*id).is_some())?;
+        let arrow = if self.show_asset_info { "\u{25BE}" } else { "\u{25B8}" };
+        let toggle = button(text(self.i18n.tr_args("library-info", &[("arrow", &arrow)])).size(12).color(Color::from_rgb(0.6, 0.6, 0.65)))
+            .on_press(Message::ToggleAssetInfo)
+            .padding([2, 6])
+            .style(button::text);
+        if !self.show_asset_info {
+            return Some(toggle.into());
+        }
+
+        let dim = Color::from_rgb(0.6, 0.6, 0.65);
+        let body: Element<'_, Message> = match &self.asset_details {
+            Some((id, Ok(details))) if *id == asset_id => column(asset_info_rows(details).into_iter().map(|(label, value)| {
+                row![
+                    text(self.i18n.tr(label)).size(11).color(dim).width(Length::Fixed(80.0)),
+                    text(value).size(11).color(Color::WHITE),
+                ]
+                .spacing(4)
+                .into()
+            }))
+            .spacing(2)
+            .into(),
+            Some((id, Err(e))) if *id == asset_id => text(e.clone()).size(11).color(Color::from_rgb(0.9, 0.4, 0.4)).into(),
+            _ => text(self.i18n.tr("library-loading")).size(11).color(dim).into(),
+        };
+        Some(column![toggle, body].spacing(4).into())
+    }
+
+    /// Shots detected in the selected asset, as sub-clip markers: clicking
+    /// one opens it in the source monitor with its range marked. Nothing
+    /// for single-shot media.
+    pub(super) fn view_shot_list(&self) -> Option<Element<'_, Message>> {
+        let asset = self.selected_asset_id.and_then(|id| self.project.source_library.get(id))?;
+        if asset.shot_starts.is_empty() {
+            return None;
+        }
+        let format_secs = |d: Duration| {
+            let secs = d.as_secs_f64();
+            format!("{:02}:{:05.2}", (secs / 60.0) as u64, secs % 60.0)
+        };
+        let shots = asset.shots();
+        let mut rows: Vec<Element<'_, Message>> = vec![
+            text(self.i18n.tr_args("library-shots", &[("count", &shots.len())])).size(12).color(Color::from_rgb(0.6, 0.6, 0.65)).into(),
+        ];
+        for (i, (start, end)) in shots.into_iter().enumerate() {
+            rows.push(
+                button(
+                    text(self.i18n.tr_args(
+                        "library-shot",
+                        &[("number", &(i + 1)), ("start", &format_secs(start)), ("end", &format_secs(end))],
+                    ))
+                        .size(11)
+                        .color(Color::WHITE),
+                )
+                .on_press(Message::OpenShotInSourceMonitor { asset_id: asset.id, shot: i })
+                .width(Length::Fill)
+                .padding([2, 6])
+                .into(),
+            );
+        }
+        Some(scrollable(column(rows).spacing(2)).height(Length::Shrink).into())
+    }
+
+    /// One compact row of the library list view. Mouse handling matches the cards.
+    pub(super) fn view_source_list_row<'a>(&'a self, asset: &'a zeditor_core::media::MediaAsset) -> Element<'a, Message> {
+        let asset_id = asset.id;
+        let is_selected = self.selected_asset_id == Some(asset_id)
+            || self.selected_asset_ids.contains(&asset_id);
+        let is_hovered = self.hovered_asset_id == Some(asset_id);
+        let secs = asset.duration.as_secs();
+        let codec = if asset.codec.is_empty() { "-" } else { asset.codec.as_str() };
+        let cell = |content: String, width: Length| {
+            container(text(content).size(11).color(Color::WHITE)).width(width)
+        };
+
+        let line = container(
+            row![
+                cell(asset.name.clone(), Length::Fill),
+                cell(format!("{}:{:02}", secs / 60, secs % 60), Length::Fixed(56.0)),
+                cell(
+                    format!("{}x{}", asset.display_width(), asset.display_height()),
+                    Length::Fixed(64.0),
+                ),
+                cell(codec.to_string(), Length::Fixed(44.0)),
+                cell(self.asset_usages(asset_id).len().to_string(), Length::Fixed(28.0)),
+            ]
+            .spacing(4),
+        )
+        .padding([3, 4])
+        .style(move |_theme| container::Style {
+            background: Some(Background::Color(if is_selected {
+                Color::from_rgb(0.35, 0.2, 0.2)
+            } else if is_hovered {
+                Color::from_rgb(0.22, 0.26, 0.34)
+            } else {
+                Color::from_rgb(0.18, 0.18, 0.20)
+            })),
+            ..Default::default()
+        });
+
+        mouse_area(line)
+            .on_enter(Message::SourceCardHovered(Some(asset_id)))
+            .on_exit(Message::SourceCardHovered(None))
+            .on_press(Message::StartDragFromSource(asset_id))
+            .on_release(Message::SelectSourceAsset(Some(asset_id)))
+            .on_double_click(Message::OpenInSourceMonitor(asset_id))
+            .into()
+    }
+
+    pub(super) fn view_source_card<'a>(&'a self, asset: &'a zeditor_core::media::MediaAsset) -> Element<'a, Message> {
+        let is_hovered = self.hovered_asset_id == Some(asset.id);
+        let is_selected = self.selected_asset_id == Some(asset.id)
+            || self.selected_asset_ids.contains(&asset.id);
+        let asset_id = asset.id;
+
+        // Thumbnail or placeholder
+        let thumb_content: Element<'_, Message> = if let Some(handle) = self.thumbnails.get(&asset_id) {
+            image(handle.clone())
+                .width(120)
+                .height(68)
+                .content_fit(iced::ContentFit::Contain)
+                .into()
+        } else {
+            container(center(text("...").size(14).color(Color::from_rgb(0.5, 0.5, 0.5))))
+                .width(120)
+                .height(68)
+                .style(|_theme| container::Style {
+                    background: Some(Background::Color(Color::from_rgb(0.2, 0.2, 0.22))),
+                    ..Default::default()
+                })
+                .into()
+        };
+
+        let border_color = if is_selected {
+            Color::from_rgb(1.0, 0.2, 0.2)
+        } else if is_hovered {
+            Color::from_rgb(0.3, 0.5, 0.9)
+        } else {
+            Color::TRANSPARENT
+        };
+
+        // Usage count in the top-right corner; clicking it reveals the clips
+        let usages = self.asset_usages(asset_id).len();
+        let thumb_content: Element<'_, Message> = if usages > 0 {
+            let badge = button(text(usages.to_string()).size(10).color(Color::WHITE))
+                .on_press(Message::RevealAssetInTimeline(asset_id))
+                .padding([1, 5])
+                .style(|_theme, status| button::Style {
+                    background: Some(Background::Color(match status {
+                        button::Status::Hovered => Color::from_rgb(0.3, 0.55, 0.95),
+                        _ => Color::from_rgba(0.2, 0.4, 0.8, 0.9),
+                    })),
+                    text_color: Color::WHITE,
+                    border: Border {
+                        radius: 8.0.into(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                });
+            stack![
+                thumb_content,
+                container(badge).align_right(120).padding(2),
+            ]
+            .into()
+        } else {
+            thumb_content
+        };
+
+        let name_label = text(&asset.name)
+            .size(11)
+            .color(Color::WHITE)
+            .width(120)
+            .center();
+
+        let card = container(
+            column![thumb_content, name_label].spacing(2).align_x(iced::Alignment::Center),
+        )
+        .padding(4)
+        .width(130)
+        .style(move |_theme| container::Style {
+            background: Some(Background::Color(Color::from_rgb(0.18, 0.18, 0.20))),
+            border: Border {
+                color: border_color,
+                width: 2.0,
+                radius: 6.0.into(),
+            },
+            ..Default::default()
+        });
+
+        mouse_area(card)
+            .on_enter(Message::SourceCardHovered(Some(asset_id)))
+            .on_exit(Message::SourceCardHovered(None))
+            .on_press(Message::StartDragFromSource(asset_id))
+            .on_release(Message::SelectSourceAsset(Some(asset_id)))
+            .on_double_click(Message::OpenInSourceMonitor(asset_id))
+            .into()
+    }
+}
+
+/// Locale key/value rows of the asset info section; fields the probe
+/// couldn't find are left out.
+pub fn asset_info_rows(details: &MediaDetails) -> Vec<(&'static str, String)> {
+    let mut rows = Vec::new();
+    if !details.container.is_empty() {
+        rows.push(("info-container", details.container.clone()));
+    }
+    if let Some(codec) = &details.video_codec {
+        rows.push(("info-video", codec.clone()));
+        rows.push(("info-resolution", format!("{}x{}", details.width, details.height)));
+        if let Some(fps) = details.fps {
+            rows.push(("info-frame-rate", format!("{:.3} fps", fps).replace(".000", "")));
+        }
+        match (&details.pixel_format, details.bit_depth) {
+            (Some(format), Some(depth)) => rows.push(("info-bit-depth", format!("{depth}-bit ({format})"))),
+            (None, Some(depth)) => rows.push(("info-bit-depth", format!("{depth}-bit"))),
+            (Some(format), None) => rows.push(("info-pixel-format", format.clone())),
+            (None, None) => {}
+        }
+    }
+    if let Some(codec) = &details.audio_codec {
+        rows.push(("info-audio", codec.clone()));
+        let channels = match details.audio_channels {
+            Some(1) => "mono".to_string(),
+            Some(2) => "stereo".to_string(),
+            Some(n) => format!("{n} channels"),
+            None => String::new(),
+        };
+        let rate = details.sample_rate.map(|r| format!("{:.1} kHz", r as f64 / 1000.0)).unwrap_or_default();
+        let layout = [channels, rate].into_iter().filter(|s| !s.is_empty()).collect::<Vec<_>>().join(", ");
+        if !layout.is_empty() {
+            rows.push(("info-channels", layout));
+        }
+    }
+    if let Some(rate) = details.bit_rate {
+        rows.push(("info-bit-rate", format!("{:.1} Mb/s", rate as f64 / 1_000_000.0)));
+    }
+    rows.push(("info-file-size", format_file_size(details.file_size)));
+    if let Some(created) = &details.created {
+        rows.push(("info-created", created.clone()));
+    }
+    rows
+}
+
+/// Human-readable size in binary units, e.g. "1.5 MB".
+pub fn format_file_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
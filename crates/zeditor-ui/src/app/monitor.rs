@@ -0,0 +1,408 @@
+//! The source monitor and the sub-clips marked in it.
+
+use super::*;
+
+impl App {
+    /// Opening, playing and marking assets in the source monitor, and saving sub-clips.
+    pub(super) fn update_source_monitor(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::OpenInSourceMonitor(asset_id) => {
+                let Some(asset) = self.project.source_library.get(asset_id) else {
+                    return Task::none();
+                };
+                let name = asset.name.clone();
+                let duration = TimelinePosition::from_secs_f64(asset.duration.as_secs_f64());
+                self.source_monitor.load(asset_id, duration);
+                self.source_pending_frame = None;
+                if let Some(rx) = &self.source_decode_rx {
+                    while rx.try_recv().is_ok() {}
+                }
+                self.send_source_decode_seek(false);
+                self.status_message = self.i18n.tr_args("status-source-monitor", &[("name", &name)]);
+                Task::none()
+            }
+            Message::OpenShotInSourceMonitor { asset_id, shot } => {
+                let Some((start, end)) = self
+                    .project
+                    .source_library
+                    .get(asset_id)
+                    .and_then(|a| a.shots().get(shot).copied())
+                else {
+                    return Task::none();
+                };
+                let start = TimelinePosition::from_secs_f64(start.as_secs_f64());
+                let task = self.update(Message::OpenInSourceMonitor(asset_id));
+                self.source_monitor.in_point = Some(start);
+                self.source_monitor.out_point = Some(TimelinePosition::from_secs_f64(end.as_secs_f64()));
+                self.source_monitor.seek(start, Instant::now());
+                self.send_source_decode_seek(false);
+                task
+            }
+            Message::OpenSubClipInSourceMonitor(subclip_id) => {
+                let Some((asset_id, range)) = self
+                    .project
+                    .source_library
+                    .find_subclip(subclip_id)
+                    .map(|(asset, subclip)| (asset.id, subclip.range))
+                else {
+                    return Task::none();
+                };
+                let task = self.update(Message::OpenInSourceMonitor(asset_id));
+                self.source_monitor.in_point = Some(range.start);
+                self.source_monitor.out_point = Some(range.end);
+                self.source_monitor.seek(range.start, Instant::now());
+                self.send_source_decode_seek(false);
+                task
+            }
+            Message::SubClipNameChanged(name) => {
+                self.subclip_name = name;
+                Task::none()
+            }
+            Message::SaveSubClip => {
+                let Some((asset_id, range)) = self
+                    .source_monitor
+                    .asset_id
+                    .zip(self.source_monitor.marked_range())
+                else {
+                    return Task::none();
+                };
+                let Some(asset) = self.project.source_library.get_mut(asset_id) else {
+                    return Task::none();
+                };
+                let name = match self.subclip_name.trim() {
+                    "" => format!("{} {}", asset.name, asset.subclips.len() + 1),
+                    name => name.to_string(),
+                };
+                match asset.add_subclip(name.clone(), range) {
+                    Ok(_) => {
+                        self.subclip_name.clear();
+                        self.status_message = self.i18n.tr_args("status-subclip-saved", &[("name", &name)]);
+                    }
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-subclip-failed", &[("error", &e)]);
+                    }
+                }
+                Task::none()
+            }
+            Message::RemoveSubClip { asset_id, subclip_id } => {
+                let removed = self
+                    .project
+                    .source_library
+                    .get_mut(asset_id)
+                    .and_then(|asset| asset.remove_subclip(subclip_id).ok());
+                if let Some(subclip) = removed {
+                    self.status_message = self.i18n.tr_args("status-removed", &[("name", &subclip.name)]);
+                }
+                Task::none()
+            }
+            Message::CloseSourceMonitor => {
+                self.source_monitor.close();
+                self.source_pending_frame = None;
+                self.send_source_decode_stop();
+                Task::none()
+            }
+            Message::SourceMonitorTogglePlay => {
+                if self.source_monitor.is_playing {
+                    self.source_monitor.pause();
+                    self.send_source_decode_stop();
+                } else if self.source_monitor.is_loaded() {
+                    // Only one player runs at a time.
+                    if self.is_playing {
+                        self.update(Message::Pause);
+                    }
+                    self.source_monitor.play(Instant::now());
+                    self.send_source_decode_seek(true);
+                }
+                Task::none()
+            }
+            Message::SourceMonitorSeek(pos) => {
+                self.source_monitor.seek(pos, Instant::now());
+                let continuous = self.source_monitor.is_playing;
+                self.send_source_decode_seek(continuous);
+                Task::none()
+            }
+            Message::SourceMonitorMarkIn => {
+                self.source_monitor.mark_in();
+                Task::none()
+            }
+            Message::SourceMonitorMarkOut => {
+                self.source_monitor.mark_out();
+                Task::none()
+            }
+            Message::SourceMonitorClearMarks => {
+                self.source_monitor.clear_marks();
+                Task::none()
+            }
+            _ => Task::none(),
+        }
+    }
+
+    pub(super) fn view_source_monitor(&self) -> Element<'_, Message> {
+        let monitor = &self.source_monitor;
+        let asset = monitor.asset_id.and_then(|id| self.project.source_library.get(id));
+        let name = asset.map(|a| a.name.as_str()).unwrap_or("");
+        let format_secs = |pos: Option<TimelinePosition>| match pos {
+            Some(p) => {
+                let secs = p.as_secs_f64();
+                format!("{:02}:{:05.2}", (secs / 60.0) as u64, secs % 60.0)
+            }
+            None => "--:--.--".to_string(),
+        };
+
+        let header = row![
+            text(self.i18n.tr_args("source-title", &[("name", &name)])).size(13),
+            Space::new().width(Length::Fill),
+            button(text(self.i18n.tr("source-close")).size(12)).on_press(Message::CloseSourceMonitor),
+        ]
+        .align_y(iced::Alignment::Center);
+
+        let viewport_height = self.preferences.layout.preview_height;
+        let aspect = asset
+            .map(|a| a.display_width().max(1) as f32 / a.display_height().max(1) as f32)
+            .unwrap_or(16.0 / 9.0);
+        let frame_content: Element<'_, Message> = if let Some(handle) = &monitor.current_frame {
+            iced::widget::image(handle.clone())
+                .content_fit(iced::ContentFit::Contain)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into()
+        } else {
+            center(text(self.i18n.tr("source-loading")).size(14)).into()
+        };
+        let inner = container(frame_content)
+            .width((viewport_height * aspect).round())
+            .height(viewport_height)
+            .style(|_theme| container::Style {
+                background: Some(Background::Color(Color::BLACK)),
+                ..Default::default()
+            });
+        let video_area = container(center(inner))
+            .width(Length::Fill)
+            .height(viewport_height)
+            .style(container::dark);
+
+        let duration = monitor.duration.as_secs_f64();
+        let scrubber = slider(0.0..=duration.max(0.001), monitor.position.as_secs_f64(), |v| {
+            Message::SourceMonitorSeek(TimelinePosition::from_secs_f64(v))
+        })
+        .step(0.001);
+
+        let play_label = if monitor.is_playing { "playback-pause" } else { "playback-play" };
+        let controls = row![
+            button(text(self.i18n.tr(play_label)).size(14)).on_press(Message::SourceMonitorTogglePlay),
+            text(format_secs(Some(monitor.position))).size(14),
+            Space::new().width(Length::Fill),
+            button(text(self.i18n.tr("source-mark-in")).size(12)).on_press(Message::SourceMonitorMarkIn),
+            button(text(self.i18n.tr("source-mark-out")).size(12)).on_press(Message::SourceMonitorMarkOut),
+            button(text(self.i18n.tr("source-clear-marks")).size(12)).on_press(Message::SourceMonitorClearMarks),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center);
+
+        let marks = text(self.i18n.tr_args(
+            "source-marks",
+            &[("in", &format_secs(monitor.in_point)), ("out", &format_secs(monitor.out_point))],
+        ))
+        .size(12)
+        .color(Color::from_rgb(0.7, 0.7, 0.7));
+
+        let save_subclip = row![
+            text_input(&self.i18n.tr("source-subclip-name"), &self.subclip_name)
+                .on_input(Message::SubClipNameChanged)
+                .on_submit(Message::SaveSubClip)
+                .size(12)
+                .padding(3),
+            button(text(self.i18n.tr("source-save-subclip")).size(12)).on_press(Message::SaveSubClip),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center);
+
+        container(column![header, video_area, scrubber, controls, marks, save_subclip].spacing(4))
+            .padding(4)
+            .width(Length::Fill)
+            .into()
+    }
+
+    /// Library card of a sub-clip: the parent's thumbnail with the sub-clip's
+    /// name and range. Drags onto the timeline trimmed to the range.
+    pub(super) fn view_subclip_card<'a>(
+        &'a self,
+        asset: &'a zeditor_core::media::MediaAsset,
+        subclip: &'a SubClip,
+    ) -> Element<'a, Message> {
+        let subclip_id = subclip.id;
+        let thumb_content: Element<'_, Message> = if let Some(handle) = self.thumbnails.get(&asset.id) {
+            image(handle.clone())
+                .width(120)
+                .height(68)
+                .content_fit(iced::ContentFit::Contain)
+                .into()
+        } else {
+            container(center(text("...").size(14).color(Color::from_rgb(0.5, 0.5, 0.5))))
+                .width(120)
+                .height(68)
+                .style(|_theme| container::Style {
+                    background: Some(Background::Color(Color::from_rgb(0.2, 0.2, 0.22))),
+                    ..Default::default()
+                })
+                .into()
+        };
+        let remove = button(text("x").size(10).color(Color::WHITE))
+            .on_press(Message::RemoveSubClip { asset_id: asset.id, subclip_id })
+            .padding([0, 4])
+            .style(|_theme, _status| button::Style {
+                background: Some(Background::Color(Color::from_rgba(0.0, 0.0, 0.0, 0.6))),
+                text_color: Color::WHITE,
+                border: Border {
+                    radius: 8.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+        let thumb_content: Element<'_, Message> = stack![
+            thumb_content,
+            container(remove).align_right(120).padding(2),
+        ]
+        .into();
+
+        let name_label = text(&subclip.name)
+            .size(11)
+            .color(Color::WHITE)
+            .width(120)
+            .center();
+        let range_label = text(format_subclip_range(subclip))
+            .size(10)
+            .color(Color::from_rgb(0.65, 0.65, 0.7))
+            .width(120)
+            .center();
+
+        let card = container(
+            column![thumb_content, name_label, range_label].spacing(2).align_x(iced::Alignment::Center),
+        )
+        .padding(4)
+        .width(130)
+        .style(|_theme| container::Style {
+            background: Some(Background::Color(Color::from_rgb(0.16, 0.18, 0.22))),
+            border: Border {
+                color: Color::from_rgb(0.3, 0.35, 0.45),
+                width: 1.0,
+                radius: 6.0.into(),
+            },
+            ..Default::default()
+        });
+
+        mouse_area(card)
+            .on_press(Message::StartDragFromSubClip(subclip_id))
+            .on_double_click(Message::OpenSubClipInSourceMonitor(subclip_id))
+            .into()
+    }
+
+    pub(super) fn view_subclip_list_row<'a>(
+        &'a self,
+        asset: &'a zeditor_core::media::MediaAsset,
+        subclip: &'a SubClip,
+    ) -> Element<'a, Message> {
+        let subclip_id = subclip.id;
+        let line = container(
+            row![
+                text(format!("  \u{21B3} {}", subclip.name)).size(11).color(Color::WHITE).width(Length::Fill),
+                text(format_subclip_range(subclip)).size(11).color(Color::from_rgb(0.65, 0.65, 0.7)),
+                button(text("x").size(10))
+                    .on_press(Message::RemoveSubClip { asset_id: asset.id, subclip_id })
+                    .padding([0, 4]),
+            ]
+            .spacing(4)
+            .align_y(iced::Alignment::Center),
+        )
+        .padding([3, 4])
+        .style(|_theme| container::Style {
+            background: Some(Background::Color(Color::from_rgb(0.16, 0.18, 0.22))),
+            ..Default::default()
+        });
+
+        mouse_area(line)
+            .on_press(Message::StartDragFromSubClip(subclip_id))
+            .on_double_click(Message::OpenSubClipInSourceMonitor(subclip_id))
+            .into()
+    }
+
+    /// Seek the source monitor's decoder to its playhead. The asset is decoded
+    /// alone at its own resolution, without effects.
+    pub(super) fn send_source_decode_seek(&mut self, continuous: bool) {
+        let Some(asset) = self.source_monitor.asset_id
+            .and_then(|id| self.project.source_library.get(id))
+        else {
+            return;
+        };
+        let clips = vec![ClipDecodeInfo {
+            path: asset.path.clone(),
+            time: self.source_monitor.position.as_secs_f64(),
+            effects: Vec::new(),
+            color: asset.color,
+            fit: CanvasFit::Fit,
+            source_size: (0, 0),
+            pan: PanPath::default(),
+        }];
+        let fps = if asset.fps > 0.0 { asset.fps } else { self.project.settings.fps };
+        let request = DecodeRequest::SeekMulti {
+            generation: next_decode_generation(&self.source_decode_generation),
+            clips,
+            continuous,
+            canvas_w: asset.display_width().max(1),
+            canvas_h: asset.display_height().max(1),
+            rate: 1.0,
+            fps,
+            quality: self.preferences.preview_quality,
+            display_lut: self.display_lut.clone(),
+        };
+        self.source_pending_frame = None;
+        if let Some(tx) = &self.source_decode_tx {
+            let _ = tx.send(request);
+        }
+    }
+
+    pub(super) fn send_source_decode_stop(&self) {
+        next_decode_generation(&self.source_decode_generation);
+        if let Some(tx) = &self.source_decode_tx {
+            let _ = tx.send(DecodeRequest::Stop);
+        }
+    }
+
+    /// Display source monitor frames. Frame PTS is already in source time.
+    pub(super) fn poll_source_frame(&mut self) {
+        if !self.source_monitor.is_loaded() {
+            return;
+        }
+        let position = self.source_monitor.position.as_secs_f64();
+        loop {
+            let frame = if self.source_pending_frame.is_some() {
+                self.source_pending_frame.take().unwrap()
+            } else if let Some(rx) = &self.source_decode_rx {
+                match rx.try_recv() {
+                    Ok(f) => f,
+                    Err(_) => break,
+                }
+            } else {
+                break;
+            };
+
+            if !self.source_monitor.is_playing || frame.pts_secs <= position + 0.02 {
+                self.source_monitor.current_frame = Some(iced::widget::image::Handle::from_rgba(
+                    frame.width, frame.height, frame.rgba,
+                ));
+            } else {
+                self.source_pending_frame = Some(frame);
+                break;
+            }
+        }
+    }
+}
+
+/// "m:ss-m:ss" source range of a sub-clip, for its library card.
+pub fn format_subclip_range(subclip: &SubClip) -> String {
+    let mmss = |pos: TimelinePosition| {
+        let secs = pos.as_secs_f64() as u64;
+        format!("{}:{:02}", secs / 60, secs % 60)
+    };
+    format!("{}-{}", mmss(subclip.range.start), mmss(subclip.range.end))
+}
@@ -0,0 +1,160 @@
+//! The preferences dialog.
+
+use super::*;
+
+impl App {
+    /// Edits to the preferences form until it is applied or dismissed.
+    pub(super) fn update_preferences(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::OpenPreferences => {
+                self.preferences_form = Some(PreferencesForm::new(&self.preferences));
+                Task::none()
+            }
+            Message::PreferenceFieldChanged(field, value) => {
+                if let Some(form) = &mut self.preferences_form {
+                    *form.field_mut(field) = value;
+                }
+                Task::none()
+            }
+            Message::SetPreviewQuality(quality) => {
+                if let Some(form) = &mut self.preferences_form {
+                    form.preview_quality = quality;
+                }
+                Task::none()
+            }
+            Message::PreferencesCanvasPreset(preset) => {
+                if let Some(form) = &mut self.preferences_form {
+                    form.set_canvas_preset(preset);
+                }
+                Task::none()
+            }
+            Message::ApplyPreferences => {
+                let Some(form) = &self.preferences_form else {
+                    return Task::none();
+                };
+                match form.apply(&self.preferences) {
+                    Ok(preferences) => {
+                        let cache_changed = preferences.cache_dir != self.preferences.cache_dir;
+                        let lut_changed = preferences.display_lut != self.preferences.display_lut;
+                        self.preferences = preferences;
+                        self.preferences_form = None;
+                        if cache_changed {
+                            self.media_cache = self.media_cache_dir().map(MediaCache::new);
+                            self.background_rendered.clear();
+                        }
+                        self.save_preferences();
+                        self.status_message = self.i18n.tr("status-preferences-saved");
+                        if lut_changed {
+                            // Cached frames were composited with the old display transform
+                            self.load_display_lut();
+                            self.preview_cache.clear();
+                            self.prerender_cache.clear();
+                            self.prerender_planned.clear();
+                            self.background_rendered.clear();
+                            self.send_decode_seek(false);
+                        }
+                    }
+                    Err(field) => {
+                        let label = self.i18n.tr(field.label_key());
+                        self.status_message = self.i18n.tr_args("status-preference-invalid", &[("field", &label)]);
+                    }
+                }
+                Task::none()
+            }
+            Message::PreferencesDismissed => {
+                self.preferences_form = None;
+                Task::none()
+            }
+            _ => Task::none(),
+        }
+    }
+
+    /// Labelled text field of the Preferences dialog.
+    pub(super) fn preference_field_row(&self, field: PreferenceField, value: &str) -> Element<'_, Message> {
+        row![
+            text(self.i18n.tr(field.label_key())).size(13).color(Color::WHITE).width(190),
+            text_input("", value)
+                .on_input(move |v| Message::PreferenceFieldChanged(field, v))
+                .on_submit(Message::ApplyPreferences)
+                .size(13)
+                .padding(4),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center)
+        .into()
+    }
+
+    /// `base_layout` under the preferences dialog, if open.
+    pub(super) fn view_preferences_dialog<'a>(&'a self, base_layout: Element<'a, Message>) -> Element<'a, Message> {
+        let Some(form) = &self.preferences_form else {
+            return base_layout;
+        };
+        let quality_button = |quality: PreviewQuality, key: &'static str| {
+            let active = form.preview_quality == quality;
+            button(text(self.i18n.tr(key)).size(13).color(Color::WHITE))
+                .on_press(Message::SetPreviewQuality(quality))
+                .padding([4, 10])
+                .style(move |_theme, _status| button::Style {
+                    background: Some(Background::Color(if active {
+                        Color::from_rgb(0.25, 0.45, 0.75)
+                    } else {
+                        Color::from_rgb(0.3, 0.3, 0.33)
+                    })),
+                    text_color: Color::WHITE,
+                    border: Border {
+                        radius: 4.0.into(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+        };
+        // Preset buttons fill in the canvas fields above them
+        let canvas_presets = row(CanvasPreset::ALL.into_iter().map(|preset| {
+            button(text(preset.label()).size(13))
+                .on_press(Message::PreferencesCanvasPreset(preset))
+                .padding([4, 10])
+                .into()
+        }))
+        .spacing(8);
+
+        let dialog_card = dialog::dialog_card(
+            column![
+                text(self.i18n.tr("prefs-title")).size(16).color(Color::WHITE),
+                self.preference_field_row(PreferenceField::CanvasWidth, &form.canvas_width),
+                self.preference_field_row(PreferenceField::CanvasHeight, &form.canvas_height),
+                row![
+                    text(self.i18n.tr("prefs-canvas-preset")).size(13).color(Color::WHITE).width(190),
+                    canvas_presets,
+                ]
+                .spacing(8)
+                .align_y(iced::Alignment::Center),
+                self.preference_field_row(PreferenceField::Fps, &form.fps),
+                self.preference_field_row(PreferenceField::AutosaveMinutes, &form.autosave_minutes),
+                self.preference_field_row(PreferenceField::SnapThresholdMs, &form.snap_threshold_ms),
+                self.preference_field_row(PreferenceField::StillDurationSecs, &form.still_duration_secs),
+                self.preference_field_row(PreferenceField::RenderSegments, &form.render_segments),
+                self.preference_field_row(PreferenceField::CacheDir, &form.cache_dir),
+                self.preference_field_row(PreferenceField::DisplayLut, &form.display_lut),
+                row![
+                    text(self.i18n.tr("prefs-preview-quality")).size(13).color(Color::WHITE).width(190),
+                    quality_button(PreviewQuality::Draft, "prefs-preview-draft"),
+                    quality_button(PreviewQuality::Full, "prefs-preview-full"),
+                ]
+                .spacing(8)
+                .align_y(iced::Alignment::Center),
+                row![
+                    dialog::dialog_button(self.i18n.tr("dialog-apply"), Message::ApplyPreferences, dialog::PRIMARY),
+                    dialog::dialog_button(
+                        self.i18n.tr("dialog-cancel"),
+                        Message::PreferencesDismissed,
+                        dialog::SECONDARY,
+                    ),
+                ]
+                .spacing(8),
+            ]
+            .spacing(10),
+            480.0,
+        );
+        dialog::dialog_overlay(base_layout, dialog_card, Some(Message::PreferencesDismissed))
+    }
+}
@@ -0,0 +1,326 @@
+//! Pre-rendering effect frames around the playhead and in the background.
+
+use super::*;
+
+impl App {
+    /// Pre-render marks and the background render toggle.
+    pub(super) fn update_prerender(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::TogglePrerenderSelectedClip => {
+                let Some(range) = self.selected_clip_range() else {
+                    return Task::none();
+                };
+                if let Some(i) = self.prerender_sections.iter().position(|r| *r == range) {
+                    self.prerender_sections.remove(i);
+                } else {
+                    self.prerender_sections.push(range);
+                }
+                self.last_prerender_plan = None;
+                Task::none()
+            }
+            Message::ToggleBackgroundRender => {
+                self.preferences.background_render = !self.preferences.background_render;
+                self.save_preferences();
+                if self.preferences.background_render {
+                    self.prune_background_render();
+                }
+                self.last_prerender_plan = None;
+                Task::none()
+            }
+            _ => Task::none(),
+        }
+    }
+
+    /// During forward playback, show the pre-rendered frame for the playhead if
+    /// there is one. Entering a pre-rendered stretch stops the decode worker;
+    /// leaving it restarts decoding at the playhead. Returns true while serving.
+    pub(super) fn serve_prerendered_frame(&mut self) -> bool {
+        let key = self.preview_key(self.playback_position);
+        let Some(handle) = self.prerender_cache.get(&key) else {
+            if self.serving_prerender.is_some() {
+                self.send_decode_seek(true);
+                self.drain_stale = true;
+            }
+            return false;
+        };
+        if self.serving_prerender.is_none() {
+            self.send_decode_stop();
+        }
+        if self.serving_prerender != Some(key) {
+            self.current_frame = Some(handle);
+            self.playback_stats.record_frame_displayed(Instant::now());
+        }
+        self.serving_prerender = Some(key);
+        true
+    }
+
+    /// Timeline range of the selected clip.
+    pub(super) fn selected_clip_range(&self) -> Option<TimeRange> {
+        let (track_index, clip_id) = self.selected_clip?;
+        let clip = self.project.timeline.tracks.get(track_index)?.get_clip(clip_id)?;
+        Some(clip.timeline_range)
+    }
+
+    /// Whether the selected clip's range is marked for pre-rendering.
+    pub(super) fn selected_clip_prerender_marked(&self) -> bool {
+        self.selected_clip_range()
+            .is_some_and(|range| self.prerender_sections.contains(&range))
+    }
+
+    pub(super) fn has_effects_at(&self, pos: TimelinePosition) -> bool {
+        let timeline = &self.project.timeline;
+        (0..timeline.tracks.len())
+            .filter(|&i| timeline.is_track_visible(i))
+            .filter_map(|i| timeline.tracks[i].clip_at(pos))
+            .any(|clip| !clip.effects.is_empty())
+    }
+
+    /// Timeline frames worth pre-rendering, most urgent first: the window ahead of
+    /// the playhead, then just behind it, then the marked sections. Only frames
+    /// with effects are included, and none while effects are bypassed.
+    pub fn prerender_plan(&self) -> Vec<TimelinePosition> {
+        if self.effects_bypassed {
+            return Vec::new();
+        }
+        let fps = if self.project.settings.fps > 0.0 { self.project.settings.fps } else { 30.0 };
+        let frame_at = |secs: f64| (secs * fps).round() as i64;
+        let pos = self.playback_position.as_secs_f64();
+        let now = frame_at(pos);
+        let last = frame_at(self.project.timeline.duration().as_secs_f64());
+
+        let ahead = now..=frame_at(pos + PRERENDER_AHEAD_SECS).min(last);
+        let behind = (frame_at((pos - PRERENDER_BEHIND_SECS).max(0.0))..now).rev();
+        let sections = self
+            .prerender_sections
+            .iter()
+            .flat_map(|range| frame_at(range.start.as_secs_f64())..frame_at(range.end.as_secs_f64()));
+
+        let mut seen = HashSet::new();
+        ahead
+            .chain(behind)
+            .chain(sections)
+            .filter(|frame| seen.insert(*frame))
+            .map(|frame| TimelinePosition::from_secs_f64(frame as f64 / fps))
+            .filter(|&p| self.has_effects_at(p))
+            .take(PRERENDER_MAX_FRAMES)
+            .collect()
+    }
+
+    /// Frames with effects anywhere on the timeline that background render
+    /// still has to put on disk, in timeline order. Empty while playing or
+    /// when background render is off or there is no media cache. Also updates
+    /// `background_render_progress` and forgets rendered frames that an edit
+    /// has made stale.
+    pub fn background_render_plan(&mut self) -> Vec<(PreviewCacheKey, TimelinePosition)> {
+        if !self.preferences.background_render || self.media_cache.is_none() || self.is_playing || self.effects_bypassed {
+            return Vec::new();
+        }
+        let fps = if self.project.settings.fps > 0.0 { self.project.settings.fps } else { 30.0 };
+        let last = (self.project.timeline.duration().as_secs_f64() * fps).round() as i64;
+        let frames: Vec<(PreviewCacheKey, TimelinePosition)> = (0..last)
+            .map(|frame| TimelinePosition::from_secs_f64(frame as f64 / fps))
+            .filter(|&pos| self.has_effects_at(pos))
+            .map(|pos| (self.preview_key(pos), pos))
+            .collect();
+        let current: HashSet<PreviewCacheKey> = frames.iter().map(|(key, _)| *key).collect();
+        self.background_rendered.retain(|key| current.contains(key));
+        self.background_render_progress = (self.background_rendered.len(), frames.len());
+        frames
+            .into_iter()
+            .filter(|(key, _)| !self.background_rendered.contains(key))
+            .take(PRERENDER_MAX_FRAMES)
+            .collect()
+    }
+
+    /// Toolbar label for the background render toggle, with progress when on.
+    pub(super) fn background_render_label(&self) -> String {
+        let (rendered, total) = self.background_render_progress;
+        let label = self.i18n.tr("preview-background-render");
+        if !self.preferences.background_render {
+            label
+        } else if total == 0 {
+            format!("{label} \u{2713}")
+        } else {
+            format!("{label} \u{2713} {}%", rendered * 100 / total)
+        }
+    }
+
+    /// Trim the background-rendered frames on disk to `BACKGROUND_RENDER_DISK_CAP`,
+    /// off the UI thread.
+    pub(super) fn prune_background_render(&self) {
+        if let Some(cache) = self.media_cache.clone() {
+            std::thread::spawn(move || {
+                // Failing to prune only leaves extra frames on disk.
+                let _ = cache.prune_preview_frames(BACKGROUND_RENDER_DISK_CAP);
+            });
+        }
+    }
+
+    /// Hand the pre-render worker the planned frames that aren't cached yet,
+    /// followed by the background render plan. Cached frames whose composition
+    /// changed (e.g. an effect parameter was edited) are dropped. Runs at most
+    /// every `PRERENDER_REPLAN_INTERVAL`.
+    pub(super) fn plan_prerender(&mut self) {
+        let now = Instant::now();
+        if self.last_prerender_plan.is_some_and(|t| now.duration_since(t) < PRERENDER_REPLAN_INTERVAL) {
+            return;
+        }
+        self.last_prerender_plan = Some(now);
+
+        let planned: Vec<(PreviewCacheKey, TimelinePosition)> = self
+            .prerender_plan()
+            .into_iter()
+            .map(|pos| (self.preview_key(pos), pos))
+            .collect();
+        let current: HashMap<i64, u64> = planned.iter().map(|(key, _)| (key.frame, key.composition)).collect();
+        self.prerender_cache
+            .invalidate_where(|k| current.get(&k.frame).is_some_and(|&composition| composition != k.composition));
+
+        let mut missing: Vec<(PreviewCacheKey, TimelinePosition, bool)> = planned
+            .into_iter()
+            .filter(|(key, _)| !self.prerender_cache.contains(key))
+            .map(|(key, pos)| (key, pos, false))
+            .collect();
+        let foreground: HashSet<PreviewCacheKey> = missing.iter().map(|(key, _, _)| *key).collect();
+        missing.extend(
+            self.background_render_plan()
+                .into_iter()
+                .filter(|(key, _)| !foreground.contains(key) && !self.prerender_cache.contains(key))
+                .map(|(key, pos)| (key, pos, true)),
+        );
+        if missing.iter().all(|(key, _, _)| self.prerender_planned.contains(key)) {
+            return;
+        }
+        self.prerender_planned = missing.iter().map(|(key, _, _)| *key).collect();
+        let frames = missing
+            .into_iter()
+            .map(|(key, pos, background)| PrerenderFrame {
+                key,
+                clips: self.video_decode_clips_at(pos).into_iter().map(|(_, info, _)| info).collect(),
+                background,
+            })
+            .collect();
+        let frame_store = self
+            .media_cache
+            .clone()
+            .filter(|_| self.preferences.background_render)
+            .map(|cache| (cache, media_cache::preview_variant(self.preferences.display_lut.as_deref())));
+        if let Some(tx) = &self.prerender_tx {
+            let _ = tx.send(PrerenderRequest {
+                frames,
+                canvas_w: self.project.settings.canvas_width,
+                canvas_h: self.project.settings.canvas_height,
+                fps: self.project.settings.fps,
+                display_lut: self.display_lut.clone(),
+                frame_store,
+            });
+        }
+    }
+
+    /// Move finished pre-rendered frames into the pre-render cache, and note
+    /// background frames that are now on disk.
+    pub(super) fn poll_prerendered_frames(&mut self) {
+        let Some(rx) = &self.prerender_rx else {
+            return;
+        };
+        while let Ok((key, frame)) = rx.try_recv() {
+            if self.preferences.background_render {
+                self.background_rendered.insert(key);
+            }
+            if let Some(frame) = frame {
+                let handle = iced::widget::image::Handle::from_rgba(frame.width, frame.height, frame.rgba);
+                self.prerender_cache.insert(key, handle);
+            }
+        }
+    }
+}
+
+/// Background pre-render thread: composites the requested frames one at a time
+/// with its own decoders and sends them back for the pre-render cache. A newer
+/// request replaces whatever is left of the current one.
+pub(super) fn prerender_worker(
+    request_rx: mpsc::Receiver<PrerenderRequest>,
+    frame_tx: mpsc::SyncSender<(PreviewCacheKey, Option<DecodedFrame>)>,
+    memory: MemoryBudget,
+) {
+    let registry = EffectRegistry::with_builtins();
+    let mut decoders: HashMap<PathBuf, CachedDecoder> = HashMap::new();
+    let mut decoder_bytes = 0;
+    let mut queue: VecDeque<PrerenderFrame> = VecDeque::new();
+    let (mut canvas_w, mut canvas_h, mut fps) = (1920, 1080, 30.0);
+    let mut display_lut: Option<Arc<CubeLut>> = None;
+    let mut frame_store: Option<(MediaCache, u64)> = None;
+
+    loop {
+        let request = if queue.is_empty() {
+            match request_rx.recv() {
+                Ok(req) => Some(req),
+                Err(_) => return,
+            }
+        } else {
+            match request_rx.try_recv() {
+                Ok(req) => Some(req),
+                Err(mpsc::TryRecvError::Empty) => None,
+                Err(mpsc::TryRecvError::Disconnected) => return,
+            }
+        };
+        if let Some(request) = request {
+            queue = request.frames.into();
+            canvas_w = request.canvas_w;
+            canvas_h = request.canvas_h;
+            fps = request.fps;
+            display_lut = request.display_lut;
+            frame_store = request.frame_store;
+            continue;
+        }
+
+        let Some(frame) = queue.pop_front() else {
+            continue;
+        };
+        let stored = frame_store
+            .as_ref()
+            .and_then(|(cache, variant)| cache.load_preview_frame(frame.key, *variant));
+        if let Some((rgba, width, height)) = stored {
+            let stored = (!frame.background).then_some(DecodedFrame { rgba, width, height, pts_secs: 0.0 });
+            if frame_tx.send((frame.key, stored)).is_err() {
+                return;
+            }
+            continue;
+        }
+        let tolerance = seek_tolerance(fps);
+        if frame.clips.iter().any(|clip| prepare_decoder(&mut decoders, clip, tolerance).is_err()) {
+            continue;
+        }
+        let target_time = frame.clips.first().map_or(0.0, |c| c.time);
+        let result = decode_and_composite_multi(
+            &frame.clips,
+            &mut decoders,
+            canvas_w,
+            canvas_h,
+            target_time,
+            true,
+            fps,
+            preview_scale_quality(false, PreviewQuality::Full),
+            &registry,
+            CancelToken::never(),
+            None,
+            display_lut.as_deref(),
+        );
+        let disconnected = result.ok().flatten().is_some_and(|rendered| {
+            if let Some((cache, variant)) = &frame_store {
+                // A failed write only means the frame is rendered again later.
+                let _ = cache.store_preview_frame(frame.key, *variant, &rendered.rgba, rendered.width, rendered.height);
+            }
+            let rendered = (!frame.background).then_some(rendered);
+            frame_tx.send((frame.key, rendered)).is_err()
+        });
+        if disconnected {
+            return;
+        }
+        memory.enforce_share(
+            MemorySubsystem::Decoders,
+            &mut decoder_bytes,
+            &mut DecoderPool { decoders: &mut decoders, active: &frame.clips },
+        );
+    }
+}
@@ -0,0 +1,157 @@
+//! The Create Slideshow dialog.
+
+use super::*;
+
+impl App {
+    /// Opening, editing and confirming the slideshow dialog.
+    pub(super) fn update_slideshow(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::OpenSlideshow => {
+                // Library display order, like dragging the selection
+                let asset_ids: Vec<Uuid> = self
+                    .visible_library_assets()
+                    .iter()
+                    .map(|a| a.id)
+                    .filter(|id| self.selected_asset_ids.contains(id))
+                    .collect();
+                if asset_ids.is_empty() {
+                    self.status_message = self.i18n.tr("status-slideshow-no-assets");
+                    return Task::none();
+                }
+                self.slideshow_dialog = Some(SlideshowDialog {
+                    asset_ids,
+                    clip_secs: format!("{}", self.preferences.still_duration_secs),
+                    shuffle: false,
+                });
+                Task::none()
+            }
+            Message::SlideshowDurationChanged(secs) => {
+                if let Some(dialog) = &mut self.slideshow_dialog {
+                    dialog.clip_secs = secs;
+                }
+                Task::none()
+            }
+            Message::SlideshowShuffleToggled(shuffle) => {
+                if let Some(dialog) = &mut self.slideshow_dialog {
+                    dialog.shuffle = shuffle;
+                }
+                Task::none()
+            }
+            Message::CreateSlideshow => {
+                let Some(dialog) = &self.slideshow_dialog else {
+                    return Task::none();
+                };
+                let Some(clip_secs) = dialog
+                    .clip_secs
+                    .trim()
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|secs| crate::preferences::valid_still_duration(*secs))
+                else {
+                    self.status_message = self.i18n.tr("status-slideshow-invalid-duration");
+                    return Task::none();
+                };
+                let Some(track_index) = self
+                    .project
+                    .timeline
+                    .tracks
+                    .iter()
+                    .position(|t| t.track_type == TrackType::Video)
+                else {
+                    self.status_message = self.i18n.tr("status-no-video-track");
+                    return Task::none();
+                };
+                let mut asset_ids = dialog.asset_ids.clone();
+                if dialog.shuffle {
+                    // Random v4 ids as sort keys: a shuffle without pulling in an RNG crate
+                    asset_ids.sort_by_cached_key(|_| Uuid::new_v4());
+                }
+                // Every still gets the same length; videos shorter than that keep their own
+                let items: Vec<(Uuid, bool, TimeRange)> = asset_ids
+                    .iter()
+                    .filter_map(|id| self.project.source_library.get(*id))
+                    .map(|asset| {
+                        let secs = match asset.kind() {
+                            MediaKind::Image => clip_secs,
+                            _ => clip_secs.min(asset.duration.as_secs_f64()),
+                        };
+                        let source_range = TimeRange {
+                            start: TimelinePosition::zero(),
+                            end: TimelinePosition::from_secs_f64(secs),
+                        };
+                        (asset.id, asset.has_audio, source_range)
+                    })
+                    .collect();
+                if items.is_empty() {
+                    self.status_message = self.i18n.tr("status-asset-not-found");
+                    return Task::none();
+                }
+                let position = self.project.timeline.tracks[track_index].end_position();
+                match self.add_clip_sequence("Create slideshow", &items, track_index, position) {
+                    Ok(()) => {
+                        self.slideshow_dialog = None;
+                        self.status_message =
+                            self.i18n.tr_args("status-slideshow-created", &[("count", &items.len())]);
+                    }
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-add-clips-failed", &[("error", &e)]);
+                    }
+                }
+                Task::none()
+            }
+            Message::SlideshowDismissed => {
+                self.slideshow_dialog = None;
+                Task::none()
+            }
+            _ => Task::none(),
+        }
+    }
+
+    /// `base_layout` under the slideshow dialog, if open.
+    pub(super) fn view_slideshow_dialog<'a>(&'a self, base_layout: Element<'a, Message>) -> Element<'a, Message> {
+        let Some(dialog) = &self.slideshow_dialog else {
+            return base_layout;
+        };
+        let shuffle_label = if dialog.shuffle {
+            format!("{} \u{2713}", self.i18n.tr("dialog-slideshow-shuffle"))
+        } else {
+            self.i18n.tr("dialog-slideshow-shuffle")
+        };
+
+        let dialog_card = dialog::dialog_card(
+            column![
+                text(self.i18n.tr("dialog-create-slideshow")).size(14).color(Color::WHITE),
+                text(self.i18n.tr_args("dialog-slideshow-summary", &[("count", &dialog.asset_ids.len())]))
+                    .size(12)
+                    .color(Color::from_rgb(0.75, 0.75, 0.8)),
+                row![
+                    text(self.i18n.tr("dialog-slideshow-clip-secs")).size(13).color(Color::WHITE).width(120),
+                    text_input("", &dialog.clip_secs)
+                        .on_input(Message::SlideshowDurationChanged)
+                        .on_submit(Message::CreateSlideshow)
+                        .size(13)
+                        .padding(4),
+                ]
+                .spacing(8)
+                .align_y(iced::Alignment::Center),
+                dialog::dialog_button(
+                    shuffle_label,
+                    Message::SlideshowShuffleToggled(!dialog.shuffle),
+                    dialog::SECONDARY,
+                ),
+                row![
+                    dialog::dialog_button(self.i18n.tr("dialog-create"), Message::CreateSlideshow, dialog::PRIMARY),
+                    dialog::dialog_button(
+                        self.i18n.tr("dialog-cancel"),
+                        Message::SlideshowDismissed,
+                        dialog::SECONDARY,
+                    ),
+                ]
+                .spacing(8),
+            ]
+            .spacing(10),
+            380.0,
+        );
+        dialog::dialog_overlay(base_layout, dialog_card, Some(Message::SlideshowDismissed))
+    }
+}
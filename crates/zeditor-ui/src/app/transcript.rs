@@ -0,0 +1,171 @@
+//! Transcribing clips, the transcript tab and cutting or exporting its words.
+
+use super::*;
+use zeditor_core::transcript::{self, TimelineWord};
+
+impl App {
+    /// Transcribing the selected clip and acting on its words.
+    pub(super) fn update_transcript(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::TranscribeSelectedClip => {
+                let asset = self
+                    .selected_clip
+                    .and_then(|(track_index, clip_id)| self.project.timeline.track(track_index).ok()?.get_clip(clip_id))
+                    .and_then(|clip| self.project.source_library.get(clip.asset_id))
+                    .filter(|a| a.has_audio)
+                    .map(|a| (a.id, a.path.clone(), a.name.clone()));
+                let Some((asset_id, path, name)) = asset else {
+                    self.status_message = self.i18n.tr("status-transcribe-needs-clip");
+                    return Task::none();
+                };
+                let Some(model) = zeditor_media::transcribe::model_path() else {
+                    let error = format!("set {}", zeditor_media::transcribe::MODEL_ENV_VAR);
+                    self.status_message = self.i18n.tr_args("status-transcription-failed", &[("error", &error)]);
+                    return Task::none();
+                };
+                self.status_message = self.i18n.tr_args("status-transcription-started", &[("name", &name)]);
+                Task::perform(
+                    async move {
+                        zeditor_media::transcribe::transcribe(&path, &model).map_err(|e| format!("{e}"))
+                    },
+                    move |result| Message::TranscriptReady { asset_id, result },
+                )
+            }
+            Message::TranscriptReady { asset_id, result } => {
+                match result {
+                    Ok(words) => {
+                        let count = words.len();
+                        if let Some(asset) = self.project.source_library.get_mut(asset_id) {
+                            asset.transcript = words;
+                        }
+                        self.transcript_selection = None;
+                        self.left_panel_tab = LeftPanelTab::Transcript;
+                        self.status_message = self.i18n.tr_args("status-transcription-done", &[("count", &count)]);
+                    }
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-transcription-failed", &[("error", &e)]);
+                    }
+                }
+                Task::none()
+            }
+            Message::SelectTranscriptWord(index) => {
+                let words = self.transcript_words();
+                let Some(word) = words.get(index) else {
+                    return Task::none();
+                };
+                self.transcript_selection = match self.transcript_selection {
+                    Some((anchor, _)) if self.keyboard_modifiers.shift() => Some((anchor, index)),
+                    _ => Some((index, index)),
+                };
+                let position = word.range.start;
+                self.reveal_position(position);
+                self.update(Message::SeekTo(position))
+            }
+            Message::CutTranscriptSelection => {
+                let range = self
+                    .transcript_selection
+                    .and_then(|(anchor, end)| transcript::words_span(&self.transcript_words(), anchor, end));
+                let Some(range) = range else {
+                    return Task::none();
+                };
+                let result = self.project.command_history.execute(
+                    &mut self.project.timeline,
+                    "Cut words",
+                    |tl| tl.ripple_delete_range(range),
+                );
+                match result {
+                    Ok(()) => {
+                        self.transcript_selection = None;
+                        self.selected_clip = None;
+                        self.selected_clips.clear();
+                        self.status_message = self.i18n.tr("status-words-cut");
+                        return self.update(Message::SeekTo(range.start));
+                    }
+                    Err(e) => {
+                        self.status_message = self.i18n.tr_args("status-cut-words-failed", &[("error", &e)]);
+                    }
+                }
+                Task::none()
+            }
+            Message::ExportTranscriptSrt => Task::perform(
+                async {
+                    let handle = rfd::AsyncFileDialog::new()
+                        .add_filter("SubRip Subtitles", &["srt"])
+                        .set_title("Export Transcript")
+                        .save_file()
+                        .await;
+                    handle.map(|f| f.path().to_path_buf())
+                },
+                Message::SrtFileDialogResult,
+            ),
+            Message::SrtFileDialogResult(path) => {
+                if let Some(mut path) = path {
+                    if path.extension().is_none_or(|e| e != "srt") {
+                        path.set_extension("srt");
+                    }
+                    let srt = transcript::to_srt(&self.transcript_words());
+                    self.status_message = match std::fs::write(&path, srt) {
+                        Ok(()) => self.i18n.tr_args("status-srt-exported", &[("path", &path.display())]),
+                        Err(e) => self.i18n.tr_args("status-srt-export-failed", &[("error", &e)]),
+                    };
+                }
+                Task::none()
+            }
+            _ => Task::none(),
+        }
+    }
+
+    /// Transcribed words on the timeline, as listed in the Transcript tab.
+    pub fn transcript_words(&self) -> Vec<TimelineWord> {
+        transcript::timeline_words(&self.project.timeline, &self.project.source_library)
+    }
+
+    /// Transcript tab: the timeline's words in order. Clicking a word seeks
+    /// to it and Shift+click extends the selection, which can then be cut.
+    pub(super) fn view_transcript(&self) -> Element<'_, Message> {
+        let words = self.transcript_words();
+        if words.is_empty() {
+            return text(self.i18n.tr("transcript-empty"))
+                .size(14)
+                .color(Color::from_rgb(0.5, 0.5, 0.5))
+                .into();
+        }
+
+        let selected = self.transcript_selection.map(|(anchor, end)| anchor.min(end)..=anchor.max(end));
+        let mut chips: Vec<Element<'_, Message>> = Vec::new();
+        for (index, word) in words.iter().enumerate() {
+            let is_selected = selected.as_ref().is_some_and(|r| r.contains(&index));
+            let is_current = word.range.contains(self.playback_position);
+            chips.push(
+                button(text(word.text.clone()).size(13))
+                    .on_press(Message::SelectTranscriptWord(index))
+                    .padding([1, 3])
+                    .style(move |_theme, status| {
+                        let bg = if is_selected {
+                            Color::from_rgb(0.3, 0.4, 0.7)
+                        } else if matches!(status, button::Status::Hovered) {
+                            Color::from_rgb(0.25, 0.25, 0.28)
+                        } else {
+                            Color::TRANSPARENT
+                        };
+                        button::Style {
+                            background: Some(Background::Color(bg)),
+                            text_color: if is_current { Color::from_rgb(1.0, 0.85, 0.4) } else { Color::WHITE },
+                            border: Border { radius: 3.0.into(), ..Default::default() },
+                            ..Default::default()
+                        }
+                    })
+                    .into(),
+            );
+        }
+
+        let actions = row![
+            button(text(self.i18n.tr("transcript-cut")).size(13))
+                .on_press_maybe(self.transcript_selection.map(|_| Message::CutTranscriptSelection)),
+            button(text(self.i18n.tr("transcript-export-srt")).size(13)).on_press(Message::ExportTranscriptSrt),
+        ]
+        .spacing(8);
+
+        column![actions, scrollable(row(chips).spacing(2).wrap())].spacing(8).into()
+    }
+}
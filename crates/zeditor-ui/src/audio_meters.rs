@@ -0,0 +1,148 @@
+use std::collections::VecDeque;
+
+/// Seconds of audio one meter reading covers; peaks older than this fall off.
+const METER_WINDOW_SECS: f64 = 0.1;
+/// Level at the bottom of the meter scale.
+pub const METER_FLOOR_DB: f32 = -60.0;
+
+/// Per-track peaks of one block of mixed audio, sent by the audio decode
+/// worker alongside what it pushes to the ring.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioLevels {
+    /// Ring epoch of the request the block was decoded for.
+    pub epoch: u64,
+    /// Source PTS of the block's first sample, as pushed to the ring.
+    pub pts_secs: f64,
+    pub duration_secs: f64,
+    /// (track index, peak absolute sample value) for each clip in the mix.
+    pub peaks: Vec<(usize, f32)>,
+}
+
+impl AudioLevels {
+    fn end_secs(&self) -> f64 {
+        self.pts_secs + self.duration_secs
+    }
+}
+
+/// Level blocks decoded ahead of the output, read back at the audio clock so
+/// the track header meters move with what is heard.
+#[derive(Debug, Clone, Default)]
+pub struct AudioMeters {
+    epoch: u64,
+    /// Blocks in decode order.
+    blocks: VecDeque<AudioLevels>,
+}
+
+impl AudioMeters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a block. A block from a new epoch (the playhead was moved) drops
+    /// everything held for the old one.
+    pub fn push(&mut self, levels: AudioLevels) {
+        if levels.epoch != self.epoch {
+            self.blocks.clear();
+            self.epoch = levels.epoch;
+        }
+        self.blocks.push_back(levels);
+    }
+
+    /// Peak of `track_index` over the window ending at source time
+    /// `pts_secs`, or 0.0 if the track has no audio there.
+    pub fn level(&self, track_index: usize, pts_secs: f64) -> f32 {
+        self.blocks
+            .iter()
+            .filter(|block| block.end_secs() > pts_secs - METER_WINDOW_SECS && block.pts_secs <= pts_secs)
+            .flat_map(|block| &block.peaks)
+            .filter(|(track, _)| *track == track_index)
+            .map(|&(_, peak)| peak)
+            .fold(0.0, f32::max)
+    }
+
+    /// Forget blocks that ended before the window at `pts_secs`.
+    pub fn prune(&mut self, pts_secs: f64) {
+        while self
+            .blocks
+            .front()
+            .is_some_and(|block| block.end_secs() <= pts_secs - METER_WINDOW_SECS)
+        {
+            self.blocks.pop_front();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.blocks.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+}
+
+/// Largest absolute sample value.
+pub fn peak(samples: &[f32]) -> f32 {
+    samples.iter().fold(0.0, |peak, s| peak.max(s.abs()))
+}
+
+/// Meter fill (0.0 to 1.0) for a peak sample value, on a dB scale from
+/// `METER_FLOOR_DB` to 0 dBFS.
+pub fn meter_fraction(peak: f32) -> f32 {
+    if peak <= 0.0 {
+        return 0.0;
+    }
+    let db = 20.0 * peak.log10();
+    ((db - METER_FLOOR_DB) / -METER_FLOOR_DB).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(epoch: u64, pts_secs: f64, peaks: Vec<(usize, f32)>) -> AudioLevels {
+        AudioLevels {
+            epoch,
+            pts_secs,
+            duration_secs: 0.02,
+            peaks,
+        }
+    }
+
+    #[test]
+    fn test_level_follows_the_audio_clock() {
+        let mut meters = AudioMeters::new();
+        meters.push(block(1, 0.0, vec![(1, 0.5), (2, 0.1)]));
+        meters.push(block(1, 0.5, vec![(1, 0.9)]));
+
+        assert_eq!(meters.level(1, 0.01), 0.5);
+        assert_eq!(meters.level(2, 0.01), 0.1);
+        // Decoded ahead but not heard yet
+        assert_eq!(meters.level(1, 0.3), 0.0);
+        assert_eq!(meters.level(1, 0.51), 0.9);
+        assert_eq!(meters.level(3, 0.51), 0.0);
+
+        meters.prune(0.51);
+        assert_eq!(meters.level(1, 0.01), 0.0);
+        assert!(!meters.is_empty());
+    }
+
+    #[test]
+    fn test_new_epoch_discards_old_blocks() {
+        let mut meters = AudioMeters::new();
+        meters.push(block(1, 0.0, vec![(1, 0.5)]));
+        meters.push(block(2, 3.0, vec![(1, 0.2)]));
+        assert_eq!(meters.level(1, 0.01), 0.0);
+        assert_eq!(meters.level(1, 3.01), 0.2);
+    }
+
+    #[test]
+    fn test_meter_fraction_is_logarithmic() {
+        assert_eq!(meter_fraction(0.0), 0.0);
+        assert_eq!(meter_fraction(1.0), 1.0);
+        assert_eq!(meter_fraction(2.0), 1.0);
+        assert!((meter_fraction(0.001) - 0.0).abs() < 1e-4);
+        // -6 dBFS sits 90% of the way up a 60 dB scale
+        assert!((meter_fraction(0.5) - 0.9).abs() < 0.01);
+        assert_eq!(peak(&[0.1, -0.7, 0.3]), 0.7);
+    }
+}
@@ -1,38 +1,136 @@
-use rodio::{OutputStream, OutputStreamHandle, Sink};
+use rodio::{OutputStream, OutputStreamHandle, Sink, Source};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
-/// Wrapper around rodio for audio playback.
+use crate::audio_ring::{AudioRing, RingFormat};
+
+/// Shared counter of audio (in source time) actually pulled by the output device.
+/// This is the master clock for playback: it advances exactly as fast as the
+/// device consumes samples, so video scheduled against it can't drift from audio.
+#[derive(Debug, Clone, Default)]
+pub struct ConsumedClock(Arc<AtomicU64>);
+
+impl ConsumedClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Total audio duration consumed so far.
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_nanos(self.0.load(Ordering::Relaxed))
+    }
+
+    fn add_nanos(&self, nanos: u64) {
+        self.0.fetch_add(nanos, Ordering::Relaxed);
+    }
+}
+
+/// Samples pulled from the ring per chunk. The format can only change between
+/// chunks, so this bounds how long a new segment waits to start (~5ms at 48kHz).
+const CHUNK_FRAMES: usize = 256;
+
+/// Endless source that plays whatever the decode thread has put in the ring,
+/// and silence when it is empty. Only real samples advance the `ConsumedClock`,
+/// so the clock stalls (rather than drifts) on an underrun.
+pub struct RingSource {
+    ring: Arc<AudioRing>,
+    clock: ConsumedClock,
+    chunk: Vec<f32>,
+    pos: usize,
+    real: usize,
+    format: RingFormat,
+}
+
+impl RingSource {
+    pub fn new(ring: Arc<AudioRing>, clock: ConsumedClock) -> Self {
+        let mut source = Self {
+            ring,
+            clock,
+            chunk: Vec::new(),
+            pos: 0,
+            real: 0,
+            format: RingFormat {
+                sample_rate: 48000,
+                channels: 2,
+            },
+        };
+        source.refill();
+        source
+    }
+
+    fn refill(&mut self) {
+        let chunk = self.ring.read(&mut self.chunk, CHUNK_FRAMES, self.clock.elapsed());
+        self.format = chunk.format;
+        self.real = chunk.samples;
+        self.pos = 0;
+        let samples_per_sec = chunk.format.sample_rate as u128 * chunk.format.channels as u128;
+        if samples_per_sec > 0 {
+            self.clock
+                .add_nanos((chunk.samples as u128 * 1_000_000_000 / samples_per_sec) as u64);
+        }
+    }
+}
+
+impl Iterator for RingSource {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.pos >= self.chunk.len() {
+            self.refill();
+        }
+        let sample = self.chunk.get(self.pos).copied().unwrap_or(0.0);
+        self.pos += 1;
+        Some(sample)
+    }
+}
+
+impl Source for RingSource {
+    fn current_frame_len(&self) -> Option<usize> {
+        Some(self.chunk.len().saturating_sub(self.pos).max(1))
+    }
+
+    fn channels(&self) -> u16 {
+        self.format.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.format.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// Wrapper around rodio for audio playback. The sink plays a single
+/// `RingSource` for the player's whole lifetime; seeking and stopping flush the
+/// ring instead of touching the sink.
 pub struct AudioPlayer {
     _stream: OutputStream,
     _stream_handle: OutputStreamHandle,
     sink: Sink,
+    consumed: ConsumedClock,
 }
 
 impl AudioPlayer {
-    pub fn new() -> Option<Self> {
+    pub fn new(ring: Arc<AudioRing>) -> Option<Self> {
         let (stream, stream_handle) = OutputStream::try_default().ok()?;
         let sink = Sink::try_new(&stream_handle).ok()?;
+        let consumed = ConsumedClock::new();
+        sink.append(RingSource::new(ring, consumed.clone()));
         Some(Self {
             _stream: stream,
             _stream_handle: stream_handle,
             sink,
+            consumed,
         })
     }
 
-    /// Queue interleaved f32 PCM audio samples for playback.
-    pub fn queue_audio(&self, samples: Vec<f32>, sample_rate: u32, channels: u16) {
-        let source = rodio::buffer::SamplesBuffer::new(channels, sample_rate, samples);
-        self.sink.append(source);
-    }
-
-    pub fn stop(&self) {
-        self.sink.stop();
-    }
-
-    /// Clear all queued audio from the sink without destroying it.
-    /// Unlike `stop()`, the sink remains usable after clearing.
-    pub fn clear(&self) {
-        self.sink.clear();
+    /// Total audio (source time) the device has consumed since the player was created.
+    /// Only differences between two readings are meaningful.
+    pub fn consumed(&self) -> Duration {
+        self.consumed.elapsed()
     }
 
     pub fn pause(&self) {
@@ -43,15 +141,48 @@ impl AudioPlayer {
         self.sink.play();
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.sink.empty()
+    /// Set the playback speed multiplier (also shifts pitch). 1.0 is normal speed.
+    pub fn set_speed(&self, speed: f32) {
+        self.sink.set_speed(speed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ring_with(sample_rate: u32, channels: u16, samples: &[f32]) -> Arc<AudioRing> {
+        let ring = Arc::new(AudioRing::new(samples.len().max(1)));
+        let epoch = ring.flush();
+        ring.push(epoch, 0.0, sample_rate, channels, samples);
+        ring
+    }
+
+    #[test]
+    fn test_ring_source_tracks_consumed_time() {
+        let clock = ConsumedClock::new();
+        // 0.5s of stereo audio at 48kHz
+        let ring = ring_with(48000, 2, &vec![0.0f32; 48000]);
+        let source = RingSource::new(ring, clock.clone());
+
+        let pulled = source.take(24000).count();
+        assert_eq!(pulled, 24000);
+        // Whole chunks are counted as they are pulled from the ring.
+        let chunk = Duration::from_nanos(1_000_000_000 * CHUNK_FRAMES as u64 / 48000);
+        assert!(clock.elapsed() >= Duration::from_millis(250));
+        assert!(clock.elapsed() < Duration::from_millis(250) + chunk);
     }
 
-    /// Get the approximate buffered duration remaining in the sink.
-    pub fn buffered_duration(&self) -> Duration {
-        // Rodio Sink doesn't expose this directly, so we can't get a precise value.
-        // Return Duration::ZERO as a placeholder — the decode loop will use
-        // its own prebuffer tracking instead.
-        Duration::ZERO
+    #[test]
+    fn test_ring_source_plays_silence_without_advancing_clock() {
+        let clock = ConsumedClock::new();
+        let ring = ring_with(1000, 1, &[0.5f32; 100]);
+        let source = RingSource::new(ring, clock.clone());
+
+        let samples: Vec<f32> = source.take(1000).collect();
+        assert_eq!(samples.len(), 1000);
+        assert!(samples[..100].iter().all(|&s| s == 0.5));
+        assert!(samples[100..].iter().all(|&s| s == 0.0));
+        assert_eq!(clock.elapsed(), Duration::from_millis(100));
     }
 }
@@ -0,0 +1,305 @@
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering, fence};
+use std::time::Duration;
+
+/// Interleaved samples the ring holds; about 1.4s of 48kHz stereo.
+pub const AUDIO_RING_SAMPLES: usize = 1 << 17;
+
+const DEFAULT_SAMPLE_RATE: u32 = 48000;
+const DEFAULT_CHANNELS: u16 = 2;
+
+/// A few words written by one thread and read by others without locking.
+/// Readers get `None` while a write is in progress and simply retry later.
+struct SeqCell<const N: usize> {
+    seq: AtomicU64,
+    words: [AtomicU64; N],
+}
+
+impl<const N: usize> SeqCell<N> {
+    fn new() -> Self {
+        Self {
+            seq: AtomicU64::new(0),
+            words: std::array::from_fn(|_| AtomicU64::new(0)),
+        }
+    }
+
+    /// Only ever called from the cell's single writer.
+    fn write(&self, values: [u64; N]) {
+        let seq = self.seq.load(Ordering::Relaxed);
+        self.seq.store(seq + 1, Ordering::Relaxed);
+        fence(Ordering::Release);
+        for (word, value) in self.words.iter().zip(values) {
+            word.store(value, Ordering::Relaxed);
+        }
+        self.seq.store(seq + 2, Ordering::Release);
+    }
+
+    fn read(&self) -> Option<[u64; N]> {
+        let before = self.seq.load(Ordering::Acquire);
+        if before % 2 == 1 {
+            return None;
+        }
+        let values = std::array::from_fn(|i| self.words[i].load(Ordering::Relaxed));
+        fence(Ordering::Acquire);
+        (self.seq.load(Ordering::Relaxed) == before).then_some(values)
+    }
+}
+
+/// Sample format of the segment currently being played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RingFormat {
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+/// Where the audio clock stood when the output started playing the current
+/// segment: its first sample has source PTS `pts_secs` and was reached when
+/// the device had consumed `consumed_at`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioAnchor {
+    pub pts_secs: f64,
+    pub consumed_at: Duration,
+}
+
+/// Result of one `AudioRing::read` by the output callback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RingChunk {
+    pub format: RingFormat,
+    /// Leading samples of the chunk that came from the ring; the rest is silence.
+    pub samples: usize,
+}
+
+/// Lock-free single-producer, single-consumer sample ring between the audio
+/// decode thread and the output device callback, so audio never waits on the UI.
+///
+/// Every seek or stop calls `flush`, which starts a new epoch. The decoder tags
+/// what it pushes with the epoch of the request it is serving; pushes for an
+/// older epoch are dropped. The first push of an epoch starts a segment, and the
+/// output skips straight to it, discarding whatever was still buffered. Until
+/// the current epoch's segment arrives the output plays silence.
+pub struct AudioRing {
+    samples: Box<[AtomicU32]>,
+    /// Total samples ever written (producer-owned).
+    write: AtomicU64,
+    /// Total samples ever read (consumer-owned).
+    read: AtomicU64,
+    requested_epoch: AtomicU64,
+    /// `[epoch, start, pts bits, sample rate, channels]`, written by the producer.
+    segment: SeqCell<5>,
+    /// `[epoch, pts bits, consumed nanos]`, written by the consumer.
+    anchor: SeqCell<3>,
+    /// Format of the last chunk read (consumer-owned).
+    format: AtomicU64,
+}
+
+impl AudioRing {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: (0..capacity.max(1)).map(|_| AtomicU32::new(0)).collect(),
+            write: AtomicU64::new(0),
+            read: AtomicU64::new(0),
+            requested_epoch: AtomicU64::new(0),
+            segment: SeqCell::new(),
+            anchor: SeqCell::new(),
+            format: AtomicU64::new(pack_format(RingFormat {
+                sample_rate: DEFAULT_SAMPLE_RATE,
+                channels: DEFAULT_CHANNELS,
+            })),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Discard everything buffered and start a new epoch, returned for tagging
+    /// the decode request that will refill the ring.
+    pub fn flush(&self) -> u64 {
+        self.requested_epoch.fetch_add(1, Ordering::AcqRel) + 1
+    }
+
+    pub fn requested_epoch(&self) -> u64 {
+        self.requested_epoch.load(Ordering::Acquire)
+    }
+
+    /// Producer: append interleaved samples decoded for `epoch`. The first push
+    /// of an epoch starts its segment with the given PTS and format. Writes as
+    /// many whole frames as fit and returns the number of samples written, or
+    /// `None` once `epoch` has been superseded.
+    pub fn push(
+        &self,
+        epoch: u64,
+        pts_secs: f64,
+        sample_rate: u32,
+        channels: u16,
+        samples: &[f32],
+    ) -> Option<usize> {
+        if epoch != self.requested_epoch() {
+            return None;
+        }
+        let write = self.write.load(Ordering::Relaxed);
+        let current = self.segment.read().map(|s| s[0]);
+        if current != Some(epoch) {
+            self.segment.write([
+                epoch,
+                write,
+                pts_secs.to_bits(),
+                sample_rate as u64,
+                channels as u64,
+            ]);
+        }
+
+        let used = write - self.read.load(Ordering::Acquire);
+        let free = self.capacity().saturating_sub(used as usize);
+        let frame = channels.max(1) as usize;
+        let n = samples.len().min(free) / frame * frame;
+        for (i, sample) in samples[..n].iter().enumerate() {
+            self.slot(write + i as u64).store(sample.to_bits(), Ordering::Relaxed);
+        }
+        self.write.store(write + n as u64, Ordering::Release);
+        Some(n)
+    }
+
+    /// Consumer: fill `out` with up to `max_frames` frames of the current
+    /// segment, padding with silence. `consumed_at` is the output clock reading
+    /// before this chunk; it anchors the clock when a new segment starts.
+    pub fn read(&self, out: &mut Vec<f32>, max_frames: usize, consumed_at: Duration) -> RingChunk {
+        let requested = self.requested_epoch();
+        let segment = self.segment.read().filter(|s| s[0] == requested);
+        let Some([epoch, start, pts_bits, sample_rate, channels]) = segment else {
+            let format = unpack_format(self.format.load(Ordering::Relaxed));
+            out.clear();
+            out.resize(max_frames * format.channels as usize, 0.0);
+            return RingChunk { format, samples: 0 };
+        };
+        let format = RingFormat {
+            sample_rate: sample_rate as u32,
+            channels: channels as u16,
+        };
+        self.format.store(pack_format(format), Ordering::Relaxed);
+        if self.anchor.read().map(|a| a[0]) != Some(epoch) {
+            self.read.store(start, Ordering::Release);
+            self.anchor.write([epoch, pts_bits, consumed_at.as_nanos() as u64]);
+        }
+
+        let len = max_frames * format.channels.max(1) as usize;
+        let read = self.read.load(Ordering::Relaxed);
+        let available = (self.write.load(Ordering::Acquire) - read) as usize;
+        let n = available.min(len);
+        out.clear();
+        out.extend((0..n).map(|i| f32::from_bits(self.slot(read + i as u64).load(Ordering::Relaxed))));
+        out.resize(len, 0.0);
+        self.read.store(read + n as u64, Ordering::Release);
+        RingChunk { format, samples: n }
+    }
+
+    /// Clock anchor of the segment being played, once the output has started
+    /// the current epoch's segment.
+    pub fn anchor(&self) -> Option<AudioAnchor> {
+        let [epoch, pts_bits, consumed_nanos] = self.anchor.read()?;
+        (epoch == self.requested_epoch()).then(|| AudioAnchor {
+            pts_secs: f64::from_bits(pts_bits),
+            consumed_at: Duration::from_nanos(consumed_nanos),
+        })
+    }
+
+    /// Audio of the current epoch written but not yet played.
+    pub fn buffered(&self) -> Duration {
+        let Some([epoch, start, _, sample_rate, channels]) = self.segment.read() else {
+            return Duration::ZERO;
+        };
+        let samples_per_sec = sample_rate * channels;
+        if epoch != self.requested_epoch() || samples_per_sec == 0 {
+            return Duration::ZERO;
+        }
+        let read = self.read.load(Ordering::Acquire).max(start);
+        let pending = self.write.load(Ordering::Acquire).saturating_sub(read);
+        Duration::from_nanos((pending as u128 * 1_000_000_000 / samples_per_sec as u128) as u64)
+    }
+
+    fn slot(&self, position: u64) -> &AtomicU32 {
+        &self.samples[(position % self.samples.len() as u64) as usize]
+    }
+}
+
+fn pack_format(format: RingFormat) -> u64 {
+    ((format.sample_rate as u64) << 16) | format.channels as u64
+}
+
+fn unpack_format(packed: u64) -> RingFormat {
+    RingFormat {
+        sample_rate: (packed >> 16) as u32,
+        channels: (packed & 0xffff) as u16,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_all(ring: &AudioRing, frames: usize) -> (Vec<f32>, RingChunk) {
+        let mut out = Vec::new();
+        let chunk = ring.read(&mut out, frames, Duration::ZERO);
+        (out, chunk)
+    }
+
+    #[test]
+    fn test_push_then_read_round_trips() {
+        let ring = AudioRing::new(64);
+        let epoch = ring.flush();
+        assert_eq!(ring.push(epoch, 1.0, 1000, 2, &[0.1, 0.2, 0.3, 0.4]), Some(4));
+
+        let (out, chunk) = read_all(&ring, 4);
+        assert_eq!(chunk.samples, 4);
+        assert_eq!(chunk.format, RingFormat { sample_rate: 1000, channels: 2 });
+        assert_eq!(out, vec![0.1, 0.2, 0.3, 0.4, 0.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_push_stops_at_capacity_on_frame_boundary() {
+        let ring = AudioRing::new(5);
+        let epoch = ring.flush();
+        assert_eq!(ring.push(epoch, 0.0, 1000, 2, &[0.5; 8]), Some(4));
+        assert_eq!(ring.push(epoch, 0.0, 1000, 2, &[0.5; 8]), Some(0));
+
+        read_all(&ring, 2);
+        assert_eq!(ring.push(epoch, 0.0, 1000, 2, &[0.5; 8]), Some(4));
+    }
+
+    #[test]
+    fn test_flush_drops_stale_pushes_and_buffered_audio() {
+        let ring = AudioRing::new(64);
+        let old = ring.flush();
+        ring.push(old, 0.0, 1000, 1, &[1.0; 10]);
+
+        let new = ring.flush();
+        assert_eq!(ring.push(old, 0.0, 1000, 1, &[1.0; 10]), None);
+        let (_, chunk) = read_all(&ring, 4);
+        assert_eq!(chunk.samples, 0, "nothing plays until the new epoch arrives");
+
+        ring.push(new, 5.0, 1000, 1, &[2.0; 3]);
+        let (out, chunk) = read_all(&ring, 4);
+        assert_eq!(chunk.samples, 3);
+        assert_eq!(out, vec![2.0, 2.0, 2.0, 0.0]);
+    }
+
+    #[test]
+    fn test_anchor_set_when_segment_starts_playing() {
+        let ring = AudioRing::new(64);
+        let epoch = ring.flush();
+        ring.push(epoch, 2.5, 1000, 1, &[0.0; 10]);
+        assert_eq!(ring.anchor(), None);
+        assert_eq!(ring.buffered(), Duration::from_millis(10));
+
+        let mut out = Vec::new();
+        ring.read(&mut out, 4, Duration::from_millis(700));
+        assert_eq!(
+            ring.anchor(),
+            Some(AudioAnchor { pts_secs: 2.5, consumed_at: Duration::from_millis(700) })
+        );
+        assert_eq!(ring.buffered(), Duration::from_millis(6));
+
+        ring.flush();
+        assert_eq!(ring.anchor(), None);
+        assert_eq!(ring.buffered(), Duration::ZERO);
+    }
+}
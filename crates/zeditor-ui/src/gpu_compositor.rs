@@ -0,0 +1,447 @@
+//! GPU compositing of preview layers (cargo feature `gpu`).
+//!
+//! Each decoded layer is uploaded to a texture and drawn as a quad onto an
+//! offscreen target with premultiplied alpha-over blending; scaling is done by
+//! the texture sampler and opacity in the fragment shader. The result is read
+//! back as straight-alpha RGBA so it travels the same way as CPU-composited
+//! frames. Without the feature (or without a usable adapter),
+//! `GpuCompositor::new` returns `None` and the CPU compositor is used.
+
+use zeditor_core::scaler::Placement;
+
+/// One layer to composite, bottom-to-top.
+pub struct GpuLayer<'a> {
+    /// Straight-alpha RGBA pixels, `width * height * 4` bytes.
+    pub rgba: &'a [u8],
+    pub width: u32,
+    pub height: u32,
+    /// Destination rectangle on the output; parts outside it are clipped.
+    pub dest: Placement,
+    pub opacity: f32,
+}
+
+/// `dest` in normalized device coordinates for a `width`×`height` target:
+/// `[left, top, width, height]`, with height negative since NDC y points up.
+pub fn ndc_rect(dest: Placement, width: u32, height: u32) -> [f32; 4] {
+    let (w, h) = (width.max(1) as f32, height.max(1) as f32);
+    [
+        dest.x as f32 / w * 2.0 - 1.0,
+        1.0 - dest.y as f32 / h * 2.0,
+        dest.width as f32 / w * 2.0,
+        -(dest.height as f32) / h * 2.0,
+    ]
+}
+
+/// Convert premultiplied RGBA (as blended on the GPU) back to straight alpha.
+pub fn unpremultiply(rgba: &mut [u8]) {
+    for pixel in rgba.chunks_exact_mut(4) {
+        let a = pixel[3] as u32;
+        if a == 0 || a == 255 {
+            continue;
+        }
+        for c in &mut pixel[..3] {
+            *c = ((*c as u32 * 255 + a / 2) / a).min(255) as u8;
+        }
+    }
+}
+
+#[cfg(not(feature = "gpu"))]
+pub struct GpuCompositor(());
+
+#[cfg(not(feature = "gpu"))]
+impl GpuCompositor {
+    pub fn new() -> Option<Self> {
+        None
+    }
+
+    pub fn composite(&mut self, _layers: &[GpuLayer<'_>], _width: u32, _height: u32) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+#[cfg(feature = "gpu")]
+pub use backend::GpuCompositor;
+
+#[cfg(feature = "gpu")]
+mod backend {
+    use iced::widget::shader::wgpu;
+
+    use super::{GpuLayer, ndc_rect, unpremultiply};
+
+    const SHADER: &str = r#"
+struct Layer {
+    rect: vec4<f32>,
+    params: vec4<f32>,
+}
+
+@group(0) @binding(0) var<uniform> layer: Layer;
+@group(0) @binding(1) var layer_texture: texture_2d<f32>;
+@group(0) @binding(2) var layer_sampler: sampler;
+
+struct VertexOut {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOut {
+    let corner = vec2<f32>(f32(index & 1u), f32((index >> 1u) & 1u));
+    var out: VertexOut;
+    out.position = vec4<f32>(layer.rect.xy + corner * layer.rect.zw, 0.0, 1.0);
+    out.uv = corner;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOut) -> @location(0) vec4<f32> {
+    let color = textureSample(layer_texture, layer_sampler, in.uv);
+    let alpha = color.a * layer.params.x;
+    return vec4<f32>(color.rgb * alpha, alpha);
+}
+"#;
+
+    /// Bytes of the per-layer uniform: `rect` and `params` (opacity in `x`).
+    const UNIFORM_SIZE: u64 = 32;
+
+    /// GPU resources for one layer slot, reused while the layer size is unchanged.
+    struct LayerSlot {
+        texture: wgpu::Texture,
+        uniform: wgpu::Buffer,
+        bind_group: wgpu::BindGroup,
+        width: u32,
+        height: u32,
+    }
+
+    /// Offscreen target and its readback buffer for one output size.
+    struct Target {
+        texture: wgpu::Texture,
+        view: wgpu::TextureView,
+        readback: wgpu::Buffer,
+        padded_row: u32,
+        width: u32,
+        height: u32,
+    }
+
+    pub struct GpuCompositor {
+        device: wgpu::Device,
+        queue: wgpu::Queue,
+        pipeline: wgpu::RenderPipeline,
+        bind_group_layout: wgpu::BindGroupLayout,
+        sampler: wgpu::Sampler,
+        slots: Vec<LayerSlot>,
+        target: Option<Target>,
+    }
+
+    impl GpuCompositor {
+        /// Set up a headless device, or `None` if no adapter is available.
+        pub fn new() -> Option<Self> {
+            let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+            let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                force_fallback_adapter: false,
+                compatible_surface: None,
+            }))
+            .ok()?;
+            let (device, queue) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+                label: Some("zeditor preview compositor"),
+                ..Default::default()
+            }))
+            .ok()?;
+
+            let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("layer shader"),
+                source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+            });
+            let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("layer bind group layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                ],
+            });
+            let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("layer pipeline layout"),
+                bind_group_layouts: &[&bind_group_layout],
+                push_constant_ranges: &[],
+            });
+            let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("layer pipeline"),
+                layout: Some(&layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    compilation_options: Default::default(),
+                    buffers: &[],
+                },
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleStrip,
+                    ..Default::default()
+                },
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some("fs_main"),
+                    compilation_options: Default::default(),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: wgpu::TextureFormat::Rgba8Unorm,
+                        blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                }),
+                multiview: None,
+                cache: None,
+            });
+            let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+                label: Some("layer sampler"),
+                mag_filter: wgpu::FilterMode::Linear,
+                min_filter: wgpu::FilterMode::Linear,
+                ..Default::default()
+            });
+
+            Some(Self {
+                device,
+                queue,
+                pipeline,
+                bind_group_layout,
+                sampler,
+                slots: Vec::new(),
+                target: None,
+            })
+        }
+
+        /// Composite `layers` onto a transparent `width`×`height` canvas and
+        /// return its straight-alpha RGBA pixels. `None` if the GPU failed.
+        pub fn composite(&mut self, layers: &[GpuLayer<'_>], width: u32, height: u32) -> Option<Vec<u8>> {
+            if width == 0 || height == 0 {
+                return None;
+            }
+            self.ensure_target(width, height);
+            for (i, layer) in layers.iter().enumerate() {
+                self.upload_layer(i, layer, width, height);
+            }
+            let target = self.target.as_ref()?;
+
+            let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("composite"),
+            });
+            {
+                let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("composite pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &target.view,
+                        resolve_target: None,
+                        depth_slice: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+                pass.set_pipeline(&self.pipeline);
+                for slot in &self.slots[..layers.len()] {
+                    pass.set_bind_group(0, &slot.bind_group, &[]);
+                    pass.draw(0..4, 0..1);
+                }
+            }
+            encoder.copy_texture_to_buffer(
+                target.texture.as_image_copy(),
+                wgpu::TexelCopyBufferInfo {
+                    buffer: &target.readback,
+                    layout: wgpu::TexelCopyBufferLayout {
+                        offset: 0,
+                        bytes_per_row: Some(target.padded_row),
+                        rows_per_image: Some(height),
+                    },
+                },
+                extent(width, height),
+            );
+            self.queue.submit([encoder.finish()]);
+
+            let slice = target.readback.slice(..);
+            let (tx, rx) = std::sync::mpsc::channel();
+            slice.map_async(wgpu::MapMode::Read, move |result| {
+                let _ = tx.send(result);
+            });
+            self.device.poll(wgpu::PollType::wait_indefinitely()).ok()?;
+            rx.recv().ok()?.ok()?;
+
+            let row = width as usize * 4;
+            let mut rgba = Vec::with_capacity(row * height as usize);
+            {
+                let mapped = slice.get_mapped_range();
+                for padded in mapped.chunks_exact(target.padded_row as usize) {
+                    rgba.extend_from_slice(&padded[..row]);
+                }
+            }
+            target.readback.unmap();
+            unpremultiply(&mut rgba);
+            Some(rgba)
+        }
+
+        fn ensure_target(&mut self, width: u32, height: u32) {
+            if self.target.as_ref().is_some_and(|t| t.width == width && t.height == height) {
+                return;
+            }
+            let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("composite target"),
+                size: extent(width, height),
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+                view_formats: &[],
+            });
+            let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+            let padded_row = (width * 4).div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+                * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+            let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("composite readback"),
+                size: padded_row as u64 * height as u64,
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            self.target = Some(Target {
+                texture,
+                view,
+                readback,
+                padded_row,
+                width,
+                height,
+            });
+        }
+
+        fn upload_layer(&mut self, index: usize, layer: &GpuLayer<'_>, width: u32, height: u32) {
+            let reusable = self
+                .slots
+                .get(index)
+                .is_some_and(|s| s.width == layer.width && s.height == layer.height);
+            if !reusable {
+                let slot = self.create_slot(layer.width, layer.height);
+                if index < self.slots.len() {
+                    self.slots[index] = slot;
+                } else {
+                    self.slots.push(slot);
+                }
+            }
+            let slot = &self.slots[index];
+
+            self.queue.write_texture(
+                slot.texture.as_image_copy(),
+                layer.rgba,
+                wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(layer.width * 4),
+                    rows_per_image: Some(layer.height),
+                },
+                extent(layer.width, layer.height),
+            );
+            let rect = ndc_rect(layer.dest, width, height);
+            let params = [layer.opacity.clamp(0.0, 1.0), 0.0, 0.0, 0.0];
+            let uniform: Vec<u8> = rect.iter().chain(&params).flat_map(|v| v.to_le_bytes()).collect();
+            self.queue.write_buffer(&slot.uniform, 0, &uniform);
+        }
+
+        fn create_slot(&self, width: u32, height: u32) -> LayerSlot {
+            let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("layer texture"),
+                size: extent(width.max(1), height.max(1)),
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            });
+            let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+            let uniform = self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("layer uniform"),
+                size: UNIFORM_SIZE,
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("layer bind group"),
+                layout: &self.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: uniform.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(&view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::Sampler(&self.sampler),
+                    },
+                ],
+            });
+            LayerSlot {
+                texture,
+                uniform,
+                bind_group,
+                width,
+                height,
+            }
+        }
+    }
+
+    fn extent(width: u32, height: u32) -> wgpu::Extent3d {
+        wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ndc_rect_maps_full_canvas_to_clip_space() {
+        let full = Placement { x: 0, y: 0, width: 100, height: 50 };
+        assert_eq!(ndc_rect(full, 100, 50), [-1.0, 1.0, 2.0, -2.0]);
+
+        let right_half = Placement { x: 50, y: 25, width: 50, height: 25 };
+        assert_eq!(ndc_rect(right_half, 100, 50), [0.0, 0.0, 1.0, -1.0]);
+    }
+
+    #[test]
+    fn test_unpremultiply_restores_straight_alpha() {
+        let mut rgba = vec![64, 32, 0, 128, 10, 20, 30, 255, 0, 0, 0, 0];
+        unpremultiply(&mut rgba);
+        assert_eq!(rgba, vec![128, 64, 0, 128, 10, 20, 30, 255, 0, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// Languages with a locale file under `locales/`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    pub fn all() -> [Language; 2] {
+        [Self::English, Self::German]
+    }
+
+    /// BCP 47 code, matching the locale file name.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::German => "de",
+        }
+    }
+
+    /// Name shown in the language selector, written in the language itself.
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::German => "Deutsch",
+        }
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Self::English => include_str!("../locales/en.ftl"),
+            Self::German => include_str!("../locales/de.ftl"),
+        }
+    }
+}
+
+/// Parse a locale file. This is the single-line subset of Fluent syntax:
+/// `key = value` messages, `#` comments and `{ $name }` placeables.
+/// Multi-line values, terms and selectors are not supported.
+pub fn parse_locale(source: &str) -> HashMap<String, String> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+/// Looks up user-facing strings for the selected language, falling back to
+/// English for messages a translation does not cover yet.
+#[derive(Debug, Clone)]
+pub struct I18n {
+    language: Language,
+    messages: HashMap<String, String>,
+    fallback: HashMap<String, String>,
+}
+
+impl Default for I18n {
+    fn default() -> Self {
+        Self::new(Language::default())
+    }
+}
+
+impl I18n {
+    pub fn new(language: Language) -> Self {
+        Self {
+            language,
+            messages: parse_locale(language.source()),
+            fallback: parse_locale(Language::English.source()),
+        }
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// The message for `key`; unknown keys come back as the key itself so
+    /// missing strings are visible rather than blank.
+    pub fn tr(&self, key: &str) -> String {
+        self.tr_args(key, &[])
+    }
+
+    /// The message for `key` with each `{ $name }` placeable replaced by its argument.
+    pub fn tr_args(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        let Some(pattern) = self.messages.get(key).or_else(|| self.fallback.get(key)) else {
+            return key.to_string();
+        };
+        let mut out = pattern.clone();
+        for (name, value) in args {
+            out = out.replace(&format!("{{ ${name} }}"), &value.to_string());
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_skips_comments_and_blank_lines() {
+        let messages = parse_locale("# Menus\n\nmenu-file = File\n  menu-edit =  Edit \n");
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages["menu-edit"], "Edit");
+    }
+
+    #[test]
+    fn test_placeables_and_missing_keys() {
+        let i18n = I18n::new(Language::English);
+        assert_eq!(i18n.tr_args("status-imported", &[("name", &"clip1")]), "Imported: clip1");
+        assert_eq!(i18n.tr("no-such-message"), "no-such-message");
+    }
+
+    #[test]
+    fn test_translation_falls_back_to_english() {
+        let i18n = I18n::new(Language::German);
+        assert_eq!(i18n.tr("menu-file"), "Datei");
+        let english = parse_locale(Language::English.source());
+        for key in english.keys() {
+            assert_ne!(&i18n.tr(key), key, "{key} should resolve");
+        }
+    }
+
+    #[test]
+    fn test_translations_only_use_english_keys() {
+        let english = parse_locale(Language::English.source());
+        for language in Language::all() {
+            for key in parse_locale(language.source()).keys() {
+                assert!(english.contains_key(key), "{} has unknown key {key}", language.code());
+            }
+        }
+    }
+}
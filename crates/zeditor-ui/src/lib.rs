@@ -1,6 +1,19 @@
 pub mod app;
+pub mod audio_meters;
 pub mod audio_player;
+pub mod audio_ring;
+pub mod gpu_compositor;
+pub mod i18n;
+pub mod library_filter;
+pub mod media_cache;
+pub mod memory;
 pub mod message;
+pub mod notifications;
+pub mod playback_stats;
+pub mod preferences;
+pub mod preview_cache;
+pub mod source_monitor;
+pub mod trim_preview;
 pub mod views;
 pub mod widgets;
 
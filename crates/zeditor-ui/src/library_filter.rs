@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+use zeditor_core::media::{MediaAsset, MediaKind, SourceLibrary};
+use zeditor_core::timeline::Timeline;
+
+/// Ordering of the source library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LibrarySort {
+    /// Import order (the library keeps assets in the order they were imported).
+    #[default]
+    Imported,
+    Name,
+    Duration,
+    /// Pixel count of the displayed picture.
+    Resolution,
+}
+
+/// How the source library is laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LibraryViewMode {
+    /// Two-column thumbnail cards.
+    #[default]
+    Grid,
+    /// Compact rows with duration, resolution and codec columns.
+    List,
+}
+
+/// Sort assets in place. The sort is stable, so ties keep import order.
+pub fn sort_assets(assets: &mut [&MediaAsset], sort: LibrarySort, descending: bool) {
+    match sort {
+        LibrarySort::Imported => {}
+        LibrarySort::Name => assets.sort_by_key(|a| a.name.to_lowercase()),
+        LibrarySort::Duration => assets.sort_by_key(|a| a.duration),
+        LibrarySort::Resolution => {
+            assets.sort_by_key(|a| a.display_width() as u64 * a.display_height() as u64)
+        }
+    }
+    if descending {
+        assets.reverse();
+    }
+}
+
+/// Whether an asset is placed on the timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UsageFilter {
+    #[default]
+    All,
+    Used,
+    Unused,
+}
+
+/// Search text and filter chips applied to the source library grid.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LibraryFilter {
+    pub query: String,
+    /// Kinds to show; empty shows every kind.
+    pub kinds: Vec<MediaKind>,
+    pub usage: UsageFilter,
+    /// Only show assets imported into this bin.
+    pub bin: Option<String>,
+}
+
+impl LibraryFilter {
+    pub fn is_active(&self) -> bool {
+        !self.query.trim().is_empty() || !self.kinds.is_empty() || self.usage != UsageFilter::All
+            || self.bin.is_some()
+    }
+
+    pub fn toggle_kind(&mut self, kind: MediaKind) {
+        if let Some(idx) = self.kinds.iter().position(|k| *k == kind) {
+            self.kinds.remove(idx);
+        } else {
+            self.kinds.push(kind);
+        }
+    }
+
+    /// Every whitespace-separated search term must appear in the name (case-insensitive).
+    pub fn matches(&self, asset: &MediaAsset, used: bool) -> bool {
+        let name = asset.name.to_lowercase();
+        let query_ok = self
+            .query
+            .split_whitespace()
+            .all(|term| name.contains(&term.to_lowercase()));
+        let kind_ok = self.kinds.is_empty() || self.kinds.contains(&asset.kind());
+        let usage_ok = match self.usage {
+            UsageFilter::All => true,
+            UsageFilter::Used => used,
+            UsageFilter::Unused => !used,
+        };
+        let bin_ok = self.bin.is_none() || self.bin == asset.bin;
+        query_ok && kind_ok && usage_ok && bin_ok
+    }
+
+    /// Assets passing the filter, in library order.
+    pub fn apply<'a>(&self, library: &'a SourceLibrary, timeline: &Timeline) -> Vec<&'a MediaAsset> {
+        library
+            .assets()
+            .iter()
+            .filter(|asset| {
+                let used = !timeline.clips_using_asset(asset.id).is_empty();
+                self.matches(asset, used)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use super::*;
+
+    fn asset(name: &str, width: u32) -> MediaAsset {
+        MediaAsset::new(
+            name.into(),
+            PathBuf::from(format!("/test/{name}")),
+            Duration::from_secs(5),
+            width,
+            width,
+            30.0,
+            true,
+        )
+    }
+
+    #[test]
+    fn test_query_matches_all_terms_case_insensitive() {
+        let filter = LibraryFilter { query: "beach SUN".into(), ..Default::default() };
+        assert!(filter.matches(&asset("Sunset_Beach.mp4", 100), false));
+        assert!(!filter.matches(&asset("beach.mp4", 100), false));
+    }
+
+    #[test]
+    fn test_kind_chips() {
+        let mut filter = LibraryFilter::default();
+        filter.toggle_kind(MediaKind::Audio);
+        assert!(filter.matches(&asset("song.wav", 0), false));
+        assert!(!filter.matches(&asset("clip.mp4", 100), false));
+        filter.toggle_kind(MediaKind::Audio);
+        assert!(!filter.is_active());
+    }
+
+    #[test]
+    fn test_bin_filter() {
+        let mut binned = asset("a.mp4", 100);
+        binned.bin = Some("Day 1".into());
+        let filter = LibraryFilter { bin: Some("Day 1".into()), ..Default::default() };
+        assert!(filter.is_active());
+        assert!(filter.matches(&binned, false));
+        assert!(!filter.matches(&asset("b.mp4", 100), false));
+    }
+
+    #[test]
+    fn test_sort_by_name_and_resolution() {
+        let small = asset("b.mp4", 100);
+        let large = asset("A.mp4", 400);
+        let mut assets = vec![&small, &large];
+        sort_assets(&mut assets, LibrarySort::Name, false);
+        assert_eq!(assets[0].name, "A.mp4");
+        sort_assets(&mut assets, LibrarySort::Resolution, true);
+        assert_eq!(assets[0].name, "A.mp4");
+        sort_assets(&mut assets, LibrarySort::Resolution, false);
+        assert_eq!(assets[0].name, "b.mp4");
+    }
+
+    #[test]
+    fn test_usage_filter() {
+        let filter = LibraryFilter { usage: UsageFilter::Unused, ..Default::default() };
+        assert!(filter.matches(&asset("a.mp4", 100), false));
+        assert!(!filter.matches(&asset("a.mp4", 100), true));
+    }
+}
@@ -9,5 +9,6 @@ fn main() -> iced::Result {
         .title(App::title)
         .subscription(App::subscription)
         .theme(dark_theme)
+        .scale_factor(App::scale_factor)
         .run()
 }
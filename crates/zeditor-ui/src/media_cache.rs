@@ -0,0 +1,334 @@
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::preview_cache::PreviewCacheKey;
+
+/// Bytes hashed from each end of a media file for its fingerprint.
+const FINGERPRINT_SAMPLE_BYTES: u64 = 64 * 1024;
+const THUMBNAIL_MAGIC: &[u8; 4] = b"ZTH1";
+/// Magic, mtime, width, height.
+const THUMBNAIL_HEADER_LEN: usize = 4 + 8 + 4 + 4;
+const PREVIEW_FRAME_MAGIC: &[u8; 4] = b"ZPF1";
+/// Magic, width, height.
+const PREVIEW_FRAME_HEADER_LEN: usize = 4 + 4 + 4;
+
+/// Identifies the contents of a media file without reading all of it: a hash
+/// of its size and first and last 64 KiB, plus its modification time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MediaFingerprint {
+    pub hash: u64,
+    pub mtime_nanos: u64,
+}
+
+impl MediaFingerprint {
+    pub fn of(path: &Path) -> std::io::Result<Self> {
+        let mut file = File::open(path)?;
+        let metadata = file.metadata()?;
+        let size = metadata.len();
+        let mtime_nanos = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_nanos() as u64);
+
+        let mut hash = fnv1a(FNV_OFFSET, &size.to_le_bytes());
+        let mut head = Vec::new();
+        (&mut file).take(FINGERPRINT_SAMPLE_BYTES).read_to_end(&mut head)?;
+        hash = fnv1a(hash, &head);
+        if size > FINGERPRINT_SAMPLE_BYTES {
+            let mut tail = Vec::new();
+            file.seek(SeekFrom::Start(size.saturating_sub(FINGERPRINT_SAMPLE_BYTES).max(FINGERPRINT_SAMPLE_BYTES)))?;
+            file.read_to_end(&mut tail)?;
+            hash = fnv1a(hash, &tail);
+        }
+        Ok(Self { hash, mtime_nanos })
+    }
+}
+
+/// Hash of a file's entire contents, for spotting the same media imported
+/// twice. Unlike `MediaFingerprint` it reads every byte, so two recordings
+/// that only share their size and silent heads and tails don't match.
+pub fn content_hash(path: &Path) -> std::io::Result<u64> {
+    let mut file = File::open(path)?;
+    let mut hash = FNV_OFFSET;
+    let mut buf = vec![0u8; 1024 * 1024];
+    loop {
+        let read = file.read(&mut buf)?;
+        if read == 0 {
+            return Ok(hash);
+        }
+        hash = fnv1a(hash, &buf[..read]);
+    }
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// FNV-1a, chosen over `DefaultHasher` because cache keys must stay stable
+/// across builds.
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for &byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// RGBA thumbnail as stored in the cache.
+pub type Thumbnail = (Vec<u8>, u32, u32);
+
+/// Tag for preview frames rendered through `display_lut`, so frames made with
+/// a different display transform are never served.
+pub fn preview_variant(display_lut: Option<&Path>) -> u64 {
+    display_lut.map_or(0, |path| fnv1a(FNV_OFFSET, path.as_os_str().as_encoded_bytes()))
+}
+
+/// Per-user cache of data derived from media files, shared by all projects.
+/// Entries are keyed by the file's content hash and record its mtime, so an
+/// entry for a file that has since been touched is treated as stale.
+#[derive(Debug, Clone)]
+pub struct MediaCache {
+    dir: PathBuf,
+}
+
+impl MediaCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// `zeditor/` in the user cache directory.
+    pub fn default_dir() -> Option<PathBuf> {
+        let cache_dir = std::env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("LOCALAPPDATA").map(PathBuf::from))
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+        Some(cache_dir.join("zeditor"))
+    }
+
+    /// Where `zeditor_media::seek_index::SeekIndex` keeps its entries.
+    pub fn seek_index_dir(&self) -> PathBuf {
+        self.dir.join("seek-index")
+    }
+
+    fn thumbnail_path(&self, fingerprint: MediaFingerprint) -> PathBuf {
+        self.dir.join("thumbnails").join(format!("{:016x}.thumb", fingerprint.hash))
+    }
+
+    /// The cached thumbnail for `fingerprint`, unless missing, stale or corrupt.
+    pub fn load_thumbnail(&self, fingerprint: MediaFingerprint) -> Option<Thumbnail> {
+        let bytes = std::fs::read(self.thumbnail_path(fingerprint)).ok()?;
+        let (header, rgba) = bytes.split_at_checked(THUMBNAIL_HEADER_LEN)?;
+        let word = |at: usize, len: usize| &header[at..at + len];
+        let mtime = u64::from_le_bytes(word(4, 8).try_into().ok()?);
+        let width = u32::from_le_bytes(word(12, 4).try_into().ok()?);
+        let height = u32::from_le_bytes(word(16, 4).try_into().ok()?);
+        let valid = &header[..4] == THUMBNAIL_MAGIC
+            && mtime == fingerprint.mtime_nanos
+            && rgba.len() == width as usize * height as usize * 4;
+        valid.then(|| (rgba.to_vec(), width, height))
+    }
+
+    /// Store a thumbnail, replacing any stale entry.
+    pub fn store_thumbnail(&self, fingerprint: MediaFingerprint, thumbnail: &Thumbnail) -> std::io::Result<()> {
+        let (rgba, width, height) = thumbnail;
+        let path = self.thumbnail_path(fingerprint);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut bytes = Vec::with_capacity(THUMBNAIL_HEADER_LEN + rgba.len());
+        bytes.extend_from_slice(THUMBNAIL_MAGIC);
+        bytes.extend_from_slice(&fingerprint.mtime_nanos.to_le_bytes());
+        bytes.extend_from_slice(&width.to_le_bytes());
+        bytes.extend_from_slice(&height.to_le_bytes());
+        bytes.extend_from_slice(rgba);
+        write_atomically(&path, &bytes)
+    }
+
+    fn preview_frame_path(&self, key: PreviewCacheKey, variant: u64) -> PathBuf {
+        self.dir
+            .join("preview")
+            .join(format!("{:016x}-{variant:016x}-{}.frame", key.composition, key.frame))
+    }
+
+    /// A background-rendered preview frame. The composition hash in `key`
+    /// changes with any edit under the frame, so entries never go stale in
+    /// place; old ones are removed by `prune_preview_frames`.
+    pub fn load_preview_frame(&self, key: PreviewCacheKey, variant: u64) -> Option<Thumbnail> {
+        let bytes = std::fs::read(self.preview_frame_path(key, variant)).ok()?;
+        let (header, rgba) = bytes.split_at_checked(PREVIEW_FRAME_HEADER_LEN)?;
+        let width = u32::from_le_bytes(header[4..8].try_into().ok()?);
+        let height = u32::from_le_bytes(header[8..12].try_into().ok()?);
+        let valid = &header[..4] == PREVIEW_FRAME_MAGIC && rgba.len() == width as usize * height as usize * 4;
+        valid.then(|| (rgba.to_vec(), width, height))
+    }
+
+    pub fn store_preview_frame(&self, key: PreviewCacheKey, variant: u64, rgba: &[u8], width: u32, height: u32) -> std::io::Result<()> {
+        let path = self.preview_frame_path(key, variant);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut bytes = Vec::with_capacity(PREVIEW_FRAME_HEADER_LEN + rgba.len());
+        bytes.extend_from_slice(PREVIEW_FRAME_MAGIC);
+        bytes.extend_from_slice(&width.to_le_bytes());
+        bytes.extend_from_slice(&height.to_le_bytes());
+        bytes.extend_from_slice(rgba);
+        write_atomically(&path, &bytes)
+    }
+
+    /// Delete the least recently written preview frames until the rest fit in
+    /// `max_bytes`. Returns the number of bytes freed.
+    pub fn prune_preview_frames(&self, max_bytes: u64) -> std::io::Result<u64> {
+        let entries = match std::fs::read_dir(self.dir.join("preview")) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e),
+        };
+        let mut frames: Vec<(std::time::SystemTime, u64, PathBuf)> = entries
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let metadata = entry.metadata().ok()?;
+                Some((metadata.modified().unwrap_or(UNIX_EPOCH), metadata.len(), entry.path()))
+            })
+            .collect();
+        frames.sort();
+        let mut total: u64 = frames.iter().map(|(_, len, _)| len).sum();
+        let mut freed = 0;
+        for (_, len, path) in frames {
+            if total <= max_bytes {
+                break;
+            }
+            std::fs::remove_file(path)?;
+            total -= len;
+            freed += len;
+        }
+        Ok(freed)
+    }
+}
+
+/// Write via a temporary file and rename so readers never see a partial entry.
+fn write_atomically(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, bytes)?;
+    std::fs::rename(tmp, path)
+}
+
+/// Thumbnail for the media at `path`: from `cache` when it holds a fresh
+/// entry, otherwise made by `generate` and stored for next time. The second
+/// value tells whether the cache was hit.
+pub fn cached_thumbnail(
+    cache: Option<&MediaCache>,
+    path: &Path,
+    generate: impl FnOnce(&Path) -> Result<Thumbnail, String>,
+) -> (Result<Thumbnail, String>, bool) {
+    let fingerprint = cache.and_then(|_| MediaFingerprint::of(path).ok());
+    if let Some(hit) = cache.zip(fingerprint).and_then(|(cache, fp)| cache.load_thumbnail(fp)) {
+        return (Ok(hit), true);
+    }
+    let result = generate(path);
+    if let (Some(cache), Some(fingerprint), Ok(thumbnail)) = (cache, fingerprint, &result) {
+        // A failed write only costs a regeneration next time.
+        let _ = cache.store_thumbnail(fingerprint, thumbnail);
+    }
+    (result, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn thumb(value: u8) -> Thumbnail {
+        (vec![value; 2 * 2 * 4], 2, 2)
+    }
+
+    #[test]
+    fn test_fingerprint_follows_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.mp4");
+        let b = dir.path().join("b.mp4");
+        std::fs::write(&a, vec![1u8; 200_000]).unwrap();
+        std::fs::write(&b, vec![1u8; 200_000]).unwrap();
+        assert_eq!(MediaFingerprint::of(&a).unwrap().hash, MediaFingerprint::of(&b).unwrap().hash);
+
+        let mut changed = vec![1u8; 200_000];
+        changed[199_999] = 2;
+        std::fs::write(&b, changed).unwrap();
+        assert_ne!(MediaFingerprint::of(&a).unwrap().hash, MediaFingerprint::of(&b).unwrap().hash);
+    }
+
+    #[test]
+    fn test_content_hash_reads_the_middle_too() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.wav");
+        let b = dir.path().join("b.wav");
+        let mut changed = vec![0u8; 400_000];
+        changed[200_000] = 1;
+        std::fs::write(&a, vec![0u8; 400_000]).unwrap();
+        std::fs::write(&b, changed).unwrap();
+
+        assert_eq!(MediaFingerprint::of(&a).unwrap().hash, MediaFingerprint::of(&b).unwrap().hash);
+        assert_ne!(content_hash(&a).unwrap(), content_hash(&b).unwrap());
+        std::fs::copy(&a, &b).unwrap();
+        assert_eq!(content_hash(&a).unwrap(), content_hash(&b).unwrap());
+    }
+
+    #[test]
+    fn test_thumbnail_round_trip_and_staleness() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = MediaCache::new(dir.path().to_path_buf());
+        let fp = MediaFingerprint { hash: 42, mtime_nanos: 7 };
+
+        assert_eq!(cache.load_thumbnail(fp), None);
+        cache.store_thumbnail(fp, &thumb(9)).unwrap();
+        assert_eq!(cache.load_thumbnail(fp), Some(thumb(9)));
+
+        let touched = MediaFingerprint { mtime_nanos: 8, ..fp };
+        assert_eq!(cache.load_thumbnail(touched), None, "a newer mtime makes the entry stale");
+    }
+
+    #[test]
+    fn test_preview_frames_round_trip_and_prune() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = MediaCache::new(dir.path().to_path_buf());
+        let key = PreviewCacheKey { frame: 12, composition: 99 };
+        let rgba = vec![7u8; 2 * 2 * 4];
+
+        assert_eq!(cache.load_preview_frame(key, 0), None);
+        cache.store_preview_frame(key, 0, &rgba, 2, 2).unwrap();
+        assert_eq!(cache.load_preview_frame(key, 0), Some((rgba.clone(), 2, 2)));
+        let lut = preview_variant(Some(Path::new("/luts/film.cube")));
+        assert_eq!(cache.load_preview_frame(key, lut), None, "frames are kept per display LUT");
+        let edited = PreviewCacheKey { composition: 100, ..key };
+        assert_eq!(cache.load_preview_frame(edited, 0), None);
+
+        cache.store_preview_frame(edited, 0, &rgba, 2, 2).unwrap();
+        assert_eq!(cache.prune_preview_frames(u64::MAX).unwrap(), 0);
+        let frame_bytes = (PREVIEW_FRAME_HEADER_LEN + rgba.len()) as u64;
+        assert_eq!(cache.prune_preview_frames(frame_bytes).unwrap(), frame_bytes);
+        let remaining = [key, edited].iter().filter(|k| cache.load_preview_frame(**k, 0).is_some()).count();
+        assert_eq!(remaining, 1);
+    }
+
+    #[test]
+    fn test_cached_thumbnail_generates_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let media = dir.path().join("clip.mp4");
+        std::fs::write(&media, b"not really a video").unwrap();
+        let cache = MediaCache::new(dir.path().join("cache"));
+        let generated = Cell::new(0);
+        let generate = |_: &Path| {
+            generated.set(generated.get() + 1);
+            Ok(thumb(3))
+        };
+
+        assert_eq!(cached_thumbnail(Some(&cache), &media, generate), (Ok(thumb(3)), false));
+        assert_eq!(cached_thumbnail(Some(&cache), &media, generate), (Ok(thumb(3)), true));
+        assert_eq!(generated.get(), 1);
+
+        // Without a cache every call generates.
+        assert_eq!(cached_thumbnail(None, &media, generate), (Ok(thumb(3)), false));
+        assert_eq!(generated.get(), 2);
+    }
+}
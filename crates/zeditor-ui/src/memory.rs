@@ -0,0 +1,281 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+const MIB: usize = 1024 * 1024;
+const SUBSYSTEM_COUNT: usize = 5;
+
+/// Parts of the editor that hold sizeable buffers, accounted separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MemorySubsystem {
+    Thumbnails,
+    PreviewCache,
+    PendingFrames,
+    Decoders,
+    Prerender,
+}
+
+impl MemorySubsystem {
+    pub fn all() -> [MemorySubsystem; SUBSYSTEM_COUNT] {
+        [Self::Thumbnails, Self::PreviewCache, Self::PendingFrames, Self::Decoders, Self::Prerender]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Thumbnails => "thumbs",
+            Self::PreviewCache => "preview",
+            Self::PendingFrames => "pending",
+            Self::Decoders => "decoders",
+            Self::Prerender => "prerender",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Per-subsystem caps in MiB, stored with the UI preferences. `None` leaves a
+/// subsystem uncapped; it is still measured. Subsystems without an `Evictable`
+/// holder (thumbnails, which are not regenerated) are only flagged when over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MemoryCaps {
+    pub thumbnails_mib: Option<usize>,
+    pub preview_cache_mib: Option<usize>,
+    pub pending_frames_mib: Option<usize>,
+    pub decoders_mib: Option<usize>,
+    pub prerender_mib: Option<usize>,
+}
+
+impl Default for MemoryCaps {
+    fn default() -> Self {
+        Self {
+            thumbnails_mib: Some(256),
+            preview_cache_mib: Some(512),
+            pending_frames_mib: None,
+            decoders_mib: Some(1024),
+            prerender_mib: Some(1024),
+        }
+    }
+}
+
+impl MemoryCaps {
+    /// Cap for `subsystem` in bytes.
+    pub fn bytes(&self, subsystem: MemorySubsystem) -> Option<usize> {
+        let mib = match subsystem {
+            MemorySubsystem::Thumbnails => self.thumbnails_mib,
+            MemorySubsystem::PreviewCache => self.preview_cache_mib,
+            MemorySubsystem::PendingFrames => self.pending_frames_mib,
+            MemorySubsystem::Decoders => self.decoders_mib,
+            MemorySubsystem::Prerender => self.prerender_mib,
+        };
+        mib.map(|mib| mib.saturating_mul(MIB))
+    }
+}
+
+/// A cache the budget can shrink when its subsystem goes over its cap.
+pub trait Evictable {
+    /// Bytes currently held.
+    fn memory_bytes(&self) -> usize;
+
+    /// Drop entries (least valuable first) until at most `max_bytes` remain.
+    /// Returns the number of bytes freed.
+    fn evict_to(&mut self, max_bytes: usize) -> usize;
+}
+
+/// Central memory accounting. Clones share the same counters, so the decode
+/// worker threads report into the budget the UI displays. Caps are fixed when
+/// the budget is created.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryBudget {
+    caps: MemoryCaps,
+    usage: Arc<[AtomicUsize; SUBSYSTEM_COUNT]>,
+    evicted: Arc<[AtomicUsize; SUBSYSTEM_COUNT]>,
+}
+
+impl MemoryBudget {
+    pub fn new(caps: MemoryCaps) -> Self {
+        Self {
+            caps,
+            ..Self::default()
+        }
+    }
+
+    pub fn caps(&self) -> MemoryCaps {
+        self.caps
+    }
+
+    pub fn cap(&self, subsystem: MemorySubsystem) -> Option<usize> {
+        self.caps.bytes(subsystem)
+    }
+
+    /// Bytes currently reported for `subsystem`.
+    pub fn usage(&self, subsystem: MemorySubsystem) -> usize {
+        self.usage[subsystem.index()].load(Ordering::Relaxed)
+    }
+
+    pub fn total(&self) -> usize {
+        MemorySubsystem::all().into_iter().map(|s| self.usage(s)).sum()
+    }
+
+    /// Bytes freed by eviction in `subsystem` since startup.
+    pub fn evicted(&self, subsystem: MemorySubsystem) -> usize {
+        self.evicted[subsystem.index()].load(Ordering::Relaxed)
+    }
+
+    pub fn over_cap(&self, subsystem: MemorySubsystem) -> bool {
+        self.cap(subsystem).is_some_and(|cap| self.usage(subsystem) > cap)
+    }
+
+    /// Set the usage of a subsystem owned by a single holder.
+    pub fn record(&self, subsystem: MemorySubsystem, bytes: usize) {
+        self.usage[subsystem.index()].store(bytes, Ordering::Relaxed);
+    }
+
+    /// Update one holder's share of a subsystem that several holders report
+    /// into (e.g. the timeline and source monitor decode workers).
+    /// `previous` is that holder's last reported share and is updated.
+    pub fn record_share(&self, subsystem: MemorySubsystem, previous: &mut usize, bytes: usize) {
+        let counter = &self.usage[subsystem.index()];
+        if bytes >= *previous {
+            counter.fetch_add(bytes - *previous, Ordering::Relaxed);
+        } else {
+            counter.fetch_sub(*previous - bytes, Ordering::Relaxed);
+        }
+        *previous = bytes;
+    }
+
+    /// Shrink `cache` to its subsystem's cap if it is over, then record what it
+    /// holds. Returns the bytes freed.
+    pub fn enforce(&self, subsystem: MemorySubsystem, cache: &mut dyn Evictable) -> usize {
+        let freed = self.evict_over_cap(subsystem, cache);
+        self.record(subsystem, cache.memory_bytes());
+        freed
+    }
+
+    /// Like `enforce`, for a holder reporting a share of its subsystem.
+    /// The cap applies to the holder's share.
+    pub fn enforce_share(&self, subsystem: MemorySubsystem, previous: &mut usize, cache: &mut dyn Evictable) -> usize {
+        let freed = self.evict_over_cap(subsystem, cache);
+        self.record_share(subsystem, previous, cache.memory_bytes());
+        freed
+    }
+
+    fn evict_over_cap(&self, subsystem: MemorySubsystem, cache: &mut dyn Evictable) -> usize {
+        let Some(cap) = self.cap(subsystem).filter(|&cap| cache.memory_bytes() > cap) else {
+            return 0;
+        };
+        let freed = cache.evict_to(cap);
+        self.evicted[subsystem.index()].fetch_add(freed, Ordering::Relaxed);
+        freed
+    }
+
+    /// One-line summary for the debug overlay, e.g.
+    /// `mem 96.0 MiB | thumbs 1.2/256 | preview 90.4/512 | ...`.
+    pub fn summary(&self) -> String {
+        let parts: Vec<String> = MemorySubsystem::all()
+            .into_iter()
+            .map(|s| {
+                let used = self.usage(s) as f64 / MIB as f64;
+                let cap = match self.cap(s) {
+                    Some(cap) => format!("/{}", cap / MIB),
+                    None => String::new(),
+                };
+                let flag = if self.over_cap(s) { " (OVER)" } else { "" };
+                format!("{} {used:.1}{cap}{flag}", s.label())
+            })
+            .collect();
+        format!("mem {:.1} MiB | {}", self.total() as f64 / MIB as f64, parts.join(" | "))
+    }
+}
+
+/// Bytes of pixel data held by an image handle.
+pub fn handle_bytes(handle: &iced::widget::image::Handle) -> usize {
+    match handle {
+        iced::widget::image::Handle::Rgba { pixels, .. } => pixels.len(),
+        iced::widget::image::Handle::Bytes(_, bytes) => bytes.len(),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Entries of `size` bytes, evicted oldest first.
+    struct Entries(Vec<usize>);
+
+    impl Evictable for Entries {
+        fn memory_bytes(&self) -> usize {
+            self.0.iter().sum()
+        }
+
+        fn evict_to(&mut self, max_bytes: usize) -> usize {
+            let mut freed = 0;
+            while self.memory_bytes() > max_bytes && !self.0.is_empty() {
+                freed += self.0.remove(0);
+            }
+            freed
+        }
+    }
+
+    fn caps(preview_mib: Option<usize>) -> MemoryCaps {
+        MemoryCaps {
+            preview_cache_mib: preview_mib,
+            ..MemoryCaps::default()
+        }
+    }
+
+    #[test]
+    fn test_enforce_evicts_down_to_cap() {
+        let budget = MemoryBudget::new(caps(Some(2)));
+        let mut cache = Entries(vec![MIB; 5]);
+
+        let freed = budget.enforce(MemorySubsystem::PreviewCache, &mut cache);
+
+        assert_eq!(freed, 3 * MIB);
+        assert_eq!(cache.0.len(), 2);
+        assert_eq!(budget.usage(MemorySubsystem::PreviewCache), 2 * MIB);
+        assert_eq!(budget.evicted(MemorySubsystem::PreviewCache), 3 * MIB);
+        assert!(!budget.over_cap(MemorySubsystem::PreviewCache));
+    }
+
+    #[test]
+    fn test_uncapped_subsystem_is_only_measured() {
+        let budget = MemoryBudget::new(caps(None));
+        let mut cache = Entries(vec![MIB; 5]);
+
+        assert_eq!(budget.enforce(MemorySubsystem::PreviewCache, &mut cache), 0);
+        assert_eq!(cache.0.len(), 5);
+        assert_eq!(budget.usage(MemorySubsystem::PreviewCache), 5 * MIB);
+    }
+
+    #[test]
+    fn test_shares_from_clones_add_up() {
+        let budget = MemoryBudget::default();
+        let worker = budget.clone();
+        let (mut a, mut b) = (0, 0);
+
+        budget.record_share(MemorySubsystem::Decoders, &mut a, 300);
+        worker.record_share(MemorySubsystem::Decoders, &mut b, 200);
+        assert_eq!(budget.usage(MemorySubsystem::Decoders), 500);
+
+        worker.record_share(MemorySubsystem::Decoders, &mut b, 50);
+        assert_eq!(budget.usage(MemorySubsystem::Decoders), 350);
+        assert_eq!(b, 50);
+    }
+
+    #[test]
+    fn test_summary_flags_over_cap() {
+        let budget = MemoryBudget::new(caps(Some(1)));
+        budget.record(MemorySubsystem::PreviewCache, 2 * MIB);
+        budget.record(MemorySubsystem::Thumbnails, MIB);
+
+        let summary = budget.summary();
+        assert!(summary.starts_with("mem 3.0 MiB"));
+        assert!(summary.contains("preview 2.0/1 (OVER)"));
+        assert!(summary.contains("thumbs 1.0/256 |"));
+    }
+}
@@ -1,8 +1,20 @@
 use std::path::PathBuf;
 
 use uuid::Uuid;
+use zeditor_core::audio::{ChannelMap, VolumeEnvelope};
 use zeditor_core::effects::EffectType;
-use zeditor_core::timeline::{TimelinePosition, TrackType};
+use zeditor_core::media::{MediaKind, PathRemap};
+use zeditor_core::project::CanvasPreset;
+use zeditor_core::reframe::PanPath;
+use zeditor_core::scaler::CanvasFit;
+use zeditor_core::timeline::{RateConform, TimeRange, TimelinePosition, TrackHeight, TrackType};
+use zeditor_core::transcript::TranscriptWord;
+use zeditor_media::beats::BeatAnalysis;
+use zeditor_media::probe::MediaDetails;
+
+use crate::i18n::Language;
+use crate::library_filter::{LibrarySort, LibraryViewMode, UsageFilter};
+use crate::preferences::PreviewQuality;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ToolMode {
@@ -16,12 +28,23 @@ pub enum LeftPanelTab {
     #[default]
     ProjectLibrary,
     Effects,
+    /// Pre-export report of gaps, overlaps and broken clips.
+    TimelineHealth,
+    /// Transcribed words of the timeline, for text-based editing.
+    Transcript,
+    /// Named timeline snapshots to compare against or restore.
+    Snapshots,
+    /// Timeline ranges flagged for batch export.
+    RenderRanges,
+    /// Summary of the project: durations, clip and effect counts, export size.
+    Statistics,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MenuId {
     File,
     Edit,
+    View,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -29,23 +52,194 @@ pub enum MenuAction {
     NewProject,
     LoadProject,
     Save,
+    SaveAs,
+    RestoreVersion,
+    RemapMediaPaths,
+    ImportFolder,
     Render,
+    ExportDescription,
+    ToggleExportStems,
     Exit,
     Undo,
     Redo,
+    GroupClips,
+    UngroupClips,
+    SynchronizeClips,
+    SplitDualMono,
+    TranscribeClip,
+    DetectBeats,
+    AutoReframe,
+    ReplaceMedia,
+    RelinkMedia,
+    RevealInTimeline,
+    ToggleRippleEdits,
+    ToggleSnapTarget(SnapTarget),
+    Preferences,
+    FitToFill,
+    CreateSlideshow,
+    /// UI scale in percent (100 = unscaled).
+    SetUiScale(u16),
+    SetLanguage(Language),
 }
 
-/// Action pending user confirmation.
+/// What dragged and resized clip edges snap to, besides neighbouring clips.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapTarget {
+    Markers,
+    Playhead,
+    /// Quiet cut points for trimmed audio clip ends.
+    QuietAudio,
+}
+
+/// Framing guides that can be toggled over the preview.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreviewOverlayKind {
+    /// Action-safe (90%) and title-safe (80%) rectangles.
+    SafeAreas,
+    /// Rule-of-thirds grid.
+    ThirdsGrid,
+    /// Center cross.
+    CenterCross,
+    /// Shade what a centered 9:16 crop of the canvas would cut off.
+    VerticalCrop,
+}
+
+/// What is shown behind transparent areas of the composited preview frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PreviewBackground {
+    #[default]
+    Checkerboard,
+    Black,
+    Gray,
+    White,
+}
+
+impl PreviewBackground {
+    /// The next choice when cycling through backgrounds from the viewport controls.
+    pub fn next(self) -> Self {
+        match self {
+            PreviewBackground::Checkerboard => PreviewBackground::Black,
+            PreviewBackground::Black => PreviewBackground::Gray,
+            PreviewBackground::Gray => PreviewBackground::White,
+            PreviewBackground::White => PreviewBackground::Checkerboard,
+        }
+    }
+
+    /// Locale key of the background's name.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            PreviewBackground::Checkerboard => "preview-background-checker",
+            PreviewBackground::Black => "preview-background-black",
+            PreviewBackground::Gray => "preview-background-gray",
+            PreviewBackground::White => "preview-background-white",
+        }
+    }
+}
+
+/// Action waiting on the answer to a dialog.
 #[derive(Debug, Clone)]
 pub enum ConfirmAction {
     RemoveAsset { asset_id: Uuid },
+    /// Replay the crash-recovery journal found next to the loaded project.
+    ReplayJournal { entry_count: usize },
+    DeleteTrack { track_index: usize },
+    /// Point the asset at the typed path.
+    RelinkAsset { asset_id: Uuid },
+    /// Save or discard unsaved edits, then carry on with the menu action.
+    UnsavedChanges { then: MenuAction },
+    /// Overwrite the existing outputs of the renders in
+    /// `App::renders_awaiting_overwrite`, or render next to them.
+    RenderOutputExists,
+}
+
+impl ConfirmAction {
+    /// Locale key of the label for the dialog's accept button.
+    pub fn accept_label_key(&self) -> &'static str {
+        match self {
+            ConfirmAction::RemoveAsset { .. } | ConfirmAction::DeleteTrack { .. } => "dialog-delete",
+            ConfirmAction::ReplayJournal { .. } => "dialog-replay-journal",
+            ConfirmAction::RelinkAsset { .. } => "dialog-relink",
+            ConfirmAction::UnsavedChanges { .. } => "dialog-discard",
+            ConfirmAction::RenderOutputExists => "dialog-overwrite",
+        }
+    }
+}
+
+/// What a dialog asks for besides accept or cancel.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DialogInput {
+    /// Accept or cancel.
+    Confirm,
+    /// A line of text, edited in the dialog.
+    Text(String),
+    /// Locale keys of the options, one button each in place of accept.
+    Choice(Vec<&'static str>),
+}
+
+/// The user's answer to a dialog, handed to its action.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DialogAnswer {
+    Accepted,
+    Text(String),
+    /// Index into the dialog's options.
+    Choice(usize),
 }
 
-/// A pending confirmation dialog.
+/// A pending modal dialog: a message, what it asks for, and the action the
+/// answer goes to. Cancelling drops the action.
 #[derive(Debug, Clone)]
 pub struct ConfirmDialog {
     pub message: String,
     pub action: ConfirmAction,
+    pub input: DialogInput,
+}
+
+impl ConfirmDialog {
+    pub fn confirm(message: String, action: ConfirmAction) -> Self {
+        Self { message, action, input: DialogInput::Confirm }
+    }
+
+    pub fn prompt(message: String, initial: String, action: ConfirmAction) -> Self {
+        Self { message, action, input: DialogInput::Text(initial) }
+    }
+
+    pub fn choice(message: String, options: Vec<&'static str>, action: ConfirmAction) -> Self {
+        Self { message, action, input: DialogInput::Choice(options) }
+    }
+}
+
+/// The open "Remap Media Paths" dialog: a path prefix, its replacement, and
+/// the assets the change would repoint.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PathRemapDialog {
+    pub from: String,
+    pub to: String,
+    /// Matching assets for the current `from`/`to`, kept up to date as they're typed.
+    pub preview: Vec<PathRemap>,
+}
+
+/// The open "Create Slideshow" dialog for the assets selected when it opened.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SlideshowDialog {
+    pub asset_ids: Vec<Uuid>,
+    /// Seconds per clip, as typed.
+    pub clip_secs: String,
+    /// Place the assets in random order instead of library order.
+    pub shuffle: bool,
+}
+
+/// Progress of an "Import Folder..." run, shown as a dialog until every file
+/// has been probed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FolderImport {
+    /// Bin the imported assets go into, named after the folder.
+    pub bin: String,
+    pub total: usize,
+    /// Files probed so far, including failures.
+    pub done: usize,
+    pub failed: usize,
+    /// Files already in the library, left out rather than imported twice.
+    pub skipped: usize,
 }
 
 /// Payload describing what is being dragged. Extensible for future drag sources.
@@ -55,7 +249,13 @@ pub enum DragPayload {
         asset_id: Uuid,
         thumbnail: Option<iced::widget::image::Handle>,
         name: String,
+        /// Other selected assets dragged along; placed back-to-back after `asset_id`.
+        additional_asset_ids: Vec<Uuid>,
+        /// Sub-clip of `asset_id` being dragged; the drop is trimmed to its range.
+        subclip_id: Option<Uuid>,
     },
+    /// An effect from the effects browser; dropped onto the clip under the cursor.
+    Effect { effect_type: EffectType },
 }
 
 /// State for a right-click context menu on a track header.
@@ -70,12 +270,70 @@ pub struct TrackContextMenu {
 #[derive(Debug, Clone)]
 pub struct SourceDragPreview {
     pub asset_id: Uuid,
+    /// Name and thumbnail from the drag payload, drawn on the drop block.
+    pub name: String,
+    pub thumbnail: Option<iced::widget::image::Handle>,
     pub duration_secs: f64,
     pub track_index: usize,
     pub position: TimelinePosition,
     pub audio_track_index: Option<usize>,
 }
 
+/// Per-track toggle shown as a button in the track header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackFlag {
+    /// Audio tracks: silence the track.
+    Mute,
+    /// Audio tracks: hear only soloed tracks.
+    Solo,
+    /// Video tracks: include the track in the preview and render.
+    Visible,
+    /// Reject clip edits on the track.
+    Lock,
+}
+
+/// A text field of the Preferences dialog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreferenceField {
+    CanvasWidth,
+    CanvasHeight,
+    Fps,
+    AutosaveMinutes,
+    SnapThresholdMs,
+    StillDurationSecs,
+    RenderSegments,
+    CacheDir,
+    DisplayLut,
+}
+
+impl PreferenceField {
+    /// Locale key of the field's label.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            PreferenceField::CanvasWidth => "prefs-canvas-width",
+            PreferenceField::CanvasHeight => "prefs-canvas-height",
+            PreferenceField::Fps => "prefs-fps",
+            PreferenceField::AutosaveMinutes => "prefs-autosave-minutes",
+            PreferenceField::SnapThresholdMs => "prefs-snap-threshold",
+            PreferenceField::StillDurationSecs => "prefs-still-duration",
+            PreferenceField::RenderSegments => "prefs-render-segments",
+            PreferenceField::CacheDir => "prefs-cache-dir",
+            PreferenceField::DisplayLut => "prefs-display-lut",
+        }
+    }
+}
+
+/// A draggable divider between two panels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanelSplitter {
+    /// Between the left panel and the preview; sets the left panel width.
+    LeftPanel,
+    /// Between the timeline and the inspector; sets the inspector width.
+    Inspector,
+    /// Between the preview row and the timeline; sets the preview height.
+    Preview,
+}
+
 /// App-level drag state tracking.
 #[derive(Debug, Clone)]
 pub struct DragState {
@@ -91,10 +349,60 @@ pub enum Message {
     // Source library
     ImportMedia(PathBuf),
     MediaImported(Result<zeditor_core::media::MediaAsset, String>),
+    /// Background hashing of an import the same size as a library asset
+    /// finished. `hashes` are for same-size assets that had none yet; `bin`
+    /// is set for folder imports.
+    ImportHashed {
+        asset: zeditor_core::media::MediaAsset,
+        hashes: Vec<(Uuid, u64)>,
+        bin: Option<String>,
+    },
     RemoveAsset(Uuid),
+    /// Select the asset's first use on the timeline, or the one after the
+    /// selected clip if that is already a use of it.
+    RevealAssetInTimeline(Uuid),
+    /// Pick a new file for the selected library asset ("Replace Media...").
+    ReplaceSelectedMedia,
+    ReplaceMediaFileChosen {
+        asset_id: Uuid,
+        path: Option<PathBuf>,
+    },
+    /// The replacement file was probed; swap it in if it's compatible.
+    ReplacementProbed {
+        asset_id: Uuid,
+        result: Result<zeditor_core::media::MediaAsset, String>,
+    },
     OpenFileDialog,
     FileDialogResult(Vec<PathBuf>),
+    /// "Import Folder...": pick a directory to import recursively.
+    ImportFolder,
+    ImportFolderChosen(Option<PathBuf>),
+    /// The chosen folder was scanned; `bin` is the folder's name.
+    FolderScanned {
+        bin: String,
+        result: Result<Vec<PathBuf>, String>,
+    },
+    /// One file of a folder import was probed.
+    FolderMediaImported {
+        bin: String,
+        result: Result<zeditor_core::media::MediaAsset, String>,
+    },
+    /// Click on a library asset. Ctrl/Cmd toggles it in the selection and Shift
+    /// extends the selection from the anchor; `None` clears the selection.
     SelectSourceAsset(Option<Uuid>),
+    LibrarySearchChanged(String),
+    /// "Find in timeline" query edited: jumps to the first matching clip.
+    TimelineSearchChanged(String),
+    /// Jump to the next matching clip, wrapping around (Enter in the find box).
+    TimelineSearchNext,
+    ToggleLibraryKindFilter(MediaKind),
+    SetLibraryUsageFilter(UsageFilter),
+    /// Show only one bin; choosing the active bin again shows all.
+    SetLibraryBinFilter(String),
+    ClearLibraryFilters,
+    /// Sort the library; choosing the active sort again reverses it.
+    SetLibrarySort(LibrarySort),
+    SetLibraryViewMode(LibraryViewMode),
 
     // Thumbnails
     ThumbnailGenerated {
@@ -105,32 +413,254 @@ pub enum Message {
     // Source library confirmation
     ConfirmRemoveAsset(Uuid),
     ConfirmDialogAccepted,
+    ConfirmDialogTextChanged(String),
+    /// One of a choice dialog's options, by index.
+    ConfirmDialogChoice(usize),
     ConfirmDialogDismissed,
 
     // Source card hover
     SourceCardHovered(Option<Uuid>),
 
+    // Source monitor
+    /// Load a library asset into the source monitor (double-click on its card).
+    OpenInSourceMonitor(Uuid),
+    /// Open an asset in the source monitor with its `shot`-th detected shot
+    /// marked in/out.
+    OpenShotInSourceMonitor { asset_id: Uuid, shot: usize },
+    /// Show or hide the library's info section for the selected asset.
+    ToggleAssetInfo,
+    /// Background extended probe of an asset finished.
+    AssetDetailsLoaded {
+        asset_id: Uuid,
+        result: Result<MediaDetails, String>,
+    },
+    /// Background shot detection finished: source times where shots start.
+    ShotsDetected {
+        asset_id: Uuid,
+        result: Result<Vec<f64>, String>,
+    },
+    /// Open a sub-clip's asset in the source monitor with its range marked.
+    OpenSubClipInSourceMonitor(Uuid),
+    /// Name typed for the next sub-clip saved from the source monitor.
+    SubClipNameChanged(String),
+    /// Save the source monitor's marked range as a sub-clip of its asset.
+    SaveSubClip,
+    RemoveSubClip {
+        asset_id: Uuid,
+        subclip_id: Uuid,
+    },
+    CloseSourceMonitor,
+    SourceMonitorTogglePlay,
+    SourceMonitorSeek(TimelinePosition),
+    SourceMonitorMarkIn,
+    SourceMonitorMarkOut,
+    SourceMonitorClearMarks,
+
     // Drag from source
     StartDragFromSource(Uuid),
+    StartDragFromSubClip(Uuid),
     DragMoved(iced::Point),
     DragReleased,
     DragEnteredTimeline,
     DragExitedTimeline,
     DragOverTimeline(iced::Point),
 
+    // Notifications
+    DismissNotification(u64),
+    ToggleNotificationHistory,
+    ClearNotifications,
+
+    // Panel splitters
+    StartSplitterDrag(PanelSplitter),
+    SplitterMoved(iced::Point),
+    SplitterReleased,
+
     // Timeline clip selection
     SelectTimelineClip(Option<(usize, uuid::Uuid)>),
+    /// Add a clip to the selection, or remove it if already selected (Shift/Ctrl+click).
+    ToggleClipSelection((usize, uuid::Uuid)),
+    /// Group the selected clips (Ctrl+G).
+    GroupSelectedClips,
+    /// Dissolve the groups of the selected clips (Ctrl+Shift+G).
+    UngroupSelectedClips,
+    /// Align two selected clips by their audio and group them.
+    SynchronizeSelectedClips,
+    /// Split the selected clip's stereo audio into left and right mono
+    /// clips on separate tracks.
+    SplitSelectedDualMono,
+    /// Background audio analysis finished: `offset_secs` places `other`
+    /// relative to `reference` (see `Timeline::sync_clip`).
+    ClipSyncAnalyzed {
+        reference: (usize, uuid::Uuid),
+        other: (usize, uuid::Uuid),
+        result: Result<f64, String>,
+    },
+    /// Fill the timeline gap under the playhead with the source monitor's
+    /// marked range, retimed to fit exactly.
+    FitToFill,
+    /// Transcribe the selected clip's asset in the background.
+    TranscribeSelectedClip,
+    /// Background transcription finished, with words in source time.
+    TranscriptReady {
+        asset_id: Uuid,
+        result: Result<Vec<TranscriptWord>, String>,
+    },
+    /// Detect the beat of the selected clip's audio in the background.
+    DetectBeatsSelectedClip,
+    /// Background beat detection finished, with beats in source seconds
+    /// (`None` if no steady beat was found).
+    BeatsDetected {
+        track_index: usize,
+        clip_id: Uuid,
+        result: Result<Option<BeatAnalysis>, String>,
+    },
+    /// Analyse the selected video clip in the background and pan its Fill
+    /// crop to follow the action on the project canvas.
+    AutoReframeSelectedClip,
+    /// Background reframe analysis finished, with pan keys in source time.
+    AutoReframeAnalyzed {
+        track_index: usize,
+        clip_id: Uuid,
+        result: Result<PanPath, String>,
+    },
+    /// Remove a clip's pan keys, cropping it centered again.
+    ClearClipReframe {
+        track_index: usize,
+        clip_id: Uuid,
+    },
+    /// Click on a word in the Transcript tab: selects it (Shift extends the
+    /// selection) and seeks to it.
+    SelectTranscriptWord(usize),
+    /// Ripple-delete the media under the selected words.
+    CutTranscriptSelection,
+    ExportTranscriptSrt,
+    SrtFileDialogResult(Option<PathBuf>),
+    /// Add a marker at the playhead (M key).
+    AddMarker,
+    /// Add a marker to the selected clip at the playhead (Shift+M).
+    AddClipMarker,
+    RemoveClipMarker {
+        track_index: usize,
+        clip_id: uuid::Uuid,
+        marker_id: uuid::Uuid,
+    },
+    /// Clip marker name typed in the inspector; applied on Enter.
+    ClipMarkerNameEdited {
+        marker_id: uuid::Uuid,
+        name: String,
+    },
+    /// Apply the name typed for a clip marker.
+    RenameClipMarker {
+        track_index: usize,
+        clip_id: uuid::Uuid,
+        marker_id: uuid::Uuid,
+    },
+    /// Add a guide line at the playhead (G key).
+    AddGuide,
+    GuideIntervalChanged(String),
+    /// Switch the guide interval between seconds and frames.
+    ToggleGuideIntervalUnit,
+    /// Add a guide every interval from the start to the end of the timeline.
+    GenerateGuides,
+    ClearGuides,
+    InsertTimeDurationChanged(String),
+    /// Open the typed number of seconds at the playhead on every unlocked
+    /// track, cutting clips that span it.
+    InsertTimeAtPlayhead,
+    /// Save a chapter list and clip list of the timeline as text.
+    ExportTimelineDescription,
+    DescriptionFileDialogResult(Option<PathBuf>),
+    /// Select the next (positive) or previous (negative) clip on the selected clip's track.
+    SelectAdjacentClip(i32),
+    /// Select the clip nearest in time on the track below (positive) or above (negative).
+    SelectClipOnAdjacentTrack(i32),
     RemoveClip {
         track_index: usize,
         clip_id: uuid::Uuid,
     },
+    /// Retime a clip (and its linked partners) to `speed` source seconds per timeline second.
+    SetClipSpeed {
+        track_index: usize,
+        clip_id: uuid::Uuid,
+        speed: f64,
+    },
+    /// Time-stretch a retimed clip's (and its linked partners') audio at
+    /// its original pitch, or let the pitch follow the speed.
+    SetClipPreservePitch {
+        track_index: usize,
+        clip_id: uuid::Uuid,
+        preserve: bool,
+    },
+    /// Route a clip's (and its linked partners') audio channels.
+    SetClipChannelMap {
+        track_index: usize,
+        clip_id: uuid::Uuid,
+        map: ChannelMap,
+    },
+    /// Choose how a clip (and its companions) conforms its source frame rate
+    /// to the project's.
+    SetClipRateConform {
+        track_index: usize,
+        clip_id: uuid::Uuid,
+        conform: RateConform,
+    },
+    /// Choose how a clip's frame is placed on the canvas.
+    SetClipCanvasFit {
+        track_index: usize,
+        clip_id: uuid::Uuid,
+        fit: CanvasFit,
+    },
+    /// Replace a clip's (and its linked partners') volume envelope, from
+    /// editing its rubber band on the timeline.
+    SetClipVolumeEnvelope {
+        track_index: usize,
+        clip_id: uuid::Uuid,
+        envelope: VolumeEnvelope,
+    },
+    /// Load the waveform around a clip's source range for the inspector.
+    LoadInspectorWaveform {
+        track_index: usize,
+        clip_id: uuid::Uuid,
+    },
+    /// Background waveform load finished; the window is in source seconds.
+    InspectorWaveformLoaded {
+        clip_id: uuid::Uuid,
+        window: (f64, f64),
+        result: Result<Vec<f32>, String>,
+    },
+    /// Give a clip (and its linked partners) new source in/out points, from
+    /// the inspector waveform's handles.
+    SetClipSourceRange {
+        track_index: usize,
+        clip_id: uuid::Uuid,
+        source: TimeRange,
+    },
+    /// Turn ripple edits on or off: resizing, removing or retiming a clip
+    /// shifts later clips on its tracks.
+    ToggleRippleEdits,
+    /// Turn writing audio stems alongside renders on or off.
+    ToggleExportStems,
+    /// Turn snapping to markers or the playhead on or off.
+    ToggleSnapTarget(SnapTarget),
 
     // Timeline
+    /// Add several assets back-to-back starting at `position`, as one undo step.
+    AddClipsToTimeline {
+        asset_ids: Vec<Uuid>,
+        track_index: usize,
+        position: TimelinePosition,
+    },
     AddClipToTimeline {
         asset_id: Uuid,
         track_index: usize,
         position: TimelinePosition,
     },
+    /// Add a clip trimmed to a sub-clip's range.
+    AddSubClipToTimeline {
+        subclip_id: Uuid,
+        track_index: usize,
+        position: TimelinePosition,
+    },
     MoveClip {
         source_track: usize,
         clip_id: Uuid,
@@ -146,18 +676,83 @@ pub enum Message {
         clip_id: Uuid,
         new_end: TimelinePosition,
     },
+    /// An audio clip's end was released at `new_end`: find a quiet cut
+    /// point near it in the background, then resize the clip there.
+    SnapAudioTrim {
+        track_index: usize,
+        clip_id: Uuid,
+        new_end: TimelinePosition,
+    },
+    /// The end of a clip being resized moved; updates the two-up trim preview.
+    TrimEdgeMoved {
+        track_index: usize,
+        clip_id: Uuid,
+        edge: TimelinePosition,
+    },
+    /// Frames either side of the trim edge finished decoding.
+    TrimFramesDecoded {
+        clip_id: Uuid,
+        outgoing: Option<iced::widget::image::Handle>,
+        incoming: Option<iced::widget::image::Handle>,
+    },
     TimelineClickEmpty(TimelinePosition),
 
     // Timeline view
     TimelineZoom { delta: f32, cursor_secs: f64 },
+    /// Scale the zoom by `factor` and scroll so `anchor_secs` sits at canvas x
+    /// `anchor_px` (a pinch zooms and pans in one step).
+    TimelineZoomBy { factor: f32, anchor_secs: f64, anchor_px: f32 },
     TimelineScroll(f32),
+    /// Scroll the track lanes vertically by a pixel delta (positive = down).
+    TimelineVerticalScroll(f32),
+    /// Hand-tool drag on the timeline: scroll by a pixel delta in both axes.
+    TimelinePan { dx: f32, dy: f32 },
+    /// A Space+drag pan began, so the Space press wasn't meant as play/pause.
+    TimelinePanStarted,
+    /// Step to the next zoom preset in (positive) or out (negative), keeping the playhead in place.
+    TimelineZoomStep(i32),
+    /// Zoom and scroll so every clip on the timeline is visible.
+    ZoomToFit,
+    WindowResized(iced::Size),
+    /// Application UI scale factor (1.0 = unscaled); persisted in preferences.
+    SetUiScale(f32),
+    /// Switch the language of menus, dialogs and status messages; persisted in preferences.
+    SetLanguage(Language),
 
     // Playback
     Play,
     Pause,
     SeekTo(TimelinePosition),
+    /// Text typed into the playhead timecode field.
+    TimecodeInputChanged(String),
+    /// Enter in the timecode field: seek to the typed timecode.
+    TimecodeSubmitted,
     TogglePlayback,
     PlaybackTick,
+    ToggleLoopPlayback,
+    TogglePreviewOverlay(PreviewOverlayKind),
+    TogglePlaybackStats,
+    ToggleMemoryOverlay,
+    SetPreviewBackground(PreviewBackground),
+    /// Reshape the project canvas, and so its renders, to a preset aspect ratio.
+    SetCanvasPreset(CanvasPreset),
+    /// L: play forward; repeated presses double the rate up to 4x.
+    ShuttleForward,
+    /// J: play in reverse; repeated presses double the reverse rate up to 4x.
+    ShuttleReverse,
+    /// K: stop shuttling and pause.
+    ShuttleStop,
+    /// Play from preroll before the playhead to postroll after it, then return.
+    PlayAround,
+    /// Mark (or unmark) the selected clip's range for background pre-rendering.
+    TogglePrerenderSelectedClip,
+    /// Turn rendering of the whole timeline to the disk cache while idle on or off.
+    ToggleBackgroundRender,
+    SetPlayAroundPreroll(f64),
+    SetPlayAroundPostroll(f64),
+    /// Step the playhead one frame (at project fps) forward or backward.
+    StepFrameForward,
+    StepFrameBackward,
 
     // Keyboard
     KeyboardEvent(iced::keyboard::Event),
@@ -166,10 +761,43 @@ pub enum Message {
     Undo,
     Redo,
     SaveProject,
+    /// Always prompt for a new path, even if the project was saved before.
+    SaveProjectAs,
     LoadProject(PathBuf),
     SaveFileDialogResult(Option<PathBuf>),
     LoadFileDialogResult(Option<PathBuf>),
     NewProject,
+    /// Open the "Restore Version" dialog listing the project's saved versions.
+    ShowProjectVersions,
+    /// Load a saved version in place of the current project, keeping its path.
+    RestoreProjectVersion(PathBuf),
+    ProjectVersionsDismissed,
+    /// Open the dialog for replacing a path prefix across all asset paths.
+    OpenPathRemap,
+    PathRemapFromChanged(String),
+    PathRemapToChanged(String),
+    /// Repoint every previewed asset whose new path exists.
+    ApplyPathRemap,
+    PathRemapDismissed,
+    /// Open the "Create Slideshow" dialog for the selected library assets.
+    OpenSlideshow,
+    SlideshowDurationChanged(String),
+    SlideshowShuffleToggled(bool),
+    /// Append the dialog's assets to the first video track, one clip each, as one undo step.
+    CreateSlideshow,
+    SlideshowDismissed,
+
+    // Preferences dialog
+    OpenPreferences,
+    PreferenceFieldChanged(PreferenceField, String),
+    SetPreviewQuality(PreviewQuality),
+    /// Fill the dialog's canvas fields with a preset shape.
+    PreferencesCanvasPreset(CanvasPreset),
+    /// Validate the dialog's fields and save them to the user config.
+    ApplyPreferences,
+    PreferencesDismissed,
+    /// Periodic autosave check, while `autosave_minutes` is non-zero.
+    AutosaveTick,
 
     // Render
     RenderFileDialogResult(Option<PathBuf>),
@@ -185,6 +813,38 @@ pub enum Message {
 
     // Left panel tabs
     SwitchLeftPanelTab(LeftPanelTab),
+    /// Seek to a Timeline Health or snapshot diff entry, selecting its clip if it has one.
+    JumpToHealthIssue {
+        track_index: usize,
+        clip_id: Option<uuid::Uuid>,
+        position: TimelinePosition,
+    },
+    SnapshotNameChanged(String),
+    /// Save the current timeline as a named snapshot.
+    TakeTimelineSnapshot,
+    /// Show (or hide, if already shown) what changed since a snapshot.
+    CompareTimelineSnapshot(uuid::Uuid),
+    RestoreTimelineSnapshot(uuid::Uuid),
+    DeleteTimelineSnapshot(uuid::Uuid),
+
+    /// Flag the playhead as the start of the next render range.
+    SetRenderRangeIn,
+    /// Flag the playhead as the end of the next render range.
+    SetRenderRangeOut,
+    RenderRangeNameChanged(String),
+    /// Save the flagged in/out as a named render range.
+    AddRenderRange,
+    DeleteRenderRange(uuid::Uuid),
+    RenderFilePatternChanged(String),
+    /// Pick a folder and queue a render of every render range into it.
+    ExportRenderRanges,
+    RenderRangesFolderResult(Option<PathBuf>),
+    /// Pick a file and queue a seamless loop render of a render range.
+    ExportRenderRangeLoop(uuid::Uuid),
+    RenderRangeLoopFileResult {
+        id: uuid::Uuid,
+        path: Option<PathBuf>,
+    },
 
     // Track context menu
     ShowTrackContextMenu {
@@ -192,18 +852,42 @@ pub enum Message {
         screen_position: iced::Point,
     },
     DismissTrackContextMenu,
+
+    // Track headers
+    ToggleTrackFlag {
+        track_index: usize,
+        flag: TrackFlag,
+    },
+    /// Expand or shrink a track's lane (and header).
+    SetTrackHeight {
+        track_index: usize,
+        height: TrackHeight,
+    },
+
+    // Track context menu actions
     AddVideoTrackAbove(usize),
     AddVideoTrackBelow(usize),
     AddAudioTrackAbove(usize),
     AddAudioTrackBelow(usize),
+    /// Delete a track, asking first when it has clips.
+    DeleteTrack(usize),
 
     // Effects
+    EffectSearchChanged(String),
+    StartDragEffect(EffectType),
     AddEffectToSelectedClip(EffectType),
+    AddEffectToClip {
+        track_index: usize,
+        clip_id: Uuid,
+        effect_type: EffectType,
+    },
     RemoveEffectFromClip {
         track_index: usize,
         clip_id: Uuid,
         effect_id: Uuid,
     },
+    /// A/B compare: preview every clip with or without its effects.
+    ToggleEffectsBypass,
     UpdateEffectParameter {
         track_index: usize,
         clip_id: Uuid,
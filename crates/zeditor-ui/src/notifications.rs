@@ -0,0 +1,192 @@
+use std::time::{Duration, Instant};
+
+/// How long success/info toasts stay on screen.
+pub const TOAST_DURATION: Duration = Duration::from_secs(4);
+
+/// Maximum number of entries kept in the history panel.
+pub const HISTORY_LIMIT: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl NotificationLevel {
+    /// Warnings and errors stay visible until dismissed; others fade after `TOAST_DURATION`.
+    pub fn is_persistent(self) -> bool {
+        matches!(self, Self::Warning | Self::Error)
+    }
+
+    /// Locale key of the level's tag in the history panel.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            Self::Info => "notifications-info",
+            Self::Success => "notifications-success",
+            Self::Warning => "notifications-warning",
+            Self::Error => "notifications-error",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub id: u64,
+    pub level: NotificationLevel,
+    pub message: String,
+    pub created: Instant,
+    /// Times this message was raised in a row (repeats are coalesced).
+    pub count: u32,
+    /// Dismissed notifications leave the toast stack but stay in the history.
+    pub dismissed: bool,
+}
+
+impl Notification {
+    /// Whether the notification is shown as a toast at `now`.
+    pub fn is_toast_visible(&self, now: Instant) -> bool {
+        if self.dismissed {
+            return false;
+        }
+        self.level.is_persistent() || now.saturating_duration_since(self.created) < TOAST_DURATION
+    }
+}
+
+/// Notification history plus the toasts derived from it.
+#[derive(Debug, Clone, Default)]
+pub struct NotificationCenter {
+    /// Oldest first, capped at `HISTORY_LIMIT`.
+    entries: Vec<Notification>,
+    next_id: u64,
+}
+
+impl NotificationCenter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a notification and return its id. Repeating the latest message
+    /// bumps its count and re-shows it instead of adding a new entry.
+    pub fn push(&mut self, level: NotificationLevel, message: impl Into<String>, now: Instant) -> u64 {
+        let message = message.into();
+        if let Some(last) = self
+            .entries
+            .last_mut()
+            .filter(|last| last.level == level && last.message == message)
+        {
+            last.count += 1;
+            last.created = now;
+            last.dismissed = false;
+            return last.id;
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        self.entries.push(Notification {
+            id,
+            level,
+            message,
+            created: now,
+            count: 1,
+            dismissed: false,
+        });
+        if self.entries.len() > HISTORY_LIMIT {
+            let excess = self.entries.len() - HISTORY_LIMIT;
+            self.entries.drain(..excess);
+        }
+        id
+    }
+
+    pub fn dismiss(&mut self, id: u64) {
+        if let Some(entry) = self.entries.iter_mut().find(|n| n.id == id) {
+            entry.dismissed = true;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// All notifications, newest first.
+    pub fn history(&self) -> impl Iterator<Item = &Notification> {
+        self.entries.iter().rev()
+    }
+
+    /// Notifications shown as toasts at `now`, newest first.
+    pub fn toasts(&self, now: Instant) -> impl Iterator<Item = &Notification> {
+        self.history().filter(move |n| n.is_toast_visible(now))
+    }
+
+    /// Undismissed warnings and errors, for the badge on the history button.
+    pub fn unresolved_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|n| n.level.is_persistent() && !n.dismissed)
+            .count()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_success_toast_expires_but_error_persists() {
+        let mut center = NotificationCenter::new();
+        let now = Instant::now();
+        center.push(NotificationLevel::Success, "Saved", now);
+        center.push(NotificationLevel::Error, "Render failed", now);
+        assert_eq!(center.toasts(now).count(), 2);
+
+        let later = now + TOAST_DURATION + Duration::from_millis(1);
+        let toasts: Vec<_> = center.toasts(later).collect();
+        assert_eq!(toasts.len(), 1);
+        assert_eq!(toasts[0].message, "Render failed");
+        assert_eq!(center.len(), 2);
+    }
+
+    #[test]
+    fn test_dismiss_hides_toast_keeps_history() {
+        let mut center = NotificationCenter::new();
+        let now = Instant::now();
+        let id = center.push(NotificationLevel::Error, "Decode failed", now);
+        assert_eq!(center.unresolved_count(), 1);
+        center.dismiss(id);
+        assert_eq!(center.toasts(now).count(), 0);
+        assert_eq!(center.unresolved_count(), 0);
+        assert_eq!(center.history().count(), 1);
+    }
+
+    #[test]
+    fn test_repeats_are_coalesced() {
+        let mut center = NotificationCenter::new();
+        let now = Instant::now();
+        let a = center.push(NotificationLevel::Error, "Decode failed", now);
+        center.dismiss(a);
+        let b = center.push(NotificationLevel::Error, "Decode failed", now);
+        assert_eq!(a, b);
+        assert_eq!(center.len(), 1);
+        let entry = center.history().next().unwrap();
+        assert_eq!(entry.count, 2);
+        assert!(!entry.dismissed);
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let mut center = NotificationCenter::new();
+        let now = Instant::now();
+        for i in 0..HISTORY_LIMIT + 10 {
+            center.push(NotificationLevel::Info, format!("msg {i}"), now);
+        }
+        assert_eq!(center.len(), HISTORY_LIMIT);
+        assert_eq!(center.history().next().unwrap().message, format!("msg {}", HISTORY_LIMIT + 9));
+    }
+}
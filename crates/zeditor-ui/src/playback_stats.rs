@@ -0,0 +1,140 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Window over which the achieved preview fps is measured.
+const FPS_WINDOW: Duration = Duration::from_secs(1);
+
+/// Audio buffered below this while playing is reported as an underrun risk.
+pub const AUDIO_BUFFER_LOW: Duration = Duration::from_millis(50);
+
+/// Rolling preview performance counters shown in the stats overlay.
+#[derive(Debug, Clone, Default)]
+pub struct PlaybackStats {
+    /// Display times of recent frames, oldest first, trimmed to `FPS_WINDOW`.
+    frame_times: VecDeque<Instant>,
+    /// Frames that were decoded but replaced before they could be shown.
+    pub dropped_frames: u64,
+    /// Time from the last decode request to its first frame arriving.
+    pub decode_latency: Option<Duration>,
+    /// Audio queued in the player but not yet consumed by the device.
+    pub audio_buffered: Duration,
+    decode_requested_at: Option<Instant>,
+}
+
+impl PlaybackStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that a frame was put on screen at `now`.
+    pub fn record_frame_displayed(&mut self, now: Instant) {
+        self.frame_times.push_back(now);
+        while let Some(&oldest) = self.frame_times.front() {
+            if now.duration_since(oldest) > FPS_WINDOW {
+                self.frame_times.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    pub fn record_frame_dropped(&mut self) {
+        self.dropped_frames += 1;
+    }
+
+    /// Mark the moment a decode (seek) request was sent.
+    pub fn record_decode_requested(&mut self, now: Instant) {
+        self.decode_requested_at = Some(now);
+    }
+
+    /// A frame arrived from the decoder. The first one after a request sets the latency.
+    pub fn record_frame_received(&mut self, now: Instant) {
+        if let Some(requested) = self.decode_requested_at.take() {
+            self.decode_latency = Some(now.duration_since(requested));
+        }
+    }
+
+    /// Frames shown per second over the last window.
+    pub fn fps(&self) -> f64 {
+        let (Some(first), Some(last)) = (self.frame_times.front(), self.frame_times.back()) else {
+            return 0.0;
+        };
+        let span = last.duration_since(*first).as_secs_f64();
+        if self.frame_times.len() < 2 || span <= 0.0 {
+            return 0.0;
+        }
+        (self.frame_times.len() - 1) as f64 / span
+    }
+
+    pub fn audio_buffer_low(&self) -> bool {
+        self.audio_buffered < AUDIO_BUFFER_LOW
+    }
+
+    /// Clear counters, e.g. when playback starts.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// One-line summary for the overlay.
+    pub fn summary(&self, is_playing: bool) -> String {
+        let latency = match self.decode_latency {
+            Some(d) => format!("{} ms", d.as_millis()),
+            None => "-".to_string(),
+        };
+        let audio_health = if is_playing && self.audio_buffer_low() { " (LOW)" } else { "" };
+        format!(
+            "{:.1} fps | dropped {} | decode {} | audio {} ms{}",
+            self.fps(),
+            self.dropped_frames,
+            latency,
+            self.audio_buffered.as_millis(),
+            audio_health,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fps_from_frame_times() {
+        let mut stats = PlaybackStats::new();
+        let start = Instant::now();
+        for i in 0..31 {
+            stats.record_frame_displayed(start + Duration::from_millis(i * 1000 / 30));
+        }
+        let fps = stats.fps();
+        assert!((fps - 30.0).abs() < 1.0, "fps {fps}");
+    }
+
+    #[test]
+    fn test_old_frames_leave_window() {
+        let mut stats = PlaybackStats::new();
+        let start = Instant::now();
+        stats.record_frame_displayed(start);
+        stats.record_frame_displayed(start + Duration::from_secs(5));
+        assert_eq!(stats.fps(), 0.0);
+    }
+
+    #[test]
+    fn test_decode_latency_measured_once_per_request() {
+        let mut stats = PlaybackStats::new();
+        let start = Instant::now();
+        stats.record_decode_requested(start);
+        stats.record_frame_received(start + Duration::from_millis(40));
+        stats.record_frame_received(start + Duration::from_millis(90));
+        assert_eq!(stats.decode_latency, Some(Duration::from_millis(40)));
+    }
+
+    #[test]
+    fn test_summary_flags_low_audio() {
+        let mut stats = PlaybackStats::new();
+        stats.dropped_frames = 2;
+        stats.audio_buffered = Duration::from_millis(10);
+        let summary = stats.summary(true);
+        assert!(summary.contains("dropped 2"));
+        assert!(summary.contains("LOW"));
+        assert!(!stats.summary(false).contains("LOW"));
+    }
+}
@@ -0,0 +1,396 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use zeditor_core::media::{MediaAsset, MediaKind};
+use zeditor_core::project::{CanvasPreset, ProjectSettings};
+use zeditor_core::render_ranges;
+
+use crate::i18n::Language;
+use crate::library_filter::{LibrarySort, LibraryViewMode};
+use crate::memory::MemoryCaps;
+use crate::message::{PanelSplitter, PreferenceField};
+
+pub const MIN_LEFT_PANEL_WIDTH: f32 = 180.0;
+pub const MAX_LEFT_PANEL_WIDTH: f32 = 800.0;
+pub const MIN_INSPECTOR_WIDTH: f32 = 180.0;
+pub const MAX_INSPECTOR_WIDTH: f32 = 700.0;
+pub const MIN_PREVIEW_HEIGHT: f32 = 120.0;
+pub const MAX_PREVIEW_HEIGHT: f32 = 1000.0;
+
+pub const MIN_UI_SCALE: f32 = 0.75;
+pub const MAX_UI_SCALE: f32 = 3.0;
+/// Scales offered in the View menu.
+pub const UI_SCALE_PRESETS: [f32; 6] = [1.0, 1.25, 1.5, 1.75, 2.0, 2.5];
+
+/// How close (in ms) a dragged clip edge must come to a neighbour to snap to it.
+pub const DEFAULT_SNAP_THRESHOLD_MS: u64 = 200;
+pub const MAX_SNAP_THRESHOLD_MS: u64 = 5000;
+/// How long a still image runs when dropped on the timeline.
+pub const DEFAULT_STILL_DURATION_SECS: f64 = 5.0;
+pub const MAX_STILL_DURATION_SECS: f64 = 3600.0;
+/// Most parallel segments an export may be split into.
+pub const MAX_RENDER_SEGMENTS: usize = 16;
+pub const MAX_CANVAS_SIZE: u32 = 16384;
+pub const MAX_FPS: f64 = 240.0;
+
+/// Scaling filter for preview frames.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PreviewQuality {
+    /// Bilinear everywhere: paused frames come up faster but softer.
+    Draft,
+    /// Bilinear during playback, Lanczos for paused and scrubbed frames.
+    #[default]
+    Full,
+}
+
+/// Sizes of the resizable panels, in logical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PanelLayout {
+    pub left_panel_width: f32,
+    pub inspector_width: f32,
+    pub preview_height: f32,
+}
+
+impl Default for PanelLayout {
+    fn default() -> Self {
+        Self {
+            left_panel_width: 300.0,
+            inspector_width: 250.0,
+            preview_height: 300.0,
+        }
+    }
+}
+
+impl PanelLayout {
+    /// Current size controlled by `splitter`.
+    pub fn size(&self, splitter: PanelSplitter) -> f32 {
+        match splitter {
+            PanelSplitter::LeftPanel => self.left_panel_width,
+            PanelSplitter::Inspector => self.inspector_width,
+            PanelSplitter::Preview => self.preview_height,
+        }
+    }
+
+    /// Set the size controlled by `splitter`, clamped to its limits.
+    pub fn set_size(&mut self, splitter: PanelSplitter, size: f32) {
+        match splitter {
+            PanelSplitter::LeftPanel => {
+                self.left_panel_width = size.clamp(MIN_LEFT_PANEL_WIDTH, MAX_LEFT_PANEL_WIDTH);
+            }
+            PanelSplitter::Inspector => {
+                self.inspector_width = size.clamp(MIN_INSPECTOR_WIDTH, MAX_INSPECTOR_WIDTH);
+            }
+            PanelSplitter::Preview => {
+                self.preview_height = size.clamp(MIN_PREVIEW_HEIGHT, MAX_PREVIEW_HEIGHT);
+            }
+        }
+    }
+
+    /// Clamp every size to its limits, e.g. after loading a hand-edited file.
+    pub fn clamped(mut self) -> Self {
+        for splitter in [PanelSplitter::LeftPanel, PanelSplitter::Inspector, PanelSplitter::Preview] {
+            self.set_size(splitter, self.size(splitter));
+        }
+        self
+    }
+}
+
+/// Per-user UI preferences, stored as JSON in the user config directory.
+/// Unknown or missing fields fall back to defaults so older files keep loading.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiPreferences {
+    pub library_sort: LibrarySort,
+    pub library_sort_descending: bool,
+    pub library_view: LibraryViewMode,
+    pub layout: PanelLayout,
+    /// Application-wide scale factor applied on top of the system DPI scale
+    /// (text, thumbnails and the timeline canvas all scale with it).
+    pub ui_scale: f32,
+    pub language: Language,
+    /// Memory caps per cache, applied at startup.
+    pub memory_caps: MemoryCaps,
+    /// Canvas size and frame rate given to new projects. Each project keeps
+    /// its own copy in `ProjectSettings`, so changing this leaves existing
+    /// projects alone.
+    pub new_project: ProjectSettings,
+    /// Minutes between automatic saves of a project with unsaved edits; 0 turns autosave off.
+    pub autosave_minutes: u32,
+    /// Media cache (thumbnails) location; `None` uses the platform cache directory.
+    pub cache_dir: Option<PathBuf>,
+    pub preview_quality: PreviewQuality,
+    pub snap_threshold_ms: u64,
+    /// Dragged and resized clip edges snap to markers.
+    pub snap_to_markers: bool,
+    /// Dragged and resized clip edges snap to the playhead.
+    pub snap_to_playhead: bool,
+    /// A trimmed audio clip's end moves to the nearest silence or zero
+    /// crossing, so the cut doesn't click. Alt skips it for one trim.
+    pub snap_audio_trims: bool,
+    /// `.cube` LUT applied to preview frames only, e.g. to view log footage
+    /// or emulate a target display. Exports never use it.
+    pub display_lut: Option<PathBuf>,
+    /// Render every frame with effects to the media cache while playback is
+    /// stopped, so those stretches play back from disk. Preview only: exports
+    /// don't reuse these frames yet.
+    pub background_render: bool,
+    /// Resizing, removing or retiming a clip shifts later clips on its tracks.
+    pub ripple_edits: bool,
+    /// File name pattern for batch-exported render ranges (see
+    /// `render_ranges::file_name`).
+    pub render_file_pattern: String,
+    /// Length of the clip a still image gets when added to the timeline.
+    pub still_duration_secs: f64,
+    /// Exports are split into this many parts rendered in parallel, then
+    /// joined; 1 renders in one pass.
+    pub render_segments: usize,
+    /// Renders also write each audio track as a WAV stem beside the output.
+    pub export_stems: bool,
+}
+
+impl Default for UiPreferences {
+    fn default() -> Self {
+        Self {
+            library_sort: LibrarySort::default(),
+            library_sort_descending: false,
+            library_view: LibraryViewMode::default(),
+            layout: PanelLayout::default(),
+            ui_scale: 1.0,
+            language: Language::default(),
+            memory_caps: MemoryCaps::default(),
+            new_project: ProjectSettings::default(),
+            autosave_minutes: 0,
+            cache_dir: None,
+            preview_quality: PreviewQuality::default(),
+            snap_threshold_ms: DEFAULT_SNAP_THRESHOLD_MS,
+            snap_to_markers: true,
+            snap_to_playhead: true,
+            snap_audio_trims: true,
+            display_lut: None,
+            background_render: false,
+            ripple_edits: false,
+            render_file_pattern: render_ranges::DEFAULT_FILE_PATTERN.to_string(),
+            still_duration_secs: DEFAULT_STILL_DURATION_SECS,
+            render_segments: 1,
+            export_stems: false,
+        }
+    }
+}
+
+/// Clamp a UI scale to its limits; non-finite values reset to 1.0.
+pub fn clamp_ui_scale(scale: f32) -> f32 {
+    if scale.is_finite() {
+        scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE)
+    } else {
+        1.0
+    }
+}
+
+impl UiPreferences {
+    /// `$XDG_CONFIG_HOME/zeditor/preferences.json`, falling back to
+    /// `~/.config` (or `%APPDATA%` on Windows).
+    pub fn default_path() -> Option<PathBuf> {
+        let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(config_dir.join("zeditor").join("preferences.json"))
+    }
+
+    /// Load preferences; a missing or unreadable file yields the defaults.
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<Self>(&json).ok())
+            .map(Self::sanitized)
+            .unwrap_or_default()
+    }
+
+    /// Out-of-range values clamped or reset to their defaults, e.g. after
+    /// reading a hand-edited file.
+    fn sanitized(mut self) -> Self {
+        self.layout = self.layout.clamped();
+        self.ui_scale = clamp_ui_scale(self.ui_scale);
+        self.snap_threshold_ms = self.snap_threshold_ms.min(MAX_SNAP_THRESHOLD_MS);
+        if !valid_still_duration(self.still_duration_secs) {
+            self.still_duration_secs = DEFAULT_STILL_DURATION_SECS;
+        }
+        self.render_segments = self.render_segments.clamp(1, MAX_RENDER_SEGMENTS);
+        if !valid_project_settings(&self.new_project) {
+            self.new_project = ProjectSettings::default();
+        }
+        self
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, json)
+    }
+
+    /// Snap distance for clip moves and trims.
+    pub fn snap_threshold(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.snap_threshold_ms)
+    }
+
+    /// How much of `asset` a drop places on the timeline: all of it, except
+    /// stills, which have no length of their own and run for the preferred
+    /// still duration.
+    pub fn placed_duration(&self, asset: &MediaAsset) -> std::time::Duration {
+        if asset.kind() == MediaKind::Image {
+            std::time::Duration::from_secs_f64(self.still_duration_secs)
+        } else {
+            asset.duration
+        }
+    }
+}
+
+pub(crate) fn valid_still_duration(secs: f64) -> bool {
+    secs > 0.0 && secs <= MAX_STILL_DURATION_SECS
+}
+
+fn valid_project_settings(settings: &ProjectSettings) -> bool {
+    (1..=MAX_CANVAS_SIZE).contains(&settings.canvas_width)
+        && (1..=MAX_CANVAS_SIZE).contains(&settings.canvas_height)
+        && settings.fps > 0.0
+        && settings.fps <= MAX_FPS
+}
+
+/// Text fields of the Preferences dialog. Edits stay here until applied, and
+/// are only applied once every field parses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreferencesForm {
+    pub canvas_width: String,
+    pub canvas_height: String,
+    pub fps: String,
+    pub autosave_minutes: String,
+    pub snap_threshold_ms: String,
+    pub still_duration_secs: String,
+    pub render_segments: String,
+    /// Empty for the platform default.
+    pub cache_dir: String,
+    /// Empty for no display LUT.
+    pub display_lut: String,
+    pub preview_quality: PreviewQuality,
+}
+
+impl PreferencesForm {
+    pub fn new(prefs: &UiPreferences) -> Self {
+        Self {
+            canvas_width: prefs.new_project.canvas_width.to_string(),
+            canvas_height: prefs.new_project.canvas_height.to_string(),
+            fps: prefs.new_project.fps.to_string(),
+            autosave_minutes: prefs.autosave_minutes.to_string(),
+            snap_threshold_ms: prefs.snap_threshold_ms.to_string(),
+            still_duration_secs: prefs.still_duration_secs.to_string(),
+            render_segments: prefs.render_segments.to_string(),
+            cache_dir: prefs
+                .cache_dir
+                .as_ref()
+                .map(|dir| dir.display().to_string())
+                .unwrap_or_default(),
+            display_lut: prefs
+                .display_lut
+                .as_ref()
+                .map(|path| path.display().to_string())
+                .unwrap_or_default(),
+            preview_quality: prefs.preview_quality,
+        }
+    }
+
+    pub fn field_mut(&mut self, field: PreferenceField) -> &mut String {
+        match field {
+            PreferenceField::CanvasWidth => &mut self.canvas_width,
+            PreferenceField::CanvasHeight => &mut self.canvas_height,
+            PreferenceField::Fps => &mut self.fps,
+            PreferenceField::AutosaveMinutes => &mut self.autosave_minutes,
+            PreferenceField::SnapThresholdMs => &mut self.snap_threshold_ms,
+            PreferenceField::StillDurationSecs => &mut self.still_duration_secs,
+            PreferenceField::RenderSegments => &mut self.render_segments,
+            PreferenceField::CacheDir => &mut self.cache_dir,
+            PreferenceField::DisplayLut => &mut self.display_lut,
+        }
+    }
+
+    /// Reshape the canvas fields to `preset`, keeping their short side (or
+    /// the default canvas's, while they don't parse).
+    pub fn set_canvas_preset(&mut self, preset: CanvasPreset) {
+        let settings = match (self.canvas_width.trim().parse(), self.canvas_height.trim().parse()) {
+            (Ok(canvas_width), Ok(canvas_height)) => ProjectSettings {
+                canvas_width,
+                canvas_height,
+                ..ProjectSettings::default()
+            },
+            _ => ProjectSettings::default(),
+        }
+        .with_canvas_preset(preset);
+        self.canvas_width = settings.canvas_width.to_string();
+        self.canvas_height = settings.canvas_height.to_string();
+    }
+
+    /// Preferences with the form's values applied on top of `prefs`, or the
+    /// first field whose value doesn't parse or is out of range.
+    pub fn apply(&self, prefs: &UiPreferences) -> Result<UiPreferences, PreferenceField> {
+        let canvas_size = |value: &str, field| {
+            value
+                .trim()
+                .parse::<u32>()
+                .ok()
+                .filter(|size| (1..=MAX_CANVAS_SIZE).contains(size))
+                .ok_or(field)
+        };
+        let new_project = ProjectSettings {
+            canvas_width: canvas_size(&self.canvas_width, PreferenceField::CanvasWidth)?,
+            canvas_height: canvas_size(&self.canvas_height, PreferenceField::CanvasHeight)?,
+            fps: self
+                .fps
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|fps| *fps > 0.0 && *fps <= MAX_FPS)
+                .ok_or(PreferenceField::Fps)?,
+        };
+        let autosave_minutes = self
+            .autosave_minutes
+            .trim()
+            .parse::<u32>()
+            .map_err(|_| PreferenceField::AutosaveMinutes)?;
+        let snap_threshold_ms = self
+            .snap_threshold_ms
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .filter(|ms| *ms <= MAX_SNAP_THRESHOLD_MS)
+            .ok_or(PreferenceField::SnapThresholdMs)?;
+        let still_duration_secs = self
+            .still_duration_secs
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|secs| valid_still_duration(*secs))
+            .ok_or(PreferenceField::StillDurationSecs)?;
+        let render_segments = self
+            .render_segments
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|segments| (1..=MAX_RENDER_SEGMENTS).contains(segments))
+            .ok_or(PreferenceField::RenderSegments)?;
+        let cache_dir = self.cache_dir.trim();
+        let display_lut = self.display_lut.trim();
+        Ok(UiPreferences {
+            new_project,
+            autosave_minutes,
+            snap_threshold_ms,
+            still_duration_secs,
+            render_segments,
+            cache_dir: (!cache_dir.is_empty()).then(|| PathBuf::from(cache_dir)),
+            display_lut: (!display_lut.is_empty()).then(|| PathBuf::from(display_lut)),
+            preview_quality: self.preview_quality,
+            ..prefs.clone()
+        })
+    }
+}
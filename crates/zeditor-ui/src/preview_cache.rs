@@ -0,0 +1,288 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use zeditor_core::effects::ParameterValue;
+use zeditor_core::project::Project;
+use zeditor_core::timeline::{TimelinePosition, TrackType};
+
+use crate::memory::{self, Evictable};
+
+/// Default number of composited preview frames kept for scrubbing.
+pub const PREVIEW_CACHE_CAPACITY: usize = 64;
+
+/// Cache key: the frame index at project fps plus a hash of everything that
+/// contributes to the composited image at that time (clips, source offsets, effects).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PreviewCacheKey {
+    pub frame: i64,
+    pub composition: u64,
+}
+
+impl PreviewCacheKey {
+    /// Build the key for the composited preview at `pos`.
+    pub fn at(project: &Project, pos: TimelinePosition) -> Self {
+        let fps = if project.settings.fps > 0.0 { project.settings.fps } else { 30.0 };
+        Self {
+            frame: (pos.as_secs_f64() * fps).round() as i64,
+            composition: composition_hash(project, pos),
+        }
+    }
+
+    /// The key for the same frame previewed with every effect bypassed.
+    pub fn without_effects(self) -> Self {
+        let mut hasher = DefaultHasher::new();
+        self.composition.hash(&mut hasher);
+        "effects bypassed".hash(&mut hasher);
+        Self { frame: self.frame, composition: hasher.finish() }
+    }
+}
+
+/// Hash the composition at `pos`: every video clip under the playhead (bottom to top)
+/// with its placement, source offset and effect parameters, plus the canvas size.
+/// Any edit that changes the frame at `pos` changes this hash.
+pub fn composition_hash(project: &Project, pos: TimelinePosition) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    project.settings.canvas_width.hash(&mut hasher);
+    project.settings.canvas_height.hash(&mut hasher);
+    for track in project.timeline.tracks.iter().rev() {
+        if track.track_type != TrackType::Video || track.hidden {
+            continue;
+        }
+        if let Some(clip) = track.clip_at(pos) {
+            clip.id.hash(&mut hasher);
+            clip.asset_id.hash(&mut hasher);
+            clip.timeline_range.start.as_secs_f64().to_bits().hash(&mut hasher);
+            clip.source_range.start.as_secs_f64().to_bits().hash(&mut hasher);
+            clip.canvas_fit.hash(&mut hasher);
+            for key in &clip.pan.keys {
+                key.source_secs.to_bits().hash(&mut hasher);
+                key.center_x.to_bits().hash(&mut hasher);
+            }
+            for effect in &clip.effects {
+                effect.id.hash(&mut hasher);
+                effect.effect_type.hash(&mut hasher);
+                for (name, value) in &effect.parameters {
+                    name.hash(&mut hasher);
+                    match value {
+                        ParameterValue::Float(f) => f.to_bits().hash(&mut hasher),
+                    }
+                }
+            }
+        }
+    }
+    hasher.finish()
+}
+
+/// LRU cache of composited preview frames so scrubbing back over a region
+/// shows frames instantly instead of re-decoding.
+pub struct PreviewFrameCache {
+    capacity: usize,
+    /// Frames with their size in bytes.
+    entries: HashMap<PreviewCacheKey, (iced::widget::image::Handle, usize)>,
+    /// Keys from least to most recently used.
+    order: VecDeque<PreviewCacheKey>,
+    bytes: usize,
+}
+
+impl PreviewFrameCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            bytes: 0,
+        }
+    }
+
+    pub fn get(&mut self, key: &PreviewCacheKey) -> Option<iced::widget::image::Handle> {
+        let handle = self.entries.get(key)?.0.clone();
+        self.touch(key);
+        Some(handle)
+    }
+
+    /// Whether `key` is cached, without marking it as recently used.
+    pub fn contains(&self, key: &PreviewCacheKey) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Insert a frame. Entries for the same frame index with a different
+    /// composition are stale (the clips or effects there changed) and are dropped.
+    pub fn insert(&mut self, key: PreviewCacheKey, handle: iced::widget::image::Handle) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.invalidate_where(|k| k.frame == key.frame && k.composition != key.composition);
+        let size = memory::handle_bytes(&handle);
+        self.bytes += size;
+        if let Some((_, old_size)) = self.entries.insert(key, (handle, size)) {
+            self.bytes -= old_size;
+            self.touch(&key);
+        } else {
+            self.order.push_back(key);
+        }
+        while self.entries.len() > self.capacity {
+            if !self.evict_oldest() {
+                break;
+            }
+        }
+    }
+
+    /// Remove every entry whose key matches `predicate`.
+    pub fn invalidate_where(&mut self, predicate: impl Fn(&PreviewCacheKey) -> bool) {
+        let mut freed = 0;
+        self.entries.retain(|k, (_, size)| {
+            let keep = !predicate(k);
+            if !keep {
+                freed += *size;
+            }
+            keep
+        });
+        self.bytes -= freed;
+        self.order.retain(|k| !predicate(k));
+    }
+
+    /// Remove entries for frame indices in `start..=end`.
+    pub fn invalidate_frames(&mut self, start: i64, end: i64) {
+        self.invalidate_where(|k| (start..=end).contains(&k.frame));
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+        self.bytes = 0;
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Drop the least recently used frame. Returns false if the cache was empty.
+    fn evict_oldest(&mut self) -> bool {
+        let Some(oldest) = self.order.pop_front() else {
+            return false;
+        };
+        if let Some((_, size)) = self.entries.remove(&oldest) {
+            self.bytes -= size;
+        }
+        true
+    }
+
+    fn touch(&mut self, key: &PreviewCacheKey) {
+        if let Some(idx) = self.order.iter().position(|k| k == key) {
+            self.order.remove(idx);
+        }
+        self.order.push_back(*key);
+    }
+}
+
+impl Evictable for PreviewFrameCache {
+    fn memory_bytes(&self) -> usize {
+        self.bytes
+    }
+
+    fn evict_to(&mut self, max_bytes: usize) -> usize {
+        let before = self.bytes;
+        while self.bytes > max_bytes && self.evict_oldest() {}
+        before - self.bytes
+    }
+}
+
+impl Default for PreviewFrameCache {
+    fn default() -> Self {
+        Self::new(PREVIEW_CACHE_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn handle() -> iced::widget::image::Handle {
+        iced::widget::image::Handle::from_rgba(1, 1, vec![0, 0, 0, 255])
+    }
+
+    fn key(frame: i64, composition: u64) -> PreviewCacheKey {
+        PreviewCacheKey { frame, composition }
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut cache = PreviewFrameCache::new(2);
+        cache.insert(key(1, 0), handle());
+        cache.insert(key(2, 0), handle());
+        // Touch frame 1 so frame 2 becomes the oldest.
+        assert!(cache.get(&key(1, 0)).is_some());
+        cache.insert(key(3, 0), handle());
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&key(1, 0)).is_some());
+        assert!(cache.get(&key(2, 0)).is_none());
+        assert!(cache.get(&key(3, 0)).is_some());
+    }
+
+    #[test]
+    fn test_new_composition_replaces_stale_frame() {
+        let mut cache = PreviewFrameCache::new(8);
+        cache.insert(key(10, 1), handle());
+        cache.insert(key(10, 2), handle());
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get(&key(10, 1)).is_none());
+    }
+
+    #[test]
+    fn test_invalidate_frames_range() {
+        let mut cache = PreviewFrameCache::new(8);
+        for frame in 0..5 {
+            cache.insert(key(frame, 0), handle());
+        }
+        cache.invalidate_frames(1, 3);
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&key(0, 0)).is_some());
+        assert!(cache.get(&key(4, 0)).is_some());
+    }
+
+    #[test]
+    fn test_evict_to_tracks_bytes() {
+        let mut cache = PreviewFrameCache::new(8);
+        for frame in 0..4 {
+            cache.insert(key(frame, 0), handle());
+        }
+        assert_eq!(cache.memory_bytes(), 16);
+
+        assert_eq!(cache.evict_to(8), 8);
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&key(0, 0)).is_none());
+        assert!(cache.get(&key(3, 0)).is_some());
+
+        cache.invalidate_frames(3, 3);
+        assert_eq!(cache.memory_bytes(), 4);
+    }
+
+    #[test]
+    fn test_composition_hash_changes_with_effects() {
+        use zeditor_core::effects::{EffectInstance, EffectType};
+        use zeditor_core::timeline::{Clip, TimeRange};
+
+        let mut project = Project::new("Hash");
+        let range = TimeRange::new(TimelinePosition::zero(), TimelinePosition::from_secs_f64(5.0)).unwrap();
+        let clip = Clip::new(uuid::Uuid::new_v4(), TimelinePosition::zero(), range);
+        let clip_id = clip.id;
+        project.timeline.add_clip(0, clip).unwrap();
+
+        let pos = TimelinePosition::from_secs_f64(1.0);
+        let before = composition_hash(&project, pos);
+        assert_eq!(before, composition_hash(&project, pos));
+
+        let clip = project.timeline.tracks[0].get_clip_mut(clip_id).unwrap();
+        clip.effects.push(EffectInstance::new(EffectType::Grayscale));
+        assert_ne!(before, composition_hash(&project, pos));
+
+        // The bypassed (A/B) preview of a frame is cached under its own key
+        let key = PreviewCacheKey::at(&project, pos);
+        assert_eq!(key.without_effects().frame, key.frame);
+        assert_ne!(key.without_effects(), key);
+    }
+}
@@ -0,0 +1,196 @@
+use std::time::Instant;
+
+use uuid::Uuid;
+use zeditor_core::timeline::{TimeRange, TimelinePosition};
+
+/// State of the source monitor: a second player that shows a single library
+/// asset in source time, independent of the timeline preview.
+#[derive(Debug, Clone)]
+pub struct SourceMonitor {
+    /// Asset currently loaded, if any.
+    pub asset_id: Option<Uuid>,
+    /// Playhead in source time.
+    pub position: TimelinePosition,
+    /// Length of the loaded asset.
+    pub duration: TimelinePosition,
+    pub in_point: Option<TimelinePosition>,
+    pub out_point: Option<TimelinePosition>,
+    pub is_playing: bool,
+    pub current_frame: Option<iced::widget::image::Handle>,
+    play_start_wall: Option<Instant>,
+    play_start_pos: TimelinePosition,
+}
+
+impl Default for SourceMonitor {
+    fn default() -> Self {
+        Self {
+            asset_id: None,
+            position: TimelinePosition::zero(),
+            duration: TimelinePosition::zero(),
+            in_point: None,
+            out_point: None,
+            is_playing: false,
+            current_frame: None,
+            play_start_wall: None,
+            play_start_pos: TimelinePosition::zero(),
+        }
+    }
+}
+
+impl SourceMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load an asset, resetting the playhead and marks.
+    pub fn load(&mut self, asset_id: Uuid, duration: TimelinePosition) {
+        *self = Self {
+            asset_id: Some(asset_id),
+            duration,
+            ..Self::default()
+        };
+    }
+
+    /// Unload the current asset.
+    pub fn close(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        self.asset_id.is_some()
+    }
+
+    /// Move the playhead, clamped to the asset. Restarts the play clock if playing.
+    pub fn seek(&mut self, pos: TimelinePosition, now: Instant) {
+        self.position = self.clamp(pos);
+        if self.is_playing {
+            self.play_start_wall = Some(now);
+            self.play_start_pos = self.position;
+        }
+    }
+
+    /// Start playing from the playhead. Playing at the end restarts from the beginning.
+    pub fn play(&mut self, now: Instant) {
+        if !self.is_loaded() {
+            return;
+        }
+        if self.position >= self.duration {
+            self.position = TimelinePosition::zero();
+        }
+        self.is_playing = true;
+        self.play_start_wall = Some(now);
+        self.play_start_pos = self.position;
+    }
+
+    pub fn pause(&mut self) {
+        self.is_playing = false;
+        self.play_start_wall = None;
+    }
+
+    /// Advance the playhead from the play clock. Pauses at the end of the asset.
+    /// Returns true if the playhead moved.
+    pub fn tick(&mut self, now: Instant) -> bool {
+        let Some(start_wall) = self.play_start_wall else {
+            return false;
+        };
+        let elapsed = now.saturating_duration_since(start_wall).as_secs_f64();
+        let new_pos = self.play_start_pos.as_secs_f64() + elapsed;
+        if new_pos >= self.duration.as_secs_f64() {
+            self.position = self.duration;
+            self.pause();
+        } else {
+            self.position = TimelinePosition::from_secs_f64(new_pos);
+        }
+        true
+    }
+
+    /// Mark the in point at the playhead. An out point before it is cleared.
+    pub fn mark_in(&mut self) {
+        self.in_point = Some(self.position);
+        if self.out_point.is_some_and(|out| out <= self.position) {
+            self.out_point = None;
+        }
+    }
+
+    /// Mark the out point at the playhead. An in point after it is cleared.
+    pub fn mark_out(&mut self) {
+        self.out_point = Some(self.position);
+        if self.in_point.is_some_and(|inp| inp >= self.position) {
+            self.in_point = None;
+        }
+    }
+
+    pub fn clear_marks(&mut self) {
+        self.in_point = None;
+        self.out_point = None;
+    }
+
+    /// The marked source range. Missing marks default to the start and end of
+    /// the asset. `None` if nothing is loaded or the range is empty.
+    pub fn marked_range(&self) -> Option<TimeRange> {
+        self.asset_id?;
+        let start = self.in_point.unwrap_or(TimelinePosition::zero());
+        let end = self.out_point.unwrap_or(self.duration);
+        TimeRange::new(start, end).ok()
+    }
+
+    fn clamp(&self, pos: TimelinePosition) -> TimelinePosition {
+        if pos > self.duration { self.duration } else { pos }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn secs(s: f64) -> TimelinePosition {
+        TimelinePosition::from_secs_f64(s)
+    }
+
+    fn loaded(duration: f64) -> SourceMonitor {
+        let mut monitor = SourceMonitor::new();
+        monitor.load(Uuid::new_v4(), secs(duration));
+        monitor
+    }
+
+    #[test]
+    fn test_marked_range_defaults_to_whole_asset() {
+        let monitor = loaded(10.0);
+        let range = monitor.marked_range().unwrap();
+        assert_eq!(range.start, TimelinePosition::zero());
+        assert_eq!(range.end, secs(10.0));
+        assert!(SourceMonitor::new().marked_range().is_none());
+    }
+
+    #[test]
+    fn test_mark_in_after_out_clears_out() {
+        let mut monitor = loaded(10.0);
+        monitor.seek(secs(4.0), Instant::now());
+        monitor.mark_out();
+        monitor.seek(secs(6.0), Instant::now());
+        monitor.mark_in();
+        assert_eq!(monitor.in_point, Some(secs(6.0)));
+        assert_eq!(monitor.out_point, None);
+    }
+
+    #[test]
+    fn test_tick_advances_and_stops_at_end() {
+        let mut monitor = loaded(2.0);
+        let start = Instant::now();
+        monitor.play(start);
+        assert!(monitor.tick(start + Duration::from_millis(500)));
+        assert_eq!(monitor.position, secs(0.5));
+        monitor.tick(start + Duration::from_secs(3));
+        assert_eq!(monitor.position, secs(2.0));
+        assert!(!monitor.is_playing);
+    }
+
+    #[test]
+    fn test_seek_clamps_to_duration() {
+        let mut monitor = loaded(5.0);
+        monitor.seek(secs(9.0), Instant::now());
+        assert_eq!(monitor.position, secs(5.0));
+    }
+}
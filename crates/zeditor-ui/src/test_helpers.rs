@@ -1,8 +1,9 @@
-use std::sync::mpsc;
+use std::sync::{Arc, mpsc};
 
 use uuid::Uuid;
 
-use crate::app::{App, DecodedAudio, DecodedFrame};
+use crate::app::{App, DecodedFrame};
+use crate::audio_ring::AudioRing;
 
 /// Public mirror of `DecodedFrame` for use in integration tests.
 pub struct TestFrame {
@@ -29,7 +30,7 @@ impl TestFrameSender {
     }
 }
 
-/// Public mirror of `DecodedAudio` for use in integration tests.
+/// Decoded audio for injecting into the app's audio ring in integration tests.
 pub struct TestAudio {
     pub samples: Vec<f32>,
     pub sample_rate: u32,
@@ -37,20 +38,18 @@ pub struct TestAudio {
     pub pts_secs: f64,
 }
 
-/// Wraps a `SyncSender<DecodedAudio>` so integration tests can inject audio frames.
+/// Pushes into the app's audio ring, as the audio decode thread would.
 pub struct TestAudioSender {
-    tx: mpsc::SyncSender<DecodedAudio>,
+    ring: Arc<AudioRing>,
 }
 
 impl TestAudioSender {
+    /// Push audio for the current epoch (i.e. the latest audio seek).
     pub fn send_audio(&self, audio: TestAudio) {
-        let decoded = DecodedAudio {
-            samples: audio.samples,
-            sample_rate: audio.sample_rate,
-            channels: audio.channels,
-            pts_secs: audio.pts_secs,
-        };
-        self.tx.send(decoded).expect("test audio channel send failed");
+        let epoch = self.ring.requested_epoch();
+        self.ring
+            .push(epoch, audio.pts_secs, audio.sample_rate, audio.channels, &audio.samples)
+            .expect("test audio push failed");
     }
 }
 
@@ -68,11 +67,37 @@ impl App {
     /// Returns the App, a video frame sender, and an audio sender.
     pub fn new_with_test_channels() -> (Self, TestFrameSender, TestAudioSender) {
         let (frame_tx, frame_rx) = mpsc::sync_channel::<DecodedFrame>(4);
-        let (audio_tx, audio_rx) = mpsc::sync_channel::<DecodedAudio>(4);
         let mut app = Self::default();
         app.decode_rx = Some(frame_rx);
-        app.audio_decode_rx = Some(audio_rx);
-        (app, TestFrameSender { tx: frame_tx }, TestAudioSender { tx: audio_tx })
+        let audio_sender = TestAudioSender { ring: Arc::clone(&app.audio_ring) };
+        (app, TestFrameSender { tx: frame_tx }, audio_sender)
+    }
+
+    /// Wire the source monitor to a test channel and return a sender for its frames.
+    pub fn attach_source_test_channel(&mut self) -> TestFrameSender {
+        let (frame_tx, frame_rx) = mpsc::sync_channel::<DecodedFrame>(4);
+        self.source_decode_rx = Some(frame_rx);
+        TestFrameSender { tx: frame_tx }
+    }
+
+    /// Wire the decode error channel to a test sender, as the decode workers would report errors.
+    pub fn attach_decode_error_channel(&mut self) -> mpsc::Sender<String> {
+        let (error_tx, error_rx) = mpsc::channel::<String>();
+        self.decode_error_rx = Some(error_rx);
+        error_tx
+    }
+
+    /// Wire the audio level channel to a test sender, as the audio decode
+    /// worker would report per-track peaks.
+    pub fn attach_audio_levels_channel(&mut self) -> mpsc::Sender<crate::audio_meters::AudioLevels> {
+        let (levels_tx, levels_rx) = mpsc::channel();
+        self.audio_levels_rx = Some(levels_rx);
+        levels_tx
+    }
+
+    /// Epoch the audio ring is expecting, which audio level blocks must carry.
+    pub fn audio_epoch(&self) -> u64 {
+        self.audio_ring.requested_epoch()
     }
 
     /// Set the decode time offset (source PTS + offset = timeline time).
@@ -90,6 +115,40 @@ impl App {
         self.decode_clip_id
     }
 
+    /// Clips the decode worker was last asked to preload ahead of a cut.
+    pub fn preloaded_clip_ids(&self) -> &[Uuid] {
+        &self.preloaded_clip_ids
+    }
+
+    /// Whether playback is currently served from the pre-render cache.
+    pub fn serving_prerender(&self) -> bool {
+        self.serving_prerender.is_some()
+    }
+
+    /// Use a media cache in `dir` (tests have none by default).
+    pub fn set_media_cache_dir(&mut self, dir: std::path::PathBuf) {
+        self.media_cache = Some(crate::media_cache::MediaCache::new(dir));
+    }
+
+    /// Block until the background project save (if any) has finished and apply
+    /// its result, as the next tick would.
+    pub fn wait_for_save(&mut self) {
+        if let Some(save) = self.pending_save.take() {
+            let result = save.rx.recv().expect("save thread dropped its result");
+            self.finish_save(save.path, &save.timeline, &save.library, result);
+        }
+    }
+
+    /// Latest timeline decode generation; bumped by every seek and stop.
+    pub fn decode_generation(&self) -> u64 {
+        self.decode_generation.load(std::sync::atomic::Ordering::Acquire)
+    }
+
+    /// Audio written to the ring for the current seek and not yet played.
+    pub fn audio_buffered(&self) -> std::time::Duration {
+        self.audio_ring.buffered()
+    }
+
     /// Get the audio clip ID currently being decoded.
     pub fn audio_decode_clip_id(&self) -> Option<Uuid> {
         self.audio_decode_clip_id
@@ -0,0 +1,192 @@
+use std::path::PathBuf;
+
+use uuid::Uuid;
+use zeditor_core::media::{MediaKind, SourceLibrary};
+use zeditor_core::timeline::{Clip, Timeline, TimelinePosition};
+
+/// Largest size the two-up frames are decoded at.
+pub const TRIM_FRAME_MAX_WIDTH: u32 = 480;
+pub const TRIM_FRAME_MAX_HEIGHT: u32 = 270;
+
+/// A source frame to decode for one side of the trim preview.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrimFrameRequest {
+    pub path: PathBuf,
+    pub source_secs: f64,
+}
+
+/// The frames that meet at a dragged clip end. A side is `None` when there
+/// is no clip there or it has no picture.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrimFrames {
+    /// Last frame the dragged clip keeps.
+    pub outgoing: Option<TrimFrameRequest>,
+    /// Frame of the next clip on the track that plays from the edge.
+    pub incoming: Option<TrimFrameRequest>,
+}
+
+/// Work out which frames end up adjacent when the end of `clip_id` on track
+/// `track_index` is dragged to `edge_secs` on a timeline at `fps`.
+pub fn trim_frames(
+    timeline: &Timeline,
+    library: &SourceLibrary,
+    track_index: usize,
+    clip_id: Uuid,
+    edge_secs: f64,
+    fps: f64,
+) -> Option<TrimFrames> {
+    let track = timeline.track(track_index).ok()?;
+    let clip = track.get_clip(clip_id)?;
+    let request = |clip: &Clip, secs: f64| {
+        let asset = library.get(clip.asset_id).filter(|a| a.kind() != MediaKind::Audio)?;
+        let source_secs = clip.source_secs_at(TimelinePosition::from_secs_f64(secs.max(0.0)));
+        Some(TrimFrameRequest {
+            path: asset.path.clone(),
+            source_secs: source_secs.max(clip.source_range.start.as_secs_f64()),
+        })
+    };
+
+    let frame_secs = if fps > 0.0 { 1.0 / fps } else { 0.0 };
+    let clip_start = clip.timeline_range.start.as_secs_f64();
+    let outgoing = request(clip, (edge_secs - frame_secs).max(clip_start));
+    // Clips the edge runs into are trimmed back to it, so the next clip is
+    // the first after the dragged one that still plays past the edge
+    let incoming = track
+        .clips
+        .iter()
+        .filter(|c| c.id != clip_id && c.timeline_range.start.as_secs_f64() >= clip_start)
+        .filter(|c| c.timeline_range.end.as_secs_f64() > edge_secs)
+        .min_by(|a, b| a.timeline_range.start.cmp(&b.timeline_range.start))
+        .and_then(|next| request(next, edge_secs.max(next.timeline_range.start.as_secs_f64())));
+    Some(TrimFrames { outgoing, incoming })
+}
+
+/// Two-up preview shown in the viewport while a clip end is dragged: the
+/// outgoing frame beside the incoming one. One decode runs at a time; when
+/// it finishes, the next starts if the edge has moved on meanwhile.
+#[derive(Debug, Clone)]
+pub struct TrimTwoUp {
+    pub track_index: usize,
+    pub clip_id: Uuid,
+    /// Timeline time of the dragged edge, on the frame grid.
+    pub edge_secs: f64,
+    /// Edge the decode in flight is for, if any.
+    pub loading: Option<f64>,
+    pub outgoing: Option<iced::widget::image::Handle>,
+    pub incoming: Option<iced::widget::image::Handle>,
+}
+
+impl TrimTwoUp {
+    pub fn new(track_index: usize, clip_id: Uuid) -> Self {
+        Self {
+            track_index,
+            clip_id,
+            edge_secs: f64::NAN,
+            loading: None,
+            outgoing: None,
+            incoming: None,
+        }
+    }
+
+    /// Move the edge. Returns the edge to start decoding now: only when it
+    /// changed and no decode is in flight.
+    pub fn move_edge(&mut self, edge_secs: f64) -> Option<f64> {
+        if edge_secs == self.edge_secs {
+            return None;
+        }
+        self.edge_secs = edge_secs;
+        if self.loading.is_some() {
+            return None;
+        }
+        self.loading = Some(edge_secs);
+        Some(edge_secs)
+    }
+
+    /// Show the frames decoded for the edge in flight. Returns the next edge
+    /// to decode if it has moved since.
+    pub fn finish(
+        &mut self,
+        outgoing: Option<iced::widget::image::Handle>,
+        incoming: Option<iced::widget::image::Handle>,
+    ) -> Option<f64> {
+        let loaded = self.loading.take()?;
+        self.outgoing = outgoing;
+        self.incoming = incoming;
+        if loaded == self.edge_secs {
+            return None;
+        }
+        self.loading = Some(self.edge_secs);
+        Some(self.edge_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use zeditor_core::media::MediaAsset;
+    use zeditor_core::timeline::TimeRange;
+
+    fn secs(s: f64) -> TimelinePosition {
+        TimelinePosition::from_secs_f64(s)
+    }
+
+    fn setup() -> (Timeline, SourceLibrary, Uuid, Uuid) {
+        let mut library = SourceLibrary::new();
+        let asset = MediaAsset::new(
+            "clip".into(),
+            PathBuf::from("/tmp/clip.mp4"),
+            Duration::from_secs(20),
+            320,
+            240,
+            30.0,
+            false,
+        );
+        let asset_id = asset.id;
+        library.import(asset);
+
+        let mut timeline = Timeline::new();
+        timeline.add_track("Video 1", zeditor_core::timeline::TrackType::Video);
+        let first = Clip::new(asset_id, secs(0.0), TimeRange::new(secs(2.0), secs(6.0)).unwrap());
+        let second = Clip::new(asset_id, secs(4.0), TimeRange::new(secs(10.0), secs(14.0)).unwrap());
+        let (first_id, second_id) = (first.id, second.id);
+        timeline.add_clip(0, first).unwrap();
+        timeline.add_clip(0, second).unwrap();
+        (timeline, library, first_id, second_id)
+    }
+
+    #[test]
+    fn test_trim_frames_either_side_of_edge() {
+        let (timeline, library, first, second) = setup();
+
+        // Shortened to 3s: the frame at 2.9s (4.9s into the source) is
+        // followed by the second clip's first frame
+        let frames = trim_frames(&timeline, &library, 0, first, 3.0, 10.0).unwrap();
+        assert!((frames.outgoing.unwrap().source_secs - 4.9).abs() < 1e-6);
+        assert!((frames.incoming.unwrap().source_secs - 10.0).abs() < 1e-6);
+
+        // Extended into the second clip, which plays on from the edge
+        let frames = trim_frames(&timeline, &library, 0, first, 5.0, 10.0).unwrap();
+        assert!((frames.outgoing.unwrap().source_secs - 6.9).abs() < 1e-6);
+        assert!((frames.incoming.unwrap().source_secs - 11.0).abs() < 1e-6);
+
+        // The last clip has nothing after it
+        let frames = trim_frames(&timeline, &library, 0, second, 7.0, 10.0).unwrap();
+        assert!(frames.incoming.is_none());
+    }
+
+    #[test]
+    fn test_two_up_coalesces_edge_moves() {
+        let mut two_up = TrimTwoUp::new(0, Uuid::new_v4());
+        assert_eq!(two_up.move_edge(1.0), Some(1.0));
+        // Moves while decoding only record the latest edge
+        assert_eq!(two_up.move_edge(1.5), None);
+        assert_eq!(two_up.move_edge(2.0), None);
+        assert_eq!(two_up.finish(None, None), Some(2.0));
+        assert_eq!(two_up.finish(None, None), None);
+        assert_eq!(two_up.loading, None);
+        // Same edge again: nothing to do
+        assert_eq!(two_up.move_edge(2.0), None);
+    }
+}
@@ -0,0 +1,70 @@
+//! Pieces every modal dialog is built from: the card, its buttons and the
+//! overlay that floats it above the editor. Menus share the click-off layer.
+
+use iced::widget::{button, center, container, mouse_area, opaque, stack, text, Button, Container};
+use iced::{Background, Border, Color, Element, Length};
+
+use crate::message::Message;
+
+/// Fill of a dialog's main action.
+pub const PRIMARY: Color = Color::from_rgb(0.25, 0.45, 0.75);
+/// Fill of Cancel and other secondary actions.
+pub const SECONDARY: Color = Color::from_rgb(0.3, 0.3, 0.33);
+/// Fill of an action that removes or overwrites something.
+pub const DESTRUCTIVE: Color = Color::from_rgb(0.8, 0.2, 0.2);
+
+/// A dialog button: a white label on a rounded `color` fill.
+pub fn dialog_button<'a>(label: String, message: Message, color: Color) -> Button<'a, Message> {
+    button(text(label).size(14).color(Color::WHITE))
+        .on_press(message)
+        .padding([6, 16])
+        .style(move |_theme, _status| button::Style {
+            background: Some(Background::Color(color)),
+            text_color: Color::WHITE,
+            border: Border {
+                radius: 4.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+}
+
+/// The panel a dialog's content sits on, `width` wide.
+pub fn dialog_card<'a>(content: impl Into<Element<'a, Message>>, width: f32) -> Container<'a, Message> {
+    container(content)
+        .padding(20)
+        .width(width)
+        .style(|_theme| container::Style {
+            background: Some(Background::Color(Color::from_rgb(0.22, 0.22, 0.25))),
+            border: Border {
+                color: Color::from_rgb(0.4, 0.4, 0.45),
+                width: 1.0,
+                radius: 8.0.into(),
+            },
+            ..Default::default()
+        })
+}
+
+/// A transparent layer over the whole window that sends `message` when
+/// clicked, for closing whatever floats above it.
+pub fn click_off<'a>(message: Message) -> Element<'a, Message> {
+    mouse_area(container("").width(Length::Fill).height(Length::Fill))
+        .on_press(message)
+        .into()
+}
+
+/// `card` centered over `base`, which no longer takes input. Clicking
+/// beside the card sends `on_dismiss`; without one the dialog can't be
+/// clicked away.
+pub fn dialog_overlay<'a>(
+    base: Element<'a, Message>,
+    card: impl Into<Element<'a, Message>>,
+    on_dismiss: Option<Message>,
+) -> Element<'a, Message> {
+    let centered = center(card).width(Length::Fill).height(Length::Fill);
+    let mut layers = stack![base].width(Length::Fill).height(Length::Fill);
+    if let Some(message) = on_dismiss {
+        layers = layers.push(click_off(message));
+    }
+    layers.push(opaque(centered)).into()
+}
@@ -1,2 +1,3 @@
 // View modules will be split out here as the UI grows.
-// For now, all views are in app.rs as methods on App.
+// For now, most views are methods on App in app.rs and the feature modules under app/.
+pub mod dialog;
@@ -0,0 +1,112 @@
+use iced::mouse;
+use iced::widget::canvas;
+use iced::{Color, Point, Rectangle, Renderer, Size, Theme};
+
+use crate::message::{Message, PreviewBackground};
+
+/// Side length of one checkerboard square in screen pixels.
+pub const CHECKER_SIZE: f32 = 8.0;
+
+const CHECKER_LIGHT: Color = Color { r: 0.40, g: 0.40, b: 0.40, a: 1.0 };
+const CHECKER_DARK: Color = Color { r: 0.26, g: 0.26, b: 0.26, a: 1.0 };
+
+/// Backdrop drawn behind the preview frame so transparent regions can be told
+/// apart from black video.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreviewBackdrop {
+    pub background: PreviewBackground,
+}
+
+impl PreviewBackdrop {
+    pub fn new(background: PreviewBackground) -> Self {
+        Self { background }
+    }
+}
+
+/// Solid fill color for a background, or `None` for the checkerboard.
+pub fn solid_color(background: PreviewBackground) -> Option<Color> {
+    match background {
+        PreviewBackground::Checkerboard => None,
+        PreviewBackground::Black => Some(Color::BLACK),
+        PreviewBackground::Gray => Some(Color::from_rgb(0.5, 0.5, 0.5)),
+        PreviewBackground::White => Some(Color::WHITE),
+    }
+}
+
+/// True if the checker square at (`col`, `row`) uses the light shade.
+pub fn is_light_square(col: u32, row: u32) -> bool {
+    (col + row) % 2 == 0
+}
+
+impl canvas::Program<Message> for PreviewBackdrop {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let size = bounds.size();
+
+        if let Some(color) = solid_color(self.background) {
+            frame.fill_rectangle(Point::ORIGIN, size, color);
+            return vec![frame.into_geometry()];
+        }
+
+        frame.fill_rectangle(Point::ORIGIN, size, CHECKER_DARK);
+        let cols = (size.width / CHECKER_SIZE).ceil() as u32;
+        let rows = (size.height / CHECKER_SIZE).ceil() as u32;
+        for row in 0..rows {
+            for col in 0..cols {
+                if !is_light_square(col, row) {
+                    continue;
+                }
+                let x = col as f32 * CHECKER_SIZE;
+                let y = row as f32 * CHECKER_SIZE;
+                frame.fill_rectangle(
+                    Point::new(x, y),
+                    Size::new(
+                        CHECKER_SIZE.min(size.width - x),
+                        CHECKER_SIZE.min(size.height - y),
+                    ),
+                    CHECKER_LIGHT,
+                );
+            }
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checker_squares_alternate() {
+        assert!(is_light_square(0, 0));
+        assert!(!is_light_square(1, 0));
+        assert!(!is_light_square(0, 1));
+        assert!(is_light_square(1, 1));
+    }
+
+    #[test]
+    fn test_solid_colors() {
+        assert_eq!(solid_color(PreviewBackground::Checkerboard), None);
+        assert_eq!(solid_color(PreviewBackground::Black), Some(Color::BLACK));
+        assert_eq!(solid_color(PreviewBackground::White), Some(Color::WHITE));
+    }
+
+    #[test]
+    fn test_background_cycle_returns_to_start() {
+        let mut bg = PreviewBackground::default();
+        for _ in 0..4 {
+            bg = bg.next();
+        }
+        assert_eq!(bg, PreviewBackground::Checkerboard);
+    }
+}
@@ -1 +1,5 @@
+pub mod checkerboard;
+pub mod preview_overlay;
 pub mod timeline_canvas;
+pub mod track_header;
+pub mod waveform;
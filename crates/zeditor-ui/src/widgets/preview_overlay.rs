@@ -0,0 +1,237 @@
+use iced::mouse;
+use iced::widget::canvas;
+use iced::{Color, Point, Rectangle, Renderer, Size, Theme};
+
+use crate::message::{Message, PreviewOverlayKind};
+
+/// Action-safe area as a fraction of the canvas (90%).
+pub const ACTION_SAFE_FRACTION: f32 = 0.9;
+/// Title-safe area as a fraction of the canvas (80%).
+pub const TITLE_SAFE_FRACTION: f32 = 0.8;
+/// Width over height of the frame shown by the vertical crop guide.
+pub const VERTICAL_CROP_ASPECT: f32 = 9.0 / 16.0;
+
+/// Framing guides drawn above the preview frame. Each guide is toggled independently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PreviewOverlay {
+    pub safe_areas: bool,
+    pub thirds_grid: bool,
+    pub center_cross: bool,
+    pub vertical_crop: bool,
+}
+
+impl PreviewOverlay {
+    pub fn is_enabled(&self, kind: PreviewOverlayKind) -> bool {
+        match kind {
+            PreviewOverlayKind::SafeAreas => self.safe_areas,
+            PreviewOverlayKind::ThirdsGrid => self.thirds_grid,
+            PreviewOverlayKind::CenterCross => self.center_cross,
+            PreviewOverlayKind::VerticalCrop => self.vertical_crop,
+        }
+    }
+
+    pub fn toggle(&mut self, kind: PreviewOverlayKind) {
+        match kind {
+            PreviewOverlayKind::SafeAreas => self.safe_areas = !self.safe_areas,
+            PreviewOverlayKind::ThirdsGrid => self.thirds_grid = !self.thirds_grid,
+            PreviewOverlayKind::CenterCross => self.center_cross = !self.center_cross,
+            PreviewOverlayKind::VerticalCrop => self.vertical_crop = !self.vertical_crop,
+        }
+    }
+
+    /// True if any guide is enabled (the overlay canvas is skipped otherwise).
+    pub fn any(&self) -> bool {
+        self.safe_areas || self.thirds_grid || self.center_cross || self.vertical_crop
+    }
+}
+
+/// Centered rectangle covering `fraction` of the canvas in each dimension.
+pub fn safe_area_rect(size: Size, fraction: f32) -> Rectangle {
+    let w = size.width * fraction;
+    let h = size.height * fraction;
+    Rectangle::new(
+        Point::new((size.width - w) / 2.0, (size.height - h) / 2.0),
+        Size::new(w, h),
+    )
+}
+
+/// Largest centered rectangle of the given aspect (width / height) that
+/// fits the canvas: the part a crop to that shape keeps.
+pub fn crop_guide_rect(size: Size, aspect: f32) -> Rectangle {
+    let (w, h) = if size.width / size.height > aspect {
+        (size.height * aspect, size.height)
+    } else {
+        (size.width, size.width / aspect)
+    };
+    Rectangle::new(
+        Point::new((size.width - w) / 2.0, (size.height - h) / 2.0),
+        Size::new(w, h),
+    )
+}
+
+/// The two vertical and two horizontal rule-of-thirds lines as (start, end) pairs.
+pub fn thirds_lines(size: Size) -> [(Point, Point); 4] {
+    let x1 = size.width / 3.0;
+    let x2 = size.width * 2.0 / 3.0;
+    let y1 = size.height / 3.0;
+    let y2 = size.height * 2.0 / 3.0;
+    [
+        (Point::new(x1, 0.0), Point::new(x1, size.height)),
+        (Point::new(x2, 0.0), Point::new(x2, size.height)),
+        (Point::new(0.0, y1), Point::new(size.width, y1)),
+        (Point::new(0.0, y2), Point::new(size.width, y2)),
+    ]
+}
+
+/// Horizontal and vertical arms of the center cross, each `arm` pixels long from center.
+pub fn center_cross_lines(size: Size, arm: f32) -> [(Point, Point); 2] {
+    let cx = size.width / 2.0;
+    let cy = size.height / 2.0;
+    [
+        (Point::new(cx - arm, cy), Point::new(cx + arm, cy)),
+        (Point::new(cx, cy - arm), Point::new(cx, cy + arm)),
+    ]
+}
+
+fn stroke_line(frame: &mut canvas::Frame, from: Point, to: Point, color: Color) {
+    let path = canvas::Path::line(from, to);
+    frame.stroke(
+        &path,
+        canvas::Stroke::default().with_color(color).with_width(1.0),
+    );
+}
+
+impl canvas::Program<Message> for PreviewOverlay {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let size = bounds.size();
+        let guide_color = Color { a: 0.6, ..Color::WHITE };
+
+        if self.vertical_crop {
+            let keep = crop_guide_rect(size, VERTICAL_CROP_ASPECT);
+            let shade = Color::from_rgba(0.0, 0.0, 0.0, 0.55);
+            // Shade the strips either side of (or above and below) the kept frame
+            let strips = if keep.x > 0.0 {
+                [
+                    Rectangle::new(Point::ORIGIN, Size::new(keep.x, size.height)),
+                    Rectangle::new(Point::new(keep.x + keep.width, 0.0), Size::new(keep.x, size.height)),
+                ]
+            } else {
+                [
+                    Rectangle::new(Point::ORIGIN, Size::new(size.width, keep.y)),
+                    Rectangle::new(Point::new(0.0, keep.y + keep.height), Size::new(size.width, keep.y)),
+                ]
+            };
+            for strip in strips {
+                frame.fill_rectangle(strip.position(), strip.size(), shade);
+            }
+            let path = canvas::Path::rectangle(keep.position(), keep.size());
+            frame.stroke(&path, canvas::Stroke::default().with_color(guide_color).with_width(1.0));
+        }
+
+        if self.safe_areas {
+            for (fraction, color) in [
+                (ACTION_SAFE_FRACTION, guide_color),
+                (TITLE_SAFE_FRACTION, Color::from_rgba(1.0, 0.85, 0.2, 0.7)),
+            ] {
+                let rect = safe_area_rect(size, fraction);
+                let path = canvas::Path::rectangle(rect.position(), rect.size());
+                frame.stroke(
+                    &path,
+                    canvas::Stroke::default().with_color(color).with_width(1.0),
+                );
+            }
+        }
+
+        if self.thirds_grid {
+            for (from, to) in thirds_lines(size) {
+                stroke_line(&mut frame, from, to, Color { a: 0.4, ..Color::WHITE });
+            }
+        }
+
+        if self.center_cross {
+            let arm = size.width.min(size.height) * 0.05;
+            for (from, to) in center_cross_lines(size, arm) {
+                stroke_line(&mut frame, from, to, guide_color);
+            }
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_safe_area_rect_is_centered() {
+        let rect = safe_area_rect(Size::new(1000.0, 500.0), ACTION_SAFE_FRACTION);
+        assert_eq!(rect.width, 900.0);
+        assert_eq!(rect.height, 450.0);
+        assert_eq!(rect.x, 50.0);
+        assert_eq!(rect.y, 25.0);
+    }
+
+    #[test]
+    fn test_title_safe_inside_action_safe() {
+        let size = Size::new(960.0, 540.0);
+        let action = safe_area_rect(size, ACTION_SAFE_FRACTION);
+        let title = safe_area_rect(size, TITLE_SAFE_FRACTION);
+        assert!(title.x > action.x && title.y > action.y);
+        assert!(title.width < action.width && title.height < action.height);
+    }
+
+    #[test]
+    fn test_vertical_crop_keeps_centered_strip() {
+        let rect = crop_guide_rect(Size::new(1600.0, 900.0), VERTICAL_CROP_ASPECT);
+        assert!((rect.width - 506.25).abs() < 1e-3);
+        assert_eq!(rect.height, 900.0);
+        assert!((rect.x - 546.875).abs() < 1e-3);
+        assert_eq!(rect.y, 0.0);
+
+        // A canvas narrower than the crop keeps its width and loses top and bottom
+        let rect = crop_guide_rect(Size::new(400.0, 1000.0), VERTICAL_CROP_ASPECT);
+        assert_eq!(rect.width, 400.0);
+        assert!((rect.y - (1000.0 - 400.0 * 16.0 / 9.0) / 2.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_thirds_lines_positions() {
+        let lines = thirds_lines(Size::new(300.0, 150.0));
+        assert_eq!(lines[0].0.x, 100.0);
+        assert_eq!(lines[1].0.x, 200.0);
+        assert_eq!(lines[2].0.y, 50.0);
+        assert_eq!(lines[3].0.y, 100.0);
+    }
+
+    #[test]
+    fn test_center_cross_meets_at_center() {
+        let [h, v] = center_cross_lines(Size::new(200.0, 100.0), 10.0);
+        assert_eq!(h.0, Point::new(90.0, 50.0));
+        assert_eq!(h.1, Point::new(110.0, 50.0));
+        assert_eq!(v.0, Point::new(100.0, 40.0));
+        assert_eq!(v.1, Point::new(100.0, 60.0));
+    }
+
+    #[test]
+    fn test_toggle_flags() {
+        let mut overlay = PreviewOverlay::default();
+        assert!(!overlay.any());
+        overlay.toggle(PreviewOverlayKind::ThirdsGrid);
+        assert!(overlay.is_enabled(PreviewOverlayKind::ThirdsGrid));
+        assert!(!overlay.is_enabled(PreviewOverlayKind::SafeAreas));
+        assert!(overlay.any());
+        overlay.toggle(PreviewOverlayKind::ThirdsGrid);
+        assert!(!overlay.any());
+    }
+}
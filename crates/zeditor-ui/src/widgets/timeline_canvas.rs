@@ -1,25 +1,41 @@
 use std::collections::HashMap;
 
+use iced::keyboard::key::Named;
+use iced::keyboard::Key;
 use iced::mouse;
+use iced::touch;
 use iced::widget::canvas;
 use iced::{border, Color, Point, Rectangle, Renderer, Size, Theme};
 use uuid::Uuid;
 
-use zeditor_core::timeline::{Timeline, TimelinePosition, TrimPreview, TrackType};
+use zeditor_core::audio::{VolumeEnvelope, MAX_GAIN_DB, MIN_GAIN_DB};
+use zeditor_core::timeline::{snap_to_points, Clip, RetimeKind, Timeline, TimelinePosition, Track, TrimPreview, TrackType};
+use zeditor_core::media::SourceLibrary;
+use zeditor_core::transcript::timeline_words;
 
 use crate::message::{Message, SourceDragPreview, ToolMode};
 
-const RULER_HEIGHT: f32 = 20.0;
-const TRACK_HEIGHT: f32 = 50.0;
+pub const RULER_HEIGHT: f32 = 20.0;
+pub const TRACK_HEIGHT: f32 = 50.0;
 const CLIP_RESIZE_EDGE_WIDTH: f32 = 8.0;
+/// Thickness of the link and group accent stripes on clips.
+const LINK_ACCENT_HEIGHT: f32 = 3.0;
 const ZOOM_MIN: f32 = 0.1;
 const ZOOM_MAX: f32 = 1000.0;
-const SNAP_THRESHOLD_SECS: f64 = 0.2;
+/// Gap between the volume envelope's range and the top/bottom of the lane.
+const ENVELOPE_PADDING: f32 = 4.0;
+/// How close (in pixels) a press must be to the envelope line to grab it.
+const ENVELOPE_GRAB_PX: f32 = 4.0;
+/// How close (in pixels) a press must be to an envelope point to grab it.
+const ENVELOPE_POINT_GRAB_PX: f32 = 5.0;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum HitZone {
     Body,
     RightEdge,
+    /// The volume envelope line of an audio clip, and the point under the
+    /// cursor if any.
+    Envelope(Option<usize>),
 }
 
 #[derive(Debug, Clone)]
@@ -38,12 +54,27 @@ pub enum TimelineInteraction {
         clip_id: Uuid,
         current_x: f32,
     },
+    /// Dragging an envelope point, or the line when `point` is `None`.
+    Envelope {
+        track_index: usize,
+        clip_id: Uuid,
+        point: Option<usize>,
+        start: Point,
+        current: Point,
+    },
+    /// Hand-tool scrolling with the middle button or Space+drag; `last` is
+    /// the cursor position the previous scroll was measured from.
+    Panning { last: Point },
 }
 
 pub struct TimelineCanvasState {
     pub interaction: TimelineInteraction,
     pub modifiers: iced::keyboard::Modifiers,
     pub cursor_position: Option<Point>,
+    /// Space is held, so a left drag pans instead of editing.
+    pub space_held: bool,
+    /// Fingers down on the canvas (at most two), for pinch-zooming.
+    pub touches: Vec<(touch::Finger, Point)>,
 }
 
 impl Default for TimelineCanvasState {
@@ -52,6 +83,8 @@ impl Default for TimelineCanvasState {
             interaction: TimelineInteraction::None,
             modifiers: iced::keyboard::Modifiers::empty(),
             cursor_position: None,
+            space_held: false,
+            touches: Vec::new(),
         }
     }
 }
@@ -60,10 +93,25 @@ pub struct TimelineCanvas<'a> {
     pub timeline: &'a Timeline,
     pub playback_position: TimelinePosition,
     pub selected_clip: Option<(usize, Uuid)>,
+    /// Clips added to the selection with Shift/Ctrl+click.
+    pub selected_clips: &'a [(usize, Uuid)],
+    /// Library the clips' transcripts are read from; their words are drawn
+    /// along the bottom of the audio clips.
+    pub source_library: Option<&'a SourceLibrary>,
     pub zoom: f32,
     pub scroll_offset: f32,
+    /// Pixels the track lanes are scrolled up under the (fixed) ruler.
+    pub vertical_scroll: f32,
     pub tool_mode: ToolMode,
     pub source_drag: Option<SourceDragPreview>,
+    /// Distance within which a dragged clip snaps to its neighbours.
+    pub snap_threshold_secs: f64,
+    /// Dragged and resized clip edges also snap to markers.
+    pub snap_to_markers: bool,
+    /// Dragged and resized clip edges also snap to the playhead.
+    pub snap_to_playhead: bool,
+    /// Released audio clip ends go to a quiet cut point unless Alt is held.
+    pub snap_audio_trims: bool,
 }
 
 impl<'a> TimelineCanvas<'a> {
@@ -75,15 +123,22 @@ impl<'a> TimelineCanvas<'a> {
         secs as f32 * self.zoom - self.scroll_offset
     }
 
+    /// Canvas y of the top of track `index`, accounting for vertical scroll.
+    pub fn track_top(&self, index: usize) -> f32 {
+        RULER_HEIGHT + track_offset(self.timeline, index) - self.vertical_scroll
+    }
+
+    /// Height of track `index`'s lane.
+    pub fn lane_height(&self, index: usize) -> f32 {
+        self.timeline.tracks.get(index).map_or(TRACK_HEIGHT, track_height)
+    }
+
     pub fn hit_test_clip(&self, x: f32, y: f32) -> Option<(usize, Uuid, HitZone)> {
         let track_y = y - RULER_HEIGHT;
         if track_y < 0.0 {
             return None;
         }
-        let track_index = (track_y / TRACK_HEIGHT) as usize;
-        if track_index >= self.timeline.tracks.len() {
-            return None;
-        }
+        let track_index = track_index_at_offset(self.timeline, track_y + self.vertical_scroll)?;
 
         let track = &self.timeline.tracks[track_index];
         for clip in &track.clips {
@@ -94,16 +149,128 @@ impl<'a> TimelineCanvas<'a> {
                 if x >= clip_end_px - CLIP_RESIZE_EDGE_WIDTH {
                     return Some((track_index, clip.id, HitZone::RightEdge));
                 }
+                if track.track_type == TrackType::Audio && self.tool_mode != ToolMode::Blade {
+                    if let Some(point) = self.hit_test_envelope(track_index, clip, x, y) {
+                        return Some((track_index, clip.id, HitZone::Envelope(point)));
+                    }
+                }
                 return Some((track_index, clip.id, HitZone::Body));
             }
         }
         None
     }
 
+    /// Canvas y of `gain_db` on the envelope of a clip in track `track_index`.
+    fn gain_to_y(&self, track_index: usize, gain_db: f32) -> f32 {
+        let span = self.lane_height(track_index) - 2.0 * ENVELOPE_PADDING;
+        self.track_top(track_index) + ENVELOPE_PADDING
+            + (MAX_GAIN_DB - gain_db) / (MAX_GAIN_DB - MIN_GAIN_DB) * span
+    }
+
+    /// Inverse of `gain_to_y`, unclamped.
+    fn y_to_gain(&self, track_index: usize, y: f32) -> f32 {
+        let span = self.lane_height(track_index) - 2.0 * ENVELOPE_PADDING;
+        let offset = y - self.track_top(track_index) - ENVELOPE_PADDING;
+        MAX_GAIN_DB - offset / span * (MAX_GAIN_DB - MIN_GAIN_DB)
+    }
+
+    /// Canvas x of source time `source_secs` within `clip`.
+    fn source_secs_to_px(&self, clip: &Clip, source_secs: f64) -> f32 {
+        let offset = (source_secs - clip.source_range.start.as_secs_f64()) / clip.speed();
+        self.secs_to_px(clip.timeline_range.start.as_secs_f64() + offset)
+    }
+
+    /// Source time under canvas x, clamped to `clip`.
+    fn px_to_source_secs(&self, clip: &Clip, x: f32) -> f64 {
+        let secs = self.px_to_secs(x).clamp(
+            clip.timeline_range.start.as_secs_f64(),
+            clip.timeline_range.end.as_secs_f64(),
+        );
+        clip.source_secs_at(TimelinePosition::from_secs_f64(secs))
+    }
+
+    /// `Some(point)` when (x, y) is on `clip`'s envelope line, with the index
+    /// of the point under it if any.
+    fn hit_test_envelope(&self, track_index: usize, clip: &Clip, x: f32, y: f32) -> Option<Option<usize>> {
+        let envelope = &clip.volume_envelope;
+        let point = envelope.points.iter().position(|p| {
+            let px = self.source_secs_to_px(clip, p.time);
+            let py = self.gain_to_y(track_index, p.gain_db);
+            (px - x).abs() <= ENVELOPE_POINT_GRAB_PX && (py - y).abs() <= ENVELOPE_POINT_GRAB_PX
+        });
+        if point.is_some() {
+            return Some(point);
+        }
+        let line_y = self.gain_to_y(track_index, envelope.gain_db_at(self.px_to_source_secs(clip, x)));
+        ((line_y - y).abs() <= ENVELOPE_GRAB_PX).then_some(None)
+    }
+
+    /// The clip's envelope with an in-progress envelope drag applied: the
+    /// grabbed point moved, a point added where the line was clicked, or the
+    /// line under the press shifted by the vertical movement.
+    fn dragged_envelope(
+        &self,
+        track_index: usize,
+        clip_id: Uuid,
+        point: Option<usize>,
+        start: Point,
+        current: Point,
+    ) -> Option<VolumeEnvelope> {
+        let clip = self.timeline.track(track_index).ok()?.get_clip(clip_id)?;
+        let mut envelope = clip.volume_envelope.clone();
+        match point {
+            Some(index) => {
+                let time = self.px_to_source_secs(clip, current.x);
+                envelope.move_point(index, time, self.y_to_gain(track_index, current.y))?;
+            }
+            None if (current.x - start.x).abs() < 3.0 && (current.y - start.y).abs() < 3.0 => {
+                let time = self.px_to_source_secs(clip, start.x);
+                let gain_db = envelope.gain_db_at(time);
+                envelope.add_point(time, gain_db);
+            }
+            None => {
+                let delta = self.y_to_gain(track_index, current.y) - self.y_to_gain(track_index, start.y);
+                envelope.shift_at(self.px_to_source_secs(clip, start.x), delta);
+            }
+        }
+        Some(envelope)
+    }
+
+    /// Draw `envelope` as a line across `clip` (drawn from `x0` to `x1`),
+    /// with a handle on each point.
+    fn draw_envelope(
+        &self,
+        frame: &mut canvas::Frame,
+        track_index: usize,
+        clip: &Clip,
+        envelope: &VolumeEnvelope,
+        x0: f32,
+        x1: f32,
+    ) {
+        let color = Color::from_rgb(1.0, 0.85, 0.2);
+        let y_at = |x: f32| self.gain_to_y(track_index, envelope.gain_db_at(self.px_to_source_secs(clip, x)));
+        let points: Vec<Point> = envelope
+            .points
+            .iter()
+            .map(|p| Point::new(self.source_secs_to_px(clip, p.time), self.gain_to_y(track_index, p.gain_db)))
+            .filter(|p| p.x > x0 && p.x < x1)
+            .collect();
+        let line = canvas::Path::new(|b| {
+            b.move_to(Point::new(x0, y_at(x0)));
+            for point in &points {
+                b.line_to(*point);
+            }
+            b.line_to(Point::new(x1, y_at(x1)));
+        });
+        frame.stroke(&line, canvas::Stroke::default().with_color(color).with_width(1.5));
+        for point in &points {
+            frame.fill_rectangle(Point::new(point.x - 3.0, point.y - 3.0), Size::new(6.0, 6.0), color);
+        }
+    }
+
     fn track_at_y(&self, y: f32) -> usize {
-        let track_y = (y - RULER_HEIGHT).max(0.0);
-        let idx = (track_y / TRACK_HEIGHT) as usize;
-        idx.min(self.timeline.tracks.len().saturating_sub(1))
+        let track_y = (y - RULER_HEIGHT).max(0.0) + self.vertical_scroll;
+        track_index_at_offset(self.timeline, track_y).unwrap_or(self.timeline.tracks.len().saturating_sub(1))
     }
 
     fn clip_duration_secs(&self, clip_id: Uuid) -> f64 {
@@ -190,44 +357,170 @@ impl<'a> TimelineCanvas<'a> {
             .min_by_key(|&i| (i as isize - target as isize).unsigned_abs())
     }
 
+    /// Guide, marker and playhead times (in secs) that clip edges snap to.
+    /// Guides always snap; timeline and clip markers and the playhead as
+    /// enabled by `snap_to_markers` / `snap_to_playhead`.
+    pub fn snap_points(&self) -> Vec<f64> {
+        self.snap_points_excluding(None)
+    }
+
+    /// Snap points, leaving out the markers of `exclude_id` and its linked
+    /// partners (which move along with a dragged clip).
+    fn snap_points_excluding(&self, exclude_id: Option<Uuid>) -> Vec<f64> {
+        let mut points: Vec<f64> = self.timeline.guides.iter().map(|g| g.as_secs_f64()).collect();
+        if self.snap_to_markers {
+            points.extend(self.timeline.markers.iter().map(|m| m.position.as_secs_f64()));
+            let exclude_link = exclude_id
+                .and_then(|id| self.timeline.tracks.iter().find_map(|t| t.get_clip(id)))
+                .and_then(|clip| clip.link_id);
+            let clips = self.timeline.tracks.iter().flat_map(|t| &t.clips).filter(|c| {
+                Some(c.id) != exclude_id && (exclude_link.is_none() || c.link_id != exclude_link)
+            });
+            points.extend(clips.flat_map(|c| c.visible_markers().map(|(_, secs)| secs)));
+        }
+        if self.snap_to_playhead {
+            points.push(self.playback_position.as_secs_f64());
+        }
+        points
+    }
+
     /// Compute the effective drop position for a drag, accounting for snap.
     /// First computes trim preview at the raw position, then checks for snap
-    /// against the resulting (trimmed) edges. Returns the snapped start in secs.
+    /// against the resulting (trimmed) edges, markers and the playhead; the
+    /// nearest wins. Returns the snapped start in secs and the time snapped
+    /// to (where the snap guide is drawn).
     fn compute_snapped_start(
         &self,
         raw_start: f64,
         clip_duration: f64,
         dest_track: usize,
         exclude_id: Uuid,
-    ) -> f64 {
+    ) -> (f64, Option<f64>) {
         let raw_end = raw_start + clip_duration;
+        let mut best = None;
         if let Ok(track) = self.timeline.track(dest_track) {
             let previews =
                 track.preview_trim_overlaps(raw_start, raw_end, Some(exclude_id));
-            if let Some(snapped) = track.preview_snap_position(
+            best = track.preview_snap_edge(
                 raw_start,
                 raw_end,
                 Some(exclude_id),
                 &previews,
-                SNAP_THRESHOLD_SECS,
-            ) {
-                return snapped.max(0.0);
+                self.snap_threshold_secs,
+            );
+        }
+        if let Some((start, point)) =
+            snap_to_points(raw_start, raw_end, &self.snap_points_excluding(Some(exclude_id)), self.snap_threshold_secs)
+        {
+            if best.is_none_or(|(best_start, _)| (start - raw_start).abs() < (best_start - raw_start).abs()) {
+                best = Some((start, point));
             }
         }
-        raw_start
+        match best {
+            Some((start, point)) => (start.max(0.0), Some(point)),
+            None => (raw_start, None),
+        }
+    }
+
+    /// Snap the end of a clip being resized to a marker or the playhead.
+    /// Returns the end in secs and the time snapped to, if any.
+    fn snap_resize_end(&self, raw_end: f64) -> (f64, Option<f64>) {
+        match snap_to_points(raw_end, raw_end, &self.snap_points(), self.snap_threshold_secs) {
+            Some((end, point)) => (end, Some(point)),
+            None => (raw_end, None),
+        }
     }
 }
 
+/// Clip marker: a tick down the clip with a small flag at the top.
+fn draw_clip_marker(frame: &mut canvas::Frame, x: f32, track_top: f32, track_height: f32) {
+    let color = Color::from_rgb(0.95, 0.85, 0.25);
+    frame.stroke(
+        &canvas::Path::line(Point::new(x, track_top + 2.0), Point::new(x, track_top + track_height - 2.0)),
+        canvas::Stroke::default().with_color(color).with_width(1.0),
+    );
+    let flag = canvas::Path::new(|b| {
+        b.move_to(Point::new(x, track_top + 2.0));
+        b.line_to(Point::new(x + 6.0, track_top + 5.0));
+        b.line_to(Point::new(x, track_top + 8.0));
+        b.close();
+    });
+    frame.fill(&flag, color);
+}
+
+/// Hatching over a retimed clip so its kind reads at a glance: sparse `/`
+/// lines for slow motion, dense `\` lines for sped-up clips and a
+/// cross-hatch for frame holds.
+fn draw_retime_hatching(
+    frame: &mut canvas::Frame,
+    kind: RetimeKind,
+    draw_x: f32,
+    draw_width: f32,
+    track_top: f32,
+    track_height: f32,
+) {
+    let (spacing, rising, falling, color) = match kind {
+        RetimeKind::Normal => return,
+        RetimeKind::Slow => (12.0, true, false, Color::from_rgba(0.6, 0.8, 1.0, 0.35)),
+        RetimeKind::Fast => (6.0, false, true, Color::from_rgba(1.0, 0.7, 0.3, 0.35)),
+        RetimeKind::Hold => (10.0, true, true, Color::from_rgba(1.0, 1.0, 1.0, 0.3)),
+    };
+    let top = track_top + 2.0;
+    let height = track_height - 4.0;
+    let bounds = Rectangle::new(Point::new(draw_x, top), Size::new(draw_width, height));
+    frame.with_clip(bounds, |frame| {
+        let stroke = canvas::Stroke::default().with_color(color).with_width(1.0);
+        let bottom = top + height;
+        let mut x = draw_x - height;
+        while x < draw_x + draw_width {
+            if rising {
+                frame.stroke(&canvas::Path::line(Point::new(x, bottom), Point::new(x + height, top)), stroke);
+            }
+            if falling {
+                frame.stroke(&canvas::Path::line(Point::new(x, top), Point::new(x + height, bottom)), stroke);
+            }
+            x += spacing;
+        }
+    });
+}
+
+/// Speed percentage (or "Hold" for a frame hold) in an amber tag at the top
+/// right of a retimed clip.
+fn draw_speed_badge(frame: &mut canvas::Frame, draw_x: f32, draw_width: f32, track_top: f32, clip: &Clip) {
+    let label = match clip.retime_kind() {
+        RetimeKind::Hold => "Hold".to_string(),
+        _ => format!("{:.0}%", clip.speed() * 100.0),
+    };
+    let badge_width = label.len() as f32 * 6.0 + 6.0;
+    if draw_width < badge_width + CLIP_RESIZE_EDGE_WIDTH + 4.0 {
+        return;
+    }
+    let badge_x = draw_x + draw_width - CLIP_RESIZE_EDGE_WIDTH - badge_width - 2.0;
+    frame.fill_rectangle(
+        Point::new(badge_x, track_top + 4.0),
+        Size::new(badge_width, 13.0),
+        Color::from_rgb(0.85, 0.6, 0.15),
+    );
+    frame.fill_text(canvas::Text {
+        content: label,
+        position: Point::new(badge_x + 3.0, track_top + 5.0),
+        color: Color::BLACK,
+        size: iced::Pixels(10.0),
+        ..canvas::Text::default()
+    });
+}
+
 fn draw_clip_shape(
     frame: &mut canvas::Frame,
     draw_x: f32,
     draw_width: f32,
     track_top: f32,
+    track_height: f32,
     color: Color,
     duration_secs: f64,
 ) {
     let clip_pos = Point::new(draw_x, track_top + 2.0);
-    let clip_size = Size::new(draw_width, TRACK_HEIGHT - 4.0);
+    let clip_size = Size::new(draw_width, track_height - 4.0);
     let clip_path = canvas::Path::new(|b| {
         b.rounded_rectangle(clip_pos, clip_size, border::Radius::from(4.0));
     });
@@ -242,7 +535,7 @@ fn draw_clip_shape(
     // Right resize edge indicator
     frame.fill_rectangle(
         Point::new(draw_x + draw_width - CLIP_RESIZE_EDGE_WIDTH, track_top + 2.0),
-        Size::new(CLIP_RESIZE_EDGE_WIDTH, TRACK_HEIGHT - 4.0),
+        Size::new(CLIP_RESIZE_EDGE_WIDTH, track_height - 4.0),
         Color {
             a: 0.3,
             ..Color::WHITE
@@ -261,6 +554,95 @@ fn draw_clip_shape(
     }
 }
 
+/// Thumbnail and name on a library drop preview block. The thumbnail covers
+/// the duration label, so it is drawn again beside it.
+fn draw_drop_preview_label(
+    frame: &mut canvas::Frame,
+    draw_x: f32,
+    draw_width: f32,
+    track_top: f32,
+    track_height: f32,
+    name: &str,
+    thumbnail: Option<&iced::widget::image::Handle>,
+    duration_secs: f64,
+) {
+    let mut text_x = draw_x + 4.0;
+    if let Some(handle) = thumbnail {
+        let height = track_height - 8.0;
+        let width = (height * 16.0 / 9.0).min(draw_width - CLIP_RESIZE_EDGE_WIDTH - 4.0);
+        if width >= 8.0 {
+            frame.draw_image(
+                Rectangle::new(Point::new(draw_x + 2.0, track_top + 4.0), Size::new(width, height)),
+                canvas::Image::new(handle.clone()),
+            );
+            text_x = draw_x + width + 6.0;
+            if draw_x + draw_width - text_x > 30.0 {
+                frame.fill_text(canvas::Text {
+                    content: format!("{:.1}s", duration_secs),
+                    position: Point::new(text_x, track_top + 18.0),
+                    color: Color::WHITE,
+                    size: iced::Pixels(11.0),
+                    ..canvas::Text::default()
+                });
+            }
+        }
+    }
+
+    let max_chars = ((draw_x + draw_width - CLIP_RESIZE_EDGE_WIDTH - text_x) / 6.0).max(0.0) as usize;
+    if max_chars >= 3 {
+        let content = if name.chars().count() > max_chars {
+            name.chars().take(max_chars - 1).chain(std::iter::once('\u{2026}')).collect()
+        } else {
+            name.to_string()
+        };
+        frame.fill_text(canvas::Text {
+            content,
+            position: Point::new(text_x, track_top + 32.0),
+            color: Color::WHITE,
+            size: iced::Pixels(10.0),
+            ..canvas::Text::default()
+        });
+    }
+}
+
+/// Accent shared by every clip with the same link or group id: a bright hue
+/// picked from the id, so partners match without storing a color.
+pub fn link_accent_color(id: Uuid) -> Color {
+    let hue = (id.as_u128() % 360) as f32 / 60.0;
+    let x = 1.0 - (hue % 2.0 - 1.0).abs();
+    let (r, g, b) = match hue as u32 {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        3 => (0.0, x, 1.0),
+        4 => (x, 0.0, 1.0),
+        _ => (1.0, 0.0, x),
+    };
+    // Lift toward white so accents read on both the green and blue clips
+    Color::from_rgb(0.35 + 0.65 * r, 0.35 + 0.65 * g, 0.35 + 0.65 * b)
+}
+
+/// Link accent as a stripe along the top of a linked clip, and group accent
+/// as a stripe along the bottom of a grouped one.
+fn draw_link_accents(frame: &mut canvas::Frame, clip: &Clip, draw_x: f32, draw_width: f32, track_top: f32, track_height: f32) {
+    let inset = 4.0_f32.min(draw_width / 2.0);
+    let stripe_width = draw_width - inset * 2.0;
+    if let Some(link_id) = clip.link_id {
+        frame.fill_rectangle(
+            Point::new(draw_x + inset, track_top + 3.0),
+            Size::new(stripe_width, LINK_ACCENT_HEIGHT),
+            link_accent_color(link_id),
+        );
+    }
+    if let Some(group_id) = clip.group_id {
+        frame.fill_rectangle(
+            Point::new(draw_x + inset, track_top + track_height - 3.0 - LINK_ACCENT_HEIGHT),
+            Size::new(stripe_width, LINK_ACCENT_HEIGHT),
+            link_accent_color(group_id),
+        );
+    }
+}
+
 pub fn color_for_track_type(track_type: TrackType) -> Color {
     match track_type {
         TrackType::Video => Color::from_rgb(0.25, 0.65, 0.35),
@@ -268,6 +650,37 @@ pub fn color_for_track_type(track_type: TrackType) -> Color {
     }
 }
 
+/// Horizontal midpoint and spread of a two-finger touch.
+fn pinch_span(touches: &[(touch::Finger, Point)]) -> Option<(f32, f32)> {
+    match touches {
+        [(_, a), (_, b)] => Some(((a.x + b.x) / 2.0, a.distance(*b))),
+        _ => None,
+    }
+}
+
+/// Height of `track`'s lane on the canvas and in the header column.
+pub fn track_height(track: &Track) -> f32 {
+    TRACK_HEIGHT * track.height.multiplier()
+}
+
+/// Distance from the top of the first lane to the top of track `index`; the
+/// height of all lanes when `index` is the track count.
+pub fn track_offset(timeline: &Timeline, index: usize) -> f32 {
+    timeline.tracks.iter().take(index).map(track_height).sum()
+}
+
+/// The track whose lane covers `offset` below the top of the first lane.
+pub fn track_index_at_offset(timeline: &Timeline, offset: f32) -> Option<usize> {
+    if offset < 0.0 {
+        return None;
+    }
+    let mut bottom = 0.0;
+    timeline.tracks.iter().position(|track| {
+        bottom += track_height(track);
+        offset < bottom
+    })
+}
+
 pub fn clamp_zoom(zoom: f32) -> f32 {
     zoom.clamp(ZOOM_MIN, ZOOM_MAX)
 }
@@ -282,6 +695,26 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
         bounds: Rectangle,
         cursor: mouse::Cursor,
     ) -> Option<canvas::Action<Message>> {
+        // Key state is tracked wherever the cursor is, so a release outside
+        // the canvas isn't missed. Never captured: the app's shortcuts see them too.
+        if let canvas::Event::Keyboard(event) = event {
+            match event {
+                iced::keyboard::Event::ModifiersChanged(modifiers) => state.modifiers = *modifiers,
+                iced::keyboard::Event::KeyPressed { key: Key::Named(Named::Space), .. } => {
+                    state.space_held = true;
+                }
+                iced::keyboard::Event::KeyReleased { key: Key::Named(Named::Space), .. } => {
+                    state.space_held = false;
+                }
+                _ => {}
+            }
+            return None;
+        }
+
+        if let canvas::Event::Touch(event) = event {
+            return self.update_touch(state, event, bounds);
+        }
+
         let cursor_pos = cursor.position_in(bounds)?;
 
         match event {
@@ -291,8 +724,15 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
                     mouse::ScrollDelta::Pixels { x, y } => (*x / 20.0, *y / 20.0),
                 };
 
-                // Plain scroll = pan, Alt+scroll = zoom
-                if state.modifiers.alt() {
+                // Plain scroll = pan, Alt+scroll = zoom, Shift+scroll = scroll tracks.
+                // Trackpad pinches arrive as Ctrl+scroll, so that zooms too.
+                if state.modifiers.shift() {
+                    let d = if dy.abs() > dx.abs() { dy } else { dx };
+                    Some(
+                        canvas::Action::publish(Message::TimelineVerticalScroll(-d * 20.0))
+                            .and_capture(),
+                    )
+                } else if state.modifiers.alt() || state.modifiers.control() {
                     // Alt held: zoom centered on cursor
                     let zoom_delta = if dy.abs() > dx.abs() { dy } else { dx };
                     let cursor_secs = self.px_to_secs(cursor_pos.x);
@@ -321,7 +761,22 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
                 // Right-click on canvas is a no-op; context menu is on track headers
                 Some(canvas::Action::capture())
             }
+            canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Middle)) => {
+                state.interaction = TimelineInteraction::Panning { last: cursor_pos };
+                Some(canvas::Action::capture())
+            }
+            canvas::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Middle)) => {
+                if matches!(state.interaction, TimelineInteraction::Panning { .. }) {
+                    state.interaction = TimelineInteraction::None;
+                    return Some(canvas::Action::capture());
+                }
+                None
+            }
             canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                if state.space_held {
+                    state.interaction = TimelineInteraction::Panning { last: cursor_pos };
+                    return Some(canvas::Action::publish(Message::TimelinePanStarted).and_capture());
+                }
                 if let Some((track_index, clip_id, zone)) =
                     self.hit_test_clip(cursor_pos.x, cursor_pos.y)
                 {
@@ -362,6 +817,30 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
                             };
                             return Some(canvas::Action::capture());
                         }
+                        HitZone::Envelope(point) => {
+                            // Alt+click removes a point
+                            if let (Some(index), true) = (point, state.modifiers.alt()) {
+                                let clip = self.timeline.track(track_index).ok()?.get_clip(clip_id)?;
+                                let mut envelope = clip.volume_envelope.clone();
+                                envelope.remove_point(index);
+                                return Some(
+                                    canvas::Action::publish(Message::SetClipVolumeEnvelope {
+                                        track_index,
+                                        clip_id,
+                                        envelope,
+                                    })
+                                    .and_capture(),
+                                );
+                            }
+                            state.interaction = TimelineInteraction::Envelope {
+                                track_index,
+                                clip_id,
+                                point,
+                                start: cursor_pos,
+                                current: cursor_pos,
+                            };
+                            return Some(canvas::Action::capture());
+                        }
                     }
                 }
 
@@ -387,10 +866,32 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
                         *current_x = cursor_pos.x.max(min_current_x);
                         Some(canvas::Action::request_redraw().and_capture())
                     }
-                    TimelineInteraction::Resizing { current_x, .. } => {
-                        *current_x = cursor_pos.x;
+                    TimelineInteraction::Resizing {
+                        track_index,
+                        clip_id,
+                        current_x,
+                    } => {
+                        let (end, _) = self.snap_resize_end(self.px_to_secs(cursor_pos.x));
+                        *current_x = self.secs_to_px(end);
+                        Some(
+                            canvas::Action::publish(Message::TrimEdgeMoved {
+                                track_index: *track_index,
+                                clip_id: *clip_id,
+                                edge: TimelinePosition::from_secs_f64(end.max(0.0)),
+                            })
+                            .and_capture(),
+                        )
+                    }
+                    TimelineInteraction::Envelope { current, .. } => {
+                        *current = cursor_pos;
                         Some(canvas::Action::request_redraw().and_capture())
                     }
+                    TimelineInteraction::Panning { last } => {
+                        // Content follows the cursor, so the view scrolls the other way
+                        let (dx, dy) = (last.x - cursor_pos.x, last.y - cursor_pos.y);
+                        *last = cursor_pos;
+                        Some(canvas::Action::publish(Message::TimelinePan { dx, dy }).and_capture())
+                    }
                     TimelineInteraction::None => {
                         if self.tool_mode == ToolMode::Blade {
                             Some(canvas::Action::request_redraw())
@@ -417,12 +918,13 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
                         // If total movement < 5px in both axes, treat as click-to-select
                         let dy = cursor_pos.y - start_y;
                         if (current_x - start_x).abs() < 5.0 && dy.abs() < 5.0 {
-                            return Some(
-                                canvas::Action::publish(Message::SelectTimelineClip(
-                                    Some((track_index, clip_id)),
-                                ))
-                                .and_capture(),
-                            );
+                            // Shift/Ctrl+click adds to (or removes from) the selection
+                            let message = if state.modifiers.shift() || state.modifiers.command() {
+                                Message::ToggleClipSelection((track_index, clip_id))
+                            } else {
+                                Message::SelectTimelineClip(Some((track_index, clip_id)))
+                            };
+                            return Some(canvas::Action::publish(message).and_capture());
                         }
                         let raw_secs = self.px_to_secs(current_x - offset_px).max(0.0);
                         let raw_dest_track = self.track_at_y(cursor_pos.y);
@@ -433,7 +935,7 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
                             track_index, clip_id, raw_dest_track,
                         );
 
-                        let effective_secs = self.compute_snapped_start(
+                        let (effective_secs, _) = self.compute_snapped_start(
                             raw_secs, duration, dest_track, clip_id,
                         );
                         Some(
@@ -451,23 +953,39 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
                         clip_id,
                         current_x,
                     } => {
-                        let new_end_secs = self.px_to_secs(current_x).max(0.0);
+                        let (new_end_secs, _) = self.snap_resize_end(self.px_to_secs(current_x).max(0.0));
+                        let new_end = TimelinePosition::from_secs_f64(new_end_secs);
+                        let quiet_cut = self.snap_audio_trims
+                            && !state.modifiers.alt()
+                            && self.timeline.tracks.get(track_index).is_some_and(|t| t.track_type == TrackType::Audio);
+                        let message = if quiet_cut {
+                            Message::SnapAudioTrim { track_index, clip_id, new_end }
+                        } else {
+                            Message::ResizeClip { track_index, clip_id, new_end }
+                        };
+                        Some(canvas::Action::publish(message).and_capture())
+                    }
+                    TimelineInteraction::Envelope {
+                        track_index,
+                        clip_id,
+                        point,
+                        start,
+                        current,
+                    } => {
+                        let envelope = self.dragged_envelope(track_index, clip_id, point, start, current)?;
                         Some(
-                            canvas::Action::publish(Message::ResizeClip {
+                            canvas::Action::publish(Message::SetClipVolumeEnvelope {
                                 track_index,
                                 clip_id,
-                                new_end: TimelinePosition::from_secs_f64(new_end_secs),
+                                envelope,
                             })
                             .and_capture(),
                         )
                     }
+                    TimelineInteraction::Panning { .. } => Some(canvas::Action::capture()),
                     TimelineInteraction::None => None,
                 }
             }
-            canvas::Event::Keyboard(iced::keyboard::Event::ModifiersChanged(modifiers)) => {
-                state.modifiers = *modifiers;
-                None
-            }
             _ => None,
         }
     }
@@ -489,9 +1007,6 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
             Color::from_rgb(0.12, 0.12, 0.15),
         );
 
-        // Time ruler
-        self.draw_ruler(&mut frame, bounds.width);
-
         // Pre-compute drag info: effective (snapped) position + trim preview
         struct LinkedDragPreview {
             track_index: usize,
@@ -509,6 +1024,7 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
             effective_duration: f64,
             preview_map: HashMap<Uuid, Vec<TrimPreview>>,
             linked: Vec<LinkedDragPreview>,
+            snap_guide: Option<f64>,
         }
 
         let drag_preview: Option<DragPreview> = if let TimelineInteraction::Dragging {
@@ -532,7 +1048,7 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
             );
 
             // Compute effective position with snap
-            let effective_start = self.compute_snapped_start(
+            let (effective_start, snap_guide) = self.compute_snapped_start(
                 raw_start,
                 drag_duration,
                 dest_track,
@@ -554,69 +1070,65 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
                 let effective_start_px = self.secs_to_px(effective_start);
                 let effective_end_px = self.secs_to_px(effective_end);
 
-                // Compute linked clip drag positions
+                // Compute drag positions of the clip's companions (linked
+                // partners and group members)
                 let mut linked = Vec::new();
-                let link_id = self.timeline.track(*drag_track)
+                let drag_clip = self.timeline.track(*drag_track)
                     .ok()
-                    .and_then(|t| t.get_clip(*drag_clip_id))
-                    .and_then(|c| c.link_id);
-
-                if let Some(link_id) = link_id {
-                    // Get original position of dragged clip to compute delta
-                    let orig_start = self.timeline.track(*drag_track)
-                        .ok()
-                        .and_then(|t| t.get_clip(*drag_clip_id))
-                        .map(|c| c.timeline_range.start.as_secs_f64())
-                        .unwrap_or(0.0);
-                    let delta = effective_start - orig_start;
-
-                    // Compute the mirror track for the linked clip if cross-track drag
-                    let dest_type = self.timeline.tracks.get(dest_track)
-                        .map(|t| t.track_type);
-                    let linked_dest = if *drag_track != dest_track {
-                        match dest_type {
-                            Some(TrackType::Video) => self.timeline.mirror_audio_track_for_video(dest_track),
-                            Some(TrackType::Audio) => self.timeline.mirror_video_track_for_audio(dest_track),
-                            None => None,
-                        }
-                    } else {
-                        None // same-track move: linked clips stay on their original tracks
-                    };
+                    .and_then(|t| t.get_clip(*drag_clip_id));
+                let link_id = drag_clip.and_then(|c| c.link_id);
+                // Get original position of dragged clip to compute delta
+                let orig_start = drag_clip
+                    .map(|c| c.timeline_range.start.as_secs_f64())
+                    .unwrap_or(0.0);
+                let delta = effective_start - orig_start;
+
+                // Compute the mirror track for the linked clip if cross-track drag
+                let dest_type = self.timeline.tracks.get(dest_track)
+                    .map(|t| t.track_type);
+                let linked_dest = if *drag_track != dest_track {
+                    match dest_type {
+                        Some(TrackType::Video) => self.timeline.mirror_audio_track_for_video(dest_track),
+                        Some(TrackType::Audio) => self.timeline.mirror_video_track_for_audio(dest_track),
+                        None => None,
+                    }
+                } else {
+                    None // same-track move: linked clips stay on their original tracks
+                };
 
-                    for (linked_track_idx, linked_clip_id) in self.timeline.find_linked_clips(link_id) {
-                        if linked_clip_id == *drag_clip_id {
-                            continue;
-                        }
-                        // Use mirror dest if cross-track, otherwise keep on original track
-                        let preview_track = linked_dest.unwrap_or(linked_track_idx);
-                        if let Ok(linked_track) = self.timeline.track(linked_track_idx) {
-                            if let Some(linked_clip) = linked_track.get_clip(linked_clip_id) {
-                                let linked_start = linked_clip.timeline_range.start.as_secs_f64() + delta;
-                                let linked_dur = linked_clip.duration().as_secs_f64();
-                                let linked_end = linked_start + linked_dur;
-                                // Preview trim on the destination track (may differ from current)
-                                let preview_track_ref = self.timeline.track(preview_track)
-                                    .unwrap_or(linked_track);
-                                let linked_previews = preview_track_ref.preview_trim_overlaps(
-                                    linked_start,
-                                    linked_end,
-                                    Some(linked_clip_id),
-                                );
-                                let mut linked_map: HashMap<Uuid, Vec<_>> = HashMap::new();
-                                for p in linked_previews {
-                                    linked_map.entry(p.clip_id).or_default().push(p);
-                                }
-                                let linked_start_px = self.secs_to_px(linked_start);
-                                let linked_end_px = self.secs_to_px(linked_end);
-                                linked.push(LinkedDragPreview {
-                                    track_index: preview_track,
-                                    clip_id: linked_clip_id,
-                                    effective_start_px: linked_start_px,
-                                    effective_width_px: linked_end_px - linked_start_px,
-                                    effective_duration: linked_dur,
-                                    preview_map: linked_map,
-                                });
+                for (linked_track_idx, linked_clip_id) in self.timeline.clip_companions(*drag_track, *drag_clip_id) {
+                    if let Ok(linked_track) = self.timeline.track(linked_track_idx) {
+                        if let Some(linked_clip) = linked_track.get_clip(linked_clip_id) {
+                            // Linked partners use the mirror dest if cross-track;
+                            // group members keep their original tracks
+                            let preview_track = linked_dest
+                                .filter(|_| link_id.is_some() && linked_clip.link_id == link_id)
+                                .unwrap_or(linked_track_idx);
+                            let linked_start = (linked_clip.timeline_range.start.as_secs_f64() + delta).max(0.0);
+                            let linked_dur = linked_clip.duration().as_secs_f64();
+                            let linked_end = linked_start + linked_dur;
+                            // Preview trim on the destination track (may differ from current)
+                            let preview_track_ref = self.timeline.track(preview_track)
+                                .unwrap_or(linked_track);
+                            let linked_previews = preview_track_ref.preview_trim_overlaps(
+                                linked_start,
+                                linked_end,
+                                Some(linked_clip_id),
+                            );
+                            let mut linked_map: HashMap<Uuid, Vec<_>> = HashMap::new();
+                            for p in linked_previews {
+                                linked_map.entry(p.clip_id).or_default().push(p);
                             }
+                            let linked_start_px = self.secs_to_px(linked_start);
+                            let linked_end_px = self.secs_to_px(linked_end);
+                            linked.push(LinkedDragPreview {
+                                track_index: preview_track,
+                                clip_id: linked_clip_id,
+                                effective_start_px: linked_start_px,
+                                effective_width_px: linked_end_px - linked_start_px,
+                                effective_duration: linked_dur,
+                                preview_map: linked_map,
+                            });
                         }
                     }
                 }
@@ -628,6 +1140,7 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
                     effective_duration: drag_duration,
                     preview_map: map,
                     linked,
+                    snap_guide,
                 })
             } else {
                 None
@@ -636,7 +1149,7 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
             None
         };
 
-        // Pre-compute resize info for linked clips
+        // Pre-compute resize info for linked and grouped clips
         let linked_resize: HashMap<Uuid, f32> = if let TimelineInteraction::Resizing {
             track_index,
             clip_id: resize_id,
@@ -646,18 +1159,13 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
             let mut map = HashMap::new();
             if let Ok(track) = self.timeline.track(*track_index) {
                 if let Some(clip) = track.get_clip(*resize_id) {
-                    if let Some(link_id) = clip.link_id {
-                        let old_end_px = self.secs_to_px(clip.timeline_range.end.as_secs_f64());
-                        let delta_px = current_x - old_end_px;
-                        for (linked_track_idx, linked_clip_id) in self.timeline.find_linked_clips(link_id) {
-                            if linked_clip_id == *resize_id {
-                                continue;
-                            }
-                            if let Ok(linked_track) = self.timeline.track(linked_track_idx) {
-                                if let Some(linked_clip) = linked_track.get_clip(linked_clip_id) {
-                                    let linked_end_px = self.secs_to_px(linked_clip.timeline_range.end.as_secs_f64());
-                                    map.insert(linked_clip_id, linked_end_px + delta_px);
-                                }
+                    let old_end_px = self.secs_to_px(clip.timeline_range.end.as_secs_f64());
+                    let delta_px = current_x - old_end_px;
+                    for (linked_track_idx, linked_clip_id) in self.timeline.clip_companions(*track_index, *resize_id) {
+                        if let Ok(linked_track) = self.timeline.track(linked_track_idx) {
+                            if let Some(linked_clip) = linked_track.get_clip(linked_clip_id) {
+                                let linked_end_px = self.secs_to_px(linked_clip.timeline_range.end.as_secs_f64());
+                                map.insert(linked_clip_id, linked_end_px + delta_px);
                             }
                         }
                     }
@@ -725,16 +1233,24 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
             })
         });
 
-        // Compute link_id of the selected clip for group highlighting
-        let selected_link_id = self.selected_clip.and_then(|(track_idx, clip_id)| {
-            self.timeline.tracks.get(track_idx)
-                .and_then(|t| t.clips.iter().find(|c| c.id == clip_id))
-                .and_then(|c| c.link_id)
-        });
+        // Selected clips plus their linked partners and group members
+        let mut highlighted: Vec<Uuid> = Vec::new();
+        for &(track_idx, clip_id) in self.selected_clip.iter().chain(self.selected_clips) {
+            highlighted.push(clip_id);
+            highlighted.extend(
+                self.timeline.clip_companions(track_idx, clip_id).into_iter().map(|(_, id)| id),
+            );
+        }
+
+        let transcript_words = self
+            .source_library
+            .map(|library| timeline_words(self.timeline, library))
+            .unwrap_or_default();
 
         // Track lanes
         for (i, track) in self.timeline.tracks.iter().enumerate() {
-            let track_top = RULER_HEIGHT + i as f32 * TRACK_HEIGHT;
+            let track_top = self.track_top(i);
+            let track_height = self.lane_height(i);
 
             let bg = match track.track_type {
                 TrackType::Audio => {
@@ -754,13 +1270,13 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
             };
             frame.fill_rectangle(
                 Point::new(0.0, track_top),
-                Size::new(bounds.width, TRACK_HEIGHT),
+                Size::new(bounds.width, track_height),
                 bg,
             );
 
             // Track separator
             frame.fill_rectangle(
-                Point::new(0.0, track_top + TRACK_HEIGHT - 1.0),
+                Point::new(0.0, track_top + track_height - 1.0),
                 Size::new(bounds.width, 1.0),
                 Color::from_rgb(0.3, 0.3, 0.35),
             );
@@ -823,7 +1339,7 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
                                         let px = self.secs_to_px(ts);
                                         let pw = (self.secs_to_px(te) - px).max(4.0);
                                         draw_clip_shape(
-                                            &mut frame, px, pw, track_top, color, te - ts,
+                                            &mut frame, px, pw, track_top, track_height, color, te - ts,
                                         );
                                     }
                                 }
@@ -843,7 +1359,7 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
                                             let px = self.secs_to_px(ts);
                                             let pw = (self.secs_to_px(te) - px).max(4.0);
                                             draw_clip_shape(
-                                                &mut frame, px, pw, track_top, color, te - ts,
+                                                &mut frame, px, pw, track_top, track_height, color, te - ts,
                                             );
                                         }
                                     }
@@ -876,7 +1392,7 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
                                         let px = self.secs_to_px(ts);
                                         let pw = (self.secs_to_px(te) - px).max(4.0);
                                         draw_clip_shape(
-                                            &mut frame, px, pw, track_top, color, te - ts,
+                                            &mut frame, px, pw, track_top, track_height, color, te - ts,
                                         );
                                     }
                                 }
@@ -907,14 +1423,24 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
                 };
 
                 let color = color_for_track_type(track.track_type);
-                draw_clip_shape(&mut frame, draw_x, draw_width.max(4.0), track_top, color, dur);
+                draw_clip_shape(&mut frame, draw_x, draw_width.max(4.0), track_top, track_height, color, dur);
+                draw_link_accents(&mut frame, clip, draw_x, draw_width.max(4.0), track_top, track_height);
+                if clip.is_retimed() {
+                    draw_retime_hatching(&mut frame, clip.retime_kind(), draw_x, draw_width.max(4.0), track_top, track_height);
+                    draw_speed_badge(&mut frame, draw_x, draw_width.max(4.0), track_top, clip);
+                }
+                for (_, secs) in clip.visible_markers() {
+                    let x = self.secs_to_px(secs);
+                    if x >= draw_x && x <= draw_x + draw_width.max(4.0) {
+                        draw_clip_marker(&mut frame, x, track_top, track_height);
+                    }
+                }
 
                 // Draw selection border if this clip is selected or linked to selected
-                let is_selected = self.selected_clip == Some((i, clip.id))
-                    || (selected_link_id.is_some() && clip.link_id == selected_link_id);
+                let is_selected = highlighted.contains(&clip.id);
                 if is_selected {
                     let sel_pos = Point::new(draw_x, track_top + 2.0);
-                    let sel_size = Size::new(draw_width.max(4.0), TRACK_HEIGHT - 4.0);
+                    let sel_size = Size::new(draw_width.max(4.0), track_height - 4.0);
                     let sel_path = canvas::Path::new(|b| {
                         b.rounded_rectangle(sel_pos, sel_size, border::Radius::from(4.0));
                     });
@@ -925,6 +1451,37 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
                             .with_width(3.0),
                     );
                 }
+
+                // Volume envelope (rubber band), with any drag in progress applied
+                if track.track_type == TrackType::Audio {
+                    let dragged = match &state.interaction {
+                        TimelineInteraction::Envelope { clip_id, point, start, current, .. }
+                            if *clip_id == clip.id =>
+                        {
+                            self.dragged_envelope(i, clip.id, *point, *start, *current)
+                        }
+                        _ => None,
+                    };
+                    let envelope = dragged.as_ref().unwrap_or(&clip.volume_envelope);
+                    self.draw_envelope(&mut frame, i, clip, envelope, draw_x, draw_x + draw_width.max(4.0));
+                }
+
+                // Transcript words, skipping any that would overlap the previous label
+                let mut label_end = f32::NEG_INFINITY;
+                for word in transcript_words.iter().filter(|w| w.clip_id == clip.id) {
+                    let x = self.secs_to_px(word.range.start.as_secs_f64());
+                    if x < label_end || x > draw_x + draw_width {
+                        continue;
+                    }
+                    frame.fill_text(canvas::Text {
+                        content: word.text.clone(),
+                        position: Point::new(x, track_top + track_height - 14.0),
+                        color: Color::from_rgb(0.85, 0.9, 1.0),
+                        size: iced::Pixels(9.0),
+                        ..canvas::Text::default()
+                    });
+                    label_end = x + word.text.chars().count() as f32 * 5.5 + 4.0;
+                }
             }
 
             // Draw source drag new clips on this track
@@ -936,9 +1493,23 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
                         sd_info.start_px,
                         sd_info.width_px,
                         track_top,
+                        track_height,
                         color,
                         sd_info.duration_secs,
                     );
+                    if let Some(preview) = &self.source_drag {
+                        let thumbnail = if i == sd_info.video_track { preview.thumbnail.as_ref() } else { None };
+                        draw_drop_preview_label(
+                            &mut frame,
+                            sd_info.start_px,
+                            sd_info.width_px,
+                            track_top,
+                            track_height,
+                            &preview.name,
+                            thumbnail,
+                            sd_info.duration_secs,
+                        );
+                    }
                 }
             }
         }
@@ -947,7 +1518,7 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
         // so they render on top of other clips).
         if let Some(ref info) = drag_preview {
             // Primary dragged clip on dest_track
-            let dest_top = RULER_HEIGHT + info.dest_track as f32 * TRACK_HEIGHT;
+            let dest_top = self.track_top(info.dest_track);
             let dest_type = self.timeline.tracks.get(info.dest_track)
                 .map(|t| t.track_type)
                 .unwrap_or(TrackType::Video);
@@ -957,13 +1528,14 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
                 info.effective_start_px,
                 info.effective_width_px.max(4.0),
                 dest_top,
+                self.lane_height(info.dest_track),
                 color,
                 info.effective_duration,
             );
 
             // Linked dragged clips on their destination tracks
             for linked in &info.linked {
-                let linked_top = RULER_HEIGHT + linked.track_index as f32 * TRACK_HEIGHT;
+                let linked_top = self.track_top(linked.track_index);
                 let linked_type = self.timeline.tracks.get(linked.track_index)
                     .map(|t| t.track_type)
                     .unwrap_or(TrackType::Audio);
@@ -973,12 +1545,53 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
                     linked.effective_start_px,
                     linked.effective_width_px.max(4.0),
                     linked_top,
+                    self.lane_height(linked.track_index),
                     linked_color,
                     linked.effective_duration,
                 );
             }
         }
 
+        // Time ruler, drawn after the lanes so scrolled tracks pass under it
+        self.draw_ruler(&mut frame, bounds.width);
+
+        // Guide lines across the track lanes
+        for guide in &self.timeline.guides {
+            let px = self.secs_to_px(guide.as_secs_f64());
+            if px < 0.0 || px > bounds.width {
+                continue;
+            }
+            frame.fill_rectangle(
+                Point::new(px, RULER_HEIGHT),
+                Size::new(1.0, (bounds.height - RULER_HEIGHT).max(0.0)),
+                Color::from_rgba(0.6, 0.4, 1.0, 0.45),
+            );
+        }
+
+        // Snap guide: a vertical line at the time a dragged or resized edge
+        // snapped to
+        let snap_guide = match &state.interaction {
+            TimelineInteraction::Dragging { .. } => drag_preview.as_ref().and_then(|p| p.snap_guide),
+            TimelineInteraction::Resizing { current_x, .. } => {
+                self.snap_resize_end(self.px_to_secs(*current_x)).1
+            }
+            TimelineInteraction::Envelope { .. }
+            | TimelineInteraction::Panning { .. }
+            | TimelineInteraction::None => None,
+        };
+        if let Some(secs) = snap_guide {
+            let px = self.secs_to_px(secs);
+            let mut y = RULER_HEIGHT;
+            while y < bounds.height {
+                frame.fill_rectangle(
+                    Point::new(px - 0.5, y),
+                    Size::new(1.0, 6.0_f32.min(bounds.height - y)),
+                    Color::from_rgb(0.3, 0.9, 1.0),
+                );
+                y += 10.0;
+            }
+        }
+
         // Playhead
         let playhead_px = self.secs_to_px(self.playback_position.as_secs_f64());
         if playhead_px >= 0.0 && playhead_px <= bounds.width {
@@ -1001,11 +1614,10 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
             if let TimelineInteraction::None = &state.interaction {
                 if let Some(cursor_pos) = state.cursor_position {
                     if self.hit_test_clip(cursor_pos.x, cursor_pos.y).is_some() {
-                        let total_track_height =
-                            RULER_HEIGHT + self.timeline.tracks.len() as f32 * TRACK_HEIGHT;
+                        let tracks_bottom = self.track_top(self.timeline.tracks.len());
                         frame.fill_rectangle(
                             Point::new(cursor_pos.x, RULER_HEIGHT),
-                            Size::new(1.0, total_track_height - RULER_HEIGHT),
+                            Size::new(1.0, (tracks_bottom - RULER_HEIGHT).max(0.0)),
                             Color::from_rgb(1.0, 0.6, 0.0),
                         );
                     }
@@ -1027,6 +1639,9 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
             TimelineInteraction::Resizing { .. } => {
                 return mouse::Interaction::ResizingHorizontally
             }
+            TimelineInteraction::Envelope { .. } => return mouse::Interaction::ResizingVertically,
+            TimelineInteraction::Panning { .. } => return mouse::Interaction::Grabbing,
+            TimelineInteraction::None if state.space_held => return mouse::Interaction::Grab,
             TimelineInteraction::None => {}
         }
 
@@ -1041,6 +1656,8 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
                         }
                     }
                     HitZone::RightEdge => mouse::Interaction::ResizingHorizontally,
+                    HitZone::Envelope(Some(_)) => mouse::Interaction::Grab,
+                    HitZone::Envelope(None) => mouse::Interaction::ResizingVertically,
                 };
             }
         }
@@ -1050,6 +1667,45 @@ impl<'a> canvas::Program<Message> for TimelineCanvas<'a> {
 }
 
 impl<'a> TimelineCanvas<'a> {
+    /// Two fingers pinch to zoom about their midpoint and drag to pan; the
+    /// time under the old midpoint ends up under the new one.
+    fn update_touch(
+        &self,
+        state: &mut TimelineCanvasState,
+        event: &touch::Event,
+        bounds: Rectangle,
+    ) -> Option<canvas::Action<Message>> {
+        match *event {
+            touch::Event::FingerPressed { id, position } => {
+                if !bounds.contains(position) || state.touches.len() >= 2 {
+                    return None;
+                }
+                let local = Point::new(position.x - bounds.x, position.y - bounds.y);
+                state.touches.push((id, local));
+                (state.touches.len() == 2).then(canvas::Action::capture)
+            }
+            touch::Event::FingerMoved { id, position } => {
+                let index = state.touches.iter().position(|(finger, _)| *finger == id)?;
+                let before = pinch_span(&state.touches);
+                state.touches[index].1 = Point::new(position.x - bounds.x, position.y - bounds.y);
+                let ((old_mid, old_dist), (new_mid, new_dist)) = before.zip(pinch_span(&state.touches))?;
+                let factor = if old_dist > 1.0 { new_dist / old_dist } else { 1.0 };
+                Some(
+                    canvas::Action::publish(Message::TimelineZoomBy {
+                        factor,
+                        anchor_secs: self.px_to_secs(old_mid),
+                        anchor_px: new_mid,
+                    })
+                    .and_capture(),
+                )
+            }
+            touch::Event::FingerLifted { id, .. } | touch::Event::FingerLost { id, .. } => {
+                state.touches.retain(|(finger, _)| *finger != id);
+                None
+            }
+        }
+    }
+
     fn draw_ruler(&self, frame: &mut canvas::Frame, width: f32) {
         frame.fill_rectangle(
             Point::ORIGIN,
@@ -1104,6 +1760,28 @@ impl<'a> TimelineCanvas<'a> {
             }
             t += tick_interval;
         }
+
+        // Markers: a flag at the bottom of the ruler with the marker name
+        for marker in &self.timeline.markers {
+            let px = self.secs_to_px(marker.position.as_secs_f64());
+            if px < -6.0 || px > width {
+                continue;
+            }
+            let flag = canvas::Path::new(|b| {
+                b.move_to(Point::new(px, RULER_HEIGHT - 8.0));
+                b.line_to(Point::new(px + 6.0, RULER_HEIGHT - 4.0));
+                b.line_to(Point::new(px, RULER_HEIGHT));
+                b.close();
+            });
+            frame.fill(&flag, Color::from_rgb(0.95, 0.75, 0.2));
+            frame.fill_text(canvas::Text {
+                content: marker.name.clone(),
+                position: Point::new(px + 8.0, RULER_HEIGHT - 10.0),
+                color: Color::from_rgb(0.95, 0.75, 0.2),
+                size: iced::Pixels(9.0),
+                ..canvas::Text::default()
+            });
+        }
     }
 }
 
@@ -1136,8 +1814,15 @@ mod tests {
             playback_position: TimelinePosition::zero(),
 
             selected_clip: None,
+            selected_clips: &[],
+            source_library: None,
+            snap_threshold_secs: 0.2,
+            snap_to_markers: true,
+            snap_to_playhead: true,
+            snap_audio_trims: false,
             zoom: 100.0,
             scroll_offset: 0.0,
+            vertical_scroll: 0.0,
             tool_mode: ToolMode::Arrow,
             source_drag: None,
         };
@@ -1153,8 +1838,15 @@ mod tests {
             playback_position: TimelinePosition::zero(),
 
             selected_clip: None,
+            selected_clips: &[],
+            source_library: None,
+            snap_threshold_secs: 0.2,
+            snap_to_markers: true,
+            snap_to_playhead: true,
+            snap_audio_trims: false,
             zoom: 100.0,
             scroll_offset: 50.0,
+            vertical_scroll: 0.0,
             tool_mode: ToolMode::Arrow,
             source_drag: None,
         };
@@ -1170,8 +1862,15 @@ mod tests {
             playback_position: TimelinePosition::zero(),
 
             selected_clip: None,
+            selected_clips: &[],
+            source_library: None,
+            snap_threshold_secs: 0.2,
+            snap_to_markers: true,
+            snap_to_playhead: true,
+            snap_audio_trims: false,
             zoom: 100.0,
             scroll_offset: 0.0,
+            vertical_scroll: 0.0,
             tool_mode: ToolMode::Arrow,
             source_drag: None,
         };
@@ -1182,6 +1881,79 @@ mod tests {
         assert_eq!(zone, HitZone::Body);
     }
 
+    #[test]
+    fn test_hit_test_with_vertical_scroll() {
+        let mut tl = make_test_timeline();
+        tl.add_track("Video 2", TrackType::Video);
+        let asset_id = Uuid::new_v4();
+        let source_range = TimeRange {
+            start: TimelinePosition::zero(),
+            end: TimelinePosition::from_secs_f64(5.0),
+        };
+        tl.add_clip(1, Clip::new(asset_id, TimelinePosition::from_secs_f64(1.0), source_range)).unwrap();
+        let canvas = TimelineCanvas {
+            timeline: &tl,
+            playback_position: TimelinePosition::zero(),
+            selected_clip: None,
+            selected_clips: &[],
+            source_library: None,
+            snap_threshold_secs: 0.2,
+            snap_to_markers: true,
+            snap_to_playhead: true,
+            snap_audio_trims: false,
+            zoom: 100.0,
+            scroll_offset: 0.0,
+            vertical_scroll: TRACK_HEIGHT,
+            tool_mode: ToolMode::Arrow,
+            source_drag: None,
+        };
+        // Scrolled by one track: the first lane under the ruler is track 1.
+        let (track_idx, _, _) = canvas.hit_test_clip(300.0, RULER_HEIGHT + 25.0).unwrap();
+        assert_eq!(track_idx, 1);
+        assert_eq!(canvas.track_top(1), RULER_HEIGHT);
+        // The ruler itself never hits a clip, even with track 0 scrolled under it.
+        assert!(canvas.hit_test_clip(300.0, RULER_HEIGHT - 5.0).is_none());
+    }
+
+    #[test]
+    fn test_expanded_track_moves_lanes_below() {
+        let mut tl = make_test_timeline();
+        tl.add_track("Video 2", TrackType::Video);
+        let asset_id = Uuid::new_v4();
+        let source_range = TimeRange {
+            start: TimelinePosition::zero(),
+            end: TimelinePosition::from_secs_f64(5.0),
+        };
+        tl.add_clip(1, Clip::new(asset_id, TimelinePosition::from_secs_f64(1.0), source_range)).unwrap();
+        tl.tracks[0].height = zeditor_core::timeline::TrackHeight::Double;
+        let canvas = TimelineCanvas {
+            timeline: &tl,
+            playback_position: TimelinePosition::zero(),
+            selected_clip: None,
+            selected_clips: &[],
+            source_library: None,
+            snap_threshold_secs: 0.2,
+            snap_to_markers: true,
+            snap_to_playhead: true,
+            snap_audio_trims: false,
+            zoom: 100.0,
+            scroll_offset: 0.0,
+            vertical_scroll: 0.0,
+            tool_mode: ToolMode::Arrow,
+            source_drag: None,
+        };
+        assert_eq!(canvas.lane_height(0), 2.0 * TRACK_HEIGHT);
+        assert_eq!(canvas.track_top(1), RULER_HEIGHT + 2.0 * TRACK_HEIGHT);
+        // Below the first lane's normal height is still track 0
+        let (track_idx, _, _) = canvas.hit_test_clip(300.0, RULER_HEIGHT + 75.0).unwrap();
+        assert_eq!(track_idx, 0);
+        let (track_idx, _, _) = canvas.hit_test_clip(300.0, RULER_HEIGHT + 125.0).unwrap();
+        assert_eq!(track_idx, 1);
+        assert!(canvas.hit_test_clip(300.0, RULER_HEIGHT + 155.0).is_none());
+        assert_eq!(canvas.track_at_y(RULER_HEIGHT + 500.0), 1);
+        assert_eq!(track_index_at_offset(&tl, 150.0), None);
+    }
+
     #[test]
     fn test_hit_test_right_edge() {
         let tl = make_test_timeline();
@@ -1190,8 +1962,15 @@ mod tests {
             playback_position: TimelinePosition::zero(),
 
             selected_clip: None,
+            selected_clips: &[],
+            source_library: None,
+            snap_threshold_secs: 0.2,
+            snap_to_markers: true,
+            snap_to_playhead: true,
+            snap_audio_trims: false,
             zoom: 100.0,
             scroll_offset: 0.0,
+            vertical_scroll: 0.0,
             tool_mode: ToolMode::Arrow,
             source_drag: None,
         };
@@ -1209,8 +1988,15 @@ mod tests {
             playback_position: TimelinePosition::zero(),
 
             selected_clip: None,
+            selected_clips: &[],
+            source_library: None,
+            snap_threshold_secs: 0.2,
+            snap_to_markers: true,
+            snap_to_playhead: true,
+            snap_audio_trims: false,
             zoom: 100.0,
             scroll_offset: 0.0,
+            vertical_scroll: 0.0,
             tool_mode: ToolMode::Arrow,
             source_drag: None,
         };
@@ -1233,8 +2019,15 @@ mod tests {
             playback_position: TimelinePosition::zero(),
 
             selected_clip: None,
+            selected_clips: &[],
+            source_library: None,
+            snap_threshold_secs: 0.2,
+            snap_to_markers: true,
+            snap_to_playhead: true,
+            snap_audio_trims: false,
             zoom: 100.0,
             scroll_offset: 200.0, // scrolled right, so negative px → negative secs
+            vertical_scroll: 0.0,
             tool_mode: ToolMode::Arrow,
             source_drag: None,
         };
@@ -1257,8 +2050,15 @@ mod tests {
             playback_position: TimelinePosition::zero(),
 
             selected_clip: None,
+            selected_clips: &[],
+            source_library: None,
+            snap_threshold_secs: 0.2,
+            snap_to_markers: true,
+            snap_to_playhead: true,
+            snap_audio_trims: false,
             zoom: 100.0,
             scroll_offset: 0.0,
+            vertical_scroll: 0.0,
             tool_mode: ToolMode::Blade,
             source_drag: None,
         };
@@ -1279,6 +2079,121 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_middle_and_space_drag_pan() {
+        let tl = make_test_timeline();
+        let canvas = TimelineCanvas {
+            timeline: &tl,
+            playback_position: TimelinePosition::zero(),
+
+            selected_clip: None,
+            selected_clips: &[],
+            source_library: None,
+            snap_threshold_secs: 0.2,
+            snap_to_markers: true,
+            snap_to_playhead: true,
+            snap_audio_trims: false,
+            zoom: 100.0,
+            scroll_offset: 0.0,
+            vertical_scroll: 0.0,
+            tool_mode: ToolMode::Arrow,
+            source_drag: None,
+        };
+        let mut state = TimelineCanvasState::default();
+        let bounds = Rectangle::new(Point::ORIGIN, Size::new(800.0, 200.0));
+        // Over the clip, where a plain left press would start a move
+        let over_clip = Point::new(300.0, RULER_HEIGHT + 25.0);
+        let cursor = mouse::Cursor::Available(over_clip);
+
+        let press = canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Middle));
+        assert!(canvas.update(&mut state, &press, bounds, cursor).is_some());
+        let moved_to = Point::new(250.0, RULER_HEIGHT + 20.0);
+        let event = canvas::Event::Mouse(mouse::Event::CursorMoved { position: moved_to });
+        assert!(canvas.update(&mut state, &event, bounds, mouse::Cursor::Available(moved_to)).is_some());
+        assert!(matches!(state.interaction, TimelineInteraction::Panning { last } if last == moved_to));
+        let release = canvas::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Middle));
+        canvas.update(&mut state, &release, bounds, cursor);
+        assert!(matches!(state.interaction, TimelineInteraction::None));
+
+        // Space turns a left drag on a clip into a pan, even with the cursor off the canvas
+        let space = |pressed: bool| {
+            let key = Key::Named(Named::Space);
+            canvas::Event::Keyboard(if pressed {
+                iced::keyboard::Event::KeyPressed {
+                    key: key.clone(),
+                    modified_key: key,
+                    physical_key: iced::keyboard::key::Physical::Unidentified(
+                        iced::keyboard::key::NativeCode::Unidentified,
+                    ),
+                    location: iced::keyboard::Location::Standard,
+                    modifiers: iced::keyboard::Modifiers::empty(),
+                    text: None,
+                    repeat: false,
+                }
+            } else {
+                iced::keyboard::Event::KeyReleased {
+                    key: key.clone(),
+                    modified_key: key,
+                    physical_key: iced::keyboard::key::Physical::Unidentified(
+                        iced::keyboard::key::NativeCode::Unidentified,
+                    ),
+                    location: iced::keyboard::Location::Standard,
+                    modifiers: iced::keyboard::Modifiers::empty(),
+                }
+            })
+        };
+        canvas.update(&mut state, &space(true), bounds, mouse::Cursor::Unavailable);
+        let left = canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left));
+        canvas.update(&mut state, &left, bounds, cursor);
+        assert!(matches!(state.interaction, TimelineInteraction::Panning { .. }));
+        let left_up = canvas::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left));
+        canvas.update(&mut state, &left_up, bounds, cursor);
+        canvas.update(&mut state, &space(false), bounds, mouse::Cursor::Unavailable);
+        canvas.update(&mut state, &left, bounds, cursor);
+        assert!(matches!(state.interaction, TimelineInteraction::Dragging { .. }));
+    }
+
+    #[test]
+    fn test_two_finger_touch_pinches() {
+        let tl = make_test_timeline();
+        let canvas = TimelineCanvas {
+            timeline: &tl,
+            playback_position: TimelinePosition::zero(),
+
+            selected_clip: None,
+            selected_clips: &[],
+            source_library: None,
+            snap_threshold_secs: 0.2,
+            snap_to_markers: true,
+            snap_to_playhead: true,
+            snap_audio_trims: false,
+            zoom: 100.0,
+            scroll_offset: 0.0,
+            vertical_scroll: 0.0,
+            tool_mode: ToolMode::Arrow,
+            source_drag: None,
+        };
+        let mut state = TimelineCanvasState::default();
+        let bounds = Rectangle::new(Point::new(0.0, 100.0), Size::new(800.0, 200.0));
+        let as_event = canvas::Event::Touch;
+        let (a, b) = (touch::Finger(1), touch::Finger(2));
+
+        let first = as_event(touch::Event::FingerPressed { id: a, position: Point::new(200.0, 150.0) });
+        assert!(canvas.update(&mut state, &first, bounds, mouse::Cursor::Unavailable).is_none());
+        let second = as_event(touch::Event::FingerPressed { id: b, position: Point::new(400.0, 150.0) });
+        assert!(canvas.update(&mut state, &second, bounds, mouse::Cursor::Unavailable).is_some());
+        assert_eq!(pinch_span(&state.touches), Some((300.0, 200.0)));
+
+        let spread = as_event(touch::Event::FingerMoved { id: b, position: Point::new(600.0, 150.0) });
+        assert!(canvas.update(&mut state, &spread, bounds, mouse::Cursor::Unavailable).is_some());
+        assert_eq!(pinch_span(&state.touches), Some((400.0, 400.0)));
+
+        let lift = as_event(touch::Event::FingerLifted { id: a, position: Point::new(200.0, 150.0) });
+        canvas.update(&mut state, &lift, bounds, mouse::Cursor::Unavailable);
+        assert_eq!(state.touches.len(), 1);
+        assert!(canvas.update(&mut state, &spread, bounds, mouse::Cursor::Unavailable).is_none());
+    }
+
     #[test]
     fn test_drag_clamp_visual_at_zero() {
         let tl = make_test_timeline();
@@ -1287,8 +2202,15 @@ mod tests {
             playback_position: TimelinePosition::zero(),
 
             selected_clip: None,
+            selected_clips: &[],
+            source_library: None,
+            snap_threshold_secs: 0.2,
+            snap_to_markers: true,
+            snap_to_playhead: true,
+            snap_audio_trims: false,
             zoom: 100.0,
             scroll_offset: 0.0,
+            vertical_scroll: 0.0,
             tool_mode: ToolMode::Arrow,
             source_drag: None,
         };
@@ -1359,8 +2281,15 @@ mod tests {
             playback_position: TimelinePosition::zero(),
 
             selected_clip: None,
+            selected_clips: &[],
+            source_library: None,
+            snap_threshold_secs: 0.2,
+            snap_to_markers: true,
+            snap_to_playhead: true,
+            snap_audio_trims: false,
             zoom: 100.0,
             scroll_offset: 0.0,
+            vertical_scroll: 0.0,
             tool_mode: ToolMode::Arrow,
             source_drag: None,
         };
@@ -1415,8 +2344,15 @@ mod tests {
             playback_position: TimelinePosition::zero(),
 
             selected_clip: None,
+            selected_clips: &[],
+            source_library: None,
+            snap_threshold_secs: 0.2,
+            snap_to_markers: true,
+            snap_to_playhead: true,
+            snap_audio_trims: false,
             zoom: 100.0,
             scroll_offset: 0.0,
+            vertical_scroll: 0.0,
             tool_mode: ToolMode::Arrow,
             source_drag: None,
         };
@@ -1459,8 +2395,15 @@ mod tests {
             playback_position: TimelinePosition::zero(),
 
             selected_clip: None,
+            selected_clips: &[],
+            source_library: None,
+            snap_threshold_secs: 0.2,
+            snap_to_markers: true,
+            snap_to_playhead: true,
+            snap_audio_trims: false,
             zoom: 100.0,
             scroll_offset: 0.0,
+            vertical_scroll: 0.0,
             tool_mode: ToolMode::Arrow,
             source_drag: None,
         };
@@ -1498,4 +2441,148 @@ mod tests {
         assert!(color.b > color.r, "audio track blue should be > red");
         assert!(color.b > color.g, "audio track blue should be > green");
     }
+
+    #[test]
+    fn test_link_accent_color_is_stable_per_id() {
+        let a = Uuid::from_u128(7);
+        let b = Uuid::from_u128(127);
+        assert_eq!(link_accent_color(a), link_accent_color(a));
+        assert_ne!(link_accent_color(a), link_accent_color(b));
+        let c = link_accent_color(b);
+        assert!(c.r.min(c.g).min(c.b) >= 0.35);
+    }
+
+    #[test]
+    fn test_drag_and_resize_snap_to_markers_and_playhead() {
+        let mut tl = make_test_timeline();
+        tl.add_marker(TimelinePosition::from_secs_f64(10.0), "Chapter");
+        let clip_id = tl.tracks[0].clips[0].id;
+        let mut canvas = TimelineCanvas {
+            timeline: &tl,
+            playback_position: TimelinePosition::from_secs_f64(20.0),
+            selected_clip: None,
+            selected_clips: &[],
+            source_library: None,
+            snap_threshold_secs: 0.2,
+            snap_to_markers: true,
+            snap_to_playhead: true,
+            snap_audio_trims: false,
+            zoom: 100.0,
+            scroll_offset: 0.0,
+            vertical_scroll: 0.0,
+            tool_mode: ToolMode::Arrow,
+            source_drag: None,
+        };
+
+        // Start edge onto the marker, end edge onto the playhead
+        assert_eq!(canvas.compute_snapped_start(10.1, 5.0, 0, clip_id), (10.0, Some(10.0)));
+        let (start, guide) = canvas.compute_snapped_start(14.9, 5.0, 0, clip_id);
+        assert!((start - 15.0).abs() < 1e-9);
+        assert_eq!(guide, Some(20.0));
+        assert_eq!(canvas.snap_resize_end(9.95), (10.0, Some(10.0)));
+
+        canvas.snap_to_markers = false;
+        assert_eq!(canvas.compute_snapped_start(10.1, 5.0, 0, clip_id), (10.1, None));
+        assert_eq!(canvas.snap_resize_end(9.95), (9.95, None));
+    }
+
+    #[test]
+    fn test_clip_markers_snap_except_when_dragging_their_clip() {
+        let mut tl = make_test_timeline();
+        let clip_id = tl.tracks[0].clips[0].id;
+        tl.add_clip_marker(0, clip_id, TimelinePosition::from_secs_f64(3.0), "Best take").unwrap();
+        let mut canvas = TimelineCanvas {
+            timeline: &tl,
+            playback_position: TimelinePosition::zero(),
+            selected_clip: None,
+            selected_clips: &[],
+            source_library: None,
+            snap_threshold_secs: 0.2,
+            snap_to_markers: true,
+            snap_to_playhead: false,
+            snap_audio_trims: false,
+            zoom: 100.0,
+            scroll_offset: 0.0,
+            vertical_scroll: 0.0,
+            tool_mode: ToolMode::Arrow,
+            source_drag: None,
+        };
+        assert_eq!(canvas.snap_points(), vec![3.0]);
+        assert!(canvas.snap_points_excluding(Some(clip_id)).is_empty());
+        canvas.snap_to_markers = false;
+        assert!(canvas.snap_points().is_empty());
+    }
+
+    #[test]
+    fn test_guides_always_snap() {
+        let mut tl = make_test_timeline();
+        tl.add_guide(TimelinePosition::from_secs_f64(12.0));
+        let clip_id = tl.tracks[0].clips[0].id;
+        let canvas = TimelineCanvas {
+            timeline: &tl,
+            playback_position: TimelinePosition::zero(),
+            selected_clip: None,
+            selected_clips: &[],
+            source_library: None,
+            snap_threshold_secs: 0.2,
+            snap_to_markers: false,
+            snap_to_playhead: false,
+            snap_audio_trims: false,
+            zoom: 100.0,
+            scroll_offset: 0.0,
+            vertical_scroll: 0.0,
+            tool_mode: ToolMode::Arrow,
+            source_drag: None,
+        };
+        assert_eq!(canvas.snap_points(), vec![12.0]);
+        assert_eq!(canvas.compute_snapped_start(7.1, 5.0, 0, clip_id), (7.0, Some(12.0)));
+    }
+
+    #[test]
+    fn test_envelope_line_hit_and_drag() {
+        let tl = make_grouped_timeline();
+        let audio_clip_id = tl.tracks[1].clips[0].id;
+        let canvas = TimelineCanvas {
+            timeline: &tl,
+            playback_position: TimelinePosition::zero(),
+
+            selected_clip: None,
+            selected_clips: &[],
+            source_library: None,
+            snap_threshold_secs: 0.2,
+            snap_to_markers: true,
+            snap_to_playhead: true,
+            snap_audio_trims: false,
+            zoom: 100.0,
+            scroll_offset: 0.0,
+            vertical_scroll: 0.0,
+            tool_mode: ToolMode::Arrow,
+            source_drag: None,
+        };
+
+        // With no points the line sits at 0 dB across the audio clip
+        let line_y = canvas.gain_to_y(1, 0.0);
+        let (_, _, zone) = canvas.hit_test_clip(300.0, line_y).unwrap();
+        assert_eq!(zone, HitZone::Envelope(None));
+        let (_, _, zone) = canvas.hit_test_clip(300.0, line_y + 15.0).unwrap();
+        assert_eq!(zone, HitZone::Body);
+        // Video clips have no envelope
+        let (_, _, zone) = canvas.hit_test_clip(300.0, canvas.gain_to_y(0, 0.0)).unwrap();
+        assert_eq!(zone, HitZone::Body);
+
+        // A click adds a point on the line at the clip's source time (3s on
+        // the timeline is 2s into the source)
+        let press = Point::new(300.0, line_y);
+        let clicked = canvas.dragged_envelope(1, audio_clip_id, None, press, press).unwrap();
+        assert_eq!(clicked.points.len(), 1);
+        assert!((clicked.points[0].time - 2.0).abs() < 1e-6);
+        assert_eq!(clicked.points[0].gain_db, 0.0);
+
+        // Dragging the line down lowers the whole clip's gain
+        let lowered = canvas
+            .dragged_envelope(1, audio_clip_id, None, press, Point::new(300.0, canvas.gain_to_y(1, -12.0)))
+            .unwrap();
+        assert!((lowered.gain_db_at(0.0) + 12.0).abs() < 1e-3);
+        assert!((lowered.gain_db_at(5.0) + 12.0).abs() < 1e-3);
+    }
 }
@@ -0,0 +1,217 @@
+use iced::widget::{button, column, container, row, text};
+use iced::{Background, Border, Color, Element};
+use zeditor_core::timeline::{Timeline, Track, TrackType};
+
+use crate::message::{Message, TrackFlag};
+
+/// Narrowest header: room for the name plus the toggle buttons.
+pub const TRACK_HEADER_MIN_WIDTH: f32 = 96.0;
+/// Headers never grow past this, however long a track name is.
+pub const TRACK_HEADER_MAX_WIDTH: f32 = 180.0;
+/// Approximate advance of one character of the 12px name label.
+const NAME_CHAR_WIDTH: f32 = 7.0;
+const HEADER_PADDING: f32 = 6.0;
+const METER_HEIGHT: f32 = 3.0;
+
+/// Header column width, sized to fit the longest track name. The timeline
+/// canvas starts right after it, so drop/hit math must use the same width.
+pub fn header_width(timeline: &Timeline) -> f32 {
+    let longest = timeline
+        .tracks
+        .iter()
+        .map(|t| t.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    (longest as f32 * NAME_CHAR_WIDTH + HEADER_PADDING * 2.0)
+        .clamp(TRACK_HEADER_MIN_WIDTH, TRACK_HEADER_MAX_WIDTH)
+}
+
+/// Flags shown for a track type, in display order.
+pub fn flags_for(track_type: TrackType) -> &'static [TrackFlag] {
+    match track_type {
+        TrackType::Video => &[TrackFlag::Visible, TrackFlag::Lock],
+        TrackType::Audio => &[TrackFlag::Mute, TrackFlag::Solo, TrackFlag::Lock],
+    }
+}
+
+/// Whether the flag's button is drawn "on" (visibility is on while not hidden).
+pub fn flag_active(track: &Track, flag: TrackFlag) -> bool {
+    match flag {
+        TrackFlag::Mute => track.muted,
+        TrackFlag::Solo => track.solo,
+        TrackFlag::Visible => !track.hidden,
+        TrackFlag::Lock => track.locked,
+    }
+}
+
+fn flag_label(flag: TrackFlag) -> &'static str {
+    match flag {
+        TrackFlag::Mute => "M",
+        TrackFlag::Solo => "S",
+        TrackFlag::Visible => "V",
+        TrackFlag::Lock => "L",
+    }
+}
+
+fn flag_color(flag: TrackFlag) -> Color {
+    match flag {
+        TrackFlag::Mute => Color::from_rgb(0.85, 0.35, 0.3),
+        TrackFlag::Solo => Color::from_rgb(0.9, 0.75, 0.2),
+        TrackFlag::Visible => Color::from_rgb(0.3, 0.6, 0.9),
+        TrackFlag::Lock => Color::from_rgb(0.6, 0.6, 0.65),
+    }
+}
+
+pub fn background(track_type: TrackType) -> Color {
+    match track_type {
+        TrackType::Video => Color::from_rgb(0.16, 0.18, 0.16),
+        TrackType::Audio => Color::from_rgb(0.14, 0.16, 0.20),
+    }
+}
+
+/// The visible sliver of a header that is partly scrolled out of view. Drawn
+/// without controls so the headers below stay aligned with their lanes.
+pub fn view_partial<'a>(track: &Track, width: f32, height: f32) -> Element<'a, Message> {
+    let bg_color = background(track.track_type);
+    container(iced::widget::Space::new())
+        .width(width)
+        .height(height)
+        .style(move |_theme| container::Style {
+            background: Some(Background::Color(bg_color)),
+            ..Default::default()
+        })
+        .into()
+}
+
+/// Meter colour: green, turning yellow then red approaching full scale.
+fn meter_color(level: f32) -> Color {
+    if level > 0.95 {
+        Color::from_rgb(0.9, 0.25, 0.2)
+    } else if level > 0.8 {
+        Color::from_rgb(0.9, 0.8, 0.2)
+    } else {
+        Color::from_rgb(0.3, 0.8, 0.35)
+    }
+}
+
+/// Thin horizontal level meter filled to `level` (0.0 to 1.0) of `width`.
+fn level_meter<'a>(level: f32, width: f32) -> Element<'a, Message> {
+    let fill = container(iced::widget::Space::new())
+        .width(width * level.clamp(0.0, 1.0))
+        .height(METER_HEIGHT)
+        .style(move |_theme| container::Style {
+            background: Some(Background::Color(meter_color(level))),
+            ..Default::default()
+        });
+    container(fill)
+        .width(width)
+        .height(METER_HEIGHT)
+        .style(|_theme| container::Style {
+            background: Some(Background::Color(Color::from_rgb(0.08, 0.08, 0.1))),
+            ..Default::default()
+        })
+        .into()
+}
+
+/// Track name plus its toggle buttons. Audio tracks get a level meter when
+/// `level` is given.
+pub fn view<'a>(
+    track_index: usize,
+    track: &'a Track,
+    width: f32,
+    height: f32,
+    level: Option<f32>,
+) -> Element<'a, Message> {
+    let buttons = flags_for(track.track_type).iter().map(|&flag| {
+        let active = flag_active(track, flag);
+        let on_color = flag_color(flag);
+        button(text(flag_label(flag)).size(10))
+            .on_press(Message::ToggleTrackFlag { track_index, flag })
+            .padding([1, 5])
+            .style(move |_theme, _status| button::Style {
+                background: Some(Background::Color(if active {
+                    on_color
+                } else {
+                    Color::from_rgb(0.25, 0.25, 0.28)
+                })),
+                text_color: if active { Color::BLACK } else { Color::from_rgb(0.7, 0.7, 0.7) },
+                border: Border {
+                    radius: 3.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .into()
+    });
+
+    // Cycles the lane through 1x, 2x and 3x height
+    let height_button = button(text(track.height.display_name()).size(10))
+        .on_press(Message::SetTrackHeight { track_index, height: track.height.next() })
+        .padding([1, 5])
+        .style(|_theme, _status| button::Style {
+            background: Some(Background::Color(Color::from_rgb(0.25, 0.25, 0.28))),
+            text_color: Color::from_rgb(0.7, 0.7, 0.7),
+            border: Border {
+                radius: 3.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+    let name_color = if track.locked {
+        Color::from_rgb(0.55, 0.55, 0.55)
+    } else {
+        Color::from_rgb(0.8, 0.8, 0.8)
+    };
+    let bg_color = background(track.track_type);
+
+    let mut content = column![
+        text(&track.name).size(12).color(name_color),
+        row(buttons).push(height_button).spacing(3),
+    ]
+    .spacing(4);
+    if let Some(level) = level.filter(|_| track.track_type == TrackType::Audio) {
+        content = content.push(level_meter(level, width - HEADER_PADDING * 2.0));
+    }
+
+    container(content)
+    .padding([4.0, HEADER_PADDING])
+    .width(width)
+    .height(height)
+    .clip(true)
+    .style(move |_theme| container::Style {
+        background: Some(Background::Color(bg_color)),
+        ..Default::default()
+    })
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_width_fits_longest_name_within_limits() {
+        let mut timeline = Timeline::new();
+        timeline.add_track("V1", TrackType::Video);
+        assert_eq!(header_width(&timeline), TRACK_HEADER_MIN_WIDTH);
+
+        timeline.add_track("Dialogue (boom mic)", TrackType::Audio);
+        let width = header_width(&timeline);
+        assert!(width > TRACK_HEADER_MIN_WIDTH && width <= TRACK_HEADER_MAX_WIDTH);
+
+        timeline.add_track("x".repeat(100), TrackType::Audio);
+        assert_eq!(header_width(&timeline), TRACK_HEADER_MAX_WIDTH);
+    }
+
+    #[test]
+    fn test_flags_per_track_type() {
+        assert_eq!(flags_for(TrackType::Video), &[TrackFlag::Visible, TrackFlag::Lock]);
+        assert!(flags_for(TrackType::Audio).contains(&TrackFlag::Solo));
+
+        let mut track = Track::video("V1");
+        assert!(flag_active(&track, TrackFlag::Visible));
+        track.hidden = true;
+        assert!(!flag_active(&track, TrackFlag::Visible));
+    }
+}
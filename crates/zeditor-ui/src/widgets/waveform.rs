@@ -0,0 +1,253 @@
+use iced::mouse;
+use iced::widget::canvas;
+use iced::{Color, Point, Rectangle, Renderer, Size, Theme};
+use uuid::Uuid;
+use zeditor_core::timeline::{TimeRange, TimelinePosition};
+
+use crate::message::Message;
+
+/// Number of peak buckets loaded for the inspector waveform.
+pub const WAVEFORM_BUCKETS: usize = 400;
+/// How close (in pixels) a press must be to an in/out handle to grab it.
+pub const HANDLE_GRAB_PX: f32 = 6.0;
+/// Shortest source range the handles can be dragged to, in seconds.
+pub const MIN_SOURCE_SECS: f64 = 0.1;
+
+/// Peaks of a stretch of source audio around a clip's in/out points, loaded
+/// for the clip inspector. `window_start`/`window_end` are source seconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InspectorWaveform {
+    pub clip_id: Uuid,
+    pub window_start: f64,
+    pub window_end: f64,
+    pub peaks: Vec<f32>,
+}
+
+/// Source window shown for a clip's `source` range: the range padded by a
+/// quarter of its length (at least a second) on each side, clamped to the
+/// asset.
+pub fn waveform_window(source: TimeRange, asset_secs: f64) -> (f64, f64) {
+    let start = source.start.as_secs_f64();
+    let end = source.end.as_secs_f64();
+    let pad = ((end - start) * 0.25).max(1.0);
+    ((start - pad).max(0.0), (end + pad).min(asset_secs.max(end)))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaveformHandle {
+    In,
+    Out,
+}
+
+#[derive(Debug, Default)]
+pub struct WaveformState {
+    /// Handle being dragged and its current source time.
+    drag: Option<(WaveformHandle, f64)>,
+}
+
+/// Zoomed waveform of a clip's source with draggable in/out handles.
+/// Releasing a handle publishes `SetClipSourceRange`.
+pub struct WaveformEditor<'a> {
+    pub waveform: &'a InspectorWaveform,
+    pub track_index: usize,
+    pub clip_id: Uuid,
+    pub source: TimeRange,
+}
+
+impl WaveformEditor<'_> {
+    fn secs_to_x(&self, secs: f64, width: f32) -> f32 {
+        let span = (self.waveform.window_end - self.waveform.window_start).max(f64::EPSILON);
+        ((secs - self.waveform.window_start) / span) as f32 * width
+    }
+
+    fn x_to_secs(&self, x: f32, width: f32) -> f64 {
+        let span = self.waveform.window_end - self.waveform.window_start;
+        self.waveform.window_start + (x / width.max(1.0)) as f64 * span
+    }
+
+    /// In and out points as drawn, with a handle drag applied.
+    fn in_out(&self, state: &WaveformState) -> (f64, f64) {
+        let (mut start, mut end) = (self.source.start.as_secs_f64(), self.source.end.as_secs_f64());
+        match state.drag {
+            Some((WaveformHandle::In, secs)) => start = secs,
+            Some((WaveformHandle::Out, secs)) => end = secs,
+            None => {}
+        }
+        (start, end)
+    }
+
+    fn handle_at(&self, x: f32, width: f32) -> Option<WaveformHandle> {
+        let (start, end) = (self.source.start.as_secs_f64(), self.source.end.as_secs_f64());
+        let in_dist = (self.secs_to_x(start, width) - x).abs();
+        let out_dist = (self.secs_to_x(end, width) - x).abs();
+        if in_dist.min(out_dist) > HANDLE_GRAB_PX {
+            None
+        } else if in_dist <= out_dist {
+            Some(WaveformHandle::In)
+        } else {
+            Some(WaveformHandle::Out)
+        }
+    }
+}
+
+impl canvas::Program<Message> for WaveformEditor<'_> {
+    type State = WaveformState;
+
+    fn update(
+        &self,
+        state: &mut Self::State,
+        event: &canvas::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Message>> {
+        let width = bounds.width;
+        match event {
+            canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                let pos = cursor.position_in(bounds)?;
+                let handle = self.handle_at(pos.x, width)?;
+                let (start, end) = self.in_out(state);
+                state.drag = Some((handle, if handle == WaveformHandle::In { start } else { end }));
+                Some(canvas::Action::request_redraw().and_capture())
+            }
+            canvas::Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                let (handle, _) = state.drag?;
+                let secs = self.x_to_secs(position.x - bounds.x, width);
+                let (start, end) = (self.source.start.as_secs_f64(), self.source.end.as_secs_f64());
+                let secs = match handle {
+                    WaveformHandle::In => secs.clamp(self.waveform.window_start, end - MIN_SOURCE_SECS),
+                    WaveformHandle::Out => secs.clamp(start + MIN_SOURCE_SECS, self.waveform.window_end),
+                };
+                state.drag = Some((handle, secs));
+                Some(canvas::Action::request_redraw().and_capture())
+            }
+            canvas::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                state.drag?;
+                let (start, end) = self.in_out(state);
+                state.drag = None;
+                let source = TimeRange::new(
+                    TimelinePosition::from_secs_f64(start.max(0.0)),
+                    TimelinePosition::from_secs_f64(end.max(0.0)),
+                )
+                .ok()?;
+                if source == self.source {
+                    return Some(canvas::Action::request_redraw().and_capture());
+                }
+                Some(
+                    canvas::Action::publish(Message::SetClipSourceRange {
+                        track_index: self.track_index,
+                        clip_id: self.clip_id,
+                        source,
+                    })
+                    .and_capture(),
+                )
+            }
+            _ => None,
+        }
+    }
+
+    fn draw(
+        &self,
+        state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let Size { width, height } = bounds.size();
+        frame.fill_rectangle(Point::ORIGIN, bounds.size(), Color::from_rgb(0.12, 0.12, 0.14));
+
+        let mid = height / 2.0;
+        let bar_w = width / self.waveform.peaks.len().max(1) as f32;
+        for (i, peak) in self.waveform.peaks.iter().enumerate() {
+            let h = (peak.clamp(0.0, 1.0) * mid).max(0.5);
+            frame.fill_rectangle(
+                Point::new(i as f32 * bar_w, mid - h),
+                Size::new(bar_w.max(1.0), h * 2.0),
+                Color::from_rgb(0.35, 0.6, 0.9),
+            );
+        }
+
+        // Shade what falls outside the clip and draw the handles.
+        let (start, end) = self.in_out(state);
+        let in_x = self.secs_to_x(start, width).clamp(0.0, width);
+        let out_x = self.secs_to_x(end, width).clamp(0.0, width);
+        let shade = Color::from_rgba(0.0, 0.0, 0.0, 0.55);
+        frame.fill_rectangle(Point::ORIGIN, Size::new(in_x, height), shade);
+        frame.fill_rectangle(Point::new(out_x, 0.0), Size::new(width - out_x, height), shade);
+        let handle_color = Color::from_rgb(1.0, 0.85, 0.2);
+        for x in [in_x, out_x] {
+            frame.fill_rectangle(Point::new(x - 1.0, 0.0), Size::new(2.0, height), handle_color);
+        }
+
+        vec![frame.into_geometry()]
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        if state.drag.is_some() {
+            return mouse::Interaction::ResizingHorizontally;
+        }
+        match cursor.position_in(bounds) {
+            Some(pos) if self.handle_at(pos.x, bounds.width).is_some() => mouse::Interaction::ResizingHorizontally,
+            _ => mouse::Interaction::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: f64, end: f64) -> TimeRange {
+        TimeRange::new(TimelinePosition::from_secs_f64(start), TimelinePosition::from_secs_f64(end)).unwrap()
+    }
+
+    #[test]
+    fn test_waveform_window_pads_and_clamps() {
+        assert_eq!(waveform_window(range(10.0, 18.0), 60.0), (8.0, 20.0));
+        // Short ranges get at least a second either side, clamped to the asset.
+        assert_eq!(waveform_window(range(0.5, 2.0), 2.5), (0.0, 2.5));
+    }
+
+    #[test]
+    fn test_dragging_in_handle_publishes_new_source() {
+        let waveform = InspectorWaveform { clip_id: Uuid::new_v4(), window_start: 0.0, window_end: 10.0, peaks: vec![0.5; 10] };
+        let editor = WaveformEditor { waveform: &waveform, track_index: 1, clip_id: waveform.clip_id, source: range(2.0, 8.0) };
+        let bounds = Rectangle::new(Point::ORIGIN, Size::new(100.0, 40.0));
+        let mut state = WaveformState::default();
+
+        let press = canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left));
+        assert!(editor.update(&mut state, &press, bounds, mouse::Cursor::Available(Point::new(21.0, 20.0))).is_some());
+        assert_eq!(state.drag, Some((WaveformHandle::In, 2.0)));
+
+        // Past the out handle the in point stops just short of it.
+        let position = Point::new(95.0, 20.0);
+        let moved = canvas::Event::Mouse(mouse::Event::CursorMoved { position });
+        editor.update(&mut state, &moved, bounds, mouse::Cursor::Available(position));
+        let (_, secs) = state.drag.unwrap();
+        assert!((secs - (8.0 - MIN_SOURCE_SECS)).abs() < 1e-9);
+
+        let position = Point::new(40.0, 20.0);
+        let moved = canvas::Event::Mouse(mouse::Event::CursorMoved { position });
+        editor.update(&mut state, &moved, bounds, mouse::Cursor::Available(position));
+        let release = canvas::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left));
+        editor.update(&mut state, &release, bounds, mouse::Cursor::Available(position));
+        assert_eq!(state.drag, None);
+    }
+
+    #[test]
+    fn test_press_away_from_handles_is_ignored() {
+        let waveform = InspectorWaveform { clip_id: Uuid::new_v4(), window_start: 0.0, window_end: 10.0, peaks: Vec::new() };
+        let editor = WaveformEditor { waveform: &waveform, track_index: 0, clip_id: waveform.clip_id, source: range(2.0, 8.0) };
+        let bounds = Rectangle::new(Point::ORIGIN, Size::new(100.0, 40.0));
+        let mut state = WaveformState::default();
+        let press = canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left));
+        assert!(editor.update(&mut state, &press, bounds, mouse::Cursor::Available(Point::new(50.0, 20.0))).is_none());
+        assert_eq!(state.drag, None);
+    }
+}
@@ -1,13 +1,16 @@
 #![allow(unused_must_use)]
 
 use std::path::PathBuf;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use zeditor_core::audio::{ChannelMap, VolumeEnvelope};
 use zeditor_core::effects::EffectType;
+use zeditor_core::health::HealthIssueKind;
 use zeditor_core::media::MediaAsset;
-use zeditor_core::timeline::TimelinePosition;
+use zeditor_core::scaler::CanvasFit;
+use zeditor_core::timeline::{RateConform, TimeRange, TimelinePosition};
 use zeditor_ui::app::App;
-use zeditor_ui::message::{MenuAction, MenuId, Message, ToolMode};
+use zeditor_ui::message::{DialogInput, MenuAction, MenuId, Message, SnapTarget, ToolMode};
 
 fn make_test_asset(name: &str, duration_secs: f64) -> MediaAsset {
     MediaAsset::new(
@@ -46,199 +49,2512 @@ fn test_import_media_error() {
     assert!(app.status_message.contains("Import failed"));
 }
 
+#[test]
+fn test_import_duplicate_selects_existing_asset() {
+    let mut app = App::new();
+    let mut asset = make_test_asset("clip1", 5.0);
+    asset.content_hash = Some(7);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+
+    // Same path again
+    app.update(Message::MediaImported(Ok(make_test_asset("clip1", 5.0))));
+    assert_eq!(app.project.source_library.len(), 1);
+    assert_eq!(app.selected_asset_id, Some(asset_id));
+    assert!(app.status_message.contains("Already imported"));
+
+    // Same contents under another name
+    let mut copy = make_test_asset("copy", 5.0);
+    copy.content_hash = Some(7);
+    app.update(Message::MediaImported(Ok(copy)));
+    assert_eq!(app.project.source_library.len(), 1);
+
+    // Folder imports skip the duplicate and count it
+    app.update(Message::FolderScanned { bin: "Day 1".into(), result: Ok(vec![PathBuf::from("/test/clip1.mp4")]) });
+    app.update(Message::FolderMediaImported { bin: "Day 1".into(), result: Ok(make_test_asset("clip1", 5.0)) });
+    assert_eq!(app.project.source_library.len(), 1);
+    assert!(app.status_message.contains("1 already imported"));
+}
+
+#[test]
+fn test_import_hashes_only_when_a_file_size_matches() {
+    let mut app = App::new();
+    let mut first = make_test_asset("clip1", 5.0);
+    first.file_size = Some(1024);
+    let first_id = first.id;
+    app.update(Message::MediaImported(Ok(first)));
+    let mut other_size = make_test_asset("other", 5.0);
+    other_size.file_size = Some(2048);
+    app.update(Message::MediaImported(Ok(other_size)));
+    assert_eq!(app.project.source_library.len(), 2);
+
+    // Same size under another path: held back until it is hashed
+    let mut copy = make_test_asset("copy", 5.0);
+    copy.file_size = Some(1024);
+    app.update(Message::MediaImported(Ok(copy.clone())));
+    assert_eq!(app.project.source_library.len(), 2);
+
+    copy.content_hash = Some(7);
+    app.update(Message::ImportHashed { asset: copy, hashes: vec![(first_id, 7)], bin: None });
+    assert_eq!(app.project.source_library.len(), 2);
+    assert_eq!(app.project.source_library.get(first_id).unwrap().content_hash, Some(7));
+    assert_eq!(app.selected_asset_id, Some(first_id));
+    assert!(app.status_message.contains("Already imported"));
+}
+
+#[test]
+fn test_import_folder_into_bin() {
+    let mut app = App::new();
+    app.update(Message::FolderScanned {
+        bin: "Day 1".into(),
+        result: Ok(vec![PathBuf::from("/test/a.mp4"), PathBuf::from("/test/b.mp4")]),
+    });
+    assert_eq!(app.folder_import.as_ref().map(|p| p.total), Some(2));
+
+    let asset = make_test_asset("a", 5.0);
+    let asset_id = asset.id;
+    app.update(Message::FolderMediaImported { bin: "Day 1".into(), result: Ok(asset) });
+    assert_eq!(app.folder_import.as_ref().map(|p| p.done), Some(1));
+    assert_eq!(
+        app.project.source_library.get(asset_id).unwrap().bin.as_deref(),
+        Some("Day 1")
+    );
+
+    app.update(Message::FolderMediaImported { bin: "Day 1".into(), result: Err("bad file".into()) });
+    assert!(app.folder_import.is_none());
+    assert_eq!(app.project.source_library.len(), 1);
+    assert!(app.status_message.contains("1 failed"));
+
+    // The bin chip narrows the library to the folder's assets
+    app.update(Message::MediaImported(Ok(make_test_asset("loose", 5.0))));
+    app.update(Message::SetLibraryBinFilter("Day 1".into()));
+    assert_eq!(app.library_filter.bin.as_deref(), Some("Day 1"));
+    app.update(Message::SetLibraryBinFilter("Day 1".into()));
+    assert!(app.library_filter.bin.is_none());
+}
+
+#[test]
+fn test_import_folder_without_media() {
+    let mut app = App::new();
+    app.update(Message::FolderScanned { bin: "Empty".into(), result: Ok(Vec::new()) });
+    assert!(app.folder_import.is_none());
+    assert!(app.status_message.contains("No supported media"));
+}
+
 #[test]
 fn test_add_clip_to_timeline() {
     let mut app = App::new();
 
-    let asset = make_test_asset("clip1", 5.0);
+    let asset = make_test_asset("clip1", 5.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 1);
+    assert_eq!(app.status_message, "Clip added");
+}
+
+#[test]
+fn test_add_still_uses_preferred_duration() {
+    let mut app = App::new();
+    app.preferences.still_duration_secs = 3.0;
+    let still = MediaAsset::new(
+        "logo".into(),
+        PathBuf::from("/test/logo.png"),
+        Duration::from_millis(40),
+        800,
+        600,
+        25.0,
+        false,
+    );
+    let asset_id = still.id;
+    app.update(Message::MediaImported(Ok(still)));
+
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+
+    let clip = &app.project.timeline.tracks[0].clips[0];
+    assert_eq!(clip.duration(), Duration::from_secs(3));
+}
+
+#[test]
+fn test_add_clip_nonexistent_asset() {
+    let mut app = App::new();
+
+    app.update(Message::AddClipToTimeline {
+        asset_id: uuid::Uuid::new_v4(),
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 0);
+    assert_eq!(app.status_message, "Asset not found");
+}
+
+#[test]
+fn test_cut_clip_flow() {
+    let mut app = App::new();
+
+    let asset = make_test_asset("clip1", 10.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+
+    app.update(Message::CutClip {
+        track_index: 0,
+        position: TimelinePosition::from_secs_f64(4.0),
+    });
+
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 2);
+    assert_eq!(app.status_message, "Clip cut");
+}
+
+#[test]
+fn test_undo_redo_flow() {
+    let mut app = App::new();
+
+    let asset = make_test_asset("clip1", 5.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 1);
+
+    app.update(Message::Undo);
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 0);
+    assert_eq!(app.status_message, "Undone");
+
+    app.update(Message::Redo);
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 1);
+    assert_eq!(app.status_message, "Redone");
+}
+
+#[test]
+fn test_play_pause() {
+    let mut app = App::new();
+    assert!(!app.is_playing);
+
+    app.update(Message::Play);
+    assert!(app.is_playing);
+    assert!(app.playback_start_wall.is_some());
+
+    app.update(Message::Pause);
+    assert!(!app.is_playing);
+    assert!(app.playback_start_wall.is_none());
+}
+
+#[test]
+fn test_seek() {
+    let mut app = App::new();
+    assert_eq!(app.playback_position, TimelinePosition::zero());
+
+    app.update(Message::SeekTo(TimelinePosition::from_secs_f64(5.0)));
+    assert_eq!(
+        app.playback_position,
+        TimelinePosition::from_secs_f64(5.0)
+    );
+}
+
+#[test]
+fn test_timecode_field_seeks() {
+    let mut app = App::new();
+    app.project.settings.fps = 25.0;
+    app.update(Message::TimecodeInputChanged("00:01:23:10".into()));
+    app.update(Message::TimecodeSubmitted);
+    assert_eq!(app.playback_position, TimelinePosition::from_secs_f64(83.4));
+    assert!(app.timecode_input.is_none());
+
+    // Relative to the playhead
+    app.update(Message::TimecodeInputChanged("-1:00".into()));
+    app.update(Message::TimecodeSubmitted);
+    assert_eq!(app.playback_position, TimelinePosition::from_secs_f64(82.4));
+
+    app.update(Message::TimecodeInputChanged("soon".into()));
+    app.update(Message::TimecodeSubmitted);
+    assert_eq!(app.playback_position, TimelinePosition::from_secs_f64(82.4));
+    assert!(app.status_message.contains("Not a timecode"));
+}
+
+#[test]
+fn test_remove_asset() {
+    let mut app = App::new();
+
+    let asset = make_test_asset("clip1", 5.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    assert_eq!(app.project.source_library.len(), 1);
+
+    app.update(Message::RemoveAsset(asset_id));
+    assert_eq!(app.project.source_library.len(), 0);
+    assert!(app.status_message.contains("Removed"));
+}
+
+#[test]
+fn test_undo_restores_removed_asset_and_its_clips() {
+    let (mut app, asset_id, _) = setup_app_with_clip();
+    app.selected_asset_id = Some(asset_id);
+
+    app.update(Message::RemoveAsset(asset_id));
+    assert!(app.project.source_library.get(asset_id).is_none());
+    assert!(app.project.timeline.tracks.iter().all(|t| t.clips.is_empty()));
+
+    app.update(Message::Undo);
+    assert!(app.project.source_library.get(asset_id).is_some());
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 1);
+    assert_eq!(app.project.timeline.tracks[1].clips.len(), 1);
+
+    app.update(Message::Redo);
+    assert!(app.project.source_library.get(asset_id).is_none());
+    assert!(app.selected_asset_id.is_none());
+
+    // Undoing the import as well empties the library
+    app.update(Message::Undo);
+    app.update(Message::Undo);
+    app.update(Message::Undo);
+    assert!(app.project.source_library.get(asset_id).is_none());
+}
+
+#[test]
+fn test_move_clip_flow() {
+    let mut app = App::new();
+
+    // Use no_audio asset to test simple move without linked audio clip
+    let asset = MediaAsset::new(
+        "clip1".into(),
+        PathBuf::from("/test/clip1.mp4"),
+        Duration::from_secs_f64(5.0),
+        1920, 1080, 30.0, false,
+    );
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+
+    let clip_id = app.project.timeline.tracks[0].clips[0].id;
+
+    app.update(Message::MoveClip {
+        source_track: 0,
+        clip_id,
+        dest_track: 1,
+        position: TimelinePosition::from_secs_f64(2.0),
+    });
+
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 0);
+    assert_eq!(app.project.timeline.tracks[1].clips.len(), 1);
+}
+
+#[test]
+fn test_select_source_asset() {
+    let mut app = App::new();
+    assert!(app.selected_asset_id.is_none());
+
+    let id = uuid::Uuid::new_v4();
+    app.update(Message::SelectSourceAsset(Some(id)));
+    assert_eq!(app.selected_asset_id, Some(id));
+
+    app.update(Message::SelectSourceAsset(None));
+    assert!(app.selected_asset_id.is_none());
+}
+
+#[test]
+fn test_open_file_dialog_returns_task() {
+    let mut app = App::new();
+    let task = app.update(Message::OpenFileDialog);
+    // OpenFileDialog returns a Task::perform, which is non-trivial
+    // We can't easily inspect it, but verify the status was updated
+    assert_eq!(app.status_message, "Opening file dialog...");
+    let _ = task; // just ensure it compiles and doesn't panic
+}
+
+#[test]
+fn test_toggle_playback() {
+    let mut app = App::new();
+    assert!(!app.is_playing);
+
+    app.update(Message::TogglePlayback);
+    assert!(app.is_playing);
+
+    app.update(Message::TogglePlayback);
+    assert!(!app.is_playing);
+}
+
+#[test]
+fn test_playback_tick_advances_position() {
+    let mut app = App::new();
+
+    // Add content so playback doesn't immediately stop
+    let asset = make_test_asset("clip1", 30.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+
+    app.update(Message::Play);
+    assert!(app.is_playing);
+
+    // Simulate a small passage of time by adjusting the start wall
+    let start = app.playback_start_wall.unwrap();
+    app.playback_start_wall = Some(start - Duration::from_millis(100));
+
+    app.update(Message::PlaybackTick);
+    assert!(app.playback_position.as_secs_f64() > 0.0);
+}
+
+#[test]
+fn test_playback_continues_past_end() {
+    let mut app = App::new();
+
+    let asset = make_test_asset("clip1", 1.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+
+    app.update(Message::Play);
+
+    // Set start wall far in the past so elapsed > timeline duration
+    let start = app.playback_start_wall.unwrap();
+    app.playback_start_wall = Some(start - Duration::from_secs(5));
+
+    app.update(Message::PlaybackTick);
+    // Playback should continue past the end — user stops manually
+    assert!(app.is_playing);
+    assert!(app.playback_position.as_secs_f64() > 1.0);
+}
+
+#[test]
+fn test_loop_playback_wraps_at_end() {
+    let mut app = App::new();
+
+    let asset = make_test_asset("clip1", 1.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+
+    app.update(Message::ToggleLoopPlayback);
+    assert!(app.loop_playback);
+    app.update(Message::Play);
+
+    let start = app.playback_start_wall.unwrap();
+    app.playback_start_wall = Some(start - Duration::from_secs(5));
+
+    app.update(Message::PlaybackTick);
+    assert!(app.is_playing, "loop should keep playing");
+    assert!(
+        app.playback_position.as_secs_f64() < 1.0,
+        "playhead should wrap to loop start, got {}",
+        app.playback_position.as_secs_f64()
+    );
+    assert_eq!(app.playback_start_pos, TimelinePosition::zero());
+}
+
+#[test]
+fn test_loop_playback_toggle_off() {
+    let mut app = App::new();
+    app.update(Message::ToggleLoopPlayback);
+    app.update(Message::ToggleLoopPlayback);
+    assert!(!app.loop_playback);
+    assert_eq!(app.status_message, "Loop playback off");
+}
+
+#[test]
+fn test_loop_range_empty_timeline() {
+    let app = App::new();
+    assert!(app.loop_range().is_none());
+}
+
+#[test]
+fn test_loop_playback_empty_timeline_does_not_wrap() {
+    let mut app = App::new();
+    app.update(Message::ToggleLoopPlayback);
+    app.update(Message::Play);
+
+    let start = app.playback_start_wall.unwrap();
+    app.playback_start_wall = Some(start - Duration::from_secs(2));
+    app.update(Message::PlaybackTick);
+    assert!(app.playback_position.as_secs_f64() > 1.0);
+}
+
+fn char_key_press(c: &str) -> Message {
+    Message::KeyboardEvent(iced::keyboard::Event::KeyPressed {
+        key: iced::keyboard::Key::Character(c.into()),
+        modified_key: iced::keyboard::Key::Character(c.into()),
+        physical_key: iced::keyboard::key::Physical::Unidentified(
+            iced::keyboard::key::NativeCode::Unidentified,
+        ),
+        location: iced::keyboard::Location::Standard,
+        modifiers: iced::keyboard::Modifiers::empty(),
+        text: None,
+        repeat: false,
+    })
+}
+
+#[test]
+fn test_shuttle_forward_doubles_rate() {
+    let mut app = App::new();
+
+    app.update(char_key_press("l"));
+    assert!(app.is_playing);
+    assert_eq!(app.playback_rate, 1.0);
+
+    app.update(char_key_press("l"));
+    assert_eq!(app.playback_rate, 2.0);
+    app.update(char_key_press("l"));
+    assert_eq!(app.playback_rate, 4.0);
+    // Capped at 4x
+    app.update(char_key_press("l"));
+    assert_eq!(app.playback_rate, 4.0);
+}
+
+#[test]
+fn test_shuttle_reverse_and_direction_change() {
+    let mut app = App::new();
+    app.playback_position = TimelinePosition::from_secs_f64(10.0);
+
+    app.update(char_key_press("j"));
+    assert!(app.is_playing);
+    assert_eq!(app.playback_rate, -1.0);
+    app.update(char_key_press("j"));
+    assert_eq!(app.playback_rate, -2.0);
+
+    // Switching direction restarts at 1x
+    app.update(char_key_press("l"));
+    assert_eq!(app.playback_rate, 1.0);
+}
+
+#[test]
+fn test_shuttle_k_pauses_and_resets_rate() {
+    let mut app = App::new();
+    app.update(char_key_press("l"));
+    app.update(char_key_press("l"));
+    app.update(char_key_press("k"));
+    assert!(!app.is_playing);
+    assert_eq!(app.playback_rate, 1.0);
+}
+
+#[test]
+fn test_shuttle_rate_scales_playback_clock() {
+    let mut app = App::new();
+    app.update(Message::ShuttleForward);
+    app.update(Message::ShuttleForward);
+    assert_eq!(app.playback_rate, 2.0);
+
+    let start = app.playback_start_wall.unwrap();
+    app.playback_start_wall = Some(start - Duration::from_secs(1));
+    app.update(Message::PlaybackTick);
+    let pos = app.playback_position.as_secs_f64();
+    assert!((2.0..2.5).contains(&pos), "2x for 1s should reach ~2s, got {pos}");
+}
+
+#[test]
+fn test_shuttle_reverse_moves_backwards_and_stops_at_zero() {
+    let mut app = App::new();
+    app.playback_position = TimelinePosition::from_secs_f64(3.0);
+    app.update(Message::ShuttleReverse);
+
+    let start = app.playback_start_wall.unwrap();
+    app.playback_start_wall = Some(start - Duration::from_secs(1));
+    app.update(Message::PlaybackTick);
+    let pos = app.playback_position.as_secs_f64();
+    assert!(pos > 1.5 && pos <= 2.0, "reverse 1s should reach ~2s, got {pos}");
+
+    app.playback_start_wall = Some(Instant::now() - Duration::from_secs(10));
+    app.update(Message::PlaybackTick);
+    assert!(!app.is_playing);
+    assert_eq!(app.playback_position, TimelinePosition::zero());
+}
+
+#[test]
+fn test_play_resets_shuttle_rate() {
+    let mut app = App::new();
+    app.update(Message::ShuttleReverse);
+    app.update(Message::Pause);
+    app.update(Message::Play);
+    assert_eq!(app.playback_rate, 1.0);
+}
+
+fn named_key_press(named: iced::keyboard::key::Named) -> Message {
+    Message::KeyboardEvent(iced::keyboard::Event::KeyPressed {
+        key: iced::keyboard::Key::Named(named),
+        modified_key: iced::keyboard::Key::Named(named),
+        physical_key: iced::keyboard::key::Physical::Unidentified(
+            iced::keyboard::key::NativeCode::Unidentified,
+        ),
+        location: iced::keyboard::Location::Standard,
+        modifiers: iced::keyboard::Modifiers::empty(),
+        text: None,
+        repeat: false,
+    })
+}
+
+#[test]
+fn test_step_frame_forward_and_backward() {
+    let mut app = App::new();
+    assert_eq!(app.project.settings.fps, 30.0);
+
+    app.update(Message::StepFrameForward);
+    assert!((app.playback_position.as_secs_f64() - 1.0 / 30.0).abs() < 1e-6);
+    app.update(Message::StepFrameForward);
+    assert!((app.playback_position.as_secs_f64() - 2.0 / 30.0).abs() < 1e-6);
+    app.update(Message::StepFrameBackward);
+    assert!((app.playback_position.as_secs_f64() - 1.0 / 30.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_step_frame_clamps_at_zero() {
+    let mut app = App::new();
+    app.update(Message::StepFrameBackward);
+    assert_eq!(app.playback_position, TimelinePosition::zero());
+}
+
+#[test]
+fn test_step_frame_snaps_to_frame_grid_and_pauses() {
+    let mut app = App::new();
+    app.project.settings.fps = 25.0;
+    app.playback_position = TimelinePosition::from_secs_f64(1.013);
+    app.update(Message::Play);
+
+    app.update(Message::StepFrameForward);
+    assert!(!app.is_playing);
+    // 1.013s is nearest frame 25; one step lands on frame 26.
+    assert!((app.playback_position.as_secs_f64() - 26.0 / 25.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_arrow_and_period_keys_step_frames() {
+    let mut app = App::new();
+    app.update(named_key_press(iced::keyboard::key::Named::ArrowRight));
+    app.update(char_key_press("."));
+    assert!((app.playback_position.as_secs_f64() - 2.0 / 30.0).abs() < 1e-6);
+    app.update(named_key_press(iced::keyboard::key::Named::ArrowLeft));
+    app.update(char_key_press(","));
+    assert_eq!(app.playback_position, TimelinePosition::zero());
+}
+
+#[test]
+fn test_arrow_keys_do_not_step_with_clip_selected() {
+    let mut app = App::new();
+    app.selected_clip = Some((0, uuid::Uuid::new_v4()));
+    app.update(named_key_press(iced::keyboard::key::Named::ArrowRight));
+    assert_eq!(app.playback_position, TimelinePosition::zero());
+}
+
+#[test]
+fn test_toggle_preview_overlays() {
+    use zeditor_ui::message::PreviewOverlayKind;
+
+    let mut app = App::new();
+    assert!(!app.preview_overlay.any());
+
+    app.update(Message::TogglePreviewOverlay(PreviewOverlayKind::SafeAreas));
+    app.update(Message::TogglePreviewOverlay(PreviewOverlayKind::CenterCross));
+    assert!(app.preview_overlay.safe_areas);
+    assert!(!app.preview_overlay.thirds_grid);
+    assert!(app.preview_overlay.center_cross);
+
+    app.update(Message::TogglePreviewOverlay(PreviewOverlayKind::SafeAreas));
+    assert!(!app.preview_overlay.safe_areas);
+}
+
+#[test]
+fn test_canvas_presets_reshape_project_and_preferences() {
+    use zeditor_core::project::CanvasPreset;
+
+    let mut app = App::new();
+    app.update(Message::SetCanvasPreset(CanvasPreset::Vertical));
+    assert_eq!((app.project.settings.canvas_width, app.project.settings.canvas_height), (1080, 1920));
+    app.update(Message::SetCanvasPreset(CanvasPreset::Portrait));
+    assert_eq!((app.project.settings.canvas_width, app.project.settings.canvas_height), (1080, 1350));
+
+    app.update(Message::OpenPreferences);
+    app.update(Message::PreferencesCanvasPreset(CanvasPreset::Square));
+    let form = app.preferences_form.as_ref().unwrap();
+    assert_eq!((form.canvas_width.as_str(), form.canvas_height.as_str()), ("1080", "1080"));
+
+    app.update(Message::TogglePreviewOverlay(zeditor_ui::message::PreviewOverlayKind::VerticalCrop));
+    assert!(app.preview_overlay.vertical_crop);
+}
+
+#[test]
+fn test_toggle_playback_stats() {
+    let mut app = App::new();
+    assert!(!app.show_playback_stats);
+    app.update(Message::TogglePlaybackStats);
+    assert!(app.show_playback_stats);
+    app.update(Message::TogglePlaybackStats);
+    assert!(!app.show_playback_stats);
+}
+
+#[test]
+fn test_set_preview_background() {
+    use zeditor_ui::message::PreviewBackground;
+
+    let mut app = App::new();
+    assert_eq!(app.preview_background, PreviewBackground::Checkerboard);
+    app.update(Message::SetPreviewBackground(PreviewBackground::White));
+    assert_eq!(app.preview_background, PreviewBackground::White);
+}
+
+#[test]
+fn test_open_asset_in_source_monitor() {
+    let mut app = App::new();
+    let asset = make_test_asset("clip1", 8.0);
+    let asset_id = asset.id;
+    app.project.source_library.import(asset);
+
+    app.update(Message::OpenInSourceMonitor(asset_id));
+    assert_eq!(app.source_monitor.asset_id, Some(asset_id));
+    assert_eq!(app.source_monitor.duration, TimelinePosition::from_secs_f64(8.0));
+
+    // Unknown assets are ignored.
+    app.update(Message::OpenInSourceMonitor(uuid::Uuid::new_v4()));
+    assert_eq!(app.source_monitor.asset_id, Some(asset_id));
+
+    app.update(Message::CloseSourceMonitor);
+    assert!(!app.source_monitor.is_loaded());
+}
+
+#[test]
+fn test_source_monitor_marks_in_and_out() {
+    let mut app = App::new();
+    let asset = make_test_asset("clip1", 10.0);
+    let asset_id = asset.id;
+    app.project.source_library.import(asset);
+    app.update(Message::OpenInSourceMonitor(asset_id));
+
+    app.update(Message::SourceMonitorSeek(TimelinePosition::from_secs_f64(2.0)));
+    app.update(Message::SourceMonitorMarkIn);
+    app.update(Message::SourceMonitorSeek(TimelinePosition::from_secs_f64(5.0)));
+    app.update(Message::SourceMonitorMarkOut);
+
+    let range = app.source_monitor.marked_range().unwrap();
+    assert_eq!(range.start, TimelinePosition::from_secs_f64(2.0));
+    assert_eq!(range.end, TimelinePosition::from_secs_f64(5.0));
+    // The timeline playhead is untouched.
+    assert_eq!(app.playback_position, TimelinePosition::zero());
+
+    app.update(Message::SourceMonitorClearMarks);
+    assert_eq!(app.source_monitor.in_point, None);
+    assert_eq!(app.source_monitor.out_point, None);
+}
+
+#[test]
+fn test_save_subclip_and_add_it_trimmed() {
+    let mut app = App::new();
+    let asset = make_test_asset("interview", 60.0);
+    let asset_id = asset.id;
+    app.project.source_library.import(asset);
+    app.update(Message::OpenInSourceMonitor(asset_id));
+    app.update(Message::SourceMonitorSeek(TimelinePosition::from_secs_f64(2.0)));
+    app.update(Message::SourceMonitorMarkIn);
+    app.update(Message::SourceMonitorSeek(TimelinePosition::from_secs_f64(5.0)));
+    app.update(Message::SourceMonitorMarkOut);
+    app.update(Message::SubClipNameChanged("Answer".into()));
+    app.update(Message::SaveSubClip);
+
+    let subclip = app.project.source_library.get(asset_id).unwrap().subclips[0].clone();
+    assert_eq!(subclip.name, "Answer");
+    assert!(app.subclip_name.is_empty());
+
+    // Unnamed sub-clips are numbered after the asset
+    app.update(Message::SaveSubClip);
+    assert_eq!(app.project.source_library.get(asset_id).unwrap().subclips[1].name, "interview 2");
+
+    app.update(Message::StartDragFromSubClip(subclip.id));
+    match &app.drag_state.as_ref().unwrap().payload {
+        DragPayload::SourceAsset { subclip_id, name, .. } => {
+            assert_eq!(*subclip_id, Some(subclip.id));
+            assert_eq!(name, "Answer");
+        }
+        _ => panic!("expected a source asset drag"),
+    }
+    app.drag_state = None;
+
+    app.update(Message::AddSubClipToTimeline {
+        subclip_id: subclip.id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+    let clip = &app.project.timeline.tracks[0].clips[0];
+    assert_eq!(clip.source_range, subclip.range);
+    assert_eq!(clip.timeline_range.end, TimelinePosition::from_secs_f64(3.0));
+
+    // Opening the sub-clip marks its range in the source monitor
+    app.update(Message::SourceMonitorClearMarks);
+    app.update(Message::OpenSubClipInSourceMonitor(subclip.id));
+    assert_eq!(app.source_monitor.marked_range(), Some(subclip.range));
+
+    app.update(Message::RemoveSubClip { asset_id, subclip_id: subclip.id });
+    assert!(app.project.source_library.find_subclip(subclip.id).is_none());
+}
+
+#[test]
+fn test_source_monitor_and_timeline_play_exclusively() {
+    let mut app = App::new();
+    let asset = make_test_asset("clip1", 10.0);
+    let asset_id = asset.id;
+    app.project.source_library.import(asset);
+    app.update(Message::OpenInSourceMonitor(asset_id));
+
+    app.update(Message::Play);
+    app.update(Message::SourceMonitorTogglePlay);
+    assert!(app.source_monitor.is_playing);
+    assert!(!app.is_playing);
+
+    app.update(Message::Play);
+    assert!(app.is_playing);
+    assert!(!app.source_monitor.is_playing);
+}
+
+#[test]
+fn test_removing_asset_closes_source_monitor() {
+    let mut app = App::new();
+    let asset = make_test_asset("clip1", 10.0);
+    let asset_id = asset.id;
+    app.project.source_library.import(asset);
+    app.update(Message::OpenInSourceMonitor(asset_id));
+
+    app.update(Message::RemoveAsset(asset_id));
+    assert!(!app.source_monitor.is_loaded());
+}
+
+#[test]
+fn test_play_around_plays_preroll_to_postroll_and_returns() {
+    let mut app = App::new();
+    app.playback_position = TimelinePosition::from_secs_f64(5.0);
+
+    app.update(char_key_press("/"));
+    assert!(app.is_playing);
+    assert_eq!(app.playback_position, TimelinePosition::from_secs_f64(3.0));
+
+    // Still inside the window: keeps playing.
+    app.playback_start_wall = Some(Instant::now() - Duration::from_secs(2));
+    app.update(Message::PlaybackTick);
+    assert!(app.is_playing);
+
+    // Past the postroll: stops and returns to the original playhead.
+    app.playback_start_wall = Some(Instant::now() - Duration::from_millis(4500));
+    app.update(Message::PlaybackTick);
+    assert!(!app.is_playing);
+    assert_eq!(app.playback_position, TimelinePosition::from_secs_f64(5.0));
+}
+
+#[test]
+fn test_play_around_preroll_clamps_at_start_and_is_configurable() {
+    let mut app = App::new();
+    app.update(Message::SetPlayAroundPreroll(1.0));
+    app.update(Message::SetPlayAroundPostroll(50.0));
+    assert_eq!(app.play_around_preroll, 1.0);
+    assert_eq!(app.play_around_postroll, 10.0);
+
+    app.playback_position = TimelinePosition::from_secs_f64(0.5);
+    app.update(Message::PlayAround);
+    assert_eq!(app.playback_position, TimelinePosition::zero());
+}
+
+#[test]
+fn test_pause_cancels_play_around() {
+    let mut app = App::new();
+    app.playback_position = TimelinePosition::from_secs_f64(5.0);
+    app.update(Message::PlayAround);
+    app.update(Message::Pause);
+    app.update(Message::Play);
+
+    // A normal play runs past the old postroll without stopping.
+    app.playback_start_wall = Some(Instant::now() - Duration::from_secs(5));
+    app.update(Message::PlaybackTick);
+    assert!(app.is_playing);
+}
+
+fn command_key_press(c: &str, shift: bool) -> Message {
+    let mut modifiers = iced::keyboard::Modifiers::COMMAND;
+    if shift {
+        modifiers |= iced::keyboard::Modifiers::SHIFT;
+    }
+    Message::KeyboardEvent(iced::keyboard::Event::KeyPressed {
+        key: iced::keyboard::Key::Character(c.into()),
+        modified_key: iced::keyboard::Key::Character(c.into()),
+        physical_key: iced::keyboard::key::Physical::Unidentified(
+            iced::keyboard::key::NativeCode::Unidentified,
+        ),
+        location: iced::keyboard::Location::Standard,
+        modifiers,
+        text: None,
+        repeat: false,
+    })
+}
+
+#[test]
+fn test_ctrl_z_and_redo_shortcuts() {
+    let mut app = App::new();
+    let asset = make_test_asset("clip1", 5.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+
+    app.update(command_key_press("z", false));
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 0);
+    app.update(command_key_press("Z", true));
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 1);
+    app.update(command_key_press("z", false));
+    app.update(command_key_press("y", false));
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 1);
+    assert_eq!(app.status_message, "Redone");
+}
+
+#[test]
+fn test_ctrl_s_without_path_opens_save_dialog() {
+    let mut app = App::new();
+    app.update(command_key_press("s", false));
+    assert_eq!(app.status_message, "Opening save dialog...");
+}
+
+#[test]
+fn test_ctrl_o_opens_load_dialog() {
+    let mut app = App::new();
+    app.update(command_key_press("o", false));
+    assert_eq!(app.status_message, "Opening load dialog...");
+}
+
+#[test]
+fn test_command_shortcuts_do_not_trigger_plain_keys() {
+    let mut app = App::new();
+    app.update(command_key_press("b", false));
+    assert_eq!(app.tool_mode, ToolMode::Arrow);
+}
+
+#[test]
+fn test_library_search_and_usage_filters() {
+    use zeditor_ui::library_filter::UsageFilter;
+
+    let mut app = App::new();
+    let beach = make_test_asset("beach", 5.0);
+    let beach_id = beach.id;
+    app.update(Message::MediaImported(Ok(beach)));
+    app.update(Message::MediaImported(Ok(make_test_asset("city", 5.0))));
+    app.update(Message::AddClipToTimeline {
+        asset_id: beach_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+
+    let visible = |app: &App| -> Vec<String> {
+        app.library_filter
+            .apply(&app.project.source_library, &app.project.timeline)
+            .iter()
+            .map(|a| a.name.clone())
+            .collect()
+    };
+
+    app.update(Message::LibrarySearchChanged("BEA".into()));
+    assert_eq!(visible(&app), vec!["beach"]);
+
+    app.update(Message::LibrarySearchChanged(String::new()));
+    app.update(Message::SetLibraryUsageFilter(UsageFilter::Unused));
+    assert_eq!(visible(&app), vec!["city"]);
+
+    // Clicking the active chip again turns it off.
+    app.update(Message::SetLibraryUsageFilter(UsageFilter::Unused));
+    assert_eq!(visible(&app).len(), 2);
+
+    app.update(Message::ToggleLibraryKindFilter(zeditor_core::media::MediaKind::Audio));
+    assert!(visible(&app).is_empty());
+    app.update(Message::ClearLibraryFilters);
+    assert!(!app.library_filter.is_active());
+}
+
+#[test]
+fn test_import_failure_becomes_persistent_notification() {
+    use zeditor_ui::notifications::NotificationLevel;
+
+    let mut app = App::new();
+    app.update(Message::MediaImported(Err("unsupported codec".into())));
+    app.update(Message::MediaImported(Ok(make_test_asset("clip1", 5.0))));
+
+    // The later success overwrites the status bar but the error stays listed.
+    assert_eq!(app.status_message, "Imported: clip1");
+    let levels: Vec<_> = app.notifications.history().map(|n| n.level).collect();
+    assert_eq!(levels, vec![NotificationLevel::Success, NotificationLevel::Error]);
+    assert_eq!(app.notifications.unresolved_count(), 1);
+
+    let error_id = app.notifications.history().nth(1).unwrap().id;
+    app.update(Message::DismissNotification(error_id));
+    assert_eq!(app.notifications.unresolved_count(), 0);
+    assert_eq!(app.notifications.len(), 2);
+}
+
+#[test]
+fn test_render_error_and_decode_errors_are_notified() {
+    use zeditor_ui::notifications::NotificationLevel;
+
+    let mut app = App::new();
+    app.update(Message::RenderError("disk full".into()));
+    let error_tx = app.attach_decode_error_channel();
+    error_tx.send("cannot open /missing.mp4".into()).unwrap();
+    error_tx.send("cannot open /missing.mp4".into()).unwrap();
+    app.update(Message::PlaybackTick);
+
+    let entries: Vec<_> = app.notifications.history().collect();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].message, "Decode failed: cannot open /missing.mp4");
+    assert_eq!(entries[0].count, 2, "repeated decode errors are coalesced");
+    assert_eq!(entries[1].message, "Render failed: disk full");
+    assert!(entries.iter().all(|n| n.level == NotificationLevel::Error));
+}
+
+#[test]
+fn test_notification_history_toggle_and_clear() {
+    let mut app = App::new();
+    app.update(Message::RenderError("disk full".into()));
+    app.update(Message::ToggleNotificationHistory);
+    assert!(app.show_notification_history);
+    app.update(Message::ClearNotifications);
+    assert!(app.notifications.is_empty());
+    app.update(Message::ToggleNotificationHistory);
+    assert!(!app.show_notification_history);
+}
+
+#[test]
+fn test_track_flags_toggle_and_undo() {
+    use zeditor_ui::message::TrackFlag;
+
+    let mut app = App::new();
+    // Default timeline: V1 (0), A1 (1)
+    app.update(Message::ToggleTrackFlag { track_index: 1, flag: TrackFlag::Mute });
+    app.update(Message::ToggleTrackFlag { track_index: 0, flag: TrackFlag::Visible });
+    assert!(app.project.timeline.tracks[1].muted);
+    assert!(app.project.timeline.tracks[0].hidden);
+
+    app.update(Message::Undo);
+    assert!(!app.project.timeline.tracks[0].hidden);
+    assert!(app.project.timeline.tracks[1].muted);
+}
+
+#[test]
+fn test_track_height_expands_lane_and_scroll_range() {
+    use zeditor_core::timeline::TrackHeight;
+
+    let mut app = App::new();
+    app.update(Message::SetTrackHeight { track_index: 0, height: TrackHeight::Triple });
+    assert_eq!(app.project.timeline.tracks[0].height, TrackHeight::Triple);
+
+    // The audio track can scroll up to the top, past the tripled video lane
+    app.update(Message::TimelineVerticalScroll(1000.0));
+    assert_eq!(app.effective_vertical_scroll(), 150.0);
+
+    app.update(Message::Undo);
+    assert_eq!(app.project.timeline.tracks[0].height, TrackHeight::Normal);
+    assert_eq!(app.effective_vertical_scroll(), 50.0);
+}
+
+#[test]
+fn test_locked_track_blocks_clip_removal() {
+    use zeditor_ui::message::TrackFlag;
+
+    let mut app = App::new();
+    let asset = make_test_asset("clip1", 5.0);
+    let asset_id = asset.id;
+    app.project.source_library.import(asset);
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+    let clip_id = app.project.timeline.tracks[0].clips[0].id;
+
+    // Locking the linked audio track protects the whole linked pair.
+    app.update(Message::ToggleTrackFlag { track_index: 1, flag: TrackFlag::Lock });
+    app.update(Message::RemoveClip { track_index: 0, clip_id });
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 1);
+    assert_eq!(app.project.timeline.tracks[1].clips.len(), 1);
+    assert!(app.status_message.contains("locked"), "{}", app.status_message);
+}
+
+#[test]
+fn test_hidden_and_muted_tracks_skip_preview_and_audio() {
+    use zeditor_ui::message::TrackFlag;
+
+    let mut app = App::new();
+    let asset = make_test_asset("clip1", 5.0);
+    let asset_id = asset.id;
+    app.project.source_library.import(asset);
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+    let pos = TimelinePosition::from_secs_f64(1.0);
+    assert_eq!(app.all_video_clips_at_position(pos).len(), 1);
+    assert!(app.audio_clip_at_position(pos).is_some());
+
+    app.update(Message::ToggleTrackFlag { track_index: 0, flag: TrackFlag::Visible });
+    app.update(Message::ToggleTrackFlag { track_index: 1, flag: TrackFlag::Mute });
+    assert!(app.all_video_clips_at_position(pos).is_empty());
+    assert!(app.audio_clip_at_position(pos).is_none());
+}
+
+#[test]
+fn test_vertical_scroll_clamped_to_tracks() {
+    let mut app = App::new();
+    for _ in 0..6 {
+        app.update(Message::AddVideoTrackAbove(0));
+    }
+    let tracks = app.project.timeline.tracks.len() as f32;
+
+    app.update(Message::TimelineVerticalScroll(-100.0));
+    assert_eq!(app.timeline_vertical_scroll, 0.0);
+
+    app.update(Message::TimelineVerticalScroll(10_000.0));
+    assert_eq!(app.timeline_vertical_scroll, (tracks - 1.0) * 50.0);
+
+    app.update(named_key_press(iced::keyboard::key::Named::PageUp));
+    assert_eq!(app.timeline_vertical_scroll, (tracks - 4.0) * 50.0);
+    app.update(named_key_press(iced::keyboard::key::Named::PageDown));
+    assert_eq!(app.timeline_vertical_scroll, (tracks - 1.0) * 50.0);
+}
+
+#[test]
+fn test_drag_over_timeline_accounts_for_vertical_scroll() {
+    use zeditor_core::timeline::TrackType;
+
+    let mut app = App::new();
+    for _ in 0..4 {
+        app.update(Message::AddVideoTrackAbove(0));
+    }
+    let asset = make_test_asset("clip1", 5.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+
+    // Scroll down two tracks, then hover the first visible lane.
+    app.update(Message::TimelineVerticalScroll(100.0));
+    app.update(Message::StartDragFromSource(asset_id));
+    app.update(Message::DragEnteredTimeline);
+    app.update(Message::DragOverTimeline(iced::Point::new(200.0, 30.0 + 20.0 + 25.0)));
+
+    let track = app.drag_state.as_ref().unwrap().timeline_track.unwrap();
+    assert_eq!(track, 2);
+    assert_eq!(app.project.timeline.tracks[track].track_type, TrackType::Video);
+}
+
+#[test]
+fn test_drag_ghost_scales_to_asset_duration_over_timeline() {
+    let mut app = App::new();
+    let asset = make_test_asset("clip1", 5.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+
+    app.update(Message::StartDragFromSource(asset_id));
+    assert_eq!(app.drag_ghost_width(), 130.0);
+
+    app.update(Message::DragEnteredTimeline);
+    app.update(Message::DragOverTimeline(iced::Point::new(200.0, 70.0)));
+    let preview = app.compute_source_drag_preview().unwrap();
+    assert_eq!(preview.name, "clip1");
+    // 5 seconds at the default 100 px/s
+    assert_eq!(app.drag_ghost_width(), 500.0);
+
+    app.timeline_zoom = 20.0;
+    assert_eq!(app.drag_ghost_width(), 100.0);
+
+    app.update(Message::DragExitedTimeline);
+    assert_eq!(app.drag_ghost_width(), 130.0);
+}
+
+#[test]
+fn test_asset_info_shows_probed_details() {
+    use zeditor_media::probe::MediaDetails;
+    use zeditor_ui::app::{asset_info_rows, format_file_size};
+
+    let mut app = App::new();
+    let asset = make_test_asset("clip1", 5.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    app.update(Message::SelectSourceAsset(Some(asset_id)));
+    app.update(Message::ToggleAssetInfo);
+    assert!(app.show_asset_info);
+
+    let details = MediaDetails {
+        container: "QuickTime / MOV".into(),
+        video_codec: Some("h264".into()),
+        width: 1920,
+        height: 1080,
+        fps: Some(30.0),
+        pixel_format: Some("yuv420p10le".into()),
+        bit_depth: Some(10),
+        audio_codec: Some("aac".into()),
+        audio_channels: Some(2),
+        sample_rate: Some(48000),
+        bit_rate: Some(25_000_000),
+        file_size: 1536 * 1024,
+        created: Some("2024-05-01 12:30:07".into()),
+    };
+    app.update(Message::AssetDetailsLoaded { asset_id, result: Ok(details.clone()) });
+    assert_eq!(app.asset_details.as_ref().map(|(id, _)| *id), Some(asset_id));
+
+    let rows = asset_info_rows(&details);
+    let value = |label: &str| rows.iter().find(|(l, _)| *l == label).map(|(_, v)| v.as_str());
+    assert_eq!(value("info-resolution"), Some("1920x1080"));
+    assert_eq!(value("info-frame-rate"), Some("30 fps"));
+    assert_eq!(value("info-bit-depth"), Some("10-bit (yuv420p10le)"));
+    assert_eq!(value("info-channels"), Some("stereo, 48.0 kHz"));
+    assert_eq!(value("info-file-size"), Some("1.5 MB"));
+    assert_eq!(format_file_size(512), "512 B");
+}
+
+#[test]
+fn test_effect_search_filters_and_groups_by_category() {
+    let mut app = App::new();
+    let groups = app.visible_effects();
+    assert_eq!(groups.iter().map(|(_, e)| e.len()).sum::<usize>(), EffectType::all_builtin().len());
+    assert!(groups.iter().all(|(category, effects)| effects.iter().all(|e| e.category() == *category)));
+
+    app.update(Message::EffectSearchChanged("gray".into()));
+    let groups = app.visible_effects();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].0, zeditor_core::effects::EffectCategory::Color);
+    assert_eq!(groups[0].1, vec![EffectType::Grayscale]);
+
+    app.update(Message::EffectSearchChanged("no such effect".into()));
+    assert!(app.visible_effects().is_empty());
+}
+
+#[test]
+fn test_drag_effect_onto_clip_applies_it() {
+    let (mut app, _, clip_id) = setup_app_with_clip();
+    app.update(Message::SelectTimelineClip(None));
+
+    app.update(Message::StartDragEffect(EffectType::Grayscale));
+    app.update(Message::DragEnteredTimeline);
+    app.update(Message::DragOverTimeline(iced::Point::new(200.0, 70.0)));
+    app.update(Message::DragReleased);
+
+    let clip = app.project.timeline.tracks[0].get_clip(clip_id).unwrap();
+    assert_eq!(clip.effects.len(), 1);
+    assert_eq!(clip.effects[0].effect_type, EffectType::Grayscale);
+    assert_eq!(app.selected_clip, Some((0, clip_id)));
+    assert!(app.drag_state.is_none());
+
+    app.update(Message::Undo);
+    assert!(app.project.timeline.tracks[0].get_clip(clip_id).unwrap().effects.is_empty());
+}
+
+#[test]
+fn test_drag_effect_onto_empty_space_does_nothing() {
+    let (mut app, _, clip_id) = setup_app_with_clip();
+
+    app.update(Message::StartDragEffect(EffectType::Grayscale));
+    app.update(Message::DragEnteredTimeline);
+    // Well past the end of the 5s clip
+    app.update(Message::DragOverTimeline(iced::Point::new(1200.0, 70.0)));
+    app.update(Message::DragReleased);
+
+    let clip = app.project.timeline.tracks[0].get_clip(clip_id).unwrap();
+    assert!(clip.effects.is_empty());
+    assert_eq!(app.status_message, "Drop effects onto a clip");
+}
+
+#[test]
+fn test_zoom_step_uses_presets_centered_on_playhead() {
+    let mut app = App::new();
+    app.timeline_zoom = 100.0;
+    app.timeline_scroll = 0.0;
+    app.playback_position = TimelinePosition::from_secs_f64(5.0);
+
+    app.update(command_key_press("=", false));
+    assert_eq!(app.timeline_zoom, 200.0);
+    // Playhead stays 500px into the viewport
+    assert!((5.0 * app.timeline_zoom - app.timeline_scroll - 500.0).abs() < 0.01);
+
+    app.update(command_key_press("-", false));
+    app.update(command_key_press("-", false));
+    assert_eq!(app.timeline_zoom, 50.0);
+    // Keeping the playhead at 500px would need a negative scroll, so it clamps to 0
+    assert_eq!(app.timeline_scroll, 0.0);
+
+    // Between presets, a step lands on the next one
+    app.timeline_zoom = 60.0;
+    app.update(Message::TimelineZoomStep(1));
+    assert_eq!(app.timeline_zoom, 100.0);
+}
+
+#[test]
+fn test_pinch_zooms_about_anchor_and_pans() {
+    let mut app = App::new();
+    app.timeline_zoom = 100.0;
+    app.timeline_scroll = 200.0;
+
+    // Fingers spread to twice the distance while their midpoint moves 50px left
+    app.update(Message::TimelineZoomBy { factor: 2.0, anchor_secs: 6.0, anchor_px: 350.0 });
+    assert_eq!(app.timeline_zoom, 200.0);
+    assert!((6.0 * app.timeline_zoom - app.timeline_scroll - 350.0).abs() < 0.01);
+
+    // Wheel zoom keeps the time under the cursor in place
+    app.update(Message::TimelineZoom { delta: 1.0, cursor_secs: 6.0 });
+    assert!((app.timeline_zoom - 230.0).abs() < 0.01);
+    assert!((6.0 * app.timeline_zoom - app.timeline_scroll - 350.0).abs() < 0.01);
+}
+
+#[test]
+fn test_zoom_to_fit_shows_all_clips() {
+    let mut app = App::new();
+    let asset = make_test_asset("clip1", 20.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::from_secs_f64(10.0),
+    });
+    app.timeline_zoom = 400.0;
+    app.timeline_scroll = 3000.0;
+
+    app.update(command_key_press("0", false));
+    let viewport = app.timeline_viewport_width();
+    let extent_px = 30.0 * app.timeline_zoom;
+    assert_eq!(app.timeline_scroll, 0.0);
+    assert!(extent_px <= viewport && extent_px > viewport * 0.9);
+
+    // A wider window fits at a higher zoom
+    let narrow_zoom = app.timeline_zoom;
+    app.update(Message::WindowResized(iced::Size::new(1920.0, 1080.0)));
+    app.update(Message::ZoomToFit);
+    assert!(app.timeline_zoom > narrow_zoom);
+}
+
+#[test]
+fn test_zoom_to_fit_empty_timeline_keeps_zoom() {
+    let mut app = App::new();
+    app.timeline_zoom = 250.0;
+    app.update(Message::ZoomToFit);
+    assert_eq!(app.timeline_zoom, 250.0);
+}
+
+fn named_key_press_with(named: iced::keyboard::key::Named, modifiers: iced::keyboard::Modifiers) -> Message {
+    Message::KeyboardEvent(iced::keyboard::Event::KeyPressed {
+        key: iced::keyboard::Key::Named(named),
+        modified_key: iced::keyboard::Key::Named(named),
+        physical_key: iced::keyboard::key::Physical::Unidentified(
+            iced::keyboard::key::NativeCode::Unidentified,
+        ),
+        location: iced::keyboard::Location::Standard,
+        modifiers,
+        text: None,
+        repeat: false,
+    })
+}
+
+/// A 5s clip at 0s and a 3s clip `gap_secs` after it on V1, each with
+/// linked audio on A1.
+fn setup_app_with_two_clips(gap_secs: f64) -> (App, uuid::Uuid, uuid::Uuid) {
+    let mut app = App::new();
+    let a = make_test_asset("a", 5.0);
+    let b = make_test_asset("b", 3.0);
+    let (a_id, b_id) = (a.id, b.id);
+    app.update(Message::MediaImported(Ok(a)));
+    app.update(Message::MediaImported(Ok(b)));
+    for (asset_id, start) in [(a_id, 0.0), (b_id, 5.0 + gap_secs)] {
+        app.update(Message::AddClipToTimeline {
+            asset_id,
+            track_index: 0,
+            position: TimelinePosition::from_secs_f64(start),
+        });
+    }
+    let first = app.project.timeline.tracks[0].clips[0].id;
+    let second = app.project.timeline.tracks[0].clips[1].id;
+    (app, first, second)
+}
+
+#[test]
+fn test_tab_and_shift_tab_walk_clips_on_track() {
+    use iced::keyboard::key::Named;
+    use iced::keyboard::Modifiers;
+    let (mut app, first, second) = setup_app_with_two_clips(0.0);
+    app.update(Message::SelectTimelineClip(Some((0, first))));
+
+    app.update(named_key_press(Named::Tab));
+    assert_eq!(app.selected_clip, Some((0, second)));
+    // No clip after the last one: selection stays put
+    app.update(named_key_press(Named::Tab));
+    assert_eq!(app.selected_clip, Some((0, second)));
+
+    app.update(named_key_press_with(Named::Tab, Modifiers::SHIFT));
+    assert_eq!(app.selected_clip, Some((0, first)));
+    app.update(named_key_press_with(Named::ArrowRight, Modifiers::ALT));
+    assert_eq!(app.selected_clip, Some((0, second)));
+    app.update(named_key_press_with(Named::ArrowLeft, Modifiers::ALT));
+    assert_eq!(app.selected_clip, Some((0, first)));
+}
+
+#[test]
+fn test_up_down_switch_tracks_keeping_time() {
+    use iced::keyboard::key::Named;
+    let (mut app, _, second) = setup_app_with_two_clips(0.0);
+    app.update(Message::SelectTimelineClip(Some((0, second))));
+
+    app.update(named_key_press(Named::ArrowDown));
+    let (track, clip_id) = app.selected_clip.unwrap();
+    assert_eq!(track, 1);
+    let audio_clip = app.project.timeline.tracks[1].get_clip(clip_id).unwrap();
+    assert_eq!(audio_clip.timeline_range.start, TimelinePosition::from_secs_f64(5.0));
+
+    // Already on the last track
+    app.update(named_key_press(Named::ArrowDown));
+    assert_eq!(app.selected_clip, Some((1, clip_id)));
+
+    app.update(named_key_press(Named::ArrowUp));
+    assert_eq!(app.selected_clip, Some((0, second)));
+}
+
+#[test]
+fn test_tab_without_selection_starts_at_playhead_and_delete_removes() {
+    use iced::keyboard::key::Named;
+    let (mut app, first, second) = setup_app_with_two_clips(0.0);
+    app.playback_position = TimelinePosition::from_secs_f64(6.0);
+
+    app.update(named_key_press(Named::Tab));
+    assert_eq!(app.selected_clip, Some((0, second)));
+
+    app.update(named_key_press(Named::Delete));
+    assert!(app.project.timeline.tracks[0].get_clip(second).is_none());
+    assert!(app.project.timeline.tracks[0].get_clip(first).is_some());
+}
+
+#[test]
+fn test_memory_budget_measures_thumbnails() {
+    use zeditor_ui::memory::MemorySubsystem;
+
+    let mut app = App::new();
+    let asset = make_test_asset("clip1", 5.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    app.update(Message::ThumbnailGenerated {
+        asset_id,
+        result: Ok((vec![128u8; 4 * 2 * 4], 4, 2)),
+    });
+    app.update(Message::PlaybackTick);
+
+    assert_eq!(app.memory.usage(MemorySubsystem::Thumbnails), 32);
+    assert_eq!(app.memory.total(), 32);
+}
+
+#[test]
+fn test_toggle_memory_overlay() {
+    let mut app = App::new();
+    assert!(!app.show_memory_overlay);
+    app.update(Message::ToggleMemoryOverlay);
+    assert!(app.show_memory_overlay);
+    app.update(Message::ToggleMemoryOverlay);
+    assert!(!app.show_memory_overlay);
+}
+
+#[test]
+fn test_load_project_reports_thumbnail_progress() {
+    let mut original = App::new();
+    let first = make_test_asset("clip1", 5.0);
+    let first_id = first.id;
+    original.update(Message::MediaImported(Ok(first)));
+    original.update(Message::MediaImported(Ok(make_test_asset("clip2", 5.0))));
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("thumbs.zpf");
+    original.project.save(&path).unwrap();
+
+    let mut app = App::new();
+    app.update(Message::LoadProject(path));
+    assert_eq!(app.thumbnail_progress(), Some((0, 2)));
+
+    app.update(Message::ThumbnailGenerated {
+        asset_id: first_id,
+        result: Ok((vec![0; 16], 2, 2)),
+    });
+    assert_eq!(app.thumbnail_progress(), Some((1, 2)));
+    assert!(app.status_message.contains("1/2"), "status: {}", app.status_message);
+
+    // A repeated result for the same asset doesn't count twice.
+    app.update(Message::ThumbnailGenerated {
+        asset_id: first_id,
+        result: Err("decode error".into()),
+    });
+    assert_eq!(app.thumbnail_progress(), Some((1, 2)));
+}
+
+#[test]
+fn test_save_runs_in_background_and_keeps_editing_journaled() {
+    let mut app = App::new();
+    let asset = make_test_asset("clip1", 5.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("background.zpf");
+    app.update(Message::SaveFileDialogResult(Some(path.clone())));
+    assert!(app.is_saving());
+    assert!(app.status_message.contains("Saving"), "status: {}", app.status_message);
+
+    // Editing continues while the file is written; the edit is journaled
+    // against the snapshot being saved.
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+    // A second save waits for the first.
+    app.update(Message::SaveProject);
+    assert!(app.status_message.contains("already"), "status: {}", app.status_message);
+
+    app.wait_for_save();
+    assert!(!app.is_saving());
+    assert!(app.status_message.contains("Saved"));
+    let saved = zeditor_core::project::Project::load(&path).unwrap();
+    assert!(saved.timeline.tracks[0].clips.is_empty(), "the snapshot predates the edit");
+    let journal = zeditor_core::journal::CommandJournal::for_project(&path);
+    assert_eq!(journal.read_entries().unwrap().len(), 1);
+}
+
+#[test]
+fn test_failed_save_keeps_project_path_and_journal() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("kept.zpf");
+    let mut app = App::new();
+    let asset = make_test_asset("clip1", 5.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    app.update(Message::SaveFileDialogResult(Some(path.clone())));
+    app.wait_for_save();
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+
+    // Save As into a folder that doesn't exist
+    app.update(Message::SaveFileDialogResult(Some(dir.path().join("missing").join("other.zpf"))));
+    app.wait_for_save();
+
+    assert!(app.status_message.contains("Save failed"), "status: {}", app.status_message);
+    assert_eq!(app.project_path, Some(path.clone()));
+    let journal = zeditor_core::journal::CommandJournal::for_project(&path);
+    assert_eq!(journal.read_entries().unwrap().len(), 1);
+}
+
+#[test]
+fn test_save_completion_polled_on_tick() {
+    let mut app = App::new();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("ticked.zpf");
+    app.update(Message::SaveFileDialogResult(Some(path.clone())));
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    while app.is_saving() && std::time::Instant::now() < deadline {
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        app.update(Message::PlaybackTick);
+    }
+    assert!(!app.is_saving());
+    assert!(path.exists());
+    assert!(app.status_message.contains("Saved"));
+}
+
+#[test]
+fn test_toggle_clip_selection_adds_and_removes() {
+    let (mut app, first, second) = setup_app_with_two_clips(1.0);
+
+    app.update(Message::ToggleClipSelection((0, first)));
+    assert_eq!(app.clip_selection(), vec![(0, first)]);
+    app.update(Message::ToggleClipSelection((0, second)));
+    assert_eq!(app.clip_selection(), vec![(0, first), (0, second)]);
+
+    // Deselecting the primary promotes the next clip.
+    app.update(Message::ToggleClipSelection((0, first)));
+    assert_eq!(app.clip_selection(), vec![(0, second)]);
+
+    // A plain click replaces the whole selection.
+    app.update(Message::ToggleClipSelection((0, first)));
+    app.update(Message::SelectTimelineClip(Some((0, first))));
+    assert_eq!(app.clip_selection(), vec![(0, first)]);
+}
+
+#[test]
+fn test_ctrl_g_groups_selection_and_group_moves_together() {
+    let (mut app, first, second) = setup_app_with_two_clips(1.0);
+    app.update(Message::SelectTimelineClip(Some((0, first))));
+    app.update(Message::ToggleClipSelection((0, second)));
+    app.update(command_key_press("g", false));
+
+    let group = |app: &App, id| app.project.timeline.tracks[0].get_clip(id).unwrap().group_id;
+    assert!(group(&app, first).is_some());
+    assert_eq!(group(&app, first), group(&app, second));
+
+    app.update(Message::MoveClip {
+        source_track: 0,
+        clip_id: first,
+        dest_track: 0,
+        position: TimelinePosition::from_secs_f64(1.0),
+    });
+    let start = |app: &App, id| {
+        app.project.timeline.tracks[0].get_clip(id).unwrap().timeline_range.start.as_secs_f64()
+    };
+    assert!((start(&app, first) - 1.0).abs() < 1e-6);
+    assert!((start(&app, second) - 7.0).abs() < 1e-6);
+
+    // Undo reverts the whole group move in one step.
+    app.update(Message::Undo);
+    assert!((start(&app, second) - 6.0).abs() < 1e-6);
+    assert!(group(&app, second).is_some());
+}
+
+#[test]
+fn test_effect_added_to_whole_selection_and_edited_together() {
+    let (mut app, first, second) = setup_app_with_two_clips(1.0);
+    app.update(Message::SelectTimelineClip(Some((0, first))));
+    app.update(Message::ToggleClipSelection((0, second)));
+    app.update(Message::AddEffectToSelectedClip(EffectType::Brightness));
+
+    let effects = |app: &App, id| app.project.timeline.tracks[0].get_clip(id).unwrap().effects.clone();
+    assert_eq!(effects(&app, first).len(), 1);
+    assert_eq!(effects(&app, second).len(), 1);
+    let first_effect = effects(&app, first)[0].id;
+    assert!(!app.effect_param_mixed(first_effect, "brightness", 0.0));
+
+    // Editing the second clip alone makes the selection mixed
+    let second_effect = effects(&app, second)[0].id;
+    app.update(Message::UpdateEffectParameter {
+        track_index: 0,
+        clip_id: second,
+        effect_id: second_effect,
+        param_name: "brightness".into(),
+        value: 0.3,
+    });
+    assert!(app.effect_param_mixed(first_effect, "brightness", 0.0));
+
+    // Editing the primary clip sets both
+    app.update(Message::UpdateEffectParameter {
+        track_index: 0,
+        clip_id: first,
+        effect_id: first_effect,
+        param_name: "brightness".into(),
+        value: 0.5,
+    });
+    assert_eq!(effects(&app, second)[0].get_float("brightness"), Some(0.5));
+    assert!(!app.effect_param_mixed(first_effect, "brightness", 0.5));
+
+    // One undo step per command
+    app.update(Message::Undo);
+    app.update(Message::Undo);
+    app.update(Message::Undo);
+    assert!(effects(&app, first).is_empty());
+    assert!(effects(&app, second).is_empty());
+}
+
+#[test]
+fn test_ctrl_shift_g_ungroups_and_single_clip_cannot_group() {
+    let (mut app, first, second) = setup_app_with_two_clips(1.0);
+    app.update(Message::SelectTimelineClip(Some((0, first))));
+    app.update(Message::GroupSelectedClips);
+    assert!(app.project.timeline.tracks[0].clips.iter().all(|c| c.group_id.is_none()));
+
+    app.update(Message::ToggleClipSelection((0, second)));
+    app.update(Message::MenuAction(MenuAction::GroupClips));
+    assert!(app.project.timeline.tracks[0].clips.iter().all(|c| c.group_id.is_some()));
+
+    // Ungrouping from one member dissolves the whole group.
+    app.update(Message::SelectTimelineClip(Some((0, second))));
+    app.update(command_key_press("g", true));
+    assert!(app.project.timeline.tracks[0].clips.iter().all(|c| c.group_id.is_none()));
+}
+
+#[test]
+fn test_timeline_health_reports_gap_and_jumps_to_it() {
+    let (mut app, first, _) = setup_app_with_two_clips(1.0);
+
+    let issues = app.timeline_health();
+    let gap = issues
+        .iter()
+        .find(|i| matches!(i.kind, HealthIssueKind::Gap { .. }))
+        .expect("gap between the clips");
+    assert_eq!(gap.position, TimelinePosition::from_secs_f64(5.0));
+    assert_eq!(gap.kind, HealthIssueKind::Gap { duration: Duration::from_secs(1) });
+
+    app.update(Message::JumpToHealthIssue {
+        track_index: gap.track_index,
+        clip_id: gap.clip_id,
+        position: gap.position,
+    });
+    assert_eq!(app.playback_position, TimelinePosition::from_secs_f64(5.0));
+    assert_eq!(app.selected_clip, None);
+
+    // The test assets don't exist on disk, so each clip is flagged and
+    // jumping to one selects it.
+    let missing = issues
+        .iter()
+        .find(|i| i.clip_id == Some(first) && matches!(i.kind, HealthIssueKind::MissingFile { .. }))
+        .expect("missing file for first clip");
+    app.update(Message::JumpToHealthIssue {
+        track_index: missing.track_index,
+        clip_id: missing.clip_id,
+        position: missing.position,
+    });
+    assert_eq!(app.playback_position, TimelinePosition::zero());
+    assert_eq!(app.selected_clip, Some((0, first)));
+}
+
+#[test]
+fn test_project_stats_summarize_timeline() {
+    let (app, _, _) = setup_app_with_two_clips(1.0);
+
+    let stats = app.project_stats();
+    assert_eq!(stats.duration, Duration::from_secs(9));
+    assert_eq!(stats.tracks[0].clip_count, 2);
+    // Each video clip has its linked audio on A1
+    assert_eq!(stats.clip_count(), 4);
+    assert_eq!(stats.source_used, Duration::from_secs(8));
+    assert_eq!(stats.source_imported, Duration::from_secs(8));
+    assert!(app.estimated_export_bytes() > 0);
+}
+
+#[test]
+fn test_auto_reframe_fills_and_pans_clip_and_undoes() {
+    use zeditor_core::project::CanvasPreset;
+    use zeditor_core::reframe::{PanKey, PanPath};
+    use zeditor_core::scaler::CanvasFit;
+
+    let (mut app, first, _) = setup_app_with_two_clips(1.0);
+    app.selected_clip = Some((0, first));
+    // A 16:9 clip on a 16:9 canvas has nothing to pan across
+    app.update(Message::AutoReframeSelectedClip);
+    assert_eq!(app.status_message, app.i18n.tr("status-reframe-fits"));
+
+    app.update(Message::SetCanvasPreset(CanvasPreset::Vertical));
+    let pan = PanPath { keys: vec![PanKey { source_secs: 0.0, center_x: 0.3 }, PanKey { source_secs: 4.0, center_x: 0.6 }] };
+    app.update(Message::AutoReframeAnalyzed { track_index: 0, clip_id: first, result: Ok(pan.clone()) });
+    let clip = app.project.timeline.tracks[0].get_clip(first).unwrap();
+    assert_eq!(clip.canvas_fit, CanvasFit::Fill);
+    assert_eq!(clip.pan, pan);
+
+    app.update(Message::ClearClipReframe { track_index: 0, clip_id: first });
+    assert!(app.project.timeline.tracks[0].get_clip(first).unwrap().pan.is_empty());
+    app.update(Message::Undo);
+    app.update(Message::Undo);
+    let clip = app.project.timeline.tracks[0].get_clip(first).unwrap();
+    assert_eq!(clip.canvas_fit, CanvasFit::Fit);
+    assert!(clip.pan.is_empty());
+}
+
+/// Camera clip (linked V1/A1 pair) at 2s plus a recorder clip on a new A2 track.
+fn app_with_camera_and_recorder() -> (App, uuid::Uuid, uuid::Uuid) {
+    let mut app = App::new();
+    let camera = make_test_asset("camera", 5.0);
+    let camera_id = camera.id;
+    let recorder = MediaAsset::new(
+        "recorder".into(),
+        PathBuf::from("/test/recorder.wav"),
+        Duration::from_secs_f64(8.0),
+        0, 0, 0.0, true,
+    );
+    let recorder_id = recorder.id;
+    app.update(Message::MediaImported(Ok(camera)));
+    app.update(Message::MediaImported(Ok(recorder)));
+    app.update(Message::AddClipToTimeline {
+        asset_id: camera_id,
+        track_index: 0,
+        position: TimelinePosition::from_secs_f64(2.0),
+    });
+    let a2 = app.project.timeline.add_track("A2", zeditor_core::timeline::TrackType::Audio);
+    let clip = zeditor_core::timeline::Clip::new(
+        recorder_id,
+        TimelinePosition::from_secs_f64(10.0),
+        zeditor_core::timeline::TimeRange::new(TimelinePosition::zero(), TimelinePosition::from_secs_f64(8.0)).unwrap(),
+    );
+    let recorder_clip = clip.id;
+    app.project.timeline.add_clip(a2, clip).unwrap();
+    let video_clip = app.project.timeline.tracks[0].clips[0].id;
+    (app, video_clip, recorder_clip)
+}
+
+#[test]
+fn test_sync_pair_moves_the_audio_only_recording() {
+    let (mut app, video, recorder) = app_with_camera_and_recorder();
+    let a2 = app.project.timeline.tracks.len() - 1;
+
+    app.update(Message::SelectTimelineClip(Some((a2, recorder))));
+    app.update(Message::SynchronizeSelectedClips);
+    assert_eq!(app.status_message, "Select two clips to synchronize");
+
+    app.update(Message::ToggleClipSelection((0, video)));
+    assert_eq!(app.sync_pair(), Some(((0, video), (a2, recorder))));
+}
+
+#[test]
+fn test_clip_sync_result_places_and_links_recording_undoably() {
+    let (mut app, video, recorder) = app_with_camera_and_recorder();
+    let a2 = app.project.timeline.tracks.len() - 1;
+
+    app.update(Message::ClipSyncAnalyzed {
+        reference: (0, video),
+        other: (a2, recorder),
+        result: Ok(-0.5),
+    });
+    let clip = app.project.timeline.tracks[a2].get_clip(recorder).unwrap();
+    assert_eq!(clip.timeline_range.start, TimelinePosition::from_secs_f64(1.5));
+    assert!(clip.link_id.is_some());
+    assert!(app.project.timeline.clip_companions(0, video).contains(&(a2, recorder)));
+
+    app.update(Message::Undo);
+    let clip = app.project.timeline.tracks[a2].get_clip(recorder).unwrap();
+    assert_eq!(clip.timeline_range.start, TimelinePosition::from_secs_f64(10.0));
+    assert_eq!(clip.link_id, None);
+
+    // A failed analysis leaves the timeline alone.
+    app.update(Message::ClipSyncAnalyzed {
+        reference: (0, video),
+        other: (a2, recorder),
+        result: Err("no confident audio match between the clips".into()),
+    });
+    assert!(app.status_message.contains("no confident audio match"));
+    assert_eq!(app.project.timeline.tracks[a2].get_clip(recorder).unwrap().link_id, None);
+}
+
+#[test]
+fn test_detected_shots_open_marked_in_source_monitor() {
+    let mut app = App::new();
+    let asset = make_test_asset("long_take", 10.0);
+    let asset_id = asset.id;
+    app.project.source_library.import(asset);
+
+    app.update(Message::ShotsDetected { asset_id, result: Ok(vec![3.0, 7.5]) });
+    let shots = app.project.source_library.get(asset_id).unwrap().shots();
+    assert_eq!(shots.len(), 3);
+
+    app.update(Message::OpenShotInSourceMonitor { asset_id, shot: 1 });
+    let range = app.source_monitor.marked_range().unwrap();
+    assert_eq!(range.start, TimelinePosition::from_secs_f64(3.0));
+    assert_eq!(range.end, TimelinePosition::from_secs_f64(7.5));
+    assert_eq!(app.source_monitor.position, TimelinePosition::from_secs_f64(3.0));
+
+    // Out-of-range shots are ignored; failures only update the status.
+    app.update(Message::OpenShotInSourceMonitor { asset_id, shot: 3 });
+    assert_eq!(app.source_monitor.marked_range().unwrap().start, TimelinePosition::from_secs_f64(3.0));
+    app.update(Message::ShotsDetected { asset_id, result: Err("decoder error".into()) });
+    assert!(app.status_message.contains("decoder error"));
+    assert_eq!(app.project.source_library.get(asset_id).unwrap().shot_starts.len(), 2);
+}
+
+#[test]
+fn test_transcript_words_select_and_cut_media() {
+    let mut app = App::new();
+    let asset = make_test_asset("interview", 10.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+    let word = |text: &str, start: f64, end: f64| zeditor_core::transcript::TranscriptWord {
+        text: text.into(),
+        start: Duration::from_secs_f64(start),
+        end: Duration::from_secs_f64(end),
+    };
+    app.update(Message::TranscriptReady {
+        asset_id,
+        result: Ok(vec![word("So", 1.0, 1.5), word("um,", 2.0, 2.5), word("like", 3.0, 3.5), word("yes.", 4.0, 4.5)]),
+    });
+    assert_eq!(app.left_panel_tab, zeditor_ui::message::LeftPanelTab::Transcript);
+    let words = app.transcript_words();
+    assert_eq!(words.len(), 4);
+    assert_eq!(words[1].track_index, 1);
+
+    app.update(Message::SelectTranscriptWord(1));
+    assert_eq!(app.playback_position, TimelinePosition::from_secs_f64(2.0));
+    app.keyboard_modifiers = iced::keyboard::Modifiers::SHIFT;
+    app.update(Message::SelectTranscriptWord(2));
+    assert_eq!(app.transcript_selection, Some((1, 2)));
+
+    app.update(Message::CutTranscriptSelection);
+    assert_eq!(app.project.timeline.duration(), Duration::from_millis(8500));
+    let texts: Vec<_> = app.transcript_words().into_iter().map(|w| w.text).collect();
+    assert_eq!(texts, ["So", "yes."]);
+    assert_eq!(app.transcript_selection, None);
+
+    app.update(Message::Undo);
+    assert_eq!(app.project.timeline.duration(), Duration::from_secs(10));
+}
+
+#[test]
+fn test_find_in_timeline_cycles_through_matching_clips() {
+    let mut app = App::new();
+    for (name, start) in [("broll_street", 0.0), ("interview", 6.0), ("BROLL_park", 12.0)] {
+        let asset = make_test_asset(name, 5.0);
+        let asset_id = asset.id;
+        app.update(Message::MediaImported(Ok(asset)));
+        app.update(Message::AddClipToTimeline {
+            asset_id,
+            track_index: 0,
+            position: TimelinePosition::from_secs_f64(start),
+        });
+    }
+
+    app.update(Message::TimelineSearchChanged("broll".into()));
+    // Linked audio clips don't count as separate matches
+    assert_eq!(app.timeline_search_matches().len(), 2);
+    assert_eq!(app.playback_position, TimelinePosition::zero());
+    assert_eq!(app.selected_clip.map(|(track, _)| track), Some(0));
+
+    app.update(Message::TimelineSearchNext);
+    assert_eq!(app.playback_position, TimelinePosition::from_secs_f64(12.0));
+    app.update(Message::TimelineSearchNext);
+    assert_eq!(app.playback_position, TimelinePosition::zero());
+    assert_eq!(app.timeline_search_cursor, Some(0));
+
+    app.update(Message::TimelineSearchChanged("nothing".into()));
+    assert_eq!(app.status_message, "No clips match the search");
+    assert_eq!(app.timeline_search_cursor, None);
+}
+
+#[test]
+fn test_fit_to_fill_retimes_source_range_into_gap_under_playhead() {
+    let mut app = App::new();
+    let filler = make_test_asset("filler", 5.0);
+    let filler_id = filler.id;
+    app.update(Message::MediaImported(Ok(filler)));
+    for start in [0.0, 6.0] {
+        app.update(Message::AddClipToTimeline {
+            asset_id: filler_id,
+            track_index: 0,
+            position: TimelinePosition::from_secs_f64(start),
+        });
+    }
+    let insert = make_test_asset("insert", 10.0);
+    let insert_id = insert.id;
+    app.project.source_library.import(insert);
+
+    app.update(Message::FitToFill);
+    assert_eq!(app.status_message, "Load a clip in the source monitor to fit");
+
+    // Shots stand in for marking in/out by hand: 2s of source
+    app.update(Message::ShotsDetected { asset_id: insert_id, result: Ok(vec![1.0, 3.0]) });
+    app.update(Message::OpenShotInSourceMonitor { asset_id: insert_id, shot: 1 });
+    app.update(Message::SeekTo(TimelinePosition::from_secs_f64(5.5)));
+    app.update(Message::FitToFill);
+
+    let (track_index, clip_id) = app.selected_clip.unwrap();
+    assert_eq!(track_index, 0);
+    let clip = app.project.timeline.tracks[0].get_clip(clip_id).unwrap();
+    assert_eq!(clip.timeline_range.start, TimelinePosition::from_secs_f64(5.0));
+    assert_eq!(clip.timeline_range.end, TimelinePosition::from_secs_f64(6.0));
+    assert!((clip.speed() - 2.0).abs() < 1e-9);
+    assert_eq!(app.status_message, "Filled gap at 200% speed");
+    // The linked audio fills the same gap on A1
+    assert_eq!(app.project.timeline.tracks[1].clips.len(), 3);
+
+    app.update(Message::FitToFill);
+    assert_eq!(app.status_message, "No gap under the playhead to fill");
+}
+
+#[test]
+fn test_restore_project_version_keeps_project_path() {
+    use std::time::{Duration, UNIX_EPOCH};
+    use zeditor_core::versions::ProjectVersions;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("MyProject.zpf");
+    let versions = ProjectVersions::for_project(&path);
+
+    // Version with one asset, then a later save with two
+    let mut original = App::new();
+    original.update(Message::MediaImported(Ok(make_test_asset("clip1", 5.0))));
+    original.project.save(&path).unwrap();
+    versions.record(&path, UNIX_EPOCH + Duration::from_secs(60), 10).unwrap();
+    original.update(Message::MediaImported(Ok(make_test_asset("clip2", 5.0))));
+    original.project.save(&path).unwrap();
+    versions.record(&path, UNIX_EPOCH + Duration::from_secs(120), 10).unwrap();
+
+    let mut app = App::new();
+    app.update(Message::ShowProjectVersions);
+    assert!(app.version_dialog.is_none(), "unsaved projects have no versions");
+
+    app.update(Message::LoadProject(path.clone()));
+    assert_eq!(app.project.source_library.len(), 2);
+    app.update(Message::ShowProjectVersions);
+    let listed = app.version_dialog.clone().expect("dialog opens");
+    assert_eq!(listed.len(), 2);
+    assert_eq!(listed[1].label, "1970-01-01T00-01-00");
+
+    app.update(Message::RestoreProjectVersion(listed[1].path.clone()));
+    assert!(app.version_dialog.is_none());
+    assert_eq!(app.project.source_library.len(), 1);
+    assert_eq!(app.project_path.as_deref(), Some(path.as_path()));
+}
+
+#[test]
+fn test_replace_media_swaps_file_and_warns() {
+    use zeditor_ui::notifications::NotificationLevel;
+
+    let mut app = App::new();
+    let asset = make_test_asset("draft_vo", 10.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+
+    app.update(Message::ReplaceSelectedMedia);
+    assert!(app.status_message.contains("Select"), "status: {}", app.status_message);
+
+    let mut final_vo = make_test_asset("final_vo", 6.0);
+    final_vo.path = "/tmp/final_vo.mp4".into();
+    app.update(Message::ReplacementProbed {
+        asset_id,
+        result: Ok(final_vo),
+    });
+
+    let asset = app.project.source_library.get(asset_id).unwrap();
+    assert_eq!(asset.name, "draft_vo");
+    assert_eq!(asset.path, std::path::Path::new("/tmp/final_vo.mp4"));
+    assert_eq!(asset.duration, Duration::from_secs(6));
+    // The linked video and audio clips both read 10s of a 6s file
+    let history: Vec<_> = app.notifications.history().map(|n| (n.level, n.message.clone())).collect();
+    assert_eq!(history.last().unwrap().0, NotificationLevel::Warning);
+    assert!(history.last().unwrap().1.contains("2 clip(s)"), "{history:?}");
+
+    // An audio-only file can't replace a video asset
+    let mut wav = make_test_asset("final_vo", 6.0);
+    wav.width = 0;
+    wav.height = 0;
+    app.update(Message::ReplacementProbed {
+        asset_id,
+        result: Ok(wav),
+    });
+    assert_eq!(app.project.source_library.get(asset_id).unwrap().width, 1920);
+    assert_eq!(app.notifications.history().last().unwrap().level, NotificationLevel::Error);
+}
+
+#[test]
+fn test_reveal_asset_steps_through_usages() {
+    let mut app = App::new();
+    let asset = make_test_asset("interview", 4.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    app.update(Message::MediaImported(Ok(make_test_asset("broll", 4.0))));
+    for secs in [10.0, 0.0] {
+        app.update(Message::AddClipToTimeline {
+            asset_id,
+            track_index: 0,
+            position: TimelinePosition::from_secs_f64(secs),
+        });
+    }
+
+    // Linked video and audio count once
+    let usages = app.asset_usages(asset_id);
+    assert_eq!(usages.len(), 2);
+    let start_of = |app: &App, (track, clip): (usize, uuid::Uuid)| {
+        app.project.timeline.tracks[track].get_clip(clip).unwrap().timeline_range.start
+    };
+    assert_eq!(start_of(&app, usages[0]), TimelinePosition::zero());
+
+    app.update(Message::RevealAssetInTimeline(asset_id));
+    assert_eq!(app.selected_clip, Some(usages[0]));
+    assert_eq!(app.playback_position, TimelinePosition::zero());
+    app.update(Message::RevealAssetInTimeline(asset_id));
+    assert_eq!(app.selected_clip, Some(usages[1]));
+    assert_eq!(app.playback_position, TimelinePosition::from_secs_f64(10.0));
+    assert!(app.status_message.contains("2 of 2"), "status: {}", app.status_message);
+    app.update(Message::RevealAssetInTimeline(asset_id));
+    assert_eq!(app.selected_clip, Some(usages[0]));
+
+    let unused = app.project.source_library.assets()[1].id;
+    app.update(Message::RevealAssetInTimeline(unused));
+    assert!(app.status_message.contains("isn't used"), "status: {}", app.status_message);
+}
+
+#[test]
+fn test_remap_media_paths_previews_then_repoints_found_files() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.mp4"), b"").unwrap();
+    let mut app = App::new();
+    let found = make_test_asset("a", 5.0);
+    let missing = make_test_asset("b", 5.0);
+    let (found_id, missing_id) = (found.id, missing.id);
+    app.update(Message::MediaImported(Ok(found)));
+    app.update(Message::MediaImported(Ok(missing)));
+
+    // Starts from the folder of the first missing file
+    app.update(Message::MenuAction(MenuAction::RemapMediaPaths));
+    let dialog = app.path_remap_dialog.as_ref().expect("dialog opens");
+    assert_eq!(dialog.from, "/test");
+    assert!(dialog.preview.is_empty(), "nothing to preview without a replacement");
+
+    app.update(Message::PathRemapToChanged(dir.path().display().to_string()));
+    let preview = &app.path_remap_dialog.as_ref().unwrap().preview;
+    assert_eq!(preview.len(), 2);
+    assert!(preview[0].resolves);
+    assert!(!preview[1].resolves);
+    // Previewing changes nothing
+    assert_eq!(app.project.source_library.get(found_id).unwrap().path, PathBuf::from("/test/a.mp4"));
+
+    app.update(Message::ApplyPathRemap);
+    assert!(app.path_remap_dialog.is_none());
+    assert_eq!(app.project.source_library.get(found_id).unwrap().path, dir.path().join("a.mp4"));
+    assert_eq!(app.project.source_library.get(missing_id).unwrap().path, PathBuf::from("/test/b.mp4"));
+    assert!(app.status_message.contains("Remapped 1"), "status: {}", app.status_message);
+}
+
+#[test]
+fn test_remap_media_paths_keeps_dialog_when_nothing_resolves() {
+    let mut app = App::new();
+    app.update(Message::MediaImported(Ok(make_test_asset("a", 5.0))));
+    app.update(Message::OpenPathRemap);
+    app.update(Message::PathRemapToChanged("/nowhere".into()));
+    app.update(Message::ApplyPathRemap);
+    assert!(app.path_remap_dialog.is_some());
+    assert!(app.status_message.contains("No matching file"), "status: {}", app.status_message);
+
+    app.update(Message::PathRemapDismissed);
+    assert!(app.path_remap_dialog.is_none());
+}
+
+#[test]
+fn test_timeline_snapshot_compare_and_restore() {
+    let mut app = App::new();
+    let asset = make_test_asset("interview", 4.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+    let v1_cut = app.project.timeline.clone();
+
+    app.update(Message::SnapshotNameChanged("v1 cut".into()));
+    app.update(Message::TakeTimelineSnapshot);
+    assert!(app.snapshot_name.is_empty());
+    assert_eq!(app.project.snapshots.len(), 1);
+    let snapshot_id = app.project.snapshots[0].id;
+    assert!(app.status_message.contains("v1 cut"), "status: {}", app.status_message);
+
+    // Unnamed snapshots get a numbered name
+    app.update(Message::TakeTimelineSnapshot);
+    assert_eq!(app.project.snapshots[1].name, "Snapshot 2");
+
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::from_secs_f64(10.0),
+    });
+    app.update(Message::CompareTimelineSnapshot(snapshot_id));
+    assert_eq!(app.compared_snapshot, Some(snapshot_id));
+    // The new linked video and audio clips
+    assert_eq!(app.project.diff_snapshot(snapshot_id).unwrap().len(), 2);
+    app.update(Message::CompareTimelineSnapshot(snapshot_id));
+    assert_eq!(app.compared_snapshot, None);
+
+    app.update(Message::RestoreTimelineSnapshot(snapshot_id));
+    assert_eq!(app.project.timeline, v1_cut);
+    app.update(Message::Undo);
+    assert_eq!(app.project.diff_snapshot(snapshot_id).unwrap().len(), 2);
+
+    app.update(Message::DeleteTimelineSnapshot(snapshot_id));
+    assert_eq!(app.project.snapshots.len(), 1);
+}
+
+#[test]
+fn test_ripple_edits_shift_downstream_clips_when_on() {
+    let mut app = App::new();
+    let asset = make_test_asset("clip1", 5.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    for start in [0.0, 5.0] {
+        app.update(Message::AddClipToTimeline {
+            asset_id,
+            track_index: 0,
+            position: TimelinePosition::from_secs_f64(start),
+        });
+    }
+    let starts = |app: &App, track: usize| -> Vec<f64> {
+        app.project.timeline.tracks[track]
+            .clips
+            .iter()
+            .map(|c| c.timeline_range.start.as_secs_f64())
+            .collect()
+    };
+    let first = app.project.timeline.tracks[0].clips[0].id;
+
+    // Off: trimming leaves a gap
+    app.update(Message::ResizeClip {
+        track_index: 0,
+        clip_id: first,
+        new_end: TimelinePosition::from_secs_f64(4.0),
+    });
+    assert_eq!(starts(&app, 0), vec![0.0, 5.0]);
+
+    app.update(Message::MenuAction(MenuAction::ToggleRippleEdits));
+    assert!(app.preferences.ripple_edits);
+    assert_eq!(app.status_message, "Ripple edits on");
+
+    // Slowing the first clip to half speed (8s) pushes the second along on V1 and A1
+    app.update(Message::SetClipSpeed {
+        track_index: 0,
+        clip_id: first,
+        speed: 0.5,
+    });
+    assert_eq!(app.project.timeline.tracks[0].clips[0].duration(), Duration::from_secs(8));
+    assert_eq!(starts(&app, 0), vec![0.0, 9.0]);
+    assert_eq!(starts(&app, 1), vec![0.0, 9.0]);
+
+    app.update(Message::RemoveClip {
+        track_index: 0,
+        clip_id: first,
+    });
+    // The gap left by the earlier trim is kept
+    assert_eq!(starts(&app, 0), vec![1.0]);
+    assert_eq!(starts(&app, 1), vec![1.0]);
+
+    app.update(Message::MenuAction(MenuAction::Undo));
+    assert_eq!(starts(&app, 0), vec![0.0, 9.0]);
+}
+
+#[test]
+fn test_toggle_snap_targets_from_menu() {
+    let mut app = App::new();
+    assert!(app.preferences.snap_to_markers && app.preferences.snap_to_playhead);
+
+    app.update(Message::MenuAction(MenuAction::ToggleSnapTarget(SnapTarget::Markers)));
+    assert!(!app.preferences.snap_to_markers);
+    assert!(app.preferences.snap_to_playhead);
+    assert_eq!(app.status_message, "Snap to Markers: off");
+
+    app.update(Message::ToggleSnapTarget(SnapTarget::Playhead));
+    app.update(Message::ToggleSnapTarget(SnapTarget::Markers));
+    assert!(app.preferences.snap_to_markers);
+    assert!(!app.preferences.snap_to_playhead);
+    assert_eq!(app.status_message, "Snap to Markers: on");
+}
+
+#[test]
+fn test_quiet_audio_trim_snapping_toggles_and_falls_back() {
+    let mut app = App::new();
+    assert!(app.preferences.snap_audio_trims);
+    app.update(Message::MenuAction(MenuAction::ToggleSnapTarget(SnapTarget::QuietAudio)));
+    assert!(!app.preferences.snap_audio_trims);
+    assert_eq!(app.status_message, "Snap Audio Trims to Quiet Points: off");
+
+    // Without audio to read, the clip is trimmed where the edge was released
+    let asset = MediaAsset::new(
+        "clip1".into(),
+        PathBuf::from("/test/clip1.mp4"),
+        Duration::from_secs_f64(5.0),
+        1920, 1080, 30.0, false,
+    );
     let asset_id = asset.id;
     app.update(Message::MediaImported(Ok(asset)));
-
     app.update(Message::AddClipToTimeline {
         asset_id,
         track_index: 0,
         position: TimelinePosition::zero(),
     });
-
-    assert_eq!(app.project.timeline.tracks[0].clips.len(), 1);
-    assert_eq!(app.status_message, "Clip added");
+    let clip_id = app.project.timeline.tracks[0].clips[0].id;
+    app.update(Message::SnapAudioTrim {
+        track_index: 0,
+        clip_id,
+        new_end: TimelinePosition::from_secs_f64(3.0),
+    });
+    let clip = &app.project.timeline.tracks[0].clips[0];
+    assert_eq!(clip.timeline_range.end, TimelinePosition::from_secs_f64(3.0));
 }
 
 #[test]
-fn test_add_clip_nonexistent_asset() {
+fn test_set_clip_channel_map_routes_linked_audio_and_undoes() {
     let mut app = App::new();
-
+    let asset = make_test_asset("camera", 5.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
     app.update(Message::AddClipToTimeline {
-        asset_id: uuid::Uuid::new_v4(),
+        asset_id,
         track_index: 0,
         position: TimelinePosition::zero(),
     });
+    let video = app.project.timeline.tracks[0].clips[0].id;
 
-    assert_eq!(app.project.timeline.tracks[0].clips.len(), 0);
-    assert_eq!(app.status_message, "Asset not found");
+    app.update(Message::SetClipChannelMap {
+        track_index: 0,
+        clip_id: video,
+        map: ChannelMap::LeftOnly,
+    });
+    assert_eq!(app.project.timeline.tracks[1].clips[0].channel_map, ChannelMap::LeftOnly);
+
+    app.update(Message::Undo);
+    assert_eq!(app.project.timeline.tracks[1].clips[0].channel_map, ChannelMap::Stereo);
 }
 
 #[test]
-fn test_cut_clip_flow() {
+fn test_set_clip_preserve_pitch_and_undo() {
     let mut app = App::new();
-
-    let asset = make_test_asset("clip1", 10.0);
+    let asset = make_test_asset("interview", 5.0);
     let asset_id = asset.id;
     app.update(Message::MediaImported(Ok(asset)));
-
     app.update(Message::AddClipToTimeline {
         asset_id,
         track_index: 0,
         position: TimelinePosition::zero(),
     });
+    let video = app.project.timeline.tracks[0].clips[0].id;
 
-    app.update(Message::CutClip {
+    app.update(Message::SetClipPreservePitch {
         track_index: 0,
-        position: TimelinePosition::from_secs_f64(4.0),
+        clip_id: video,
+        preserve: true,
     });
+    assert!(app.project.timeline.tracks[1].clips[0].preserve_pitch);
 
-    assert_eq!(app.project.timeline.tracks[0].clips.len(), 2);
-    assert_eq!(app.status_message, "Clip cut");
+    app.update(Message::Undo);
+    assert!(!app.project.timeline.tracks[1].clips[0].preserve_pitch);
 }
 
 #[test]
-fn test_undo_redo_flow() {
+fn test_set_clip_rate_conform_retimes_and_undoes() {
     let mut app = App::new();
-
-    let asset = make_test_asset("clip1", 5.0);
+    let mut asset = make_test_asset("film", 5.0);
+    asset.fps = 24.0;
     let asset_id = asset.id;
     app.update(Message::MediaImported(Ok(asset)));
-
     app.update(Message::AddClipToTimeline {
         asset_id,
         track_index: 0,
         position: TimelinePosition::zero(),
     });
-    assert_eq!(app.project.timeline.tracks[0].clips.len(), 1);
+    assert!(app.project.has_mismatched_fps(asset_id));
+    let video = app.project.timeline.tracks[0].clips[0].id;
 
-    app.update(Message::Undo);
-    assert_eq!(app.project.timeline.tracks[0].clips.len(), 0);
-    assert_eq!(app.status_message, "Undone");
+    app.update(Message::SetClipRateConform {
+        track_index: 0,
+        clip_id: video,
+        conform: RateConform::Reinterpret,
+    });
+    for track in 0..2 {
+        let clip = &app.project.timeline.tracks[track].clips[0];
+        assert_eq!(clip.rate_conform, RateConform::Reinterpret);
+        assert!((clip.duration().as_secs_f64() - 4.0).abs() < 1e-6);
+    }
 
-    app.update(Message::Redo);
-    assert_eq!(app.project.timeline.tracks[0].clips.len(), 1);
-    assert_eq!(app.status_message, "Redone");
+    app.update(Message::Undo);
+    let clip = &app.project.timeline.tracks[0].clips[0];
+    assert_eq!(clip.rate_conform, RateConform::Resample);
+    assert_eq!(clip.duration(), Duration::from_secs(5));
 }
 
 #[test]
-fn test_play_pause() {
+fn test_set_clip_canvas_fit_and_undo() {
     let mut app = App::new();
-    assert!(!app.is_playing);
-
-    app.update(Message::Play);
-    assert!(app.is_playing);
-    assert!(app.playback_start_wall.is_some());
-
-    app.update(Message::Pause);
-    assert!(!app.is_playing);
-    assert!(app.playback_start_wall.is_none());
-}
+    let asset = make_test_asset("clip", 5.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+    let video = app.project.timeline.tracks[0].clips[0].id;
 
-#[test]
-fn test_seek() {
-    let mut app = App::new();
-    assert_eq!(app.playback_position, TimelinePosition::zero());
+    app.update(Message::SetClipCanvasFit {
+        track_index: 0,
+        clip_id: video,
+        fit: CanvasFit::Fill,
+    });
+    assert_eq!(app.project.timeline.tracks[0].clips[0].canvas_fit, CanvasFit::Fill);
+    assert_eq!(app.project.timeline.tracks[1].clips[0].canvas_fit, CanvasFit::Fit);
 
-    app.update(Message::SeekTo(TimelinePosition::from_secs_f64(5.0)));
-    assert_eq!(
-        app.playback_position,
-        TimelinePosition::from_secs_f64(5.0)
-    );
+    app.update(Message::Undo);
+    assert_eq!(app.project.timeline.tracks[0].clips[0].canvas_fit, CanvasFit::Fit);
 }
 
 #[test]
-fn test_remove_asset() {
+fn test_set_clip_volume_envelope_applies_to_linked_audio_and_undoes() {
     let mut app = App::new();
-
-    let asset = make_test_asset("clip1", 5.0);
+    let asset = make_test_asset("camera", 5.0);
     let asset_id = asset.id;
     app.update(Message::MediaImported(Ok(asset)));
-    assert_eq!(app.project.source_library.len(), 1);
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+    let audio = app.project.timeline.tracks[1].clips[0].id;
 
-    app.update(Message::RemoveAsset(asset_id));
-    assert_eq!(app.project.source_library.len(), 0);
-    assert!(app.status_message.contains("Removed"));
+    let mut envelope = VolumeEnvelope::default();
+    envelope.add_point(1.0, 0.0);
+    envelope.add_point(2.0, -12.0);
+    app.update(Message::SetClipVolumeEnvelope {
+        track_index: 1,
+        clip_id: audio,
+        envelope: envelope.clone(),
+    });
+    assert_eq!(app.project.timeline.tracks[1].clips[0].volume_envelope, envelope);
+    assert_eq!(app.project.timeline.tracks[0].clips[0].volume_envelope, envelope);
+
+    app.update(Message::Undo);
+    assert!(app.project.timeline.tracks[1].clips[0].volume_envelope.is_empty());
 }
 
 #[test]
-fn test_move_clip_flow() {
+fn test_inspector_waveform_trims_clip_source() {
     let mut app = App::new();
-
-    // Use no_audio asset to test simple move without linked audio clip
-    let asset = MediaAsset::new(
-        "clip1".into(),
-        PathBuf::from("/test/clip1.mp4"),
-        Duration::from_secs_f64(5.0),
-        1920, 1080, 30.0, false,
-    );
+    let asset = make_test_asset("camera", 5.0);
     let asset_id = asset.id;
     app.update(Message::MediaImported(Ok(asset)));
-
     app.update(Message::AddClipToTimeline {
         asset_id,
         track_index: 0,
         position: TimelinePosition::zero(),
     });
+    let video = app.project.timeline.tracks[0].clips[0].id;
 
-    let clip_id = app.project.timeline.tracks[0].clips[0].id;
+    app.update(Message::InspectorWaveformLoaded {
+        clip_id: video,
+        window: (0.0, 5.0),
+        result: Ok(vec![0.5; 10]),
+    });
+    assert_eq!(app.inspector_waveform.as_ref().map(|w| w.clip_id), Some(video));
 
-    app.update(Message::MoveClip {
-        source_track: 0,
-        clip_id,
-        dest_track: 1,
-        position: TimelinePosition::from_secs_f64(2.0),
+    let source = TimeRange::new(TimelinePosition::from_secs_f64(1.0), TimelinePosition::from_secs_f64(3.0)).unwrap();
+    app.update(Message::SetClipSourceRange {
+        track_index: 0,
+        clip_id: video,
+        source,
     });
+    for track in 0..2 {
+        let clip = &app.project.timeline.tracks[track].clips[0];
+        assert_eq!(clip.source_range, source);
+        assert_eq!(clip.timeline_range.end, TimelinePosition::from_secs_f64(2.0));
+    }
 
-    assert_eq!(app.project.timeline.tracks[0].clips.len(), 0);
-    assert_eq!(app.project.timeline.tracks[1].clips.len(), 1);
+    app.update(Message::Undo);
+    assert_eq!(app.project.timeline.tracks[1].clips[0].source_range.end, TimelinePosition::from_secs_f64(5.0));
 }
 
 #[test]
-fn test_select_source_asset() {
+fn test_split_dual_mono_from_menu_and_undo() {
     let mut app = App::new();
-    assert!(app.selected_asset_id.is_none());
+    let asset = make_test_asset("camera", 5.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
 
-    let id = uuid::Uuid::new_v4();
-    app.update(Message::SelectSourceAsset(Some(id)));
-    assert_eq!(app.selected_asset_id, Some(id));
+    app.update(Message::MenuAction(MenuAction::SplitDualMono));
+    assert_eq!(app.status_message, "Select a clip to split its audio");
 
-    app.update(Message::SelectSourceAsset(None));
-    assert!(app.selected_asset_id.is_none());
+    let video = app.project.timeline.tracks[0].clips[0].id;
+    app.update(Message::SelectTimelineClip(Some((0, video))));
+    app.update(Message::MenuAction(MenuAction::SplitDualMono));
+    assert_eq!(app.status_message, "Split audio: left channel kept, right channel on A2");
+    assert_eq!(app.project.timeline.tracks.len(), 3);
+    assert_eq!(app.project.timeline.tracks[1].clips[0].channel_map, ChannelMap::LeftOnly);
+    assert_eq!(app.project.timeline.tracks[2].clips[0].channel_map, ChannelMap::RightOnly);
+
+    app.update(Message::Undo);
+    assert_eq!(app.project.timeline.tracks.len(), 2);
+    assert_eq!(app.project.timeline.tracks[1].clips[0].channel_map, ChannelMap::Stereo);
 }
 
 #[test]
-fn test_open_file_dialog_returns_task() {
+fn test_render_ranges_flag_add_and_queue() {
     let mut app = App::new();
-    let task = app.update(Message::OpenFileDialog);
-    // OpenFileDialog returns a Task::perform, which is non-trivial
-    // We can't easily inspect it, but verify the status was updated
-    assert_eq!(app.status_message, "Opening file dialog...");
-    let _ = task; // just ensure it compiles and doesn't panic
+    let asset = make_test_asset("clip1", 5.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+
+    app.update(Message::AddRenderRange);
+    assert_eq!(app.status_message, "Flag an in and an out point first");
+
+    app.playback_position = TimelinePosition::from_secs_f64(3.0);
+    app.update(Message::SetRenderRangeIn);
+    app.playback_position = TimelinePosition::from_secs_f64(1.0);
+    app.update(Message::SetRenderRangeOut);
+    app.update(Message::AddRenderRange);
+    assert_eq!(app.status_message, "The out flag must be after the in flag");
+
+    app.update(Message::SetRenderRangeIn);
+    app.playback_position = TimelinePosition::from_secs_f64(3.0);
+    app.update(Message::SetRenderRangeOut);
+    app.update(Message::RenderRangeNameChanged("Teaser".into()));
+    app.update(Message::AddRenderRange);
+    assert_eq!(app.status_message, "Added render range Teaser");
+    assert_eq!(app.project.render_ranges.len(), 1);
+    assert_eq!(app.render_range_in, None);
+
+    app.update(Message::RenderFilePatternChanged("{index}-{project}".into()));
+    let jobs = app.render_range_jobs(&PathBuf::from("/exports"));
+    assert_eq!(jobs.len(), 1);
+    let job = &jobs[0];
+    assert_eq!(job.output_path, PathBuf::from("/exports/01-Untitled.mkv"));
+    assert_eq!(job.timeline.duration(), Duration::from_secs(2));
 }
 
 #[test]
-fn test_toggle_playback() {
+fn test_add_marker_at_playhead_is_undoable() {
     let mut app = App::new();
-    assert!(!app.is_playing);
-
-    app.update(Message::TogglePlayback);
-    assert!(app.is_playing);
+    app.playback_position = TimelinePosition::from_secs_f64(4.0);
+    app.update(Message::AddMarker);
+    assert_eq!(app.status_message, "Added Marker 1");
+    let marker = &app.project.timeline.markers[0];
+    assert_eq!((marker.name.as_str(), marker.position), ("Marker 1", TimelinePosition::from_secs_f64(4.0)));
 
-    app.update(Message::TogglePlayback);
-    assert!(!app.is_playing);
+    app.update(Message::Undo);
+    assert!(app.project.timeline.markers.is_empty());
 }
 
 #[test]
-fn test_playback_tick_advances_position() {
+fn test_generate_and_clear_guides() {
     let mut app = App::new();
+    app.update(Message::GenerateGuides);
+    assert_eq!(app.status_message, "Add clips to the timeline before generating guides");
 
-    // Add content so playback doesn't immediately stop
-    let asset = make_test_asset("clip1", 30.0);
+    let asset = make_test_asset("song", 2.0);
     let asset_id = asset.id;
     app.update(Message::MediaImported(Ok(asset)));
     app.update(Message::AddClipToTimeline {
@@ -247,40 +2563,63 @@ fn test_playback_tick_advances_position() {
         position: TimelinePosition::zero(),
     });
 
-    app.update(Message::Play);
-    assert!(app.is_playing);
+    app.update(Message::GuideIntervalChanged("0".into()));
+    app.update(Message::GenerateGuides);
+    assert_eq!(app.status_message, "Guide interval must be a positive number");
+    assert!(app.project.timeline.guides.is_empty());
 
-    // Simulate a small passage of time by adjusting the start wall
-    let start = app.playback_start_wall.unwrap();
-    app.playback_start_wall = Some(start - Duration::from_millis(100));
+    // 15 frames at the default 30fps: every 0.5s over [0, 2]
+    app.update(Message::GuideIntervalChanged("15".into()));
+    app.update(Message::ToggleGuideIntervalUnit);
+    app.update(Message::GenerateGuides);
+    assert_eq!(app.project.timeline.guides.len(), 5);
+    assert_eq!(app.status_message, "Added 5 guides");
 
-    app.update(Message::PlaybackTick);
-    assert!(app.playback_position.as_secs_f64() > 0.0);
+    app.update(Message::ClearGuides);
+    assert!(app.project.timeline.guides.is_empty());
+    app.update(Message::Undo);
+    assert_eq!(app.project.timeline.guides.len(), 5);
+
+    app.update(Message::SeekTo(TimelinePosition::from_secs_f64(0.25)));
+    app.update(Message::AddGuide);
+    assert_eq!(app.project.timeline.guides[1], TimelinePosition::from_secs_f64(0.25));
 }
 
 #[test]
-fn test_playback_continues_past_end() {
+fn test_detected_beats_become_guides_in_timeline_time() {
+    use zeditor_media::beats::BeatAnalysis;
+
     let mut app = App::new();
+    app.update(Message::DetectBeatsSelectedClip);
+    assert_eq!(app.status_message, "Select a clip with audio to detect beats");
 
-    let asset = make_test_asset("clip1", 1.0);
+    let asset = make_test_asset("song", 4.0);
     let asset_id = asset.id;
     app.update(Message::MediaImported(Ok(asset)));
     app.update(Message::AddClipToTimeline {
         asset_id,
         track_index: 0,
-        position: TimelinePosition::zero(),
+        position: TimelinePosition::from_secs_f64(10.0),
     });
+    let clip_id = app.project.timeline.tracks[1].clips[0].id;
+    // Trim the source to [1, 3): the clip now covers 10s..12s
+    let source = TimeRange::new(TimelinePosition::from_secs_f64(1.0), TimelinePosition::from_secs_f64(3.0)).unwrap();
+    app.update(Message::SetClipSourceRange { track_index: 1, clip_id, source });
 
-    app.update(Message::Play);
+    app.update(Message::BeatsDetected {
+        track_index: 1,
+        clip_id,
+        result: Ok(Some(BeatAnalysis { bpm: 120.0, beats: vec![1.0, 1.5, 2.0, 2.5] })),
+    });
+    let guides: Vec<f64> = app.project.timeline.guides.iter().map(|g| g.as_secs_f64()).collect();
+    assert_eq!(guides, vec![10.0, 10.5, 11.0, 11.5]);
+    assert_eq!(app.status_message, "Added 4 beat guides (120.0 BPM)");
 
-    // Set start wall far in the past so elapsed > timeline duration
-    let start = app.playback_start_wall.unwrap();
-    app.playback_start_wall = Some(start - Duration::from_secs(5));
+    app.update(Message::Undo);
+    assert!(app.project.timeline.guides.is_empty());
 
-    app.update(Message::PlaybackTick);
-    // Playback should continue past the end — user stops manually
-    assert!(app.is_playing);
-    assert!(app.playback_position.as_secs_f64() > 1.0);
+    app.update(Message::BeatsDetected { track_index: 1, clip_id, result: Ok(None) });
+    assert_eq!(app.status_message, "No steady beat found");
 }
 
 #[test]
@@ -302,6 +2641,49 @@ fn test_spacebar_toggles() {
     assert!(app.is_playing);
 }
 
+#[test]
+fn test_space_drag_pan_undoes_playback_toggle() {
+    let mut app = App::new();
+    let space = iced::keyboard::Key::Named(iced::keyboard::key::Named::Space);
+    let physical_key = iced::keyboard::key::Physical::Unidentified(iced::keyboard::key::NativeCode::Unidentified);
+    let press = |repeat| {
+        Message::KeyboardEvent(iced::keyboard::Event::KeyPressed {
+            key: space.clone(),
+            modified_key: space.clone(),
+            physical_key,
+            location: iced::keyboard::Location::Standard,
+            modifiers: iced::keyboard::Modifiers::empty(),
+            text: None,
+            repeat,
+        })
+    };
+    let release = Message::KeyboardEvent(iced::keyboard::Event::KeyReleased {
+        key: space.clone(),
+        modified_key: space.clone(),
+        physical_key,
+        location: iced::keyboard::Location::Standard,
+        modifiers: iced::keyboard::Modifiers::empty(),
+    });
+
+    app.update(press(false));
+    app.update(press(true));
+    assert!(app.is_playing, "auto-repeat doesn't toggle back");
+    app.update(Message::TimelinePanStarted);
+    assert!(!app.is_playing);
+    app.update(Message::TimelinePan { dx: 120.0, dy: 0.0 });
+    assert_eq!(app.timeline_scroll, 120.0);
+    app.update(release.clone());
+
+    // Once Space is up, a later pan leaves playback alone
+    app.update(press(false));
+    app.update(release);
+    assert!(app.is_playing);
+    app.update(Message::TimelinePanStarted);
+    assert!(app.is_playing);
+    app.update(Message::TimelinePan { dx: -500.0, dy: 0.0 });
+    assert_eq!(app.timeline_scroll, 0.0);
+}
+
 #[test]
 fn test_timeline_click_empty_moves_cursor() {
     let mut app = App::new();
@@ -725,6 +3107,39 @@ fn test_resize_linked_pair() {
 }
 
 
+#[test]
+fn test_trim_two_up_follows_resize_drag() {
+    let (mut app, _, clip_id) = setup_app_with_clip();
+    let fps = app.project.settings.fps;
+
+    // Edges land on the frame grid; moves during a decode wait for it
+    app.update(Message::TrimEdgeMoved {
+        track_index: 0,
+        clip_id,
+        edge: TimelinePosition::from_secs_f64(3.0 + 0.2 / fps),
+    });
+    let two_up = app.trim_two_up.as_ref().unwrap();
+    assert!((two_up.edge_secs - 3.0).abs() < 1e-9);
+    assert_eq!(two_up.loading, Some(two_up.edge_secs));
+
+    app.update(Message::TrimEdgeMoved {
+        track_index: 0,
+        clip_id,
+        edge: TimelinePosition::from_secs_f64(4.0),
+    });
+    app.update(Message::TrimFramesDecoded { clip_id, outgoing: None, incoming: None });
+    let two_up = app.trim_two_up.as_ref().unwrap();
+    assert_eq!(two_up.loading, Some(4.0));
+
+    // Releasing the edge ends the preview
+    app.update(Message::ResizeClip {
+        track_index: 0,
+        clip_id,
+        new_end: TimelinePosition::from_secs_f64(4.0),
+    });
+    assert!(app.trim_two_up.is_none());
+}
+
 #[test]
 fn test_undo_redo_grouped_add() {
     let mut app = App::new();
@@ -1023,6 +3438,7 @@ fn test_start_drag_from_source() {
             assert_eq!(*id, asset_id);
             assert_eq!(name, "clip1");
         }
+        other => panic!("unexpected drag payload: {other:?}"),
     }
     assert!(!drag.over_timeline);
 }
@@ -1110,25 +3526,155 @@ fn test_drag_released_over_timeline_adds_clip() {
     app.update(Message::DragEnteredTimeline);
     app.update(Message::DragOverTimeline(iced::Point::new(200.0, 70.0)));
 
-    app.update(Message::DragReleased);
+    app.update(Message::DragReleased);
+
+    assert!(app.drag_state.is_none());
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 1);
+    assert_eq!(app.status_message, "Clip added");
+}
+
+#[test]
+fn test_drag_released_off_timeline_no_clip() {
+    let mut app = App::new();
+    let asset = make_test_asset("clip1", 5.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    app.update(Message::StartDragFromSource(asset_id));
+    // Don't enter timeline — just release
+    app.update(Message::DragReleased);
+
+    assert!(app.drag_state.is_none());
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 0);
+}
+
+#[test]
+fn test_ctrl_click_toggles_asset_selection() {
+    let mut app = App::new();
+    let a = make_test_asset("a", 2.0);
+    let b = make_test_asset("b", 3.0);
+    let (a_id, b_id) = (a.id, b.id);
+    app.update(Message::MediaImported(Ok(a)));
+    app.update(Message::MediaImported(Ok(b)));
+
+    app.update(Message::SelectSourceAsset(Some(a_id)));
+    app.keyboard_modifiers = iced::keyboard::Modifiers::COMMAND;
+    app.update(Message::SelectSourceAsset(Some(b_id)));
+    assert_eq!(app.selected_asset_ids, vec![a_id, b_id]);
+
+    app.update(Message::SelectSourceAsset(Some(a_id)));
+    assert_eq!(app.selected_asset_ids, vec![b_id]);
+    assert_eq!(app.selected_asset_id, Some(b_id));
+}
+
+#[test]
+fn test_shift_click_selects_range() {
+    let mut app = App::new();
+    let assets: Vec<MediaAsset> = ["a", "b", "c", "d"].iter().map(|n| make_test_asset(n, 1.0)).collect();
+    let ids: Vec<_> = assets.iter().map(|a| a.id).collect();
+    for asset in assets {
+        app.update(Message::MediaImported(Ok(asset)));
+    }
+
+    app.update(Message::SelectSourceAsset(Some(ids[1])));
+    app.keyboard_modifiers = iced::keyboard::Modifiers::SHIFT;
+    app.update(Message::SelectSourceAsset(Some(ids[3])));
+    assert_eq!(app.selected_asset_ids, ids[1..=3].to_vec());
+
+    app.keyboard_modifiers = iced::keyboard::Modifiers::default();
+    app.update(Message::SelectSourceAsset(Some(ids[0])));
+    assert_eq!(app.selected_asset_ids, vec![ids[0]]);
+}
+
+#[test]
+fn test_create_slideshow_from_selection() {
+    let mut app = App::new();
+    let still = MediaAsset::new(
+        "photo".into(),
+        PathBuf::from("/test/photo.jpg"),
+        Duration::from_millis(40),
+        800,
+        600,
+        25.0,
+        false,
+    );
+    let short = make_test_asset("short", 2.0);
+    let long = make_test_asset("long", 10.0);
+    let ids = [still.id, short.id, long.id];
+    for asset in [still, short, long] {
+        app.update(Message::MediaImported(Ok(asset)));
+    }
 
-    assert!(app.drag_state.is_none());
-    assert_eq!(app.project.timeline.tracks[0].clips.len(), 1);
-    assert_eq!(app.status_message, "Clip added");
+    app.update(Message::OpenSlideshow);
+    assert!(app.slideshow_dialog.is_none(), "nothing selected yet");
+
+    app.update(Message::SelectSourceAsset(Some(ids[0])));
+    app.keyboard_modifiers = iced::keyboard::Modifiers::COMMAND;
+    app.update(Message::SelectSourceAsset(Some(ids[1])));
+    app.update(Message::SelectSourceAsset(Some(ids[2])));
+    app.keyboard_modifiers = iced::keyboard::Modifiers::default();
+
+    app.update(Message::OpenSlideshow);
+    app.update(Message::SlideshowDurationChanged("0".into()));
+    app.update(Message::CreateSlideshow);
+    assert!(app.slideshow_dialog.is_some(), "an invalid duration keeps the dialog open");
+    assert!(app.project.timeline.tracks[0].clips.is_empty());
+
+    app.update(Message::SlideshowDurationChanged("3".into()));
+    app.update(Message::CreateSlideshow);
+    assert!(app.slideshow_dialog.is_none());
+
+    // Stills and long videos get 3s, the 2s video keeps its length, back to back
+    let clips = &app.project.timeline.tracks[0].clips;
+    assert_eq!(clips.len(), 3);
+    let duration_of = |id| clips.iter().find(|c| c.asset_id == id).unwrap().duration();
+    assert_eq!(duration_of(ids[0]), Duration::from_secs(3));
+    assert_eq!(duration_of(ids[1]), Duration::from_secs(2));
+    assert_eq!(duration_of(ids[2]), Duration::from_secs(3));
+    assert_eq!(app.project.timeline.tracks[0].end_position(), TimelinePosition::from_secs_f64(8.0));
+    assert_eq!(app.project.timeline.tracks[1].clips.len(), 2, "videos bring their audio");
+
+    app.update(Message::Undo);
+    assert!(app.project.timeline.tracks[0].clips.is_empty());
+    assert!(app.project.timeline.tracks[1].clips.is_empty());
 }
 
 #[test]
-fn test_drag_released_off_timeline_no_clip() {
+fn test_drag_multiple_assets_places_back_to_back() {
     let mut app = App::new();
-    let asset = make_test_asset("clip1", 5.0);
-    let asset_id = asset.id;
-    app.update(Message::MediaImported(Ok(asset)));
-    app.update(Message::StartDragFromSource(asset_id));
-    // Don't enter timeline — just release
+    let a = make_test_asset("a", 2.0);
+    let b = make_test_asset("b", 3.0);
+    let (a_id, b_id) = (a.id, b.id);
+    app.update(Message::MediaImported(Ok(a)));
+    app.update(Message::MediaImported(Ok(b)));
+    app.update(Message::SelectSourceAsset(Some(a_id)));
+    app.keyboard_modifiers = iced::keyboard::Modifiers::COMMAND;
+    app.update(Message::SelectSourceAsset(Some(b_id)));
+    app.keyboard_modifiers = iced::keyboard::Modifiers::default();
+
+    app.update(Message::StartDragFromSource(a_id));
+    match &app.drag_state.as_ref().unwrap().payload {
+        DragPayload::SourceAsset { additional_asset_ids, name, .. } => {
+            assert_eq!(additional_asset_ids, &vec![b_id]);
+            assert_eq!(name, "a +1");
+        }
+        other => panic!("unexpected drag payload: {other:?}"),
+    }
+    app.update(Message::DragEnteredTimeline);
+    app.update(Message::DragOverTimeline(iced::Point::new(200.0, 70.0)));
+    let drop_pos = app.drag_state.as_ref().unwrap().timeline_position.unwrap();
     app.update(Message::DragReleased);
 
-    assert!(app.drag_state.is_none());
-    assert_eq!(app.project.timeline.tracks[0].clips.len(), 0);
+    let clips = &app.project.timeline.tracks[0].clips;
+    assert_eq!(clips.len(), 2);
+    let first = clips.iter().find(|c| c.asset_id == a_id).unwrap();
+    let second = clips.iter().find(|c| c.asset_id == b_id).unwrap();
+    assert_eq!(first.timeline_range.start, drop_pos);
+    assert!((second.timeline_range.start.as_secs_f64() - (drop_pos.as_secs_f64() + 2.0)).abs() < 1e-6);
+    assert_eq!(app.status_message, "Added 2 clips");
+
+    // One drop is one undo step.
+    app.update(Message::Undo);
+    assert!(app.project.timeline.tracks[0].clips.is_empty());
 }
 
 #[test]
@@ -1199,6 +3745,8 @@ fn test_save_project_with_path_saves_file() {
     app.project_path = Some(path.clone());
 
     app.update(Message::SaveProject);
+    assert!(app.is_saving(), "the file is written in the background");
+    app.wait_for_save();
     assert!(path.exists(), "file should be created");
     assert!(app.status_message.contains("Saved"));
 }
@@ -1212,6 +3760,7 @@ fn test_save_file_dialog_result_some() {
     let path = dir.path().join("MyProject.zpf");
 
     app.update(Message::SaveFileDialogResult(Some(path.clone())));
+    app.wait_for_save();
 
     assert_eq!(app.project_path, Some(path));
     assert_eq!(app.project.name, "MyProject");
@@ -1404,15 +3953,175 @@ fn test_save_then_save_again_no_dialog() {
     let dir = tempfile::tempdir().unwrap();
     let path = dir.path().join("Project.zpf");
     app.update(Message::SaveFileDialogResult(Some(path.clone())));
+    app.wait_for_save();
     assert!(app.project_path.is_some());
 
     // Second save: direct (has path)
     app.update(Message::SaveProject);
+    app.wait_for_save();
     assert!(app.status_message.contains("Saved"));
     // Should NOT say "Opening save dialog..."
     assert!(!app.status_message.contains("dialog"));
 }
 
+// ===== Crash recovery journal tests =====
+
+/// Save an app with one imported asset, then add a clip without saving,
+/// leaving an unsaved operation in the journal. Returns the project path.
+fn save_then_edit_unsaved(dir: &tempfile::TempDir) -> PathBuf {
+    let mut app = App::new();
+    let asset = make_test_asset("clip1", 5.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    let path = dir.path().join("journal.zpf");
+    app.update(Message::SaveFileDialogResult(Some(path.clone())));
+    app.wait_for_save();
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+    path
+}
+
+#[test]
+fn test_edits_after_save_are_journaled() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = save_then_edit_unsaved(&dir);
+
+    let journal = zeditor_core::journal::CommandJournal::for_project(&path);
+    let entries = journal.read_entries().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].timeline.tracks[0].clips.len(), 1);
+}
+
+#[test]
+fn test_save_clears_journal() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = save_then_edit_unsaved(&dir);
+
+    let mut app = App::new();
+    app.update(Message::LoadProject(path.clone()));
+    app.update(Message::ConfirmDialogAccepted);
+    app.update(Message::SaveProject);
+    app.wait_for_save();
+
+    let journal = zeditor_core::journal::CommandJournal::for_project(&path);
+    assert!(journal.read_entries().unwrap().is_empty());
+}
+
+#[test]
+fn test_load_with_journal_prompts_replay() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = save_then_edit_unsaved(&dir);
+
+    let mut app = App::new();
+    app.update(Message::LoadProject(path));
+
+    let dialog = app.confirm_dialog.as_ref().expect("replay dialog should be shown");
+    assert!(matches!(
+        dialog.action,
+        zeditor_ui::message::ConfirmAction::ReplayJournal { entry_count: 1 }
+    ));
+    assert_eq!(app.i18n.tr(dialog.action.accept_label_key()), "Replay journal");
+    // Nothing replayed until the user decides.
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 0);
+}
+
+#[test]
+fn test_replay_journal_restores_unsaved_edits() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = save_then_edit_unsaved(&dir);
+
+    let mut app = App::new();
+    app.update(Message::LoadProject(path));
+    app.update(Message::ConfirmDialogAccepted);
+
+    assert!(app.confirm_dialog.is_none());
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 1);
+    assert!(app.status_message.contains("Recovered 1"), "status: {}", app.status_message);
+
+    // Recovered steps are undoable.
+    app.update(Message::Undo);
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 0);
+}
+
+#[test]
+fn test_replay_journal_restores_unsaved_imports() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("imports.zpf");
+    let mut app = App::new();
+    app.update(Message::SaveFileDialogResult(Some(path.clone())));
+    app.wait_for_save();
+    let asset = make_test_asset("clip1", 5.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+
+    let mut app = App::new();
+    app.update(Message::LoadProject(path));
+    app.update(Message::ConfirmDialogAccepted);
+
+    assert!(app.project.source_library.get(asset_id).is_some());
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 1);
+}
+
+#[test]
+fn test_imports_during_a_save_are_journaled_after_it() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("in-flight.zpf");
+    let mut app = App::new();
+    app.update(Message::SaveFileDialogResult(Some(path.clone())));
+    assert!(app.is_saving());
+    let asset = make_test_asset("clip1", 5.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+    app.wait_for_save();
+
+    let mut app = App::new();
+    app.update(Message::LoadProject(path));
+    app.update(Message::ConfirmDialogAccepted);
+
+    assert!(app.project.source_library.get(asset_id).is_some());
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 1);
+}
+
+#[test]
+fn test_dismiss_replay_discards_journal() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = save_then_edit_unsaved(&dir);
+
+    let mut app = App::new();
+    app.update(Message::LoadProject(path.clone()));
+    app.update(Message::ConfirmDialogDismissed);
+
+    assert!(app.confirm_dialog.is_none());
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 0);
+    let journal = zeditor_core::journal::CommandJournal::for_project(&path);
+    assert!(journal.read_entries().unwrap().is_empty());
+}
+
+#[test]
+fn test_load_without_journal_shows_no_dialog() {
+    let project = zeditor_core::project::Project::new("Clean");
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("clean.zpf");
+    project.save(&path).unwrap();
+
+    let mut app = App::new();
+    app.update(Message::LoadProject(path));
+    assert!(app.confirm_dialog.is_none());
+}
+
 // ===== Brief 10: Render tests =====
 
 #[test]
@@ -1454,6 +4163,96 @@ fn test_render_dialog_none_cancels() {
     assert_eq!(app.status_message, "Render cancelled");
 }
 
+#[test]
+fn test_render_to_existing_file_asks_first() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cut.mkv");
+    std::fs::write(&path, b"keep me").unwrap();
+    let mut app = App::new();
+
+    app.update(Message::RenderFileDialogResult(Some(path.clone())));
+    let dialog = app.confirm_dialog.as_ref().expect("overwrite dialog should be shown");
+    assert!(matches!(dialog.input, DialogInput::Choice(ref options) if options.len() == 2));
+    assert!(!app.is_rendering);
+
+    app.update(Message::ConfirmDialogDismissed);
+    assert_eq!(app.status_message, "Render cancelled");
+    assert!(!app.is_rendering);
+    assert_eq!(std::fs::read(&path).unwrap(), b"keep me");
+}
+
+/// An app with a clip and render ranges "A" (0-1s) and "B" (1-2s), exported
+/// as `{name}.mkv`.
+fn app_with_render_ranges() -> App {
+    let mut app = App::new();
+    let asset = make_test_asset("clip1", 5.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+    for (name, start) in [("A", 0.0), ("B", 1.0)] {
+        let range = TimeRange::new(
+            TimelinePosition::from_secs_f64(start),
+            TimelinePosition::from_secs_f64(start + 1.0),
+        )
+        .unwrap();
+        app.project.render_ranges.push(zeditor_core::render_ranges::RenderRange::new(name, range));
+    }
+    app.preferences.render_file_pattern = "{name}".into();
+    app
+}
+
+#[test]
+fn test_range_exports_ask_before_overwriting() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("A.mkv"), b"keep me").unwrap();
+    std::fs::write(dir.path().join("B.mkv"), b"keep me").unwrap();
+    let mut app = app_with_render_ranges();
+
+    app.update(Message::RenderRangesFolderResult(Some(dir.path().to_path_buf())));
+    let dialog = app.confirm_dialog.as_ref().expect("overwrite dialog should be shown");
+    assert!(dialog.message.starts_with("2 of these outputs"), "message: {}", dialog.message);
+    assert!(!app.is_rendering);
+    assert!(app.render_queue.is_empty());
+
+    // Keep both: each range renders to a numbered file instead
+    app.update(Message::ConfirmDialogChoice(1));
+    assert!(app.is_rendering);
+    assert_eq!(app.render_queue[0].output_path, dir.path().join("B (2).mkv"));
+    assert!(app.renders_awaiting_overwrite.is_empty());
+}
+
+#[test]
+fn test_loop_export_to_existing_file_asks_first() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("loop.mkv");
+    std::fs::write(&path, b"keep me").unwrap();
+    let mut app = app_with_render_ranges();
+    let id = app.project.render_ranges[0].id;
+
+    app.update(Message::RenderRangeLoopFileResult { id, path: Some(path.clone()) });
+    assert!(app.confirm_dialog.is_some());
+    assert!(!app.is_rendering);
+
+    app.update(Message::ConfirmDialogDismissed);
+    assert_eq!(app.status_message, "Render cancelled");
+    assert!(app.renders_awaiting_overwrite.is_empty());
+    assert_eq!(std::fs::read(&path).unwrap(), b"keep me");
+}
+
+#[test]
+fn test_render_to_missing_folder_fails_before_starting() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut app = App::new();
+
+    app.update(Message::RenderFileDialogResult(Some(dir.path().join("gone").join("cut.mkv"))));
+    assert!(!app.is_rendering);
+    assert!(app.status_message.starts_with("Cannot render to"), "status: {}", app.status_message);
+}
+
 #[test]
 fn test_menu_render_dispatches() {
     let mut app = App::new();
@@ -1709,6 +4508,82 @@ fn test_confirm_removes_asset_and_clips() {
     assert_eq!(app.project.timeline.tracks[1].clips.len(), 0);
 }
 
+#[test]
+fn test_delete_track_asks_first_when_it_has_clips() {
+    let mut app = App::new();
+    let asset = MediaAsset::new(
+        "clip1".into(),
+        PathBuf::from("/test/clip1.mp4"),
+        Duration::from_secs_f64(5.0),
+        1920, 1080, 30.0, false,
+    );
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+    app.update(Message::AddVideoTrackAbove(0));
+    assert_eq!(app.project.timeline.tracks.len(), 3);
+
+    // The new track is empty, so it goes without asking
+    app.update(Message::DeleteTrack(0));
+    assert!(app.confirm_dialog.is_none());
+    assert_eq!(app.project.timeline.tracks.len(), 2);
+
+    app.update(Message::AddVideoTrackAbove(0));
+    app.update(Message::DeleteTrack(1));
+    assert!(app.confirm_dialog.is_some());
+    assert_eq!(app.project.timeline.tracks.len(), 3);
+    app.update(Message::ConfirmDialogAccepted);
+    assert_eq!(app.project.timeline.tracks.len(), 2);
+    assert!(app.project.timeline.tracks.iter().all(|t| t.clips.is_empty()));
+    assert_eq!(app.status_message, "Track deleted");
+
+    app.update(Message::Undo);
+    assert_eq!(app.project.timeline.tracks[1].clips.len(), 1);
+}
+
+#[test]
+fn test_unsaved_changes_prompt_before_new_project() {
+    let (mut app, _, _) = setup_app_with_clip();
+
+    app.update(Message::MenuAction(MenuAction::NewProject));
+    let dialog = app.confirm_dialog.as_ref().expect("unsaved changes dialog should be shown");
+    assert!(matches!(dialog.input, DialogInput::Choice(ref options) if options.len() == 2));
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 1);
+
+    // Cancelling keeps the project
+    app.update(Message::ConfirmDialogDismissed);
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 1);
+
+    app.update(Message::MenuAction(MenuAction::NewProject));
+    app.update(Message::ConfirmDialogChoice(1));
+    assert!(app.confirm_dialog.is_none());
+    assert!(app.project.timeline.tracks.iter().all(|t| t.clips.is_empty()));
+    assert_eq!(app.status_message, "New project created");
+}
+
+#[test]
+fn test_relink_media_prompts_for_a_path() {
+    let (mut app, asset_id, _) = setup_app_with_clip();
+    app.selected_asset_id = Some(asset_id);
+
+    app.update(Message::MenuAction(MenuAction::RelinkMedia));
+    let dialog = app.confirm_dialog.as_ref().expect("relink prompt should be shown");
+    let current = app.project.source_library.get(asset_id).unwrap().path.display().to_string();
+    assert_eq!(dialog.input, DialogInput::Text(current));
+
+    app.update(Message::ConfirmDialogTextChanged("/moved/clip1.mp4".into()));
+    assert_eq!(
+        app.confirm_dialog.as_ref().unwrap().input,
+        DialogInput::Text("/moved/clip1.mp4".into())
+    );
+    app.update(Message::ConfirmDialogAccepted);
+    assert!(app.confirm_dialog.is_none());
+}
+
 #[test]
 fn test_dismiss_keeps_asset() {
     let mut app = App::new();
@@ -1781,6 +4656,54 @@ fn setup_app_with_clip() -> (App, uuid::Uuid, uuid::Uuid) {
     (app, asset_id, clip_id)
 }
 
+#[test]
+fn test_insert_time_at_playhead_cuts_and_shifts() {
+    let (mut app, _, _) = setup_app_with_clip();
+    app.update(Message::SeekTo(TimelinePosition::from_secs_f64(2.0)));
+    app.update(Message::InsertTimeDurationChanged("abc".into()));
+    app.update(Message::InsertTimeAtPlayhead);
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 1);
+
+    app.update(Message::InsertTimeDurationChanged("1.5".into()));
+    app.update(Message::InsertTimeAtPlayhead);
+    for track in &app.project.timeline.tracks[..2] {
+        assert_eq!(track.clips.len(), 2);
+        assert!(track.clip_at(TimelinePosition::from_secs_f64(3.0)).is_none());
+    }
+    assert_eq!(app.project.timeline.duration(), std::time::Duration::from_secs_f64(6.5));
+    assert!(app.selected_clip.is_none());
+
+    app.update(Message::Undo);
+    assert_eq!(app.project.timeline.tracks[0].clips.len(), 1);
+}
+
+#[test]
+fn test_clip_markers_added_renamed_and_undone() {
+    let (mut app, _, clip_id) = setup_app_with_clip();
+    app.update(Message::SeekTo(TimelinePosition::from_secs_f64(2.0)));
+    app.update(Message::AddClipMarker);
+    let marker = app.project.timeline.tracks[0].get_clip(clip_id).unwrap().markers[0].clone();
+    assert_eq!(marker.name, "Marker 1");
+    assert_eq!(marker.source_time, TimelinePosition::from_secs_f64(2.0));
+    // The linked audio clip carries the same marker
+    assert_eq!(app.project.timeline.tracks[1].clips[0].markers.len(), 1);
+
+    app.update(Message::ClipMarkerNameEdited { marker_id: marker.id, name: "Best take".into() });
+    app.update(Message::RenameClipMarker { track_index: 0, clip_id, marker_id: marker.id });
+    assert_eq!(app.project.timeline.tracks[0].get_clip(clip_id).unwrap().markers[0].name, "Best take");
+    assert!(app.clip_marker_edit.is_none());
+
+    app.update(Message::Undo);
+    assert_eq!(app.project.timeline.tracks[0].get_clip(clip_id).unwrap().markers[0].name, "Marker 1");
+    app.update(Message::RemoveClipMarker { track_index: 0, clip_id, marker_id: marker.id });
+    assert!(app.project.timeline.tracks[0].get_clip(clip_id).unwrap().markers.is_empty());
+
+    // Outside the clip
+    app.update(Message::SeekTo(TimelinePosition::from_secs_f64(8.0)));
+    app.update(Message::AddClipMarker);
+    assert!(app.status_message.contains("Clip marker failed"));
+}
+
 #[test]
 fn test_add_effect_to_selected_clip() {
     let (mut app, _, clip_id) = setup_app_with_clip();
@@ -2343,46 +5266,47 @@ fn test_all_audio_clips_at_position_multi_track() {
 
 #[test]
 fn test_composite_rgba_layers() {
-    use zeditor_ui::app::blit_rgba_scaled;
+    use zeditor_core::pipeline::FrameBuffer;
+    use zeditor_core::scaler::{blit_scaled, Placement, ScaleQuality};
 
     // Create a small 4x4 black canvas
     let canvas_w = 4u32;
-    let canvas_h = 4u32;
-    let mut canvas = vec![0u8; (canvas_w * canvas_h * 4) as usize];
+    let mut canvas = FrameBuffer::new(canvas_w, 4);
 
     // Create a 2x2 red source
-    let src_w = 2u32;
-    let src_h = 2u32;
-    let red_src = vec![255u8, 0, 0, 255, 255, 0, 0, 255,
-                       255, 0, 0, 255, 255, 0, 0, 255];
+    let red_src = FrameBuffer::from_rgba_vec(2, 2, vec![255u8, 0, 0, 255, 255, 0, 0, 255,
+                                                        255, 0, 0, 255, 255, 0, 0, 255]);
 
     // Blit red at offset (1, 1) with size 2x2
-    blit_rgba_scaled(&red_src, src_w, src_h, &mut canvas, canvas_w, canvas_h, 1, 1, 2, 2);
+    let placement = Placement { x: 1, y: 1, width: 2, height: 2 };
+    blit_scaled(&red_src, &mut canvas, placement, ScaleQuality::Bilinear);
+    let canvas_data = &canvas.data;
 
     // Check that pixel (0,0) is still black
-    assert_eq!(canvas[0], 0);
-    assert_eq!(canvas[1], 0);
-    assert_eq!(canvas[2], 0);
-    assert_eq!(canvas[3], 0);
+    assert_eq!(canvas_data[0], 0);
+    assert_eq!(canvas_data[1], 0);
+    assert_eq!(canvas_data[2], 0);
+    assert_eq!(canvas_data[3], 0);
 
     // Check that pixel (1,1) is red
     let idx = ((1 * canvas_w + 1) * 4) as usize;
-    assert_eq!(canvas[idx], 255);
-    assert_eq!(canvas[idx + 1], 0);
-    assert_eq!(canvas[idx + 2], 0);
-    assert_eq!(canvas[idx + 3], 255);
+    assert_eq!(canvas_data[idx], 255);
+    assert_eq!(canvas_data[idx + 1], 0);
+    assert_eq!(canvas_data[idx + 2], 0);
+    assert_eq!(canvas_data[idx + 3], 255);
 
     // Now blit a green 2x2 on top at offset (2, 2) — partially overlapping
-    let green_src = vec![0u8, 255, 0, 255, 0, 255, 0, 255,
-                         0, 255, 0, 255, 0, 255, 0, 255];
-    blit_rgba_scaled(&green_src, src_w, src_h, &mut canvas, canvas_w, canvas_h, 2, 2, 2, 2);
+    let green_src = FrameBuffer::from_rgba_vec(2, 2, vec![0u8, 255, 0, 255, 0, 255, 0, 255,
+                                                          0, 255, 0, 255, 0, 255, 0, 255]);
+    let placement = Placement { x: 2, y: 2, width: 2, height: 2 };
+    blit_scaled(&green_src, &mut canvas, placement, ScaleQuality::Bilinear);
 
     // Check that pixel (2,2) is now green (overwrites red)
     let idx = ((2 * canvas_w + 2) * 4) as usize;
-    assert_eq!(canvas[idx], 0);
-    assert_eq!(canvas[idx + 1], 255);
-    assert_eq!(canvas[idx + 2], 0);
-    assert_eq!(canvas[idx + 3], 255);
+    assert_eq!(canvas.data[idx], 0);
+    assert_eq!(canvas.data[idx + 1], 255);
+    assert_eq!(canvas.data[idx + 2], 0);
+    assert_eq!(canvas.data[idx + 3], 255);
 }
 
 // =============================================================================
@@ -2671,3 +5595,14 @@ fn test_update_effect_param_clears_text_state() {
     });
     assert!(!app.effect_param_texts.contains_key(&(effect_id, "x_offset".into())));
 }
+
+#[test]
+fn test_export_stems_toggle_from_file_menu() {
+    let mut app = App::new();
+    assert!(!app.preferences.export_stems);
+    app.update(Message::MenuAction(MenuAction::ToggleExportStems));
+    assert!(app.preferences.export_stems);
+    assert_eq!(app.status_message, "Renders will also write a WAV per audio track");
+    app.update(Message::ToggleExportStems);
+    assert!(!app.preferences.export_stems);
+}
@@ -4,10 +4,13 @@ use std::path::PathBuf;
 use std::time::Duration;
 
 use uuid::Uuid;
+use zeditor_core::effects::EffectType;
 use zeditor_core::media::MediaAsset;
 use zeditor_core::timeline::TimelinePosition;
 use zeditor_ui::app::App;
+use zeditor_ui::audio_meters::{self, AudioLevels};
 use zeditor_ui::message::Message;
+use zeditor_ui::preview_cache::PreviewCacheKey;
 use zeditor_ui::test_helpers::{TestAudio, TestAudioSender, TestFrame, TestFrameSender};
 
 fn make_test_asset(name: &str, duration_secs: f64) -> MediaAsset {
@@ -688,6 +691,40 @@ fn test_audio_stopped_in_gap() {
     );
 }
 
+#[test]
+fn test_audio_meters_follow_playback() {
+    let (mut app, _video_sender, _audio_sender) = App::new_with_test_channels();
+    let levels_tx = app.attach_audio_levels_channel();
+    let asset = make_test_asset_with_audio("clip_a", 5.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::zero(),
+    });
+
+    app.update(Message::Play);
+    let block = |epoch: u64, peak: f32| AudioLevels {
+        epoch,
+        pts_secs: 0.0,
+        duration_secs: 1.0,
+        peaks: vec![(1, peak)],
+    };
+    levels_tx.send(block(app.audio_epoch() - 1, 0.9)).unwrap();
+    levels_tx.send(block(app.audio_epoch(), 0.5)).unwrap();
+    app.update(Message::PlaybackTick);
+
+    let level = app.audio_meter_level(1);
+    assert!((level - audio_meters::meter_fraction(0.5)).abs() < 1e-6, "stale blocks are ignored: {level}");
+    assert_eq!(app.audio_meter_level(0), 0.0);
+
+    app.update(Message::Pause);
+    app.update(Message::PlaybackTick);
+    assert_eq!(app.audio_meter_level(1), 0.0);
+    assert!(app.audio_meters.is_empty());
+}
+
 #[test]
 fn test_audio_transition_across_clip_boundary() {
     let (mut app, _video_sender, _audio_sender) = App::new_with_test_channels();
@@ -744,8 +781,8 @@ fn test_audio_frames_polled_during_playback() {
     let start = app.playback_start_wall.unwrap();
     app.playback_start_wall = Some(start - Duration::from_millis(500));
 
-    // Inject an audio frame — it should be consumed by poll_decoded_audio
-    // (which feeds it to audio_player, but in tests there's no audio_player)
+    // Inject an audio frame — it goes straight into the audio ring the output
+    // device reads (in tests there's no audio_player to drain it)
     audio_sender.send_audio(TestAudio {
         samples: vec![0.0; 1024],
         sample_rate: 44100,
@@ -753,12 +790,8 @@ fn test_audio_frames_polled_during_playback() {
         pts_secs: 0.5,
     });
 
-    // Tick should drain the audio channel without panic
+    // Ticks don't touch the buffered audio; the important thing is no panic.
     app.update(Message::PlaybackTick);
-
-    // After the tick, the audio channel should be empty (frame was consumed)
-    // Since there's no audio_player in test mode, the frame is drained but
-    // not played. The important thing is no panic and the channel is drained.
     assert!(app.is_playing, "playback should still be running");
 }
 
@@ -860,3 +893,346 @@ fn test_timeline_click_stops_audio_decode() {
         "cursor should move to clicked position"
     );
 }
+
+#[test]
+fn test_scrub_frame_is_cached_and_reused() {
+    let (mut app, sender, _clip_id) = setup_app_with_clip(0.0, 10.0);
+
+    app.update(Message::SeekTo(TimelinePosition::from_secs_f64(3.0)));
+    sender.send_frame(solid_frame(3.0, 255, 0, 0));
+    app.update(Message::PlaybackTick);
+    let red = app.current_frame.clone().expect("scrub frame displayed");
+    assert_eq!(app.preview_cache.len(), 1);
+
+    app.update(Message::SeekTo(TimelinePosition::from_secs_f64(5.0)));
+    sender.send_frame(solid_frame(5.0, 0, 255, 0));
+    app.update(Message::PlaybackTick);
+    assert_ne!(app.current_frame.as_ref(), Some(&red));
+
+    // Seeking back shows the cached frame immediately — no decode, no tick.
+    app.update(Message::SeekTo(TimelinePosition::from_secs_f64(3.0)));
+    assert_eq!(app.current_frame.as_ref(), Some(&red));
+}
+
+#[test]
+fn test_preview_cache_misses_after_effect_change() {
+    let (mut app, sender, clip_id) = setup_app_with_clip(0.0, 10.0);
+
+    app.update(Message::SeekTo(TimelinePosition::from_secs_f64(3.0)));
+    sender.send_frame(solid_frame(3.0, 255, 0, 0));
+    app.update(Message::PlaybackTick);
+    let red = app.current_frame.clone().unwrap();
+
+    app.update(Message::SeekTo(TimelinePosition::from_secs_f64(5.0)));
+    sender.send_frame(solid_frame(5.0, 0, 255, 0));
+    app.update(Message::PlaybackTick);
+
+    // Changing the clip's effects invalidates its cached frames.
+    app.selected_clip = Some((0, clip_id));
+    app.update(Message::AddEffectToSelectedClip(
+        zeditor_core::effects::EffectType::Grayscale,
+    ));
+    app.update(Message::SeekTo(TimelinePosition::from_secs_f64(3.0)));
+    assert_ne!(
+        app.current_frame.as_ref(),
+        Some(&red),
+        "stale cached frame must not be shown after an effect change"
+    );
+}
+
+#[test]
+fn test_preview_cache_not_filled_during_playback() {
+    let (mut app, sender, _clip_id) = setup_app_with_clip(0.0, 10.0);
+
+    app.update(Message::Play);
+    let start = app.playback_start_wall.unwrap();
+    app.playback_start_wall = Some(start - Duration::from_millis(500));
+    sender.send_frame(solid_frame(0.5, 255, 0, 0));
+    app.update(Message::PlaybackTick);
+
+    assert!(app.current_frame.is_some());
+    assert!(app.preview_cache.is_empty());
+}
+
+#[test]
+fn test_playback_stats_count_skipped_frames() {
+    let (mut app, sender, _clip_id) = setup_app_with_clip(0.0, 10.0);
+
+    app.update(Message::Play);
+    let start = app.playback_start_wall.unwrap();
+    app.playback_start_wall = Some(start - Duration::from_millis(500));
+    // Both frames are due in the same tick; only the later one is ever seen.
+    sender.send_frame(solid_frame(0.4, 255, 0, 0));
+    sender.send_frame(solid_frame(0.5, 0, 255, 0));
+    app.update(Message::PlaybackTick);
+
+    assert_eq!(app.playback_stats.dropped_frames, 1);
+}
+
+#[test]
+fn test_playback_stats_record_decode_latency() {
+    let (mut app, sender, _clip_id) = setup_app_with_clip(0.0, 10.0);
+
+    app.update(Message::SeekTo(TimelinePosition::from_secs_f64(2.0)));
+    assert!(app.playback_stats.decode_latency.is_none());
+    sender.send_frame(solid_frame(2.0, 255, 0, 0));
+    app.update(Message::PlaybackTick);
+
+    assert!(app.playback_stats.decode_latency.is_some());
+    assert_eq!(app.playback_stats.dropped_frames, 0);
+}
+
+#[test]
+fn test_source_monitor_frames_do_not_touch_timeline_preview() {
+    let (mut app, _sender, _clip_id) = setup_app_with_clip(0.0, 10.0);
+    let source_sender = app.attach_source_test_channel();
+    let asset_id = app.project.source_library.assets()[0].id;
+
+    app.update(Message::OpenInSourceMonitor(asset_id));
+    source_sender.send_frame(solid_frame(0.0, 0, 0, 255));
+    app.update(Message::PlaybackTick);
+
+    assert!(app.source_monitor.current_frame.is_some());
+    assert!(app.current_frame.is_none());
+}
+
+/// Clip A (0-5s) followed by clip B (5-8s) on V1.
+fn setup_app_with_cut() -> (App, TestFrameSender, Uuid, Uuid) {
+    let (mut app, sender, clip_a) = setup_app_with_clip(0.0, 5.0);
+    let asset = make_test_asset("next_clip", 3.0);
+    let asset_id = asset.id;
+    app.update(Message::MediaImported(Ok(asset)));
+    app.update(Message::AddClipToTimeline {
+        asset_id,
+        track_index: 0,
+        position: TimelinePosition::from_secs_f64(5.0),
+    });
+    let clip_b = app.project.timeline.tracks[0]
+        .clips
+        .iter()
+        .find(|c| c.id != clip_a)
+        .unwrap()
+        .id;
+    (app, sender, clip_a, clip_b)
+}
+
+#[test]
+fn test_upcoming_video_cut_within_lead() {
+    let (mut app, _sender, _clip_a, _clip_b) = setup_app_with_cut();
+
+    app.playback_position = TimelinePosition::from_secs_f64(3.0);
+    assert!(app.upcoming_video_cut(1.0).is_none());
+
+    app.playback_position = TimelinePosition::from_secs_f64(4.5);
+    let cut = app.upcoming_video_cut(1.0).unwrap();
+    assert!((cut.as_secs_f64() - 5.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_playback_preloads_next_clip_before_cut() {
+    let (mut app, _sender, clip_a, clip_b) = setup_app_with_cut();
+
+    app.update(Message::Play);
+    app.update(Message::PlaybackTick);
+    assert!(app.preloaded_clip_ids().is_empty());
+
+    let start = app.playback_start_wall.unwrap();
+    app.playback_start_wall = Some(start - Duration::from_millis(4500));
+    app.update(Message::PlaybackTick);
+
+    assert_eq!(app.preloaded_clip_ids(), &[clip_b]);
+    assert!(!app.preloaded_clip_ids().contains(&clip_a));
+}
+
+#[test]
+fn test_reverse_playback_does_not_preload() {
+    let (mut app, _sender, _clip_a, _clip_b) = setup_app_with_cut();
+
+    app.update(Message::SeekTo(TimelinePosition::from_secs_f64(4.5)));
+    app.update(Message::Play);
+    app.playback_rate = -1.0;
+    app.update(Message::PlaybackTick);
+
+    assert!(app.preloaded_clip_ids().is_empty());
+}
+
+fn add_effect(app: &mut App, clip_id: Uuid, effect_type: EffectType) {
+    app.update(Message::AddEffectToClip {
+        track_index: 0,
+        clip_id,
+        effect_type,
+    });
+}
+
+fn prerendered(app: &mut App, secs: f64) -> PreviewCacheKey {
+    let key = PreviewCacheKey::at(&app.project, TimelinePosition::from_secs_f64(secs));
+    let handle = iced::widget::image::Handle::from_rgba(1, 1, vec![0, 255, 0, 255]);
+    app.prerender_cache.insert(key, handle);
+    key
+}
+
+#[test]
+fn test_prerender_plan_covers_effect_frames_ahead_of_playhead() {
+    let (mut app, _sender, clip_id) = setup_app_with_clip(0.0, 10.0);
+    assert!(app.prerender_plan().is_empty(), "clips without effects use the fast path");
+
+    add_effect(&mut app, clip_id, EffectType::Grayscale);
+    let plan = app.prerender_plan();
+
+    // 5 s ahead at 30 fps, starting with the playhead frame.
+    assert_eq!(plan.len(), 151);
+    assert!(plan[0].as_secs_f64().abs() < 1e-6);
+    assert!(plan.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[test]
+fn test_effects_bypass_skips_prerender_until_restored() {
+    let (mut app, _sender, clip_id) = setup_app_with_clip(0.0, 10.0);
+    add_effect(&mut app, clip_id, EffectType::Grayscale);
+
+    app.update(Message::ToggleEffectsBypass);
+    assert!(app.effects_bypassed);
+    assert!(app.prerender_plan().is_empty(), "bypassed frames decode without effects");
+
+    app.update(Message::ToggleEffectsBypass);
+    assert_eq!(app.prerender_plan().len(), 151);
+    // The clip's effects themselves are never touched
+    assert_eq!(app.project.timeline.tracks[0].clips[0].effects.len(), 1);
+}
+
+#[test]
+fn test_marked_section_extends_prerender_plan() {
+    let (mut app, _sender, clip_id) = setup_app_with_clip(0.0, 10.0);
+    add_effect(&mut app, clip_id, EffectType::Grayscale);
+    app.update(Message::SelectTimelineClip(Some((0, clip_id))));
+
+    app.update(Message::TogglePrerenderSelectedClip);
+    assert_eq!(app.prerender_sections.len(), 1);
+    assert_eq!(app.prerender_plan().len(), 300);
+
+    app.update(Message::TogglePrerenderSelectedClip);
+    assert!(app.prerender_sections.is_empty());
+}
+
+#[test]
+fn test_background_render_plans_whole_timeline_while_idle() {
+    let (mut app, _sender, clip_id) = setup_app_with_clip(0.0, 10.0);
+    add_effect(&mut app, clip_id, EffectType::Grayscale);
+    let dir = tempfile::tempdir().unwrap();
+    app.set_media_cache_dir(dir.path().to_path_buf());
+    assert!(app.background_render_plan().is_empty(), "off by default");
+
+    app.update(Message::ToggleBackgroundRender);
+    assert!(app.preferences.background_render);
+    let plan = app.background_render_plan();
+    assert_eq!(plan.len(), 300);
+    assert_eq!(app.background_render_progress, (0, 300));
+
+    // Rendered frames drop out of the plan until an edit makes them stale.
+    app.background_rendered.extend(plan[..100].iter().map(|(key, _)| *key));
+    assert_eq!(app.background_render_plan().len(), 200);
+    assert_eq!(app.background_render_progress, (100, 300));
+    add_effect(&mut app, clip_id, EffectType::Brightness);
+    assert_eq!(app.background_render_plan().len(), 300);
+    assert!(app.background_rendered.is_empty());
+
+    app.update(Message::Play);
+    assert!(app.background_render_plan().is_empty(), "only renders while idle");
+}
+
+#[test]
+fn test_playback_serves_prerendered_frames() {
+    let (mut app, sender, clip_id) = setup_app_with_clip(0.0, 10.0);
+    add_effect(&mut app, clip_id, EffectType::Grayscale);
+    prerendered(&mut app, 0.5);
+
+    app.update(Message::Play);
+    let start = app.playback_start_wall.unwrap();
+    app.playback_start_wall = Some(start - Duration::from_millis(500));
+    sender.send_frame(solid_frame(0.5, 255, 0, 0));
+    app.update(Message::PlaybackTick);
+
+    assert!(app.serving_prerender());
+    assert!(app.current_frame.is_some());
+
+    // Past the pre-rendered frame, decoding takes over again.
+    let start = app.playback_start_wall.unwrap();
+    app.playback_start_wall = Some(start - Duration::from_millis(500));
+    app.update(Message::PlaybackTick);
+    assert!(!app.serving_prerender());
+}
+
+#[test]
+fn test_effect_change_invalidates_prerendered_frame() {
+    let (mut app, _sender, clip_id) = setup_app_with_clip(0.0, 10.0);
+    add_effect(&mut app, clip_id, EffectType::Grayscale);
+    let stale = prerendered(&mut app, 0.0);
+
+    add_effect(&mut app, clip_id, EffectType::Brightness);
+    app.update(Message::PlaybackTick);
+
+    assert!(!app.prerender_cache.contains(&stale));
+}
+
+#[test]
+fn test_every_scrub_starts_a_new_decode_generation() {
+    let (mut app, _sender, _clip_id) = setup_app_with_clip(0.0, 10.0);
+    let before = app.decode_generation();
+
+    for i in 1..=5 {
+        app.update(Message::SeekTo(TimelinePosition::from_secs_f64(i as f64 * 0.1)));
+    }
+    assert_eq!(app.decode_generation(), before + 5);
+
+    // Stopping supersedes in-flight work too.
+    app.update(Message::Play);
+    let playing = app.decode_generation();
+    app.update(Message::Pause);
+    assert!(app.decode_generation() > playing);
+}
+
+#[test]
+fn test_audio_buffered_without_ui_tick() {
+    let (mut app, _video_sender, audio_sender, _video_clip_id, _audio_clip_id) =
+        setup_audio_app_with_clip(0.0, 10.0);
+    app.update(Message::Play);
+
+    // 0.5s of stereo audio, pushed as the decode thread would.
+    audio_sender.send_audio(TestAudio {
+        samples: vec![0.0; 44100],
+        sample_rate: 44100,
+        channels: 2,
+        pts_secs: 0.0,
+    });
+    assert_eq!(app.audio_buffered(), Duration::from_millis(500));
+
+    app.update(Message::PlaybackTick);
+    assert_eq!(
+        app.audio_buffered(),
+        Duration::from_millis(500),
+        "the UI tick must not drain audio; only the output device reads the ring"
+    );
+}
+
+#[test]
+fn test_seek_and_pause_flush_buffered_audio() {
+    let (mut app, _video_sender, audio_sender, _video_clip_id, _audio_clip_id) =
+        setup_audio_app_with_clip(0.0, 10.0);
+    let half_second = || TestAudio {
+        samples: vec![0.0; 44100],
+        sample_rate: 44100,
+        channels: 2,
+        pts_secs: 0.0,
+    };
+    app.update(Message::Play);
+    audio_sender.send_audio(half_second());
+
+    app.update(Message::SeekTo(TimelinePosition::from_secs_f64(4.0)));
+    assert_eq!(app.audio_buffered(), Duration::ZERO, "seek drops audio for the old position");
+
+    audio_sender.send_audio(half_second());
+    assert_eq!(app.audio_buffered(), Duration::from_millis(500));
+
+    app.update(Message::Pause);
+    assert_eq!(app.audio_buffered(), Duration::ZERO, "pause silences buffered audio");
+}
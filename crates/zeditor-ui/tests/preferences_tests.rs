@@ -0,0 +1,249 @@
+use zeditor_ui::app::App;
+use zeditor_ui::i18n::Language;
+use zeditor_ui::library_filter::{LibrarySort, LibraryViewMode};
+use zeditor_ui::memory::MemoryCaps;
+use zeditor_core::project::ProjectSettings;
+use zeditor_ui::message::{MenuAction, Message, PanelSplitter, PreferenceField};
+use zeditor_ui::preferences::{
+    PanelLayout, PreviewQuality, UiPreferences, DEFAULT_SNAP_THRESHOLD_MS, MAX_UI_SCALE, MIN_LEFT_PANEL_WIDTH,
+};
+
+#[test]
+fn test_preferences_roundtrip() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("zeditor").join("preferences.json");
+    let prefs = UiPreferences {
+        library_sort: LibrarySort::Duration,
+        library_sort_descending: true,
+        library_view: LibraryViewMode::List,
+        layout: PanelLayout {
+            left_panel_width: 420.0,
+            inspector_width: 320.0,
+            preview_height: 260.0,
+        },
+        ui_scale: 1.5,
+        language: Language::German,
+        memory_caps: MemoryCaps {
+            preview_cache_mib: Some(128),
+            decoders_mib: None,
+            ..MemoryCaps::default()
+        },
+        new_project: ProjectSettings {
+            canvas_width: 3840,
+            canvas_height: 2160,
+            fps: 24.0,
+        },
+        autosave_minutes: 5,
+        cache_dir: Some(dir.path().join("cache")),
+        preview_quality: PreviewQuality::Draft,
+        snap_threshold_ms: 500,
+        display_lut: Some(dir.path().join("rec709.cube")),
+        still_duration_secs: 8.0,
+        render_segments: 4,
+        export_stems: true,
+        ..UiPreferences::default()
+    };
+    prefs.save(&path).unwrap();
+    assert_eq!(UiPreferences::load(&path), prefs);
+}
+
+#[test]
+fn test_missing_or_corrupt_preferences_use_defaults() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("preferences.json");
+    assert_eq!(UiPreferences::load(&path), UiPreferences::default());
+
+    std::fs::write(&path, "{ not json").unwrap();
+    assert_eq!(UiPreferences::load(&path), UiPreferences::default());
+
+    // Fields missing from older files fall back to defaults.
+    std::fs::write(&path, r#"{"library_view":"List"}"#).unwrap();
+    let prefs = UiPreferences::load(&path);
+    assert_eq!(prefs.library_view, LibraryViewMode::List);
+    assert_eq!(prefs.library_sort, LibrarySort::Imported);
+}
+
+#[test]
+fn test_library_sort_and_view_are_persisted() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("preferences.json");
+    let mut app = App::new();
+    app.preferences_path = Some(path.clone());
+
+    app.update(Message::SetLibrarySort(LibrarySort::Name));
+    app.update(Message::SetLibrarySort(LibrarySort::Name));
+    app.update(Message::SetLibraryViewMode(LibraryViewMode::List));
+
+    let saved = UiPreferences::load(&path);
+    assert_eq!(saved.library_sort, LibrarySort::Name);
+    assert!(saved.library_sort_descending, "choosing the active sort again reverses it");
+    assert_eq!(saved.library_view, LibraryViewMode::List);
+}
+
+#[test]
+fn test_out_of_range_layout_is_clamped_on_load() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("preferences.json");
+    std::fs::write(&path, r#"{"layout":{"left_panel_width":5.0}}"#).unwrap();
+    let prefs = UiPreferences::load(&path);
+    assert_eq!(prefs.layout.left_panel_width, MIN_LEFT_PANEL_WIDTH);
+    assert_eq!(prefs.layout.inspector_width, PanelLayout::default().inspector_width);
+}
+
+#[test]
+fn test_splitter_drag_resizes_and_persists_layout() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("preferences.json");
+    let mut app = App::new();
+    app.preferences_path = Some(path.clone());
+
+    app.update(Message::StartSplitterDrag(PanelSplitter::LeftPanel));
+    app.update(Message::SplitterMoved(iced::Point::new(300.0, 100.0)));
+    app.update(Message::SplitterMoved(iced::Point::new(380.0, 100.0)));
+    assert_eq!(app.preferences.layout.left_panel_width, 380.0);
+    app.update(Message::SplitterReleased);
+    assert_eq!(UiPreferences::load(&path).layout.left_panel_width, 380.0);
+
+    // The inspector is right of its splitter: dragging left widens it.
+    app.update(Message::StartSplitterDrag(PanelSplitter::Inspector));
+    app.update(Message::SplitterMoved(iced::Point::new(900.0, 500.0)));
+    app.update(Message::SplitterMoved(iced::Point::new(850.0, 500.0)));
+    app.update(Message::SplitterReleased);
+    assert_eq!(app.preferences.layout.inspector_width, 300.0);
+}
+
+#[test]
+fn test_splitter_respects_minimum() {
+    let mut app = App::new();
+    app.update(Message::StartSplitterDrag(PanelSplitter::Preview));
+    app.update(Message::SplitterMoved(iced::Point::new(0.0, 400.0)));
+    app.update(Message::SplitterMoved(iced::Point::new(0.0, -2000.0)));
+    app.update(Message::SplitterReleased);
+    assert_eq!(app.preferences.layout.preview_height, zeditor_ui::preferences::MIN_PREVIEW_HEIGHT);
+}
+
+#[test]
+fn test_ui_scale_is_persisted_and_clamped() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("preferences.json");
+    let mut app = App::new();
+    assert_eq!(app.scale_factor(), 1.0);
+    app.preferences_path = Some(path.clone());
+
+    app.update(Message::MenuAction(MenuAction::SetUiScale(150)));
+    assert_eq!(app.scale_factor(), 1.5);
+    assert_eq!(UiPreferences::load(&path).ui_scale, 1.5);
+
+    app.update(Message::SetUiScale(40.0));
+    assert_eq!(app.preferences.ui_scale, MAX_UI_SCALE);
+
+    std::fs::write(&path, r#"{"ui_scale":0.1}"#).unwrap();
+    assert_eq!(UiPreferences::load(&path).ui_scale, zeditor_ui::preferences::MIN_UI_SCALE);
+}
+
+#[test]
+fn test_ui_scale_shrinks_timeline_viewport() {
+    let mut app = App::new();
+    app.update(Message::WindowResized(iced::Size::new(1920.0, 1080.0)));
+    let unscaled = app.timeline_viewport_width();
+    app.update(Message::SetUiScale(2.0));
+    assert!(app.timeline_viewport_width() < unscaled);
+}
+
+#[test]
+fn test_language_switch_translates_and_persists() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("preferences.json");
+    let mut app = App::new();
+    app.preferences_path = Some(path.clone());
+    app.update(Message::ToggleLoopPlayback);
+    assert_eq!(app.status_message, "Loop playback on");
+
+    app.update(Message::MenuAction(MenuAction::SetLanguage(Language::German)));
+    assert_eq!(app.i18n.language(), Language::German);
+    assert_eq!(UiPreferences::load(&path).language, Language::German);
+    assert_eq!(app.status_message, "Sprache: Deutsch");
+
+    app.update(Message::ToggleLoopPlayback);
+    assert_eq!(app.status_message, "Schleifenwiedergabe aus");
+}
+
+#[test]
+fn test_preferences_dialog_applies_and_persists() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("preferences.json");
+    let mut app = App::new();
+    app.preferences_path = Some(path.clone());
+
+    app.update(Message::MenuAction(MenuAction::Preferences));
+    let form = app.preferences_form.as_ref().expect("dialog opens");
+    assert_eq!(form.canvas_width, "1920");
+    assert_eq!(form.snap_threshold_ms, DEFAULT_SNAP_THRESHOLD_MS.to_string());
+
+    app.update(Message::PreferenceFieldChanged(PreferenceField::CanvasWidth, "1280".into()));
+    app.update(Message::PreferenceFieldChanged(PreferenceField::CanvasHeight, "720".into()));
+    app.update(Message::PreferenceFieldChanged(PreferenceField::Fps, "25".into()));
+    app.update(Message::PreferenceFieldChanged(PreferenceField::AutosaveMinutes, "10".into()));
+    app.update(Message::PreferenceFieldChanged(PreferenceField::SnapThresholdMs, "50".into()));
+    app.update(Message::SetPreviewQuality(PreviewQuality::Draft));
+    app.update(Message::ApplyPreferences);
+    assert!(app.preferences_form.is_none());
+
+    let saved = UiPreferences::load(&path);
+    assert_eq!(saved.new_project.canvas_width, 1280);
+    assert_eq!(saved.new_project.canvas_height, 720);
+    assert_eq!(saved.new_project.fps, 25.0);
+    assert_eq!(saved.autosave_minutes, 10);
+    assert_eq!(saved.snap_threshold_ms, 50);
+    assert_eq!(saved.preview_quality, PreviewQuality::Draft);
+    assert_eq!(saved.cache_dir, None);
+
+    // New projects start from the preferred canvas and frame rate
+    app.update(Message::NewProject);
+    assert_eq!(app.project.settings, saved.new_project);
+}
+
+#[test]
+fn test_invalid_preference_keeps_dialog_open() {
+    let mut app = App::new();
+    app.update(Message::OpenPreferences);
+    app.update(Message::PreferenceFieldChanged(PreferenceField::Fps, "fast".into()));
+    app.update(Message::PreferenceFieldChanged(PreferenceField::SnapThresholdMs, "25".into()));
+    app.update(Message::ApplyPreferences);
+
+    assert!(app.preferences_form.is_some());
+    assert!(app.status_message.contains("frame rate"), "status: {}", app.status_message);
+    assert_eq!(app.preferences, UiPreferences::default());
+
+    app.update(Message::PreferencesDismissed);
+    assert!(app.preferences_form.is_none());
+}
+
+#[test]
+fn test_display_lut_preference_loads_and_warns_on_bad_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let lut_path = dir.path().join("invert.cube");
+    std::fs::write(&lut_path, "LUT_3D_SIZE 2\n1 1 1\n0 1 1\n1 0 1\n0 0 1\n1 1 0\n0 1 0\n1 0 0\n0 0 0\n").unwrap();
+    let mut app = App::new();
+
+    app.update(Message::OpenPreferences);
+    let lut_field = lut_path.display().to_string();
+    app.update(Message::PreferenceFieldChanged(PreferenceField::DisplayLut, lut_field));
+    app.update(Message::ApplyPreferences);
+    assert_eq!(app.preferences.display_lut.as_deref(), Some(lut_path.as_path()));
+    assert_eq!(app.display_lut.as_ref().map(|lut| lut.size()), Some(2));
+
+    // An unreadable LUT leaves the preview untransformed
+    app.update(Message::OpenPreferences);
+    let missing = dir.path().join("missing.cube").display().to_string();
+    app.update(Message::PreferenceFieldChanged(PreferenceField::DisplayLut, missing));
+    app.update(Message::ApplyPreferences);
+    assert!(app.display_lut.is_none());
+    assert!(app.status_message.contains("display LUT"), "status: {}", app.status_message);
+
+    app.update(Message::OpenPreferences);
+    app.update(Message::PreferenceFieldChanged(PreferenceField::DisplayLut, String::new()));
+    app.update(Message::ApplyPreferences);
+    assert_eq!(app.preferences.display_lut, None);
+    assert!(app.display_lut.is_none());
+}